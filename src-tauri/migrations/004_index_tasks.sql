@@ -0,0 +1,26 @@
+-- NeuralFS Migration: Durable indexing task queue
+-- Version: 004
+-- Description: Persists indexer tasks, retry state and dead letters across restarts
+
+-- Indexing tasks (pending, failed awaiting retry, in progress, dead letter)
+CREATE TABLE IF NOT EXISTS index_tasks (
+    id TEXT PRIMARY KEY NOT NULL,
+    file_id TEXT NOT NULL,
+    path TEXT NOT NULL,
+    priority INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    retry_count INTEGER NOT NULL DEFAULT 0,
+    max_retries INTEGER NOT NULL,
+    next_retry_at TEXT,
+    last_error TEXT, -- JSON-serialized IndexError
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- Indexes for index_tasks table
+CREATE INDEX IF NOT EXISTS idx_index_tasks_status ON index_tasks(status);
+CREATE INDEX IF NOT EXISTS idx_index_tasks_file_id ON index_tasks(file_id);
+
+-- Insert migration record
+INSERT OR IGNORE INTO schema_migrations (version, name, applied_at, checksum)
+VALUES (4, '004_index_tasks', datetime('now'), 'index_tasks');
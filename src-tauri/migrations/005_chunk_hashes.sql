@@ -0,0 +1,13 @@
+-- NeuralFS Migration: Chunk content hashes
+-- Version: 005
+-- Description: Adds a BLAKE3 hash per content chunk so unchanged chunks keep their vectors on reindex
+
+-- Add content_hash column to content_chunks table
+ALTER TABLE content_chunks ADD COLUMN content_hash TEXT;
+
+-- Create index for matching chunks by hash within a file
+CREATE INDEX IF NOT EXISTS idx_chunks_file_hash ON content_chunks(file_id, content_hash);
+
+-- Insert migration record
+INSERT OR IGNORE INTO schema_migrations (version, name, applied_at, checksum)
+VALUES (5, '005_chunk_hashes', datetime('now'), 'chunk_hashes');
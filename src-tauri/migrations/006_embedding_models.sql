@@ -0,0 +1,27 @@
+-- NeuralFS Migration: Embedding model versioning
+-- Version: 006
+-- Description: Records which embedding model produced each chunk vector and tracks the vector collection per model
+
+-- Add embedding_model column (model_id@version) to content_chunks table
+ALTER TABLE content_chunks ADD COLUMN embedding_model TEXT;
+
+-- Create index for finding chunks still embedded by another model
+CREATE INDEX IF NOT EXISTS idx_chunks_embedding_model ON content_chunks(embedding_model);
+
+-- Vector collections, one per embedding model
+CREATE TABLE IF NOT EXISTS vector_collections (
+    name TEXT PRIMARY KEY,
+    model_id TEXT NOT NULL,
+    model_version TEXT NOT NULL,
+    dimension INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    activated_at TEXT
+);
+
+-- Create index for looking up the active and building collections
+CREATE INDEX IF NOT EXISTS idx_vector_collections_status ON vector_collections(status);
+
+-- Insert migration record
+INSERT OR IGNORE INTO schema_migrations (version, name, applied_at, checksum)
+VALUES (6, '006_embedding_models', datetime('now'), 'embedding_models');
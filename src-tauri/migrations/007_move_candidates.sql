@@ -0,0 +1,22 @@
+-- NeuralFS Migration: Move candidates
+-- Version: 007
+-- Description: Holds possible moves found by content hash that need the user's confirmation
+
+-- A missing file and a path where identical content appeared
+CREATE TABLE IF NOT EXISTS move_candidates (
+    id TEXT PRIMARY KEY NOT NULL,
+    file_id TEXT NOT NULL,
+    old_path TEXT NOT NULL,
+    new_path TEXT NOT NULL,
+    confidence REAL NOT NULL,
+    detected_at TEXT NOT NULL,
+    FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE,
+    UNIQUE (file_id, new_path)
+);
+
+-- Create index for finding the candidates of a file
+CREATE INDEX IF NOT EXISTS idx_move_candidates_file_id ON move_candidates(file_id);
+
+-- Insert migration record
+INSERT OR IGNORE INTO schema_migrations (version, name, applied_at, checksum)
+VALUES (7, '007_move_candidates', datetime('now'), 'move_candidates');
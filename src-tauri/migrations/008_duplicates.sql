@@ -0,0 +1,43 @@
+-- NeuralFS Migration: Duplicate groups
+-- Version: 008
+-- Description: Stores clusters of duplicate and near-duplicate files and cached perceptual image hashes
+
+-- A cluster of files holding the same or nearly the same content
+CREATE TABLE IF NOT EXISTS duplicate_groups (
+    id TEXT PRIMARY KEY NOT NULL,
+    kind TEXT NOT NULL, -- Exact, SimilarContent, SimilarImage
+    status TEXT NOT NULL DEFAULT 'Open', -- Open, Resolved
+    canonical_file_id TEXT,
+    detected_at TEXT NOT NULL,
+    resolved_at TEXT,
+    FOREIGN KEY (canonical_file_id) REFERENCES files(id) ON DELETE SET NULL
+);
+
+-- Create index for listing open groups
+CREATE INDEX IF NOT EXISTS idx_duplicate_groups_status ON duplicate_groups(status);
+
+-- Files belonging to a group
+CREATE TABLE IF NOT EXISTS duplicate_members (
+    group_id TEXT NOT NULL,
+    file_id TEXT NOT NULL,
+    similarity REAL NOT NULL,
+    PRIMARY KEY (group_id, file_id),
+    FOREIGN KEY (group_id) REFERENCES duplicate_groups(id) ON DELETE CASCADE,
+    FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE
+);
+
+-- Create index for finding the group of a file
+CREATE INDEX IF NOT EXISTS idx_duplicate_members_file_id ON duplicate_members(file_id);
+
+-- Perceptual hash (dHash) per image, valid while content_hash matches the file
+CREATE TABLE IF NOT EXISTS image_hashes (
+    file_id TEXT PRIMARY KEY NOT NULL,
+    content_hash TEXT NOT NULL,
+    hash INTEGER NOT NULL,
+    computed_at TEXT NOT NULL,
+    FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE
+);
+
+-- Insert migration record
+INSERT OR IGNORE INTO schema_migrations (version, name, applied_at, checksum)
+VALUES (8, '008_duplicates', datetime('now'), 'duplicates');
@@ -0,0 +1,29 @@
+-- NeuralFS Migration: Image metadata
+-- Version: 009
+-- Description: Stores EXIF/XMP metadata of photos for capture date and location filters
+
+-- One row per indexed image; GPS columns are sensitive and never leave the device
+CREATE TABLE IF NOT EXISTS image_metadata (
+    file_id TEXT PRIMARY KEY NOT NULL,
+    width INTEGER,
+    height INTEGER,
+    captured_at TEXT, -- RFC 3339 with the camera's offset, if recorded
+    captured_ts INTEGER, -- camera wall-clock time as a Unix timestamp
+    camera TEXT,
+    lens TEXT,
+    orientation INTEGER,
+    latitude REAL,
+    longitude REAL,
+    altitude REAL,
+    location TEXT,
+    keywords TEXT, -- JSON array
+    FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE
+);
+
+-- Create indexes for capture date and place filters
+CREATE INDEX IF NOT EXISTS idx_image_metadata_captured_ts ON image_metadata(captured_ts);
+CREATE INDEX IF NOT EXISTS idx_image_metadata_location ON image_metadata(location);
+
+-- Insert migration record
+INSERT OR IGNORE INTO schema_migrations (version, name, applied_at, checksum)
+VALUES (9, '009_image_metadata', datetime('now'), 'image_metadata');
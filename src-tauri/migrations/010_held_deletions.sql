@@ -0,0 +1,20 @@
+-- NeuralFS Migration: Held deletions
+-- Version: 010
+-- Description: Keeps deletions over the safety threshold until the user confirms them
+
+-- An indexed file that went missing as part of a mass deletion; the row
+-- follows the file's row when it is renamed or removed
+CREATE TABLE IF NOT EXISTS held_deletions (
+    path TEXT PRIMARY KEY NOT NULL,
+    root TEXT NOT NULL,
+    indexed_files INTEGER NOT NULL,
+    held_at TEXT NOT NULL,
+    FOREIGN KEY (path) REFERENCES files(path) ON DELETE CASCADE ON UPDATE CASCADE
+);
+
+-- Create index for listing the deletions of a root
+CREATE INDEX IF NOT EXISTS idx_held_deletions_root ON held_deletions(root);
+
+-- Insert migration record
+INSERT OR IGNORE INTO schema_migrations (version, name, applied_at, checksum)
+VALUES (10, '010_held_deletions', datetime('now'), 'held_deletions');
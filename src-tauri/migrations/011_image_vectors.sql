@@ -0,0 +1,15 @@
+-- NeuralFS Migration: Image vectors
+-- Version: 011
+-- Description: Tracks the CLIP image embedding of each indexed image
+
+-- One row per image with a vector in the image space
+CREATE TABLE IF NOT EXISTS image_vectors (
+    file_id TEXT PRIMARY KEY NOT NULL,
+    vector_id INTEGER NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE
+);
+
+-- Insert migration record
+INSERT OR IGNORE INTO schema_migrations (version, name, applied_at, checksum)
+VALUES (11, '011_image_vectors', datetime('now'), 'image_vectors');
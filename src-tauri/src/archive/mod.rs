@@ -0,0 +1,608 @@
+//! Archive access
+//!
+//! Reads zip and tar archives (plain or gzip-compressed) for the parser,
+//! preview and asset layers:
+//! - members are visited in archive order, each through a reader that
+//!   stops at the configured member and total size limits, so a
+//!   decompression bomb cannot exhaust memory or disk
+//! - `ArchiveLimits` also caps the number of entries, the compression
+//!   ratio and how deep nested archives are opened; an `ArchiveBudget`
+//!   shares one total size limit across several passes, such as the
+//!   archives nested in one file
+//! - members are addressed by virtual paths such as
+//!   `project-2023.zip!/docs/spec.md`; a member of a nested archive
+//!   chains the separator (`outer.zip!/inner.tar!/notes.txt`)
+//!
+//! Member names are normalized to `/`-separated relative paths; entries
+//! that would escape the archive (`..`, absolute paths) are ignored.
+
+#[cfg(test)]
+mod tests;
+
+use std::fs::File;
+use std::io::{self, BufReader, Cursor, Read, Seek, Write};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use flate2::read::GzDecoder;
+use thiserror::Error;
+use zip::ZipArchive;
+
+/// Separator between an archive path and a member path
+pub const MEMBER_SEPARATOR: &str = "!/";
+
+/// Members smaller than this are never rejected for their compression ratio
+const RATIO_CHECK_MIN_SIZE: u64 = 1024 * 1024;
+
+/// Errors that can occur while reading an archive
+#[derive(Error, Debug)]
+pub enum ArchiveError {
+    #[error("Archive not found: {path}")]
+    NotFound { path: String },
+
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("Unsupported archive format: {path}")]
+    UnsupportedFormat { path: String },
+
+    #[error("Archive corrupted or invalid: {reason}")]
+    Corrupted { reason: String },
+
+    #[error("Archive member not found: {member}")]
+    MemberNotFound { member: String },
+
+    #[error("Archive limit exceeded: {reason}")]
+    LimitExceeded { reason: String },
+}
+
+/// Limits applied while reading archives
+#[derive(Debug, Clone)]
+pub struct ArchiveLimits {
+    /// How many archives deep members are opened (1 = only the members of
+    /// the archive itself, not of archives inside it)
+    pub max_depth: u32,
+    /// Maximum number of file entries read from one archive
+    pub max_entries: usize,
+    /// Maximum uncompressed size of one member in bytes
+    pub max_member_size: u64,
+    /// Maximum uncompressed bytes read from one archive
+    pub max_total_size: u64,
+    /// Maximum ratio of uncompressed to compressed size of a zip member
+    pub max_compression_ratio: u64,
+}
+
+impl Default for ArchiveLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: 3,
+            max_entries: 10_000,
+            max_member_size: 64 * 1024 * 1024,
+            max_total_size: 512 * 1024 * 1024,
+            max_compression_ratio: 100,
+        }
+    }
+}
+
+impl ArchiveLimits {
+    /// Whether a member may be extracted, judging by its declared sizes
+    ///
+    /// Declared sizes can lie; readers from `for_each_member` enforce the
+    /// size limits on the bytes actually read as well.
+    pub fn allows(&self, entry: &ArchiveEntry) -> bool {
+        if entry.size > self.max_member_size {
+            return false;
+        }
+        match entry.compressed_size {
+            Some(compressed) if entry.size >= RATIO_CHECK_MIN_SIZE => {
+                entry.size / compressed.max(1) <= self.max_compression_ratio
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Uncompressed bytes left to read, shared by several archive passes
+///
+/// Clones share one budget: a pass starts with what earlier passes left
+/// and its reads are charged back when it ends.
+#[derive(Debug, Clone)]
+pub struct ArchiveBudget {
+    left: Arc<AtomicU64>,
+}
+
+impl ArchiveBudget {
+    /// Budget of `bytes` uncompressed bytes
+    pub fn new(bytes: u64) -> Self {
+        Self {
+            left: Arc::new(AtomicU64::new(bytes)),
+        }
+    }
+
+    /// Bytes left to read
+    pub fn remaining(&self) -> u64 {
+        self.left.load(Ordering::SeqCst)
+    }
+
+    fn spend(&self, bytes: u64) {
+        let _ = self
+            .left
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |left| Some(left.saturating_sub(bytes)));
+    }
+}
+
+/// Supported archive container formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveFormat {
+    /// Detect the format from a file or member name
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        if name.ends_with(".zip") {
+            Some(ArchiveFormat::Zip)
+        } else if name.ends_with(".tar") {
+            Some(ArchiveFormat::Tar)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(ArchiveFormat::TarGz)
+        } else {
+            None
+        }
+    }
+
+    /// Detect the format from a path
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.file_name()
+            .and_then(|n| n.to_str())
+            .and_then(Self::from_name)
+    }
+}
+
+/// A file entry of an archive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveEntry {
+    /// Normalized member path (`docs/spec.md`)
+    pub path: String,
+    /// Declared uncompressed size in bytes
+    pub size: u64,
+    /// Declared compressed size in bytes (zip only)
+    pub compressed_size: Option<u64>,
+}
+
+impl ArchiveEntry {
+    /// Last component of the member path
+    pub fn file_name(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or(&self.path)
+    }
+}
+
+/// Counts from a pass over an archive
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArchiveSummary {
+    /// File entries visited
+    pub entries: usize,
+    /// Whether entries were left unread because of `max_entries`
+    pub truncated: bool,
+}
+
+// ============================================================================
+// Virtual paths
+// ============================================================================
+
+/// Virtual path of an archive member (`archive.zip!/docs/spec.md`)
+pub fn virtual_path(archive: &Path, member: &str) -> PathBuf {
+    PathBuf::from(format!("{}{}{}", archive.display(), MEMBER_SEPARATOR, member))
+}
+
+/// Split a virtual path into the archive path and member path
+///
+/// Returns `None` for plain paths. The member of a nested archive keeps
+/// its own separators.
+pub fn split_virtual_path(path: &Path) -> Option<(PathBuf, String)> {
+    let path = path.to_str()?;
+    let mut search_from = 0;
+    while let Some(found) = path[search_from..].find(MEMBER_SEPARATOR) {
+        let split = search_from + found;
+        let (archive, member) = (&path[..split], &path[split + MEMBER_SEPARATOR.len()..]);
+        // `!` may also appear inside a file name
+        if ArchiveFormat::from_name(archive).is_some() && !member.is_empty() {
+            return Some((PathBuf::from(archive), member.to_string()));
+        }
+        search_from = split + MEMBER_SEPARATOR.len();
+    }
+    None
+}
+
+// ============================================================================
+// Reading
+// ============================================================================
+
+/// Visit the file entries of the archive at `path` in archive order
+///
+/// `visit` receives each entry and a reader of its content bounded by the
+/// size limits; reading past a limit fails with an IO error. Returning
+/// `ControlFlow::Break` stops the pass.
+pub fn for_each_member<F>(path: &Path, limits: &ArchiveLimits, visit: F) -> Result<ArchiveSummary, ArchiveError>
+where
+    F: FnMut(&ArchiveEntry, &mut dyn Read) -> Result<ControlFlow<()>, ArchiveError>,
+{
+    for_each_member_within(path, limits, &ArchiveBudget::new(limits.max_total_size), visit)
+}
+
+/// Visit the file entries of the archive at `path`, reading at most what
+/// is left of `budget` in total
+///
+/// `limits.max_total_size` is not applied on its own; the budget stands
+/// in for it.
+pub fn for_each_member_within<F>(
+    path: &Path,
+    limits: &ArchiveLimits,
+    budget: &ArchiveBudget,
+    visit: F,
+) -> Result<ArchiveSummary, ArchiveError>
+where
+    F: FnMut(&ArchiveEntry, &mut dyn Read) -> Result<ControlFlow<()>, ArchiveError>,
+{
+    if !path.exists() {
+        return Err(ArchiveError::NotFound {
+            path: path.display().to_string(),
+        });
+    }
+    let format = ArchiveFormat::from_path(path).ok_or_else(|| ArchiveError::UnsupportedFormat {
+        path: path.display().to_string(),
+    })?;
+
+    visit_archive(BufReader::new(File::open(path)?), format, limits, budget, visit)
+}
+
+/// List the file entries of the archive at `path`
+pub fn list_entries(path: &Path, limits: &ArchiveLimits) -> Result<Vec<ArchiveEntry>, ArchiveError> {
+    let mut entries = Vec::new();
+    for_each_member(path, limits, |entry, _| {
+        entries.push(entry.clone());
+        Ok(ControlFlow::Continue(()))
+    })?;
+    Ok(entries)
+}
+
+/// Read one member of the archive at `path`
+///
+/// `member` may address a member of a nested archive
+/// (`inner.tar!/notes.txt`), up to `max_depth` archives deep.
+pub fn read_member(path: &Path, member: &str, limits: &ArchiveLimits) -> Result<Vec<u8>, ArchiveError> {
+    with_member(path, member, limits, |entry, reader| read_all(entry, reader, limits))
+}
+
+/// Copy one member of the archive at `path` into `writer`
+///
+/// Like `read_member`, but the member streams through its bounded reader
+/// instead of being collected. Archives it is nested in are still read
+/// into memory, since zip needs to seek. Returns the bytes copied.
+pub fn copy_member(path: &Path, member: &str, limits: &ArchiveLimits, writer: &mut dyn Write) -> Result<u64, ArchiveError> {
+    with_member(path, member, limits, |entry, reader| {
+        io::copy(reader, writer).map_err(|e| member_read_error(e, &entry.path))
+    })
+}
+
+/// Find a member, opening the archives it is nested in, and hand its
+/// bounded reader to `use_member`
+fn with_member<T, U>(path: &Path, member: &str, limits: &ArchiveLimits, use_member: U) -> Result<T, ArchiveError>
+where
+    U: FnOnce(&ArchiveEntry, &mut dyn Read) -> Result<T, ArchiveError>,
+{
+    let budget = ArchiveBudget::new(limits.max_total_size);
+    let (outer, rest) = split_member(member);
+    let Some(mut inner) = rest else {
+        return find_member(
+            |visit| for_each_member_within(path, limits, &budget, visit),
+            outer,
+            limits,
+            use_member,
+        );
+    };
+    let mut data = find_member(
+        |visit| for_each_member_within(path, limits, &budget, visit),
+        outer,
+        limits,
+        |entry, reader| read_all(entry, reader, limits),
+    )?;
+
+    let mut depth = 1;
+    let mut name = outer;
+    loop {
+        depth += 1;
+        if depth > limits.max_depth {
+            return Err(ArchiveError::LimitExceeded {
+                reason: format!("{} is nested more than {} archives deep", member, limits.max_depth),
+            });
+        }
+        let format = ArchiveFormat::from_name(name).ok_or_else(|| ArchiveError::MemberNotFound {
+            member: member.to_string(),
+        })?;
+        let (next, next_rest) = split_member(inner);
+        let bytes = data;
+        let Some(next_inner) = next_rest else {
+            return find_member(
+                |visit| visit_archive(Cursor::new(&bytes), format, limits, &budget, visit),
+                next,
+                limits,
+                use_member,
+            );
+        };
+        data = find_member(
+            |visit| visit_archive(Cursor::new(&bytes), format, limits, &budget, visit),
+            next,
+            limits,
+            |entry, reader| read_all(entry, reader, limits),
+        )?;
+        name = next;
+        inner = next_inner;
+    }
+}
+
+/// Collect a member's content
+fn read_all(entry: &ArchiveEntry, reader: &mut dyn Read, limits: &ArchiveLimits) -> Result<Vec<u8>, ArchiveError> {
+    let mut data = Vec::with_capacity(entry.size.min(limits.max_member_size) as usize);
+    reader.read_to_end(&mut data).map_err(|e| member_read_error(e, &entry.path))?;
+    Ok(data)
+}
+
+/// Read the member a virtual path addresses
+pub fn read_virtual_path(path: &Path, limits: &ArchiveLimits) -> Result<Vec<u8>, ArchiveError> {
+    let (archive, member) = split_virtual_path(path).ok_or_else(|| ArchiveError::MemberNotFound {
+        member: path.display().to_string(),
+    })?;
+    read_member(&archive, &member, limits)
+}
+
+/// First member path and the rest of a chained member path
+fn split_member(member: &str) -> (&str, Option<&str>) {
+    match member.split_once(MEMBER_SEPARATOR) {
+        Some((first, rest)) if ArchiveFormat::from_name(first).is_some() => (first, Some(rest)),
+        _ => (member, None),
+    }
+}
+
+type Visitor<'a> = &'a mut dyn FnMut(&ArchiveEntry, &mut dyn Read) -> Result<ControlFlow<()>, ArchiveError>;
+
+/// Hand the reader of the member named `member` in the given archive
+/// pass to `use_member`
+fn find_member<P, T, U>(pass: P, member: &str, limits: &ArchiveLimits, use_member: U) -> Result<T, ArchiveError>
+where
+    P: FnOnce(Visitor<'_>) -> Result<ArchiveSummary, ArchiveError>,
+    U: FnOnce(&ArchiveEntry, &mut dyn Read) -> Result<T, ArchiveError>,
+{
+    let wanted = normalize_member(member).ok_or_else(|| ArchiveError::MemberNotFound {
+        member: member.to_string(),
+    })?;
+    let mut use_member = Some(use_member);
+    let mut found = None;
+    let mut visit = |entry: &ArchiveEntry, reader: &mut dyn Read| -> Result<ControlFlow<()>, ArchiveError> {
+        if entry.path != wanted {
+            return Ok(ControlFlow::Continue(()));
+        }
+        if !limits.allows(entry) {
+            return Err(size_limit_error(&entry.path));
+        }
+        if let Some(use_member) = use_member.take() {
+            found = Some(use_member(entry, reader)?);
+        }
+        Ok(ControlFlow::Break(()))
+    };
+    pass(&mut visit)?;
+    found.ok_or_else(|| ArchiveError::MemberNotFound {
+        member: member.to_string(),
+    })
+}
+
+/// Visit the file entries of an archive read from `reader`, charging the
+/// bytes read to `budget`
+fn visit_archive<R, F>(
+    reader: R,
+    format: ArchiveFormat,
+    limits: &ArchiveLimits,
+    budget: &ArchiveBudget,
+    visit: F,
+) -> Result<ArchiveSummary, ArchiveError>
+where
+    R: Read + Seek,
+    F: FnMut(&ArchiveEntry, &mut dyn Read) -> Result<ControlFlow<()>, ArchiveError>,
+{
+    let start = budget.remaining();
+    let mut total_left = start;
+    let result = visit_entries(reader, format, limits, &mut total_left, visit);
+    budget.spend(start - total_left);
+    result
+}
+
+fn visit_entries<R, F>(
+    reader: R,
+    format: ArchiveFormat,
+    limits: &ArchiveLimits,
+    total_left: &mut u64,
+    mut visit: F,
+) -> Result<ArchiveSummary, ArchiveError>
+where
+    R: Read + Seek,
+    F: FnMut(&ArchiveEntry, &mut dyn Read) -> Result<ControlFlow<()>, ArchiveError>,
+{
+    let mut summary = ArchiveSummary::default();
+
+    match format {
+        ArchiveFormat::Zip => {
+            let mut archive = ZipArchive::new(reader).map_err(|e| ArchiveError::Corrupted {
+                reason: e.to_string(),
+            })?;
+            for index in 0..archive.len() {
+                let mut file = archive.by_index(index).map_err(|e| ArchiveError::Corrupted {
+                    reason: e.to_string(),
+                })?;
+                if file.is_dir() {
+                    continue;
+                }
+                let Some(path) = normalize_member(file.name()) else {
+                    continue;
+                };
+                if summary.entries >= limits.max_entries {
+                    summary.truncated = true;
+                    break;
+                }
+                summary.entries += 1;
+
+                let entry = ArchiveEntry {
+                    path,
+                    size: file.size(),
+                    compressed_size: Some(file.compressed_size()),
+                };
+                let mut bounded = Bounded::new(&mut file, limits.max_member_size, &mut *total_left);
+                if visit(&entry, &mut bounded)?.is_break() {
+                    break;
+                }
+            }
+        }
+        ArchiveFormat::Tar => visit_tar(reader, limits, &mut summary, total_left, &mut visit)?,
+        ArchiveFormat::TarGz => visit_tar(GzDecoder::new(reader), limits, &mut summary, total_left, &mut visit)?,
+    }
+
+    Ok(summary)
+}
+
+fn visit_tar<R, F>(
+    reader: R,
+    limits: &ArchiveLimits,
+    summary: &mut ArchiveSummary,
+    total_left: &mut u64,
+    visit: &mut F,
+) -> Result<(), ArchiveError>
+where
+    R: Read,
+    F: FnMut(&ArchiveEntry, &mut dyn Read) -> Result<ControlFlow<()>, ArchiveError>,
+{
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let Some(path) = normalize_member(&entry.path()?.to_string_lossy()) else {
+            continue;
+        };
+        if summary.entries >= limits.max_entries {
+            summary.truncated = true;
+            break;
+        }
+        summary.entries += 1;
+
+        let entry_info = ArchiveEntry {
+            path,
+            size: entry.size(),
+            compressed_size: None,
+        };
+        let mut bounded = Bounded::new(&mut entry, limits.max_member_size, total_left);
+        if visit(&entry_info, &mut bounded)?.is_break() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Normalize a member name to a relative `/`-separated path
+///
+/// Returns `None` for names that are empty or would leave the archive.
+fn normalize_member(name: &str) -> Option<String> {
+    let name = name.replace('\\', "/");
+    if name.starts_with('/') {
+        return None;
+    }
+    let mut parts = Vec::new();
+    for part in name.split('/') {
+        match part {
+            "" | "." => continue,
+            ".." => return None,
+            // Drive prefixes (`C:`) are absolute on Windows
+            _ if parts.is_empty() && part.ends_with(':') => return None,
+            _ => parts.push(part),
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+// ============================================================================
+// Size limits
+// ============================================================================
+
+/// Marker error for reads past a size limit
+#[derive(Debug)]
+struct SizeLimit;
+
+impl std::fmt::Display for SizeLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("archive member exceeds the size limit")
+    }
+}
+
+impl std::error::Error for SizeLimit {}
+
+/// Whether an IO error came from a `Bounded` reader
+pub fn is_size_limit(error: &io::Error) -> bool {
+    error.get_ref().is_some_and(|inner| inner.is::<SizeLimit>())
+}
+
+fn size_limit_error(member: &str) -> ArchiveError {
+    ArchiveError::LimitExceeded {
+        reason: format!("{} exceeds the size limit", member),
+    }
+}
+
+fn member_read_error(error: io::Error, member: &str) -> ArchiveError {
+    if is_size_limit(&error) {
+        size_limit_error(member)
+    } else {
+        ArchiveError::Io(error)
+    }
+}
+
+/// Reader failing once a member or the whole archive reads past its limit
+struct Bounded<'a, R> {
+    inner: R,
+    member_left: u64,
+    total_left: &'a mut u64,
+}
+
+impl<'a, R: Read> Bounded<'a, R> {
+    fn new(inner: R, max_member_size: u64, total_left: &'a mut u64) -> Self {
+        Self {
+            inner,
+            member_left: max_member_size,
+            total_left,
+        }
+    }
+}
+
+impl<R: Read> Read for Bounded<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let allowed = self.member_left.min(*self.total_left);
+        if allowed == 0 {
+            // At the limit: fine if the member ends here
+            let mut probe = [0u8; 1];
+            return match self.inner.read(&mut probe)? {
+                0 => Ok(0),
+                _ => Err(io::Error::new(io::ErrorKind::InvalidData, SizeLimit)),
+            };
+        }
+        let len = buf.len().min(allowed.min(usize::MAX as u64) as usize);
+        let read = self.inner.read(&mut buf[..len])?;
+        self.member_left -= read as u64;
+        *self.total_left -= read as u64;
+        Ok(read)
+    }
+}
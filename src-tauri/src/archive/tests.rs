@@ -0,0 +1,266 @@
+//! Tests for archive access
+
+use super::*;
+use std::io::Write;
+use tempfile::TempDir;
+
+/// Zip archive with the given members, deflate-compressed
+fn zip_bytes(members: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, data) in members {
+        writer.start_file(*name, options).unwrap();
+        writer.write_all(data).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+/// Tar archive with the given members
+fn tar_bytes(members: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut builder = tar::Builder::new(Vec::new());
+    for (name, data) in members {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append_data(&mut header, name, *data).unwrap();
+    }
+    builder.into_inner().unwrap()
+}
+
+fn write_archive(dir: &TempDir, name: &str, bytes: &[u8]) -> PathBuf {
+    let path = dir.path().join(name);
+    std::fs::write(&path, bytes).unwrap();
+    path
+}
+
+#[test]
+fn test_format_detection() {
+    assert_eq!(ArchiveFormat::from_name("project-2023.zip"), Some(ArchiveFormat::Zip));
+    assert_eq!(ArchiveFormat::from_name("backup.TAR"), Some(ArchiveFormat::Tar));
+    assert_eq!(ArchiveFormat::from_name("src.tar.gz"), Some(ArchiveFormat::TarGz));
+    assert_eq!(ArchiveFormat::from_name("src.tgz"), Some(ArchiveFormat::TarGz));
+    assert_eq!(ArchiveFormat::from_name("notes.txt.gz"), None);
+    assert_eq!(ArchiveFormat::from_name("photos.7z"), None);
+}
+
+#[test]
+fn test_virtual_path_roundtrip() {
+    let path = virtual_path(Path::new("/home/mei/project-2023.zip"), "docs/spec.md");
+    assert_eq!(path, PathBuf::from("/home/mei/project-2023.zip!/docs/spec.md"));
+
+    assert_eq!(
+        split_virtual_path(&path),
+        Some((PathBuf::from("/home/mei/project-2023.zip"), "docs/spec.md".to_string()))
+    );
+    assert_eq!(
+        split_virtual_path(Path::new("/data/outer.zip!/inner.tar!/notes.txt")),
+        Some((PathBuf::from("/data/outer.zip"), "inner.tar!/notes.txt".to_string()))
+    );
+    // `!/` after something that is not an archive is part of a name
+    assert_eq!(
+        split_virtual_path(Path::new("/data/wow!/release.zip!/readme.md")),
+        Some((PathBuf::from("/data/wow!/release.zip"), "readme.md".to_string()))
+    );
+    assert_eq!(split_virtual_path(Path::new("/data/wow!/readme.md")), None);
+    assert_eq!(split_virtual_path(Path::new("/data/release.zip!/")), None);
+}
+
+#[test]
+fn test_normalize_member() {
+    assert_eq!(normalize_member("docs/spec.md"), Some("docs/spec.md".to_string()));
+    assert_eq!(normalize_member("./docs//spec.md"), Some("docs/spec.md".to_string()));
+    assert_eq!(normalize_member("docs\\spec.md"), Some("docs/spec.md".to_string()));
+    assert_eq!(normalize_member("../etc/passwd"), None);
+    assert_eq!(normalize_member("docs/../../etc/passwd"), None);
+    assert_eq!(normalize_member("/etc/passwd"), None);
+    assert_eq!(normalize_member("C:/Windows/win.ini"), None);
+    assert_eq!(normalize_member("docs/"), Some("docs".to_string()));
+}
+
+#[test]
+fn test_list_zip_entries() {
+    let dir = TempDir::new().unwrap();
+    let path = write_archive(
+        &dir,
+        "project-2023.zip",
+        &zip_bytes(&[
+            ("docs/spec.md", &b"# Spec"[..]),
+            ("../escape.txt", &b"no"[..]),
+            ("README", &b"hi"[..]),
+        ]),
+    );
+
+    let entries = list_entries(&path, &ArchiveLimits::default()).unwrap();
+    let paths: Vec<&str> = entries.iter().map(|e| e.path.as_str()).collect();
+    assert_eq!(paths, vec!["docs/spec.md", "README"]);
+    assert_eq!(entries[0].size, 6);
+    assert_eq!(entries[0].file_name(), "spec.md");
+}
+
+#[test]
+fn test_read_member_from_tar_gz() {
+    let dir = TempDir::new().unwrap();
+    let tar = tar_bytes(&[
+        ("notes/kyoto.txt", &b"Philosopher's Path"[..]),
+        ("notes/nara.txt", &b"Deer park"[..]),
+    ]);
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&tar).unwrap();
+    let path = write_archive(&dir, "notes.tar.gz", &encoder.finish().unwrap());
+
+    let limits = ArchiveLimits::default();
+    assert_eq!(read_member(&path, "notes/nara.txt", &limits).unwrap(), b"Deer park");
+    assert!(matches!(
+        read_member(&path, "notes/osaka.txt", &limits),
+        Err(ArchiveError::MemberNotFound { .. })
+    ));
+}
+
+#[test]
+fn test_read_nested_member() {
+    let dir = TempDir::new().unwrap();
+    let inner = tar_bytes(&[("notes.txt", &b"Inner notes"[..])]);
+    let path = write_archive(&dir, "outer.zip", &zip_bytes(&[("archive/inner.tar", inner.as_slice())]));
+
+    let limits = ArchiveLimits::default();
+    assert_eq!(
+        read_virtual_path(&virtual_path(&path, "archive/inner.tar!/notes.txt"), &limits).unwrap(),
+        b"Inner notes"
+    );
+
+    let shallow = ArchiveLimits {
+        max_depth: 1,
+        ..Default::default()
+    };
+    assert!(matches!(
+        read_member(&path, "archive/inner.tar!/notes.txt", &shallow),
+        Err(ArchiveError::LimitExceeded { .. })
+    ));
+}
+
+#[test]
+fn test_entry_limit_truncates() {
+    let dir = TempDir::new().unwrap();
+    let members: Vec<(String, Vec<u8>)> = (0..5).map(|i| (format!("file{}.txt", i), vec![b'x'])).collect();
+    let refs: Vec<(&str, &[u8])> = members.iter().map(|(n, d)| (n.as_str(), d.as_slice())).collect();
+    let path = write_archive(&dir, "many.zip", &zip_bytes(&refs));
+
+    let limits = ArchiveLimits {
+        max_entries: 3,
+        ..Default::default()
+    };
+    let mut seen = 0;
+    let summary = for_each_member(&path, &limits, |_, _| {
+        seen += 1;
+        Ok(ControlFlow::Continue(()))
+    })
+    .unwrap();
+
+    assert_eq!(seen, 3);
+    assert_eq!(summary.entries, 3);
+    assert!(summary.truncated);
+}
+
+#[test]
+fn test_compression_bomb_is_refused() {
+    let dir = TempDir::new().unwrap();
+    // 4 MB of zeros compress to a few kilobytes
+    let zeros = vec![0u8; 4 * 1024 * 1024];
+    let path = write_archive(&dir, "bomb.zip", &zip_bytes(&[("zeros.bin", zeros.as_slice())]));
+
+    let limits = ArchiveLimits::default();
+    let entries = list_entries(&path, &limits).unwrap();
+    assert!(!limits.allows(&entries[0]));
+    assert!(matches!(
+        read_member(&path, "zeros.bin", &limits),
+        Err(ArchiveError::LimitExceeded { .. })
+    ));
+}
+
+#[test]
+fn test_total_size_limit_stops_reads() {
+    let dir = TempDir::new().unwrap();
+    let path = write_archive(
+        &dir,
+        "big.tar",
+        &tar_bytes(&[("a.txt", &[b'a'; 600][..]), ("b.txt", &[b'b'; 600][..])]),
+    );
+
+    let limits = ArchiveLimits {
+        max_total_size: 1000,
+        ..Default::default()
+    };
+    let mut results = Vec::new();
+    for_each_member(&path, &limits, |entry, reader| {
+        let mut data = Vec::new();
+        results.push((entry.path.clone(), reader.read_to_end(&mut data).map_err(|e| is_size_limit(&e))));
+        Ok(ControlFlow::Continue(()))
+    })
+    .unwrap();
+
+    assert_eq!(results, vec![("a.txt".to_string(), Ok(600)), ("b.txt".to_string(), Err(true))]);
+}
+
+#[test]
+fn test_budget_is_shared_between_passes() {
+    let dir = TempDir::new().unwrap();
+    let path = write_archive(&dir, "a.tar", &tar_bytes(&[("a.txt", &[b'a'; 600][..])]));
+    let limits = ArchiveLimits::default();
+    let budget = ArchiveBudget::new(1000);
+
+    let read = |budget: &ArchiveBudget| {
+        let mut result = None;
+        for_each_member_within(&path, &limits, budget, |_, reader| {
+            result = Some(reader.read_to_end(&mut Vec::new()).map_err(|e| is_size_limit(&e)));
+            Ok(ControlFlow::Continue(()))
+        })
+        .unwrap();
+        result.unwrap()
+    };
+
+    assert_eq!(read(&budget), Ok(600));
+    assert_eq!(budget.remaining(), 400);
+    assert_eq!(read(&budget), Err(true));
+    assert_eq!(budget.remaining(), 0);
+}
+
+#[test]
+fn test_copy_nested_member() {
+    let dir = TempDir::new().unwrap();
+    let inner = tar_bytes(&[("notes.txt", &b"Inner notes"[..])]);
+    let path = write_archive(&dir, "outer.zip", &zip_bytes(&[("archive/inner.tar", inner.as_slice())]));
+
+    let mut copied = Vec::new();
+    let limits = ArchiveLimits::default();
+    assert_eq!(copy_member(&path, "archive/inner.tar!/notes.txt", &limits, &mut copied).unwrap(), 11);
+    assert_eq!(copied, b"Inner notes");
+
+    let small = ArchiveLimits {
+        max_member_size: 4,
+        ..Default::default()
+    };
+    assert!(matches!(
+        copy_member(&path, "archive/inner.tar!/notes.txt", &small, &mut Vec::new()),
+        Err(ArchiveError::LimitExceeded { .. })
+    ));
+}
+
+#[test]
+fn test_unsupported_and_missing_archives() {
+    let dir = TempDir::new().unwrap();
+    let limits = ArchiveLimits::default();
+
+    let rar = write_archive(&dir, "photos.rar", b"Rar!");
+    assert!(matches!(
+        list_entries(&rar, &limits),
+        Err(ArchiveError::UnsupportedFormat { .. })
+    ));
+    assert!(matches!(
+        list_entries(&dir.path().join("missing.zip"), &limits),
+        Err(ArchiveError::NotFound { .. })
+    ));
+
+    let broken = write_archive(&dir, "broken.zip", b"not a zip file");
+    assert!(matches!(list_entries(&broken, &limits), Err(ArchiveError::Corrupted { .. })));
+}
@@ -1,6 +1,7 @@
 //! Secure Asset Streaming Server
 //!
-//! This module provides a secure HTTP server for streaming assets (thumbnails, previews, files)
+//! This module provides a secure HTTP server for streaming assets (thumbnails, previews, files,
+//! archive members)
 //! to the frontend without IPC serialization overhead.
 //!
 //! Security features:
@@ -20,4 +21,4 @@ pub use server::{
     SecureAssetStreamServer, AssetServerConfig, AssetServerState,
     CachedThumbnail, CachedPreview,
 };
-pub use routes::{TokenParams, serve_thumbnail, serve_preview, serve_file};
+pub use routes::{TokenParams, serve_thumbnail, serve_preview, serve_file, serve_archive_member};
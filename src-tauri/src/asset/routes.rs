@@ -1,7 +1,8 @@
 //! Asset server routes and middleware
 //!
-//! Provides HTTP handlers for serving thumbnails, previews, and files
-//! with security middleware for token validation and CSRF protection.
+//! Provides HTTP handlers for serving thumbnails, previews, files and
+//! archive members with security middleware for token validation and
+//! CSRF protection.
 
 use axum::{
     body::Body,
@@ -10,10 +11,12 @@ use axum::{
     middleware::Next,
     response::{IntoResponse, Response},
 };
+use futures::StreamExt;
 use serde::Deserialize;
 use uuid::Uuid;
 
 use super::server::AssetServerState;
+use crate::archive::{self, ArchiveError, ArchiveLimits};
 
 /// Query parameters for token-based authentication
 #[derive(Debug, Deserialize)]
@@ -193,6 +196,108 @@ pub async fn serve_file(
     )
 }
 
+/// Serve a member of a registered archive
+///
+/// Route: GET /archive/:uuid/*member
+///
+/// The member is read under the default `ArchiveLimits`, so oversized or
+/// suspiciously compressed members are refused rather than unpacked. It
+/// is streamed as it is decompressed; a limit hit mid-stream aborts the
+/// response.
+pub async fn serve_archive_member(
+    State(state): State<AssetServerState>,
+    Path((uuid, member)): Path<(Uuid, String)>,
+) -> Response {
+    let text_response = |status: StatusCode, message: String| {
+        let headers = [
+            (header::CONTENT_TYPE, "text/plain".to_string()),
+            (header::CACHE_CONTROL, "no-cache".to_string()),
+        ];
+        (status, headers, message.into_bytes()).into_response()
+    };
+
+    let member = member.trim_start_matches('/').to_string();
+    let Some(archive_path) = state.get_archive_path(&uuid) else {
+        return text_response(StatusCode::NOT_FOUND, "Archive not found".to_string());
+    };
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel(MEMBER_CHUNKS_BUFFERED);
+    let member_name = member.clone();
+    tokio::task::spawn_blocking(move || {
+        let mut writer = ChunkSender(tx.clone());
+        if let Err(e) = archive::copy_member(&archive_path, &member_name, &ArchiveLimits::default(), &mut writer) {
+            let _ = tx.blocking_send(Err(e));
+        }
+    });
+
+    // Errors before the first chunk still get a status of their own
+    let first = match rx.recv().await {
+        Some(Ok(chunk)) => Some(chunk),
+        Some(Err(ArchiveError::MemberNotFound { .. } | ArchiveError::NotFound { .. })) => {
+            return text_response(StatusCode::NOT_FOUND, "Archive member not found".to_string());
+        }
+        Some(Err(e)) => {
+            tracing::warn!("Failed to read member {} of archive {}: {}", member, uuid, e);
+            return text_response(StatusCode::UNPROCESSABLE_ENTITY, e.to_string());
+        }
+        None => None,
+    };
+
+    let rest = futures::stream::unfold(rx, |mut rx| async move { rx.recv().await.map(|item| (item, rx)) });
+    let chunks = futures::stream::iter(first.map(Ok))
+        .chain(rest)
+        .map(|item| item.map_err(std::io::Error::other));
+
+    let headers = [
+        (header::CONTENT_TYPE, member_content_type(&member).to_string()),
+        (header::CACHE_CONTROL, "private, max-age=3600".to_string()),
+    ];
+    (StatusCode::OK, headers, Body::from_stream(chunks)).into_response()
+}
+
+/// Chunks of a streamed member waiting for the client
+const MEMBER_CHUNKS_BUFFERED: usize = 16;
+
+/// Writer handing the chunks of a member to the response body
+struct ChunkSender(tokio::sync::mpsc::Sender<Result<Vec<u8>, ArchiveError>>);
+
+impl std::io::Write for ChunkSender {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0
+            .blocking_send(Ok(buf.to_vec()))
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::BrokenPipe, "response closed"))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Content type of an archive member by extension
+///
+/// Unknown and scriptable types (HTML, SVG) are served as bytes;
+/// `nosniff` keeps browsers from guessing otherwise.
+fn member_content_type(member: &str) -> &'static str {
+    let extension = member
+        .rsplit('/')
+        .next()
+        .and_then(|name| name.rsplit_once('.'))
+        .map(|(_, ext)| ext.to_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "txt" | "md" | "markdown" | "rst" | "log" | "csv" => "text/plain; charset=utf-8",
+        "json" => "application/json",
+        "pdf" => "application/pdf",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "bmp" => "image/bmp",
+        _ => "application/octet-stream",
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
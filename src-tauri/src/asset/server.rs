@@ -6,6 +6,7 @@
 //! - Security headers
 
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -20,7 +21,7 @@ use tower_http::cors::{CorsLayer, Any};
 use uuid::Uuid;
 
 use super::error::AssetError;
-use super::routes::{serve_thumbnail, serve_preview, serve_file, security_middleware};
+use super::routes::{serve_thumbnail, serve_preview, serve_file, serve_archive_member, security_middleware};
 
 /// Default port for the asset server
 pub const DEFAULT_ASSET_SERVER_PORT: u16 = 19283;
@@ -152,6 +153,8 @@ pub struct AssetServerState {
     pub thumbnail_cache: Arc<DashMap<Uuid, CachedThumbnail>>,
     /// Preview cache
     pub preview_cache: Arc<DashMap<Uuid, CachedPreview>>,
+    /// Paths of archives whose members may be streamed
+    pub archive_paths: Arc<DashMap<Uuid, PathBuf>>,
     /// Allowed origins for CSRF protection
     pub allowed_origins: Vec<String>,
     /// Configuration
@@ -168,6 +171,7 @@ impl AssetServerState {
             session_token,
             thumbnail_cache: Arc::new(DashMap::new()),
             preview_cache: Arc::new(DashMap::new()),
+            archive_paths: Arc::new(DashMap::new()),
             allowed_origins,
             config,
         }
@@ -221,6 +225,32 @@ impl AssetServerState {
         self.preview_cache.get(uuid).map(|entry| entry.clone())
     }
 
+    /// Allow the members of an archive to be streamed
+    pub fn register_archive(&self, uuid: Uuid, path: PathBuf) {
+        self.archive_paths.insert(uuid, path);
+    }
+
+    /// Get the path of a registered archive
+    pub fn get_archive_path(&self, uuid: &Uuid) -> Option<PathBuf> {
+        self.archive_paths.get(uuid).map(|entry| entry.clone())
+    }
+
+    /// Stop streaming the members of an archive
+    pub fn unregister_archive(&self, uuid: &Uuid) {
+        self.archive_paths.remove(uuid);
+    }
+
+    /// Get the URL of a member (`docs/spec.md`) of a registered archive
+    pub fn archive_member_url(&self, uuid: Uuid, member: &str) -> String {
+        format!(
+            "http://127.0.0.1:{}/archive/{}/{}?token={}",
+            self.config.port,
+            uuid,
+            encode_member_path(member),
+            self.session_token
+        )
+    }
+
     /// Remove a thumbnail from the cache
     pub fn invalidate_thumbnail(&self, uuid: &Uuid) {
         self.thumbnail_cache.remove(uuid);
@@ -303,6 +333,12 @@ impl SecureAssetStreamServer {
         }
     }
 
+    /// Create a server over existing state, sharing its session token,
+    /// caches and registered archives
+    pub fn with_state(state: AssetServerState) -> Self {
+        Self { state }
+    }
+
     /// Get the session token (for frontend to use in requests)
     pub fn get_session_token(&self) -> &str {
         &self.state.session_token
@@ -342,6 +378,7 @@ impl SecureAssetStreamServer {
             .route("/thumbnail/:uuid", get(serve_thumbnail))
             .route("/preview/:uuid", get(serve_preview))
             .route("/file/:uuid", get(serve_file))
+            .route("/archive/:uuid/*member", get(serve_archive_member))
             .route("/health", get(|| async { "OK" }))
             .layer(middleware::from_fn_with_state(state.clone(), security_middleware))
             .layer(cors)
@@ -419,6 +456,11 @@ impl SecureAssetStreamServer {
         self.state.cache_stats()
     }
 
+    /// Allow the members of an archive to be streamed
+    pub fn register_archive(&self, uuid: Uuid, path: PathBuf) {
+        self.state.register_archive(uuid, path);
+    }
+
     /// Get the thumbnail URL for a file
     pub fn get_thumbnail_url(&self, uuid: Uuid) -> String {
         format!(
@@ -448,4 +490,23 @@ impl SecureAssetStreamServer {
             self.state.session_token
         )
     }
+
+    /// Get the URL of an archive member (`docs/spec.md`)
+    pub fn get_archive_member_url(&self, uuid: Uuid, member: &str) -> String {
+        self.state.archive_member_url(uuid, member)
+    }
+}
+
+/// Percent-encode a member path for a URL, keeping its `/` separators
+fn encode_member_path(member: &str) -> String {
+    let mut encoded = String::with_capacity(member.len());
+    for byte in member.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
 }
@@ -59,6 +59,16 @@ mod unit_tests {
         assert!(file_url.contains("file"));
     }
 
+    #[test]
+    fn test_archive_member_url() {
+        let server = SecureAssetStreamServer::new(19283);
+        let uuid = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap();
+        server.register_archive(uuid, std::path::PathBuf::from("/data/project-2023.zip"));
+
+        let url = server.get_archive_member_url(uuid, "docs/meeting notes#1.md");
+        assert!(url.contains(&format!("/archive/{}/docs/meeting%20notes%231.md?token=", uuid)));
+    }
+
     #[test]
     fn test_cache_stats() {
         let server = SecureAssetStreamServer::new(19283);
@@ -14,9 +14,10 @@ use std::sync::Arc;
 use tauri::State;
 use tokio::sync::RwLock;
 
+use crate::commands::onboarding::IndexingState;
 use crate::config::{
     ConfigStore, ConfigStoreConfig, AppConfig, CloudConfig, 
-    PerformanceConfig, PrivacyConfig, UIConfig,
+    IndexingBudget, PerformanceConfig, PrivacyConfig, UIConfig,
 };
 
 /// Application state containing the config store
@@ -107,6 +108,9 @@ pub struct PerformanceConfigDto {
     pub enable_cuda: bool,
     /// Enable fast inference mode
     pub fast_inference_mode: bool,
+    /// Resource budgets for background indexing
+    #[serde(default)]
+    pub indexing_budget: IndexingBudget,
 }
 
 /// Privacy config DTO
@@ -209,8 +213,15 @@ pub struct ConfigOperationResult {
 
 /// Initialize config state
 #[tauri::command]
-pub async fn init_config(state: State<'_, ConfigState>) -> Result<(), String> {
-    state.initialize().await
+pub async fn init_config(
+    state: State<'_, ConfigState>,
+    indexing: State<'_, IndexingState>,
+) -> Result<(), String> {
+    state.initialize().await?;
+
+    let store = state.get_store().await?;
+    indexing.apply_performance_config(&store.get().await.performance);
+    Ok(())
 }
 
 /// Get current application configuration
@@ -232,6 +243,7 @@ pub async fn get_config(state: State<'_, ConfigState>) -> Result<AppConfigDto, S
 #[tauri::command]
 pub async fn set_config(
     state: State<'_, ConfigState>,
+    indexing: State<'_, IndexingState>,
     request: UpdateConfigRequest,
 ) -> Result<ConfigOperationResult, String> {
     let store_guard = state.store.read().await;
@@ -280,6 +292,7 @@ pub async fn set_config(
             config.performance.embedding_batch_size = perf.embedding_batch_size;
             config.performance.enable_cuda = perf.enable_cuda;
             config.performance.fast_inference_mode = perf.fast_inference_mode;
+            config.performance.indexing_budget = perf.indexing_budget.clone();
         }
 
         if let Some(privacy) = &request.privacy {
@@ -307,6 +320,8 @@ pub async fn set_config(
         }
     }).await.map_err(|e| e.to_string())?;
 
+    indexing.apply_performance_config(&updated.performance);
+
     Ok(ConfigOperationResult {
         success: true,
         message: "Configuration updated successfully".to_string(),
@@ -530,6 +545,7 @@ pub async fn export_config(
 #[tauri::command]
 pub async fn import_config(
     state: State<'_, ConfigState>,
+    indexing: State<'_, IndexingState>,
     path: String,
 ) -> Result<ConfigOperationResult, String> {
     let path_buf = PathBuf::from(&path);
@@ -552,6 +568,8 @@ pub async fn import_config(
         .await
         .map_err(|e| e.to_string())?;
 
+    indexing.apply_performance_config(&imported.performance);
+
     Ok(ConfigOperationResult {
         success: true,
         message: "Configuration imported successfully".to_string(),
@@ -563,6 +581,7 @@ pub async fn import_config(
 #[tauri::command]
 pub async fn reset_config(
     state: State<'_, ConfigState>,
+    indexing: State<'_, IndexingState>,
 ) -> Result<ConfigOperationResult, String> {
     let store_guard = state.store.read().await;
     
@@ -578,6 +597,8 @@ pub async fn reset_config(
         .await
         .map_err(|e| e.to_string())?;
 
+    indexing.apply_performance_config(&reset.performance);
+
     Ok(ConfigOperationResult {
         success: true,
         message: "Configuration reset to defaults".to_string(),
@@ -607,6 +628,7 @@ pub async fn list_config_backups(
 #[tauri::command]
 pub async fn restore_config_backup(
     state: State<'_, ConfigState>,
+    indexing: State<'_, IndexingState>,
     backup_path: String,
 ) -> Result<ConfigOperationResult, String> {
     let path_buf = PathBuf::from(&backup_path);
@@ -629,6 +651,8 @@ pub async fn restore_config_backup(
         .await
         .map_err(|e| e.to_string())?;
 
+    indexing.apply_performance_config(&restored.performance);
+
     Ok(ConfigOperationResult {
         success: true,
         message: "Configuration restored from backup".to_string(),
@@ -672,6 +696,7 @@ fn performance_config_to_dto(config: &PerformanceConfig) -> PerformanceConfigDto
         embedding_batch_size: config.embedding_batch_size,
         enable_cuda: config.enable_cuda,
         fast_inference_mode: config.fast_inference_mode,
+        indexing_budget: config.indexing_budget.clone(),
     }
 }
 
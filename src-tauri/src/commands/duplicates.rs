@@ -0,0 +1,159 @@
+//! Duplicate Commands for NeuralFS
+//!
+//! Provides Tauri commands for reviewing duplicate files:
+//! - list_duplicate_groups: List groups of duplicate and near-duplicate files
+//! - get_duplicate_detection_status: State of the background detection run
+//! - resolve_duplicates: Keep one file of a group and link the others to it
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tauri::State;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::onboarding::IndexingState;
+use crate::dedup::{DetectionReport, DuplicateGroup, DuplicateService};
+
+/// State of background duplicate detection
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DuplicateDetectionStatus {
+    /// Whether a detection run is in progress
+    pub running: bool,
+    /// Outcome of the last finished run
+    pub last_report: Option<DetectionReport>,
+    /// Error of the last finished run, if it failed
+    pub last_error: Option<String>,
+    /// When the last run finished
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Runs duplicate detection in the background, one run at a time
+#[derive(Clone, Default)]
+pub struct DuplicateDetectionState {
+    status: Arc<RwLock<DuplicateDetectionStatus>>,
+}
+
+impl DuplicateDetectionState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a detection run unless one is in progress
+    ///
+    /// Returns whether a run was started.
+    pub async fn start(&self, service: DuplicateService) -> bool {
+        {
+            let mut status = self.status.write().await;
+            if status.running {
+                return false;
+            }
+            status.running = true;
+        }
+
+        let status = self.status.clone();
+        tokio::spawn(async move {
+            let result = service.detect().await;
+            let mut status = status.write().await;
+            status.running = false;
+            status.finished_at = Some(Utc::now());
+            match result {
+                Ok(report) => {
+                    for (path, error) in &report.errors {
+                        tracing::debug!("Skipped {:?} during duplicate detection: {}", path, error);
+                    }
+                    status.last_report = Some(report);
+                    status.last_error = None;
+                }
+                Err(e) => {
+                    tracing::warn!("Duplicate detection failed: {}", e);
+                    status.last_error = Some(e.to_string());
+                }
+            }
+        });
+        true
+    }
+
+    /// Current detection state
+    pub async fn status(&self) -> DuplicateDetectionStatus {
+        self.status.read().await.clone()
+    }
+}
+
+/// List groups of duplicate and near-duplicate files
+///
+/// Detection runs in the background; poll `get_duplicate_detection_status`
+/// and list again once it has finished.
+///
+/// # Arguments
+/// * `refresh` - Start a detection run over the index
+/// * `include_resolved` - Also list groups that were already resolved
+///
+/// # Returns
+/// Groups stored so far, open ones first, largest reclaimable space first
+#[tauri::command]
+pub async fn list_duplicate_groups(
+    indexing: State<'_, IndexingState>,
+    detection: State<'_, DuplicateDetectionState>,
+    refresh: Option<bool>,
+    include_resolved: Option<bool>,
+) -> Result<Vec<DuplicateGroup>, String> {
+    let Some(pipeline) = indexing.pipeline().await else {
+        return Ok(vec![]);
+    };
+
+    if refresh.unwrap_or(false) {
+        let service = DuplicateService::new(pipeline.db().clone())
+            .with_vectors(&pipeline.collections().active());
+        if !detection.start(service).await {
+            tracing::debug!("Duplicate detection already running");
+        }
+    }
+
+    DuplicateService::new(pipeline.db().clone())
+        .groups(include_resolved.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get the state of background duplicate detection
+///
+/// # Returns
+/// Whether a run is in progress and the outcome of the last one
+#[tauri::command]
+pub async fn get_duplicate_detection_status(
+    detection: State<'_, DuplicateDetectionState>,
+) -> Result<DuplicateDetectionStatus, String> {
+    Ok(detection.status().await)
+}
+
+/// Keep one file of a duplicate group and link the others to it
+///
+/// The other members get a `duplicate` relation from the kept file; no
+/// file is deleted. Search collapses the group onto the kept file.
+///
+/// # Arguments
+/// * `group_id` - Duplicate group ID
+/// * `keep_file_id` - Member to keep
+///
+/// # Returns
+/// The resolved group, or `None` if it no longer exists
+#[tauri::command]
+pub async fn resolve_duplicates(
+    indexing: State<'_, IndexingState>,
+    group_id: String,
+    keep_file_id: String,
+) -> Result<Option<DuplicateGroup>, String> {
+    let group_id = Uuid::parse_str(&group_id).map_err(|e| format!("Invalid group ID: {}", e))?;
+    let keep_file_id = Uuid::parse_str(&keep_file_id).map_err(|e| format!("Invalid file ID: {}", e))?;
+    let pipeline = indexing
+        .pipeline()
+        .await
+        .ok_or_else(|| "Indexing is not running".to_string())?;
+
+    DuplicateService::new(pipeline.db().clone())
+        .resolve(group_id, keep_file_id)
+        .await
+        .map_err(|e| e.to_string())
+}
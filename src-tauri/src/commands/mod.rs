@@ -6,8 +6,12 @@
 //! - Tag commands (get_tags, add_tag, remove_tag, confirm_tag, reject_tag)
 //! - Relation commands (get_relations, confirm_relation, reject_relation, block_relation)
 //! - Config commands (get_config, set_config, get_cloud_status, set_cloud_enabled)
-//! - Status commands (get_index_status, get_system_status, get_dead_letter_tasks, retry_dead_letter)
-//! - Protocol commands (get_session_token, build_thumbnail_url, build_preview_url, build_file_url)
+//! - Status commands (get_index_status, get_system_status, get_dead_letter_tasks, retry_dead_letter,
+//!   get_move_candidates, resolve_move_candidate, preview_reconcile, get_held_deletions,
+//!   confirm_held_deletions, get_watch_modes)
+//! - Duplicate commands (list_duplicate_groups, get_duplicate_detection_status, resolve_duplicates)
+//! - Protocol commands (get_session_token, build_thumbnail_url, build_preview_url, build_file_url,
+//!   build_archive_member_url)
 //! - Onboarding commands (check_first_launch, get_suggested_directories, save_onboarding_config, etc.)
 
 pub mod search;
@@ -15,6 +19,7 @@ pub mod tags;
 pub mod relations;
 pub mod config;
 pub mod status;
+pub mod duplicates;
 pub mod protocol;
 pub mod onboarding;
 
@@ -23,5 +28,6 @@ pub use tags::*;
 pub use relations::*;
 pub use config::*;
 pub use status::*;
+pub use duplicates::*;
 pub use protocol::*;
 pub use onboarding::*;
@@ -12,13 +12,30 @@
 //! **Validates: Requirements 17.1, 17.2, 17.3, 17.4, 17.5**
 
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use tauri::api::dialog::FileDialogBuilder;
-use tokio::sync::RwLock;
+use tauri::State;
+use tokio::sync::{mpsc, RwLock};
 
 use crate::core::config::AppConfig;
+use crate::db::{create_database_pool, migration::MigrationManager, DatabaseConfig};
+use crate::embeddings::{EmbeddingConfig, EmbeddingEngine, TextEmbeddingConfig};
+use crate::config::PerformanceConfig;
+use crate::indexer::{
+    CatchUp, ImageSpace, IndexProgress, IndexerConfig, IndexingPipeline, ModelMigrator, ResilientBatchIndexer,
+    ResourceScheduler, TaskStore,
+};
+use crate::logging::MetricsCollector;
+use crate::parser::ContentParserService;
+use crate::reconcile::{HeldDeletions, ReconcileResult, ReconciliationService};
+use crate::search::{TextIndex, TextIndexConfig};
+use crate::vector::{VectorSpace, VectorStore, VectorStoreConfig};
+use crate::watcher::{
+    DirectoryFilter, DirectoryFilterConfig, EventBatch, EventJournal, FileWatcher, FileWatcherConfig, JournalRecovery,
+    RootWatchStatus,
+};
 
 /// Directory suggestion for onboarding
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -102,6 +119,351 @@ struct ScanState {
     files_per_second: f64,
 }
 
+/// Indexing services used by the initial scan and status commands
+///
+/// The pipeline is built on first use, under the application data
+/// directory, so the rest of startup does not wait on opening stores.
+#[derive(Clone)]
+pub struct IndexingState {
+    pipeline: Arc<RwLock<Option<Arc<IndexingPipeline>>>>,
+    migrator: Arc<RwLock<Option<Arc<ModelMigrator>>>>,
+    scheduler: Arc<ResourceScheduler>,
+    progress: Arc<IndexProgress>,
+    /// What the event journal held when opened, until caught up
+    recovery: Arc<tokio::sync::Mutex<Option<JournalRecovery>>>,
+    /// Feeds watcher batches to the background worker, once started
+    batches: Arc<tokio::sync::Mutex<Option<mpsc::Sender<EventBatch>>>>,
+    /// Watcher of the monitored roots, once started
+    watcher: Arc<tokio::sync::Mutex<Option<FileWatcher>>>,
+}
+
+impl IndexingState {
+    pub fn new() -> Self {
+        Self {
+            pipeline: Arc::new(RwLock::new(None)),
+            migrator: Arc::new(RwLock::new(None)),
+            scheduler: Arc::new(ResourceScheduler::from_config(
+                &PerformanceConfig::default(),
+                IndexerConfig::default().batch_size,
+            )),
+            progress: Arc::new(IndexProgress::new()),
+            recovery: Arc::new(tokio::sync::Mutex::new(None)),
+            batches: Arc::new(tokio::sync::Mutex::new(None)),
+            watcher: Arc::new(tokio::sync::Mutex::new(None)),
+        }
+    }
+
+    /// Use an already constructed pipeline
+    pub fn with_pipeline(pipeline: Arc<IndexingPipeline>) -> Self {
+        let scheduler = pipeline
+            .scheduler()
+            .cloned()
+            .unwrap_or_else(|| Self::new().scheduler);
+        let progress = pipeline.progress().clone();
+        Self {
+            pipeline: Arc::new(RwLock::new(Some(pipeline))),
+            migrator: Arc::new(RwLock::new(None)),
+            scheduler,
+            progress,
+            recovery: Arc::new(tokio::sync::Mutex::new(None)),
+            batches: Arc::new(tokio::sync::Mutex::new(None)),
+            watcher: Arc::new(tokio::sync::Mutex::new(None)),
+        }
+    }
+
+    /// Pause indexing whenever `flag` is set, e.g.
+    /// `GameModeController::indexer_paused_flag`
+    pub fn with_pause_flag(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.scheduler = Arc::new(
+            ResourceScheduler::from_config(&PerformanceConfig::default(), IndexerConfig::default().batch_size)
+                .with_pause_flag(flag),
+        );
+        self
+    }
+
+    /// Record stage timings into a shared metrics collector
+    pub fn with_metrics(mut self, metrics: Arc<MetricsCollector>) -> Self {
+        self.progress = Arc::new(IndexProgress::with_metrics(metrics));
+        self
+    }
+
+    /// Scheduler that paces the pipeline
+    pub fn scheduler(&self) -> &Arc<ResourceScheduler> {
+        &self.scheduler
+    }
+
+    /// Indexing progress per monitored root and stage
+    pub fn progress(&self) -> &Arc<IndexProgress> {
+        &self.progress
+    }
+
+    /// Apply performance settings, including indexing budgets
+    pub fn apply_performance_config(&self, config: &PerformanceConfig) {
+        self.scheduler.configure(config);
+    }
+
+    /// Get the pipeline if it has been initialized
+    pub async fn pipeline(&self) -> Option<Arc<IndexingPipeline>> {
+        self.pipeline.read().await.clone()
+    }
+
+    /// Get the embedding model migrator if the pipeline has been initialized
+    pub async fn migrator(&self) -> Option<Arc<ModelMigrator>> {
+        self.migrator.read().await.clone()
+    }
+
+    /// Get the pipeline, opening the database and stores under `data_dir`
+    /// on first use and restoring tasks persisted by the previous run.
+    /// Starts re-embedding in the background if the embedding model changed.
+    pub async fn get_or_init(&self, data_dir: &Path) -> Result<Arc<IndexingPipeline>, String> {
+        let mut slot = self.pipeline.write().await;
+        if let Some(ref pipeline) = *slot {
+            return Ok(pipeline.clone());
+        }
+
+        tokio::fs::create_dir_all(data_dir)
+            .await
+            .map_err(|e| format!("Failed to create data directory: {}", e))?;
+
+        let db_config = DatabaseConfig::with_path(data_dir.join("neuralfs.db")).with_wal(true);
+        let db = create_database_pool(&db_config)
+            .await
+            .map_err(|e| format!("Failed to open database: {}", e))?;
+        MigrationManager::new(db.clone())
+            .run_migrations()
+            .await
+            .map_err(|e| format!("Failed to migrate database: {}", e))?;
+
+        let text_index = TextIndex::new(TextIndexConfig {
+            index_path: data_dir.join("text_index"),
+            ..Default::default()
+        })
+        .map_err(|e| format!("Failed to open text index: {}", e))?;
+
+        // A text model other than the active collection's starts a migration
+        let embedder = Arc::new(EmbeddingEngine::new(EmbeddingConfig {
+            models_dir: data_dir.join("models"),
+            text_config: saved_text_model().unwrap_or_default(),
+            ..Default::default()
+        }));
+
+        let migrator = Arc::new(
+            ModelMigrator::open(
+                db.clone(),
+                VectorStoreConfig::default()
+                    .with_storage_path(data_dir.join("vectors").to_string_lossy().to_string()),
+                embedder.clone(),
+            )
+            .await
+            .map_err(|e| format!("Failed to open vector store: {}", e))?
+            .with_scheduler(self.scheduler.clone()),
+        );
+
+        let image_store = VectorStore::new(
+            VectorStoreConfig::for_space(VectorSpace::Image)
+                .with_storage_path(data_dir.join("image_vectors").to_string_lossy().to_string()),
+        )
+        .await
+        .map_err(|e| format!("Failed to open image vector store: {}", e))?;
+        let image_space = ImageSpace::new(Arc::new(image_store), embedder.clone());
+
+        let (journal, recovery) = EventJournal::open(data_dir.join("watcher.journal"))
+            .map_err(|e| format!("Failed to open event journal: {}", e))?;
+
+        let pipeline = IndexingPipeline::new(
+            db.clone(),
+            Arc::new(ResilientBatchIndexer::with_defaults().with_store(TaskStore::new(db.clone()))),
+            Arc::new(ContentParserService::new()),
+            embedder,
+            migrator.collections().active().store,
+            Arc::new(text_index),
+        )
+        .map_err(|e| format!("Failed to create indexing pipeline: {}", e))?
+        .with_collections(migrator.collections().clone())
+        .with_image_space(image_space)
+        .with_scheduler(self.scheduler.clone())
+        .with_progress(self.progress.clone())
+        .with_journal(Arc::new(journal));
+        let pipeline = match DirectoryFilter::new(DirectoryFilterConfig::default()) {
+            Ok(filter) => pipeline.with_filter(Arc::new(filter)),
+            Err(e) => {
+                tracing::warn!("Indexing directories without directory filter: {}", e);
+                pipeline
+            }
+        };
+        let guard = Arc::new(default_reconciler(&pipeline));
+        let pipeline = pipeline.with_deletion_guard(guard);
+        let pipeline = Arc::new(pipeline);
+        pipeline
+            .restore_tasks()
+            .await
+            .map_err(|e| format!("Failed to restore indexing tasks: {}", e))?;
+
+        if migrator.is_migrating() {
+            migrator.clone().spawn(pipeline.config().poll_interval);
+        }
+        *self.migrator.write().await = Some(migrator);
+        *self.recovery.lock().await = Some(recovery);
+
+        *slot = Some(pipeline.clone());
+        Ok(pipeline)
+    }
+
+    /// Catch up on changes the previous run received but did not apply
+    ///
+    /// Replays the event journal if it can be trusted, otherwise reconciles
+    /// `roots` with a full scan. Does nothing once caught up.
+    pub async fn catch_up(&self, data_dir: &Path, roots: &[PathBuf]) -> Result<Option<CatchUp>, String> {
+        let pipeline = self.get_or_init(data_dir).await?;
+        let Some(recovery) = self.recovery.lock().await.take() else {
+            return Ok(None);
+        };
+
+        // Journaled deletions count against the same roots as live ones
+        pipeline.set_monitored_roots(roots);
+        let caught_up = pipeline
+            .catch_up(recovery, &default_reconciler(&pipeline), roots)
+            .await
+            .map_err(|e| format!("Failed to catch up on file changes: {}", e))?;
+        Ok(Some(caught_up))
+    }
+
+    /// Deletions held back for confirmation, by reconciliation or the
+    /// watcher, including those of earlier runs
+    pub async fn held_deletions(&self, data_dir: &Path) -> Result<Vec<HeldDeletions>, String> {
+        let pipeline = self.get_or_init(data_dir).await?;
+        default_reconciler(&pipeline)
+            .held_deletions()
+            .await
+            .map_err(|e| format!("Failed to load held deletions: {}", e))
+    }
+
+    /// Report what reconciling `roots` would change, without changing anything
+    pub async fn preview_reconcile(&self, data_dir: &Path, roots: &[PathBuf]) -> Result<ReconcileResult, String> {
+        let pipeline = self.get_or_init(data_dir).await?;
+        default_reconciler(&pipeline)
+            .reconcile_dry_run(roots)
+            .await
+            .map_err(|e| format!("Failed to preview reconciliation: {}", e))
+    }
+
+    /// Apply the held deletions of `root` the user reviewed and confirmed
+    ///
+    /// Only `paths` are deleted; files among them that reappeared are kept.
+    /// Returns the number of files removed from the index.
+    pub async fn confirm_deletions(&self, data_dir: &Path, root: &Path, paths: &[PathBuf]) -> Result<usize, String> {
+        let pipeline = self.get_or_init(data_dir).await?;
+        let result = default_reconciler(&pipeline)
+            .confirm_held_deletions(root, paths)
+            .await
+            .map_err(|e| format!("Failed to confirm deletions in {:?}: {}", root, e))?;
+        let report = pipeline
+            .handle_reconcile(&result)
+            .await
+            .map_err(|e| format!("Failed to apply deletions in {:?}: {}", root, e))?;
+
+        Ok(report.removed)
+    }
+
+    /// Start the background worker that runs queued tasks for the rest of
+    /// the session, including tasks waiting on a retry delay or a pause
+    ///
+    /// Does nothing if the worker is already running.
+    pub async fn start_worker(&self, data_dir: &Path) -> Result<(), String> {
+        let pipeline = self.get_or_init(data_dir).await?;
+        let mut slot = self.batches.lock().await;
+        if slot.is_none() {
+            let (tx, rx) = mpsc::channel(WORKER_CHANNEL_SIZE);
+            pipeline.spawn(rx);
+            *slot = Some(tx);
+        }
+        Ok(())
+    }
+
+    /// Sender that hands batches to the background worker, if it is running
+    pub async fn batch_sender(&self) -> Option<mpsc::Sender<EventBatch>> {
+        self.batches.lock().await.clone()
+    }
+
+    /// Watch `roots` for changes and apply them in the background worker
+    ///
+    /// Starts the worker and the watcher on first use; later calls add the
+    /// roots that are not watched yet.
+    pub async fn watch_roots(&self, data_dir: &Path, roots: &[PathBuf]) -> Result<(), String> {
+        self.start_worker(data_dir).await?;
+        let pipeline = self.get_or_init(data_dir).await?;
+        let Some(sender) = self.batch_sender().await else {
+            return Ok(());
+        };
+
+        let mut slot = self.watcher.lock().await;
+        let mut monitored = match *slot {
+            Some(ref watcher) => watcher.watched_directories().await,
+            None => Vec::new(),
+        };
+        monitored.extend(roots.iter().cloned());
+        pipeline.set_monitored_roots(&monitored);
+        if let Some(ref mut watcher) = *slot {
+            let watched = watcher.watched_directories().await;
+            for root in roots.iter().filter(|root| !watched.contains(root)) {
+                watcher
+                    .add_watch(root)
+                    .await
+                    .map_err(|e| format!("Failed to watch {:?}: {}", root, e))?;
+            }
+            return Ok(());
+        }
+
+        let filter = DirectoryFilter::new(DirectoryFilterConfig::default())
+            .map_err(|e| format!("Failed to create directory filter: {}", e))?;
+        let mut watcher = FileWatcher::with_sender(filter, FileWatcherConfig::default(), sender)
+            .with_identity_source(pipeline.clone());
+        if let Some(journal) = pipeline.journal() {
+            watcher = watcher.with_journal(journal.clone());
+        }
+        watcher
+            .start(roots.to_vec())
+            .await
+            .map_err(|e| format!("Failed to start file watcher: {}", e))?;
+        *slot = Some(watcher);
+        Ok(())
+    }
+
+    /// How each watched root is monitored, natively or by polling
+    pub async fn watch_modes(&self) -> Vec<RootWatchStatus> {
+        match *self.watcher.lock().await {
+            Some(ref watcher) => watcher.watch_modes().await,
+            None => Vec::new(),
+        }
+    }
+
+    /// Forget the journal state, e.g. after a full scan made it irrelevant
+    async fn take_recovery(&self) -> Option<JournalRecovery> {
+        self.recovery.lock().await.take()
+    }
+}
+
+/// Watcher batches buffered for the background worker
+const WORKER_CHANNEL_SIZE: usize = 64;
+
+/// Reconciler over the pipeline's database with the default directory filter
+fn default_reconciler(pipeline: &IndexingPipeline) -> ReconciliationService {
+    let reconciler = ReconciliationService::new(pipeline.db().clone());
+    match DirectoryFilter::new(DirectoryFilterConfig::default()) {
+        Ok(filter) => reconciler.with_filter(filter),
+        Err(e) => {
+            tracing::warn!("Scanning without directory filter: {}", e);
+            reconciler
+        }
+    }
+}
+
+impl Default for IndexingState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Check if this is the first launch (onboarding needed)
 ///
 /// Returns true if the user has not completed onboarding yet.
@@ -347,12 +709,20 @@ pub async fn save_onboarding_config(
 
 /// Start initial directory scan
 ///
-/// Begins scanning the specified directories in the background.
+/// Reconciles the specified directories against the index and runs the
+/// indexing pipeline over new and changed files in the background.
 ///
 /// # Arguments
 /// * `directories` - List of directories to scan
 #[tauri::command]
-pub async fn start_initial_scan(directories: Vec<String>) -> Result<(), String> {
+pub async fn start_initial_scan(
+    indexing: State<'_, IndexingState>,
+    directories: Vec<String>,
+) -> Result<(), String> {
+    let pipeline = indexing.get_or_init(&default_data_dir()).await?;
+    let dirs: Vec<PathBuf> = directories.iter().map(PathBuf::from).collect();
+    indexing.watch_roots(&default_data_dir(), &dirs).await?;
+
     // Reset scan state
     {
         let mut state = SCAN_STATE.write().await;
@@ -366,33 +736,71 @@ pub async fn start_initial_scan(directories: Vec<String>) -> Result<(), String>
     }
     
     // Spawn background task to perform scanning
-    // In production, this would use the actual indexer
-    let dirs = directories.clone();
+    pipeline.progress().set_roots(dirs.clone());
+    // A full scan supersedes whatever the journal held
+    indexing.take_recovery().await;
     tokio::spawn(async move {
         tracing::info!("Starting initial scan of {} directories", dirs.len());
+        let scan_start = std::time::Instant::now();
+
+        // Reconcile directories against the database to find what needs indexing
+        let reconciler = default_reconciler(&pipeline);
+        let journaled = pipeline.journal().map(|journal| journal.last_seq());
+
+        let submitted = match reconciler.reconcile_on_startup(&dirs).await {
+            Ok(result) => {
+                if let (Some(journal), Some(through)) = (pipeline.journal().cloned(), journaled) {
+                    match tokio::task::spawn_blocking(move || journal.mark_reconciled(through)).await {
+                        Ok(Ok(())) => {}
+                        Ok(Err(e)) => tracing::warn!("Failed to record scan in event journal: {}", e),
+                        Err(e) => tracing::warn!("Failed to record scan in event journal: {}", e),
+                    }
+                }
+                let found: Vec<PathBuf> = result.added.iter().chain(result.modified.iter()).cloned().collect();
+                pipeline.progress().record_scan(&found, scan_start.elapsed());
+                match pipeline.handle_reconcile(&result).await {
+                    Ok(report) => report.submitted as u64,
+                    Err(e) => {
+                        tracing::error!("Failed to queue scanned files: {}", e);
+                        0
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::error!("Scan error: {}", e);
+                0
+            }
+        };
         
-        // Count total files first
-        let mut total = 0u64;
-        for dir in &dirs {
-            let count = count_files_recursive(&PathBuf::from(dir)).unwrap_or(0);
-            tracing::debug!("Directory {} contains {} files", dir, count);
-            total += count;
-        }
-        
-        tracing::info!("Total files to scan: {}", total);
+        tracing::info!("Total files to index: {}", submitted);
         
         {
             let mut state = SCAN_STATE.write().await;
-            state.total_files = total;
+            state.total_files = submitted;
         }
         
         // Process files
         let mut processed = 0u64;
-        let scan_start = std::time::Instant::now();
-        
-        for dir in &dirs {
-            if let Err(e) = scan_directory_recursive(&PathBuf::from(dir), &mut processed, scan_start).await {
-                tracing::error!("Scan error for {}: {}", dir, e);
+        loop {
+            let report = pipeline.run_ready_batch().await;
+            if report.processed() == 0 {
+                // Wait out a pause rather than ending the scan early
+                if pipeline.is_paused() && pipeline.indexer().pending_count().await > 0 {
+                    tokio::time::sleep(pipeline.config().poll_interval).await;
+                    continue;
+                }
+                break;
+            }
+            processed += report.processed() as u64;
+
+            let mut state = SCAN_STATE.write().await;
+            state.processed_files = processed.min(state.total_files);
+            state.current_file = report.last_path.map(|p| p.to_string_lossy().to_string());
+
+            // Calculate files per second
+            let elapsed = scan_start.elapsed().as_secs_f64();
+            if elapsed > 0.0 {
+                state.files_per_second = processed as f64 / elapsed;
             }
         }
         
@@ -402,7 +810,7 @@ pub async fn start_initial_scan(directories: Vec<String>) -> Result<(), String>
             state.is_scanning = false;
             state.is_complete = true;
             state.current_file = None;
-            state.processed_files = processed;
+            state.processed_files = state.total_files;
         }
         
         let duration = scan_start.elapsed();
@@ -459,6 +867,32 @@ pub async fn complete_onboarding() -> Result<(), String> {
     Ok(())
 }
 
+/// Monitored directories saved by onboarding, if any
+pub fn saved_monitored_directories() -> Vec<PathBuf> {
+    std::fs::read_to_string(get_config_path())
+        .ok()
+        .and_then(|content| serde_json::from_str::<AppConfig>(&content).ok())
+        .map(|config| config.monitored_directories)
+        .unwrap_or_default()
+}
+
+/// Text embedding model chosen in the saved settings, if any
+fn saved_text_model() -> Option<TextEmbeddingConfig> {
+    let content = std::fs::read_to_string(get_config_path()).ok()?;
+    serde_json::from_str::<crate::config::AppConfig>(&content)
+        .ok()?
+        .embedding
+        .text_model
+}
+
+/// Application data directory holding the database and index stores
+pub fn default_data_dir() -> PathBuf {
+    get_config_path()
+        .parent()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
 // Helper functions
 
 fn get_config_path() -> PathBuf {
@@ -480,118 +914,6 @@ fn get_config_path() -> PathBuf {
     PathBuf::from("config.json")
 }
 
-fn count_files_recursive(path: &PathBuf) -> Result<u64, std::io::Error> {
-    let mut count = 0u64;
-    
-    if path.is_file() {
-        return Ok(1);
-    }
-    
-    if path.is_dir() {
-        let entries = match std::fs::read_dir(path) {
-            Ok(entries) => entries,
-            Err(e) => {
-                tracing::warn!("Cannot read directory {}: {}", path.display(), e);
-                return Ok(0);
-            }
-        };
-        
-        for entry in entries {
-            let entry = match entry {
-                Ok(e) => e,
-                Err(_) => continue,
-            };
-            let entry_path = entry.path();
-            
-            // Skip hidden files and common excluded directories
-            if let Some(name) = entry_path.file_name() {
-                let name_str = name.to_string_lossy();
-                if name_str.starts_with('.') || 
-                   name_str == "node_modules" || 
-                   name_str == "__pycache__" ||
-                   name_str == "target" ||
-                   name_str == ".git" ||
-                   name_str == "venv" ||
-                   name_str == ".venv" {
-                    continue;
-                }
-            }
-            
-            if entry_path.is_file() {
-                count += 1;
-            } else if entry_path.is_dir() {
-                count += count_files_recursive(&entry_path).unwrap_or(0);
-            }
-        }
-    }
-    
-    Ok(count)
-}
-
-async fn scan_directory_recursive(
-    path: &PathBuf, 
-    processed: &mut u64,
-    start_time: std::time::Instant,
-) -> Result<(), std::io::Error> {
-    if path.is_file() {
-        // Update progress
-        {
-            let mut state = SCAN_STATE.write().await;
-            state.processed_files = *processed;
-            state.current_file = Some(path.to_string_lossy().to_string());
-            
-            // Calculate files per second
-            let elapsed = start_time.elapsed().as_secs_f64();
-            if elapsed > 0.0 {
-                state.files_per_second = *processed as f64 / elapsed;
-            }
-        }
-        *processed += 1;
-        
-        // In production, this would:
-        // 1. Extract file metadata
-        // 2. Parse content based on file type
-        // 3. Generate embeddings
-        // 4. Store in database and vector store
-        
-        // Simulate minimal processing time (actual indexing would be slower)
-        tokio::time::sleep(tokio::time::Duration::from_micros(100)).await;
-        return Ok(());
-    }
-    
-    if path.is_dir() {
-        let entries: Vec<_> = match std::fs::read_dir(path) {
-            Ok(entries) => entries.filter_map(|e| e.ok()).collect(),
-            Err(e) => {
-                tracing::warn!("Cannot read directory {}: {}", path.display(), e);
-                return Ok(());
-            }
-        };
-        
-        for entry in entries {
-            let entry_path = entry.path();
-            
-            // Skip hidden files and common excluded directories
-            if let Some(name) = entry_path.file_name() {
-                let name_str = name.to_string_lossy();
-                if name_str.starts_with('.') || 
-                   name_str == "node_modules" || 
-                   name_str == "__pycache__" ||
-                   name_str == "target" ||
-                   name_str == ".git" ||
-                   name_str == "venv" ||
-                   name_str == ".venv" {
-                    continue;
-                }
-            }
-            
-            Box::pin(scan_directory_recursive(&entry_path, processed, start_time)).await?;
-        }
-    }
-    
-    Ok(())
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -626,6 +948,16 @@ mod tests {
         assert!(!progress.is_scanning || progress.is_complete);
     }
 
+    #[test]
+    fn test_pause_flag_reaches_scheduler() {
+        let flag = Arc::new(AtomicBool::new(false));
+        let indexing = IndexingState::new().with_pause_flag(flag.clone());
+        assert_ne!(indexing.scheduler().plan().mode, crate::indexer::SchedulerMode::Paused);
+
+        flag.store(true, Ordering::SeqCst);
+        assert_eq!(indexing.scheduler().plan().mode, crate::indexer::SchedulerMode::Paused);
+    }
+
     #[tokio::test]
     async fn test_complete_onboarding() {
         ONBOARDING_COMPLETE.store(false, Ordering::SeqCst);
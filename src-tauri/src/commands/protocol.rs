@@ -3,8 +3,14 @@
 //! This module provides commands for the frontend to interact with the
 //! custom protocol system, including session token retrieval and URL building.
 
+use tauri::State;
 use uuid::Uuid;
-use crate::protocol::{ProtocolState, SessionTokenResponse, build_thumbnail_url, build_preview_url, build_file_url};
+use crate::commands::onboarding::IndexingState;
+use crate::core::types::file::FileType;
+use crate::protocol::{
+    ProtocolState, SessionTokenResponse, build_thumbnail_url, build_preview_url, build_file_url,
+    build_archive_member_url,
+};
 
 /// Get the session token for authenticating asset requests.
 ///
@@ -101,6 +107,48 @@ pub fn build_file_url_cmd(
     Ok(build_file_url(&state, uuid, use_protocol))
 }
 
+/// Build the URL of a member of an indexed archive.
+///
+/// Registers the archive with the asset server so its members can be
+/// streamed. Members are served over HTTP only.
+///
+/// # Arguments
+///
+/// * `uuid` - The archive's file UUID
+/// * `member` - Member path within the archive (`docs/spec.md`), as in a
+///   passage's `location.member`
+///
+/// # Returns
+///
+/// The complete URL with session token included.
+#[tauri::command]
+pub async fn build_archive_member_url_cmd(
+    state: State<'_, ProtocolState>,
+    indexing: State<'_, IndexingState>,
+    uuid: String,
+    member: String,
+) -> Result<String, String> {
+    let uuid = Uuid::parse_str(&uuid).map_err(|e| format!("Invalid UUID: {}", e))?;
+    let pipeline = indexing
+        .pipeline()
+        .await
+        .ok_or_else(|| "Indexing is not running".to_string())?;
+
+    let row: Option<(String, String)> = sqlx::query_as("SELECT path, file_type FROM files WHERE id = ?")
+        .bind(uuid.to_string())
+        .fetch_optional(pipeline.db())
+        .await
+        .map_err(|e| e.to_string())?;
+    let path = match row {
+        Some((path, file_type)) if file_type == format!("{:?}", FileType::Archive) => path,
+        Some(_) => return Err(format!("File {} is not an archive", uuid)),
+        None => return Err(format!("File {} is not indexed", uuid)),
+    };
+
+    state.asset_state.register_archive(uuid, std::path::PathBuf::from(path));
+    Ok(build_archive_member_url(&state, uuid, &member))
+}
+
 /// Get the asset server port.
 ///
 /// # Returns
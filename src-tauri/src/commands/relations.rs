@@ -348,6 +348,7 @@ fn parse_relation_type(type_str: &str) -> Result<RelationType, String> {
         "derivative" => Ok(RelationType::Derivative),
         "workflow" => Ok(RelationType::Workflow),
         "user_defined" | "custom" => Ok(RelationType::UserDefined),
+        "duplicate" => Ok(RelationType::Duplicate),
         _ => Err(format!("Unknown relation type: {}", type_str)),
     }
 }
@@ -362,6 +363,7 @@ fn relation_type_to_string(rel_type: &RelationType) -> String {
         RelationType::Derivative => "derivative".to_string(),
         RelationType::Workflow => "workflow".to_string(),
         RelationType::UserDefined => "user_defined".to_string(),
+        RelationType::Duplicate => "duplicate".to_string(),
     }
 }
 
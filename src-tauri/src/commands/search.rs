@@ -2,21 +2,119 @@
 //!
 //! Provides Tauri commands for semantic search functionality:
 //! - search_files: Execute semantic search with intent parsing
+//! - search_files_stream: Progressive search with results pushed over Tauri events
+//! - cancel_search: Cancel an in-flight streaming search
+//! - record_clarification: Remember the user's answer to a clarification question
 //! - get_search_suggestions: Get search suggestions based on partial query
 //!
 //! **Validates: Requirements 2.1, 2.2**
 
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use tauri::State;
 use uuid::Uuid;
 
-use crate::core::types::file::FileType;
+use crate::archive;
+use crate::core::types::chunk::ChunkLocation;
+use crate::core::types::file::{FileType, PrivacyLevel};
 use crate::core::types::search::{
     Pagination, SearchFilters, SearchIntent, SearchRequest, SearchResponse, SearchResult,
     SearchStatus, TimeRange, ResultSource,
 };
-use crate::search::intent::{IntentParser, IntentParseResult};
-use crate::search::hybrid::{HybridSearchEngine, QueryType, classify_query};
+use crate::commands::config::ConfigState;
+use crate::commands::onboarding::IndexingState;
+use crate::config::{AppConfig, CloudConfig, ConfigStoreConfig, SearchConfig};
+use crate::dedup::DuplicateService;
+use crate::embeddings::{EmbeddingConfig, EmbeddingEngine};
+use crate::indexer::IndexingPipeline;
+use crate::search::intent::{IntentCategory, IntentLexicon, IntentParser, IntentParseResult};
+use crate::search::hybrid::{
+    FileResult, HybridSearchConfig, HybridSearchEngine, HybridSearchFilters, QueryType,
+    ScoreExplanation, ScoredResult, SearchSource, classify_query, select_vector_space,
+};
+use crate::search::streaming::{
+    run_progressive_search, PhaseFuture, SearchCancellationRegistry, SearchPhase,
+};
+use crate::inference::{
+    CloudConfig as InferenceCloudConfig, CloudModelType, CloudUnderstanding, HybridInferenceEngine,
+    InferenceContext, InferenceOptions, InferenceRequest,
+};
+use crate::vector::VectorSpace;
+
+/// Prefix of the Tauri event carrying streaming search updates.
+/// The full event name is `search-stream:<request_id>`.
+pub const SEARCH_STREAM_EVENT_PREFIX: &str = "search-stream:";
+
+/// State for streaming searches
+pub struct SearchStreamState {
+    /// In-flight searches, for cancellation
+    pub registry: SearchCancellationRegistry,
+    /// Inference engine used for the cloud-refined phase
+    pub inference: Option<Arc<HybridInferenceEngine>>,
+}
+
+impl SearchStreamState {
+    pub fn new() -> Self {
+        Self {
+            registry: SearchCancellationRegistry::new(),
+            inference: None,
+        }
+    }
+
+    /// Attach the inference engine used for cloud refinement
+    pub fn with_inference_engine(mut self, engine: Arc<HybridInferenceEngine>) -> Self {
+        self.inference = Some(engine);
+        self
+    }
+}
+
+impl Default for SearchStreamState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Intent parser built from the saved search settings, shared by searches
+pub struct IntentParserState {
+    /// Parser and the `AppConfig::last_modified` it was built from
+    cached: std::sync::Mutex<Option<(String, Arc<IntentParser>)>>,
+}
+
+impl IntentParserState {
+    pub fn new() -> Self {
+        Self {
+            cached: std::sync::Mutex::new(None),
+        }
+    }
+
+    /// Parser for `config`, rebuilt only when the config changed since the
+    /// last call
+    ///
+    /// Every `ConfigStore` update stamps `last_modified`, so a changed
+    /// lexicon path, override or clarification answer invalidates the cache.
+    pub fn parser_for(&self, config: &AppConfig) -> Arc<IntentParser> {
+        let mut cached = self.cached.lock().unwrap();
+        if let Some((stamp, parser)) = cached.as_ref() {
+            if *stamp == config.last_modified {
+                return parser.clone();
+            }
+        }
+        let parser = Arc::new(intent_parser_from_config(&config.search));
+        *cached = Some((config.last_modified.clone(), parser.clone()));
+        parser
+    }
+}
+
+impl Default for IntentParserState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 /// Search request from frontend
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,6 +137,13 @@ pub struct SearchFilesRequest {
     pub offset: Option<u32>,
     /// Pagination limit
     pub limit: Option<u32>,
+    /// Return a per-result score explanation (relevance debugging)
+    pub explain: Option<bool>,
+    /// Show one result per group of duplicate files
+    pub collapse_duplicates: Option<bool>,
+    /// Client-chosen request ID (streaming only), so the client can
+    /// subscribe to the event before the first phase is emitted
+    pub request_id: Option<String>,
 }
 
 /// Time range DTO for frontend
@@ -69,6 +174,9 @@ pub struct SearchFilesResponse {
     pub intent: Option<IntentInfoDto>,
     /// Clarification suggestions if query is ambiguous
     pub clarifications: Option<Vec<ClarificationDto>>,
+    /// Ranking parameters for this query (only when `explain` was requested)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<SearchExplanationDto>,
 }
 
 /// Search result DTO for frontend
@@ -88,10 +196,123 @@ pub struct SearchResultDto {
     pub preview: Option<String>,
     /// Matched chunk ID (if segment-level result)
     pub chunk_id: Option<String>,
-    /// Result source (local_vector, local_tag, cloud_enhanced)
+    /// Result source (local_vector, local_keyword, cloud_enhanced)
     pub source: String,
     /// Associated tag names
     pub tags: Vec<String>,
+    /// Score breakdown (only when `explain` was requested)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<ResultExplanationDto>,
+    /// Best matching passages within the file, highest score first
+    #[serde(default)]
+    pub passages: Vec<PassageDto>,
+    /// IDs of duplicate files folded into this result
+    #[serde(default)]
+    pub duplicates: Vec<String>,
+}
+
+/// Matching passage (chunk) DTO
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PassageDto {
+    /// Chunk ID
+    pub chunk_id: Option<String>,
+    /// Passage score
+    pub score: f32,
+    /// Location of the passage within the file
+    pub location: Option<ChunkLocation>,
+    /// Virtual path of the archive member holding the passage
+    /// (`archive.zip!/docs/spec.md`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub virtual_path: Option<String>,
+}
+
+impl PassageDto {
+    /// Address the passage's archive member, if any, within `file_path`
+    pub fn in_file(mut self, file_path: &Path) -> Self {
+        self.virtual_path = self
+            .location
+            .as_ref()
+            .and_then(|location| location.member.as_deref())
+            .map(|member| archive::virtual_path(file_path, member).display().to_string());
+        self
+    }
+}
+
+impl From<&ScoredResult> for PassageDto {
+    fn from(result: &ScoredResult) -> Self {
+        Self {
+            chunk_id: result.chunk_id.map(|id| id.to_string()),
+            score: result.score,
+            location: result.location.clone(),
+            virtual_path: None,
+        }
+    }
+}
+
+/// Query-level ranking parameters DTO
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchExplanationDto {
+    /// Query type from `classify_query` (exact_keyword, natural_language, mixed)
+    pub query_type: String,
+    /// Vector weight chosen by `get_adjusted_weights`
+    pub vector_weight: f32,
+    /// BM25 weight chosen by `get_adjusted_weights`
+    pub bm25_weight: f32,
+    /// Vector space searched (text, image)
+    pub vector_space: String,
+}
+
+/// Per-result score breakdown DTO
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultExplanationDto {
+    /// Raw vector similarity
+    pub raw_vector_score: Option<f32>,
+    /// Normalized vector score
+    pub vector_score: Option<f32>,
+    /// Raw BM25 score
+    pub raw_bm25_score: Option<f32>,
+    /// Normalized BM25 score
+    pub bm25_score: Option<f32>,
+    /// Tantivy explanation of the BM25 score (JSON)
+    pub bm25_explanation: Option<String>,
+    /// Vector weight used for merging
+    pub vector_weight: f32,
+    /// BM25 weight used for merging
+    pub bm25_weight: f32,
+    /// Weighted score before boosts
+    pub merged_score: f32,
+    /// Boosts applied, as (reason, factor)
+    pub boosts: Vec<(String, f32)>,
+    /// Filter decisions, as (filter, passed, detail)
+    pub filters: Vec<(String, bool, String)>,
+    /// Final score
+    pub final_score: f32,
+}
+
+impl From<&ScoreExplanation> for ResultExplanationDto {
+    fn from(explanation: &ScoreExplanation) -> Self {
+        Self {
+            raw_vector_score: explanation.raw_vector_score,
+            vector_score: explanation.vector_score,
+            raw_bm25_score: explanation.raw_bm25_score,
+            bm25_score: explanation.bm25_score,
+            bm25_explanation: explanation.bm25_explanation.clone(),
+            vector_weight: explanation.vector_weight,
+            bm25_weight: explanation.bm25_weight,
+            merged_score: explanation.merged_score,
+            boosts: explanation
+                .boosts
+                .iter()
+                .map(|b| (b.reason.clone(), b.factor))
+                .collect(),
+            filters: explanation
+                .filters
+                .iter()
+                .map(|f| (f.filter.clone(), f.passed, f.detail.clone()))
+                .collect(),
+            final_score: explanation.final_score,
+        }
+    }
 }
 
 /// Intent information DTO
@@ -107,6 +328,8 @@ pub struct IntentInfoDto {
     pub file_type_hint: Option<String>,
     /// Time hint if detected
     pub time_hint: Option<String>,
+    /// Vector space queried (text, image)
+    pub vector_space: String,
 }
 
 /// Clarification suggestion DTO
@@ -123,6 +346,8 @@ pub struct ClarificationDto {
 pub struct ClarificationOptionDto {
     /// Option text
     pub text: String,
+    /// Choice to pass to `record_clarification` ("file" or "content")
+    pub choice: String,
     /// Estimated result count
     pub estimated_count: Option<u64>,
 }
@@ -149,12 +374,17 @@ pub struct SearchSuggestion {
 /// # Returns
 /// Search response with results, intent info, and optional clarifications
 #[tauri::command]
-pub async fn search_files(request: SearchFilesRequest) -> Result<SearchFilesResponse, String> {
+pub async fn search_files(
+    config_state: State<'_, ConfigState>,
+    parsers: State<'_, IntentParserState>,
+    indexing: State<'_, IndexingState>,
+    request: SearchFilesRequest,
+) -> Result<SearchFilesResponse, String> {
     let start_time = std::time::Instant::now();
     let request_id = Uuid::now_v7();
 
     // Parse intent
-    let intent_parser = IntentParser::new();
+    let intent_parser = load_intent_parser(&config_state, &parsers).await;
     let intent_result = intent_parser.parse(&request.query);
 
     // Classify query type for search strategy
@@ -163,18 +393,58 @@ pub async fn search_files(request: SearchFilesRequest) -> Result<SearchFilesResp
     // Build search filters
     let filters = build_search_filters(&request)?;
 
+    // Image-oriented queries are embedded with the CLIP text tower and
+    // matched against the image vector space instead of text chunks
+    let vector_space = select_vector_space(
+        intent_parser.extract_file_type_hint(&request.query),
+        filters.file_types.as_deref(),
+    );
+    let filters = with_photo_hints(
+        HybridSearchFilters::from(&filters),
+        &intent_parser,
+        &request.query,
+        vector_space,
+    );
+
+    // Explanations are opt-in: they cost an extra Tantivy explain per hit
+    let explain = request.explain.unwrap_or(false);
+    let engine = HybridSearchEngine::with_config(
+        HybridSearchConfig::default()
+            .with_explain(explain)
+            .with_collapse_duplicates(request.collapse_duplicates.unwrap_or(false)),
+    )
+    .map_err(|e| e.to_string())?;
+
     // Create pagination
     let pagination = Pagination {
         offset: request.offset.unwrap_or(0),
         limit: request.limit.unwrap_or(20),
     };
 
-    // For now, return a mock response since we don't have full integration
-    // In production, this would call the actual search engine
+    // Nothing has been indexed until the pipeline is up
+    let (results, total_count) = match indexing.pipeline().await {
+        Some(pipeline) => {
+            let query_vector = embed_query(&pipeline, &request.query, vector_space).await;
+            search_page(
+                &pipeline,
+                &engine,
+                &request.query,
+                query_vector.as_deref(),
+                vector_space,
+                &filters,
+                &intent_result.intent,
+                &pagination,
+            )
+            .await?
+        }
+        None => (Vec::new(), 0),
+    };
+    let has_more = u64::from(pagination.offset) + (results.len() as u64) < total_count;
+
     let duration_ms = start_time.elapsed().as_millis() as u64;
 
     // Build intent info
-    let intent_info = build_intent_info(&intent_result, query_type);
+    let intent_info = build_intent_info(&intent_result, query_type, vector_space);
 
     // Build clarifications if intent is ambiguous
     let clarifications = if intent_result.is_ambiguous {
@@ -183,9 +453,21 @@ pub async fn search_files(request: SearchFilesRequest) -> Result<SearchFilesResp
         None
     };
 
+    let explanation = explain.then(|| {
+        let (vector_weight, bm25_weight) = engine.get_adjusted_weights(query_type);
+        SearchExplanationDto {
+            query_type: query_type_name(query_type).to_string(),
+            vector_weight,
+            bm25_weight,
+            vector_space: vector_space_name(vector_space).to_string(),
+        }
+    });
+
     // Determine status
     let status = if intent_result.is_ambiguous {
         "needs_clarity"
+    } else if results.is_empty() {
+        "no_results"
     } else {
         "success"
     };
@@ -193,15 +475,314 @@ pub async fn search_files(request: SearchFilesRequest) -> Result<SearchFilesResp
     Ok(SearchFilesResponse {
         request_id: request_id.to_string(),
         status: status.to_string(),
-        results: vec![], // Would be populated by actual search
-        total_count: 0,
-        has_more: false,
+        results,
+        total_count,
+        has_more,
         duration_ms,
         intent: Some(intent_info),
         clarifications,
+        explanation,
     })
 }
 
+/// Start a progressive search
+///
+/// Results are pushed in phases on the `search-stream:<request_id>` event:
+/// BM25 keyword hits first, then the hybrid ranking with vector hits, then
+/// results for the cloud's refined query when cloud enhancement is enabled.
+/// Only the query is sent to the cloud, and sensitive and private files are
+/// left out of the cloud-refined results. Every update carries a
+/// `SearchStatus`; intermediate phases are `Partial` and the last one has
+/// `is_final` set.
+///
+/// # Arguments
+/// * `request` - Search request; `request_id` may be supplied by the client
+///
+/// # Returns
+/// The request ID the updates are keyed by
+#[tauri::command]
+pub async fn search_files_stream(
+    window: tauri::Window,
+    state: State<'_, SearchStreamState>,
+    config_state: State<'_, ConfigState>,
+    parsers: State<'_, IntentParserState>,
+    indexing: State<'_, IndexingState>,
+    request: SearchFilesRequest,
+) -> Result<String, String> {
+    let request_id = match request.request_id.as_deref() {
+        Some(id) => Uuid::parse_str(id).map_err(|e| format!("Invalid request ID: {}", e))?,
+        None => Uuid::now_v7(),
+    };
+
+    // Validate filters up front so errors reach the caller, not the event stream
+    let filters = build_search_filters(&request)?;
+
+    let intent_parser = load_intent_parser(&config_state, &parsers).await;
+    let intent = intent_parser.parse(&request.query).intent;
+    let vector_space = select_vector_space(
+        intent_parser.extract_file_type_hint(&request.query),
+        filters.file_types.as_deref(),
+    );
+    let engine = HybridSearchEngine::with_config(
+        HybridSearchConfig::default()
+            .with_explain(request.explain.unwrap_or(false))
+            .with_collapse_duplicates(request.collapse_duplicates.unwrap_or(false)),
+    )
+    .map_err(|e| e.to_string())?;
+    let filters = with_photo_hints(
+        HybridSearchFilters::from(&filters),
+        &intent_parser,
+        &request.query,
+        vector_space,
+    );
+    // Sensitive and private files are processed locally only
+    let cloud_filters = filters.clone().exclude_sensitive();
+    let pagination = Pagination {
+        offset: request.offset.unwrap_or(0),
+        limit: request.limit.unwrap_or(20),
+    };
+
+    let pipeline = indexing.pipeline().await;
+    let token = state.registry.register(request_id);
+    let registry = state.registry.clone();
+    let inference = state.inference.clone();
+    let event_name = format!("{}{}", SEARCH_STREAM_EVENT_PREFIX, request_id);
+
+    tokio::spawn(async move {
+        let query = request.query.as_str();
+        let (pipeline, engine, filters, cloud_filters, intent, pagination) = (
+            pipeline.as_deref(),
+            &engine,
+            &filters,
+            &cloud_filters,
+            &intent,
+            &pagination,
+        );
+        // Nothing has been indexed until the pipeline is up
+        let search = move |query: String, query_vector: Option<Vec<f32>>, cloud: bool| async move {
+            let Some(pipeline) = pipeline else {
+                return Ok(Vec::new());
+            };
+            search_page(
+                pipeline,
+                engine,
+                &query,
+                query_vector.as_deref(),
+                vector_space,
+                if cloud { cloud_filters } else { filters },
+                intent,
+                pagination,
+            )
+            .await
+            .map(|(results, _)| results)
+        };
+
+        let mut phases: Vec<(SearchPhase, PhaseFuture<'_, SearchResultDto>)> = vec![
+            (SearchPhase::Keyword, Box::pin(search(query.to_string(), None, false))),
+            (
+                SearchPhase::Vector,
+                Box::pin(async move {
+                    let Some(pipeline) = pipeline else {
+                        return Ok(Vec::new());
+                    };
+                    let query_vector = embed_query(pipeline, query, vector_space)
+                        .await
+                        .ok_or_else(|| "Query embedding unavailable".to_string())?;
+                    search(query.to_string(), Some(query_vector), false).await
+                }),
+            ),
+        ];
+
+        if request.enable_cloud.unwrap_or(false) {
+            if let Some(inference) = inference {
+                phases.push((
+                    SearchPhase::CloudRefined,
+                    Box::pin(async move {
+                        let inference_request = InferenceRequest::new(
+                            query.to_string(),
+                            InferenceContext::default(),
+                            InferenceOptions {
+                                enable_cloud: true,
+                                ..Default::default()
+                            },
+                        );
+                        let response = inference
+                            .infer(inference_request)
+                            .await
+                            .map_err(|e| e.to_string())?;
+                        let refined = response
+                            .cloud_understanding
+                            .as_ref()
+                            .filter(|_| response.cloud_enhanced)
+                            .and_then(refined_query)
+                            .ok_or_else(|| "Cloud enhancement unavailable".to_string())?;
+
+                        search(refined, None, true).await.map(|results| {
+                            results
+                                .into_iter()
+                                .map(|result| SearchResultDto {
+                                    source: "cloud_enhanced".to_string(),
+                                    ..result
+                                })
+                                .collect()
+                        })
+                    }),
+                ));
+            }
+        }
+
+        let status = run_progressive_search(request_id, token.clone(), phases, |update| {
+            if let Err(e) = window.emit(&event_name, &update) {
+                tracing::warn!("Failed to emit search update for {}: {}", request_id, e);
+            }
+        })
+        .await;
+
+        registry.complete(&request_id, &token);
+        tracing::debug!("Streaming search {} finished with {:?}", request_id, status);
+    });
+
+    Ok(request_id.to_string())
+}
+
+/// Inference engine for the cloud-refined phase, built from the saved
+/// cloud settings
+///
+/// Returns `None` unless cloud features are enabled with an API key and
+/// privacy mode is off. Settings changed while the app runs take effect on
+/// the next launch.
+pub fn saved_inference_engine(models_dir: PathBuf) -> Option<Arc<HybridInferenceEngine>> {
+    let content = std::fs::read_to_string(ConfigStoreConfig::default().config_path).ok()?;
+    let config: AppConfig = serde_json::from_str(&content).ok()?;
+    if !config.cloud.enabled || config.privacy.privacy_mode {
+        return None;
+    }
+
+    let cloud = inference_cloud_config(&config.cloud)?;
+    let embedder = Arc::new(EmbeddingEngine::new(EmbeddingConfig {
+        models_dir,
+        ..Default::default()
+    }));
+    Some(Arc::new(HybridInferenceEngine::with_cloud(embedder, cloud)))
+}
+
+/// Cloud bridge settings for the saved provider, model and limits
+fn inference_cloud_config(cloud: &CloudConfig) -> Option<InferenceCloudConfig> {
+    let api_key = cloud.api_key.clone().filter(|key| !key.is_empty())?;
+    let mut config = match cloud.provider.as_str() {
+        "anthropic" => InferenceCloudConfig::anthropic(api_key),
+        _ => InferenceCloudConfig::openai(api_key),
+    };
+    if let Some(ref endpoint) = cloud.endpoint {
+        config.endpoint = endpoint.clone();
+    }
+    config.model = match cloud.model.as_str() {
+        "gpt-4o-mini" => CloudModelType::GPT4oMini,
+        model if model == CloudModelType::ClaudeHaiku.to_string() => CloudModelType::ClaudeHaiku,
+        model => CloudModelType::Custom(model.to_string()),
+    };
+    config.monthly_cost_limit = cloud.monthly_cost_limit;
+    config.requests_per_minute = cloud.requests_per_minute;
+    Some(config)
+}
+
+/// Keyword query for the cloud's understanding of a search: its suggested
+/// terms, or its refined intent if it suggested none
+fn refined_query(understanding: &CloudUnderstanding) -> Option<String> {
+    let terms: Vec<&str> = understanding
+        .suggested_terms
+        .iter()
+        .map(|t| t.trim())
+        .filter(|t| !t.is_empty())
+        .collect();
+    if !terms.is_empty() {
+        return Some(terms.join(" "));
+    }
+    understanding
+        .refined_intent
+        .as_deref()
+        .map(str::trim)
+        .filter(|intent| !intent.is_empty())
+        .map(str::to_string)
+}
+
+/// Cancel an in-flight streaming search
+///
+/// # Arguments
+/// * `request_id` - ID returned by `search_files_stream`
+///
+/// # Returns
+/// `true` if a running search was cancelled
+#[tauri::command]
+pub async fn cancel_search(
+    state: State<'_, SearchStreamState>,
+    request_id: String,
+) -> Result<bool, String> {
+    let request_id = Uuid::parse_str(&request_id).map_err(|e| format!("Invalid request ID: {}", e))?;
+    Ok(state.registry.cancel(&request_id))
+}
+
+/// Record the user's answer to a clarification question
+///
+/// Once the same query pattern has been answered consistently, later
+/// ambiguous queries with that pattern are resolved without asking.
+///
+/// # Arguments
+/// * `query` - The query that was ambiguous
+/// * `choice` - The chosen option ("file" or "content")
+///
+/// # Returns
+/// `true` if the answer was recorded
+#[tauri::command]
+pub async fn record_clarification(
+    config_state: State<'_, ConfigState>,
+    parsers: State<'_, IntentParserState>,
+    query: String,
+    choice: String,
+) -> Result<bool, String> {
+    let category = match choice.as_str() {
+        "file" => IntentCategory::File,
+        "content" => IntentCategory::Content,
+        other => return Err(format!("Unknown clarification choice: {}", other)),
+    };
+
+    let pattern = load_intent_parser(&config_state, &parsers)
+        .await
+        .clarification_pattern(&query);
+    if pattern.is_empty() {
+        return Ok(false);
+    }
+
+    let store = config_state.get_store().await?;
+    store
+        .record_clarification(pattern, category)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+/// Build an intent parser from the search section of `AppConfig`
+///
+/// Uses the configured lexicon file (or the built-in lexicon), applies the
+/// user's overrides and loads the learned clarification answers.
+pub fn intent_parser_from_config(config: &SearchConfig) -> IntentParser {
+    let lexicon = IntentLexicon::load_or_builtin(config.intent_lexicon_path.as_deref())
+        .with_overrides(&config.intent_overrides);
+    IntentParser::with_lexicon(lexicon)
+        .with_clarification_memory(config.learned_clarifications.clone())
+}
+
+/// Load the intent parser for the current configuration
+///
+/// Falls back to the built-in lexicon if the config store is not initialized.
+async fn load_intent_parser(config_state: &ConfigState, parsers: &IntentParserState) -> Arc<IntentParser> {
+    let store_guard = config_state.store.read().await;
+    match store_guard.as_ref() {
+        Some(store) => parsers.parser_for(&store.get().await),
+        None => Arc::new(IntentParser::new()),
+    }
+}
+
 /// Get search suggestions based on partial query
 ///
 /// Returns suggestions including:
@@ -263,6 +844,262 @@ pub async fn get_search_suggestions(
 
 // Helper functions
 
+/// Maximum length of a result preview, in characters
+const PREVIEW_CHARS: usize = 240;
+
+/// Search the pipeline's text index and the vector store of the query's
+/// space and build one page of results, shaped for the intent
+///
+/// Text queries use the active collection; image queries use the image
+/// space when it is configured.
+#[allow(clippy::too_many_arguments)]
+async fn search_page(
+    pipeline: &IndexingPipeline,
+    engine: &HybridSearchEngine,
+    query: &str,
+    query_vector: Option<&[f32]>,
+    vector_space: VectorSpace,
+    filters: &HybridSearchFilters,
+    intent: &SearchIntent,
+    pagination: &Pagination,
+) -> Result<(Vec<SearchResultDto>, u64), String> {
+    let store = match (vector_space, pipeline.image_space()) {
+        (VectorSpace::Image, Some(space)) => space.store.clone(),
+        _ => pipeline.collections().active().store,
+    };
+    let mut hits = engine
+        .search(pipeline.text_index(), &store, query, query_vector, filters)
+        .await
+        .map_err(|e| e.to_string())?;
+    if let Some(allowed) = files_within(pipeline.db(), &hits, filters).await? {
+        hits.retain(|hit| allowed.contains(&hit.file_id));
+    }
+
+    // Content searches list passages, other intents one result per file
+    let mut files = engine.group_for_intent(hits, intent);
+    if engine.config().collapse_duplicates {
+        let file_ids: Vec<Uuid> = files.iter().map(|file| file.file_id).collect();
+        let duplicates = DuplicateService::new(pipeline.db().clone())
+            .duplicate_map(&file_ids)
+            .await
+            .map_err(|e| e.to_string())?;
+        files = engine.collapse_duplicates(files, &duplicates);
+    }
+    let total_count = files.len() as u64;
+    let page: Vec<FileResult> = files
+        .into_iter()
+        .skip(pagination.offset as usize)
+        .take(pagination.limit as usize)
+        .collect();
+    Ok((result_dtos(pipeline.db(), &page).await?, total_count))
+}
+
+/// Files of the hits that pass the photo and privacy filters, or `None`
+/// when no such filter is set
+///
+/// The text index only applies the photo filters to keyword hits and
+/// knows no privacy levels, so every hit is checked against its file row.
+async fn files_within(
+    db: &SqlitePool,
+    hits: &[ScoredResult],
+    filters: &HybridSearchFilters,
+) -> Result<Option<HashSet<Uuid>>, String> {
+    if filters.capture_range.is_none() && filters.location.is_none() && !filters.exclude_sensitive {
+        return Ok(None);
+    }
+
+    let mut conditions = Vec::new();
+    let mut values = Vec::new();
+    if let Some(ref range) = filters.capture_range {
+        if let Some(start) = range.start {
+            conditions.push("m.captured_ts >= ?");
+            values.push(start.timestamp().to_string());
+        }
+        if let Some(end) = range.end {
+            conditions.push("m.captured_ts <= ?");
+            values.push(end.timestamp().to_string());
+        }
+    }
+    if let Some(ref place) = filters.location {
+        conditions.push("instr(lower(m.location), ?) > 0");
+        values.push(place.to_lowercase());
+    }
+    if filters.exclude_sensitive {
+        conditions.push("f.privacy_level = ?");
+        values.push(format!("{:?}", PrivacyLevel::Normal));
+    }
+
+    let file_ids: Vec<String> = hits
+        .iter()
+        .map(|hit| hit.file_id.to_string())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let mut allowed = HashSet::new();
+    for ids in file_ids.chunks(500) {
+        let sql = format!(
+            "SELECT f.id FROM files f LEFT JOIN image_metadata m ON m.file_id = f.id \
+             WHERE f.id IN ({}) AND {}",
+            vec!["?"; ids.len()].join(", "),
+            conditions.join(" AND ")
+        );
+        let mut query = sqlx::query_as::<_, (String,)>(&sql);
+        for id in ids {
+            query = query.bind(id);
+        }
+        for value in &values {
+            query = query.bind(value);
+        }
+        for (id,) in query.fetch_all(db).await.map_err(|e| e.to_string())? {
+            allowed.extend(Uuid::parse_str(&id).ok());
+        }
+    }
+    Ok(Some(allowed))
+}
+
+/// Embed the query for the given vector space
+///
+/// Returns `None`, and the search falls back to keywords, while the model
+/// is unavailable or while the active collection still holds another
+/// model's vectors during a migration.
+async fn embed_query(pipeline: &IndexingPipeline, query: &str, vector_space: VectorSpace) -> Option<Vec<f32>> {
+    // Image queries are embedded with the CLIP text tower
+    if vector_space == VectorSpace::Image {
+        let space = pipeline.image_space()?;
+        return match space.embedder.embed_image_query(query).await {
+            Ok(vector) => Some(vector),
+            Err(e) => {
+                tracing::warn!("Searching keywords only, image query embedding failed: {}", e);
+                None
+            }
+        };
+    }
+
+    let model = pipeline.collections().active().model;
+    let embedder = pipeline.embedder();
+    if embedder.model() != model {
+        tracing::debug!("Query model differs from {}; searching keywords only", model.key());
+        return None;
+    }
+
+    match embedder.embed_batch(&[query]).await {
+        Ok(mut vectors) => vectors.pop(),
+        Err(e) => {
+            tracing::warn!("Searching keywords only, query embedding failed: {}", e);
+            None
+        }
+    }
+}
+
+/// Indexed file a result belongs to
+struct FileDetails {
+    path: String,
+    filename: String,
+    file_type: String,
+}
+
+/// Build result DTOs, dropping hits whose file is no longer indexed
+///
+/// The best passage of each file supplies its chunk ID, preview and score
+/// explanation.
+async fn result_dtos(db: &SqlitePool, results: &[FileResult]) -> Result<Vec<SearchResultDto>, String> {
+    let file_ids: Vec<String> = results.iter().map(|r| r.file_id.to_string()).collect();
+    let chunk_ids: Vec<String> = results
+        .iter()
+        .filter_map(|r| r.passages.first()?.chunk_id.map(|id| id.to_string()))
+        .collect();
+
+    let mut files: HashMap<String, FileDetails> = HashMap::new();
+    for ids in file_ids.chunks(500) {
+        let sql = format!(
+            "SELECT id, path, filename, file_type FROM files WHERE id IN ({})",
+            vec!["?"; ids.len()].join(", ")
+        );
+        let mut query = sqlx::query_as::<_, (String, String, String, String)>(&sql);
+        for id in ids {
+            query = query.bind(id);
+        }
+        for (id, path, filename, file_type) in query.fetch_all(db).await.map_err(|e| e.to_string())? {
+            files.insert(id, FileDetails { path, filename, file_type });
+        }
+    }
+
+    let mut previews: HashMap<String, String> = HashMap::new();
+    for ids in chunk_ids.chunks(500) {
+        let sql = format!(
+            "SELECT id, content FROM content_chunks WHERE id IN ({})",
+            vec!["?"; ids.len()].join(", ")
+        );
+        let mut query = sqlx::query_as::<_, (String, String)>(&sql);
+        for id in ids {
+            query = query.bind(id);
+        }
+        for (id, content) in query.fetch_all(db).await.map_err(|e| e.to_string())? {
+            previews.insert(id, content.chars().take(PREVIEW_CHARS).collect());
+        }
+    }
+
+    Ok(results
+        .iter()
+        .filter_map(|result| {
+            let file = files.get(&result.file_id.to_string())?;
+            let best = result.passages.first();
+            let chunk_id = best.and_then(|p| p.chunk_id).map(|id| id.to_string());
+            Some(SearchResultDto {
+                file_id: result.file_id.to_string(),
+                path: file.path.clone(),
+                filename: file.filename.clone(),
+                file_type: file.file_type.clone(),
+                score: result.score,
+                preview: chunk_id.as_ref().and_then(|id| previews.get(id).cloned()),
+                chunk_id,
+                source: source_name(result.source).to_string(),
+                tags: result.tags.clone(),
+                explanation: best
+                    .and_then(|p| p.explanation.as_ref())
+                    .map(ResultExplanationDto::from),
+                passages: result
+                    .passages
+                    .iter()
+                    .map(|passage| PassageDto::from(passage).in_file(Path::new(&file.path)))
+                    .collect(),
+                duplicates: result.duplicates.iter().map(|id| id.to_string()).collect(),
+            })
+        })
+        .collect())
+}
+
+fn source_name(source: SearchSource) -> &'static str {
+    match source {
+        SearchSource::Vector | SearchSource::Both => "local_vector",
+        SearchSource::BM25 => "local_keyword",
+    }
+}
+
+/// Add the capture date and place a photo query names to its filters
+///
+/// Only image searches are narrowed, so "report from March" still finds
+/// documents. Places match the XMP location photos record.
+fn with_photo_hints(
+    mut filters: HybridSearchFilters,
+    parser: &IntentParser,
+    query: &str,
+    vector_space: VectorSpace,
+) -> HybridSearchFilters {
+    if vector_space != VectorSpace::Image {
+        return filters;
+    }
+
+    let hints = parser.extract_photo_hints(query);
+    if let Some(range) = hints.capture_range {
+        filters = filters.with_capture_range(range.start, range.end);
+    }
+    if let Some(location) = hints.location {
+        filters = filters.with_location(location);
+    }
+    filters
+}
+
 fn build_search_filters(request: &SearchFilesRequest) -> Result<SearchFilters, String> {
     let mut filters = SearchFilters::default();
 
@@ -326,7 +1163,11 @@ fn parse_file_type(type_str: &str) -> Option<FileType> {
     }
 }
 
-fn build_intent_info(intent_result: &IntentParseResult, query_type: QueryType) -> IntentInfoDto {
+fn build_intent_info(
+    intent_result: &IntentParseResult,
+    query_type: QueryType,
+    vector_space: VectorSpace,
+) -> IntentInfoDto {
     let category = match &intent_result.intent {
         SearchIntent::FindFile { .. } => "file",
         SearchIntent::FindContent { .. } => "content",
@@ -348,6 +1189,22 @@ fn build_intent_info(intent_result: &IntentParseResult, query_type: QueryType) -
         keywords: intent_result.extracted_keywords.clone(),
         file_type_hint,
         time_hint,
+        vector_space: vector_space_name(vector_space).to_string(),
+    }
+}
+
+fn vector_space_name(vector_space: VectorSpace) -> &'static str {
+    match vector_space {
+        VectorSpace::Text => "text",
+        VectorSpace::Image => "image",
+    }
+}
+
+fn query_type_name(query_type: QueryType) -> &'static str {
+    match query_type {
+        QueryType::ExactKeyword => "exact_keyword",
+        QueryType::NaturalLanguage => "natural_language",
+        QueryType::Mixed => "mixed",
     }
 }
 
@@ -360,10 +1217,12 @@ fn build_clarifications(intent_result: &IntentParseResult) -> Vec<ClarificationD
                 options: vec![
                     ClarificationOptionDto {
                         text: "Find files".to_string(),
+                        choice: "file".to_string(),
                         estimated_count: None,
                     },
                     ClarificationOptionDto {
                         text: "Find content".to_string(),
+                        choice: "content".to_string(),
                         estimated_count: None,
                     },
                 ],
@@ -2,16 +2,32 @@
 //!
 //! Provides Tauri commands for system status monitoring:
 //! - get_index_status: Get indexing status and statistics
+//! - get_index_progress: Get progress per monitored root and pipeline stage
 //! - get_system_status: Get overall system status
 //! - get_dead_letter_tasks: Get failed tasks from dead letter queue
 //! - retry_dead_letter: Retry a failed task
+//! - get_move_candidates: Get possible moves awaiting confirmation
+//! - resolve_move_candidate: Confirm or reject a possible move
+//! - preview_reconcile: Dry-run reconciliation of the monitored directories
+//! - get_held_deletions / confirm_held_deletions: Review mass deletions
+//! - get_watch_modes: How each monitored root is watched (native or polling)
 //!
 //! **Validates: Requirements 16.1, Indexer Resilience**
 
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
 use uuid::Uuid;
 
-use crate::indexer::{IndexTask, TaskStatus, TaskPriority, IndexerStatsSnapshot, DeadLetterStats};
+use super::onboarding::{default_data_dir, saved_monitored_directories, IndexingState};
+use crate::indexer::{error_type_key, IndexTask, TaskStatus, TaskPriority, IndexerStatsSnapshot, DeadLetterStats, IndexProgressSnapshot};
+use crate::reconcile::{HeldDeletions, MoveCandidate, ReconcileResult, ReconciliationService};
+use crate::watcher::RootWatchStatus;
+
+/// Event carrying `IndexProgressSnapshot` updates
+pub const INDEX_PROGRESS_EVENT: &str = "index-progress";
 
 /// Index status response
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +48,29 @@ pub struct IndexStatusDto {
     pub is_indexing: bool,
     /// Estimated time to complete (seconds)
     pub estimated_completion_secs: Option<u64>,
+    /// Embedding model serving search (`model_id@version`)
+    pub embedding_model: Option<String>,
+    /// Re-embedding progress after an embedding model change
+    pub model_migration: Option<ModelMigrationDto>,
+    /// Scheduler policies off on this platform for lack of a load reading
+    pub disabled_scheduler_policies: Vec<String>,
+}
+
+/// Embedding model migration progress
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelMigrationDto {
+    /// Model serving search until cutover (`model_id@version`)
+    pub from_model: String,
+    /// Model being migrated to (`model_id@version`)
+    pub to_model: String,
+    /// Chunks already embedded with the new model
+    pub migrated_chunks: u64,
+    /// Chunks still to re-embed
+    pub remaining_chunks: u64,
+    /// Chunks the new model failed on, left for the next indexing of their file
+    pub skipped_chunks: u64,
+    /// Percentage complete
+    pub percent: f64,
 }
 
 /// System status response
@@ -133,21 +172,108 @@ pub struct RetryOperationResult {
 /// - Queue sizes
 /// - Processing statistics
 /// - Throughput metrics
+/// - Embedding model and re-embedding progress
 ///
 /// # Returns
 /// Index status
 #[tauri::command]
-pub async fn get_index_status() -> Result<IndexStatusDto, String> {
-    // In production, this would query the ResilientBatchIndexer
+pub async fn get_index_status(
+    indexing: State<'_, IndexingState>,
+) -> Result<IndexStatusDto, String> {
+    let disabled_scheduler_policies: Vec<String> = indexing
+        .scheduler()
+        .capabilities()
+        .disabled_policies()
+        .into_iter()
+        .map(String::from)
+        .collect();
+    let Some(pipeline) = indexing.pipeline().await else {
+        return Ok(IndexStatusDto {
+            total_processed: 0,
+            total_failed: 0,
+            total_dead_letter: 0,
+            current_queue_size: 0,
+            current_dead_letter_size: 0,
+            throughput_per_minute: 0.0,
+            is_indexing: false,
+            estimated_completion_secs: None,
+            embedding_model: None,
+            model_migration: None,
+            disabled_scheduler_policies,
+        });
+    };
+
+    let stats = pipeline.indexer().stats().snapshot();
+    let progress = indexing.progress().snapshot().total;
+    let model_migration = match indexing.migrator().await {
+        Some(migrator) => migrator
+            .progress()
+            .await
+            .map_err(|e| format!("Failed to read model migration progress: {}", e))?
+            .map(|progress| ModelMigrationDto {
+                percent: progress.percent(),
+                from_model: progress.from_model.key(),
+                to_model: progress.to_model.key(),
+                migrated_chunks: progress.migrated_chunks,
+                remaining_chunks: progress.remaining_chunks,
+                skipped_chunks: progress.skipped_chunks,
+            }),
+        None => None,
+    };
+
     Ok(IndexStatusDto {
-        total_processed: 0,
-        total_failed: 0,
-        total_dead_letter: 0,
-        current_queue_size: 0,
-        current_dead_letter_size: 0,
-        throughput_per_minute: 0.0,
-        is_indexing: false,
-        estimated_completion_secs: None,
+        total_processed: stats.total_processed,
+        total_failed: stats.total_failed,
+        total_dead_letter: stats.total_dead_letter,
+        current_queue_size: stats.current_queue_size,
+        current_dead_letter_size: stats.current_dead_letter_size,
+        throughput_per_minute: progress.throughput_per_minute,
+        is_indexing: stats.current_queue_size > 0 || model_migration.is_some(),
+        estimated_completion_secs: progress.eta_secs.filter(|_| stats.current_queue_size > 0),
+        embedding_model: Some(pipeline.collections().active().model.key()),
+        model_migration,
+        disabled_scheduler_policies,
+    })
+}
+
+/// Get indexing progress per monitored root and pipeline stage
+///
+/// Returns files queued, completed and failed, throughput and ETA for
+/// every root, with time spent in scan, parse, embed and store. The same
+/// snapshot is pushed as `index-progress` events while indexing runs.
+///
+/// # Returns
+/// Progress snapshot
+#[tauri::command]
+pub async fn get_index_progress(
+    indexing: State<'_, IndexingState>,
+) -> Result<IndexProgressSnapshot, String> {
+    Ok(indexing.progress().snapshot())
+}
+
+/// Push `index-progress` events to all windows whenever progress changes
+///
+/// Checks for changes every `interval`, so idle periods emit nothing.
+pub fn spawn_index_progress_events(
+    app: AppHandle,
+    indexing: IndexingState,
+    interval: Duration,
+) -> tauri::async_runtime::JoinHandle<()> {
+    tauri::async_runtime::spawn(async move {
+        let mut last_version = None;
+        loop {
+            tokio::time::sleep(interval).await;
+
+            let version = indexing.progress().version();
+            if last_version == Some(version) {
+                continue;
+            }
+            last_version = Some(version);
+
+            if let Err(e) = app.emit_all(INDEX_PROGRESS_EVENT, indexing.progress().snapshot()) {
+                tracing::warn!("Failed to emit index progress: {}", e);
+            }
+        }
     })
 }
 
@@ -192,14 +318,25 @@ pub async fn get_system_status() -> Result<SystemStatusDto, String> {
 /// List of dead letter tasks
 #[tauri::command]
 pub async fn get_dead_letter_tasks(
+    indexing: State<'_, IndexingState>,
     limit: Option<usize>,
     error_type: Option<String>,
 ) -> Result<Vec<DeadLetterTaskDto>, String> {
-    let _limit = limit.unwrap_or(100);
-    let _error_type = error_type;
+    let limit = limit.unwrap_or(100);
+    let Some(pipeline) = indexing.pipeline().await else {
+        return Ok(vec![]);
+    };
 
-    // In production, this would query the ResilientBatchIndexer
-    Ok(vec![])
+    let tasks = pipeline.indexer().get_dead_letter_tasks().await;
+    Ok(tasks
+        .iter()
+        .filter(|task| match error_type {
+            Some(ref wanted) => task.last_error.as_ref().map(error_type_key).as_ref() == Some(wanted),
+            None => true,
+        })
+        .take(limit)
+        .map(dead_letter_task_to_dto)
+        .collect())
 }
 
 /// Get dead letter queue statistics
@@ -209,16 +346,22 @@ pub async fn get_dead_letter_tasks(
 /// # Returns
 /// Dead letter queue statistics
 #[tauri::command]
-pub async fn get_dead_letter_stats() -> Result<DeadLetterStatsDto, String> {
-    // In production, this would query the ResilientBatchIndexer
-    Ok(DeadLetterStatsDto {
-        total_count: 0,
-        max_size: 1000,
-        utilization_percent: 0.0,
-        by_error_type: vec![],
-        oldest_task_age_secs: None,
-        newest_task_age_secs: None,
-    })
+pub async fn get_dead_letter_stats(
+    indexing: State<'_, IndexingState>,
+) -> Result<DeadLetterStatsDto, String> {
+    let Some(pipeline) = indexing.pipeline().await else {
+        return Ok(DeadLetterStatsDto {
+            total_count: 0,
+            max_size: 1000,
+            utilization_percent: 0.0,
+            by_error_type: vec![],
+            oldest_task_age_secs: None,
+            newest_task_age_secs: None,
+        });
+    };
+
+    let stats = pipeline.indexer().dead_letter_stats().await;
+    Ok(dead_letter_stats_to_dto(&stats))
 }
 
 /// Retry a specific task from dead letter queue
@@ -231,16 +374,30 @@ pub async fn get_dead_letter_stats() -> Result<DeadLetterStatsDto, String> {
 /// # Returns
 /// Operation result
 #[tauri::command]
-pub async fn retry_dead_letter(task_id: String) -> Result<RetryOperationResult, String> {
-    let _task_uuid = Uuid::parse_str(&task_id)
+pub async fn retry_dead_letter(
+    indexing: State<'_, IndexingState>,
+    task_id: String,
+) -> Result<RetryOperationResult, String> {
+    let task_uuid = Uuid::parse_str(&task_id)
         .map_err(|e| format!("Invalid task_id: {}", e))?;
 
-    // In production, this would use ResilientBatchIndexer::retry_dead_letter_task
-    Ok(RetryOperationResult {
-        success: true,
-        message: "Task queued for retry".to_string(),
-        tasks_retried: 1,
-    })
+    let pipeline = indexing
+        .pipeline()
+        .await
+        .ok_or_else(|| "Indexing is not running".to_string())?;
+
+    match pipeline.indexer().retry_dead_letter_task(task_uuid).await {
+        Ok(()) => Ok(RetryOperationResult {
+            success: true,
+            message: "Task queued for retry".to_string(),
+            tasks_retried: 1,
+        }),
+        Err(e) => Ok(RetryOperationResult {
+            success: false,
+            message: e.to_string(),
+            tasks_retried: 0,
+        }),
+    }
 }
 
 /// Retry all tasks in dead letter queue
@@ -250,12 +407,18 @@ pub async fn retry_dead_letter(task_id: String) -> Result<RetryOperationResult,
 /// # Returns
 /// Operation result
 #[tauri::command]
-pub async fn retry_all_dead_letter() -> Result<RetryOperationResult, String> {
-    // In production, this would use ResilientBatchIndexer::retry_all_dead_letter_tasks
+pub async fn retry_all_dead_letter(
+    indexing: State<'_, IndexingState>,
+) -> Result<RetryOperationResult, String> {
+    let retried = match indexing.pipeline().await {
+        Some(pipeline) => pipeline.indexer().retry_all_dead_letter_tasks().await,
+        None => 0,
+    };
+
     Ok(RetryOperationResult {
         success: true,
         message: "All tasks queued for retry".to_string(),
-        tasks_retried: 0,
+        tasks_retried: retried,
     })
 }
 
@@ -266,8 +429,13 @@ pub async fn retry_all_dead_letter() -> Result<RetryOperationResult, String> {
 /// # Returns
 /// Operation result
 #[tauri::command]
-pub async fn clear_dead_letter() -> Result<RetryOperationResult, String> {
-    // In production, this would use ResilientBatchIndexer::clear_dead_letter_queue
+pub async fn clear_dead_letter(
+    indexing: State<'_, IndexingState>,
+) -> Result<RetryOperationResult, String> {
+    if let Some(pipeline) = indexing.pipeline().await {
+        pipeline.indexer().clear_dead_letter_queue().await;
+    }
+
     Ok(RetryOperationResult {
         success: true,
         message: "Dead letter queue cleared".to_string(),
@@ -307,6 +475,139 @@ pub async fn resume_indexing() -> Result<RetryOperationResult, String> {
     })
 }
 
+/// Get possible moves awaiting confirmation
+///
+/// Files that disappeared while identical content appeared elsewhere, when
+/// the match was too ambiguous to apply automatically.
+///
+/// # Returns
+/// Move candidates, grouped by the missing file
+#[tauri::command]
+pub async fn get_move_candidates(
+    indexing: State<'_, IndexingState>,
+) -> Result<Vec<MoveCandidate>, String> {
+    let Some(pipeline) = indexing.pipeline().await else {
+        return Ok(vec![]);
+    };
+
+    ReconciliationService::new(pipeline.db().clone())
+        .move_candidates()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Confirm or reject a possible move
+///
+/// Confirming moves the missing file's index entry, with its tags and
+/// relations, to the new path. A rejected file is removed from the index
+/// once it has no candidates left.
+///
+/// # Arguments
+/// * `candidate_id` - Move candidate ID
+/// * `accept` - Whether the file was moved
+///
+/// # Returns
+/// `false` if the candidate no longer exists
+#[tauri::command]
+pub async fn resolve_move_candidate(
+    indexing: State<'_, IndexingState>,
+    candidate_id: String,
+    accept: bool,
+) -> Result<bool, String> {
+    let candidate_uuid = Uuid::parse_str(&candidate_id)
+        .map_err(|e| format!("Invalid candidate_id: {}", e))?;
+
+    let pipeline = indexing
+        .pipeline()
+        .await
+        .ok_or_else(|| "Indexing is not running".to_string())?;
+
+    let resolution = ReconciliationService::new(pipeline.db().clone())
+        .resolve_move_candidate(candidate_uuid, accept)
+        .await
+        .map_err(|e| e.to_string())?;
+    let Some(resolution) = resolution else {
+        return Ok(false);
+    };
+
+    if let Some((old_path, new_path)) = resolution.moved {
+        pipeline
+            .rename_path(&old_path, &new_path)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    for path in resolution.removed {
+        pipeline.remove_path(&path).await.map_err(|e| e.to_string())?;
+    }
+    Ok(true)
+}
+
+/// Preview what reconciling the monitored directories would change
+///
+/// Scans without touching the index, e.g. to review changes before a
+/// rescan.
+///
+/// # Returns
+/// Files that would be added, deleted, modified or moved, plus held
+/// deletions and skipped roots
+#[tauri::command]
+pub async fn preview_reconcile(
+    indexing: State<'_, IndexingState>,
+) -> Result<ReconcileResult, String> {
+    indexing
+        .preview_reconcile(&default_data_dir(), &saved_monitored_directories())
+        .await
+}
+
+/// Get deletions held back for confirmation
+///
+/// Reconciliation and the file watcher hold deletions that would remove too
+/// much of a monitored directory at once. They are kept across restarts
+/// until confirmed.
+///
+/// # Returns
+/// Held deletions per monitored directory
+#[tauri::command]
+pub async fn get_held_deletions(
+    indexing: State<'_, IndexingState>,
+) -> Result<Vec<HeldDeletions>, String> {
+    indexing.held_deletions(&default_data_dir()).await
+}
+
+/// Confirm held deletions of a monitored directory
+///
+/// # Arguments
+/// * `root` - Monitored directory the deletions were held in
+/// * `paths` - Held files the user reviewed and agreed to remove
+///
+/// # Returns
+/// Number of files removed from the index
+#[tauri::command]
+pub async fn confirm_held_deletions(
+    indexing: State<'_, IndexingState>,
+    root: String,
+    paths: Vec<String>,
+) -> Result<usize, String> {
+    let paths: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+    indexing
+        .confirm_deletions(&default_data_dir(), Path::new(&root), &paths)
+        .await
+}
+
+/// Get how each monitored directory is watched
+///
+/// Roots are polled when native watching is unavailable for them, e.g.
+/// after the inotify watch limit was reached or on network filesystems.
+///
+/// # Returns
+/// Watch mode and polling reason per monitored directory
+#[tauri::command]
+pub async fn get_watch_modes(
+    indexing: State<'_, IndexingState>,
+) -> Result<Vec<RootWatchStatus>, String> {
+    Ok(indexing.watch_modes().await)
+}
+
 // Helper functions
 
 fn task_status_to_string(status: &TaskStatus) -> String {
@@ -327,3 +628,43 @@ fn task_priority_to_string(priority: &TaskPriority) -> String {
         TaskPriority::Urgent => "urgent".to_string(),
     }
 }
+
+fn dead_letter_task_to_dto(task: &IndexTask) -> DeadLetterTaskDto {
+    let age = task.created_at.elapsed();
+    let created_at = chrono::Utc::now()
+        - chrono::Duration::from_std(age).unwrap_or_else(|_| chrono::Duration::zero());
+
+    DeadLetterTaskDto {
+        id: task.id.to_string(),
+        file_id: task.file_id.to_string(),
+        path: task.path.to_string_lossy().to_string(),
+        priority: task_priority_to_string(&task.priority),
+        retry_count: task.retry_count,
+        max_retries: task.max_retries,
+        last_error: task.last_error.as_ref().map(|e| e.to_string()),
+        error_type: task.last_error.as_ref().map(error_type_key),
+        created_at: created_at.to_rfc3339(),
+        age_secs: age.as_secs(),
+    }
+}
+
+fn dead_letter_stats_to_dto(stats: &DeadLetterStats) -> DeadLetterStatsDto {
+    let mut by_error_type: Vec<ErrorTypeCount> = stats
+        .by_error_type
+        .iter()
+        .map(|(error_type, count)| ErrorTypeCount {
+            error_type: error_type.clone(),
+            count: *count,
+        })
+        .collect();
+    by_error_type.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.error_type.cmp(&b.error_type)));
+
+    DeadLetterStatsDto {
+        total_count: stats.total_count,
+        max_size: stats.max_size,
+        utilization_percent: stats.utilization_percent(),
+        by_error_type,
+        oldest_task_age_secs: stats.oldest_task_age.map(|d| d.as_secs()),
+        newest_task_age_secs: stats.newest_task_age.map(|d| d.as_secs()),
+    }
+}
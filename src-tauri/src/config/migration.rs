@@ -269,6 +269,25 @@ pub fn validate_config(config: &AppConfig) -> ConfigResult<()> {
         ));
     }
 
+    let budget = &config.performance.indexing_budget;
+    if !(budget.max_cpu_percent > 0.0 && budget.max_cpu_percent <= 100.0) {
+        return Err(ConfigError::Invalid(
+            "indexing_budget.max_cpu_percent must be in (0, 100]".to_string()
+        ));
+    }
+
+    if !(budget.max_io_wait_percent > 0.0 && budget.max_io_wait_percent <= 100.0) {
+        return Err(ConfigError::Invalid(
+            "indexing_budget.max_io_wait_percent must be in (0, 100]".to_string()
+        ));
+    }
+
+    if budget.turbo_multiplier == 0 {
+        return Err(ConfigError::Invalid(
+            "indexing_budget.turbo_multiplier must be at least 1".to_string()
+        ));
+    }
+
     // Validate UI config
     let valid_themes = ["light", "dark", "system"];
     if !valid_themes.contains(&config.ui.theme.as_str()) {
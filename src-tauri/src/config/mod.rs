@@ -15,7 +15,8 @@ mod tests;
 
 pub use storage::{
     ConfigStore, ConfigStoreConfig, ConfigError, ConfigResult,
-    AppConfig, CloudConfig, PerformanceConfig, PrivacyConfig, UIConfig,
+    AppConfig, CloudConfig, PerformanceConfig, IndexingBudget, PrivacyConfig, UIConfig, SearchConfig,
+    EmbeddingSettings,
 };
 pub use migration::{
     ConfigMigration, MigrationManager, MigrationError, MigrationResult,
@@ -14,6 +14,9 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use thiserror::Error;
 
+use crate::embeddings::TextEmbeddingConfig;
+use crate::search::intent::{ClarificationMemory, IntentCategory, IntentLexiconOverrides};
+
 /// Configuration error types
 #[derive(Debug, Error)]
 pub enum ConfigError {
@@ -94,6 +97,14 @@ pub struct AppConfig {
     #[serde(default)]
     pub ui: UIConfig,
 
+    /// Search settings
+    #[serde(default)]
+    pub search: SearchConfig,
+
+    /// Embedding model settings
+    #[serde(default)]
+    pub embedding: EmbeddingSettings,
+
     /// Last modified timestamp
     #[serde(default = "default_timestamp")]
     pub last_modified: String,
@@ -191,6 +202,10 @@ pub struct PerformanceConfig {
     /// Enable fast inference mode
     #[serde(default = "default_fast_mode")]
     pub fast_inference_mode: bool,
+
+    /// Resource budgets for background indexing
+    #[serde(default)]
+    pub indexing_budget: IndexingBudget,
 }
 
 fn default_vram() -> u32 {
@@ -221,6 +236,81 @@ impl Default for PerformanceConfig {
             embedding_batch_size: default_batch(),
             enable_cuda: default_cuda(),
             fast_inference_mode: default_fast_mode(),
+            indexing_budget: IndexingBudget::default(),
+        }
+    }
+}
+
+/// Resource budgets the indexing scheduler adapts to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexingBudget {
+    /// System CPU usage (percent) above which indexing backs off
+    #[serde(default = "default_max_cpu")]
+    pub max_cpu_percent: f32,
+
+    /// Disk IO wait (percent) above which indexing backs off
+    #[serde(default = "default_max_io_wait")]
+    pub max_io_wait_percent: f32,
+
+    /// Maximum concurrent indexing tasks while on battery
+    #[serde(default = "default_battery_concurrency")]
+    pub battery_max_concurrency: u32,
+
+    /// Battery level (percent) below which indexing pauses
+    #[serde(default = "default_battery_pause")]
+    pub pause_below_battery_percent: u8,
+
+    /// Raise concurrency and batch size while the user is idle
+    #[serde(default = "default_turbo_when_idle")]
+    pub turbo_when_idle: bool,
+
+    /// Seconds without user input before turbo mode may start
+    #[serde(default = "default_idle_threshold")]
+    pub idle_threshold_secs: u64,
+
+    /// Factor applied to concurrency and batch size in turbo mode
+    #[serde(default = "default_turbo_multiplier")]
+    pub turbo_multiplier: u32,
+}
+
+fn default_max_cpu() -> f32 {
+    60.0
+}
+
+fn default_max_io_wait() -> f32 {
+    20.0
+}
+
+fn default_battery_concurrency() -> u32 {
+    1
+}
+
+fn default_battery_pause() -> u8 {
+    20
+}
+
+fn default_turbo_when_idle() -> bool {
+    true
+}
+
+fn default_idle_threshold() -> u64 {
+    300
+}
+
+fn default_turbo_multiplier() -> u32 {
+    2
+}
+
+impl Default for IndexingBudget {
+    fn default() -> Self {
+        Self {
+            max_cpu_percent: default_max_cpu(),
+            max_io_wait_percent: default_max_io_wait(),
+            battery_max_concurrency: default_battery_concurrency(),
+            pause_below_battery_percent: default_battery_pause(),
+            turbo_when_idle: default_turbo_when_idle(),
+            idle_threshold_secs: default_idle_threshold(),
+            turbo_multiplier: default_turbo_multiplier(),
         }
     }
 }
@@ -321,6 +411,32 @@ fn default_thumb_size() -> String {
     "medium".to_string()
 }
 
+/// Search configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchConfig {
+    /// Intent lexicon data file replacing the built-in lexicon
+    #[serde(default)]
+    pub intent_lexicon_path: Option<PathBuf>,
+
+    /// User additions to and removals from the intent lexicon
+    #[serde(default)]
+    pub intent_overrides: IntentLexiconOverrides,
+
+    /// Clarification answers used to auto-resolve ambiguous queries, for
+    /// the most recently answered patterns
+    #[serde(default)]
+    pub learned_clarifications: ClarificationMemory,
+}
+
+/// Embedding model settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EmbeddingSettings {
+    /// Text embedding model replacing the built-in one; a change re-embeds
+    /// every chunk in the background on the next launch
+    #[serde(default)]
+    pub text_model: Option<TextEmbeddingConfig>,
+}
+
 impl Default for UIConfig {
     fn default() -> Self {
         Self {
@@ -343,6 +459,8 @@ impl Default for AppConfig {
             performance: PerformanceConfig::default(),
             privacy: PrivacyConfig::default(),
             ui: UIConfig::default(),
+            search: SearchConfig::default(),
+            embedding: EmbeddingSettings::default(),
             last_modified: default_timestamp(),
         }
     }
@@ -599,4 +717,15 @@ impl ConfigStore {
             }
         }).await
     }
+
+    /// Record a clarification answer for an ambiguous query pattern
+    pub async fn record_clarification(
+        &self,
+        pattern: String,
+        choice: IntentCategory,
+    ) -> ConfigResult<AppConfig> {
+        self.update(|config| {
+            config.search.learned_clarifications.record(&pattern, choice);
+        }).await
+    }
 }
@@ -106,6 +106,31 @@ async fn test_privacy_mode_disables_cloud() {
     assert!(!updated.cloud.enabled);
 }
 
+#[tokio::test]
+async fn test_record_clarification() {
+    use crate::search::intent::IntentCategory;
+
+    let (store, _temp) = create_test_store().await;
+
+    store.record_clarification("report".to_string(), IntentCategory::File).await.unwrap();
+    let updated = store.record_clarification("report".to_string(), IntentCategory::File).await.unwrap();
+
+    let counts = updated.search.learned_clarifications.patterns["report"];
+    assert_eq!(counts.file, 2);
+    assert_eq!(
+        updated.search.learned_clarifications.resolve("report").map(|(c, _)| c),
+        Some(IntentCategory::File)
+    );
+}
+
+#[test]
+fn test_search_config_defaults_for_older_files() {
+    // Config files written before the search section existed still load
+    let config: AppConfig = serde_json::from_str(r#"{"version": 1}"#).unwrap();
+    assert!(config.search.intent_lexicon_path.is_none());
+    assert!(config.search.learned_clarifications.patterns.is_empty());
+}
+
 #[tokio::test]
 async fn test_backup_creation() {
     let (store, _temp) = create_test_store().await;
@@ -200,6 +225,24 @@ fn test_validate_config_invalid_threads() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_validate_config_invalid_indexing_budget() {
+    let mut config = AppConfig::default();
+    config.performance.indexing_budget.max_cpu_percent = 0.0;
+    assert!(validate_config(&config).is_err());
+
+    let mut config = AppConfig::default();
+    config.performance.indexing_budget.turbo_multiplier = 0;
+    assert!(validate_config(&config).is_err());
+}
+
+#[test]
+fn test_performance_config_without_budget_uses_defaults() {
+    let json = r#"{"max_vram_mb": 2048}"#;
+    let config: PerformanceConfig = serde_json::from_str(json).unwrap();
+    assert_eq!(config.indexing_budget, IndexingBudget::default());
+}
+
 #[test]
 fn test_validate_config_invalid_theme() {
     let mut config = AppConfig::default();
@@ -60,6 +60,8 @@ pub enum ChunkType {
     Image,
     /// Image/table caption
     Caption,
+    /// Presentation slide text
+    Slide,
 }
 
 /// Location of chunk within original file
@@ -79,6 +81,16 @@ pub struct ChunkLocation {
     /// Image region coordinates (x, y, width, height) - normalized to 0-1
     #[cfg_attr(test, proptest(strategy = "proptest::option::of((0.0f32..1.0, 0.0f32..1.0, 0.0f32..1.0, 0.0f32..1.0))"))]
     pub bounding_box: Option<(f32, f32, f32, f32)>,
+    /// Slide, sheet or other section of the document (1-based)
+    #[serde(default)]
+    pub section: Option<u32>,
+    /// Name of the section (sheet name, chapter title)
+    #[serde(default)]
+    pub section_name: Option<String>,
+    /// Archive member holding the chunk (`docs/spec.md`); offsets and
+    /// lines are then relative to the member
+    #[serde(default)]
+    pub member: Option<String>,
 }
 
 impl Default for ChunkLocation {
@@ -90,6 +102,9 @@ impl Default for ChunkLocation {
             end_line: None,
             page_number: None,
             bounding_box: None,
+            section: None,
+            section_name: None,
+            member: None,
         }
     }
 }
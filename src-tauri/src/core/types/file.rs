@@ -126,7 +126,8 @@ impl FileType {
     /// Determine file type from extension
     pub fn from_extension(ext: &str) -> Self {
         match ext.to_lowercase().as_str() {
-            "txt" | "md" | "markdown" | "rst" | "rtf" => FileType::TextDocument,
+            "txt" | "md" | "markdown" | "rst" | "rtf" | "html" | "htm" | "xhtml" | "mhtml" | "mht"
+            | "epub" | "eml" | "mbox" => FileType::TextDocument,
             "pdf" => FileType::Pdf,
             "doc" | "docx" | "xls" | "xlsx" | "ppt" | "pptx" | "odt" | "ods" | "odp" => {
                 FileType::OfficeDocument
@@ -137,10 +138,10 @@ impl FileType {
             "mp4" | "avi" | "mkv" | "mov" | "wmv" | "flv" | "webm" => FileType::Video,
             "mp3" | "wav" | "flac" | "aac" | "ogg" | "wma" | "m4a" => FileType::Audio,
             "rs" | "py" | "js" | "ts" | "jsx" | "tsx" | "java" | "c" | "cpp" | "h" | "hpp"
-            | "cs" | "go" | "rb" | "php" | "swift" | "kt" | "scala" | "html" | "css" | "scss"
+            | "cs" | "go" | "rb" | "php" | "swift" | "kt" | "scala" | "css" | "scss"
             | "json" | "yaml" | "yml" | "toml" | "xml" | "sql" => FileType::Code,
             "obj" | "fbx" | "gltf" | "glb" | "stl" | "3ds" | "blend" => FileType::Model3D,
-            "zip" | "tar" | "gz" | "7z" | "rar" | "bz2" | "xz" => FileType::Archive,
+            "zip" | "tar" | "gz" | "tgz" | "7z" | "rar" | "bz2" | "xz" => FileType::Archive,
             _ => FileType::Other,
         }
     }
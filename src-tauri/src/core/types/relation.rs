@@ -73,6 +73,8 @@ pub enum RelationType {
     Workflow,
     /// User-defined relationship
     UserDefined,
+    /// Same or nearly the same content (kept copy -> linked copy)
+    Duplicate,
 }
 
 /// Source of relation
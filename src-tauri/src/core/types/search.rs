@@ -146,6 +146,8 @@ pub enum SearchStatus {
     NeedsClarity,   // Needs user clarification
     NoResults,
     Error,
+    Partial,        // Streaming search, more phases will follow
+    Cancelled,      // Streaming search cancelled by the user
 }
 
 /// Result source
@@ -87,9 +87,70 @@ impl MigrationManager {
             include_str!("../../migrations/001_initial_schema.sql"),
         );
         self.add_migration(initial_migration);
+        self.add_migration(Migration::new(
+            2,
+            "002_add_file_id",
+            include_str!("../../migrations/002_add_file_id.sql"),
+        ));
+        self.add_migration(Migration::new(
+            3,
+            "003_add_session_columns",
+            include_str!("../../migrations/003_add_session_columns.sql"),
+        ));
+        self.add_migration(Migration::new(
+            4,
+            "004_index_tasks",
+            include_str!("../../migrations/004_index_tasks.sql"),
+        ));
+        self.add_migration(Migration::new(
+            5,
+            "005_chunk_hashes",
+            include_str!("../../migrations/005_chunk_hashes.sql"),
+        ));
+        self.add_migration(Migration::new(
+            6,
+            "006_embedding_models",
+            include_str!("../../migrations/006_embedding_models.sql"),
+        ));
+        self.add_migration(Migration::new(
+            7,
+            "007_move_candidates",
+            include_str!("../../migrations/007_move_candidates.sql"),
+        ));
+        self.add_migration(Migration::new(
+            8,
+            "008_duplicates",
+            include_str!("../../migrations/008_duplicates.sql"),
+        ));
+        self.add_migration(Migration::new(
+            9,
+            "009_image_metadata",
+            include_str!("../../migrations/009_image_metadata.sql"),
+        ));
+        self.add_migration(Migration::new(
+            10,
+            "010_held_deletions",
+            include_str!("../../migrations/010_held_deletions.sql"),
+        ));
+        self.add_migration(Migration::new(
+            11,
+            "011_image_vectors",
+            include_str!("../../migrations/011_image_vectors.sql"),
+        ));
         self
     }
 
+    /// Apply the embedded schema migrations
+    ///
+    /// Uses the migrations already added to the manager, or the embedded
+    /// set if none were added.
+    pub async fn run_migrations(mut self) -> Result<MigrationResult> {
+        if self.migrations.is_empty() {
+            self = self.with_embedded_migrations();
+        }
+        self.migrate().await
+    }
+
     /// Load migrations from a directory
     pub async fn load_migrations_from_dir(&mut self, dir: &Path) -> Result<()> {
         let mut entries = tokio::fs::read_dir(dir).await.map_err(|e| {
@@ -255,13 +316,8 @@ impl MigrationManager {
 
         // Execute migration SQL
         // Split by semicolons and execute each statement
-        for statement in migration.up_sql.split(';') {
-            let statement = statement.trim();
-            if statement.is_empty() || statement.starts_with("--") {
-                continue;
-            }
-
-            sqlx::query(statement)
+        for statement in sql_statements(&migration.up_sql) {
+            sqlx::query(&statement)
                 .execute(&mut *tx)
                 .await
                 .map_err(|e| {
@@ -274,11 +330,11 @@ impl MigrationManager {
                 })?;
         }
 
-        // Record the migration
+        // Record the migration (replacing any record the SQL file wrote itself)
         let applied_at = Utc::now().to_rfc3339();
         sqlx::query(
             r#"
-            INSERT INTO schema_migrations (version, name, applied_at, checksum)
+            INSERT OR REPLACE INTO schema_migrations (version, name, applied_at, checksum)
             VALUES (?, ?, ?, ?)
             "#,
         )
@@ -333,13 +389,8 @@ impl MigrationManager {
         let mut tx = self.pool.begin().await.map_err(NeuralFSError::Database)?;
 
         // Execute rollback SQL
-        for statement in down_sql.split(';') {
-            let statement = statement.trim();
-            if statement.is_empty() || statement.starts_with("--") {
-                continue;
-            }
-
-            sqlx::query(statement)
+        for statement in sql_statements(down_sql) {
+            sqlx::query(&statement)
                 .execute(&mut *tx)
                 .await
                 .map_err(|e| {
@@ -388,6 +439,25 @@ impl MigrationManager {
     }
 }
 
+/// Split migration SQL into executable statements
+///
+/// Comment-only lines are dropped so that a statement preceded by a
+/// `--` comment is still executed.
+fn sql_statements(sql: &str) -> Vec<String> {
+    sql.split(';')
+        .map(|statement| {
+            statement
+                .lines()
+                .filter(|line| !line.trim_start().starts_with("--"))
+                .collect::<Vec<_>>()
+                .join("\n")
+                .trim()
+                .to_string()
+        })
+        .filter(|statement| !statement.is_empty())
+        .collect()
+}
+
 /// Information about an applied migration
 #[derive(Debug, Clone)]
 pub struct AppliedMigration {
@@ -640,4 +710,39 @@ mod tests {
             panic!("Expected checksum mismatch error");
         }
     }
+
+    #[tokio::test]
+    async fn test_commented_statements_are_applied() {
+        let (pool, _temp_dir) = setup_test_db().await;
+        let mut manager = MigrationManager::new(pool.clone());
+        manager.add_migration(Migration::new(
+            1,
+            "commented",
+            "-- Create the table\nCREATE TABLE commented (id INTEGER);\n-- Seed it\nINSERT INTO commented (id) VALUES (1);",
+        ));
+        manager.migrate().await.unwrap();
+
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM commented")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(count.0, 1);
+    }
+
+    #[tokio::test]
+    async fn test_run_migrations_applies_all_embedded() {
+        let (pool, _temp_dir) = setup_test_db().await;
+        let result = MigrationManager::new(pool.clone()).run_migrations().await.unwrap();
+        assert_eq!(result.current_version, 11);
+
+        // Columns added by later migrations must exist
+        sqlx::query("SELECT file_id FROM files")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+
+        // Running again is a no-op
+        let result = MigrationManager::new(pool).run_migrations().await.unwrap();
+        assert_eq!(result.applied, 0);
+    }
 }
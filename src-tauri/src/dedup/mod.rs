@@ -0,0 +1,922 @@
+//! Duplicate and near-duplicate detection
+//!
+//! Finds indexed files that hold the same or nearly the same content:
+//! - Exact duplicates share a `content_hash`
+//! - Near-duplicate documents have nearly parallel mean chunk vectors;
+//!   candidates come from nearest-neighbour searches of their chunks
+//! - Near-duplicate images have perceptual hashes a few bits apart, found
+//!   through a BK-tree of the hashes
+//!
+//! Matches are clustered into groups stored in `duplicate_groups` and
+//! `duplicate_members`. Resolving a group keeps one file and links the
+//! others to it with a `Duplicate` relation; later detection runs leave
+//! resolved groups alone unless new files join them. Search uses
+//! `DuplicateMap` to collapse the members of a group into one result.
+
+mod phash;
+
+#[cfg(test)]
+mod tests;
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::core::error::{NeuralFSError, Result};
+use crate::core::types::relation::{RelationSource, RelationType, UserFeedback};
+use crate::vector::{VectorCollection, VectorStore};
+
+pub use phash::{dhash, dhash_file, hamming_distance, is_hashable, HashTree};
+
+/// Number of image hashes computed concurrently
+const HASH_PARALLELISM: usize = 4;
+
+/// Chunks of each document used as nearest-neighbour queries
+const CONTENT_PROBES: usize = 4;
+
+/// Neighbours fetched per probe chunk
+const CONTENT_NEIGHBOURS: usize = 16;
+
+/// How a group's members resemble each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DuplicateKind {
+    /// Byte-identical content
+    Exact,
+    /// Nearly the same text content
+    SimilarContent,
+    /// Visually near-identical images
+    SimilarImage,
+}
+
+impl DuplicateKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            DuplicateKind::Exact => "Exact",
+            DuplicateKind::SimilarContent => "SimilarContent",
+            DuplicateKind::SimilarImage => "SimilarImage",
+        }
+    }
+
+    fn parse(value: &str) -> Self {
+        match value {
+            "SimilarContent" => DuplicateKind::SimilarContent,
+            "SimilarImage" => DuplicateKind::SimilarImage,
+            _ => DuplicateKind::Exact,
+        }
+    }
+}
+
+/// Review state of a group
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DuplicateStatus {
+    /// Waiting for the user to pick a file to keep
+    Open,
+    /// A file was kept and the others linked to it
+    Resolved,
+}
+
+/// A file belonging to a duplicate group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateMember {
+    /// File ID
+    pub file_id: Uuid,
+    /// Current path
+    pub path: PathBuf,
+    /// File size
+    pub size_bytes: u64,
+    /// Best match score within the group (1.0 for identical content)
+    pub similarity: f32,
+}
+
+/// Files holding the same or nearly the same content
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateGroup {
+    /// Group ID
+    pub id: Uuid,
+    /// How the members resemble each other
+    pub kind: DuplicateKind,
+    /// Review state
+    pub status: DuplicateStatus,
+    /// File kept when the group was resolved; for an open group, the file
+    /// kept by an earlier resolution it replaced
+    pub canonical_file_id: Option<Uuid>,
+    /// Members, ordered by path
+    pub members: Vec<DuplicateMember>,
+    /// When the group was found
+    pub detected_at: DateTime<Utc>,
+    /// When the group was resolved
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl DuplicateGroup {
+    /// Space freed by keeping a single member
+    pub fn reclaimable_bytes(&self) -> u64 {
+        let total: u64 = self.members.iter().map(|m| m.size_bytes).sum();
+        let largest = self.members.iter().map(|m| m.size_bytes).max().unwrap_or(0);
+        total - largest
+    }
+}
+
+/// Duplicate detection configuration
+#[derive(Debug, Clone)]
+pub struct DedupConfig {
+    /// Compare documents by their chunk vectors
+    pub detect_similar_content: bool,
+    /// Compare images by perceptual hash
+    pub detect_similar_images: bool,
+    /// Cosine similarity of mean chunk vectors at which documents match
+    pub content_similarity_threshold: f32,
+    /// Relative difference in chunk count beyond which documents are not compared
+    pub chunk_count_tolerance: f32,
+    /// Maximum differing perceptual hash bits for images to match
+    pub max_image_distance: u32,
+}
+
+impl Default for DedupConfig {
+    fn default() -> Self {
+        Self {
+            detect_similar_content: true,
+            detect_similar_images: true,
+            content_similarity_threshold: 0.97,
+            chunk_count_tolerance: 0.2,
+            max_image_distance: 6,
+        }
+    }
+}
+
+/// Outcome of a detection run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DetectionReport {
+    /// Open groups after the run
+    pub open_groups: usize,
+    /// Groups of byte-identical files
+    pub exact_groups: usize,
+    /// Groups of nearly identical documents
+    pub similar_content_groups: usize,
+    /// Groups of nearly identical images
+    pub similar_image_groups: usize,
+    /// Image hashes computed during the run
+    pub hashed_images: usize,
+    /// Files that could not be examined (path, error)
+    pub errors: Vec<(PathBuf, String)>,
+}
+
+/// Group membership of files, used to collapse search results
+#[derive(Debug, Clone, Default)]
+pub struct DuplicateMap {
+    group_of: HashMap<Uuid, Uuid>,
+    canonical: HashMap<Uuid, Uuid>,
+}
+
+impl DuplicateMap {
+    /// Empty map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that `file_id` belongs to `group_id`
+    pub fn insert(&mut self, file_id: Uuid, group_id: Uuid) {
+        self.group_of.insert(file_id, group_id);
+    }
+
+    /// Record the file kept for a group
+    pub fn set_canonical(&mut self, group_id: Uuid, file_id: Uuid) {
+        self.canonical.insert(group_id, file_id);
+    }
+
+    /// Group a file belongs to
+    pub fn group_of(&self, file_id: &Uuid) -> Option<Uuid> {
+        self.group_of.get(file_id).copied()
+    }
+
+    /// File kept for a group, if any
+    pub fn canonical(&self, group_id: &Uuid) -> Option<Uuid> {
+        self.canonical.get(group_id).copied()
+    }
+
+    /// Whether no file belongs to a group
+    pub fn is_empty(&self) -> bool {
+        self.group_of.is_empty()
+    }
+}
+
+/// Indexed file considered for detection
+struct Candidate {
+    id: Uuid,
+    path: PathBuf,
+    content_hash: String,
+    size_bytes: u64,
+    is_image: bool,
+}
+
+/// Link between two candidates found by a detector
+struct Edge {
+    a: usize,
+    b: usize,
+    kind: DuplicateKind,
+    similarity: f32,
+}
+
+/// Disjoint sets over candidate indices
+struct UnionFind {
+    parent: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+        }
+    }
+
+    fn find(&mut self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            self.parent[i] = self.parent[self.parent[i]];
+            i = self.parent[i];
+        }
+        i
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a != b {
+            self.parent[b.max(a)] = a.min(b);
+        }
+    }
+}
+
+/// Finds, stores and resolves duplicate groups
+pub struct DuplicateService {
+    pool: SqlitePool,
+    config: DedupConfig,
+    vectors: Option<(Arc<VectorStore>, String)>,
+}
+
+impl DuplicateService {
+    /// Create a service over the index database
+    ///
+    /// Without a vector collection only exact and image duplicates are found.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self::with_config(pool, DedupConfig::default())
+    }
+
+    /// Create a service with custom configuration
+    pub fn with_config(pool: SqlitePool, config: DedupConfig) -> Self {
+        Self {
+            pool,
+            config,
+            vectors: None,
+        }
+    }
+
+    /// Compare documents using the chunk vectors of `collection`
+    pub fn with_vectors(mut self, collection: &VectorCollection) -> Self {
+        self.vectors = Some((collection.store.clone(), collection.model.key()));
+        self
+    }
+
+    /// Run detection over every indexed file and store the groups found
+    ///
+    /// Open groups from earlier runs are replaced. A resolved group is kept
+    /// as long as no new file matches its members.
+    pub async fn detect(&self) -> Result<DetectionReport> {
+        let mut report = DetectionReport::default();
+        let candidates = self.load_candidates().await?;
+
+        let mut edges = exact_edges(&candidates);
+        if self.config.detect_similar_images {
+            edges.extend(self.image_edges(&candidates, &mut report).await?);
+        }
+        if self.config.detect_similar_content {
+            if let Some((store, model_key)) = &self.vectors {
+                edges.extend(self.content_edges(&candidates, store, model_key).await?);
+            }
+        }
+
+        let groups = cluster(&candidates, &edges);
+        self.store_groups(&candidates, groups, &mut report).await?;
+
+        tracing::info!(
+            "Duplicate detection: {} open groups ({} exact, {} similar content, {} similar images)",
+            report.open_groups,
+            report.exact_groups,
+            report.similar_content_groups,
+            report.similar_image_groups
+        );
+        Ok(report)
+    }
+
+    /// Stored groups, open ones first, largest reclaimable space first
+    pub async fn groups(&self, include_resolved: bool) -> Result<Vec<DuplicateGroup>> {
+        let rows: Vec<(String,)> = sqlx::query_as(
+            "SELECT id FROM duplicate_groups WHERE ? OR status = 'Open' ORDER BY status, detected_at",
+        )
+        .bind(include_resolved)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut groups = Vec::with_capacity(rows.len());
+        for (id,) in rows {
+            let Ok(id) = Uuid::parse_str(&id) else { continue };
+            if let Some(group) = self.group(id).await? {
+                groups.push(group);
+            }
+        }
+        groups.sort_by(|a, b| {
+            (a.status == DuplicateStatus::Resolved)
+                .cmp(&(b.status == DuplicateStatus::Resolved))
+                .then(b.reclaimable_bytes().cmp(&a.reclaimable_bytes()))
+        });
+        Ok(groups)
+    }
+
+    /// A single stored group
+    pub async fn group(&self, id: Uuid) -> Result<Option<DuplicateGroup>> {
+        let row: Option<(String, String, Option<String>, String, Option<String>)> = sqlx::query_as(
+            r#"
+            SELECT kind, status, canonical_file_id, detected_at, resolved_at
+            FROM duplicate_groups
+            WHERE id = ?
+            "#,
+        )
+        .bind(id.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let Some((kind, status, canonical, detected_at, resolved_at)) = row else {
+            return Ok(None);
+        };
+
+        let members: Vec<(String, String, i64, f64)> = sqlx::query_as(
+            r#"
+            SELECT f.id, f.path, f.size_bytes, m.similarity
+            FROM duplicate_members m
+            JOIN files f ON f.id = m.file_id
+            WHERE m.group_id = ?
+            ORDER BY f.path
+            "#,
+        )
+        .bind(id.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(Some(DuplicateGroup {
+            id,
+            kind: DuplicateKind::parse(&kind),
+            status: if status == "Resolved" {
+                DuplicateStatus::Resolved
+            } else {
+                DuplicateStatus::Open
+            },
+            canonical_file_id: canonical.and_then(|c| Uuid::parse_str(&c).ok()),
+            members: members
+                .into_iter()
+                .filter_map(|(file_id, path, size_bytes, similarity)| {
+                    Some(DuplicateMember {
+                        file_id: Uuid::parse_str(&file_id).ok()?,
+                        path: PathBuf::from(path),
+                        size_bytes: size_bytes.max(0) as u64,
+                        similarity: similarity as f32,
+                    })
+                })
+                .collect(),
+            detected_at: parse_time(&detected_at).unwrap_or_else(Utc::now),
+            resolved_at: resolved_at.as_deref().and_then(parse_time),
+        }))
+    }
+
+    /// Keep `keep_file_id` and link the other members to it
+    ///
+    /// Each other member gets a confirmed `Duplicate` relation from the kept
+    /// file. No file is deleted. Returns `None` if the group does not exist.
+    pub async fn resolve(&self, group_id: Uuid, keep_file_id: Uuid) -> Result<Option<DuplicateGroup>> {
+        let Some(group) = self.group(group_id).await? else {
+            return Ok(None);
+        };
+        if !group.members.iter().any(|m| m.file_id == keep_file_id) {
+            return Err(NeuralFSError::Internal(format!(
+                "File {} is not a member of duplicate group {}",
+                keep_file_id, group_id
+            )));
+        }
+
+        let now = Utc::now().to_rfc3339();
+        let relation_type = format!("{:?}", RelationType::Duplicate);
+        let source = format!("{:?}", RelationSource::UserManual);
+        let feedback = serde_json::to_string(&UserFeedback::Confirmed)
+            .map_err(|e| NeuralFSError::Internal(e.to_string()))?;
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            r#"
+            UPDATE duplicate_groups
+            SET status = 'Resolved', canonical_file_id = ?, resolved_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(keep_file_id.to_string())
+        .bind(&now)
+        .bind(group_id.to_string())
+        .execute(&mut *tx)
+        .await?;
+
+        for member in group.members.iter().filter(|m| m.file_id != keep_file_id) {
+            sqlx::query(
+                r#"
+                INSERT INTO file_relations (id, source_file_id, target_file_id, relation_type,
+                                            strength, source, user_feedback, created_at,
+                                            updated_at, user_action_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT (source_file_id, target_file_id, relation_type) DO UPDATE SET
+                    strength = excluded.strength,
+                    user_feedback = excluded.user_feedback,
+                    updated_at = excluded.updated_at,
+                    user_action_at = excluded.user_action_at
+                "#,
+            )
+            .bind(Uuid::now_v7().to_string())
+            .bind(keep_file_id.to_string())
+            .bind(member.file_id.to_string())
+            .bind(&relation_type)
+            .bind(member.similarity)
+            .bind(&source)
+            .bind(&feedback)
+            .bind(&now)
+            .bind(&now)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        self.group(group_id).await
+    }
+
+    /// Group membership of the given files, for collapsing search results
+    pub async fn duplicate_map(&self, file_ids: &[Uuid]) -> Result<DuplicateMap> {
+        let mut map = DuplicateMap::new();
+        for chunk in file_ids.chunks(500) {
+            let placeholders = vec!["?"; chunk.len()].join(", ");
+            let sql = format!(
+                r#"
+                SELECT m.file_id, g.id, g.canonical_file_id
+                FROM duplicate_members m
+                JOIN duplicate_groups g ON g.id = m.group_id
+                WHERE m.file_id IN ({})
+                "#,
+                placeholders
+            );
+            let mut query = sqlx::query_as::<_, (String, String, Option<String>)>(&sql);
+            for id in chunk {
+                query = query.bind(id.to_string());
+            }
+
+            for (file_id, group_id, canonical) in query.fetch_all(&self.pool).await? {
+                let (Ok(file_id), Ok(group_id)) = (Uuid::parse_str(&file_id), Uuid::parse_str(&group_id)) else {
+                    continue;
+                };
+                map.insert(file_id, group_id);
+                if let Some(canonical) = canonical.and_then(|c| Uuid::parse_str(&c).ok()) {
+                    map.set_canonical(group_id, canonical);
+                }
+            }
+        }
+        Ok(map)
+    }
+
+    async fn load_candidates(&self) -> Result<Vec<Candidate>> {
+        let rows: Vec<(String, String, String, i64, String)> = sqlx::query_as(
+            r#"
+            SELECT id, path, content_hash, size_bytes, file_type
+            FROM files
+            WHERE index_status = 'Indexed' AND is_excluded = 0 AND size_bytes > 0
+            ORDER BY path
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(id, path, content_hash, size_bytes, file_type)| {
+                Some(Candidate {
+                    id: Uuid::parse_str(&id).ok()?,
+                    path: PathBuf::from(path),
+                    content_hash,
+                    size_bytes: size_bytes as u64,
+                    is_image: file_type == "Image",
+                })
+            })
+            .collect())
+    }
+
+    /// Link images whose perceptual hashes are within `max_image_distance`
+    ///
+    /// Hashes are cached in `image_hashes` and recomputed when the file's
+    /// content hash changes.
+    async fn image_edges(&self, candidates: &[Candidate], report: &mut DetectionReport) -> Result<Vec<Edge>> {
+        let cached: HashMap<String, (String, i64)> = sqlx::query_as::<_, (String, String, i64)>(
+            "SELECT file_id, content_hash, hash FROM image_hashes",
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|(file_id, content_hash, hash)| (file_id, (content_hash, hash)))
+        .collect();
+
+        let images = representatives(candidates, |c| c.is_image && is_hashable(&c.path));
+        let mut hashes: Vec<(usize, u64)> = Vec::with_capacity(images.len());
+        let mut stale = Vec::new();
+        for &i in &images {
+            match cached.get(&candidates[i].id.to_string()) {
+                Some((content_hash, hash)) if *content_hash == candidates[i].content_hash => {
+                    hashes.push((i, *hash as u64));
+                }
+                _ => stale.push(i),
+            }
+        }
+
+        let computed: Vec<(usize, Result<u64>)> = stream::iter(stale)
+            .map(|i| {
+                let path = candidates[i].path.clone();
+                async move {
+                    let hash = tokio::task::spawn_blocking(move || dhash_file(&path))
+                        .await
+                        .unwrap_or_else(|e| Err(NeuralFSError::Internal(e.to_string())));
+                    (i, hash)
+                }
+            })
+            .buffer_unordered(HASH_PARALLELISM)
+            .collect()
+            .await;
+
+        let now = Utc::now().to_rfc3339();
+        for (i, hash) in computed {
+            match hash {
+                Ok(hash) => {
+                    sqlx::query(
+                        r#"
+                        INSERT INTO image_hashes (file_id, content_hash, hash, computed_at)
+                        VALUES (?, ?, ?, ?)
+                        ON CONFLICT (file_id) DO UPDATE SET
+                            content_hash = excluded.content_hash,
+                            hash = excluded.hash,
+                            computed_at = excluded.computed_at
+                        "#,
+                    )
+                    .bind(candidates[i].id.to_string())
+                    .bind(&candidates[i].content_hash)
+                    .bind(hash as i64)
+                    .bind(&now)
+                    .execute(&self.pool)
+                    .await?;
+                    report.hashed_images += 1;
+                    hashes.push((i, hash));
+                }
+                Err(e) => report.errors.push((candidates[i].path.clone(), e.to_string())),
+            }
+        }
+
+        // Each image is matched against those inserted before it
+        let mut tree = HashTree::new();
+        let mut edges = Vec::new();
+        for (b, hash) in hashes {
+            for (a, distance) in tree.within(hash, self.config.max_image_distance) {
+                edges.push(Edge {
+                    a,
+                    b,
+                    kind: DuplicateKind::SimilarImage,
+                    similarity: 1.0 - distance as f32 / 64.0,
+                });
+            }
+            tree.insert(hash, b);
+        }
+        Ok(edges)
+    }
+
+    /// Link documents whose mean chunk vectors are nearly parallel
+    ///
+    /// A few chunks of each document are searched in the vector store; only
+    /// documents owning one of their nearest neighbours are compared, and
+    /// only if their chunk counts are similar, since near-identical
+    /// documents split into about the same number of chunks.
+    async fn content_edges(&self, candidates: &[Candidate], store: &VectorStore, model_key: &str) -> Result<Vec<Edge>> {
+        // Rows from before model tagging belong to the active model
+        let rows: Vec<(String, i64)> = sqlx::query_as(
+            r#"
+            SELECT file_id, vector_id
+            FROM content_chunks
+            WHERE embedding_model IS NULL OR embedding_model = ?
+            ORDER BY file_id
+            "#,
+        )
+        .bind(model_key)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut vector_ids: HashMap<String, Vec<u64>> = HashMap::new();
+        for (file_id, vector_id) in rows {
+            vector_ids.entry(file_id).or_default().push(vector_id as u64);
+        }
+
+        // Documents by candidate index, and the document owning each vector
+        let mut documents: HashMap<usize, (usize, Vec<f32>)> = HashMap::new();
+        let mut probes: Vec<(usize, Vec<f32>)> = Vec::new();
+        let mut owner: HashMap<u64, usize> = HashMap::new();
+        for i in representatives(candidates, |c| !c.is_image) {
+            let Some(ids) = vector_ids.get(&candidates[i].id.to_string()) else { continue };
+            let vectors = store
+                .get_batch(ids)
+                .await
+                .map_err(|e| NeuralFSError::Internal(e.to_string()))?;
+            if let Some(mean) = mean_vector(vectors.iter().filter_map(|v| v.vector.as_deref())) {
+                owner.extend(ids.iter().map(|&id| (id, i)));
+                probes.extend(
+                    vectors
+                        .into_iter()
+                        .filter_map(|v| v.vector)
+                        .take(CONTENT_PROBES)
+                        .map(|vector| (i, vector)),
+                );
+                documents.insert(i, (ids.len(), mean));
+            }
+        }
+
+        let mut pairs: HashSet<(usize, usize)> = HashSet::new();
+        for (i, vector) in probes {
+            let neighbours = store
+                .search(&vector, CONTENT_NEIGHBOURS, None)
+                .await
+                .map_err(|e| NeuralFSError::Internal(e.to_string()))?;
+            for neighbour in neighbours {
+                match owner.get(&neighbour.id) {
+                    Some(&j) if j != i => {
+                        pairs.insert((i.min(j), i.max(j)));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut pairs: Vec<(usize, usize)> = pairs.into_iter().collect();
+        pairs.sort_unstable();
+        let mut edges = Vec::new();
+        for (a, b) in pairs {
+            let ((chunks_a, mean_a), (chunks_b, mean_b)) = (&documents[&a], &documents[&b]);
+            let (fewer, more) = (*chunks_a.min(chunks_b), *chunks_a.max(chunks_b));
+            if more > (fewer as f32 * (1.0 + self.config.chunk_count_tolerance)).ceil() as usize {
+                continue;
+            }
+            let similarity = dot(mean_a, mean_b);
+            if similarity >= self.config.content_similarity_threshold {
+                edges.push(Edge {
+                    a,
+                    b,
+                    kind: DuplicateKind::SimilarContent,
+                    similarity: similarity.min(1.0),
+                });
+            }
+        }
+        Ok(edges)
+    }
+
+    /// Replace open groups with the clusters found
+    ///
+    /// A cluster matching a resolved group exactly is skipped. A cluster
+    /// that overlaps a resolved group otherwise replaces it, suggesting the
+    /// file kept before.
+    async fn store_groups(
+        &self,
+        candidates: &[Candidate],
+        groups: Vec<(DuplicateKind, Vec<(usize, f32)>)>,
+        report: &mut DetectionReport,
+    ) -> Result<()> {
+        let resolved: Vec<(String, String, Option<String>)> = sqlx::query_as(
+            r#"
+            SELECT m.group_id, m.file_id, g.canonical_file_id
+            FROM duplicate_members m
+            JOIN duplicate_groups g ON g.id = m.group_id
+            WHERE g.status = 'Resolved'
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut resolved_members: HashMap<String, HashSet<String>> = HashMap::new();
+        let mut resolved_group_of: HashMap<String, String> = HashMap::new();
+        let mut resolved_canonical: HashMap<String, String> = HashMap::new();
+        for (group_id, file_id, canonical) in resolved {
+            resolved_group_of.insert(file_id.clone(), group_id.clone());
+            if let Some(canonical) = canonical {
+                resolved_canonical.insert(group_id.clone(), canonical);
+            }
+            resolved_members.entry(group_id).or_default().insert(file_id);
+        }
+
+        let now = Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM duplicate_groups WHERE status = 'Open'")
+            .execute(&mut *tx)
+            .await?;
+
+        // Resolved groups whose members were deleted down to one file
+        sqlx::query(
+            r#"
+            DELETE FROM duplicate_groups
+            WHERE (SELECT COUNT(*) FROM duplicate_members m WHERE m.group_id = duplicate_groups.id) < 2
+            "#,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        for (kind, members) in groups {
+            let ids: Vec<String> = members.iter().map(|(i, _)| candidates[*i].id.to_string()).collect();
+            let already_resolved = resolved_group_of
+                .get(&ids[0])
+                .and_then(|group_id| resolved_members.get(group_id))
+                .map(|files| files.len() == ids.len() && ids.iter().all(|id| files.contains(id)))
+                .unwrap_or(false);
+            if already_resolved {
+                continue;
+            }
+
+            let superseded: HashSet<&String> = ids.iter().filter_map(|id| resolved_group_of.get(id)).collect();
+            let suggested = superseded
+                .iter()
+                .filter_map(|group_id| resolved_canonical.get(*group_id))
+                .find(|canonical| ids.contains(canonical));
+            for group_id in &superseded {
+                sqlx::query("DELETE FROM duplicate_groups WHERE id = ?")
+                    .bind(*group_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+
+            let group_id = Uuid::now_v7().to_string();
+            sqlx::query(
+                r#"
+                INSERT INTO duplicate_groups (id, kind, status, canonical_file_id, detected_at)
+                VALUES (?, ?, 'Open', ?, ?)
+                "#,
+            )
+            .bind(&group_id)
+            .bind(kind.as_str())
+            .bind(suggested.cloned())
+            .bind(&now)
+            .execute(&mut *tx)
+            .await?;
+
+            for (id, (_, similarity)) in ids.iter().zip(&members) {
+                sqlx::query("INSERT INTO duplicate_members (group_id, file_id, similarity) VALUES (?, ?, ?)")
+                    .bind(&group_id)
+                    .bind(id)
+                    .bind(*similarity)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+
+            report.open_groups += 1;
+            match kind {
+                DuplicateKind::Exact => report.exact_groups += 1,
+                DuplicateKind::SimilarContent => report.similar_content_groups += 1,
+                DuplicateKind::SimilarImage => report.similar_image_groups += 1,
+            }
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+/// Link candidates with the same content hash and size
+fn exact_edges(candidates: &[Candidate]) -> Vec<Edge> {
+    let mut first_with: HashMap<(&str, u64), usize> = HashMap::new();
+    let mut edges = Vec::new();
+    for (i, candidate) in candidates.iter().enumerate() {
+        match first_with.get(&(candidate.content_hash.as_str(), candidate.size_bytes)) {
+            Some(&first) => edges.push(Edge {
+                a: first,
+                b: i,
+                kind: DuplicateKind::Exact,
+                similarity: 1.0,
+            }),
+            None => {
+                first_with.insert((candidate.content_hash.as_str(), candidate.size_bytes), i);
+            }
+        }
+    }
+    edges
+}
+
+/// One candidate per distinct content among those matching `filter`
+///
+/// Exact copies are already linked, so near-duplicate detectors only need
+/// to compare each content once.
+fn representatives(candidates: &[Candidate], filter: impl Fn(&Candidate) -> bool) -> Vec<usize> {
+    let mut seen = HashSet::new();
+    candidates
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| filter(c) && seen.insert((c.content_hash.as_str(), c.size_bytes)))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Cluster linked candidates into groups of two or more
+///
+/// A group is `Exact` when all members share one content hash, otherwise
+/// it takes the kind of its near-duplicate links (images win over text).
+/// Each member's similarity is the best near-duplicate link of its content,
+/// or 1.0 in an exact group.
+fn cluster(candidates: &[Candidate], edges: &[Edge]) -> Vec<(DuplicateKind, Vec<(usize, f32)>)> {
+    let mut sets = UnionFind::new(candidates.len());
+    let mut best_link: HashMap<(&str, u64), f32> = HashMap::new();
+    for edge in edges {
+        sets.union(edge.a, edge.b);
+        if edge.kind != DuplicateKind::Exact {
+            for i in [edge.a, edge.b] {
+                let key = (candidates[i].content_hash.as_str(), candidates[i].size_bytes);
+                let best = best_link.entry(key).or_insert(0.0);
+                *best = best.max(edge.similarity);
+            }
+        }
+    }
+
+    let mut image_roots = HashSet::new();
+    for edge in edges.iter().filter(|e| e.kind == DuplicateKind::SimilarImage) {
+        image_roots.insert(sets.find(edge.a));
+    }
+
+    let mut components: HashMap<usize, Vec<usize>> = HashMap::new();
+    for i in 0..candidates.len() {
+        components.entry(sets.find(i)).or_default().push(i);
+    }
+
+    let mut groups: Vec<(usize, DuplicateKind, Vec<(usize, f32)>)> = components
+        .into_iter()
+        .filter(|(_, members)| members.len() > 1)
+        .map(|(root, members)| {
+            let first = &candidates[members[0]];
+            let exact = members
+                .iter()
+                .all(|&i| candidates[i].content_hash == first.content_hash && candidates[i].size_bytes == first.size_bytes);
+            let kind = if exact {
+                DuplicateKind::Exact
+            } else if image_roots.contains(&root) {
+                DuplicateKind::SimilarImage
+            } else {
+                DuplicateKind::SimilarContent
+            };
+            let members = members
+                .into_iter()
+                .map(|i| {
+                    let key = (candidates[i].content_hash.as_str(), candidates[i].size_bytes);
+                    let similarity = if exact { 1.0 } else { best_link.get(&key).copied().unwrap_or(1.0) };
+                    (i, similarity)
+                })
+                .collect();
+            (root, kind, members)
+        })
+        .collect();
+
+    // Deterministic order: by the first member's path
+    groups.sort_by_key(|(root, _, _)| *root);
+    groups.into_iter().map(|(_, kind, members)| (kind, members)).collect()
+}
+
+/// Normalized mean of a file's chunk vectors
+fn mean_vector<'a>(vectors: impl Iterator<Item = &'a [f32]>) -> Option<Vec<f32>> {
+    let mut sum: Vec<f32> = Vec::new();
+    for vector in vectors {
+        if sum.is_empty() {
+            sum = vec![0.0; vector.len()];
+        }
+        if vector.len() != sum.len() {
+            continue;
+        }
+        for (total, value) in sum.iter_mut().zip(vector) {
+            *total += value;
+        }
+    }
+
+    let norm = dot(&sum, &sum).sqrt();
+    if norm <= f32::EPSILON {
+        return None;
+    }
+    Some(sum.into_iter().map(|v| v / norm).collect())
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
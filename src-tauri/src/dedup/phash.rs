@@ -0,0 +1,139 @@
+//! Perceptual image hashes
+//!
+//! A difference hash (dHash) compares the brightness of neighbouring pixels
+//! in a 9x8 grayscale thumbnail, giving 64 bits that survive resizing,
+//! re-encoding and small edits. Images whose hashes differ in only a few
+//! bits look alike.
+
+use std::path::Path;
+
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat};
+
+use crate::core::error::{FileSystemError, NeuralFSError, Result};
+
+/// Thumbnail width; one column more than the 8 compared pairs per row
+const HASH_WIDTH: u32 = 9;
+/// Thumbnail height
+const HASH_HEIGHT: u32 = 8;
+
+/// Difference hash of a decoded image
+pub fn dhash(image: &DynamicImage) -> u64 {
+    let thumb = image
+        .resize_exact(HASH_WIDTH, HASH_HEIGHT, FilterType::Triangle)
+        .to_luma8();
+
+    let mut hash = 0u64;
+    for y in 0..HASH_HEIGHT {
+        for x in 0..HASH_WIDTH - 1 {
+            hash <<= 1;
+            if thumb.get_pixel(x, y)[0] < thumb.get_pixel(x + 1, y)[0] {
+                hash |= 1;
+            }
+        }
+    }
+    hash
+}
+
+/// Difference hash of an image file
+pub fn dhash_file(path: &Path) -> Result<u64> {
+    let image = image::open(path).map_err(|e| {
+        NeuralFSError::FileSystem(FileSystemError::ReadFailed {
+            path: path.display().to_string(),
+            reason: e.to_string(),
+        })
+    })?;
+    Ok(dhash(&image))
+}
+
+/// Whether the file is a raster format that can be decoded for hashing
+pub fn is_hashable(path: &Path) -> bool {
+    ImageFormat::from_path(path)
+        .map(|format| format.can_read())
+        .unwrap_or(false)
+}
+
+/// Number of differing bits between two hashes
+pub fn hamming_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+/// BK-tree of hashes under Hamming distance
+///
+/// Finds every hash within a radius without comparing all pairs: a subtree
+/// is searched only if its edge distance could hold a match, by the
+/// triangle inequality.
+#[derive(Debug, Default)]
+pub struct HashTree {
+    nodes: Vec<HashNode>,
+}
+
+#[derive(Debug)]
+struct HashNode {
+    hash: u64,
+    item: usize,
+    /// Child nodes keyed by their distance to this node
+    children: Vec<(u32, usize)>,
+}
+
+impl HashTree {
+    /// Empty tree
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `item` with its hash
+    pub fn insert(&mut self, hash: u64, item: usize) {
+        let new = self.nodes.len();
+        self.nodes.push(HashNode {
+            hash,
+            item,
+            children: Vec::new(),
+        });
+        if new == 0 {
+            return;
+        }
+
+        let mut node = 0;
+        loop {
+            let distance = hamming_distance(self.nodes[node].hash, hash);
+            match self.nodes[node].children.iter().find(|(d, _)| *d == distance) {
+                Some(&(_, child)) => node = child,
+                None => {
+                    self.nodes[node].children.push((distance, new));
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Items whose hash is at most `radius` bits from `hash`, with their distance
+    pub fn within(&self, hash: u64, radius: u32) -> Vec<(usize, u32)> {
+        let mut found = Vec::new();
+        let mut pending = if self.nodes.is_empty() { vec![] } else { vec![0] };
+        while let Some(node) = pending.pop() {
+            let node = &self.nodes[node];
+            let distance = hamming_distance(node.hash, hash);
+            if distance <= radius {
+                found.push((node.item, distance));
+            }
+            pending.extend(
+                node.children
+                    .iter()
+                    .filter(|(d, _)| d.abs_diff(distance) <= radius)
+                    .map(|(_, child)| *child),
+            );
+        }
+        found
+    }
+
+    /// Number of hashes in the tree
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Whether the tree is empty
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
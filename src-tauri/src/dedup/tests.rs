@@ -0,0 +1,277 @@
+//! Tests for duplicate detection
+
+use super::*;
+use crate::embeddings::EmbeddingModelTag;
+use crate::vector::VectorStoreConfig;
+use image::{ImageBuffer, Rgb};
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Helper to create a test database
+async fn create_test_db() -> (SqlitePool, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+
+    let config = crate::db::DatabaseConfig::with_path(db_path).with_wal(true);
+    let pool = crate::db::create_database_pool(&config).await.unwrap();
+
+    let migration_manager = crate::db::migration::MigrationManager::new(pool.clone());
+    migration_manager.run_migrations().await.unwrap();
+
+    (pool, temp_dir)
+}
+
+/// Helper to add an indexed file row with the given content
+async fn insert_file(pool: &SqlitePool, path: &Path, content: &[u8], file_type: &str) -> Uuid {
+    let id = Uuid::now_v7();
+    let now = Utc::now().to_rfc3339();
+    sqlx::query(
+        r#"
+        INSERT INTO files (id, path, filename, file_type, size_bytes, content_hash,
+                           created_at, modified_at, indexed_at, index_status)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, 'Indexed')
+        "#,
+    )
+    .bind(id.to_string())
+    .bind(path.to_string_lossy().to_string())
+    .bind(path.file_name().unwrap().to_string_lossy().to_string())
+    .bind(file_type)
+    .bind(content.len() as i64)
+    .bind(blake3::hash(content).to_hex().to_string())
+    .bind(&now)
+    .bind(&now)
+    .bind(&now)
+    .execute(pool)
+    .await
+    .unwrap();
+    id
+}
+
+/// Helper to write a file to disk and index it
+async fn add_file(pool: &SqlitePool, dir: &Path, name: &str, content: &[u8], file_type: &str) -> Uuid {
+    let path = dir.join(name);
+    std::fs::write(&path, content).unwrap();
+    insert_file(pool, &path, content, file_type).await
+}
+
+/// Helper to store chunk vectors for a file
+async fn add_chunks(pool: &SqlitePool, store: &VectorStore, file_id: Uuid, vectors: &[Vec<f32>]) {
+    for (index, vector) in vectors.iter().enumerate() {
+        let vector_id = store.insert(vector.clone(), HashMap::new()).await.unwrap();
+        sqlx::query(
+            r#"
+            INSERT INTO content_chunks (id, file_id, chunk_index, chunk_type, content,
+                                        start_offset, end_offset, vector_id, created_at)
+            VALUES (?, ?, ?, 'Paragraph', '', 0, 0, ?, ?)
+            "#,
+        )
+        .bind(Uuid::now_v7().to_string())
+        .bind(file_id.to_string())
+        .bind(index as i64)
+        .bind(vector_id as i64)
+        .bind(Utc::now().to_rfc3339())
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+}
+
+/// Helper to encode a horizontal gradient as PNG, optionally darkened
+fn gradient_png(width: u32, height: u32, darken: u8, reverse: bool) -> Vec<u8> {
+    let image = ImageBuffer::from_fn(width, height, |x, _| {
+        let mut value = (x * 255 / (width - 1)) as u8;
+        if reverse {
+            value = 255 - value;
+        }
+        let value = value.saturating_sub(darken);
+        Rgb([value, value, value])
+    });
+    let mut bytes = std::io::Cursor::new(Vec::new());
+    image.write_to(&mut bytes, image::ImageOutputFormat::Png).unwrap();
+    bytes.into_inner()
+}
+
+#[tokio::test]
+async fn test_detect_groups_exact_duplicates() {
+    let (pool, _db_dir) = create_test_db().await;
+    let files_dir = TempDir::new().unwrap();
+
+    let a = add_file(&pool, files_dir.path(), "a.txt", b"same content", "TextDocument").await;
+    let b = add_file(&pool, files_dir.path(), "b.txt", b"same content", "TextDocument").await;
+    add_file(&pool, files_dir.path(), "c.txt", b"other content", "TextDocument").await;
+
+    let service = DuplicateService::new(pool);
+    let report = service.detect().await.unwrap();
+    assert_eq!(report.open_groups, 1);
+    assert_eq!(report.exact_groups, 1);
+
+    let groups = service.groups(false).await.unwrap();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].kind, DuplicateKind::Exact);
+    assert_eq!(groups[0].status, DuplicateStatus::Open);
+    let members: Vec<Uuid> = groups[0].members.iter().map(|m| m.file_id).collect();
+    assert_eq!(members, vec![a, b]);
+    assert_eq!(groups[0].reclaimable_bytes(), 12);
+
+    // Running again replaces the open group instead of adding another
+    service.detect().await.unwrap();
+    assert_eq!(service.groups(true).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_resolve_links_members_and_survives_detection() {
+    let (pool, _db_dir) = create_test_db().await;
+    let files_dir = TempDir::new().unwrap();
+
+    let keep = add_file(&pool, files_dir.path(), "keep.txt", b"copy", "TextDocument").await;
+    let copy = add_file(&pool, files_dir.path(), "copy.txt", b"copy", "TextDocument").await;
+
+    let service = DuplicateService::new(pool.clone());
+    service.detect().await.unwrap();
+    let group = service.groups(false).await.unwrap().remove(0);
+
+    // Only members can be kept
+    assert!(service.resolve(group.id, Uuid::now_v7()).await.is_err());
+
+    let resolved = service.resolve(group.id, keep).await.unwrap().unwrap();
+    assert_eq!(resolved.status, DuplicateStatus::Resolved);
+    assert_eq!(resolved.canonical_file_id, Some(keep));
+    assert!(resolved.resolved_at.is_some());
+
+    let relation: (String, String, String) = sqlx::query_as(
+        "SELECT source_file_id, target_file_id, relation_type FROM file_relations",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(relation, (keep.to_string(), copy.to_string(), "Duplicate".to_string()));
+
+    // A resolved group is not proposed again
+    let report = service.detect().await.unwrap();
+    assert_eq!(report.open_groups, 0);
+    assert!(service.groups(false).await.unwrap().is_empty());
+    assert_eq!(service.groups(true).await.unwrap().len(), 1);
+
+    // A new copy reopens the question
+    let third = add_file(&pool, files_dir.path(), "third.txt", b"copy", "TextDocument").await;
+    let report = service.detect().await.unwrap();
+    assert_eq!(report.open_groups, 1);
+    let open = service.groups(false).await.unwrap();
+    assert!(open[0].members.iter().any(|m| m.file_id == third));
+
+    // It replaces the resolved group and suggests the file kept before
+    assert_eq!(service.groups(true).await.unwrap().len(), 1);
+    assert_eq!(open[0].canonical_file_id, Some(keep));
+    let map = service.duplicate_map(&[keep, copy, third]).await.unwrap();
+    assert_eq!(map.group_of(&keep), Some(open[0].id));
+    assert_eq!(map.group_of(&third), Some(open[0].id));
+    assert_eq!(map.canonical(&open[0].id), Some(keep));
+}
+
+#[tokio::test]
+async fn test_detect_similar_images_by_perceptual_hash() {
+    let (pool, _db_dir) = create_test_db().await;
+    let files_dir = TempDir::new().unwrap();
+
+    // The same picture at another size and slightly darker, plus a mirrored one
+    let original = add_file(&pool, files_dir.path(), "photo.png", &gradient_png(64, 48, 0, false), "Image").await;
+    let resized = add_file(&pool, files_dir.path(), "photo_small.png", &gradient_png(32, 24, 10, false), "Image").await;
+    add_file(&pool, files_dir.path(), "mirrored.png", &gradient_png(64, 48, 0, true), "Image").await;
+
+    let service = DuplicateService::new(pool.clone());
+    let report = service.detect().await.unwrap();
+    assert_eq!(report.hashed_images, 3);
+    assert!(report.errors.is_empty());
+    assert_eq!(report.similar_image_groups, 1);
+
+    let groups = service.groups(false).await.unwrap();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].kind, DuplicateKind::SimilarImage);
+    let mut members: Vec<Uuid> = groups[0].members.iter().map(|m| m.file_id).collect();
+    members.sort();
+    let mut expected = vec![original, resized];
+    expected.sort();
+    assert_eq!(members, expected);
+
+    // Hashes are cached until the content changes
+    let report = service.detect().await.unwrap();
+    assert_eq!(report.hashed_images, 0);
+}
+
+#[tokio::test]
+async fn test_detect_similar_content_by_chunk_vectors() {
+    let (pool, _db_dir) = create_test_db().await;
+    let files_dir = TempDir::new().unwrap();
+    let store_dir = TempDir::new().unwrap();
+    let store = Arc::new(
+        VectorStore::new(
+            VectorStoreConfig::default()
+                .with_storage_path(store_dir.path().to_string_lossy().to_string())
+                .with_vector_size(4),
+        )
+        .await
+        .unwrap(),
+    );
+    let collection = VectorCollection::new(EmbeddingModelTag::new("mock", "1", 4), store.clone());
+
+    let draft = add_file(&pool, files_dir.path(), "draft.md", b"first draft", "TextDocument").await;
+    let final_copy = add_file(&pool, files_dir.path(), "final.md", b"final draft!", "TextDocument").await;
+    let unrelated = add_file(&pool, files_dir.path(), "notes.md", b"shopping list", "TextDocument").await;
+    add_chunks(&pool, &store, draft, &[vec![1.0, 0.0, 0.0, 0.0], vec![0.0, 1.0, 0.0, 0.0]]).await;
+    add_chunks(&pool, &store, final_copy, &[vec![1.0, 0.05, 0.0, 0.0], vec![0.0, 1.0, 0.0, 0.0]]).await;
+    add_chunks(&pool, &store, unrelated, &[vec![0.0, 0.0, 1.0, 0.0], vec![0.0, 0.0, 0.0, 1.0]]).await;
+
+    // Without vectors only exact matches are found
+    let report = DuplicateService::new(pool.clone()).detect().await.unwrap();
+    assert_eq!(report.open_groups, 0);
+
+    let service = DuplicateService::new(pool).with_vectors(&collection);
+    let report = service.detect().await.unwrap();
+    assert_eq!(report.similar_content_groups, 1);
+
+    let groups = service.groups(false).await.unwrap();
+    assert_eq!(groups[0].kind, DuplicateKind::SimilarContent);
+    let members: Vec<Uuid> = groups[0].members.iter().map(|m| m.file_id).collect();
+    assert_eq!(members, vec![draft, final_copy]);
+    assert!(groups[0].members.iter().all(|m| m.similarity > 0.97 && m.similarity <= 1.0));
+}
+
+#[test]
+fn test_dhash_tolerates_resizing() {
+    let decode = |bytes: Vec<u8>| image::load_from_memory(&bytes).unwrap();
+    let large = dhash(&decode(gradient_png(90, 80, 0, false)));
+    let small = dhash(&decode(gradient_png(18, 16, 0, false)));
+    let mirrored = dhash(&decode(gradient_png(90, 80, 0, true)));
+
+    assert!(hamming_distance(large, small) <= 2);
+    assert!(hamming_distance(large, mirrored) > 32);
+}
+
+#[test]
+fn test_hash_tree_matches_pairwise_search() {
+    // Deterministic spread of hashes, some only a few bits apart
+    let mut hashes = Vec::new();
+    let mut state = 0x9E37_79B9_7F4A_7C15u64;
+    for _ in 0..200 {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        hashes.push(state);
+        hashes.push(state ^ (1 << (state % 64)) ^ (1 << ((state >> 8) % 64)));
+    }
+
+    let mut tree = HashTree::new();
+    for (item, &hash) in hashes.iter().enumerate() {
+        tree.insert(hash, item);
+    }
+    assert_eq!(tree.len(), hashes.len());
+
+    for &query in hashes.iter().step_by(7) {
+        let mut found: Vec<usize> = tree.within(query, 6).into_iter().map(|(item, _)| item).collect();
+        found.sort_unstable();
+        let expected: Vec<usize> = (0..hashes.len())
+            .filter(|&item| hamming_distance(hashes[item], query) <= 6)
+            .collect();
+        assert_eq!(found, expected);
+    }
+}
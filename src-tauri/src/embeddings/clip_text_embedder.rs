@@ -0,0 +1,227 @@
+//! CLIP Text Embedder
+//!
+//! Implements the text tower of CLIP so that natural-language queries can be
+//! projected into the same space as `ImageEmbedder` output. This is what makes
+//! text-to-image search ("whiteboard photo with diagrams") possible.
+
+use std::path::Path;
+use std::sync::Arc;
+use ndarray::Array2;
+use ort::Value;
+use tokenizers::Tokenizer;
+
+use super::config::ClipTextEmbeddingConfig;
+use super::error::{EmbeddingError, EmbeddingResult};
+use super::model_manager::ModelHandle;
+
+/// CLIP end-of-text token, also used for padding
+const CLIP_PAD_TOKEN: &str = "<|endoftext|>";
+
+/// Token ID of `<|endoftext|>` in the CLIP BPE vocabulary
+const CLIP_PAD_ID: u32 = 49407;
+
+/// Text embedder for the CLIP text tower
+pub struct ClipTextEmbedder {
+    /// Model handle
+    model_handle: Arc<ModelHandle>,
+
+    /// CLIP BPE tokenizer loaded from tokenizer.json
+    tokenizer: Tokenizer,
+
+    /// Configuration
+    config: ClipTextEmbeddingConfig,
+}
+
+impl ClipTextEmbedder {
+    /// Create a new CLIP text embedder with the given model handle
+    ///
+    /// # Arguments
+    /// * `model_handle` - The ONNX model handle for the text tower
+    /// * `tokenizer_path` - Path to the CLIP tokenizer.json file
+    /// * `config` - CLIP text configuration
+    pub fn new(
+        model_handle: Arc<ModelHandle>,
+        tokenizer_path: &Path,
+        config: ClipTextEmbeddingConfig,
+    ) -> EmbeddingResult<Self> {
+        let tokenizer = Tokenizer::from_file(tokenizer_path)
+            .map_err(|e| EmbeddingError::ModelLoadFailed {
+                reason: format!("Failed to load CLIP tokenizer from {:?}: {}", tokenizer_path, e),
+            })?;
+
+        Ok(Self {
+            model_handle,
+            tokenizer,
+            config,
+        })
+    }
+
+    /// Embed a single query string into the image embedding space
+    pub async fn embed(&self, text: &str) -> EmbeddingResult<Vec<f32>> {
+        let embeddings = self.batch_embed(&[text]).await?;
+        Ok(embeddings.into_iter().next().unwrap_or_default())
+    }
+
+    /// Embed multiple query strings in a batch
+    pub async fn batch_embed(&self, texts: &[&str]) -> EmbeddingResult<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let (input_ids, attention_mask) = self.tokenize_batch(texts)?;
+
+        let session = self.model_handle.session.clone();
+        let embedding_dim = self.config.embedding_dim;
+
+        tokio::task::spawn_blocking(move || {
+            Self::run_inference_sync(&session, input_ids, attention_mask, embedding_dim)
+        })
+        .await
+        .map_err(|e| EmbeddingError::InferenceFailed {
+            reason: format!("Task join error: {}", e),
+        })?
+    }
+
+    /// Tokenize texts with fixed-length padding to the CLIP context length
+    fn tokenize_batch(&self, texts: &[&str]) -> EmbeddingResult<(Vec<Vec<i64>>, Vec<Vec<i64>>)> {
+        let max_len = self.config.max_seq_length;
+
+        let mut tokenizer = self.tokenizer.clone();
+        tokenizer.with_truncation(Some(tokenizers::TruncationParams {
+            max_length: max_len,
+            strategy: tokenizers::TruncationStrategy::LongestFirst,
+            ..Default::default()
+        })).map_err(|e| EmbeddingError::TokenizationFailed {
+            reason: format!("Failed to set truncation: {}", e),
+        })?;
+
+        tokenizer.with_padding(Some(tokenizers::PaddingParams {
+            strategy: tokenizers::PaddingStrategy::Fixed(max_len),
+            pad_id: CLIP_PAD_ID,
+            pad_token: CLIP_PAD_TOKEN.to_string(),
+            ..Default::default()
+        }));
+
+        let encodings = tokenizer.encode_batch(texts.to_vec(), true)
+            .map_err(|e| EmbeddingError::TokenizationFailed {
+                reason: format!("Batch encoding failed: {}", e),
+            })?;
+
+        let mut input_ids = Vec::with_capacity(texts.len());
+        let mut attention_mask = Vec::with_capacity(texts.len());
+
+        for encoding in encodings {
+            let mut padded_ids = vec![CLIP_PAD_ID as i64; max_len];
+            let mut padded_mask = vec![0i64; max_len];
+
+            let ids = encoding.get_ids();
+            let mask = encoding.get_attention_mask();
+            let copy_len = ids.len().min(max_len);
+            for i in 0..copy_len {
+                padded_ids[i] = ids[i] as i64;
+                padded_mask[i] = mask[i] as i64;
+            }
+
+            input_ids.push(padded_ids);
+            attention_mask.push(padded_mask);
+        }
+
+        Ok((input_ids, attention_mask))
+    }
+
+    /// Synchronous inference (runs in blocking task)
+    fn run_inference_sync(
+        session: &ort::Session,
+        input_ids: Vec<Vec<i64>>,
+        attention_mask: Vec<Vec<i64>>,
+        embedding_dim: usize,
+    ) -> EmbeddingResult<Vec<Vec<f32>>> {
+        let batch_size = input_ids.len();
+        let seq_len = input_ids[0].len();
+
+        let input_ids_array = Array2::from_shape_vec(
+            (batch_size, seq_len),
+            input_ids.into_iter().flatten().collect(),
+        )
+        .map_err(|e| EmbeddingError::InferenceFailed {
+            reason: format!("Failed to create input_ids array: {}", e),
+        })?;
+
+        let attention_mask_array = Array2::from_shape_vec(
+            (batch_size, seq_len),
+            attention_mask.into_iter().flatten().collect(),
+        )
+        .map_err(|e| EmbeddingError::InferenceFailed {
+            reason: format!("Failed to create attention_mask array: {}", e),
+        })?;
+
+        let input_ids_value = Value::from_array(input_ids_array.view())
+            .map_err(|e| EmbeddingError::InferenceFailed {
+                reason: format!("Failed to create input_ids value: {}", e),
+            })?;
+
+        let attention_mask_value = Value::from_array(attention_mask_array.view())
+            .map_err(|e| EmbeddingError::InferenceFailed {
+                reason: format!("Failed to create attention_mask value: {}", e),
+            })?;
+
+        let outputs = session.run(ort::inputs![
+            "input_ids" => input_ids_value,
+            "attention_mask" => attention_mask_value,
+        ].map_err(|e| EmbeddingError::InferenceFailed {
+            reason: format!("Failed to create inputs: {}", e),
+        })?)
+        .map_err(|e| EmbeddingError::InferenceFailed {
+            reason: format!("Inference failed: {}", e),
+        })?;
+
+        // The projected embedding is what lives in the shared CLIP space;
+        // pooler_output is only a fallback for exports without a projection head.
+        let output = outputs.get("text_embeds")
+            .or_else(|| outputs.get("pooler_output"))
+            .or_else(|| outputs.iter().next().map(|(_, v)| v))
+            .ok_or_else(|| EmbeddingError::InferenceFailed {
+                reason: "No output found".to_string(),
+            })?;
+
+        if let Ok(tensor) = output.try_extract_tensor::<f32>() {
+            let shape = tensor.shape();
+            if shape.len() == 2 {
+                let hidden_size = shape[1].min(embedding_dim);
+                let embeddings = (0..batch_size)
+                    .map(|b| {
+                        let embedding: Vec<f32> = (0..hidden_size)
+                            .map(|h| tensor.get([b, h]).copied().unwrap_or(0.0))
+                            .collect();
+                        normalize(embedding)
+                    })
+                    .collect();
+                return Ok(embeddings);
+            }
+        }
+
+        // Fallback: return zero embeddings
+        tracing::warn!("Could not extract CLIP text embeddings from model output, returning zeros");
+        Ok(vec![vec![0.0; embedding_dim]; batch_size])
+    }
+
+    /// Get the embedding dimension
+    pub fn embedding_dim(&self) -> usize {
+        self.config.embedding_dim
+    }
+
+    /// Get the maximum sequence length
+    pub fn max_seq_length(&self) -> usize {
+        self.config.max_seq_length
+    }
+}
+
+/// L2-normalize an embedding so cosine similarity against image vectors is a dot product
+fn normalize(embedding: Vec<f32>) -> Vec<f32> {
+    let norm: f32 = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 1e-8 {
+        embedding.into_iter().map(|x| x / norm).collect()
+    } else {
+        embedding
+    }
+}
@@ -18,6 +18,10 @@ pub struct EmbeddingConfig {
     /// Image embedding configuration
     pub image_config: ImageEmbeddingConfig,
     
+    /// CLIP text tower configuration (text-to-image search)
+    #[serde(default)]
+    pub clip_text_config: ClipTextEmbeddingConfig,
+    
     /// Whether to use GPU acceleration
     pub use_gpu: bool,
     
@@ -32,6 +36,7 @@ impl Default for EmbeddingConfig {
             max_vram_mb: 4096, // 4GB default limit
             text_config: TextEmbeddingConfig::default(),
             image_config: ImageEmbeddingConfig::default(),
+            clip_text_config: ClipTextEmbeddingConfig::default(),
             use_gpu: true,
             batch_size: 32,
         }
@@ -41,6 +46,14 @@ impl Default for EmbeddingConfig {
 /// Configuration for text embedding model
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TextEmbeddingConfig {
+    /// Model identifier recorded with every vector it produces
+    #[serde(default = "default_text_model_id")]
+    pub model_id: String,
+    
+    /// Model version; bump when weights change under the same identifier
+    #[serde(default = "default_text_model_version")]
+    pub model_version: String,
+    
     /// Model filename (relative to models_dir)
     pub model_file: String,
     
@@ -57,9 +70,19 @@ pub struct TextEmbeddingConfig {
     pub vram_mb: u64,
 }
 
+fn default_text_model_id() -> String {
+    "all-MiniLM-L6-v2".to_string()
+}
+
+fn default_text_model_version() -> String {
+    "1".to_string()
+}
+
 impl Default for TextEmbeddingConfig {
     fn default() -> Self {
         Self {
+            model_id: default_text_model_id(),
+            model_version: default_text_model_version(),
             model_file: "all-MiniLM-L6-v2.onnx".to_string(),
             vocab_file: "vocab.txt".to_string(),
             max_seq_length: 256,
@@ -69,6 +92,53 @@ impl Default for TextEmbeddingConfig {
     }
 }
 
+impl TextEmbeddingConfig {
+    /// Tag identifying vectors produced by this model
+    pub fn model_tag(&self) -> EmbeddingModelTag {
+        EmbeddingModelTag::new(&self.model_id, &self.model_version, self.embedding_dim)
+    }
+}
+
+/// Identifies the model and version that produced a vector
+///
+/// Vectors from different tags are not comparable, so each tag gets its
+/// own vector collection.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct EmbeddingModelTag {
+    /// Model identifier
+    pub model_id: String,
+    /// Model version
+    pub version: String,
+    /// Vector dimension
+    pub dimension: usize,
+}
+
+impl EmbeddingModelTag {
+    /// Create a tag
+    pub fn new(model_id: impl Into<String>, version: impl Into<String>, dimension: usize) -> Self {
+        Self {
+            model_id: model_id.into(),
+            version: version.into(),
+            dimension,
+        }
+    }
+
+    /// Compact `id@version` form stored with chunk records
+    pub fn key(&self) -> String {
+        format!("{}@{}", self.model_id, self.version)
+    }
+
+    /// Name of the vector collection holding this model's vectors
+    pub fn collection_name(&self, base: &str) -> String {
+        let slug: String = self
+            .key()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+            .collect();
+        format!("{}__{}", base, slug)
+    }
+}
+
 /// Configuration for image embedding model (CLIP)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImageEmbeddingConfig {
@@ -96,6 +166,41 @@ impl Default for ImageEmbeddingConfig {
     }
 }
 
+/// Configuration for the CLIP text tower
+///
+/// The text tower projects queries into the same space as
+/// `ImageEmbeddingConfig`, so its embedding dimension must match the
+/// image model it was exported with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipTextEmbeddingConfig {
+    /// Model filename (relative to models_dir)
+    pub model_file: String,
+    
+    /// Tokenizer file (tokenizer.json, relative to models_dir)
+    pub tokenizer_file: String,
+    
+    /// Maximum sequence length (77 for CLIP)
+    pub max_seq_length: usize,
+    
+    /// Embedding dimension (512 for CLIP ViT-B/32)
+    pub embedding_dim: usize,
+    
+    /// Estimated VRAM usage in MB
+    pub vram_mb: u64,
+}
+
+impl Default for ClipTextEmbeddingConfig {
+    fn default() -> Self {
+        Self {
+            model_file: "clip-vit-base-patch32-text.onnx".to_string(),
+            tokenizer_file: "clip-tokenizer.json".to_string(),
+            max_seq_length: 77,
+            embedding_dim: 512,
+            vram_mb: 256, // ~256MB for the CLIP text transformer
+        }
+    }
+}
+
 /// Model type enumeration
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ModelType {
@@ -105,6 +210,9 @@ pub enum ModelType {
     /// Image embedding model (CLIP)
     ImageEmbedding,
     
+    /// CLIP text tower, shares the image embedding space
+    ClipText,
+    
     /// Fast text model for quick inference
     FastText,
     
@@ -118,6 +226,7 @@ impl ModelType {
         match self {
             ModelType::TextEmbedding => "all-MiniLM-L6-v2.onnx",
             ModelType::ImageEmbedding => "clip-vit-base-patch32.onnx",
+            ModelType::ClipText => "clip-vit-base-patch32-text.onnx",
             ModelType::FastText => "all-MiniLM-L6-v2.onnx",
             ModelType::AccurateText => "bge-base-en-v1.5.onnx",
         }
@@ -128,6 +237,7 @@ impl ModelType {
         match self {
             ModelType::TextEmbedding => 384,
             ModelType::ImageEmbedding => 512,
+            ModelType::ClipText => 512,
             ModelType::FastText => 384,
             ModelType::AccurateText => 768,
         }
@@ -138,6 +248,7 @@ impl ModelType {
         match self {
             ModelType::TextEmbedding => 256,
             ModelType::ImageEmbedding => 512,
+            ModelType::ClipText => 256,
             ModelType::FastText => 256,
             ModelType::AccurateText => 512,
         }
@@ -195,4 +306,17 @@ impl ModelConfig {
             use_gpu,
         }
     }
+    
+    /// Create a new model config for the CLIP text tower
+    pub fn clip_text_embedding(models_dir: &PathBuf, use_gpu: bool) -> Self {
+        Self {
+            model_type: ModelType::ClipText,
+            model_path: models_dir.join("clip-vit-base-patch32-text.onnx"),
+            embedding_dim: 512,
+            max_input_length: Some(77),
+            input_image_size: None,
+            vram_mb: 256,
+            use_gpu,
+        }
+    }
 }
@@ -314,6 +314,9 @@ impl DilutedAttentionProcessor {
                         end_line: None,
                         page_number: None,
                         bounding_box: None,
+                        section: None,
+                        section_name: None,
+                        member: None,
                     },
                     vector_id: 0, // To be assigned by vector store
                     created_at: Utc::now(),
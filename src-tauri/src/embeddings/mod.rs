@@ -4,6 +4,7 @@
 //! It supports:
 //! - Text embeddings using all-MiniLM-L6-v2 (384 dimensions)
 //! - Image embeddings using CLIP model
+//! - CLIP text tower embeddings for text-to-image search
 //! - VRAM management with LRU model caching
 //! - Graceful degradation when models are not ready
 //! - Diluted attention for processing long documents
@@ -14,17 +15,19 @@ mod model_manager;
 mod vram_manager;
 mod text_embedder;
 mod image_embedder;
+mod clip_text_embedder;
 mod diluted;
 
 #[cfg(test)]
 mod tests;
 
-pub use config::{EmbeddingConfig, ModelConfig, ModelType};
+pub use config::{EmbeddingConfig, ClipTextEmbeddingConfig, EmbeddingModelTag, ModelConfig, ModelType, TextEmbeddingConfig};
 pub use error::{EmbeddingError, EmbeddingResult};
 pub use model_manager::{ModelManager, ModelHandle, ModelLoadingState, ModelId};
 pub use vram_manager::{VRAMManager, VRAMStatus, ModelInfo};
 pub use text_embedder::TextEmbedder;
 pub use image_embedder::ImageEmbedder;
+pub use clip_text_embedder::ClipTextEmbedder;
 pub use diluted::{DilutedAttentionProcessor, DilutedAttentionConfig, ProcessedWindow, CoverageStats, Token};
 
 use std::sync::Arc;
@@ -44,6 +47,9 @@ pub struct EmbeddingEngine {
     /// Image embedder instance
     image_embedder: Arc<RwLock<Option<ImageEmbedder>>>,
     
+    /// CLIP text tower instance (queries into the image space)
+    clip_text_embedder: Arc<RwLock<Option<ClipTextEmbedder>>>,
+    
     /// Configuration
     config: EmbeddingConfig,
 }
@@ -52,16 +58,17 @@ impl EmbeddingEngine {
     /// Create a new embedding engine with the given configuration
     pub fn new(config: EmbeddingConfig) -> Self {
         let vram_manager = Arc::new(VRAMManager::new(config.max_vram_mb));
-        let model_manager = Arc::new(ModelManager::new(
-            config.models_dir.clone(),
-            vram_manager.clone(),
-        ));
+        let model_manager = Arc::new(
+            ModelManager::new(config.models_dir.clone(), vram_manager.clone())
+                .with_model_file(ModelType::TextEmbedding, config.text_config.model_file.clone()),
+        );
         
         Self {
             model_manager,
             vram_manager,
             text_embedder: Arc::new(RwLock::new(None)),
             image_embedder: Arc::new(RwLock::new(None)),
+            clip_text_embedder: Arc::new(RwLock::new(None)),
             config,
         }
     }
@@ -177,6 +184,42 @@ impl EmbeddingEngine {
         self.embed_image(&image_data).await
     }
     
+    /// Embed a text query into the CLIP image space
+    ///
+    /// The resulting vector is comparable with `embed_image` output and is
+    /// used to search the image vector space from natural language.
+    /// Returns empty vector if model is not ready (graceful degradation)
+    pub async fn embed_text_for_image_search(&self, query: &str) -> EmbeddingResult<Vec<f32>> {
+        // Check if CLIP text embedder is initialized
+        let embedder = self.clip_text_embedder.read().await;
+        if let Some(ref embedder) = *embedder {
+            return embedder.embed(query).await;
+        }
+        drop(embedder);
+        
+        // Try to initialize CLIP text embedder
+        match self.ensure_clip_text_embedder().await {
+            Ok(()) => {
+                let embedder = self.clip_text_embedder.read().await;
+                if let Some(ref embedder) = *embedder {
+                    embedder.embed(query).await
+                } else {
+                    tracing::warn!("CLIP text model not ready, returning empty embedding");
+                    Ok(vec![])
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Failed to load CLIP text model: {}, returning empty embedding", e);
+                Ok(vec![])
+            }
+        }
+    }
+    
+    /// Tag of the configured text embedding model
+    pub fn text_model_tag(&self) -> EmbeddingModelTag {
+        self.config.text_config.model_tag()
+    }
+    
     /// Get current VRAM status
     pub fn get_vram_status(&self) -> VRAMStatus {
         self.vram_manager.get_status()
@@ -196,6 +239,12 @@ impl EmbeddingEngine {
             *embedder = None;
         }
         
+        // Clear CLIP text embedder
+        {
+            let mut embedder = self.clip_text_embedder.write().await;
+            *embedder = None;
+        }
+        
         // Evict all models from VRAM manager
         self.vram_manager.evict_all_models().await;
         
@@ -238,6 +287,29 @@ impl EmbeddingEngine {
         
         Ok(())
     }
+    
+    /// Ensure CLIP text embedder is initialized
+    async fn ensure_clip_text_embedder(&self) -> EmbeddingResult<()> {
+        let mut embedder = self.clip_text_embedder.write().await;
+        if embedder.is_some() {
+            return Ok(());
+        }
+        
+        // Load the CLIP text tower
+        let model_handle = self.model_manager
+            .load_model(ModelType::ClipText)
+            .await?;
+        
+        let tokenizer_path = self.config.models_dir.join(&self.config.clip_text_config.tokenizer_file);
+        let clip_text_embedder = ClipTextEmbedder::new(
+            model_handle,
+            &tokenizer_path,
+            self.config.clip_text_config.clone(),
+        )?;
+        *embedder = Some(clip_text_embedder);
+        
+        Ok(())
+    }
 }
 
 impl Default for EmbeddingEngine {
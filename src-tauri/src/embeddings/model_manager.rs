@@ -114,6 +114,9 @@ pub struct ModelManager {
     
     /// Loaded model handles
     loaded_models: RwLock<HashMap<ModelId, Arc<ModelHandle>>>,
+
+    /// Model filenames replacing the defaults
+    model_files: HashMap<ModelType, String>,
 }
 
 impl ModelManager {
@@ -139,8 +142,16 @@ impl ModelManager {
             environment,
             model_states: RwLock::new(HashMap::new()),
             loaded_models: RwLock::new(HashMap::new()),
+            model_files: HashMap::new(),
         }
     }
+
+    /// Load `model_type` from `filename` (relative to the models directory)
+    /// instead of its default file
+    pub fn with_model_file(mut self, model_type: ModelType, filename: impl Into<String>) -> Self {
+        self.model_files.insert(model_type, filename.into());
+        self
+    }
     
     /// Get the current state of a model
     pub async fn get_model_state(&self, model_type: ModelType) -> ModelLoadingState {
@@ -322,7 +333,10 @@ impl ModelManager {
     
     /// Get the path to a model file
     fn get_model_path(&self, model_type: ModelType) -> PathBuf {
-        self.models_dir.join(model_type.default_filename())
+        match self.model_files.get(&model_type) {
+            Some(filename) => self.models_dir.join(filename),
+            None => self.models_dir.join(model_type.default_filename()),
+        }
     }
     
     /// Unload a specific model
@@ -20,6 +20,7 @@ mod config_tests {
     fn test_model_type_properties() {
         assert_eq!(ModelType::TextEmbedding.embedding_dim(), 384);
         assert_eq!(ModelType::ImageEmbedding.embedding_dim(), 512);
+        assert_eq!(ModelType::ClipText.embedding_dim(), 512);
         assert_eq!(ModelType::FastText.embedding_dim(), 384);
         assert_eq!(ModelType::AccurateText.embedding_dim(), 768);
     }
@@ -28,6 +29,17 @@ mod config_tests {
     fn test_model_type_vram() {
         assert_eq!(ModelType::TextEmbedding.estimated_vram_mb(), 256);
         assert_eq!(ModelType::ImageEmbedding.estimated_vram_mb(), 512);
+        assert_eq!(ModelType::ClipText.estimated_vram_mb(), 256);
+    }
+    
+    #[test]
+    fn test_clip_text_shares_image_space() {
+        // Text-to-image search compares CLIP text vectors against image vectors,
+        // so the two towers must agree on dimension
+        let config = EmbeddingConfig::default();
+        assert_eq!(config.clip_text_config.embedding_dim, config.image_config.embedding_dim);
+        assert_eq!(ModelType::ClipText.embedding_dim(), ModelType::ImageEmbedding.embedding_dim());
+        assert_eq!(config.clip_text_config.max_seq_length, 77);
     }
 }
 
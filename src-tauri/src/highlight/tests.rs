@@ -28,6 +28,9 @@ async fn test_navigation_target_with_location() {
         end_line: Some(15),
         page_number: None,
         bounding_box: None,
+        section: None,
+        section_name: None,
+        member: None,
     };
 
     let target = NavigationTarget::new("/path/to/file.txt")
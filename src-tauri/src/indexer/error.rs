@@ -1,5 +1,6 @@
 //! Error types for the indexer module
 
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use thiserror::Error;
 use uuid::Uuid;
@@ -7,7 +8,7 @@ use uuid::Uuid;
 use super::TaskStatus;
 
 /// Errors that can occur during indexing operations
-#[derive(Error, Debug, Clone)]
+#[derive(Error, Debug, Clone, Serialize, Deserialize)]
 pub enum IndexError {
     #[error("File not found: {path}")]
     FileNotFound { path: PathBuf },
@@ -5,6 +5,11 @@
 //! - Dead letter queue for failed tasks
 //! - File lock detection and special handling
 //! - Task state machine with valid transitions
+//! - Indexing pipeline from watcher events to vector and text stores
+//! - Optional SQLite persistence of the task queue and dead letters
+//! - Resource-aware scheduling of concurrency and batch size
+//! - Background re-embedding when the embedding model changes
+//! - Progress per monitored root and pipeline stage with throughput and ETA
 
 use std::collections::VecDeque;
 use std::path::PathBuf;
@@ -12,14 +17,29 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use serde::{Deserialize, Serialize};
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
 pub mod error;
+pub mod pipeline;
+pub mod progress;
+pub mod reembed;
+pub mod scheduler;
+pub mod store;
 #[cfg(test)]
 mod tests;
 
 pub use error::IndexError;
+pub use pipeline::{
+    CatchUp, ChunkEmbedder, ImageSpace, ImageSpaceEmbedder, IndexingPipeline, PipelineConfig, PipelineReport,
+};
+pub use progress::{IndexProgress, IndexProgressSnapshot, IndexStage, ProgressSummary, RootProgress, StageProgress};
+pub use reembed::{MigrationProgress, ModelMigrator};
+pub use scheduler::{
+    ProbeCapabilities, ResourceProbe, ResourceSample, ResourceScheduler, SchedulePlan, SchedulerMode, SystemProbe,
+};
+pub use store::TaskStore;
 
 /// Task priority levels for indexing
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -37,7 +57,7 @@ impl Default for TaskPriority {
 }
 
 /// Task status representing the state machine states
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TaskStatus {
     /// Waiting to be executed
     Pending,
@@ -260,9 +280,26 @@ pub struct IndexerStats {
     pub current_queue_size: AtomicU64,
     /// Current dead letter queue size
     pub current_dead_letter_size: AtomicU64,
+    /// Files whose content hash was unchanged, so indexing was skipped
+    pub files_unchanged: AtomicU64,
+    /// Chunks whose stored vector was reused
+    pub chunks_reused: AtomicU64,
+    /// Chunks that had to be embedded
+    pub chunks_embedded: AtomicU64,
 }
 
 impl IndexerStats {
+    /// Record the chunk-level outcome of indexing one file
+    pub fn record_chunks(&self, reused: u64, embedded: u64) {
+        self.chunks_reused.fetch_add(reused, Ordering::SeqCst);
+        self.chunks_embedded.fetch_add(embedded, Ordering::SeqCst);
+    }
+
+    /// Record a file skipped because its content hash was unchanged
+    pub fn record_unchanged_file(&self) {
+        self.files_unchanged.fetch_add(1, Ordering::SeqCst);
+    }
+
     /// Create a snapshot of current stats
     pub fn snapshot(&self) -> IndexerStatsSnapshot {
         IndexerStatsSnapshot {
@@ -271,6 +308,9 @@ impl IndexerStats {
             total_dead_letter: self.total_dead_letter.load(Ordering::SeqCst),
             current_queue_size: self.current_queue_size.load(Ordering::SeqCst),
             current_dead_letter_size: self.current_dead_letter_size.load(Ordering::SeqCst),
+            files_unchanged: self.files_unchanged.load(Ordering::SeqCst),
+            chunks_reused: self.chunks_reused.load(Ordering::SeqCst),
+            chunks_embedded: self.chunks_embedded.load(Ordering::SeqCst),
         }
     }
 }
@@ -283,6 +323,23 @@ pub struct IndexerStatsSnapshot {
     pub total_dead_letter: u64,
     pub current_queue_size: u64,
     pub current_dead_letter_size: u64,
+    pub files_unchanged: u64,
+    pub chunks_reused: u64,
+    pub chunks_embedded: u64,
+}
+
+impl IndexerStatsSnapshot {
+    /// Fraction of chunks whose vectors were reused instead of re-embedded
+    ///
+    /// Returns 0.0 before any chunk has been indexed.
+    pub fn chunk_reuse_ratio(&self) -> f64 {
+        let total = self.chunks_reused + self.chunks_embedded;
+        if total == 0 {
+            0.0
+        } else {
+            self.chunks_reused as f64 / total as f64
+        }
+    }
 }
 
 
@@ -298,6 +355,19 @@ pub struct ResilientBatchIndexer {
     stats: Arc<IndexerStats>,
     /// Shutdown flag
     shutdown: Arc<std::sync::atomic::AtomicBool>,
+    /// Durable storage for tasks (in-memory only when absent)
+    store: Option<TaskStore>,
+}
+
+/// Tasks reloaded from the task store on startup
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RestoredTasks {
+    /// Tasks put back in the pending queue
+    pub pending: usize,
+    /// Tasks put back in the dead letter queue
+    pub dead_letter: usize,
+    /// Tasks that were interrupted while processing and were requeued
+    pub requeued: usize,
 }
 
 impl ResilientBatchIndexer {
@@ -309,9 +379,21 @@ impl ResilientBatchIndexer {
             config,
             stats: Arc::new(IndexerStats::default()),
             shutdown: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            store: None,
         }
     }
 
+    /// Persist tasks and dead letters in the given store
+    pub fn with_store(mut self, store: TaskStore) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Get the task store, if persistence is enabled
+    pub fn store(&self) -> Option<&TaskStore> {
+        self.store.as_ref()
+    }
+
     /// Create with default configuration
     pub fn with_defaults() -> Self {
         Self::new(IndexerConfig::default())
@@ -329,6 +411,10 @@ impl ResilientBatchIndexer {
 
     /// Submit a new task for indexing
     pub async fn submit(&self, task: IndexTask) -> Result<(), IndexError> {
+        if let Some(ref store) = self.store {
+            store.insert(&task).await?;
+        }
+
         let mut queue = self.pending_queue.lock().await;
         queue.push_back(task);
         self.stats.current_queue_size.store(queue.len() as u64, Ordering::SeqCst);
@@ -337,6 +423,12 @@ impl ResilientBatchIndexer {
 
     /// Submit multiple tasks for indexing
     pub async fn submit_batch(&self, tasks: Vec<IndexTask>) -> Result<(), IndexError> {
+        if let Some(ref store) = self.store {
+            for task in &tasks {
+                store.insert(task).await?;
+            }
+        }
+
         let mut queue = self.pending_queue.lock().await;
         for task in tasks {
             queue.push_back(task);
@@ -357,8 +449,13 @@ impl ResilientBatchIndexer {
 
     /// Collect tasks that are ready to execute
     pub async fn collect_ready_tasks(&self) -> Vec<IndexTask> {
+        self.collect_ready_tasks_up_to(self.config.batch_size).await
+    }
+
+    /// Collect at most `limit` tasks that are ready to execute
+    pub async fn collect_ready_tasks_up_to(&self, limit: usize) -> Vec<IndexTask> {
         let mut queue = self.pending_queue.lock().await;
-        let mut batch = Vec::with_capacity(self.config.batch_size);
+        let mut batch = Vec::with_capacity(limit);
 
         // Collect all tasks and sort by priority and retry time
         let mut tasks: Vec<_> = queue.drain(..).collect();
@@ -375,7 +472,7 @@ impl ResilientBatchIndexer {
 
         // Select ready tasks up to batch size
         for task in tasks {
-            if task.is_ready() && batch.len() < self.config.batch_size {
+            if task.is_ready() && batch.len() < limit {
                 batch.push(task);
             } else {
                 queue.push_back(task);
@@ -386,8 +483,36 @@ impl ResilientBatchIndexer {
         batch
     }
 
+    /// Get a copy of the tasks waiting in the pending queue
+    pub async fn pending_tasks(&self) -> Vec<IndexTask> {
+        self.pending_queue.lock().await.iter().cloned().collect()
+    }
+
+    /// Move a collected task into `Processing`
+    ///
+    /// Tasks coming back for a retry pass through `Pending` first so every
+    /// step is a valid state machine transition.
+    pub async fn start_task(&self, task: &mut IndexTask) -> Result<(), IndexError> {
+        if matches!(task.status, TaskStatus::Failed | TaskStatus::DeadLetter) {
+            task.transition_to(TaskStatus::Pending)?;
+            self.persist(task).await?;
+        }
+
+        task.transition_to(TaskStatus::Processing)?;
+        self.persist(task).await
+    }
+
     /// Handle a successful task completion
-    pub async fn handle_success(&self, task: IndexTask) {
+    pub async fn handle_success(&self, mut task: IndexTask) {
+        if let Err(e) = task.transition_to(TaskStatus::Completed) {
+            tracing::debug!("Completed task for {:?} was not started: {}", task.path, e);
+        }
+        if let Some(ref store) = self.store {
+            if let Err(e) = store.delete(task.id).await {
+                tracing::warn!("Failed to remove completed task {}: {}", task.id, e);
+            }
+        }
+
         self.stats.total_processed.fetch_add(1, Ordering::SeqCst);
         tracing::debug!("Indexed successfully: {:?}", task.path);
     }
@@ -396,12 +521,22 @@ impl ResilientBatchIndexer {
     pub async fn handle_failure(&self, mut task: IndexTask, error: IndexError) {
         let is_file_locked = matches!(error, IndexError::FileLocked { .. });
 
+        if task.status != TaskStatus::Processing {
+            if let Err(e) = self.start_task(&mut task).await {
+                tracing::warn!("Failed to record start of task {}: {}", task.id, e);
+            }
+        }
+
         if is_file_locked {
             task.mark_failed_file_locked(error, self.config.file_lock_retry_secs);
         } else {
             task.mark_failed(error);
         }
 
+        if let Err(e) = self.persist(&task).await {
+            tracing::warn!("Failed to persist failed task {}: {}", task.id, e);
+        }
+
         if task.status == TaskStatus::DeadLetter {
             self.move_to_dead_letter(task).await;
             self.stats.total_dead_letter.fetch_add(1, Ordering::SeqCst);
@@ -419,7 +554,9 @@ impl ResilientBatchIndexer {
 
         // Enforce dead letter queue size limit
         while dlq.len() >= self.config.dead_letter_max_size {
-            dlq.pop_front();
+            if let Some(evicted) = dlq.pop_front() {
+                self.forget(evicted.id).await;
+            }
         }
 
         tracing::warn!(
@@ -445,6 +582,10 @@ impl ResilientBatchIndexer {
         if let Some(pos) = dlq.iter().position(|t| t.id == task_id) {
             let mut task = dlq.remove(pos).unwrap();
             task.reset_for_retry();
+            if let Err(e) = self.persist(&task).await {
+                dlq.insert(pos, task);
+                return Err(e);
+            }
 
             // Update dead letter queue size
             self.stats.current_dead_letter_size.store(dlq.len() as u64, Ordering::SeqCst);
@@ -474,6 +615,12 @@ impl ResilientBatchIndexer {
         self.stats.current_dead_letter_size.store(0, Ordering::SeqCst);
         drop(dlq);
 
+        for task in &tasks {
+            if let Err(e) = self.persist(task).await {
+                tracing::warn!("Failed to persist retried task {}: {}", task.id, e);
+            }
+        }
+
         let mut queue = self.pending_queue.lock().await;
         for task in tasks {
             queue.push_back(task);
@@ -489,9 +636,96 @@ impl ResilientBatchIndexer {
         let count = dlq.len();
         dlq.clear();
         self.stats.current_dead_letter_size.store(0, Ordering::SeqCst);
+
+        if let Some(ref store) = self.store {
+            if let Err(e) = store.delete_dead_letters().await {
+                tracing::warn!("Failed to clear persisted dead letters: {}", e);
+            }
+        }
         count
     }
 
+    /// Reload persisted tasks into the queues
+    ///
+    /// Tasks that were `Processing` when the previous run stopped are
+    /// failed and moved back to `Pending` (without counting a retry), and
+    /// completed leftovers are discarded. Does nothing without a store.
+    pub async fn restore(&self) -> Result<RestoredTasks, IndexError> {
+        let mut restored = RestoredTasks::default();
+        let Some(ref store) = self.store else {
+            return Ok(restored);
+        };
+
+        let mut pending = Vec::new();
+        let mut dead_letter = Vec::new();
+
+        for mut task in store.load_all().await? {
+            match task.status {
+                TaskStatus::Pending | TaskStatus::Failed => pending.push(task),
+                TaskStatus::DeadLetter => dead_letter.push(task),
+                TaskStatus::Processing => {
+                    task.transition_to(TaskStatus::Failed)?;
+                    store.update(&task).await?;
+                    task.transition_to(TaskStatus::Pending)?;
+                    task.next_retry_at = None;
+                    store.update(&task).await?;
+                    restored.requeued += 1;
+                    pending.push(task);
+                }
+                TaskStatus::Completed => {
+                    store.delete(task.id).await?;
+                }
+            }
+        }
+
+        {
+            let mut queue = self.pending_queue.lock().await;
+            for task in pending {
+                if queue.iter().all(|t| t.id != task.id) {
+                    queue.push_back(task);
+                    restored.pending += 1;
+                }
+            }
+            self.stats.current_queue_size.store(queue.len() as u64, Ordering::SeqCst);
+        }
+
+        {
+            let mut dlq = self.dead_letter_queue.lock().await;
+            for task in dead_letter {
+                if dlq.iter().all(|t| t.id != task.id) {
+                    dlq.push_back(task);
+                    restored.dead_letter += 1;
+                }
+            }
+            self.stats.current_dead_letter_size.store(dlq.len() as u64, Ordering::SeqCst);
+        }
+
+        tracing::info!(
+            "Restored {} pending ({} interrupted) and {} dead letter tasks",
+            restored.pending,
+            restored.requeued,
+            restored.dead_letter
+        );
+        Ok(restored)
+    }
+
+    /// Write the task's current state to the store, if any
+    async fn persist(&self, task: &IndexTask) -> Result<(), IndexError> {
+        match self.store {
+            Some(ref store) => store.update(task).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Drop a task from the store, if any
+    async fn forget(&self, task_id: Uuid) {
+        if let Some(ref store) = self.store {
+            if let Err(e) = store.delete(task_id).await {
+                tracing::warn!("Failed to remove task {} from store: {}", task_id, e);
+            }
+        }
+    }
+
     /// Signal shutdown
     pub fn shutdown(&self) {
         self.shutdown.store(true, Ordering::SeqCst);
@@ -684,6 +918,7 @@ impl ResilientBatchIndexer {
         if let Some(pos) = dlq.iter().position(|t| t.id == task_id) {
             let task = dlq.remove(pos);
             self.stats.current_dead_letter_size.store(dlq.len() as u64, Ordering::SeqCst);
+            self.forget(task_id).await;
             task
         } else {
             None
@@ -760,7 +995,7 @@ impl DeadLetterStats {
 }
 
 /// Get a string key for an error type (for statistics)
-fn error_type_key(error: &IndexError) -> String {
+pub(crate) fn error_type_key(error: &IndexError) -> String {
     match error {
         IndexError::FileNotFound { .. } => "FileNotFound".to_string(),
         IndexError::FileLocked { .. } => "FileLocked".to_string(),
@@ -0,0 +1,1817 @@
+//! Indexing pipeline
+//!
+//! Connects the watcher, reconciler, parser, embedder and stores:
+//! - Watcher `EventBatch`es and `ReconcileResult`s become `IndexTask`s
+//! - Tasks run parse → chunk → embed → upsert (vectors, text index, `content_chunks`)
+//! - `files.index_status` follows each task (Pending → Indexing → Indexed/Failed/Skipped)
+//! - Deletions purge vectors, text documents and rows; renames only move the row
+//! - Watcher deletions over the reconciler's safety threshold are held for
+//!   confirmation, like those found by reconciliation
+//! - Vectors and chunk rows are tagged with the embedding model that produced them
+//! - Vector payloads carry the file's privacy level so cloud-assisted searches
+//!   can leave out `Sensitive` and `Private` files
+//! - Photo metadata goes to `image_metadata` and the text index; a GPS
+//!   position raises the file to `PrivacyLevel::Sensitive`
+//! - Images are also embedded into the CLIP image space, tracked in
+//!   `image_vectors`, when an `ImageSpace` is configured
+//! - Per-root and per-stage progress is recorded in an `IndexProgress` tracker
+//! - Applied watcher batches are acknowledged in the `EventJournal`, which is
+//!   replayed on startup; the monitored roots are reconciled instead only
+//!   when the journal is missing, corrupt or unreconciled
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use tantivy::IndexWriter;
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use super::progress::{IndexProgress, IndexStage};
+use super::scheduler::{ResourceScheduler, SchedulerMode};
+use super::{file_access, IndexError, IndexTask, ResilientBatchIndexer, RestoredTasks, TaskPriority};
+use crate::core::types::{ContentChunk, FileType, IndexStatus, PrivacyLevel};
+use crate::embeddings::{EmbeddingEngine, EmbeddingModelTag};
+use crate::parser::{ContentParserService, ImageMetadata, ParseError};
+use crate::reconcile::{
+    hash_content, root_of, root_prefix, FileId, HeldDeletions, ReconcileResult, ReconciliationService,
+};
+use crate::search::{PhotoFields, TextIndex};
+use crate::vector::{VectorCollection, VectorCollections, VectorPoint, VectorStore};
+use crate::watcher::{
+    DirectoryFilter, EventBatch, EventJournal, FileEvent, FileIdentity, FileIdentitySource, FilterResult,
+    JournalRecovery, JournalState,
+};
+
+// ============================================================================
+// Embedder abstraction
+// ============================================================================
+
+/// Produces embeddings for chunk text
+///
+/// Implemented by `EmbeddingEngine`; tests substitute a deterministic mock.
+#[async_trait]
+pub trait ChunkEmbedder: Send + Sync {
+    /// Model the embeddings come from
+    fn model(&self) -> EmbeddingModelTag;
+
+    /// Embed a batch of texts, returning one vector per input
+    async fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, IndexError>;
+}
+
+#[async_trait]
+impl ChunkEmbedder for EmbeddingEngine {
+    fn model(&self) -> EmbeddingModelTag {
+        self.text_model_tag()
+    }
+
+    async fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, IndexError> {
+        let vectors = self
+            .batch_embed_text(texts)
+            .await
+            .map_err(|e| IndexError::EmbeddingFailed { reason: e.to_string() })?;
+
+        // The engine degrades to empty vectors while the model is unavailable;
+        // treat that as a retryable failure rather than storing nothing.
+        if vectors.iter().any(|v| v.is_empty()) {
+            return Err(IndexError::EmbeddingFailed {
+                reason: "text embedding model not ready".to_string(),
+            });
+        }
+
+        Ok(vectors)
+    }
+}
+
+/// Embeds images, and text queries against them, into the CLIP image space
+///
+/// Implemented by `EmbeddingEngine`; tests substitute a deterministic mock.
+#[async_trait]
+pub trait ImageSpaceEmbedder: Send + Sync {
+    /// Embed an image file
+    async fn embed_image_path(&self, path: &Path) -> Result<Vec<f32>, IndexError>;
+
+    /// Embed a text query with the CLIP text tower
+    async fn embed_image_query(&self, query: &str) -> Result<Vec<f32>, IndexError>;
+}
+
+#[async_trait]
+impl ImageSpaceEmbedder for EmbeddingEngine {
+    async fn embed_image_path(&self, path: &Path) -> Result<Vec<f32>, IndexError> {
+        let vector = self
+            .embed_image_file(path)
+            .await
+            .map_err(|e| IndexError::EmbeddingFailed { reason: e.to_string() })?;
+        if vector.is_empty() {
+            return Err(IndexError::EmbeddingFailed {
+                reason: "image embedding model not ready".to_string(),
+            });
+        }
+        Ok(vector)
+    }
+
+    async fn embed_image_query(&self, query: &str) -> Result<Vec<f32>, IndexError> {
+        let vector = self
+            .embed_text_for_image_search(query)
+            .await
+            .map_err(|e| IndexError::EmbeddingFailed { reason: e.to_string() })?;
+        if vector.is_empty() {
+            return Err(IndexError::EmbeddingFailed {
+                reason: "CLIP text model not ready".to_string(),
+            });
+        }
+        Ok(vector)
+    }
+}
+
+/// The image vector space: its store and the embedder that fills it
+#[derive(Clone)]
+pub struct ImageSpace {
+    /// Store of image embeddings (`VectorSpace::Image`)
+    pub store: Arc<VectorStore>,
+    /// Embedder for images and image-space queries
+    pub embedder: Arc<dyn ImageSpaceEmbedder>,
+}
+
+impl ImageSpace {
+    /// Pair an image store with its embedder
+    pub fn new(store: Arc<VectorStore>, embedder: Arc<dyn ImageSpaceEmbedder>) -> Self {
+        Self { store, embedder }
+    }
+}
+
+// ============================================================================
+// Configuration and reports
+// ============================================================================
+
+/// Configuration for the indexing pipeline
+#[derive(Debug, Clone)]
+pub struct PipelineConfig {
+    /// Number of chunks sent to the embedder per call
+    pub embed_batch_size: usize,
+    /// How often the background loop checks for ready tasks
+    pub poll_interval: Duration,
+    /// Priority for tasks raised by live watcher events
+    pub watcher_priority: TaskPriority,
+    /// Priority for tasks raised by scans and reconciliation
+    pub scan_priority: TaskPriority,
+}
+
+impl Default for PipelineConfig {
+    fn default() -> Self {
+        Self {
+            embed_batch_size: 32,
+            poll_interval: Duration::from_millis(500),
+            watcher_priority: TaskPriority::High,
+            scan_priority: TaskPriority::Normal,
+        }
+    }
+}
+
+impl PipelineConfig {
+    /// Set the embedding batch size
+    pub fn with_embed_batch_size(mut self, size: usize) -> Self {
+        self.embed_batch_size = size.max(1);
+        self
+    }
+
+    /// Set the background poll interval
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+}
+
+/// Counts of what a pipeline call did
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PipelineReport {
+    /// Tasks submitted to the indexer
+    pub submitted: usize,
+    /// Files parsed, embedded and stored
+    pub indexed: usize,
+    /// Files whose content hash was unchanged
+    pub unchanged: usize,
+    /// Files skipped (unsupported type)
+    pub skipped: usize,
+    /// Task attempts that failed
+    pub failed: usize,
+    /// Files removed from the index
+    pub removed: usize,
+    /// Files whose path was updated in place
+    pub renamed: usize,
+    /// Chunks whose stored vector was reused
+    pub chunks_reused: usize,
+    /// Chunks that were embedded
+    pub chunks_embedded: usize,
+    /// Last file a task ran for
+    pub last_path: Option<PathBuf>,
+    /// Events that could not be applied, with the error
+    pub errors: Vec<(PathBuf, String)>,
+}
+
+impl PipelineReport {
+    /// Number of task attempts that finished, successfully or not
+    pub fn processed(&self) -> usize {
+        self.indexed + self.unchanged + self.skipped + self.failed
+    }
+
+    /// Fold another report into this one
+    pub fn merge(&mut self, other: PipelineReport) {
+        self.submitted += other.submitted;
+        self.indexed += other.indexed;
+        self.unchanged += other.unchanged;
+        self.skipped += other.skipped;
+        self.failed += other.failed;
+        self.removed += other.removed;
+        self.renamed += other.renamed;
+        self.chunks_reused += other.chunks_reused;
+        self.chunks_embedded += other.chunks_embedded;
+        if other.last_path.is_some() {
+            self.last_path = other.last_path;
+        }
+        self.errors.extend(other.errors);
+    }
+}
+
+/// How the index was brought up to date at startup
+#[derive(Debug)]
+pub enum CatchUp {
+    /// Unprocessed batches from the event journal were applied
+    Replayed {
+        /// Batches replayed
+        batches: usize,
+        /// What replaying did
+        report: PipelineReport,
+        /// Deletions waiting for the user's confirmation
+        held: Vec<HeldDeletions>,
+    },
+    /// The journal could not be trusted, so the roots were reconciled
+    FullScan {
+        /// Why the journal was not used
+        reason: String,
+        /// What the reconciliation queued and removed
+        report: PipelineReport,
+        /// Deletions waiting for the user's confirmation
+        held: Vec<HeldDeletions>,
+    },
+}
+
+impl CatchUp {
+    /// Deletions waiting for the user's confirmation
+    pub fn held(&self) -> &[HeldDeletions] {
+        match self {
+            CatchUp::Replayed { held, .. } | CatchUp::FullScan { held, .. } => held,
+        }
+    }
+}
+
+/// How a single task ended
+enum TaskOutcome {
+    Indexed { reused: usize, embedded: usize },
+    Unchanged,
+    Skipped,
+}
+
+/// A chunk row from a previous index of a file
+struct StoredChunk {
+    id: Uuid,
+    vector_id: u64,
+    content_hash: Option<String>,
+    embedding_model: String,
+}
+
+// ============================================================================
+// Pipeline
+// ============================================================================
+
+/// Drives files from change notifications to searchable index entries
+pub struct IndexingPipeline {
+    db: SqlitePool,
+    indexer: Arc<ResilientBatchIndexer>,
+    parser: Arc<ContentParserService>,
+    embedder: Arc<dyn ChunkEmbedder>,
+    collections: Arc<VectorCollections>,
+    /// Image embeddings, when the image space is configured
+    image_space: Option<ImageSpace>,
+    text_index: Arc<TextIndex>,
+    text_writer: Mutex<IndexWriter>,
+    /// Files with a task waiting in the indexer queue
+    queued: std::sync::Mutex<HashSet<Uuid>>,
+    /// Adapts concurrency and batch size to system load when set
+    scheduler: Option<Arc<ResourceScheduler>>,
+    /// Progress per monitored root and stage
+    progress: Arc<IndexProgress>,
+    /// Journal of watcher batches, acknowledged once applied
+    journal: Option<Arc<EventJournal>>,
+    /// Excludes paths when a whole directory is indexed
+    filter: Option<Arc<DirectoryFilter>>,
+    /// Holds watcher deletions over the safety threshold for confirmation
+    deletion_guard: Option<Arc<ReconciliationService>>,
+    /// Roots the safety threshold is counted against
+    monitored_roots: std::sync::RwLock<Vec<PathBuf>>,
+    config: PipelineConfig,
+}
+
+impl IndexingPipeline {
+    /// Create a pipeline over the given services
+    ///
+    /// Takes the text index writer for the lifetime of the pipeline. The
+    /// vector store becomes the active collection for the embedder's model.
+    pub fn new(
+        db: SqlitePool,
+        indexer: Arc<ResilientBatchIndexer>,
+        parser: Arc<ContentParserService>,
+        embedder: Arc<dyn ChunkEmbedder>,
+        vectors: Arc<VectorStore>,
+        text_index: Arc<TextIndex>,
+    ) -> Result<Self, IndexError> {
+        let text_writer = text_index.writer().map_err(storage_error)?;
+        let collections = VectorCollections::new(VectorCollection::new(embedder.model(), vectors));
+
+        Ok(Self {
+            db,
+            indexer,
+            parser,
+            embedder,
+            collections: Arc::new(collections),
+            image_space: None,
+            text_index,
+            text_writer: Mutex::new(text_writer),
+            queued: std::sync::Mutex::new(HashSet::new()),
+            scheduler: None,
+            progress: Arc::new(IndexProgress::new()),
+            journal: None,
+            filter: None,
+            deletion_guard: None,
+            monitored_roots: std::sync::RwLock::new(Vec::new()),
+            config: PipelineConfig::default(),
+        })
+    }
+
+    /// Schedule task execution by system load
+    ///
+    /// Without a scheduler, tasks run one at a time in batches of the
+    /// indexer's configured size.
+    pub fn with_scheduler(mut self, scheduler: Arc<ResourceScheduler>) -> Self {
+        self.scheduler = Some(scheduler);
+        self
+    }
+
+    /// Use shared vector collections, e.g. while a model migration runs
+    pub fn with_collections(mut self, collections: Arc<VectorCollections>) -> Self {
+        self.collections = collections;
+        self
+    }
+
+    /// Get the vector collections
+    pub fn collections(&self) -> &Arc<VectorCollections> {
+        &self.collections
+    }
+
+    /// Embed images into the image space as well
+    pub fn with_image_space(mut self, image_space: ImageSpace) -> Self {
+        self.image_space = Some(image_space);
+        self
+    }
+
+    /// Get the image space, if configured
+    pub fn image_space(&self) -> Option<&ImageSpace> {
+        self.image_space.as_ref()
+    }
+
+    /// Record progress into a shared tracker
+    pub fn with_progress(mut self, progress: Arc<IndexProgress>) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Get the progress tracker
+    pub fn progress(&self) -> &Arc<IndexProgress> {
+        &self.progress
+    }
+
+    /// Acknowledge applied watcher batches in `journal`
+    pub fn with_journal(mut self, journal: Arc<EventJournal>) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    /// Skip paths `filter` excludes when a directory is indexed in full
+    pub fn with_filter(mut self, filter: Arc<DirectoryFilter>) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Get the directory filter, if any
+    pub fn filter(&self) -> Option<&Arc<DirectoryFilter>> {
+        self.filter.as_ref()
+    }
+
+    /// Hold watcher deletions that would remove too much of a monitored
+    /// root for confirmation, using `reconciler`'s safety threshold
+    pub fn with_deletion_guard(mut self, reconciler: Arc<ReconciliationService>) -> Self {
+        self.deletion_guard = Some(reconciler);
+        self
+    }
+
+    /// Set the monitored roots, for the deletion threshold and the ignore
+    /// rules of the directory filter
+    pub fn set_monitored_roots(&self, roots: &[PathBuf]) {
+        if let Some(ref filter) = self.filter {
+            filter.set_monitored_roots(roots);
+        }
+        *self.monitored_roots.write().unwrap() = roots.to_vec();
+    }
+
+    /// Get the event journal, if any
+    pub fn journal(&self) -> Option<&Arc<EventJournal>> {
+        self.journal.as_ref()
+    }
+
+    /// Get the resource scheduler, if any
+    pub fn scheduler(&self) -> Option<&Arc<ResourceScheduler>> {
+        self.scheduler.as_ref()
+    }
+
+    /// Whether the scheduler currently holds indexing paused
+    pub fn is_paused(&self) -> bool {
+        self.scheduler
+            .as_ref()
+            .is_some_and(|scheduler| scheduler.plan().mode == SchedulerMode::Paused)
+    }
+
+    /// Set the pipeline configuration
+    pub fn with_config(mut self, config: PipelineConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &PipelineConfig {
+        &self.config
+    }
+
+    /// Get the underlying task indexer
+    pub fn indexer(&self) -> &Arc<ResilientBatchIndexer> {
+        &self.indexer
+    }
+
+    /// Get the database pool
+    pub fn db(&self) -> &SqlitePool {
+        &self.db
+    }
+
+    /// Get the full-text index
+    pub fn text_index(&self) -> &Arc<TextIndex> {
+        &self.text_index
+    }
+
+    /// Get the chunk embedder, also used to embed text-space queries
+    pub fn embedder(&self) -> &Arc<dyn ChunkEmbedder> {
+        &self.embedder
+    }
+
+    /// Reload persisted tasks after a restart
+    ///
+    /// Files left in `Indexing` by an interrupted run go back to `Pending`.
+    pub async fn restore_tasks(&self) -> Result<RestoredTasks, IndexError> {
+        sqlx::query("UPDATE files SET index_status = ? WHERE index_status = ?")
+            .bind(status_str(IndexStatus::Pending))
+            .bind(status_str(IndexStatus::Indexing))
+            .execute(&self.db)
+            .await
+            .map_err(db_error)?;
+
+        let restored = self.indexer.restore().await?;
+
+        let pending = self.indexer.pending_tasks().await;
+        let mut queued = self.queued.lock().unwrap();
+        queued.extend(pending.iter().map(|task| task.file_id));
+
+        Ok(restored)
+    }
+
+    // ------------------------------------------------------------------------
+    // Change intake
+    // ------------------------------------------------------------------------
+
+    /// Apply a watcher batch: queue new and modified files, purge deleted
+    /// ones and move renamed ones
+    ///
+    /// Deleting or renaming a directory applies to every file indexed below
+    /// it, and a directory that appears is indexed in full. Deletions over
+    /// the safety threshold of a root are held instead. An event that fails
+    /// is recorded in `errors`; the rest of the batch still applies.
+    pub async fn handle_batch(&self, batch: &EventBatch) -> Result<PipelineReport, IndexError> {
+        let mut report = PipelineReport::default();
+        let held = self.hold_mass_deletions(&batch.events).await?;
+
+        for event in &batch.events {
+            if matches!(event, FileEvent::Deleted(path) if held.contains(path)) {
+                continue;
+            }
+            if let Err(e) = self.apply_event(event, &mut report).await {
+                let path = match event {
+                    FileEvent::Created(path) | FileEvent::Modified(path) | FileEvent::Deleted(path) => path,
+                    FileEvent::Renamed(_, new_path) => new_path,
+                };
+                tracing::warn!("Failed to apply {:?}: {}", event, e);
+                report.errors.push((path.clone(), e.to_string()));
+            }
+        }
+
+        Ok(report)
+    }
+
+    async fn apply_event(&self, event: &FileEvent, report: &mut PipelineReport) -> Result<(), IndexError> {
+        let priority = self.config.watcher_priority;
+        let paths = match event {
+            FileEvent::Renamed(old_path, new_path) => vec![old_path, new_path],
+            FileEvent::Created(path) | FileEvent::Modified(path) | FileEvent::Deleted(path) => vec![path],
+        };
+        for path in paths {
+            self.apply_ignore_change(path, report).await?;
+        }
+
+        match event {
+            FileEvent::Created(path) if is_dir(path).await => {
+                report.submitted += self.enqueue_tree(path, priority).await?;
+            }
+            FileEvent::Created(path) | FileEvent::Modified(path) => {
+                if self.enqueue_path(path, priority).await? {
+                    report.submitted += 1;
+                }
+            }
+            FileEvent::Deleted(path) => {
+                if self.remove_path(path).await? {
+                    report.removed += 1;
+                } else {
+                    // Nothing indexed at the path itself: a directory went away
+                    report.removed += self.remove_tree(path).await?;
+                }
+            }
+            FileEvent::Renamed(old_path, new_path) => {
+                if self.rename_path(old_path, new_path).await? {
+                    report.renamed += 1;
+                } else if is_dir(new_path).await {
+                    report.renamed += self.rename_tree(old_path, new_path).await?;
+                    // Files below it that were never indexed under the old name
+                    report.submitted += self.enqueue_tree(new_path, priority).await?;
+                } else if self.enqueue_path(new_path, priority).await? {
+                    // Unknown source: index the destination as a new file
+                    report.submitted += 1;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Hold the deletions of a batch that would remove too much of a
+    /// monitored root at once
+    ///
+    /// Counts the indexed files each delete event removes, per root, and
+    /// leaves their rows for the user to confirm when the deletion guard
+    /// holds them. Returns the paths of the held events.
+    async fn hold_mass_deletions(&self, events: &[FileEvent]) -> Result<HashSet<PathBuf>, IndexError> {
+        let Some(ref guard) = self.deletion_guard else {
+            return Ok(HashSet::new());
+        };
+        let roots = self.monitored_roots.read().unwrap().clone();
+
+        // Per root: delete events and the indexed files they remove
+        let mut per_root: HashMap<usize, (Vec<PathBuf>, Vec<PathBuf>)> = HashMap::new();
+        for event in events {
+            let FileEvent::Deleted(path) = event else {
+                continue;
+            };
+            let Some(root) = root_of(path, &roots) else {
+                continue;
+            };
+            let removed = match self.lookup_file_id(path).await? {
+                Some(_) => vec![path.clone()],
+                None => self.indexed_below(path).await?,
+            };
+            if !removed.is_empty() {
+                let entry = per_root.entry(root).or_default();
+                entry.0.push(path.clone());
+                entry.1.extend(removed);
+            }
+        }
+
+        let mut held = HashSet::new();
+        for (root, (deleted, removed)) in per_root {
+            let over = guard
+                .hold_if_over_threshold(&roots[root], &removed)
+                .await
+                .map_err(|e| IndexError::IoError { reason: e.to_string() })?;
+            if over {
+                held.extend(deleted);
+            }
+        }
+        Ok(held)
+    }
+
+    /// Follow up on a reconciliation pass
+    ///
+    /// The reconciler has already written rows, so this queues added and
+    /// modified files, refreshes text documents for renames and purges the
+    /// derived data of deleted files.
+    pub async fn handle_reconcile(&self, result: &ReconcileResult) -> Result<PipelineReport, IndexError> {
+        let mut report = PipelineReport::default();
+
+        for file_id in &result.deleted_file_ids {
+            self.purge_derived(*file_id).await?;
+            report.removed += 1;
+        }
+
+        for rename in &result.renamed {
+            if let Some(file_id) = self.lookup_file_id(&rename.new_path).await? {
+                self.refresh_text_documents(file_id, &rename.new_path).await?;
+                report.renamed += 1;
+            }
+        }
+
+        for path in result.added.iter().chain(result.modified.iter()) {
+            let Some(file_id) = self.lookup_file_id(path).await? else {
+                continue;
+            };
+            if self.submit_or_skip(file_id, path, self.config.scan_priority).await? {
+                report.submitted += 1;
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Make sure a `files` row exists for `path` and queue it for indexing
+    ///
+    /// Returns `false` for paths that are not regular files or whose type
+    /// cannot be parsed (those are marked `Skipped`).
+    pub async fn enqueue_path(&self, path: &Path, priority: TaskPriority) -> Result<bool, IndexError> {
+        let metadata = match tokio::fs::metadata(path).await {
+            Ok(metadata) if metadata.is_file() => metadata,
+            _ => return Ok(false),
+        };
+
+        let file_id = self.upsert_file_row(path, &metadata).await?;
+        self.submit_or_skip(file_id, path, priority).await
+    }
+
+    async fn submit_or_skip(&self, file_id: Uuid, path: &Path, priority: TaskPriority) -> Result<bool, IndexError> {
+        if !self.parser.is_supported(path) {
+            self.set_index_status(file_id, IndexStatus::Skipped).await?;
+            return Ok(false);
+        }
+
+        if !self.queued.lock().unwrap().insert(file_id) {
+            // Already waiting in the queue; that task will pick up the change
+            return Ok(false);
+        }
+
+        let submitted = self
+            .indexer
+            .submit(IndexTask::new(file_id, path.to_path_buf(), priority))
+            .await;
+        if let Err(e) = submitted {
+            // Nothing was queued, so later events must be able to retry
+            self.queued.lock().unwrap().remove(&file_id);
+            return Err(e);
+        }
+        self.progress.record_queued(path);
+        Ok(true)
+    }
+
+    /// Remove a file and everything derived from it
+    pub async fn remove_path(&self, path: &Path) -> Result<bool, IndexError> {
+        let Some(file_id) = self.lookup_file_id(path).await? else {
+            return Ok(false);
+        };
+
+        self.purge_derived(file_id).await?;
+
+        // Cascades to content_chunks, tags and relations
+        sqlx::query("DELETE FROM files WHERE id = ?")
+            .bind(file_id.to_string())
+            .execute(&self.db)
+            .await
+            .map_err(db_error)?;
+
+        tracing::debug!("Removed from index: {:?}", path);
+        Ok(true)
+    }
+
+    /// Move an indexed file to a new path without re-embedding it
+    ///
+    /// Returns `false` if no row exists for `old_path`.
+    pub async fn rename_path(&self, old_path: &Path, new_path: &Path) -> Result<bool, IndexError> {
+        let Some(file_id) = self.lookup_file_id(old_path).await? else {
+            return Ok(false);
+        };
+
+        // The move replaced a file that was indexed at the destination
+        if self.lookup_file_id(new_path).await?.map_or(false, |existing| existing != file_id) {
+            self.remove_path(new_path).await?;
+        }
+
+        let file_id_repr = FileId::from_path(new_path).ok().map(|id| id.to_string_repr());
+
+        sqlx::query(
+            r#"
+            UPDATE files
+            SET path = ?, filename = ?, extension = ?, file_id = COALESCE(?, file_id)
+            WHERE id = ?
+            "#,
+        )
+        .bind(new_path.to_string_lossy().to_string())
+        .bind(file_name(new_path))
+        .bind(extension(new_path))
+        .bind(file_id_repr)
+        .bind(file_id.to_string())
+        .execute(&self.db)
+        .await
+        .map_err(db_error)?;
+
+        // Text documents carry the filename, so rebuild them from stored chunks
+        self.refresh_text_documents(file_id, new_path).await?;
+
+        tracing::debug!("Renamed in index: {:?} -> {:?}", old_path, new_path);
+        Ok(true)
+    }
+
+    /// Queue the files below `dir` that have no row yet, returning how
+    /// many were queued
+    async fn enqueue_tree(&self, dir: &Path, priority: TaskPriority) -> Result<usize, IndexError> {
+        let root = dir.to_path_buf();
+        let filter = self.filter.clone();
+        let files = tokio::task::spawn_blocking(move || files_below(&root, filter.as_deref()))
+            .await
+            .map_err(|e| IndexError::IoError { reason: format!("Task join error: {}", e) })?;
+
+        let mut submitted = 0;
+        for path in files {
+            if self.lookup_file_id(&path).await?.is_none() && self.enqueue_path(&path, priority).await? {
+                submitted += 1;
+            }
+        }
+        Ok(submitted)
+    }
+
+    /// Re-apply ignore rules after `path`, an ignore file, changed
+    ///
+    /// Indexed files below its directory that the rules now exclude are
+    /// removed; files they no longer exclude are queued. Does nothing for
+    /// other paths.
+    async fn apply_ignore_change(&self, path: &Path, report: &mut PipelineReport) -> Result<(), IndexError> {
+        let Some(ref filter) = self.filter else {
+            return Ok(());
+        };
+        let Some(dir) = path.parent().filter(|_| filter.invalidate_ignore_file(path)) else {
+            return Ok(());
+        };
+
+        for indexed in self.indexed_below(dir).await? {
+            if matches!(filter.should_filter(&indexed), FilterResult::Exclude(_)) && self.remove_path(&indexed).await? {
+                report.removed += 1;
+            }
+        }
+        report.submitted += self.enqueue_tree(dir, self.config.watcher_priority).await?;
+        Ok(())
+    }
+
+    /// Remove every file indexed below `dir`, returning how many were removed
+    async fn remove_tree(&self, dir: &Path) -> Result<usize, IndexError> {
+        let mut removed = 0;
+        for path in self.indexed_below(dir).await? {
+            if self.remove_path(&path).await? {
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Move every file indexed below `old_dir` to `new_dir`, returning how
+    /// many were moved
+    async fn rename_tree(&self, old_dir: &Path, new_dir: &Path) -> Result<usize, IndexError> {
+        let mut renamed = 0;
+        for old_path in self.indexed_below(old_dir).await? {
+            let Ok(relative) = old_path.strip_prefix(old_dir) else {
+                continue;
+            };
+            if self.rename_path(&old_path, &new_dir.join(relative)).await? {
+                renamed += 1;
+            }
+        }
+        Ok(renamed)
+    }
+
+    /// Paths of the rows below `dir`
+    async fn indexed_below(&self, dir: &Path) -> Result<Vec<PathBuf>, IndexError> {
+        let prefix = root_prefix(dir);
+        let rows: Vec<(String,)> = sqlx::query_as("SELECT path FROM files WHERE substr(path, 1, length(?)) = ?")
+            .bind(&prefix)
+            .bind(&prefix)
+            .fetch_all(&self.db)
+            .await
+            .map_err(db_error)?;
+        Ok(rows.into_iter().map(|(path,)| PathBuf::from(path)).collect())
+    }
+
+    /// Bring the index up to date after a restart
+    ///
+    /// Replays the batches the journal holds beyond its processed
+    /// watermark. Only when the journal is missing, corrupt or was never
+    /// reconciled are `roots` reconciled with a full scan instead; files
+    /// whose mtime and size are unchanged are not read.
+    pub async fn catch_up(
+        &self,
+        recovery: JournalRecovery,
+        reconciler: &ReconciliationService,
+        roots: &[PathBuf],
+    ) -> Result<CatchUp, IndexError> {
+        let reason = match (&self.journal, &recovery.state) {
+            (None, _) => Some("no event journal".to_string()),
+            (Some(_), JournalState::Intact) => None,
+            (Some(_), JournalState::Missing) => Some("event journal missing".to_string()),
+            (Some(_), JournalState::Unreconciled) => Some("previous full scan did not finish".to_string()),
+            (Some(_), JournalState::Corrupt(error)) => Some(format!("event journal corrupt: {}", error)),
+        };
+
+        let mut report = PipelineReport::default();
+        let Some(reason) = reason else {
+            for batch in &recovery.pending {
+                report.merge(self.handle_batch(batch).await?);
+                self.acknowledge(batch).await;
+            }
+            tracing::info!("Replayed {} journaled watcher batches", recovery.pending.len());
+            // Deletions held at an earlier start still await confirmation
+            let held = reconciler
+                .held_deletions()
+                .await
+                .map_err(|e| IndexError::IoError { reason: e.to_string() })?;
+            return Ok(CatchUp::Replayed {
+                batches: recovery.pending.len(),
+                report,
+                held,
+            });
+        };
+
+        tracing::info!("Not replaying event journal, scanning instead: {}", reason);
+        let through = self.journal.as_ref().map(|journal| journal.last_seq());
+        let result = reconciler
+            .reconcile_on_startup(roots)
+            .await
+            .map_err(|e| IndexError::IoError { reason: e.to_string() })?;
+        report.merge(self.handle_reconcile(&result).await?);
+
+        if let (Some(journal), Some(through)) = (self.journal.clone(), through) {
+            match tokio::task::spawn_blocking(move || journal.mark_reconciled(through)).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => tracing::warn!("Failed to record reconciliation in event journal: {}", e),
+                Err(e) => tracing::warn!("Failed to record reconciliation in event journal: {}", e),
+            }
+        }
+
+        Ok(CatchUp::FullScan {
+            reason,
+            report,
+            held: result.held_deletions,
+        })
+    }
+
+    /// Record a batch as applied; the journal write syncs to disk, so it
+    /// runs off the async runtime
+    async fn acknowledge(&self, batch: &EventBatch) {
+        let Some(journal) = self.journal.clone() else {
+            return;
+        };
+        let batch_id = batch.id;
+        match tokio::task::spawn_blocking(move || journal.mark_processed(batch_id)).await {
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => tracing::warn!("Failed to acknowledge watcher batch {}: {}", batch_id, e),
+            Err(e) => tracing::warn!("Failed to acknowledge watcher batch {}: {}", batch_id, e),
+        }
+    }
+
+    /// Consume watcher batches and run queued tasks in the background
+    ///
+    /// Runs for the life of the application: tasks waiting out a retry
+    /// delay or held by a scheduler pause are picked up once they become
+    /// ready. Stops when the indexer shuts down, or when the channel closes
+    /// and the remaining ready tasks have run.
+    pub fn spawn(self: Arc<Self>, mut batches: mpsc::Receiver<EventBatch>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut busy = false;
+            loop {
+                // Keep going without a pause while tasks are ready
+                let idle = if busy { Duration::ZERO } else { self.config.poll_interval };
+                tokio::select! {
+                    batch = batches.recv() => match batch {
+                        Some(batch) => match self.handle_batch(&batch).await {
+                            Ok(_) => self.acknowledge(&batch).await,
+                            Err(e) => tracing::warn!("Failed to apply watcher batch {}: {}", batch.id, e),
+                        },
+                        None => break,
+                    },
+                    _ = tokio::time::sleep(idle) => {}
+                }
+
+                if self.indexer.is_shutdown() {
+                    return;
+                }
+                busy = self.run_ready_batch().await.processed() > 0;
+            }
+
+            self.run_until_idle().await;
+        })
+    }
+
+    // ------------------------------------------------------------------------
+    // Task execution
+    // ------------------------------------------------------------------------
+
+    /// Run one batch of ready tasks
+    ///
+    /// Returns an empty report while the scheduler holds indexing paused.
+    pub async fn run_ready_batch(&self) -> PipelineReport {
+        let (batch_size, concurrency) = match &self.scheduler {
+            Some(scheduler) => {
+                let plan = scheduler.plan();
+                if plan.mode == SchedulerMode::Paused {
+                    return PipelineReport::default();
+                }
+                (plan.batch_size, plan.concurrency)
+            }
+            None => (self.indexer.config().batch_size, 1),
+        };
+
+        let tasks = self.indexer.collect_ready_tasks_up_to(batch_size).await;
+        let reports: Vec<PipelineReport> = stream::iter(tasks)
+            .map(|task| self.run_task(task))
+            .buffer_unordered(concurrency.max(1))
+            .collect()
+            .await;
+
+        let mut report = PipelineReport::default();
+        for task_report in reports {
+            report.merge(task_report);
+        }
+        report
+    }
+
+    /// Start, run and settle a single task
+    async fn run_task(&self, mut task: IndexTask) -> PipelineReport {
+        let mut report = PipelineReport::default();
+
+        if let Err(e) = self.indexer.start_task(&mut task).await {
+            // Keep the task rather than lose it; it is retried later
+            tracing::warn!("Could not start task for {:?}: {}", task.path, e);
+            if task.retry_count + 1 >= task.max_retries {
+                self.queued.lock().unwrap().remove(&task.file_id);
+            }
+            self.indexer.handle_failure(task, e).await;
+            return report;
+        }
+
+        // Changes arriving from here on need a fresh task
+        self.queued.lock().unwrap().remove(&task.file_id);
+        report.last_path = Some(task.path.clone());
+
+        match self.process_task(&task).await {
+            Ok(outcome) => {
+                match outcome {
+                    TaskOutcome::Indexed { reused, embedded } => {
+                        report.indexed += 1;
+                        report.chunks_reused += reused;
+                        report.chunks_embedded += embedded;
+                    }
+                    TaskOutcome::Unchanged => report.unchanged += 1,
+                    TaskOutcome::Skipped => report.skipped += 1,
+                }
+                self.progress.record_finished(&task.path, false);
+                self.indexer.handle_success(task).await;
+            }
+            Err(error) => {
+                report.failed += 1;
+                let will_retry = task.retry_count + 1 < task.max_retries;
+                let status = if will_retry { IndexStatus::Pending } else { IndexStatus::Failed };
+                if let Err(e) = self.set_index_status(task.file_id, status).await {
+                    tracing::warn!("Failed to record status for {:?}: {}", task.path, e);
+                }
+
+                tracing::warn!("Indexing failed for {:?}: {}", task.path, error);
+                if will_retry {
+                    self.queued.lock().unwrap().insert(task.file_id);
+                } else {
+                    self.progress.record_finished(&task.path, true);
+                }
+                self.indexer.handle_failure(task, error).await;
+            }
+        }
+
+        report
+    }
+
+    /// Run ready tasks until none are left
+    ///
+    /// Tasks waiting on a retry delay stay queued for a later call.
+    pub async fn run_until_idle(&self) -> PipelineReport {
+        let mut report = PipelineReport::default();
+
+        loop {
+            let batch = self.run_ready_batch().await;
+            if batch.processed() == 0 {
+                break;
+            }
+            report.merge(batch);
+        }
+
+        report
+    }
+
+    /// Index a single file: parse, chunk, embed and store
+    ///
+    /// Files whose BLAKE3 hash matches the last successful run are skipped,
+    /// and chunks whose content is unchanged keep their stored vectors.
+    /// Vectors go to the collection being built for a new model, if any,
+    /// and only vectors of that model are reused.
+    async fn process_task(&self, task: &IndexTask) -> Result<TaskOutcome, IndexError> {
+        file_access::check_file_or_error_async(&task.path).await?;
+        let target = self.collections.write_target();
+        let model_key = target.model.key();
+
+        let content_hash = hash_content(task.path.clone()).await?;
+        let metadata = tokio::fs::metadata(&task.path).await?;
+        let modified_at: DateTime<Utc> = metadata.modified().map(DateTime::from).unwrap_or_else(|_| Utc::now());
+        let previous = self.stored_chunks(task.file_id).await?;
+        let present = self.present_vectors(&previous, &target).await?;
+        let reusable = |stored: &StoredChunk| stored.embedding_model == model_key && present.contains(&stored.vector_id);
+
+        if self.stored_content_hash(task.file_id).await?.as_deref() == Some(content_hash.as_str())
+            && previous.iter().all(reusable)
+            && self.image_vector_current(task.file_id, &task.path).await?
+        {
+            self.mark_indexed(task.file_id, &content_hash, metadata.len(), modified_at).await?;
+            self.indexer.stats().record_unchanged_file();
+            tracing::debug!("Unchanged since last index: {:?}", task.path);
+            return Ok(TaskOutcome::Unchanged);
+        }
+
+        self.set_index_status(task.file_id, IndexStatus::Indexing).await?;
+
+        let stage_start = Instant::now();
+        let parsed = match self.parser.parse(&task.path).await {
+            Ok(parsed) => parsed,
+            Err(ParseError::UnsupportedFileType { .. }) => {
+                self.set_index_status(task.file_id, IndexStatus::Skipped).await?;
+                return Ok(TaskOutcome::Skipped);
+            }
+            Err(e) => return Err(parse_error(&task.path, e)),
+        };
+        let photo = self
+            .store_image_metadata(task.file_id, parsed.metadata.image.as_ref())
+            .await?;
+        // Read after the photo metadata, which may have raised it
+        let privacy_level = self.stored_privacy_level(task.file_id).await?;
+
+        // Parsers assign placeholder ids; bind chunks to the file row
+        let mut chunks = parsed.chunks;
+        for (index, chunk) in chunks.iter_mut().enumerate() {
+            chunk.file_id = task.file_id;
+            chunk.chunk_index = index as u32;
+        }
+        let hashes: Vec<String> = chunks.iter().map(chunk_hash).collect();
+        self.progress.record_stage(&task.path, IndexStage::Parse, stage_start.elapsed());
+
+        // Match chunks against the previous run by content hash
+        let mut by_hash: HashMap<String, Vec<StoredChunk>> = HashMap::new();
+        for stored in previous {
+            // Rows written before chunk hashing never match and become stale
+            let hash = stored.content_hash.clone().unwrap_or_default();
+            by_hash.entry(hash).or_default().push(stored);
+        }
+
+        let mut reused = Vec::new();
+        let mut to_embed = Vec::new();
+        let mut stale = Vec::new();
+        for (index, chunk) in chunks.iter_mut().enumerate() {
+            match by_hash.get_mut(&hashes[index]).and_then(|candidates| candidates.pop()) {
+                Some(stored) if reusable(&stored) => {
+                    chunk.id = stored.id;
+                    chunk.vector_id = stored.vector_id;
+                    reused.push(index);
+                }
+                Some(stored) => {
+                    stale.push(stored);
+                    to_embed.push(index);
+                }
+                None => to_embed.push(index),
+            }
+        }
+        stale.extend(by_hash.into_values().flatten());
+
+        let texts: Vec<&str> = to_embed.iter().map(|&i| chunks[i].content.as_str()).collect();
+        let stage_start = Instant::now();
+        let embeddings = self.embed_texts(&texts).await?;
+        self.progress.record_stage(&task.path, IndexStage::Embed, stage_start.elapsed());
+
+        // Drop vectors of chunks that no longer exist or came from another model
+        let stage_start = Instant::now();
+        self.delete_vectors(&stale).await?;
+
+        let file_type = format!("{:?}", FileType::from_extension(&extension(&task.path)));
+
+        // Reused vectors keep their id; refresh the payload since offsets may have moved
+        let reused_ids: Vec<u64> = reused.iter().map(|&i| chunks[i].vector_id).collect();
+        let mut stored_vectors: HashMap<u64, Vec<f32>> = target
+            .store
+            .get_batch(&reused_ids)
+            .await
+            .map_err(storage_error)?
+            .into_iter()
+            .filter_map(|result| result.vector.map(|vector| (result.id, vector)))
+            .collect();
+        let mut refreshed = Vec::with_capacity(reused.len());
+        for &index in &reused {
+            let chunk = &chunks[index];
+            if let Some(vector) = stored_vectors.remove(&chunk.vector_id) {
+                refreshed.push(
+                    VectorPoint::new(chunk.vector_id, vector)
+                        .with_file_id(task.file_id)
+                        .with_chunk_id(chunk.id)
+                        .with_chunk_location(&chunk.location)
+                        .with_file_type(&file_type)
+                        .with_privacy_level(&privacy_level)
+                        .with_model(&target.model),
+                );
+            }
+        }
+        target.store.upsert_batch(refreshed).await.map_err(storage_error)?;
+
+        for (&index, vector) in to_embed.iter().zip(embeddings) {
+            let chunk = &mut chunks[index];
+            let point = VectorPoint::new(0, vector)
+                .with_file_id(task.file_id)
+                .with_chunk_id(chunk.id)
+                .with_chunk_location(&chunk.location)
+                .with_file_type(&file_type)
+                .with_privacy_level(&privacy_level)
+                .with_model(&target.model);
+            chunk.vector_id = target
+                .store
+                .insert(point.vector, point.payload)
+                .await
+                .map_err(storage_error)?;
+        }
+
+        self.write_text_documents(task.file_id, &file_name(&task.path), &chunks, modified_at, &photo)
+            .await?;
+        self.store_chunks(task.file_id, &chunks, &hashes, &model_key).await?;
+        self.store_image_vector(task.file_id, &task.path, &privacy_level).await?;
+        self.mark_indexed(task.file_id, &content_hash, metadata.len(), modified_at).await?;
+        self.progress.record_stage(&task.path, IndexStage::Store, stage_start.elapsed());
+
+        self.indexer
+            .stats()
+            .record_chunks(reused.len() as u64, to_embed.len() as u64);
+        tracing::debug!(
+            "Indexed {:?} ({} chunks, {} reused)",
+            task.path,
+            chunks.len(),
+            reused.len()
+        );
+        Ok(TaskOutcome::Indexed {
+            reused: reused.len(),
+            embedded: to_embed.len(),
+        })
+    }
+
+    async fn embed_texts(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, IndexError> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+
+        for batch in texts.chunks(self.config.embed_batch_size.max(1)) {
+            let vectors = self.embedder.embed_batch(batch).await?;
+            if vectors.len() != batch.len() {
+                return Err(IndexError::EmbeddingFailed {
+                    reason: format!("expected {} embeddings, got {}", batch.len(), vectors.len()),
+                });
+            }
+            embeddings.extend(vectors);
+        }
+
+        Ok(embeddings)
+    }
+
+    // ------------------------------------------------------------------------
+    // Storage helpers
+    // ------------------------------------------------------------------------
+
+    /// Remove vectors and text documents for a file
+    async fn purge_derived(&self, file_id: Uuid) -> Result<(), IndexError> {
+        self.collections.delete_by_file_id(file_id).await.map_err(storage_error)?;
+        if let Some(ref space) = self.image_space {
+            space.store.delete_by_file_id(file_id).await.map_err(storage_error)?;
+        }
+
+        let mut writer = self.text_writer.lock().await;
+        self.text_index
+            .delete_by_file_id(&writer, &file_id)
+            .map_err(storage_error)?;
+        self.text_index.commit(&mut writer).map_err(storage_error)?;
+        Ok(())
+    }
+
+    async fn write_text_documents(
+        &self,
+        file_id: Uuid,
+        filename: &str,
+        chunks: &[ContentChunk],
+        modified_at: DateTime<Utc>,
+        photo: &PhotoFields,
+    ) -> Result<(), IndexError> {
+        let modified_at = modified_at.timestamp().max(0) as u64;
+        let mut writer = self.text_writer.lock().await;
+
+        self.text_index
+            .delete_by_file_id(&writer, &file_id)
+            .map_err(storage_error)?;
+        for chunk in chunks {
+            self.text_index
+                .index_photo_document(
+                    &writer,
+                    &file_id,
+                    Some(&chunk.id),
+                    filename,
+                    &chunk.content,
+                    &[],
+                    modified_at,
+                    photo,
+                )
+                .map_err(storage_error)?;
+        }
+        self.text_index.commit(&mut writer).map_err(storage_error)?;
+        Ok(())
+    }
+
+    /// Rebuild a file's text documents from its stored chunks
+    async fn refresh_text_documents(&self, file_id: Uuid, path: &Path) -> Result<(), IndexError> {
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT id, content FROM content_chunks WHERE file_id = ? ORDER BY chunk_index",
+        )
+        .bind(file_id.to_string())
+        .fetch_all(&self.db)
+        .await
+        .map_err(db_error)?;
+
+        let modified_at = tokio::fs::metadata(path)
+            .await
+            .and_then(|m| m.modified())
+            .map(|t| DateTime::<Utc>::from(t).timestamp().max(0) as u64)
+            .unwrap_or(0);
+        let filename = file_name(path);
+        let photo = self.stored_photo_fields(file_id).await?;
+
+        let mut writer = self.text_writer.lock().await;
+        self.text_index
+            .delete_by_file_id(&writer, &file_id)
+            .map_err(storage_error)?;
+        for (chunk_id, content) in rows {
+            let chunk_id = Uuid::parse_str(&chunk_id).ok();
+            self.text_index
+                .index_photo_document(
+                    &writer,
+                    &file_id,
+                    chunk_id.as_ref(),
+                    &filename,
+                    &content,
+                    &[],
+                    modified_at,
+                    &photo,
+                )
+                .map_err(storage_error)?;
+        }
+        self.text_index.commit(&mut writer).map_err(storage_error)?;
+        Ok(())
+    }
+
+    /// Replace the `content_chunks` rows of a file
+    async fn store_chunks(
+        &self,
+        file_id: Uuid,
+        chunks: &[ContentChunk],
+        hashes: &[String],
+        model_key: &str,
+    ) -> Result<(), IndexError> {
+        let mut tx = self.db.begin().await.map_err(db_error)?;
+
+        sqlx::query("DELETE FROM content_chunks WHERE file_id = ?")
+            .bind(file_id.to_string())
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+
+        for (chunk, hash) in chunks.iter().zip(hashes) {
+            let bounding_box = chunk
+                .location
+                .bounding_box
+                .map(|bbox| serde_json::to_string(&bbox).unwrap_or_default());
+
+            sqlx::query(
+                r#"
+                INSERT INTO content_chunks (
+                    id, file_id, chunk_index, chunk_type, content,
+                    start_offset, end_offset, start_line, end_line, page_number,
+                    bounding_box, vector_id, created_at, content_hash, embedding_model
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(chunk.id.to_string())
+            .bind(file_id.to_string())
+            .bind(chunk.chunk_index as i64)
+            .bind(format!("{:?}", chunk.chunk_type))
+            .bind(&chunk.content)
+            .bind(chunk.location.start_offset as i64)
+            .bind(chunk.location.end_offset as i64)
+            .bind(chunk.location.start_line.map(|l| l as i64))
+            .bind(chunk.location.end_line.map(|l| l as i64))
+            .bind(chunk.location.page_number.map(|p| p as i64))
+            .bind(bounding_box)
+            .bind(chunk.vector_id as i64)
+            .bind(chunk.created_at.to_rfc3339())
+            .bind(hash)
+            .bind(model_key)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        }
+
+        tx.commit().await.map_err(db_error)?;
+        Ok(())
+    }
+
+    /// Whether the file needs no image vector or its vector is still stored
+    async fn image_vector_current(&self, file_id: Uuid, path: &Path) -> Result<bool, IndexError> {
+        let Some(ref space) = self.image_space else {
+            return Ok(true);
+        };
+        if FileType::from_extension(&extension(path)) != FileType::Image {
+            return Ok(true);
+        }
+
+        let row: Option<(i64,)> = sqlx::query_as("SELECT vector_id FROM image_vectors WHERE file_id = ?")
+            .bind(file_id.to_string())
+            .fetch_optional(&self.db)
+            .await
+            .map_err(db_error)?;
+        match row {
+            Some((vector_id,)) => space.store.exists(vector_id as u64).await.map_err(storage_error),
+            None => Ok(false),
+        }
+    }
+
+    /// Replace the image-space vector of an image file
+    ///
+    /// While the image model is unavailable the file is indexed without
+    /// one; it is embedded the next time the file is indexed.
+    async fn store_image_vector(&self, file_id: Uuid, path: &Path, privacy_level: &str) -> Result<(), IndexError> {
+        let Some(ref space) = self.image_space else {
+            return Ok(());
+        };
+        let file_type = FileType::from_extension(&extension(path));
+        if file_type != FileType::Image {
+            return Ok(());
+        }
+
+        space.store.delete_by_file_id(file_id).await.map_err(storage_error)?;
+        sqlx::query("DELETE FROM image_vectors WHERE file_id = ?")
+            .bind(file_id.to_string())
+            .execute(&self.db)
+            .await
+            .map_err(db_error)?;
+
+        let vector = match space.embedder.embed_image_path(path).await {
+            Ok(vector) => vector,
+            Err(e) => {
+                tracing::warn!("Indexed {:?} without an image vector: {}", path, e);
+                return Ok(());
+            }
+        };
+        let point = VectorPoint::new(0, vector)
+            .with_file_id(file_id)
+            .with_file_type(&format!("{:?}", file_type))
+            .with_privacy_level(privacy_level);
+        let vector_id = space
+            .store
+            .insert(point.vector, point.payload)
+            .await
+            .map_err(storage_error)?;
+
+        sqlx::query("INSERT INTO image_vectors (file_id, vector_id, created_at) VALUES (?, ?, ?)")
+            .bind(file_id.to_string())
+            .bind(vector_id as i64)
+            .bind(Utc::now().to_rfc3339())
+            .execute(&self.db)
+            .await
+            .map_err(db_error)?;
+        Ok(())
+    }
+
+    /// Replace the `image_metadata` row of a file, returning its text index fields
+    ///
+    /// A GPS position raises the file to `Sensitive`; levels the user set
+    /// above `Normal` are kept.
+    async fn store_image_metadata(
+        &self,
+        file_id: Uuid,
+        image: Option<&ImageMetadata>,
+    ) -> Result<PhotoFields, IndexError> {
+        sqlx::query("DELETE FROM image_metadata WHERE file_id = ?")
+            .bind(file_id.to_string())
+            .execute(&self.db)
+            .await
+            .map_err(db_error)?;
+
+        let image = match image {
+            Some(image) => image,
+            None => return Ok(PhotoFields::default()),
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO image_metadata (
+                file_id, width, height, captured_at, captured_ts, camera, lens,
+                orientation, latitude, longitude, altitude, location, keywords
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(file_id.to_string())
+        .bind(image.width.map(i64::from))
+        .bind(image.height.map(i64::from))
+        .bind(image.captured_at.map(|t| t.to_rfc3339()))
+        .bind(image.capture_timestamp())
+        .bind(image.camera())
+        .bind(image.lens.as_deref())
+        .bind(image.orientation.map(i64::from))
+        .bind(image.gps.map(|gps| gps.latitude))
+        .bind(image.gps.map(|gps| gps.longitude))
+        .bind(image.gps.and_then(|gps| gps.altitude))
+        .bind(image.location.as_deref())
+        .bind(serde_json::to_string(&image.keywords).unwrap_or_default())
+        .execute(&self.db)
+        .await
+        .map_err(db_error)?;
+
+        if image.privacy_level() == PrivacyLevel::Sensitive {
+            sqlx::query("UPDATE files SET privacy_level = ? WHERE id = ? AND privacy_level = ?")
+                .bind(format!("{:?}", PrivacyLevel::Sensitive))
+                .bind(file_id.to_string())
+                .bind(format!("{:?}", PrivacyLevel::Normal))
+                .execute(&self.db)
+                .await
+                .map_err(db_error)?;
+        }
+
+        Ok(PhotoFields {
+            captured_at: image.capture_timestamp().map(|ts| ts.max(0) as u64),
+            location: image.location.clone(),
+        })
+    }
+
+    /// Text index fields of a file's stored photo metadata
+    async fn stored_photo_fields(&self, file_id: Uuid) -> Result<PhotoFields, IndexError> {
+        let row: Option<(Option<i64>, Option<String>)> =
+            sqlx::query_as("SELECT captured_ts, location FROM image_metadata WHERE file_id = ?")
+                .bind(file_id.to_string())
+                .fetch_optional(&self.db)
+                .await
+                .map_err(db_error)?;
+
+        Ok(row
+            .map(|(captured_ts, location)| PhotoFields {
+                captured_at: captured_ts.map(|ts| ts.max(0) as u64),
+                location,
+            })
+            .unwrap_or_default())
+    }
+
+    /// Record a successful index of the given content
+    async fn mark_indexed(
+        &self,
+        file_id: Uuid,
+        content_hash: &str,
+        size_bytes: u64,
+        modified_at: DateTime<Utc>,
+    ) -> Result<(), IndexError> {
+        sqlx::query(
+            r#"
+            UPDATE files
+            SET index_status = ?, content_hash = ?, size_bytes = ?, modified_at = ?, indexed_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(status_str(IndexStatus::Indexed))
+        .bind(content_hash)
+        .bind(size_bytes as i64)
+        .bind(modified_at.to_rfc3339())
+        .bind(Utc::now().to_rfc3339())
+        .bind(file_id.to_string())
+        .execute(&self.db)
+        .await
+        .map_err(db_error)?;
+        Ok(())
+    }
+
+    /// Content hash recorded by the last successful index
+    async fn stored_content_hash(&self, file_id: Uuid) -> Result<Option<String>, IndexError> {
+        let row: Option<(String,)> = sqlx::query_as("SELECT content_hash FROM files WHERE id = ?")
+            .bind(file_id.to_string())
+            .fetch_optional(&self.db)
+            .await
+            .map_err(db_error)?;
+
+        // Placeholder hashes ('pending') never match a real BLAKE3 digest
+        Ok(row.map(|(hash,)| hash))
+    }
+
+    /// Privacy level of the file row, as stored in vector payloads
+    async fn stored_privacy_level(&self, file_id: Uuid) -> Result<String, IndexError> {
+        let row: Option<(String,)> = sqlx::query_as("SELECT privacy_level FROM files WHERE id = ?")
+            .bind(file_id.to_string())
+            .fetch_optional(&self.db)
+            .await
+            .map_err(db_error)?;
+
+        Ok(row
+            .map(|(level,)| level)
+            .unwrap_or_else(|| format!("{:?}", PrivacyLevel::Normal)))
+    }
+
+    async fn stored_chunks(&self, file_id: Uuid) -> Result<Vec<StoredChunk>, IndexError> {
+        let rows: Vec<(String, i64, Option<String>, Option<String>)> = sqlx::query_as(
+            r#"
+            SELECT id, vector_id, content_hash, embedding_model
+            FROM content_chunks
+            WHERE file_id = ?
+            ORDER BY chunk_index DESC
+            "#,
+        )
+        .bind(file_id.to_string())
+        .fetch_all(&self.db)
+        .await
+        .map_err(db_error)?;
+
+        // Rows from before model tagging belong to the active model
+        let active_key = self.collections.active().model.key();
+        Ok(rows
+            .into_iter()
+            .filter_map(|(id, vector_id, content_hash, embedding_model)| {
+                Some(StoredChunk {
+                    id: Uuid::parse_str(&id).ok()?,
+                    vector_id: vector_id as u64,
+                    content_hash,
+                    embedding_model: embedding_model.unwrap_or_else(|| active_key.clone()),
+                })
+            })
+            .collect())
+    }
+
+    /// Vector ids of stored chunks of the target's model that its store
+    /// still holds, looked up at once
+    async fn present_vectors(
+        &self,
+        chunks: &[StoredChunk],
+        target: &VectorCollection,
+    ) -> Result<HashSet<u64>, IndexError> {
+        let model_key = target.model.key();
+        let ids: Vec<u64> = chunks
+            .iter()
+            .filter(|chunk| chunk.embedding_model == model_key)
+            .map(|chunk| chunk.vector_id)
+            .collect();
+        target.store.existing(&ids).await.map_err(storage_error)
+    }
+
+    /// Delete chunk vectors from the collection of the model that made them
+    async fn delete_vectors(&self, chunks: &[StoredChunk]) -> Result<(), IndexError> {
+        let mut by_model: HashMap<&str, Vec<u64>> = HashMap::new();
+        for chunk in chunks {
+            by_model.entry(&chunk.embedding_model).or_default().push(chunk.vector_id);
+        }
+
+        for (model_key, ids) in by_model {
+            // Collections of retired models are already gone
+            if let Some(store) = self.collections.store_for(model_key) {
+                store.delete_batch(&ids).await.map_err(storage_error)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Insert or refresh the `files` row for a path, returning its id
+    async fn upsert_file_row(&self, path: &Path, metadata: &std::fs::Metadata) -> Result<Uuid, IndexError> {
+        let extension = extension(path);
+        let file_type = format!("{:?}", FileType::from_extension(&extension));
+        let modified_at = metadata
+            .modified()
+            .map(DateTime::<Utc>::from)
+            .unwrap_or_else(|_| Utc::now())
+            .to_rfc3339();
+        let created_at = metadata
+            .created()
+            .map(DateTime::<Utc>::from)
+            .unwrap_or_else(|_| Utc::now())
+            .to_rfc3339();
+        let file_id_repr = FileId::from_path(path).ok().map(|id| id.to_string_repr());
+
+        // Placeholder hash - computed during indexing
+        sqlx::query(
+            r#"
+            INSERT INTO files (
+                id, path, filename, extension, file_type, size_bytes,
+                content_hash, created_at, modified_at, indexed_at,
+                index_status, privacy_level, is_excluded, file_id
+            ) VALUES (?, ?, ?, ?, ?, ?, 'pending', ?, ?, datetime('now'), 'Pending', 'Normal', 0, ?)
+            ON CONFLICT(path) DO UPDATE SET
+                size_bytes = excluded.size_bytes,
+                modified_at = excluded.modified_at,
+                index_status = 'Pending',
+                file_id = COALESCE(excluded.file_id, files.file_id)
+            "#,
+        )
+        .bind(Uuid::now_v7().to_string())
+        .bind(path.to_string_lossy().to_string())
+        .bind(file_name(path))
+        .bind(&extension)
+        .bind(file_type)
+        .bind(metadata.len() as i64)
+        .bind(created_at)
+        .bind(modified_at)
+        .bind(file_id_repr)
+        .execute(&self.db)
+        .await
+        .map_err(db_error)?;
+
+        self.lookup_file_id(path).await?.ok_or_else(|| IndexError::StorageFailed {
+            reason: format!("file row missing after upsert: {}", path.display()),
+        })
+    }
+
+    async fn lookup_file_id(&self, path: &Path) -> Result<Option<Uuid>, IndexError> {
+        let row: Option<(String,)> = sqlx::query_as("SELECT id FROM files WHERE path = ?")
+            .bind(path.to_string_lossy().to_string())
+            .fetch_optional(&self.db)
+            .await
+            .map_err(db_error)?;
+
+        Ok(row.and_then(|(id,)| Uuid::parse_str(&id).ok()))
+    }
+
+    async fn set_index_status(&self, file_id: Uuid, status: IndexStatus) -> Result<(), IndexError> {
+        sqlx::query("UPDATE files SET index_status = ? WHERE id = ?")
+            .bind(status_str(status))
+            .bind(file_id.to_string())
+            .execute(&self.db)
+            .await
+            .map_err(db_error)?;
+        Ok(())
+    }
+}
+
+/// Identities of indexed files, so the watcher can pair moves of files it
+/// has not seen since it started
+#[async_trait]
+impl FileIdentitySource for IndexingPipeline {
+    async fn identity(&self, path: &Path) -> Option<FileIdentity> {
+        let row: Option<(Option<String>, i64, String, String)> = sqlx::query_as(
+            "SELECT file_id, size_bytes, content_hash, index_status FROM files WHERE path = ?",
+        )
+        .bind(path.to_string_lossy().to_string())
+        .fetch_optional(&self.db)
+        .await
+        .ok()?;
+
+        let (file_id, size, content_hash, status) = row?;
+        Some(stored_identity(file_id, size, content_hash, status))
+    }
+
+    async fn identities(&self, paths: &[PathBuf]) -> HashMap<PathBuf, FileIdentity> {
+        let mut found = HashMap::new();
+        for chunk in paths.chunks(500) {
+            let placeholders = vec!["?"; chunk.len()].join(", ");
+            let sql = format!(
+                "SELECT path, file_id, size_bytes, content_hash, index_status FROM files WHERE path IN ({})",
+                placeholders
+            );
+            let mut query = sqlx::query_as::<_, (String, Option<String>, i64, String, String)>(&sql);
+            for path in chunk {
+                query = query.bind(path.to_string_lossy().to_string());
+            }
+            let rows = match query.fetch_all(&self.db).await {
+                Ok(rows) => rows,
+                Err(e) => {
+                    tracing::warn!("Failed to look up identities of deleted files: {}", e);
+                    continue;
+                }
+            };
+            for (path, file_id, size, content_hash, status) in rows {
+                found.insert(PathBuf::from(path), stored_identity(file_id, size, content_hash, status));
+            }
+        }
+        found
+    }
+}
+
+/// Identity of a file as recorded in its `files` row
+fn stored_identity(file_id: Option<String>, size: i64, content_hash: String, status: String) -> FileIdentity {
+    FileIdentity {
+        file_id: file_id.as_deref().and_then(FileId::from_string_repr),
+        size: size as u64,
+        // Only indexed rows carry the hash of the current content
+        content_hash: (status == status_str(IndexStatus::Indexed)).then_some(content_hash),
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// BLAKE3 hash identifying a chunk's content and type
+fn chunk_hash(chunk: &ContentChunk) -> String {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(format!("{:?}", chunk.chunk_type).as_bytes());
+    hasher.update(&[0]);
+    hasher.update(chunk.content.as_bytes());
+    hasher.finalize().to_hex().to_string()
+}
+
+async fn is_dir(path: &Path) -> bool {
+    tokio::fs::metadata(path).await.map_or(false, |m| m.is_dir())
+}
+
+/// Regular files below `dir`, skipping paths `filter` excludes
+fn files_below(dir: &Path, filter: Option<&DirectoryFilter>) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::warn!("Failed to read directory {:?}: {}", dir, e);
+                continue;
+            }
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if filter.is_some_and(|filter| matches!(filter.should_filter(&path), FilterResult::Exclude(_))) {
+                continue;
+            }
+            match entry.file_type() {
+                Ok(file_type) if file_type.is_dir() => pending.push(path),
+                Ok(file_type) if file_type.is_file() => files.push(path),
+                _ => {}
+            }
+        }
+    }
+
+    files
+}
+
+fn status_str(status: IndexStatus) -> String {
+    format!("{:?}", status)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("")
+        .to_string()
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase()
+}
+
+fn db_error(e: sqlx::Error) -> IndexError {
+    IndexError::StorageFailed { reason: format!("database: {}", e) }
+}
+
+fn storage_error(e: impl std::fmt::Display) -> IndexError {
+    IndexError::StorageFailed { reason: e.to_string() }
+}
+
+fn parse_error(path: &Path, e: ParseError) -> IndexError {
+    match e {
+        ParseError::FileNotFound { .. } => IndexError::FileNotFound { path: path.to_path_buf() },
+        ParseError::Io(e) => IndexError::IoError { reason: e.to_string() },
+        other => IndexError::ContentExtractionFailed { reason: other.to_string() },
+    }
+}
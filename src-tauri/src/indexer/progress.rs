@@ -0,0 +1,372 @@
+//! Indexing progress per monitored root and pipeline stage
+//!
+//! Tracks, for every monitored root:
+//! - Files queued, completed and failed
+//! - Time spent and items handled in each stage (scan, parse, embed, store)
+//! - Throughput over a sliding window and the resulting ETA
+//!
+//! Stage timings are also recorded in the `MetricsCollector` so they show
+//! up in performance summaries and exported metrics.
+
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::logging::{MetricType, MetricsCollector};
+
+/// Default window for throughput calculation
+const DEFAULT_WINDOW: Duration = Duration::from_secs(60);
+
+/// Stage of the indexing pipeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum IndexStage {
+    /// Walking the roots to find new and changed files
+    Scan,
+    /// Reading and chunking file content
+    Parse,
+    /// Embedding chunk text
+    Embed,
+    /// Writing vectors, text documents and chunk rows
+    Store,
+}
+
+impl IndexStage {
+    /// All stages in pipeline order
+    pub const ALL: [IndexStage; 4] = [IndexStage::Scan, IndexStage::Parse, IndexStage::Embed, IndexStage::Store];
+
+    /// Stage name used in metrics and events
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IndexStage::Scan => "scan",
+            IndexStage::Parse => "parse",
+            IndexStage::Embed => "embed",
+            IndexStage::Store => "store",
+        }
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+/// Progress of one pipeline stage
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StageProgress {
+    /// The stage
+    pub stage: IndexStage,
+    /// Files that went through the stage
+    pub completed: u64,
+    /// Average time per file in milliseconds
+    pub avg_ms: f64,
+    /// Files per minute over the throughput window
+    pub throughput_per_minute: f64,
+}
+
+/// Aggregate progress of a set of files
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProgressSummary {
+    /// Files queued for indexing
+    pub queued: u64,
+    /// Files indexed, unchanged or skipped
+    pub completed: u64,
+    /// Files that failed for good
+    pub failed: u64,
+    /// Files still waiting or being retried
+    pub remaining: u64,
+    /// Files finished per minute over the throughput window
+    pub throughput_per_minute: f64,
+    /// Estimated seconds until the remaining files are done
+    pub eta_secs: Option<u64>,
+    /// Per-stage progress in pipeline order
+    pub stages: Vec<StageProgress>,
+}
+
+/// Progress of one monitored root
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RootProgress {
+    /// The monitored root
+    pub root: PathBuf,
+    /// Progress of files under the root
+    #[serde(flatten)]
+    pub progress: ProgressSummary,
+}
+
+/// Progress across all roots
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexProgressSnapshot {
+    /// Totals, including files outside any monitored root
+    pub total: ProgressSummary,
+    /// Per monitored root, in the order the roots were set
+    pub roots: Vec<RootProgress>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct StageCounters {
+    completed: u64,
+    busy: Duration,
+    recent: VecDeque<Instant>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct Counters {
+    queued: u64,
+    completed: u64,
+    failed: u64,
+    recent: VecDeque<Instant>,
+    started: Option<Instant>,
+    stages: [StageCounters; 4],
+}
+
+impl Counters {
+    fn touch(&mut self, now: Instant) {
+        self.started.get_or_insert(now);
+    }
+
+    fn summary(&mut self, now: Instant, window: Duration) -> ProgressSummary {
+        let span = self
+            .started
+            .map(|started| now.duration_since(started).min(window))
+            .unwrap_or_default();
+
+        prune(&mut self.recent, now, window);
+        let throughput_per_minute = per_minute(self.recent.len(), span);
+        let remaining = self.queued.saturating_sub(self.completed + self.failed);
+        let eta_secs = if remaining == 0 {
+            Some(0)
+        } else if throughput_per_minute > 0.0 {
+            Some((remaining as f64 / throughput_per_minute * 60.0).ceil() as u64)
+        } else {
+            None
+        };
+
+        let stages = IndexStage::ALL
+            .iter()
+            .map(|stage| {
+                let counters = &mut self.stages[stage.index()];
+                prune(&mut counters.recent, now, window);
+                StageProgress {
+                    stage: *stage,
+                    completed: counters.completed,
+                    avg_ms: if counters.completed == 0 {
+                        0.0
+                    } else {
+                        counters.busy.as_secs_f64() * 1000.0 / counters.completed as f64
+                    },
+                    throughput_per_minute: per_minute(counters.recent.len(), span),
+                }
+            })
+            .collect();
+
+        ProgressSummary {
+            queued: self.queued,
+            completed: self.completed,
+            failed: self.failed,
+            remaining,
+            throughput_per_minute,
+            eta_secs,
+            stages,
+        }
+    }
+}
+
+fn prune(recent: &mut VecDeque<Instant>, now: Instant, window: Duration) {
+    while recent.front().map_or(false, |at| now.duration_since(*at) > window) {
+        recent.pop_front();
+    }
+}
+
+fn per_minute(count: usize, span: Duration) -> f64 {
+    if span.is_zero() {
+        0.0
+    } else {
+        count as f64 / span.as_secs_f64() * 60.0
+    }
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    roots: Vec<PathBuf>,
+    per_root: HashMap<PathBuf, Counters>,
+    total: Counters,
+}
+
+impl Inner {
+    /// Longest monitored root containing `path`
+    fn root_for(&self, path: &Path) -> Option<PathBuf> {
+        self.roots
+            .iter()
+            .filter(|root| path.starts_with(root))
+            .max_by_key(|root| root.components().count())
+            .cloned()
+    }
+
+    /// Apply an update to the totals and to the root of `path`
+    fn update(&mut self, path: &Path, now: Instant, apply: impl Fn(&mut Counters)) -> Option<PathBuf> {
+        self.total.touch(now);
+        apply(&mut self.total);
+
+        let root = self.root_for(path)?;
+        let counters = self.per_root.entry(root.clone()).or_default();
+        counters.touch(now);
+        apply(counters);
+        Some(root)
+    }
+}
+
+/// Progress tracker shared by the pipeline, the scanner and status commands
+pub struct IndexProgress {
+    inner: Mutex<Inner>,
+    metrics: Arc<MetricsCollector>,
+    window: Duration,
+    version: AtomicU64,
+}
+
+impl IndexProgress {
+    /// Create a tracker with its own metrics collector
+    pub fn new() -> Self {
+        Self::with_metrics(Arc::new(MetricsCollector::new()))
+    }
+
+    /// Create a tracker recording stage timings into `metrics`
+    pub fn with_metrics(metrics: Arc<MetricsCollector>) -> Self {
+        Self {
+            inner: Mutex::new(Inner::default()),
+            metrics,
+            window: DEFAULT_WINDOW,
+            version: AtomicU64::new(0),
+        }
+    }
+
+    /// Set the window throughput is measured over
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Metrics collector stage timings are recorded into
+    pub fn metrics(&self) -> &Arc<MetricsCollector> {
+        &self.metrics
+    }
+
+    /// Set the monitored roots progress is grouped by
+    ///
+    /// Counters of roots that remain monitored are kept.
+    pub fn set_roots(&self, roots: Vec<PathBuf>) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.per_root.retain(|root, _| roots.contains(root));
+        for root in &roots {
+            inner.per_root.entry(root.clone()).or_default();
+        }
+        inner.roots = roots;
+        self.bump();
+    }
+
+    /// Changes whenever progress is recorded, so pollers can skip idle periods
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::SeqCst)
+    }
+
+    /// Record a file queued for indexing
+    pub fn record_queued(&self, path: &Path) {
+        let now = Instant::now();
+        self.inner.lock().unwrap().update(path, now, |counters| counters.queued += 1);
+        self.bump();
+    }
+
+    /// Record files found by a scan that took `elapsed`
+    ///
+    /// The time is split across roots by the share of files found in each.
+    pub fn record_scan(&self, paths: &[PathBuf], elapsed: Duration) {
+        if paths.is_empty() {
+            return;
+        }
+        let now = Instant::now();
+        let share = elapsed / paths.len() as u32;
+        for path in paths {
+            self.record_stage_at(path, IndexStage::Scan, share, now);
+        }
+    }
+
+    /// Record a file passing through a stage
+    pub fn record_stage(&self, path: &Path, stage: IndexStage, elapsed: Duration) {
+        self.record_stage_at(path, stage, elapsed, Instant::now());
+    }
+
+    fn record_stage_at(&self, path: &Path, stage: IndexStage, elapsed: Duration, now: Instant) {
+        let root = self.inner.lock().unwrap().update(path, now, |counters| {
+            let stage = &mut counters.stages[stage.index()];
+            stage.completed += 1;
+            stage.busy += elapsed;
+            stage.recent.push_back(now);
+        });
+
+        let mut labels = HashMap::new();
+        if let Some(root) = root {
+            labels.insert("root".to_string(), root.to_string_lossy().to_string());
+        }
+        self.metrics.record_with_labels(
+            &format!("indexing.stage.{}", stage.as_str()),
+            MetricType::Duration(elapsed),
+            labels,
+        );
+        self.bump();
+    }
+
+    /// Record a file that finished indexing, successfully or for good
+    pub fn record_finished(&self, path: &Path, failed: bool) {
+        let now = Instant::now();
+        self.inner.lock().unwrap().update(path, now, |counters| {
+            if failed {
+                counters.failed += 1;
+            } else {
+                counters.completed += 1;
+            }
+            counters.recent.push_back(now);
+        });
+        self.bump();
+    }
+
+    /// Current progress for all roots
+    pub fn snapshot(&self) -> IndexProgressSnapshot {
+        let now = Instant::now();
+        let window = self.window;
+        let mut inner = self.inner.lock().unwrap();
+        let inner = &mut *inner;
+
+        let total = inner.total.summary(now, window);
+        let roots = inner
+            .roots
+            .iter()
+            .map(|root| RootProgress {
+                root: root.clone(),
+                progress: inner.per_root.entry(root.clone()).or_default().summary(now, window),
+            })
+            .collect();
+
+        IndexProgressSnapshot { total, roots }
+    }
+
+    /// Forget all counters, keeping the roots
+    pub fn reset(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.total = Counters::default();
+        for counters in inner.per_root.values_mut() {
+            *counters = Counters::default();
+        }
+        self.bump();
+    }
+
+    fn bump(&self) {
+        self.version.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+impl Default for IndexProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
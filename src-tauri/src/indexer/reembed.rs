@@ -0,0 +1,551 @@
+//! Background re-embedding after an embedding model change
+//!
+//! Every model writes to its own vector collection, recorded in the
+//! `vector_collections` table:
+//! - On startup the configured model is compared with the active collection
+//! - A different model gets a fresh `Building` collection; search keeps the old one
+//! - Chunks still tagged with another model are re-embedded in batches
+//! - A chunk the new model fails on is retried alone and, after
+//!   `MAX_CHUNK_ATTEMPTS`, skipped until the next start; it keeps its old
+//!   vector and holds back the cutover until the pipeline re-embeds its
+//!   changed file or a later run of the migration succeeds
+//! - Once none remain or are skipped, the new collection becomes `Active` in
+//!   one transaction and the old one is retired and cleared
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use super::pipeline::ChunkEmbedder;
+use super::scheduler::{ResourceScheduler, SchedulerMode};
+use super::IndexError;
+use crate::embeddings::EmbeddingModelTag;
+use crate::vector::{VectorCollection, VectorCollections, VectorPoint, VectorStore, VectorStoreConfig};
+
+/// Failed attempts to embed a chunk alone before the migration skips it
+const MAX_CHUNK_ATTEMPTS: u32 = 3;
+
+/// Lifecycle of a vector collection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CollectionStatus {
+    /// Serves search
+    Active,
+    /// Being filled for a new model
+    Building,
+    /// Replaced; its vectors are dropped
+    Retired,
+}
+
+impl CollectionStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            CollectionStatus::Active => "Active",
+            CollectionStatus::Building => "Building",
+            CollectionStatus::Retired => "Retired",
+        }
+    }
+}
+
+/// Progress of a model migration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MigrationProgress {
+    /// Model serving search until cutover
+    pub from_model: EmbeddingModelTag,
+    /// Model being migrated to
+    pub to_model: EmbeddingModelTag,
+    /// Chunks already embedded with the new model
+    pub migrated_chunks: u64,
+    /// Chunks still embedded with another model, not counting skipped ones
+    pub remaining_chunks: u64,
+    /// Chunks skipped because the new model failed on them; they hold back
+    /// the cutover
+    pub skipped_chunks: u64,
+}
+
+impl MigrationProgress {
+    /// Percentage of chunks migrated
+    pub fn percent(&self) -> f64 {
+        let total = self.migrated_chunks + self.remaining_chunks + self.skipped_chunks;
+        if total == 0 {
+            100.0
+        } else {
+            self.migrated_chunks as f64 / total as f64 * 100.0
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct CollectionRow {
+    name: String,
+    model_id: String,
+    model_version: String,
+    dimension: i64,
+    status: String,
+}
+
+impl CollectionRow {
+    fn model(&self) -> EmbeddingModelTag {
+        EmbeddingModelTag::new(&self.model_id, &self.model_version, self.dimension as usize)
+    }
+}
+
+/// Re-embeds chunks into the collection of the configured model
+pub struct ModelMigrator {
+    db: SqlitePool,
+    base: VectorStoreConfig,
+    collections: Arc<VectorCollections>,
+    embedder: Arc<dyn ChunkEmbedder>,
+    scheduler: Option<Arc<ResourceScheduler>>,
+    batch_size: usize,
+    /// Failed attempts per chunk ID
+    failures: Mutex<HashMap<String, u32>>,
+    /// Chunks left out of this migration
+    skipped: Mutex<HashSet<String>>,
+}
+
+impl ModelMigrator {
+    /// Open the collections recorded in the database
+    ///
+    /// `base` is the configuration of the original text collection; other
+    /// collections derive their name and dimension from their model. If the
+    /// embedder's model differs from the active one, a build is started or
+    /// resumed.
+    pub async fn open(
+        db: SqlitePool,
+        base: VectorStoreConfig,
+        embedder: Arc<dyn ChunkEmbedder>,
+    ) -> Result<Self, IndexError> {
+        let rows: Vec<CollectionRow> = sqlx::query_as(
+            "SELECT name, model_id, model_version, dimension, status FROM vector_collections WHERE status IN (?, ?)",
+        )
+        .bind(CollectionStatus::Active.as_str())
+        .bind(CollectionStatus::Building.as_str())
+        .fetch_all(&db)
+        .await
+        .map_err(db_error)?;
+
+        let configured = embedder.model();
+        let (name, model) = match rows.iter().find(|row| row.status == CollectionStatus::Active.as_str()) {
+            Some(row) => (row.name.clone(), row.model()),
+            None => {
+                // First run with model tracking: existing vectors came from the configured model
+                register(&db, &base.collection_name, &configured, CollectionStatus::Active).await?;
+                (base.collection_name.clone(), configured.clone())
+            }
+        };
+        let collections = VectorCollections::new(VectorCollection::new(
+            model.clone(),
+            open_store(&base, &name, &model).await?,
+        ));
+
+        if let Some(row) = rows.iter().find(|row| row.status == CollectionStatus::Building.as_str()) {
+            if row.model() == configured && configured != model {
+                let store = open_store(&base, &row.name, &configured).await?;
+                collections.begin_build(VectorCollection::new(configured, store));
+            } else {
+                // The configuration moved on before the previous build finished
+                retire(&db, &row.name).await?;
+            }
+        }
+
+        let migrator = Self {
+            db,
+            base,
+            collections: Arc::new(collections),
+            embedder,
+            scheduler: None,
+            batch_size: 64,
+            failures: Mutex::new(HashMap::new()),
+            skipped: Mutex::new(HashSet::new()),
+        };
+        migrator.sync_model().await?;
+        Ok(migrator)
+    }
+
+    /// Start re-embedding if the embedder's model differs from the active one
+    ///
+    /// A build for a model that is no longer configured is abandoned.
+    /// Returns whether a migration is in progress.
+    pub async fn sync_model(&self) -> Result<bool, IndexError> {
+        let configured = self.embedder.model();
+        if let Some(building) = self.collections.building() {
+            if building.model == configured {
+                return Ok(true);
+            }
+            self.collections.abort_build();
+            retire(&self.db, &building.store.config().collection_name).await?;
+            self.failures.lock().unwrap().clear();
+            self.skipped.lock().unwrap().clear();
+        }
+
+        let active = self.collections.active();
+        if configured == active.model {
+            return Ok(false);
+        }
+
+        let name = configured.collection_name(&self.base.collection_name);
+        register(&self.db, &name, &configured, CollectionStatus::Building).await?;
+        let store = open_store(&self.base, &name, &configured).await?;
+        self.collections.begin_build(VectorCollection::new(configured.clone(), store));
+        tracing::info!(
+            "Embedding model changed from {} to {}, re-embedding into {}",
+            active.model.key(),
+            configured.key(),
+            name
+        );
+        Ok(true)
+    }
+
+    /// Pause and size batches by the resource scheduler
+    pub fn with_scheduler(mut self, scheduler: Arc<ResourceScheduler>) -> Self {
+        self.scheduler = Some(scheduler);
+        self
+    }
+
+    /// Set the number of chunks re-embedded per batch
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Collections shared with the indexing pipeline
+    pub fn collections(&self) -> &Arc<VectorCollections> {
+        &self.collections
+    }
+
+    /// Whether a migration is in progress
+    pub fn is_migrating(&self) -> bool {
+        self.collections.building().is_some()
+    }
+
+    /// Progress of the current migration, if any
+    pub async fn progress(&self) -> Result<Option<MigrationProgress>, IndexError> {
+        let Some(target) = self.collections.building() else {
+            return Ok(None);
+        };
+
+        let skipped = self.skipped_ids();
+        let sql = format!(
+            r#"
+            SELECT
+                COALESCE(SUM(CASE WHEN embedding_model = ? THEN 1 ELSE 0 END), 0),
+                COALESCE(SUM(CASE WHEN embedding_model = ? OR id IN ({skipped}) THEN 0 ELSE 1 END), 0),
+                COALESCE(SUM(CASE WHEN embedding_model = ? OR id NOT IN ({skipped}) THEN 0 ELSE 1 END), 0)
+            FROM content_chunks
+            "#,
+            skipped = vec!["?"; skipped.len()].join(", ")
+        );
+        let mut query = sqlx::query_as::<_, (i64, i64, i64)>(&sql).bind(target.model.key());
+        for _ in 0..2 {
+            query = query.bind(target.model.key());
+            for id in &skipped {
+                query = query.bind(id);
+            }
+        }
+        let (migrated, remaining, skipped) = query.fetch_one(&self.db).await.map_err(db_error)?;
+
+        Ok(Some(MigrationProgress {
+            from_model: self.collections.active().model,
+            to_model: target.model,
+            migrated_chunks: migrated as u64,
+            remaining_chunks: remaining as u64,
+            skipped_chunks: skipped as u64,
+        }))
+    }
+
+    /// Re-embed one batch of chunks still tagged with another model
+    ///
+    /// Returns the number of chunks moved to the new collection.
+    pub async fn run_batch(&self) -> Result<usize, IndexError> {
+        let Some(target) = self.collections.building() else {
+            return Ok(0);
+        };
+        let model_key = target.model.key();
+        let active_key = self.collections.active().model.key();
+
+        let batch_size = match &self.scheduler {
+            Some(scheduler) => scheduler.plan().batch_size.max(1),
+            None => self.batch_size,
+        };
+        let skipped = self.skipped_ids();
+        let sql = format!(
+            r#"
+            SELECT id, file_id, content, vector_id, embedding_model
+            FROM content_chunks
+            WHERE (embedding_model IS NULL OR embedding_model != ?) AND id NOT IN ({})
+            ORDER BY id
+            LIMIT ?
+            "#,
+            vec!["?"; skipped.len()].join(", ")
+        );
+        let mut query = sqlx::query_as::<_, (String, String, String, i64, Option<String>)>(&sql).bind(&model_key);
+        for id in &skipped {
+            query = query.bind(id);
+        }
+        let rows = query
+            .bind(batch_size as i64)
+            .fetch_all(&self.db)
+            .await
+            .map_err(db_error)?;
+        if rows.is_empty() {
+            return Ok(0);
+        }
+
+        let chunks: Vec<(&str, &str)> = rows.iter().map(|row| (row.0.as_str(), row.2.as_str())).collect();
+        let texts: Vec<&str> = chunks.iter().map(|&(_, text)| text).collect();
+        let embeddings = match self.embedder.embed_batch(&texts).await {
+            Ok(embeddings) if embeddings.len() == rows.len() => embeddings.into_iter().map(Some).collect(),
+            Ok(embeddings) => {
+                return Err(IndexError::EmbeddingFailed {
+                    reason: format!("expected {} embeddings, got {}", rows.len(), embeddings.len()),
+                });
+            }
+            Err(e) => self.embed_alone(&chunks, e).await?,
+        };
+
+        let mut migrated = 0;
+        for ((id, file_id, _, vector_id, embedding_model), vector) in rows.into_iter().zip(embeddings) {
+            let Some(vector) = vector else {
+                continue;
+            };
+            // Carry over location and filter fields from the old vector
+            let old_key = embedding_model.unwrap_or_else(|| active_key.clone());
+            let old_payload = match self.collections.store_for(&old_key) {
+                Some(store) => store.get(vector_id as u64).await.map_err(storage_error)?.map(|old| old.payload),
+                None => None,
+            };
+
+            let mut point = VectorPoint::new(0, vector);
+            if let (Ok(file_id), Ok(chunk_id)) = (Uuid::parse_str(&file_id), Uuid::parse_str(&id)) {
+                point = point.with_file_id(file_id).with_chunk_id(chunk_id);
+            }
+            point.payload.extend(old_payload.unwrap_or_default());
+            let point = point.with_model(&target.model);
+            let new_id = target.store.insert(point.vector, point.payload).await.map_err(storage_error)?;
+
+            // The pipeline may have reindexed the chunk meanwhile
+            let updated = sqlx::query(
+                r#"
+                UPDATE content_chunks SET vector_id = ?, embedding_model = ?
+                WHERE id = ? AND vector_id = ? AND (embedding_model IS NULL OR embedding_model != ?)
+                "#,
+            )
+            .bind(new_id as i64)
+            .bind(&model_key)
+            .bind(&id)
+            .bind(vector_id)
+            .bind(&model_key)
+            .execute(&self.db)
+            .await
+            .map_err(db_error)?;
+
+            if updated.rows_affected() == 0 {
+                target.store.delete(new_id).await.map_err(storage_error)?;
+            } else {
+                migrated += 1;
+            }
+        }
+
+        Ok(migrated)
+    }
+
+    /// Embed the chunks of a failed batch one at a time
+    ///
+    /// Each chunk failing on its own counts towards `MAX_CHUNK_ATTEMPTS`.
+    /// When every chunk of a larger batch fails, the model itself is taken
+    /// to be unavailable and the batch error is returned instead.
+    async fn embed_alone(
+        &self,
+        chunks: &[(&str, &str)],
+        batch_error: IndexError,
+    ) -> Result<Vec<Option<Vec<f32>>>, IndexError> {
+        let mut vectors = Vec::with_capacity(chunks.len());
+        let mut failed = Vec::new();
+        for &(id, text) in chunks {
+            match self.embedder.embed_batch(&[text]).await {
+                Ok(mut embedded) if embedded.len() == 1 => vectors.push(embedded.pop()),
+                Ok(embedded) => {
+                    failed.push((id, format!("expected 1 embedding, got {}", embedded.len())));
+                    vectors.push(None);
+                }
+                Err(e) => {
+                    failed.push((id, e.to_string()));
+                    vectors.push(None);
+                }
+            }
+        }
+        if chunks.len() > 1 && failed.len() == chunks.len() {
+            return Err(batch_error);
+        }
+
+        let mut failures = self.failures.lock().unwrap();
+        let mut skipped = self.skipped.lock().unwrap();
+        for (id, reason) in failed {
+            let attempts = failures.entry(id.to_string()).or_insert(0);
+            *attempts += 1;
+            if *attempts >= MAX_CHUNK_ATTEMPTS {
+                tracing::warn!("Skipping chunk {} in the embedding model migration: {}", id, reason);
+                skipped.insert(id.to_string());
+            }
+        }
+        Ok(vectors)
+    }
+
+    /// IDs of the chunks skipped by this migration
+    fn skipped_ids(&self) -> Vec<String> {
+        self.skipped.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Make the new collection active if no chunks are left to migrate
+    ///
+    /// Skipped chunks still only have vectors in the old collection, so
+    /// they block the cutover as well. Returns whether the cutover happened.
+    pub async fn try_cut_over(&self) -> Result<bool, IndexError> {
+        match self.progress().await? {
+            Some(progress) if progress.remaining_chunks == 0 && progress.skipped_chunks == 0 => {}
+            _ => return Ok(false),
+        }
+        let Some(target) = self.collections.building() else {
+            return Ok(false);
+        };
+
+        let mut tx = self.db.begin().await.map_err(db_error)?;
+        sqlx::query("UPDATE vector_collections SET status = ? WHERE status = ?")
+            .bind(CollectionStatus::Retired.as_str())
+            .bind(CollectionStatus::Active.as_str())
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        sqlx::query("UPDATE vector_collections SET status = ?, activated_at = ? WHERE name = ?")
+            .bind(CollectionStatus::Active.as_str())
+            .bind(Utc::now().to_rfc3339())
+            .bind(&target.store.config().collection_name)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        tx.commit().await.map_err(db_error)?;
+
+        self.failures.lock().unwrap().clear();
+        self.skipped.lock().unwrap().clear();
+        if let Some(retired) = self.collections.cut_over() {
+            retired.store.clear().await.map_err(storage_error)?;
+            tracing::info!(
+                "Cut over from embedding model {} to {}",
+                retired.model.key(),
+                target.model.key()
+            );
+        }
+        Ok(true)
+    }
+
+    /// Re-embed until every chunk uses the new model, then cut over
+    ///
+    /// Waits out scheduler pauses and batches the model failed on, checking
+    /// again every `poll_interval`. While skipped chunks remain, the old
+    /// collection keeps serving search and the loop waits for the pipeline
+    /// to re-embed them.
+    pub async fn run(&self, poll_interval: Duration) -> Result<(), IndexError> {
+        while self.is_migrating() {
+            let paused = self
+                .scheduler
+                .as_ref()
+                .map(|scheduler| scheduler.plan().mode == SchedulerMode::Paused)
+                .unwrap_or(false);
+            if paused {
+                tokio::time::sleep(poll_interval).await;
+                continue;
+            }
+
+            match self.run_batch().await {
+                Ok(0) => {
+                    if !self.try_cut_over().await? {
+                        // Remaining or skipped chunks are left to the pipeline
+                        tokio::time::sleep(poll_interval).await;
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!("Re-embedding batch failed, retrying: {}", e);
+                    tokio::time::sleep(poll_interval).await;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Run the migration in the background
+    pub fn spawn(self: Arc<Self>, poll_interval: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            if let Err(e) = self.run(poll_interval).await {
+                tracing::warn!("Embedding model migration stopped: {}", e);
+            }
+        })
+    }
+}
+
+async fn register(
+    db: &SqlitePool,
+    name: &str,
+    model: &EmbeddingModelTag,
+    status: CollectionStatus,
+) -> Result<(), IndexError> {
+    sqlx::query(
+        r#"
+        INSERT INTO vector_collections (name, model_id, model_version, dimension, status, created_at, activated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(name) DO UPDATE SET
+            model_id = excluded.model_id,
+            model_version = excluded.model_version,
+            dimension = excluded.dimension,
+            status = excluded.status,
+            activated_at = excluded.activated_at
+        "#,
+    )
+    .bind(name)
+    .bind(&model.model_id)
+    .bind(&model.version)
+    .bind(model.dimension as i64)
+    .bind(status.as_str())
+    .bind(Utc::now().to_rfc3339())
+    .bind((status == CollectionStatus::Active).then(|| Utc::now().to_rfc3339()))
+    .execute(db)
+    .await
+    .map_err(db_error)?;
+    Ok(())
+}
+
+async fn retire(db: &SqlitePool, name: &str) -> Result<(), IndexError> {
+    sqlx::query("UPDATE vector_collections SET status = ? WHERE name = ?")
+        .bind(CollectionStatus::Retired.as_str())
+        .bind(name)
+        .execute(db)
+        .await
+        .map_err(db_error)?;
+    Ok(())
+}
+
+async fn open_store(
+    base: &VectorStoreConfig,
+    name: &str,
+    model: &EmbeddingModelTag,
+) -> Result<Arc<VectorStore>, IndexError> {
+    let config = base
+        .clone()
+        .with_collection_name(name)
+        .with_vector_size(model.dimension as u64);
+    Ok(Arc::new(VectorStore::new(config).await.map_err(storage_error)?))
+}
+
+fn db_error(e: sqlx::Error) -> IndexError {
+    IndexError::StorageFailed { reason: format!("database: {}", e) }
+}
+
+fn storage_error(e: impl std::fmt::Display) -> IndexError {
+    IndexError::StorageFailed { reason: e.to_string() }
+}
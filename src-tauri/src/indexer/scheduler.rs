@@ -0,0 +1,477 @@
+//! Resource-aware scheduling for background indexing
+//!
+//! Adapts how many tasks the pipeline runs at once, and how many it takes
+//! per batch, to the current system load:
+//! - Backs off as CPU usage or disk IO wait approach their budgets
+//! - Caps concurrency on battery and pauses below a battery floor
+//! - Pauses while game mode holds the indexer paused flag
+//! - Optionally runs in turbo while the user is idle
+//!
+//! Load readings come from a `ResourceProbe` so tests can inject them.
+//! Policies whose reading the platform cannot take are switched off rather
+//! than fed a neutral value: idle turbo needs Windows, IO wait needs Linux,
+//! and neither CPU nor battery readings exist on macOS yet.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{IndexingBudget, PerformanceConfig};
+
+// ============================================================================
+// Probes
+// ============================================================================
+
+/// A point-in-time reading of system load
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ResourceSample {
+    /// System-wide CPU usage in percent
+    pub cpu_percent: f32,
+    /// Share of CPU time spent waiting on disk IO, in percent
+    pub io_wait_percent: f32,
+    /// Whether the machine is running on battery power
+    pub on_battery: bool,
+    /// Battery charge in percent, if a battery is present
+    pub battery_percent: Option<u8>,
+    /// Time since the last user input
+    pub idle_for: Duration,
+}
+
+/// Which readings a probe can take
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProbeCapabilities {
+    /// System-wide CPU usage
+    pub cpu: bool,
+    /// Disk IO wait
+    pub io_wait: bool,
+    /// Power source and battery charge
+    pub power: bool,
+    /// Time since the last user input
+    pub idle: bool,
+}
+
+impl ProbeCapabilities {
+    /// Every reading is available
+    pub const ALL: Self = Self {
+        cpu: true,
+        io_wait: true,
+        power: true,
+        idle: true,
+    };
+
+    /// Policies switched off for lack of a reading
+    pub fn disabled_policies(&self) -> Vec<&'static str> {
+        let mut disabled = Vec::new();
+        if !self.cpu && !self.io_wait {
+            disabled.push("load backoff");
+        }
+        if !self.power {
+            disabled.push("battery limits");
+        }
+        if !self.idle {
+            disabled.push("idle turbo");
+        }
+        disabled
+    }
+}
+
+/// Source of load readings for the scheduler
+pub trait ResourceProbe: Send + Sync {
+    /// Take a reading of the current system load
+    fn sample(&self) -> ResourceSample;
+
+    /// Readings `sample` actually takes; the others are left at their default
+    fn capabilities(&self) -> ProbeCapabilities {
+        ProbeCapabilities::ALL
+    }
+}
+
+/// Reads load from the operating system
+///
+/// CPU and IO wait are measured between consecutive samples, so the first
+/// reading reports no load. Platforms without a source for a reading report
+/// its neutral value (no load, mains power, no idle time) and leave it out
+/// of `capabilities`.
+#[derive(Debug, Default)]
+pub struct SystemProbe {
+    last_cpu: Mutex<Option<CpuTimes>>,
+}
+
+/// Cumulative CPU time counters
+#[derive(Debug, Clone, Copy, Default)]
+struct CpuTimes {
+    total: u64,
+    idle: u64,
+    io_wait: u64,
+}
+
+impl SystemProbe {
+    /// Create a probe for the current platform
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// CPU and IO wait percentages since the previous call
+    fn cpu_usage(&self) -> (f32, f32) {
+        let Some(current) = read_cpu_times() else {
+            return (0.0, 0.0);
+        };
+        let previous = self.last_cpu.lock().unwrap().replace(current);
+
+        let Some(previous) = previous else {
+            return (0.0, 0.0);
+        };
+        let total = current.total.saturating_sub(previous.total);
+        if total == 0 {
+            return (0.0, 0.0);
+        }
+        let idle = current.idle.saturating_sub(previous.idle);
+        let io_wait = current.io_wait.saturating_sub(previous.io_wait);
+
+        let busy = total.saturating_sub(idle + io_wait);
+        (
+            busy as f32 * 100.0 / total as f32,
+            io_wait as f32 * 100.0 / total as f32,
+        )
+    }
+}
+
+impl ResourceProbe for SystemProbe {
+    fn sample(&self) -> ResourceSample {
+        let (cpu_percent, io_wait_percent) = self.cpu_usage();
+        let (on_battery, battery_percent) = read_power_status();
+
+        ResourceSample {
+            cpu_percent,
+            io_wait_percent,
+            on_battery,
+            battery_percent,
+            idle_for: read_idle_time(),
+        }
+    }
+
+    fn capabilities(&self) -> ProbeCapabilities {
+        ProbeCapabilities {
+            cpu: cfg!(any(target_os = "linux", windows)),
+            io_wait: cfg!(target_os = "linux"),
+            power: cfg!(any(target_os = "linux", windows)),
+            idle: cfg!(windows),
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_cpu_times() -> Option<CpuTimes> {
+    let stat = std::fs::read_to_string("/proc/stat").ok()?;
+    let line = stat.lines().find(|line| line.starts_with("cpu "))?;
+    let fields: Vec<u64> = line
+        .split_whitespace()
+        .skip(1)
+        .filter_map(|field| field.parse().ok())
+        .collect();
+
+    // user nice system idle iowait irq softirq steal ...
+    if fields.len() < 5 {
+        return None;
+    }
+    Some(CpuTimes {
+        total: fields.iter().take(8).sum(),
+        idle: fields[3],
+        io_wait: fields[4],
+    })
+}
+
+#[cfg(windows)]
+fn read_cpu_times() -> Option<CpuTimes> {
+    use windows::Win32::Foundation::FILETIME;
+    use windows::Win32::System::Threading::GetSystemTimes;
+
+    fn ticks(time: FILETIME) -> u64 {
+        ((time.dwHighDateTime as u64) << 32) | time.dwLowDateTime as u64
+    }
+
+    let mut idle = FILETIME::default();
+    let mut kernel = FILETIME::default();
+    let mut user = FILETIME::default();
+    unsafe {
+        GetSystemTimes(Some(&mut idle as *mut _), Some(&mut kernel as *mut _), Some(&mut user as *mut _)).ok()?;
+    }
+
+    // Kernel time includes idle time; Windows does not expose IO wait
+    Some(CpuTimes {
+        total: ticks(kernel) + ticks(user),
+        idle: ticks(idle),
+        io_wait: 0,
+    })
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn read_cpu_times() -> Option<CpuTimes> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn read_power_status() -> (bool, Option<u8>) {
+    let Ok(entries) = std::fs::read_dir("/sys/class/power_supply") else {
+        return (false, None);
+    };
+
+    let mut on_battery = false;
+    let mut battery_percent = None;
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        let read = |name: &str| std::fs::read_to_string(dir.join(name)).map(|s| s.trim().to_string());
+
+        if read("type").as_deref() == Ok("Battery") {
+            if read("status").as_deref() == Ok("Discharging") {
+                on_battery = true;
+            }
+            if let Ok(capacity) = read("capacity") {
+                battery_percent = capacity.parse().ok().or(battery_percent);
+            }
+        }
+    }
+
+    (on_battery, battery_percent)
+}
+
+#[cfg(windows)]
+fn read_power_status() -> (bool, Option<u8>) {
+    use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+    let mut status = SYSTEM_POWER_STATUS::default();
+    if unsafe { GetSystemPowerStatus(&mut status) }.is_err() {
+        return (false, None);
+    }
+
+    // ACLineStatus: 0 = offline, 1 = online; 255 = unknown battery level
+    let battery_percent = (status.BatteryLifePercent != 255).then_some(status.BatteryLifePercent);
+    (status.ACLineStatus == 0, battery_percent)
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn read_power_status() -> (bool, Option<u8>) {
+    (false, None)
+}
+
+#[cfg(windows)]
+fn read_idle_time() -> Duration {
+    use windows::Win32::System::SystemInformation::GetTickCount;
+    use windows::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+
+    let mut info = LASTINPUTINFO {
+        cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+        dwTime: 0,
+    };
+    unsafe {
+        if !GetLastInputInfo(&mut info).as_bool() {
+            return Duration::ZERO;
+        }
+        Duration::from_millis(GetTickCount().wrapping_sub(info.dwTime) as u64)
+    }
+}
+
+/// Idle time has no portable source outside Windows; idle turbo is off there
+#[cfg(not(windows))]
+fn read_idle_time() -> Duration {
+    Duration::ZERO
+}
+
+// ============================================================================
+// Scheduler
+// ============================================================================
+
+/// How the scheduler is currently running the indexer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SchedulerMode {
+    /// No tasks are started
+    Paused,
+    /// Running below the configured concurrency
+    Throttled,
+    /// Running at the configured concurrency
+    Normal,
+    /// Running above the configured concurrency while the user is idle
+    Turbo,
+}
+
+/// What the pipeline should run next
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchedulePlan {
+    /// Mode the plan was made in
+    pub mode: SchedulerMode,
+    /// Tasks to run at once
+    pub concurrency: usize,
+    /// Tasks to take from the queue
+    pub batch_size: usize,
+    /// Reading the plan was based on
+    pub sample: ResourceSample,
+}
+
+/// Budgets and baseline sizes the scheduler scales from
+#[derive(Debug, Clone)]
+struct SchedulerSettings {
+    budget: IndexingBudget,
+    base_concurrency: usize,
+    base_batch_size: usize,
+}
+
+/// Picks indexing concurrency and batch size from system load
+pub struct ResourceScheduler {
+    settings: RwLock<SchedulerSettings>,
+    probe: Arc<dyn ResourceProbe>,
+    capabilities: ProbeCapabilities,
+    paused: Option<Arc<AtomicBool>>,
+    last_mode: Mutex<Option<SchedulerMode>>,
+}
+
+impl ResourceScheduler {
+    /// Create a scheduler with explicit budgets and baseline sizes
+    pub fn new(
+        budget: IndexingBudget,
+        base_concurrency: usize,
+        base_batch_size: usize,
+        probe: Arc<dyn ResourceProbe>,
+    ) -> Self {
+        let capabilities = probe.capabilities();
+        let disabled = capabilities.disabled_policies();
+        if !disabled.is_empty() {
+            tracing::info!("Indexing scheduler: {} unavailable on this platform", disabled.join(", "));
+        }
+
+        Self {
+            settings: RwLock::new(SchedulerSettings {
+                budget,
+                base_concurrency: base_concurrency.max(1),
+                base_batch_size: base_batch_size.max(1),
+            }),
+            probe,
+            capabilities,
+            paused: None,
+            last_mode: Mutex::new(None),
+        }
+    }
+
+    /// Create a scheduler from the performance settings, reading the system
+    pub fn from_config(config: &PerformanceConfig, base_batch_size: usize) -> Self {
+        Self::new(
+            config.indexing_budget.clone(),
+            config.indexing_threads as usize,
+            base_batch_size,
+            Arc::new(SystemProbe::new()),
+        )
+    }
+
+    /// Pause whenever the flag is set, e.g. `GameModePolicy::indexer_paused_flag`
+    pub fn with_pause_flag(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.paused = Some(flag);
+        self
+    }
+
+    /// Apply updated performance settings
+    pub fn configure(&self, config: &PerformanceConfig) {
+        let mut settings = self.settings.write().unwrap();
+        settings.budget = config.indexing_budget.clone();
+        settings.base_concurrency = (config.indexing_threads as usize).max(1);
+    }
+
+    /// Current budgets
+    pub fn budget(&self) -> IndexingBudget {
+        self.settings.read().unwrap().budget.clone()
+    }
+
+    /// Readings the scheduler's probe can take
+    pub fn capabilities(&self) -> ProbeCapabilities {
+        self.capabilities
+    }
+
+    /// Mode of the most recent plan
+    pub fn last_mode(&self) -> Option<SchedulerMode> {
+        *self.last_mode.lock().unwrap()
+    }
+
+    /// Take a reading and plan the next batch
+    pub fn plan(&self) -> SchedulePlan {
+        let plan = self.plan_for(self.probe.sample());
+
+        let previous = self.last_mode.lock().unwrap().replace(plan.mode);
+        if previous != Some(plan.mode) {
+            tracing::info!(
+                "Indexing scheduler {:?}: concurrency {}, batch {} (cpu {:.0}%, io wait {:.0}%, battery {})",
+                plan.mode,
+                plan.concurrency,
+                plan.batch_size,
+                plan.sample.cpu_percent,
+                plan.sample.io_wait_percent,
+                plan.sample.on_battery
+            );
+        }
+
+        plan
+    }
+
+    /// Plan the next batch for a given reading
+    pub fn plan_for(&self, sample: ResourceSample) -> SchedulePlan {
+        let settings = self.settings.read().unwrap().clone();
+        let budget = &settings.budget;
+        let caps = self.capabilities;
+        let base_concurrency = settings.base_concurrency;
+        let base_batch = settings.base_batch_size;
+
+        let plan = |mode, concurrency: usize, batch_size: usize| SchedulePlan {
+            mode,
+            concurrency,
+            batch_size,
+            sample,
+        };
+
+        if self.paused.as_ref().is_some_and(|flag| flag.load(Ordering::SeqCst)) {
+            return plan(SchedulerMode::Paused, 0, 0);
+        }
+        let on_battery = caps.power && sample.on_battery;
+        if on_battery
+            && sample
+                .battery_percent
+                .is_some_and(|percent| percent < budget.pause_below_battery_percent)
+        {
+            return plan(SchedulerMode::Paused, 0, 0);
+        }
+
+        // Share of the tighter budget already used by the rest of the system
+        let cpu_load = sample.cpu_percent / budget.max_cpu_percent.max(1.0);
+        let io_load = sample.io_wait_percent / budget.max_io_wait_percent.max(1.0);
+        let load = (if caps.cpu { cpu_load } else { 0.0 }).max(if caps.io_wait { io_load } else { 0.0 });
+
+        let idle = caps.idle && sample.idle_for >= Duration::from_secs(budget.idle_threshold_secs);
+        if budget.turbo_when_idle && idle && !on_battery && load < 1.0 {
+            let multiplier = budget.turbo_multiplier.max(1) as usize;
+            return plan(SchedulerMode::Turbo, base_concurrency * multiplier, base_batch * multiplier);
+        }
+
+        // Full speed below half the budget, scaling down to one task at the limit
+        let factor = ((1.0 - load) * 2.0).clamp(0.0, 1.0);
+        let mut concurrency = ((base_concurrency as f32 * factor).ceil() as usize).max(1);
+        let batch_size = ((base_batch as f32 * factor).ceil() as usize).max(1);
+
+        if on_battery {
+            concurrency = concurrency.min(budget.battery_max_concurrency.max(1) as usize);
+        }
+
+        let mode = if concurrency < base_concurrency || batch_size < base_batch {
+            SchedulerMode::Throttled
+        } else {
+            SchedulerMode::Normal
+        };
+        plan(mode, concurrency, batch_size)
+    }
+}
+
+impl std::fmt::Debug for ResourceScheduler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResourceScheduler")
+            .field("settings", &self.settings)
+            .field("last_mode", &self.last_mode())
+            .finish()
+    }
+}
@@ -0,0 +1,274 @@
+//! Durable task storage for the resilient indexer
+//!
+//! Persists pending, failed, in-progress and dead-letter tasks in the
+//! `index_tasks` table so the backlog and failure history survive restarts.
+//! Status updates are only applied when `TaskStatus::can_transition_to`
+//! allows the move from the stored status.
+
+use std::path::PathBuf;
+use std::time::Instant;
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use super::{IndexError, IndexTask, TaskPriority, TaskStatus};
+
+/// Every task status, for deriving allowed predecessors
+const ALL_STATUSES: [TaskStatus; 5] = [
+    TaskStatus::Pending,
+    TaskStatus::Processing,
+    TaskStatus::Completed,
+    TaskStatus::Failed,
+    TaskStatus::DeadLetter,
+];
+
+/// Row shape of `index_tasks`
+type TaskRow = (
+    String,         // id
+    String,         // file_id
+    String,         // path
+    i64,            // priority
+    String,         // status
+    i64,            // retry_count
+    i64,            // max_retries
+    Option<String>, // next_retry_at
+    Option<String>, // last_error
+    String,         // created_at
+);
+
+/// SQLite-backed store for indexer tasks
+#[derive(Debug, Clone)]
+pub struct TaskStore {
+    db: SqlitePool,
+}
+
+impl TaskStore {
+    /// Create a store over a migrated database
+    pub fn new(db: SqlitePool) -> Self {
+        Self { db }
+    }
+
+    /// Persist a newly submitted task
+    pub async fn insert(&self, task: &IndexTask) -> Result<(), IndexError> {
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            r#"
+            INSERT INTO index_tasks (
+                id, file_id, path, priority, status, retry_count, max_retries,
+                next_retry_at, last_error, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(task.id.to_string())
+        .bind(task.file_id.to_string())
+        .bind(task.path.to_string_lossy().to_string())
+        .bind(task.priority as i64)
+        .bind(status_to_str(task.status))
+        .bind(task.retry_count as i64)
+        .bind(task.max_retries as i64)
+        .bind(task.next_retry_at.map(|t| instant_to_utc(t).to_rfc3339()))
+        .bind(error_to_json(task))
+        .bind(instant_to_utc(task.created_at).to_rfc3339())
+        .bind(now)
+        .execute(&self.db)
+        .await
+        .map_err(db_error)?;
+
+        Ok(())
+    }
+
+    /// Persist the current state of a task
+    ///
+    /// Fails with `InvalidStateTransition` if the stored status cannot move
+    /// to the task's status, and with `TaskNotFound` if the task was never
+    /// inserted.
+    pub async fn update(&self, task: &IndexTask) -> Result<(), IndexError> {
+        let predecessors: Vec<TaskStatus> = ALL_STATUSES
+            .iter()
+            .copied()
+            .filter(|from| from.can_transition_to(task.status))
+            .collect();
+        let placeholders = vec!["?"; predecessors.len()].join(", ");
+
+        let sql = format!(
+            r#"
+            UPDATE index_tasks
+            SET status = ?, retry_count = ?, max_retries = ?, next_retry_at = ?,
+                last_error = ?, path = ?, updated_at = ?
+            WHERE id = ? AND status IN ({})
+            "#,
+            placeholders
+        );
+
+        let mut query = sqlx::query(&sql)
+            .bind(status_to_str(task.status))
+            .bind(task.retry_count as i64)
+            .bind(task.max_retries as i64)
+            .bind(task.next_retry_at.map(|t| instant_to_utc(t).to_rfc3339()))
+            .bind(error_to_json(task))
+            .bind(task.path.to_string_lossy().to_string())
+            .bind(Utc::now().to_rfc3339())
+            .bind(task.id.to_string());
+        for status in &predecessors {
+            query = query.bind(status_to_str(*status));
+        }
+
+        let updated = query.execute(&self.db).await.map_err(db_error)?.rows_affected();
+        if updated > 0 {
+            return Ok(());
+        }
+
+        match self.stored_status(task.id).await? {
+            Some(from) => Err(IndexError::InvalidStateTransition {
+                from,
+                to: task.status,
+            }),
+            None => Err(IndexError::TaskNotFound { task_id: task.id }),
+        }
+    }
+
+    /// Remove a task, returning whether it existed
+    pub async fn delete(&self, task_id: Uuid) -> Result<bool, IndexError> {
+        let result = sqlx::query("DELETE FROM index_tasks WHERE id = ?")
+            .bind(task_id.to_string())
+            .execute(&self.db)
+            .await
+            .map_err(db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Remove all dead-letter tasks
+    pub async fn delete_dead_letters(&self) -> Result<u64, IndexError> {
+        let result = sqlx::query("DELETE FROM index_tasks WHERE status = ?")
+            .bind(status_to_str(TaskStatus::DeadLetter))
+            .execute(&self.db)
+            .await
+            .map_err(db_error)?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Load every stored task, oldest first
+    ///
+    /// Rows that cannot be decoded are logged and skipped.
+    pub async fn load_all(&self) -> Result<Vec<IndexTask>, IndexError> {
+        let rows: Vec<TaskRow> = sqlx::query_as(
+            r#"
+            SELECT id, file_id, path, priority, status, retry_count, max_retries,
+                   next_retry_at, last_error, created_at
+            FROM index_tasks
+            ORDER BY created_at
+            "#,
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(db_error)?;
+
+        let mut tasks = Vec::with_capacity(rows.len());
+        for row in rows {
+            let id = row.0.clone();
+            match task_from_row(row) {
+                Some(task) => tasks.push(task),
+                None => tracing::warn!("Skipping undecodable index task {}", id),
+            }
+        }
+
+        Ok(tasks)
+    }
+
+    async fn stored_status(&self, task_id: Uuid) -> Result<Option<TaskStatus>, IndexError> {
+        let row: Option<(String,)> = sqlx::query_as("SELECT status FROM index_tasks WHERE id = ?")
+            .bind(task_id.to_string())
+            .fetch_optional(&self.db)
+            .await
+            .map_err(db_error)?;
+
+        Ok(row.and_then(|(status,)| status_from_str(&status)))
+    }
+}
+
+// ============================================================================
+// Encoding helpers
+// ============================================================================
+
+fn task_from_row(row: TaskRow) -> Option<IndexTask> {
+    let (id, file_id, path, priority, status, retry_count, max_retries, next_retry_at, last_error, created_at) = row;
+
+    Some(IndexTask {
+        id: Uuid::parse_str(&id).ok()?,
+        file_id: Uuid::parse_str(&file_id).ok()?,
+        path: PathBuf::from(path),
+        priority: priority_from_i64(priority),
+        created_at: parse_time(&created_at).map(utc_to_instant).unwrap_or_else(Instant::now),
+        retry_count: retry_count.max(0) as u32,
+        max_retries: max_retries.max(0) as u32,
+        next_retry_at: next_retry_at.as_deref().and_then(parse_time).map(utc_to_instant),
+        last_error: last_error.and_then(|json| serde_json::from_str(&json).ok()),
+        status: status_from_str(&status)?,
+    })
+}
+
+fn status_to_str(status: TaskStatus) -> String {
+    format!("{:?}", status)
+}
+
+fn status_from_str(s: &str) -> Option<TaskStatus> {
+    ALL_STATUSES.iter().copied().find(|status| status_to_str(*status) == s)
+}
+
+fn priority_from_i64(level: i64) -> TaskPriority {
+    match level {
+        0 => TaskPriority::Low,
+        2 => TaskPriority::High,
+        3 => TaskPriority::Urgent,
+        _ => TaskPriority::Normal,
+    }
+}
+
+fn error_to_json(task: &IndexTask) -> Option<String> {
+    task.last_error
+        .as_ref()
+        .and_then(|e| serde_json::to_string(e).ok())
+}
+
+fn parse_time(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Convert a monotonic instant to wall-clock time for storage
+fn instant_to_utc(instant: Instant) -> DateTime<Utc> {
+    let now = Instant::now();
+    let wall_now = Utc::now();
+
+    if instant >= now {
+        wall_now + chrono::Duration::from_std(instant - now).unwrap_or_else(|_| chrono::Duration::zero())
+    } else {
+        wall_now - chrono::Duration::from_std(now - instant).unwrap_or_else(|_| chrono::Duration::zero())
+    }
+}
+
+/// Convert stored wall-clock time back to a monotonic instant
+fn utc_to_instant(at: DateTime<Utc>) -> Instant {
+    let now = Instant::now();
+    let delta = at - Utc::now();
+
+    match delta.to_std() {
+        Ok(ahead) => now + ahead,
+        Err(_) => (-delta)
+            .to_std()
+            .ok()
+            .and_then(|behind| now.checked_sub(behind))
+            .unwrap_or(now),
+    }
+}
+
+fn db_error(e: sqlx::Error) -> IndexError {
+    IndexError::StorageFailed {
+        reason: format!("task store: {}", e),
+    }
+}
@@ -444,6 +444,20 @@ fn test_dead_letter_stats() {
     });
 }
 
+#[test]
+fn test_chunk_reuse_ratio() {
+    let stats = IndexerStats::default();
+    assert_eq!(stats.snapshot().chunk_reuse_ratio(), 0.0);
+
+    stats.record_chunks(3, 1);
+    stats.record_unchanged_file();
+    let snapshot = stats.snapshot();
+    assert_eq!(snapshot.chunks_reused, 3);
+    assert_eq!(snapshot.chunks_embedded, 1);
+    assert_eq!(snapshot.files_unchanged, 1);
+    assert!((snapshot.chunk_reuse_ratio() - 0.75).abs() < f64::EPSILON);
+}
+
 #[test]
 fn test_file_access_check() {
     use file_access::*;
@@ -460,3 +474,1780 @@ fn test_file_access_check() {
     let non_existent = PathBuf::from("/this/path/does/not/exist/file.txt");
     assert_eq!(check_file_accessible(&non_existent), FileAccessResult::NotFound);
 }
+
+// ============================================================================
+// Indexing Pipeline Integration Tests
+// ============================================================================
+
+mod pipeline_tests {
+    use super::super::pipeline::{ChunkEmbedder, ImageSpace, ImageSpaceEmbedder, IndexingPipeline, PipelineConfig};
+    use super::super::reembed::ModelMigrator;
+    use super::super::scheduler::{ResourceSample, ResourceScheduler};
+    use super::super::{IndexError, ResilientBatchIndexer, TaskPriority, TaskStore};
+    use super::scheduler_tests::FixedProbe;
+    use crate::config::IndexingBudget;
+    use crate::embeddings::EmbeddingModelTag;
+    use crate::parser::ContentParserService;
+    use crate::reconcile::ReconciliationService;
+    use crate::search::{TextIndex, TextIndexConfig};
+    use crate::vector::{payload_fields, VectorStore, VectorStoreConfig};
+    use super::super::CatchUp;
+    use crate::watcher::{
+        DirectoryFilter, EventBatch, EventJournal, FileEvent, FileIdentity, FileIdentitySource, JournalState,
+    };
+    use async_trait::async_trait;
+    use sqlx::SqlitePool;
+    use std::path::{Path, PathBuf};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    const DIM: usize = 8;
+
+    /// Deterministic embedder that counts calls and can be made to fail,
+    /// for every text or for texts containing `poison`
+    #[derive(Default)]
+    struct MockEmbedder {
+        calls: AtomicUsize,
+        fail: AtomicBool,
+        version: AtomicUsize,
+        poison: std::sync::Mutex<Option<String>>,
+    }
+
+    #[async_trait]
+    impl ChunkEmbedder for MockEmbedder {
+        fn model(&self) -> EmbeddingModelTag {
+            EmbeddingModelTag::new("mock", self.version.load(Ordering::SeqCst).to_string(), DIM)
+        }
+
+        async fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, IndexError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let poisoned = match *self.poison.lock().unwrap() {
+                Some(ref poison) => texts.iter().any(|text| text.contains(poison.as_str())),
+                None => false,
+            };
+            if self.fail.load(Ordering::SeqCst) || poisoned {
+                return Err(IndexError::EmbeddingFailed {
+                    reason: "mock failure".to_string(),
+                });
+            }
+            Ok(texts
+                .iter()
+                .map(|text| {
+                    let mut vector = vec![0.0; DIM];
+                    for (i, byte) in text.bytes().enumerate() {
+                        vector[i % DIM] += byte as f32 / 255.0;
+                    }
+                    vector
+                })
+                .collect())
+        }
+    }
+
+    struct Harness {
+        pipeline: IndexingPipeline,
+        embedder: Arc<MockEmbedder>,
+        vectors: Arc<VectorStore>,
+        text_index: Arc<TextIndex>,
+        db: SqlitePool,
+        files: PathBuf,
+        _temp_dir: TempDir,
+    }
+
+    async fn harness() -> Harness {
+        let temp_dir = TempDir::new().unwrap();
+        let files = temp_dir.path().join("files");
+        std::fs::create_dir_all(&files).unwrap();
+
+        let config = crate::db::DatabaseConfig::with_path(temp_dir.path().join("test.db")).with_wal(true);
+        let db = crate::db::create_database_pool(&config).await.unwrap();
+        crate::db::migration::MigrationManager::new(db.clone())
+            .run_migrations()
+            .await
+            .unwrap();
+
+        let vectors = Arc::new(
+            VectorStore::new(
+                VectorStoreConfig::default()
+                    .with_storage_path(temp_dir.path().join("vectors").to_string_lossy().to_string())
+                    .with_vector_size(DIM as u64),
+            )
+            .await
+            .unwrap(),
+        );
+        let text_index = Arc::new(
+            TextIndex::new(TextIndexConfig {
+                index_path: temp_dir.path().join("text_index"),
+                ..Default::default()
+            })
+            .unwrap(),
+        );
+        let embedder = Arc::new(MockEmbedder::default());
+
+        let pipeline = IndexingPipeline::new(
+            db.clone(),
+            Arc::new(ResilientBatchIndexer::with_defaults()),
+            Arc::new(ContentParserService::new()),
+            embedder.clone(),
+            vectors.clone(),
+            text_index.clone(),
+        )
+        .unwrap();
+
+        Harness {
+            pipeline,
+            embedder,
+            vectors,
+            text_index,
+            db,
+            files,
+            _temp_dir: temp_dir,
+        }
+    }
+
+    fn write_file(dir: &Path, name: &str, content: &str) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn batch(events: Vec<FileEvent>) -> EventBatch {
+        EventBatch {
+            id: Uuid::now_v7(),
+            events,
+            created_at: Instant::now(),
+        }
+    }
+
+    async fn file_row(db: &SqlitePool, path: &Path) -> Option<(String, String)> {
+        sqlx::query_as("SELECT id, index_status FROM files WHERE path = ?")
+            .bind(path.to_string_lossy().to_string())
+            .fetch_optional(db)
+            .await
+            .unwrap()
+    }
+
+    async fn chunk_count(db: &SqlitePool, file_id: &str) -> i64 {
+        let row: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM content_chunks WHERE file_id = ?")
+            .bind(file_id)
+            .fetch_one(db)
+            .await
+            .unwrap();
+        row.0
+    }
+
+    /// The text index reader reloads shortly after commit; poll for it
+    async fn text_hits(index: &TextIndex, query: &str, expected: usize) -> usize {
+        let mut hits = 0;
+        for _ in 0..40 {
+            hits = index.search(query, 10).unwrap().len();
+            if hits == expected {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+        hits
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_indexes_created_files() {
+        let h = harness().await;
+        let path = write_file(&h.files, "notes.md", "# Quarterly plan\n\nThe zeppelin budget is approved.");
+
+        let report = h.pipeline.handle_batch(&batch(vec![FileEvent::Created(path.clone())])).await.unwrap();
+        assert_eq!(report.submitted, 1);
+
+        let report = h.pipeline.run_until_idle().await;
+        assert_eq!(report.indexed, 1);
+        assert_eq!(report.failed, 0);
+
+        let (file_id, status) = file_row(&h.db, &path).await.unwrap();
+        assert_eq!(status, "Indexed");
+
+        let chunks = chunk_count(&h.db, &file_id).await;
+        assert!(chunks > 0);
+        assert_eq!(h.vectors.count().await.unwrap(), chunks as u64);
+        assert!(text_hits(&h.text_index, "zeppelin", 1).await >= 1);
+
+        // Stored vector ids point at real vectors carrying the file id
+        let (vector_id,): (i64,) = sqlx::query_as("SELECT vector_id FROM content_chunks WHERE file_id = ? LIMIT 1")
+            .bind(&file_id)
+            .fetch_one(&h.db)
+            .await
+            .unwrap();
+        let point = h.vectors.get(vector_id as u64).await.unwrap().unwrap();
+        assert_eq!(point.file_id().unwrap().to_string(), file_id);
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_reindexes_modified_file_without_duplicates() {
+        let h = harness().await;
+        let path = write_file(&h.files, "draft.txt", "first version");
+        h.pipeline.handle_batch(&batch(vec![FileEvent::Created(path.clone())])).await.unwrap();
+        h.pipeline.run_until_idle().await;
+
+        write_file(&h.files, "draft.txt", "second version with more words");
+        h.pipeline.handle_batch(&batch(vec![FileEvent::Modified(path.clone())])).await.unwrap();
+        h.pipeline.run_until_idle().await;
+
+        let (file_id, status) = file_row(&h.db, &path).await.unwrap();
+        assert_eq!(status, "Indexed");
+        let chunks = chunk_count(&h.db, &file_id).await;
+        assert_eq!(h.vectors.count().await.unwrap(), chunks as u64);
+    }
+
+    #[tokio::test]
+    async fn test_hybrid_search_over_indexed_files() {
+        use crate::search::hybrid::{HybridSearchConfig, HybridSearchEngine, HybridSearchFilters, SearchSource};
+
+        let h = harness().await;
+        let notes = write_file(&h.files, "notes.md", "The zeppelin budget is approved.");
+        let other = write_file(&h.files, "other.md", "Minutes of the gardening club.");
+        h.pipeline
+            .handle_batch(&batch(vec![FileEvent::Created(notes.clone()), FileEvent::Created(other)]))
+            .await
+            .unwrap();
+        h.pipeline.run_until_idle().await;
+        assert_eq!(text_hits(&h.text_index, "zeppelin", 1).await, 1);
+        let (file_id, _) = file_row(&h.db, &notes).await.unwrap();
+
+        let engine = HybridSearchEngine::with_config(HybridSearchConfig::default().with_explain(true)).unwrap();
+        let query = h.pipeline.embedder().embed_batch(&["zeppelin"]).await.unwrap().remove(0);
+        let results = engine
+            .search(h.pipeline.text_index(), &h.vectors, "zeppelin", Some(&query), &HybridSearchFilters::new())
+            .await
+            .unwrap();
+
+        // The keyword hit is merged with the same chunk's vector hit
+        let top = &results[0];
+        assert_eq!(top.file_id.to_string(), file_id);
+        assert_eq!(top.source, SearchSource::Both);
+        let explanation = top.explanation.as_ref().expect("explanation");
+        assert!(explanation.raw_vector_score.is_some());
+        assert!(explanation.bm25_explanation.is_some());
+
+        // Without a query vector only keyword hits come back, at full weight
+        let results = engine
+            .search(h.pipeline.text_index(), &h.vectors, "zeppelin", None, &HybridSearchFilters::new())
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].source, SearchSource::BM25);
+        assert!((results[0].explanation.as_ref().unwrap().merged_score - 1.0).abs() < 0.001);
+    }
+
+    /// Image-space embedder that maps every image and query to the same vector
+    struct MockImageEmbedder;
+
+    #[async_trait]
+    impl ImageSpaceEmbedder for MockImageEmbedder {
+        async fn embed_image_path(&self, _path: &Path) -> Result<Vec<f32>, IndexError> {
+            Ok(vec![1.0; DIM])
+        }
+
+        async fn embed_image_query(&self, _query: &str) -> Result<Vec<f32>, IndexError> {
+            Ok(vec![1.0; DIM])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_indexes_image_vectors() {
+        use crate::search::hybrid::{HybridSearchEngine, HybridSearchFilters};
+
+        let h = harness().await;
+        let image_store = Arc::new(
+            VectorStore::new(
+                VectorStoreConfig::default()
+                    .with_storage_path(h._temp_dir.path().join("image_vectors").to_string_lossy().to_string())
+                    .with_vector_size(DIM as u64),
+            )
+            .await
+            .unwrap(),
+        );
+        let pipeline = h
+            .pipeline
+            .with_image_space(ImageSpace::new(image_store.clone(), Arc::new(MockImageEmbedder)));
+        let h = Harness { pipeline, ..h };
+
+        let photo = h.files.join("beach.jpg");
+        image::RgbImage::new(16, 16).save(&photo).unwrap();
+        let notes = write_file(&h.files, "notes.txt", "no pictures here");
+        h.pipeline
+            .handle_batch(&batch(vec![FileEvent::Created(photo.clone()), FileEvent::Created(notes)]))
+            .await
+            .unwrap();
+        h.pipeline.run_until_idle().await;
+        let (file_id, status) = file_row(&h.db, &photo).await.unwrap();
+        assert_eq!(status, "Indexed");
+
+        // Only the image is embedded into the image space
+        assert_eq!(image_store.count().await.unwrap(), 1);
+        let (rows,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM image_vectors WHERE file_id = ?")
+            .bind(&file_id)
+            .fetch_one(&h.db)
+            .await
+            .unwrap();
+        assert_eq!(rows, 1);
+
+        // A re-index of the unchanged image keeps its vector
+        h.pipeline.handle_batch(&batch(vec![FileEvent::Modified(photo.clone())])).await.unwrap();
+        h.pipeline.run_until_idle().await;
+        assert_eq!(image_store.count().await.unwrap(), 1);
+
+        // Image queries find the photo through the image store
+        let space = h.pipeline.image_space().unwrap();
+        let query = space.embedder.embed_image_query("sunny beach").await.unwrap();
+        let results = HybridSearchEngine::new()
+            .search(h.pipeline.text_index(), &space.store, "sunny beach", Some(&query), &HybridSearchFilters::new())
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].file_id.to_string(), file_id);
+
+        std::fs::remove_file(&photo).unwrap();
+        h.pipeline.handle_batch(&batch(vec![FileEvent::Deleted(photo)])).await.unwrap();
+        assert_eq!(image_store.count().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_deletes_file_data() {
+        let h = harness().await;
+        let path = write_file(&h.files, "old.txt", "obsolete quokka report");
+        h.pipeline.handle_batch(&batch(vec![FileEvent::Created(path.clone())])).await.unwrap();
+        h.pipeline.run_until_idle().await;
+        let (file_id, _) = file_row(&h.db, &path).await.unwrap();
+
+        std::fs::remove_file(&path).unwrap();
+        let report = h.pipeline.handle_batch(&batch(vec![FileEvent::Deleted(path.clone())])).await.unwrap();
+        assert_eq!(report.removed, 1);
+
+        assert!(file_row(&h.db, &path).await.is_none());
+        assert_eq!(chunk_count(&h.db, &file_id).await, 0);
+        assert_eq!(h.vectors.count().await.unwrap(), 0);
+        assert_eq!(text_hits(&h.text_index, "quokka", 0).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_mass_deletion_from_watcher_is_held() {
+        let h = harness().await;
+        let guard = Arc::new(ReconciliationService::new(h.db.clone()));
+        let pipeline = h.pipeline.with_deletion_guard(guard.clone());
+        pipeline.set_monitored_roots(&[h.files.clone()]);
+
+        let docs = h.files.join("docs");
+        std::fs::create_dir(&docs).unwrap();
+        let mut events = vec![FileEvent::Created(write_file(&h.files, "keep.txt", "kept wombat"))];
+        let mut paths = Vec::new();
+        for i in 0..11 {
+            let path = write_file(&docs, &format!("note{:02}.txt", i), &format!("numbat note {}", i));
+            events.push(FileEvent::Created(path.clone()));
+            paths.push(path);
+        }
+        pipeline.handle_batch(&batch(events)).await.unwrap();
+        pipeline.run_until_idle().await;
+
+        // Most of the root at once waits for confirmation
+        std::fs::remove_dir_all(&docs).unwrap();
+        let report = pipeline.handle_batch(&batch(vec![FileEvent::Deleted(docs.clone())])).await.unwrap();
+        assert_eq!(report.removed, 0);
+        assert!(file_row(&h.db, &paths[0]).await.is_some());
+        let held = guard.held_deletions().await.unwrap();
+        assert_eq!(held.len(), 1);
+        assert_eq!(held[0].paths, paths);
+
+        let result = guard.confirm_held_deletions(&h.files, &paths[..3]).await.unwrap();
+        let report = pipeline.handle_reconcile(&result).await.unwrap();
+        assert_eq!(report.removed, 3);
+        assert!(file_row(&h.db, &paths[0]).await.is_none());
+        assert!(file_row(&h.db, &paths[3]).await.is_some());
+        assert_eq!(guard.held_deletions().await.unwrap()[0].paths, paths[3..].to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_rename_updates_path_only() {
+        let h = harness().await;
+        let old_path = write_file(&h.files, "before.txt", "platypus migration notes");
+        h.pipeline.handle_batch(&batch(vec![FileEvent::Created(old_path.clone())])).await.unwrap();
+        h.pipeline.run_until_idle().await;
+        let (file_id, _) = file_row(&h.db, &old_path).await.unwrap();
+        let embed_calls = h.embedder.calls.load(Ordering::SeqCst);
+
+        let new_path = h.files.join("after.txt");
+        std::fs::rename(&old_path, &new_path).unwrap();
+        let report = h
+            .pipeline
+            .handle_batch(&batch(vec![FileEvent::Renamed(old_path.clone(), new_path.clone())]))
+            .await
+            .unwrap();
+        assert_eq!(report.renamed, 1);
+        assert_eq!(report.submitted, 0);
+
+        assert!(file_row(&h.db, &old_path).await.is_none());
+        let (renamed_id, status) = file_row(&h.db, &new_path).await.unwrap();
+        assert_eq!(renamed_id, file_id);
+        assert_eq!(status, "Indexed");
+        assert!(chunk_count(&h.db, &file_id).await > 0);
+
+        // Nothing was re-embedded
+        h.pipeline.run_until_idle().await;
+        assert_eq!(h.embedder.calls.load(Ordering::SeqCst), embed_calls);
+        assert!(text_hits(&h.text_index, "platypus", 1).await >= 1);
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_provides_identity_of_indexed_files() {
+        let h = harness().await;
+        let path = write_file(&h.files, "tracked.txt", "numbat field observations");
+        h.pipeline.handle_batch(&batch(vec![FileEvent::Created(path.clone())])).await.unwrap();
+        h.pipeline.run_until_idle().await;
+
+        // Still answers once the file is gone from disk
+        let on_disk = FileIdentity::from_path(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let stored = h.pipeline.identity(&path).await.unwrap();
+
+        assert_eq!(stored.size, on_disk.size);
+        assert_eq!(stored.file_id, on_disk.file_id);
+        assert_eq!(
+            stored.content_hash.as_deref(),
+            Some(blake3::hash(b"numbat field observations").to_hex().as_str())
+        );
+        assert!(h.pipeline.identity(&h.files.join("unknown.txt")).await.is_none());
+
+        // Several deleted paths are answered together
+        let unknown = h.files.join("unknown.txt");
+        let found = h.pipeline.identities(&[path.clone(), unknown.clone()]).await;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[&path], stored);
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_rename_over_indexed_destination() {
+        let h = harness().await;
+        let source = write_file(&h.files, "draft.txt", "wombat burrow survey");
+        let target = write_file(&h.files, "final.txt", "outdated survey");
+        h.pipeline
+            .handle_batch(&batch(vec![FileEvent::Created(source.clone()), FileEvent::Created(target.clone())]))
+            .await
+            .unwrap();
+        h.pipeline.run_until_idle().await;
+        let (source_id, _) = file_row(&h.db, &source).await.unwrap();
+        let (target_id, _) = file_row(&h.db, &target).await.unwrap();
+
+        std::fs::rename(&source, &target).unwrap();
+        let report = h
+            .pipeline
+            .handle_batch(&batch(vec![FileEvent::Renamed(source.clone(), target.clone())]))
+            .await
+            .unwrap();
+        assert_eq!(report.renamed, 1);
+
+        let (id, _) = file_row(&h.db, &target).await.unwrap();
+        assert_eq!(id, source_id);
+        assert_eq!(chunk_count(&h.db, &target_id).await, 0);
+        assert!(chunk_count(&h.db, &source_id).await > 0);
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_marks_unsupported_files_skipped() {
+        let h = harness().await;
+        let path = write_file(&h.files, "blob.bin", "\u{0}\u{1}\u{2}");
+
+        let report = h.pipeline.handle_batch(&batch(vec![FileEvent::Created(path.clone())])).await.unwrap();
+        assert_eq!(report.submitted, 0);
+
+        let (_, status) = file_row(&h.db, &path).await.unwrap();
+        assert_eq!(status, "Skipped");
+        assert_eq!(h.embedder.calls.load(Ordering::SeqCst), 0);
+    }
+
+    /// JPEG whose EXIF records a GPS position and whose XMP names the city
+    fn write_gps_photo(dir: &Path, name: &str) -> PathBuf {
+        use exif::{Field, In, Rational, Tag, Value};
+
+        let ascii = |tag, value: &str| Field {
+            tag,
+            ifd_num: In::PRIMARY,
+            value: Value::Ascii(vec![value.as_bytes().to_vec()]),
+        };
+        let dms = |tag, values: [u32; 3]| Field {
+            tag,
+            ifd_num: In::PRIMARY,
+            value: Value::Rational(values.iter().map(|&num| Rational { num, denom: 1 }).collect()),
+        };
+        let fields = [
+            ascii(Tag::DateTimeOriginal, "2023:04:02 10:15:00"),
+            ascii(Tag::GPSLatitudeRef, "N"),
+            dms(Tag::GPSLatitude, [35, 0, 42]),
+            ascii(Tag::GPSLongitudeRef, "E"),
+            dms(Tag::GPSLongitude, [135, 46, 5]),
+        ];
+        let mut writer = exif::experimental::Writer::new();
+        for field in &fields {
+            writer.push_field(field);
+        }
+        let mut tiff = std::io::Cursor::new(Vec::new());
+        writer.write(&mut tiff, false).unwrap();
+        let xmp = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/"><rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"><rdf:Description xmlns:photoshop="http://ns.adobe.com/photoshop/1.0/" photoshop:City="Kyoto" photoshop:Country="Japan"/></rdf:RDF></x:xmpmeta>"#;
+
+        let mut jpeg = Vec::new();
+        image::DynamicImage::ImageRgb8(image::RgbImage::new(16, 16))
+            .write_to(&mut std::io::Cursor::new(&mut jpeg), image::ImageOutputFormat::Jpeg(80))
+            .unwrap();
+        let mut bytes = jpeg[..2].to_vec();
+        for payload in [
+            [b"Exif\0\0".as_slice(), tiff.get_ref()].concat(),
+            [b"http://ns.adobe.com/xap/1.0/\0".as_slice(), xmp.as_bytes()].concat(),
+        ] {
+            bytes.extend_from_slice(&[0xFF, 0xE1]);
+            bytes.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+            bytes.extend_from_slice(&payload);
+        }
+        bytes.extend_from_slice(&jpeg[2..]);
+
+        let path = dir.join(name);
+        std::fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_stores_photo_metadata() {
+        let h = harness().await;
+        let path = write_gps_photo(&h.files, "IMG_0412.jpg");
+
+        h.pipeline.handle_batch(&batch(vec![FileEvent::Created(path.clone())])).await.unwrap();
+        let report = h.pipeline.run_until_idle().await;
+        assert_eq!(report.indexed, 1);
+
+        let (file_id, status) = file_row(&h.db, &path).await.unwrap();
+        assert_eq!(status, "Indexed");
+
+        let (captured_ts, location, latitude): (Option<i64>, Option<String>, Option<f64>) =
+            sqlx::query_as("SELECT captured_ts, location, latitude FROM image_metadata WHERE file_id = ?")
+                .bind(&file_id)
+                .fetch_one(&h.db)
+                .await
+                .unwrap();
+        assert_eq!(captured_ts, Some(1680430500));
+        assert_eq!(location.as_deref(), Some("Kyoto, Japan"));
+        assert!(latitude.is_some());
+
+        // A GPS position keeps the photo on the device
+        let (privacy,): (String,) = sqlx::query_as("SELECT privacy_level FROM files WHERE id = ?")
+            .bind(&file_id)
+            .fetch_one(&h.db)
+            .await
+            .unwrap();
+        assert_eq!(privacy, "Sensitive");
+
+        // Its vectors carry the level, so cloud-assisted searches skip them
+        let (vector_id,): (i64,) = sqlx::query_as("SELECT vector_id FROM content_chunks WHERE file_id = ? LIMIT 1")
+            .bind(&file_id)
+            .fetch_one(&h.db)
+            .await
+            .unwrap();
+        let point = h.vectors.get(vector_id as u64).await.unwrap().unwrap();
+        assert_eq!(
+            point.payload.get(payload_fields::PRIVACY_LEVEL).and_then(|v| v.as_str()),
+            Some("Sensitive")
+        );
+        let vector = point.vector.clone().unwrap();
+        let filter = crate::search::hybrid::HybridSearchFilters::new()
+            .exclude_sensitive()
+            .to_vector_filter();
+        assert!(h.vectors.search(&vector, 10, Some(filter)).await.unwrap().is_empty());
+        assert!(!h.vectors.search(&vector, 10, None).await.unwrap().is_empty());
+
+        // Place and capture date are searchable fields
+        assert_eq!(text_hits(&h.text_index, "kyoto", 1).await, 1);
+        let filters = crate::search::text_index::SearchFilters {
+            min_captured_at: Some(1677628800),
+            max_captured_at: Some(1685577599),
+            location: Some("kyoto".to_string()),
+            ..Default::default()
+        };
+        let hits = h.text_index.search_with_filters("kyoto", &filters, 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].file_id.to_string(), file_id);
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_embedding_failure_schedules_retry() {
+        let h = harness().await;
+        h.embedder.fail.store(true, Ordering::SeqCst);
+        let path = write_file(&h.files, "retry.txt", "needs another attempt");
+
+        h.pipeline.handle_batch(&batch(vec![FileEvent::Created(path.clone())])).await.unwrap();
+        let report = h.pipeline.run_until_idle().await;
+        assert_eq!(report.failed, 1);
+
+        let (file_id, status) = file_row(&h.db, &path).await.unwrap();
+        assert_eq!(status, "Pending");
+        assert_eq!(chunk_count(&h.db, &file_id).await, 0);
+        assert_eq!(h.pipeline.indexer().pending_count().await, 1);
+        assert_eq!(h.pipeline.indexer().stats().snapshot().total_failed, 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_event_keeps_rest_of_batch() {
+        let h = harness().await;
+        sqlx::query(
+            "CREATE TRIGGER reject_poison BEFORE INSERT ON files WHEN NEW.filename = 'poison.txt' \
+             BEGIN SELECT RAISE(ABORT, 'rejected'); END",
+        )
+        .execute(&h.db)
+        .await
+        .unwrap();
+        let poison = write_file(&h.files, "poison.txt", "cannot be stored");
+        let fine = write_file(&h.files, "fine.txt", "stored normally");
+
+        let report = h
+            .pipeline
+            .handle_batch(&batch(vec![FileEvent::Created(poison.clone()), FileEvent::Created(fine.clone())]))
+            .await
+            .unwrap();
+        assert_eq!(report.submitted, 1);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].0, poison);
+        assert!(file_row(&h.db, &fine).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_directory_events_apply_to_descendants() {
+        let h = harness().await;
+        let project = h.files.join("project");
+        std::fs::create_dir_all(project.join("docs")).unwrap();
+        let readme = write_file(&project, "README.md", "platypus field guide");
+        let spec = write_file(&project.join("docs"), "spec.txt", "numbat specification");
+
+        // A directory that appears is indexed in full
+        let report = h.pipeline.handle_batch(&batch(vec![FileEvent::Created(project.clone())])).await.unwrap();
+        assert_eq!(report.submitted, 2);
+        assert_eq!(h.pipeline.run_until_idle().await.indexed, 2);
+
+        // Renaming it moves every file below it
+        let archived = h.files.join("archived");
+        std::fs::rename(&project, &archived).unwrap();
+        let report = h
+            .pipeline
+            .handle_batch(&batch(vec![FileEvent::Renamed(project.clone(), archived.clone())]))
+            .await
+            .unwrap();
+        assert_eq!(report.renamed, 2);
+        assert!(file_row(&h.db, &readme).await.is_none());
+        assert!(file_row(&h.db, &spec).await.is_none());
+        let moved = archived.join("docs").join("spec.txt");
+        assert_eq!(file_row(&h.db, &moved).await.unwrap().1, "Indexed");
+
+        // Deleting it removes them and their derived data
+        std::fs::remove_dir_all(&archived).unwrap();
+        let report = h.pipeline.handle_batch(&batch(vec![FileEvent::Deleted(archived.clone())])).await.unwrap();
+        assert_eq!(report.removed, 2);
+        assert!(file_row(&h.db, &moved).await.is_none());
+        assert_eq!(h.vectors.count().await.unwrap(), 0);
+        assert_eq!(text_hits(&h.text_index, "numbat", 0).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_edited_ignore_file_updates_index() {
+        let h = harness().await;
+        let filter = Arc::new(DirectoryFilter::with_defaults().unwrap());
+        filter.set_monitored_roots(&[h.files.clone()]);
+        let pipeline = h.pipeline.with_filter(filter);
+
+        let gitignore = write_file(&h.files, ".gitignore", "drafts/\n");
+        std::fs::create_dir_all(h.files.join("drafts")).unwrap();
+        let kept = write_file(&h.files, "kept.txt", "wombat burrow notes");
+        let draft = write_file(&h.files.join("drafts"), "draft.txt", "quokka draft");
+        pipeline.handle_batch(&batch(vec![FileEvent::Created(kept.clone())])).await.unwrap();
+        pipeline.run_until_idle().await;
+        assert!(file_row(&h.db, &kept).await.is_some());
+        assert!(file_row(&h.db, &draft).await.is_none());
+
+        // The rules flip: the indexed file is dropped, the ignored one queued
+        std::fs::write(&gitignore, "kept.txt\n").unwrap();
+        let report = pipeline
+            .handle_batch(&batch(vec![FileEvent::Modified(gitignore.clone())]))
+            .await
+            .unwrap();
+        assert_eq!(report.removed, 1);
+        assert!(file_row(&h.db, &kept).await.is_none());
+        pipeline.run_until_idle().await;
+        assert_eq!(file_row(&h.db, &draft).await.unwrap().1, "Indexed");
+        assert_eq!(text_hits(&h.text_index, "quokka", 1).await, 1);
+        assert_eq!(text_hits(&h.text_index, "wombat", 0).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_worker_runs_tasks_after_retry_delay() {
+        let h = harness().await;
+        h.embedder.fail.store(true, Ordering::SeqCst);
+        let path = write_file(&h.files, "later.txt", "indexed on the second attempt");
+        let pipeline = Arc::new(
+            h.pipeline
+                .with_config(PipelineConfig::default().with_poll_interval(Duration::from_millis(20))),
+        );
+
+        let (tx, rx) = tokio::sync::mpsc::channel(4);
+        let worker = pipeline.clone().spawn(rx);
+        tx.send(batch(vec![FileEvent::Created(path.clone())])).await.unwrap();
+
+        // The first attempt fails and the task waits out its retry delay
+        for _ in 0..100 {
+            if pipeline.indexer().stats().snapshot().total_failed > 0 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(file_row(&h.db, &path).await.unwrap().1, "Pending");
+        h.embedder.fail.store(false, Ordering::SeqCst);
+
+        // The worker is still running when the task becomes ready again
+        let mut status = String::new();
+        for _ in 0..100 {
+            status = file_row(&h.db, &path).await.unwrap().1;
+            if status == "Indexed" {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert_eq!(status, "Indexed");
+        assert!(!worker.is_finished());
+        worker.abort();
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_duplicate_events_queue_one_task() {
+        let h = harness().await;
+        let path = write_file(&h.files, "busy.txt", "saved twice");
+
+        let report = h
+            .pipeline
+            .handle_batch(&batch(vec![
+                FileEvent::Created(path.clone()),
+                FileEvent::Modified(path.clone()),
+            ]))
+            .await
+            .unwrap();
+        assert_eq!(report.submitted, 1);
+        assert_eq!(h.pipeline.indexer().pending_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_follows_reconcile_result() {
+        let h = harness().await;
+        let kept = write_file(&h.files, "kept.txt", "stays around");
+        let gone = write_file(&h.files, "gone.txt", "walrus to be removed");
+
+        let reconciler = ReconciliationService::new(h.db.clone());
+        let result = reconciler.reconcile_on_startup(&[h.files.clone()]).await.unwrap();
+        let report = h.pipeline.handle_reconcile(&result).await.unwrap();
+        assert_eq!(report.submitted, 2);
+        assert_eq!(h.pipeline.run_until_idle().await.indexed, 2);
+        assert_eq!(file_row(&h.db, &kept).await.unwrap().1, "Indexed");
+
+        std::fs::remove_file(&gone).unwrap();
+        let result = reconciler.reconcile_on_startup(&[h.files.clone()]).await.unwrap();
+        assert_eq!(result.deleted_file_ids.len(), 1);
+        let report = h.pipeline.handle_reconcile(&result).await.unwrap();
+        assert_eq!(report.removed, 1);
+
+        let (kept_id, _) = file_row(&h.db, &kept).await.unwrap();
+        assert_eq!(h.vectors.count().await.unwrap(), chunk_count(&h.db, &kept_id).await as u64);
+        assert_eq!(text_hits(&h.text_index, "walrus", 0).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_catch_up_scans_without_trusted_journal() {
+        let h = harness().await;
+        let path = write_file(&h.files, "offline.txt", "added while closed");
+        let journal_path = h._temp_dir.path().join("watcher.journal");
+        let (journal, recovery) = EventJournal::open(&journal_path).unwrap();
+        assert_eq!(recovery.state, JournalState::Missing);
+        let pipeline = h.pipeline.with_journal(Arc::new(journal));
+
+        let reconciler = ReconciliationService::new(h.db.clone());
+        let outcome = pipeline.catch_up(recovery, &reconciler, &[h.files.clone()]).await.unwrap();
+        match outcome {
+            CatchUp::FullScan { report, .. } => assert_eq!(report.submitted, 1),
+            other => panic!("expected a full scan, got {:?}", other),
+        }
+        assert!(file_row(&h.db, &path).await.is_some());
+
+        // The scan was recorded, so the next start trusts the journal
+        drop(pipeline);
+        let (_, recovery) = EventJournal::open(&journal_path).unwrap();
+        assert_eq!(recovery.state, JournalState::Intact);
+    }
+
+    #[tokio::test]
+    async fn test_catch_up_replays_unprocessed_batches() {
+        let h = harness().await;
+        let journal_path = h._temp_dir.path().join("watcher.journal");
+        let (journal, _) = EventJournal::open(&journal_path).unwrap();
+        journal.mark_reconciled(journal.last_seq()).unwrap();
+
+        // Received before a crash, never applied
+        let path = write_file(&h.files, "journaled.txt", "echidna sighting log");
+        let pending = batch(vec![FileEvent::Created(path.clone())]);
+        journal.append(&pending).unwrap();
+        drop(journal);
+
+        let (journal, recovery) = EventJournal::open(&journal_path).unwrap();
+        assert_eq!(recovery.state, JournalState::Intact);
+        let journal = Arc::new(journal);
+        let pipeline = h.pipeline.with_journal(journal.clone());
+
+        let reconciler = ReconciliationService::new(h.db.clone());
+        let outcome = pipeline.catch_up(recovery, &reconciler, &[h.files.clone()]).await.unwrap();
+        match outcome {
+            CatchUp::Replayed { batches, report, .. } => {
+                assert_eq!(batches, 1);
+                assert_eq!(report.submitted, 1);
+            }
+            other => panic!("expected a replay, got {:?}", other),
+        }
+        assert_eq!(journal.unprocessed(), 0);
+        assert_eq!(pipeline.run_until_idle().await.indexed, 1);
+        assert_eq!(file_row(&h.db, &path).await.unwrap().1, "Indexed");
+    }
+
+    #[tokio::test]
+    async fn test_catch_up_trusts_intact_journal_without_scanning() {
+        let h = harness().await;
+        let kept = write_file(&h.files, "kept.txt", "indexed before shutdown");
+        let reconciler = ReconciliationService::new(h.db.clone());
+        let result = reconciler.reconcile_on_startup(&[h.files.clone()]).await.unwrap();
+        h.pipeline.handle_reconcile(&result).await.unwrap();
+        assert_eq!(h.pipeline.run_until_idle().await.indexed, 1);
+
+        let journal_path = h._temp_dir.path().join("watcher.journal");
+        let (journal, _) = EventJournal::open(&journal_path).unwrap();
+        journal.mark_reconciled(journal.last_seq()).unwrap();
+        drop(journal);
+
+        // Not journaled, and not looked for: the replay replaces the scan
+        let unseen = write_file(&h.files, "unseen.txt", "never reported by the watcher");
+        std::fs::remove_file(&kept).unwrap();
+
+        let (journal, recovery) = EventJournal::open(&journal_path).unwrap();
+        assert_eq!(recovery.state, JournalState::Intact);
+        assert!(recovery.pending.is_empty());
+        let pipeline = h.pipeline.with_journal(Arc::new(journal));
+
+        let outcome = pipeline.catch_up(recovery, &reconciler, &[h.files.clone()]).await.unwrap();
+        match outcome {
+            CatchUp::Replayed { batches, report, held } => {
+                assert_eq!(batches, 0);
+                assert_eq!(report.submitted, 0);
+                assert_eq!(report.removed, 0);
+                assert!(held.is_empty());
+            }
+            other => panic!("expected a replay, got {:?}", other),
+        }
+        assert!(file_row(&h.db, &kept).await.is_some());
+        assert!(file_row(&h.db, &unseen).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_catch_up_scans_after_corrupt_journal() {
+        let h = harness().await;
+        let path = write_file(&h.files, "offline.txt", "added while closed");
+        let journal_path = h._temp_dir.path().join("watcher.journal");
+        std::fs::write(&journal_path, "not a journal\n").unwrap();
+        let (journal, recovery) = EventJournal::open(&journal_path).unwrap();
+        assert!(matches!(recovery.state, JournalState::Corrupt(_)));
+        let pipeline = h.pipeline.with_journal(Arc::new(journal));
+
+        let reconciler = ReconciliationService::new(h.db.clone());
+        let outcome = pipeline.catch_up(recovery, &reconciler, &[h.files.clone()]).await.unwrap();
+        match outcome {
+            CatchUp::FullScan { reason, report, .. } => {
+                assert!(reason.starts_with("event journal corrupt"));
+                assert_eq!(report.submitted, 1);
+            }
+            other => panic!("expected a full scan, got {:?}", other),
+        }
+        assert_eq!(pipeline.run_until_idle().await.indexed, 1);
+        assert_eq!(file_row(&h.db, &path).await.unwrap().1, "Indexed");
+    }
+
+    #[tokio::test]
+    async fn test_failed_submit_does_not_block_later_events() {
+        let h = harness().await;
+        let pipeline = IndexingPipeline::new(
+            h.db.clone(),
+            Arc::new(ResilientBatchIndexer::with_defaults().with_store(TaskStore::new(h.db.clone()))),
+            Arc::new(ContentParserService::new()),
+            h.embedder.clone(),
+            h.vectors.clone(),
+            h.text_index.clone(),
+        )
+        .unwrap();
+        let path = write_file(&h.files, "retry.txt", "queued on the second try");
+
+        // The task store rejects the first submission
+        sqlx::query("ALTER TABLE index_tasks RENAME TO index_tasks_away").execute(&h.db).await.unwrap();
+        let report = pipeline.handle_batch(&batch(vec![FileEvent::Created(path.clone())])).await.unwrap();
+        assert_eq!(report.submitted, 0);
+        assert_eq!(report.errors.len(), 1);
+
+        sqlx::query("ALTER TABLE index_tasks_away RENAME TO index_tasks").execute(&h.db).await.unwrap();
+        let report = pipeline.handle_batch(&batch(vec![FileEvent::Modified(path.clone())])).await.unwrap();
+        assert_eq!(report.submitted, 1);
+        assert_eq!(pipeline.run_until_idle().await.indexed, 1);
+        assert_eq!(file_row(&h.db, &path).await.unwrap().1, "Indexed");
+    }
+
+    #[tokio::test]
+    async fn test_enqueue_ignores_directories() {
+        let h = harness().await;
+        let queued = h.pipeline.enqueue_path(&h.files, TaskPriority::Normal).await.unwrap();
+        assert!(!queued);
+        assert_eq!(h.pipeline.indexer().pending_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_unchanged_file_skips_embedding() {
+        let h = harness().await;
+        let path = write_file(&h.files, "stable.txt", "nothing changes here");
+        h.pipeline.handle_batch(&batch(vec![FileEvent::Created(path.clone())])).await.unwrap();
+        h.pipeline.run_until_idle().await;
+        let calls = h.embedder.calls.load(Ordering::SeqCst);
+
+        // Touching the file without changing its bytes
+        h.pipeline.handle_batch(&batch(vec![FileEvent::Modified(path.clone())])).await.unwrap();
+        let report = h.pipeline.run_until_idle().await;
+
+        assert_eq!(report.unchanged, 1);
+        assert_eq!(report.indexed, 0);
+        assert_eq!(h.embedder.calls.load(Ordering::SeqCst), calls);
+        assert_eq!(h.pipeline.indexer().stats().snapshot().files_unchanged, 1);
+
+        let (file_id, status) = file_row(&h.db, &path).await.unwrap();
+        assert_eq!(status, "Indexed");
+        assert_eq!(h.vectors.count().await.unwrap(), chunk_count(&h.db, &file_id).await as u64);
+    }
+
+    #[tokio::test]
+    async fn test_changed_file_reuses_vectors_of_unchanged_chunks() {
+        let h = harness().await;
+        let original = "# Alpha\n\nThe alpha section stays the same.\n\n# Beta\n\nThe beta section stays too.\n";
+        let path = write_file(&h.files, "guide.md", original);
+        h.pipeline.handle_batch(&batch(vec![FileEvent::Created(path.clone())])).await.unwrap();
+        h.pipeline.run_until_idle().await;
+
+        let (file_id, _) = file_row(&h.db, &path).await.unwrap();
+        let before: Vec<(i64,)> = sqlx::query_as("SELECT vector_id FROM content_chunks WHERE file_id = ?")
+            .bind(&file_id)
+            .fetch_all(&h.db)
+            .await
+            .unwrap();
+
+        let appended = format!("{}\n# Gamma\n\nA brand new gamma section.\n", original);
+        write_file(&h.files, "guide.md", &appended);
+        h.pipeline.handle_batch(&batch(vec![FileEvent::Modified(path.clone())])).await.unwrap();
+        let report = h.pipeline.run_until_idle().await;
+
+        assert_eq!(report.indexed, 1);
+        assert!(report.chunks_reused > 0);
+        assert!(report.chunks_embedded > 0);
+        assert!(report.chunks_embedded < chunk_count(&h.db, &file_id).await as usize);
+
+        // Reused chunks keep their vector ids and nothing is left behind
+        let after: Vec<(i64,)> = sqlx::query_as("SELECT vector_id FROM content_chunks WHERE file_id = ?")
+            .bind(&file_id)
+            .fetch_all(&h.db)
+            .await
+            .unwrap();
+        assert!(before.iter().filter(|id| after.contains(id)).count() >= report.chunks_reused);
+        assert_eq!(h.vectors.count().await.unwrap(), after.len() as u64);
+        assert!(text_hits(&h.text_index, "gamma", 1).await >= 1);
+
+        let stats = h.pipeline.indexer().stats().snapshot();
+        assert_eq!(stats.chunks_reused, report.chunks_reused as u64);
+        assert!(stats.chunk_reuse_ratio() > 0.0 && stats.chunk_reuse_ratio() < 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_paused_scheduler_holds_tasks() {
+        let h = harness().await;
+        let probe = Arc::new(FixedProbe::default());
+        probe.set(ResourceSample {
+            on_battery: true,
+            battery_percent: Some(5),
+            ..Default::default()
+        });
+        let scheduler = Arc::new(ResourceScheduler::new(IndexingBudget::default(), 2, 10, probe.clone()));
+        let pipeline = h.pipeline.with_scheduler(scheduler);
+
+        let path = write_file(&h.files, "waiting.txt", "held until power returns");
+        pipeline.handle_batch(&batch(vec![FileEvent::Created(path.clone())])).await.unwrap();
+
+        assert!(pipeline.is_paused());
+        assert_eq!(pipeline.run_until_idle().await.processed(), 0);
+        assert_eq!(pipeline.indexer().pending_count().await, 1);
+
+        probe.set(ResourceSample::default());
+        assert!(!pipeline.is_paused());
+        assert_eq!(pipeline.run_until_idle().await.indexed, 1);
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_runs_tasks_concurrently() {
+        let h = harness().await;
+        let probe = Arc::new(FixedProbe::default());
+        let scheduler = Arc::new(ResourceScheduler::new(IndexingBudget::default(), 4, 3, probe));
+        let pipeline = h.pipeline.with_scheduler(scheduler);
+
+        let events = (0..7)
+            .map(|i| FileEvent::Created(write_file(&h.files, &format!("doc{}.txt", i), &format!("document number {}", i))))
+            .collect();
+        pipeline.handle_batch(&batch(events)).await.unwrap();
+
+        // Batches are capped at the planned size
+        assert_eq!(pipeline.run_ready_batch().await.indexed, 3);
+        assert_eq!(pipeline.run_until_idle().await.indexed, 4);
+
+        let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM files WHERE index_status = 'Indexed'")
+            .fetch_one(&h.db)
+            .await
+            .unwrap();
+        assert_eq!(count, 7);
+    }
+
+    #[tokio::test]
+    async fn test_chunks_missing_vectors_are_reembedded() {
+        let h = harness().await;
+        let path = write_file(&h.files, "lost.txt", "vectors for this file go missing");
+        h.pipeline.handle_batch(&batch(vec![FileEvent::Created(path.clone())])).await.unwrap();
+        h.pipeline.run_until_idle().await;
+        h.vectors.clear().await.unwrap();
+
+        // Same bytes, but the stored vectors are gone
+        h.pipeline.handle_batch(&batch(vec![FileEvent::Modified(path.clone())])).await.unwrap();
+        let report = h.pipeline.run_until_idle().await;
+
+        assert_eq!(report.unchanged, 0);
+        assert_eq!(report.chunks_reused, 0);
+        let (file_id, _) = file_row(&h.db, &path).await.unwrap();
+        assert_eq!(h.vectors.count().await.unwrap(), chunk_count(&h.db, &file_id).await as u64);
+    }
+
+    async fn chunk_models(db: &SqlitePool) -> Vec<String> {
+        let rows: Vec<(Option<String>,)> = sqlx::query_as("SELECT embedding_model FROM content_chunks")
+            .fetch_all(db)
+            .await
+            .unwrap();
+        rows.into_iter().map(|(model,)| model.unwrap_or_default()).collect()
+    }
+
+    /// Migrator over the harness database, with the pipeline writing through its collections
+    async fn with_migrator(h: Harness) -> (Harness, Arc<ModelMigrator>) {
+        let base = VectorStoreConfig::default()
+            .with_storage_path(h._temp_dir.path().join("collections").to_string_lossy().to_string())
+            .with_vector_size(DIM as u64);
+        let migrator = Arc::new(
+            ModelMigrator::open(h.db.clone(), base, h.embedder.clone())
+                .await
+                .unwrap()
+                .with_batch_size(2),
+        );
+        let pipeline = h.pipeline.with_collections(migrator.collections().clone());
+        (Harness { pipeline, ..h }, migrator)
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_reports_progress_per_root() {
+        let h = harness().await;
+        h.pipeline.progress().set_roots(vec![h.files.clone()]);
+        for i in 0..3 {
+            let path = write_file(&h.files, &format!("p{}.txt", i), &format!("progress sample {}", i));
+            h.pipeline.handle_batch(&batch(vec![FileEvent::Created(path)])).await.unwrap();
+        }
+        h.pipeline.run_until_idle().await;
+
+        let snapshot = h.pipeline.progress().snapshot();
+        let root = &snapshot.roots[0].progress;
+        assert_eq!(root.queued, 3);
+        assert_eq!(root.completed, 3);
+        assert_eq!(root.remaining, 0);
+        for stage in &root.stages[1..] {
+            assert_eq!(stage.completed, 3, "{:?}", stage.stage);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_vectors_are_tagged_with_embedding_model() {
+        let h = harness().await;
+        let path = write_file(&h.files, "tagged.txt", "which model embedded this");
+        h.pipeline.handle_batch(&batch(vec![FileEvent::Created(path.clone())])).await.unwrap();
+        h.pipeline.run_until_idle().await;
+
+        let models = chunk_models(&h.db).await;
+        assert!(!models.is_empty());
+        assert!(models.iter().all(|model| model == "mock@0"));
+
+        let (vector_id,): (i64,) = sqlx::query_as("SELECT vector_id FROM content_chunks LIMIT 1")
+            .fetch_one(&h.db)
+            .await
+            .unwrap();
+        let point = h.vectors.get(vector_id as u64).await.unwrap().unwrap();
+        assert_eq!(point.payload[payload_fields::MODEL_ID], "mock");
+        assert_eq!(point.payload[payload_fields::MODEL_VERSION], "0");
+    }
+
+    #[tokio::test]
+    async fn test_model_change_reembeds_then_cuts_over() {
+        let (h, migrator) = with_migrator(harness().await).await;
+        assert!(!migrator.is_migrating());
+        for i in 0..3 {
+            let path = write_file(&h.files, &format!("note{}.md", i), &format!("# Note {}\n\nBody of note {}.", i, i));
+            h.pipeline.handle_batch(&batch(vec![FileEvent::Created(path)])).await.unwrap();
+        }
+        h.pipeline.run_until_idle().await;
+        let old = migrator.collections().active();
+        let chunks = old.store.count().await.unwrap();
+        assert!(chunks > 0);
+
+        // Configure a new model version
+        h.embedder.version.store(1, Ordering::SeqCst);
+        assert!(migrator.sync_model().await.unwrap());
+        let progress = migrator.progress().await.unwrap().unwrap();
+        assert_eq!(progress.from_model.key(), "mock@0");
+        assert_eq!(progress.to_model.key(), "mock@1");
+        assert_eq!(progress.migrated_chunks, 0);
+        assert_eq!(progress.remaining_chunks, chunks);
+
+        // Search keeps the old collection while the new one fills
+        assert!(migrator.run_batch().await.unwrap() > 0);
+        assert!(!migrator.try_cut_over().await.unwrap());
+        assert_eq!(migrator.collections().active().model.key(), "mock@0");
+        assert_eq!(old.store.count().await.unwrap(), chunks);
+        let progress = migrator.progress().await.unwrap().unwrap();
+        assert!(progress.percent() > 0.0 && progress.percent() < 100.0);
+
+        migrator.run(std::time::Duration::from_millis(10)).await.unwrap();
+        assert!(!migrator.is_migrating());
+        assert!(migrator.progress().await.unwrap().is_none());
+
+        let active = migrator.collections().active();
+        assert_eq!(active.model.key(), "mock@1");
+        assert_eq!(active.store.count().await.unwrap(), chunks);
+        assert_eq!(old.store.count().await.unwrap(), 0);
+        assert!(chunk_models(&h.db).await.iter().all(|model| model == "mock@1"));
+
+        let statuses: Vec<(String, String)> =
+            sqlx::query_as("SELECT model_version, status FROM vector_collections ORDER BY model_version")
+                .fetch_all(&h.db)
+                .await
+                .unwrap();
+        assert_eq!(
+            statuses,
+            vec![("0".to_string(), "Retired".to_string()), ("1".to_string(), "Active".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_files_indexed_during_migration_use_new_model() {
+        let (h, migrator) = with_migrator(harness().await).await;
+        let path = write_file(&h.files, "old.txt", "embedded before the switch");
+        h.pipeline.handle_batch(&batch(vec![FileEvent::Created(path.clone())])).await.unwrap();
+        h.pipeline.run_until_idle().await;
+
+        h.embedder.version.store(1, Ordering::SeqCst);
+        migrator.sync_model().await.unwrap();
+        let target = migrator.collections().building().unwrap();
+
+        // Reindexing writes to the new collection and drops the old vectors
+        write_file(&h.files, "old.txt", "embedded after the switch");
+        h.pipeline.handle_batch(&batch(vec![FileEvent::Modified(path.clone())])).await.unwrap();
+        h.pipeline.run_until_idle().await;
+
+        let (file_id, _) = file_row(&h.db, &path).await.unwrap();
+        let chunks = chunk_count(&h.db, &file_id).await as u64;
+        assert_eq!(target.store.count().await.unwrap(), chunks);
+        assert_eq!(migrator.collections().active().store.count().await.unwrap(), 0);
+        assert_eq!(migrator.progress().await.unwrap().unwrap().remaining_chunks, 0);
+        assert!(migrator.try_cut_over().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_migration_skips_chunks_the_model_fails_on() {
+        let (h, migrator) = with_migrator(harness().await).await;
+        let mut paths = Vec::new();
+        for (i, body) in ["first note", "poison pill", "third note"].iter().enumerate() {
+            let path = write_file(&h.files, &format!("n{}.txt", i), body);
+            h.pipeline.handle_batch(&batch(vec![FileEvent::Created(path.clone())])).await.unwrap();
+            paths.push(path);
+        }
+        h.pipeline.run_until_idle().await;
+
+        h.embedder.version.store(1, Ordering::SeqCst);
+        *h.embedder.poison.lock().unwrap() = Some("poison".to_string());
+        migrator.sync_model().await.unwrap();
+
+        // The other chunks move on while the failing one is retried, then skipped
+        for _ in 0..6 {
+            migrator.run_batch().await.unwrap();
+        }
+        let progress = migrator.progress().await.unwrap().unwrap();
+        assert_eq!(progress.remaining_chunks, 0);
+        assert_eq!(progress.skipped_chunks, 1);
+
+        // The skipped chunk holds back the cutover and keeps its old vector
+        let run = {
+            let migrator = migrator.clone();
+            tokio::spawn(async move { migrator.run(std::time::Duration::from_millis(10)).await })
+        };
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(!migrator.try_cut_over().await.unwrap());
+        assert!(migrator.is_migrating());
+        let active = migrator.collections().active();
+        assert_eq!(active.model.key(), "mock@0");
+        let (file_id, _) = file_row(&h.db, &paths[1]).await.unwrap();
+        let (vector_id, model): (i64, String) =
+            sqlx::query_as("SELECT vector_id, embedding_model FROM content_chunks WHERE file_id = ?")
+                .bind(&file_id)
+                .fetch_one(&h.db)
+                .await
+                .unwrap();
+        assert_eq!(model, "mock@0");
+        assert!(active.store.get(vector_id as u64).await.unwrap().is_some());
+
+        // Once the pipeline re-embeds the changed file, the migration completes
+        *h.embedder.poison.lock().unwrap() = None;
+        write_file(&h.files, "n1.txt", "poison pill, revised");
+        h.pipeline.handle_batch(&batch(vec![FileEvent::Modified(paths[1].clone())])).await.unwrap();
+        h.pipeline.run_until_idle().await;
+        run.await.unwrap().unwrap();
+
+        let active = migrator.collections().active();
+        assert_eq!(active.model.key(), "mock@1");
+        assert!(chunk_models(&h.db).await.iter().all(|model| model == "mock@1"));
+        let (vector_id,): (i64,) = sqlx::query_as("SELECT vector_id FROM content_chunks WHERE file_id = ?")
+            .bind(&file_id)
+            .fetch_one(&h.db)
+            .await
+            .unwrap();
+        assert!(active.store.get(vector_id as u64).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_migration_waits_out_an_unavailable_model() {
+        let (h, migrator) = with_migrator(harness().await).await;
+        for i in 0..3 {
+            let path = write_file(&h.files, &format!("w{}.txt", i), &format!("waiting note {}", i));
+            h.pipeline.handle_batch(&batch(vec![FileEvent::Created(path)])).await.unwrap();
+        }
+        h.pipeline.run_until_idle().await;
+
+        h.embedder.version.store(1, Ordering::SeqCst);
+        h.embedder.fail.store(true, Ordering::SeqCst);
+        migrator.sync_model().await.unwrap();
+
+        // Whole batches failing skip nothing
+        assert!(migrator.run_batch().await.is_err());
+        let progress = migrator.progress().await.unwrap().unwrap();
+        assert_eq!(progress.skipped_chunks, 0);
+        assert!(progress.remaining_chunks > 0);
+
+        let run = {
+            let migrator = migrator.clone();
+            tokio::spawn(async move { migrator.run(std::time::Duration::from_millis(10)).await })
+        };
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(migrator.is_migrating());
+
+        h.embedder.fail.store(false, Ordering::SeqCst);
+        run.await.unwrap().unwrap();
+        assert!(chunk_models(&h.db).await.iter().all(|model| model == "mock@1"));
+    }
+}
+
+// ============================================================================
+// Resource Scheduler Tests
+// ============================================================================
+
+mod scheduler_tests {
+    use super::super::scheduler::{
+        ProbeCapabilities, ResourceProbe, ResourceSample, ResourceScheduler, SchedulerMode,
+    };
+    use crate::config::{IndexingBudget, PerformanceConfig};
+    use std::sync::atomic::AtomicBool;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    /// Probe returning whatever reading the test sets
+    #[derive(Default)]
+    pub(super) struct FixedProbe {
+        sample: Mutex<ResourceSample>,
+    }
+
+    impl FixedProbe {
+        pub(super) fn set(&self, sample: ResourceSample) {
+            *self.sample.lock().unwrap() = sample;
+        }
+    }
+
+    impl ResourceProbe for FixedProbe {
+        fn sample(&self) -> ResourceSample {
+            *self.sample.lock().unwrap()
+        }
+    }
+
+    fn fixed_scheduler(budget: IndexingBudget) -> (ResourceScheduler, Arc<FixedProbe>) {
+        let probe = Arc::new(FixedProbe::default());
+        (ResourceScheduler::new(budget, 4, 10, probe.clone()), probe)
+    }
+
+    fn load(cpu_percent: f32, io_wait_percent: f32) -> ResourceSample {
+        ResourceSample {
+            cpu_percent,
+            io_wait_percent,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_light_load_runs_at_full_speed() {
+        let (scheduler, probe) = fixed_scheduler(IndexingBudget::default());
+        probe.set(load(10.0, 1.0));
+
+        let plan = scheduler.plan();
+        assert_eq!(plan.mode, SchedulerMode::Normal);
+        assert_eq!(plan.concurrency, 4);
+        assert_eq!(plan.batch_size, 10);
+        assert_eq!(scheduler.last_mode(), Some(SchedulerMode::Normal));
+    }
+
+    #[test]
+    fn test_cpu_load_throttles() {
+        let (scheduler, _) = fixed_scheduler(IndexingBudget::default());
+
+        // 45% of a 60% budget: three quarters used
+        let plan = scheduler.plan_for(load(45.0, 0.0));
+        assert_eq!(plan.mode, SchedulerMode::Throttled);
+        assert_eq!(plan.concurrency, 2);
+        assert_eq!(plan.batch_size, 5);
+
+        // Over budget still makes progress one task at a time
+        let plan = scheduler.plan_for(load(95.0, 0.0));
+        assert_eq!(plan.mode, SchedulerMode::Throttled);
+        assert_eq!(plan.concurrency, 1);
+        assert_eq!(plan.batch_size, 1);
+    }
+
+    #[test]
+    fn test_io_wait_throttles() {
+        let (scheduler, _) = fixed_scheduler(IndexingBudget::default());
+
+        let plan = scheduler.plan_for(load(5.0, 25.0));
+        assert_eq!(plan.mode, SchedulerMode::Throttled);
+        assert_eq!(plan.concurrency, 1);
+    }
+
+    #[test]
+    fn test_battery_caps_and_pauses() {
+        let (scheduler, _) = fixed_scheduler(IndexingBudget::default());
+
+        let plan = scheduler.plan_for(ResourceSample {
+            on_battery: true,
+            battery_percent: Some(80),
+            ..Default::default()
+        });
+        assert_eq!(plan.mode, SchedulerMode::Throttled);
+        assert_eq!(plan.concurrency, 1);
+
+        let plan = scheduler.plan_for(ResourceSample {
+            on_battery: true,
+            battery_percent: Some(10),
+            ..Default::default()
+        });
+        assert_eq!(plan.mode, SchedulerMode::Paused);
+        assert_eq!(plan.concurrency, 0);
+
+        // A low battery that is charging does not pause
+        let plan = scheduler.plan_for(ResourceSample {
+            on_battery: false,
+            battery_percent: Some(10),
+            ..Default::default()
+        });
+        assert_eq!(plan.mode, SchedulerMode::Normal);
+    }
+
+    #[test]
+    fn test_turbo_when_idle() {
+        let (scheduler, _) = fixed_scheduler(IndexingBudget::default());
+        let idle = ResourceSample {
+            idle_for: Duration::from_secs(600),
+            ..Default::default()
+        };
+
+        let plan = scheduler.plan_for(idle);
+        assert_eq!(plan.mode, SchedulerMode::Turbo);
+        assert_eq!(plan.concurrency, 8);
+        assert_eq!(plan.batch_size, 20);
+
+        // No turbo on battery, while over budget, or when disabled
+        let plan = scheduler.plan_for(ResourceSample {
+            on_battery: true,
+            battery_percent: Some(90),
+            ..idle
+        });
+        assert_ne!(plan.mode, SchedulerMode::Turbo);
+
+        let plan = scheduler.plan_for(ResourceSample {
+            cpu_percent: 90.0,
+            ..idle
+        });
+        assert_eq!(plan.mode, SchedulerMode::Throttled);
+
+        let (scheduler, _) = fixed_scheduler(IndexingBudget {
+            turbo_when_idle: false,
+            ..Default::default()
+        });
+        assert_eq!(scheduler.plan_for(idle).mode, SchedulerMode::Normal);
+    }
+
+    #[test]
+    fn test_pause_flag_pauses() {
+        let flag = Arc::new(AtomicBool::new(true));
+        let probe = Arc::new(FixedProbe::default());
+        let scheduler =
+            ResourceScheduler::new(IndexingBudget::default(), 4, 10, probe).with_pause_flag(flag.clone());
+
+        assert_eq!(scheduler.plan().mode, SchedulerMode::Paused);
+
+        flag.store(false, std::sync::atomic::Ordering::SeqCst);
+        assert_eq!(scheduler.plan().mode, SchedulerMode::Normal);
+    }
+
+    /// Probe of a platform that can take no readings at all
+    struct BlindProbe;
+
+    impl ResourceProbe for BlindProbe {
+        fn sample(&self) -> ResourceSample {
+            ResourceSample {
+                cpu_percent: 100.0,
+                io_wait_percent: 100.0,
+                on_battery: true,
+                battery_percent: Some(1),
+                idle_for: Duration::from_secs(3600),
+            }
+        }
+
+        fn capabilities(&self) -> ProbeCapabilities {
+            ProbeCapabilities {
+                cpu: false,
+                io_wait: false,
+                power: false,
+                idle: false,
+            }
+        }
+    }
+
+    #[test]
+    fn test_policies_without_readings_are_off() {
+        let budget = IndexingBudget {
+            turbo_when_idle: true,
+            ..IndexingBudget::default()
+        };
+        let scheduler = ResourceScheduler::new(budget, 4, 10, Arc::new(BlindProbe));
+
+        // Neither paused for battery, throttled for load nor turbo for idle
+        let plan = scheduler.plan();
+        assert_eq!(plan.mode, SchedulerMode::Normal);
+        assert_eq!((plan.concurrency, plan.batch_size), (4, 10));
+        assert_eq!(
+            scheduler.capabilities().disabled_policies(),
+            vec!["load backoff", "battery limits", "idle turbo"]
+        );
+    }
+
+    #[test]
+    fn test_configure_applies_new_budget() {
+        let (scheduler, _) = fixed_scheduler(IndexingBudget::default());
+
+        let mut config = PerformanceConfig::default();
+        config.indexing_threads = 2;
+        config.indexing_budget.max_cpu_percent = 20.0;
+        scheduler.configure(&config);
+
+        assert_eq!(scheduler.budget().max_cpu_percent, 20.0);
+        let plan = scheduler.plan_for(load(5.0, 0.0));
+        assert_eq!(plan.concurrency, 2);
+        assert_eq!(scheduler.plan_for(load(30.0, 0.0)).concurrency, 1);
+    }
+}
+
+// ============================================================================
+// Progress Tests
+// ============================================================================
+
+mod progress_tests {
+    use super::super::progress::{IndexProgress, IndexStage};
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn tracker() -> IndexProgress {
+        let progress = IndexProgress::new();
+        progress.set_roots(vec![PathBuf::from("/data"), PathBuf::from("/data/photos"), PathBuf::from("/work")]);
+        progress
+    }
+
+    #[test]
+    fn test_files_count_toward_deepest_root() {
+        let progress = tracker();
+        progress.record_queued(&PathBuf::from("/data/notes.txt"));
+        progress.record_queued(&PathBuf::from("/data/photos/cat.jpg"));
+        progress.record_queued(&PathBuf::from("/elsewhere/file.txt"));
+
+        let snapshot = progress.snapshot();
+        let queued: Vec<u64> = snapshot.roots.iter().map(|root| root.progress.queued).collect();
+        assert_eq!(queued, vec![1, 1, 0]);
+        assert_eq!(snapshot.total.queued, 3);
+    }
+
+    #[test]
+    fn test_remaining_throughput_and_eta() {
+        let progress = tracker();
+        for i in 0..4 {
+            progress.record_queued(&PathBuf::from(format!("/work/{}.txt", i)));
+        }
+        std::thread::sleep(Duration::from_millis(20));
+        progress.record_finished(&PathBuf::from("/work/0.txt"), false);
+        progress.record_finished(&PathBuf::from("/work/1.txt"), true);
+
+        let work = progress.snapshot().roots.into_iter().find(|r| r.root == PathBuf::from("/work")).unwrap();
+        assert_eq!(work.progress.completed, 1);
+        assert_eq!(work.progress.failed, 1);
+        assert_eq!(work.progress.remaining, 2);
+        assert!(work.progress.throughput_per_minute > 0.0);
+        assert!(work.progress.eta_secs.is_some());
+
+        // An idle root has nothing left to do
+        let data = &progress.snapshot().roots[0];
+        assert_eq!(data.progress.remaining, 0);
+        assert_eq!(data.progress.eta_secs, Some(0));
+    }
+
+    #[test]
+    fn test_stage_timings_and_metrics() {
+        let progress = tracker();
+        let path = PathBuf::from("/data/report.pdf");
+        progress.record_stage(&path, IndexStage::Parse, Duration::from_millis(30));
+        progress.record_stage(&path, IndexStage::Embed, Duration::from_millis(90));
+        progress.record_scan(&[path.clone(), PathBuf::from("/work/a.txt")], Duration::from_millis(10));
+
+        let data = &progress.snapshot().roots[0];
+        let stages: Vec<(IndexStage, u64)> = data.progress.stages.iter().map(|s| (s.stage, s.completed)).collect();
+        assert_eq!(
+            stages,
+            vec![(IndexStage::Scan, 1), (IndexStage::Parse, 1), (IndexStage::Embed, 1), (IndexStage::Store, 0)]
+        );
+        assert!((data.progress.stages[2].avg_ms - 90.0).abs() < 1e-6);
+
+        let embed = progress.metrics().get_stats("indexing.stage.embed").unwrap();
+        assert_eq!(embed.count, 1);
+        let entries = progress.metrics().get_recent_entries_for("indexing.stage.parse");
+        assert_eq!(entries[0].labels.get("root").map(String::as_str), Some("/data"));
+    }
+
+    #[test]
+    fn test_version_changes_on_updates_and_reset_keeps_roots() {
+        let progress = tracker();
+        let version = progress.version();
+        progress.record_queued(&PathBuf::from("/work/a.txt"));
+        assert!(progress.version() > version);
+
+        progress.reset();
+        let snapshot = progress.snapshot();
+        assert_eq!(snapshot.roots.len(), 3);
+        assert_eq!(snapshot.total.queued, 0);
+    }
+}
+
+// ============================================================================
+// Durable Task Store Tests
+// ============================================================================
+
+mod task_store_tests {
+    use super::super::store::TaskStore;
+    use super::super::*;
+    use sqlx::SqlitePool;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    async fn create_store() -> (TaskStore, SqlitePool, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let config = crate::db::DatabaseConfig::with_path(temp_dir.path().join("tasks.db")).with_wal(true);
+        let db = crate::db::create_database_pool(&config).await.unwrap();
+        crate::db::migration::MigrationManager::new(db.clone())
+            .run_migrations()
+            .await
+            .unwrap();
+        (TaskStore::new(db.clone()), db, temp_dir)
+    }
+
+    fn indexer_with(store: &TaskStore) -> ResilientBatchIndexer {
+        ResilientBatchIndexer::with_defaults().with_store(store.clone())
+    }
+
+    fn task(name: &str) -> IndexTask {
+        IndexTask::new(Uuid::now_v7(), PathBuf::from(format!("/docs/{}", name)), TaskPriority::High)
+    }
+
+    #[tokio::test]
+    async fn test_pending_tasks_survive_restart() {
+        let (store, _db, _dir) = create_store().await;
+        let submitted = task("a.txt");
+        indexer_with(&store).submit(submitted.clone()).await.unwrap();
+
+        let restarted = indexer_with(&store);
+        let restored = restarted.restore().await.unwrap();
+        assert_eq!(restored.pending, 1);
+        assert_eq!(restored.requeued, 0);
+
+        let tasks = restarted.pending_tasks().await;
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].id, submitted.id);
+        assert_eq!(tasks[0].file_id, submitted.file_id);
+        assert_eq!(tasks[0].path, submitted.path);
+        assert_eq!(tasks[0].priority, TaskPriority::High);
+        assert_eq!(tasks[0].status, TaskStatus::Pending);
+        assert_eq!(restarted.stats().snapshot().current_queue_size, 1);
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_tasks_are_requeued() {
+        let (store, _db, _dir) = create_store().await;
+        let indexer = indexer_with(&store);
+        indexer.submit(task("b.txt")).await.unwrap();
+
+        let mut running = indexer.collect_ready_tasks().await.pop().unwrap();
+        indexer.start_task(&mut running).await.unwrap();
+        assert_eq!(running.status, TaskStatus::Processing);
+        // Process "crashes" here
+
+        let restarted = indexer_with(&store);
+        let restored = restarted.restore().await.unwrap();
+        assert_eq!(restored.requeued, 1);
+        assert_eq!(restored.pending, 1);
+
+        let tasks = restarted.pending_tasks().await;
+        assert_eq!(tasks[0].status, TaskStatus::Pending);
+        assert_eq!(tasks[0].retry_count, 0);
+        assert!(tasks[0].is_ready());
+    }
+
+    #[tokio::test]
+    async fn test_retry_schedule_survives_restart() {
+        let (store, _db, _dir) = create_store().await;
+        let indexer = indexer_with(&store);
+        indexer.submit(task("c.txt")).await.unwrap();
+
+        let mut running = indexer.collect_ready_tasks().await.pop().unwrap();
+        indexer.start_task(&mut running).await.unwrap();
+        indexer
+            .handle_failure(running, IndexError::IoError { reason: "disk".to_string() })
+            .await;
+
+        let restarted = indexer_with(&store);
+        restarted.restore().await.unwrap();
+        let tasks = restarted.pending_tasks().await;
+        assert_eq!(tasks[0].status, TaskStatus::Failed);
+        assert_eq!(tasks[0].retry_count, 1);
+        assert!(tasks[0].next_retry_at.is_some());
+        assert!(!tasks[0].is_ready());
+        assert!(matches!(tasks[0].last_error, Some(IndexError::IoError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_dead_letters_survive_restart() {
+        let (store, _db, _dir) = create_store().await;
+        let indexer = indexer_with(&store);
+        let mut doomed = task("d.txt");
+        doomed.max_retries = 1;
+        indexer.submit(doomed).await.unwrap();
+
+        let mut running = indexer.collect_ready_tasks().await.pop().unwrap();
+        indexer.start_task(&mut running).await.unwrap();
+        indexer
+            .handle_failure(running, IndexError::EmbeddingFailed { reason: "model".to_string() })
+            .await;
+        assert_eq!(indexer.dead_letter_count().await, 1);
+
+        let restarted = indexer_with(&store);
+        let restored = restarted.restore().await.unwrap();
+        assert_eq!(restored.dead_letter, 1);
+        assert_eq!(restored.pending, 0);
+
+        let dead = restarted.get_dead_letter_tasks().await;
+        assert_eq!(dead[0].status, TaskStatus::DeadLetter);
+        assert!(matches!(dead[0].last_error, Some(IndexError::EmbeddingFailed { .. })));
+
+        // Clearing is durable too
+        restarted.clear_dead_letter_queue().await;
+        assert!(store.load_all().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_completed_tasks_leave_the_store() {
+        let (store, _db, _dir) = create_store().await;
+        let indexer = indexer_with(&store);
+        indexer.submit(task("e.txt")).await.unwrap();
+
+        let mut running = indexer.collect_ready_tasks().await.pop().unwrap();
+        indexer.start_task(&mut running).await.unwrap();
+        indexer.handle_success(running).await;
+
+        assert!(store.load_all().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_retried_dead_letter_is_persisted_as_pending() {
+        let (store, _db, _dir) = create_store().await;
+        let indexer = indexer_with(&store);
+        let mut doomed = task("f.txt");
+        doomed.max_retries = 1;
+        let task_id = doomed.id;
+        indexer.submit(doomed).await.unwrap();
+
+        let running = indexer.collect_ready_tasks().await.pop().unwrap();
+        indexer.handle_failure(running, IndexError::Timeout).await;
+        indexer.retry_dead_letter_task(task_id).await.unwrap();
+
+        let stored = store.load_all().await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].status, TaskStatus::Pending);
+        assert_eq!(stored[0].retry_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_store_rejects_invalid_transitions() {
+        let (store, _db, _dir) = create_store().await;
+        let mut stored = task("g.txt");
+        store.insert(&stored).await.unwrap();
+
+        // Pending -> Completed skips Processing
+        stored.status = TaskStatus::Completed;
+        let result = store.update(&stored).await;
+        assert!(matches!(
+            result,
+            Err(IndexError::InvalidStateTransition {
+                from: TaskStatus::Pending,
+                to: TaskStatus::Completed,
+            })
+        ));
+
+        let missing = task("h.txt");
+        assert!(matches!(
+            store.update(&missing).await,
+            Err(IndexError::TaskNotFound { .. })
+        ));
+    }
+}
@@ -4,6 +4,7 @@
 //! - Remove sensitive file paths
 //! - Replace usernames with placeholders
 //! - Strip personal identifiers
+//! - Redact message participants (display names and addresses)
 //! - Configurable anonymization rules
 //!
 //! **Validates: Requirements 13.2**
@@ -40,6 +41,10 @@ pub struct AnonymizationConfig {
     /// Whether to anonymize IP addresses
     pub anonymize_ips: bool,
     
+    /// Whether to anonymize message participants: the values of
+    /// `From`/`To`/`Cc`-style header lines and `Name <address>` mailboxes
+    pub anonymize_participants: bool,
+    
     /// Custom patterns to anonymize (regex strings)
     pub custom_patterns: Vec<String>,
     
@@ -54,6 +59,7 @@ impl Default for AnonymizationConfig {
             anonymize_paths: true,
             anonymize_emails: true,
             anonymize_ips: true,
+            anonymize_participants: true,
             custom_patterns: Vec::new(),
             preserve_words: HashSet::new(),
         }
@@ -78,6 +84,12 @@ struct AnonymizationPatterns {
     /// Unix path pattern
     unix_path: Regex,
     
+    /// Participant header line (`From:`, `To:`, `Cc:`, ...)
+    participant_header: Regex,
+    
+    /// `Name <address>` mailbox
+    mailbox: Regex,
+    
     /// Custom patterns
     custom: Vec<Regex>,
 }
@@ -91,10 +103,16 @@ impl Default for AnonymizationPatterns {
                 .expect("Invalid IPv4 regex"),
             ipv6: Regex::new(r"([0-9a-fA-F]{1,4}:){7}[0-9a-fA-F]{1,4}")
                 .expect("Invalid IPv6 regex"),
-            windows_path: Regex::new(r"[A-Za-z]:\\[^\s\"\'\n]+")
+            windows_path: Regex::new(r#"[A-Za-z]:\\[^\s"'\n]+"#)
                 .expect("Invalid Windows path regex"),
-            unix_path: Regex::new(r"/(?:home|Users|var|tmp|etc)/[^\s\"\'\n]+")
+            unix_path: Regex::new(r#"/(?:home|Users|var|tmp|etc)/[^\s"'\n]+"#)
                 .expect("Invalid Unix path regex"),
+            participant_header: Regex::new(
+                r"(?mi)^([ \t]*(?:from|to|cc|bcc|reply-to|sender)):[ \t]*\S[^\n]*",
+            )
+            .expect("Invalid participant header regex"),
+            mailbox: Regex::new(r#"(?:"[^"\n]*"[ \t]*|(?:[^\s<>",;:]+[ \t]+){1,3})<[^<>\s]*@[^<>\s]*>"#)
+                .expect("Invalid mailbox regex"),
             custom: Vec::new(),
         }
     }
@@ -126,6 +144,11 @@ impl DataAnonymizer {
     pub fn anonymize(&self, input: &str) -> String {
         let mut result = input.to_string();
         
+        // Anonymize participants before their addresses are rewritten
+        if self.config.anonymize_participants {
+            result = self.anonymize_participants(&result);
+        }
+        
         // Anonymize usernames
         if self.config.anonymize_usernames {
             result = self.anonymize_usernames(&result);
@@ -280,6 +303,15 @@ impl DataAnonymizer {
         self.patterns.email.replace_all(input, "[EMAIL]").to_string()
     }
     
+    /// Anonymize message participants in the input
+    ///
+    /// Header values are replaced as a whole, so display names and
+    /// addresses the email pattern does not recognize are dropped too.
+    fn anonymize_participants(&self, input: &str) -> String {
+        let result = self.patterns.participant_header.replace_all(input, "$1: [CONTACT]");
+        self.patterns.mailbox.replace_all(&result, "[CONTACT]").to_string()
+    }
+    
     /// Anonymize IP addresses in the input
     fn anonymize_ips(&self, input: &str) -> String {
         let mut result = self.patterns.ipv4.replace_all(input, "[IP]").to_string();
@@ -327,6 +359,12 @@ impl DataAnonymizer {
             return true;
         }
         
+        // Check for participants
+        if self.config.anonymize_participants &&
+           (self.patterns.participant_header.is_match(input) || self.patterns.mailbox.is_match(input)) {
+            return true;
+        }
+        
         // Check for IPs
         if self.config.anonymize_ips && 
            (self.patterns.ipv4.is_match(input) || self.patterns.ipv6.is_match(input)) {
@@ -462,6 +500,7 @@ mod tests {
             anonymize_paths: false,
             anonymize_emails: false,
             anonymize_ips: false,
+            anonymize_participants: false,
             custom_patterns: Vec::new(),
             preserve_words: HashSet::new(),
         };
@@ -475,6 +514,17 @@ mod tests {
         assert_eq!(input, result);
     }
 
+    #[test]
+    fn test_anonymize_participants() {
+        let anonymizer = DataAnonymizer::new();
+        
+        let input = "From: Mei Tan <mei@example.com>\nTo: kenji at example dot org\nSubject: Dinner\nAsk \"Sato, Kenji\" <kenji@mailhost> first";
+        let result = anonymizer.anonymize(input);
+        
+        assert_eq!(result, "From: [CONTACT]\nTo: [CONTACT]\nSubject: Dinner\nAsk [CONTACT] first");
+        assert!(anonymizer.contains_sensitive("cc: Hana Müller"));
+    }
+
     #[test]
     fn test_multiple_sensitive_items() {
         let anonymizer = DataAnonymizer::new();
@@ -26,7 +26,7 @@ pub use merger::{ResultMerger, MergerConfig, MergedResult};
 pub use hybrid::{HybridInferenceEngine, InferenceCache};
 pub use error::{InferenceError, InferenceResult};
 pub use types::{
-    InferenceRequest, InferenceResponse, InferenceContext, InferenceOptions,
+    InferenceRequest, InferenceResponse, InferenceContext, InferenceOptions, CloudUnderstanding,
     LocalModelType, CloudModelType, FileStructureContext, UserHistoryContext,
     SessionContext, RecentFile,
 };
@@ -409,6 +409,7 @@ fn test_anonymizer_disabled_features() {
         anonymize_paths: false,
         anonymize_emails: false,
         anonymize_ips: false,
+        anonymize_participants: false,
         custom_patterns: Vec::new(),
         preserve_words: HashSet::new(),
     };
@@ -4,6 +4,7 @@
 //! - Semantic search with intent parsing
 //! - Intelligent tag management
 //! - Logic chain file associations
+//! - Duplicate and near-duplicate detection
 //! - Hybrid local/cloud inference
 //! - Process supervision via watchdog
 //! - OS integration (desktop takeover, hotkeys, multi-monitor)
@@ -11,7 +12,8 @@
 //! - Full-text search with multi-language tokenization
 //! - File system monitoring with event deduplication
 //! - File system reconciliation with rename detection
-//! - Content parsing for various file formats
+//! - Content parsing for various file formats, including archive members
+//! - Photo metadata (capture time, camera, place, keywords) for date and location search
 //! - Embedding generation with ONNX Runtime
 //! - Tauri IPC commands for frontend communication
 
@@ -30,6 +32,8 @@ pub mod embeddings;
 pub mod inference;
 pub mod tag;
 pub mod relation;
+pub mod dedup;
+pub mod archive;
 pub mod asset;
 pub mod preview;
 pub mod highlight;
@@ -44,7 +48,7 @@ pub use core::error::{NeuralFSError, Result};
 pub use core::config::AppConfig as CoreAppConfig;
 pub use config::{
     ConfigStore, ConfigStoreConfig, ConfigError, ConfigResult,
-    AppConfig, CloudConfig, PerformanceConfig, PrivacyConfig, UIConfig,
+    AppConfig, CloudConfig, PerformanceConfig, IndexingBudget, PrivacyConfig, UIConfig, SearchConfig,
     ConfigMigration, MigrationManager, MigrationError, MigrationResult,
     ConfigVersion, VersionedConfig,
 };
@@ -54,13 +58,13 @@ pub use os::{
     SystemActivityMonitor, ActivityMonitorConfig, SystemState, StateChangeCallback,
     GameModePolicy, GameModePolicyConfig, GameModeStatus, GameModeController,
 };
-pub use vector::{VectorStore, VectorStoreConfig, VectorError};
+pub use vector::{VectorStore, VectorPoint, VectorStoreConfig, VectorSpace, VectorError};
 pub use search::{TextIndex, TextIndexConfig, TextIndexError, MultilingualTokenizer};
-pub use watcher::{FileWatcher, FileWatcherConfig, FileWatcherBuilder, FileEvent, EventBatch, DirectoryFilter, DirectoryFilterConfig, FilterResult, FilterReason};
-pub use reconcile::{ReconciliationService, ReconcileConfig, ReconcileResult, FileId, RenameEvent};
-pub use parser::{ContentParserService, ContentParser, ParseConfig, ParseResult, ParseMetadata, ParseError, TextParser, PdfParser, CodeParser};
-pub use indexer::{ResilientBatchIndexer, IndexerConfig, IndexerStats, IndexTask, TaskStatus, TaskPriority, IndexError as IndexerError};
-pub use embeddings::{EmbeddingEngine, EmbeddingConfig, EmbeddingError, ModelManager, ModelLoadingState, VRAMManager, VRAMStatus, ModelType, DilutedAttentionProcessor, DilutedAttentionConfig, ProcessedWindow, CoverageStats};
+pub use watcher::{FileWatcher, FileWatcherConfig, FileWatcherBuilder, FileEvent, EventBatch, DirectoryFilter, DirectoryFilterConfig, FilterResult, FilterReason, IgnoreRule, WatchMode, PollingReason, RootWatchStatus, FileIdentity, FileIdentitySource, EventJournal, JournalRecovery, JournalState};
+pub use reconcile::{ReconciliationService, ReconcileConfig, ReconcileResult, FileId, RenameEvent, MoveCandidate, MoveResolution, HeldDeletions};
+pub use parser::{ContentParserService, ContentParser, ParseConfig, ParseResult, ParseMetadata, ParseError, TextParser, PdfParser, CodeParser, OfficeParser, OpenDocumentParser, RtfParser, HtmlParser, EpubParser, EmailParser, ArchiveParser, ImageMetadataParser, ImageMetadata, GpsPosition, DocumentBlock};
+pub use indexer::{ResilientBatchIndexer, IndexerConfig, IndexerStats, IndexTask, TaskStatus, TaskPriority, IndexError as IndexerError, IndexingPipeline, CatchUp, PipelineConfig, PipelineReport, ChunkEmbedder, TaskStore, RestoredTasks, ResourceScheduler, ResourceProbe, ResourceSample, ProbeCapabilities, SchedulePlan, SchedulerMode, SystemProbe, ModelMigrator, MigrationProgress, IndexProgress, IndexProgressSnapshot, IndexStage, ProgressSummary, RootProgress, StageProgress};
+pub use embeddings::{EmbeddingEngine, EmbeddingConfig, ClipTextEmbeddingConfig, EmbeddingModelTag, EmbeddingError, ModelManager, ModelLoadingState, VRAMManager, VRAMStatus, ModelType, DilutedAttentionProcessor, DilutedAttentionConfig, ProcessedWindow, CoverageStats};
 pub use inference::{
     HybridInferenceEngine, LocalInferenceEngine, CloudBridge, CloudConfig, ResultMerger,
     MergerConfig, DataAnonymizer, InferenceRequest, InferenceResponse, InferenceContext,
@@ -77,6 +81,11 @@ pub use relation::{
     RelationCommand, RelationCorrectionService, RelationCorrectionResult, BlockScope,
     BlockRuleStore, BlockRuleFilter, RelationError,
 };
+pub use dedup::{
+    DuplicateService, DedupConfig, DuplicateGroup, DuplicateMember, DuplicateKind, DuplicateStatus,
+    DuplicateMap, DetectionReport,
+};
+pub use archive::{ArchiveError, ArchiveLimits, ArchiveFormat, ArchiveEntry};
 pub use asset::{
     SecureAssetStreamServer, AssetServerConfig, AssetServerState,
     CachedThumbnail, CachedPreview, AssetError,
@@ -99,7 +108,7 @@ pub use update::{
 pub use protocol::{
     register_custom_protocol, ProtocolState, ProtocolConfig,
     get_session_token, AssetProtocolHandler, SessionTokenResponse,
-    build_thumbnail_url, build_preview_url, build_file_url,
+    build_thumbnail_url, build_preview_url, build_file_url, build_archive_member_url,
 };
 pub use logging::{
     LoggingSystem, LoggingConfig, LoggingError, LoggingResult,
@@ -11,7 +11,8 @@ use neural_fs::core::{RuntimeDependencies, RuntimeStatus};
 use neural_fs::logging::{LoggingSystem, LoggingConfig, LogLevel, LogOutput};
 use neural_fs::commands::{
     // Search commands
-    search_files, get_search_suggestions,
+    search_files, search_files_stream, cancel_search, get_search_suggestions, record_clarification,
+    SearchStreamState, IntentParserState, saved_inference_engine,
     // Tag commands
     get_tags, get_file_tags, add_tag, remove_tag, confirm_tag, reject_tag, create_tag,
     // Relation commands
@@ -25,18 +26,25 @@ use neural_fs::commands::{
     // Status commands
     get_index_status, get_system_status, get_dead_letter_tasks, get_dead_letter_stats,
     retry_dead_letter, retry_all_dead_letter, clear_dead_letter,
-    pause_indexing, resume_indexing,
+    pause_indexing, resume_indexing, get_index_progress, spawn_index_progress_events,
+    get_move_candidates, resolve_move_candidate,
+    preview_reconcile, get_held_deletions, confirm_held_deletions, get_watch_modes,
+    // Duplicate commands
+    list_duplicate_groups, get_duplicate_detection_status, resolve_duplicates,
+    DuplicateDetectionState,
     // Protocol commands
     get_session_token_cmd, build_thumbnail_url_cmd, build_preview_url_cmd,
-    build_file_url_cmd, get_asset_server_port, is_protocol_ready,
+    build_file_url_cmd, build_archive_member_url_cmd, get_asset_server_port, is_protocol_ready,
     // Onboarding commands
     check_first_launch, get_suggested_directories, browse_directory,
     save_onboarding_config, start_initial_scan, get_scan_progress, complete_onboarding,
+    IndexingState, default_data_dir, saved_monitored_directories,
 };
 use neural_fs::protocol::{
     register_custom_protocol, ProtocolState,
 };
-use neural_fs::asset::AssetServerConfig;
+use neural_fs::asset::{AssetServerConfig, SecureAssetStreamServer};
+use neural_fs::os::GameModeController;
 
 /// Application state
 pub struct AppState {
@@ -103,6 +111,50 @@ fn main() {
     // Create config state
     let config_state = ConfigState::new();
 
+    // Create streaming search state (cancellation registry), with the
+    // inference engine for cloud refinement when cloud features are on
+    let search_stream_state = match saved_inference_engine(default_data_dir().join("models")) {
+        Some(engine) => SearchStreamState::new().with_inference_engine(engine),
+        None => SearchStreamState::new(),
+    };
+
+    // Watch for fullscreen applications; game mode holds indexing paused
+    let mut game_mode = GameModeController::new();
+    let indexer_paused = tauri::async_runtime::block_on(async {
+        game_mode.start().await;
+        game_mode.indexer_paused_flag().await
+    });
+
+    // Create indexing state and reopen the index in the background so
+    // tasks queued by the previous run are restored and resumed, and file
+    // changes it received but did not apply are caught up on. The roots are
+    // then watched, and the worker keeps applying changes and running
+    // queued tasks for the rest of the session
+    let indexing_state = match _logging_system {
+        Some(ref system) => IndexingState::new().with_metrics(system.metrics()),
+        None => IndexingState::new(),
+    }
+    .with_pause_flag(indexer_paused);
+    {
+        let indexing_state = indexing_state.clone();
+        tauri::async_runtime::spawn(async move {
+            match indexing_state.get_or_init(&default_data_dir()).await {
+                Ok(_) => {
+                    let roots = saved_monitored_directories();
+                    if !roots.is_empty() {
+                        if let Err(e) = indexing_state.catch_up(&default_data_dir(), &roots).await {
+                            tracing::warn!("{}", e);
+                        }
+                    }
+                    if let Err(e) = indexing_state.watch_roots(&default_data_dir(), &roots).await {
+                        tracing::warn!("{}", e);
+                    }
+                }
+                Err(e) => tracing::warn!("Indexing pipeline unavailable: {}", e),
+            }
+        });
+    }
+
     // Create protocol state with default configuration
     // This generates the session token that will be used for asset requests
     let asset_config = AssetServerConfig::default();
@@ -115,11 +167,31 @@ fn main() {
         &protocol_state.get_session_token()[..8]
     );
 
+    // Archive members are streamed by the HTTP asset server, which shares
+    // the protocol's token and registered archives
+    let asset_server = SecureAssetStreamServer::with_state((*protocol_state.asset_state).clone());
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = asset_server.start().await {
+            tracing::warn!("Asset server stopped: {}", e);
+        }
+    });
+
     // Build and run Tauri application with custom protocol
+    let progress_indexing_state = indexing_state.clone();
     let builder = tauri::Builder::default()
         .manage(app_state)
         .manage(config_state)
-        .manage(protocol_state.clone());
+        .manage(search_stream_state)
+        .manage(IntentParserState::new())
+        .manage(indexing_state)
+        .manage(DuplicateDetectionState::new())
+        .manage(game_mode)
+        .manage(protocol_state.clone())
+        .setup(move |app| {
+            // Push per-root indexing progress to the frontend
+            spawn_index_progress_events(app.handle(), progress_indexing_state, std::time::Duration::from_secs(1));
+            Ok(())
+        });
 
     // Register the nfs:// custom protocol
     let builder = register_custom_protocol(builder, protocol_state);
@@ -134,11 +206,15 @@ fn main() {
             build_thumbnail_url_cmd,
             build_preview_url_cmd,
             build_file_url_cmd,
+            build_archive_member_url_cmd,
             get_asset_server_port,
             is_protocol_ready,
             // Search commands (Requirements 2.1, 2.2)
             search_files,
+            search_files_stream,
+            cancel_search,
             get_search_suggestions,
+            record_clarification,
             // Tag commands (Requirements 5.1, Human-in-the-Loop)
             get_tags,
             get_file_tags,
@@ -172,6 +248,7 @@ fn main() {
             restore_config_backup,
             // Status commands (Requirements 16.1, Indexer Resilience)
             get_index_status,
+            get_index_progress,
             get_system_status,
             get_dead_letter_tasks,
             get_dead_letter_stats,
@@ -180,6 +257,16 @@ fn main() {
             clear_dead_letter,
             pause_indexing,
             resume_indexing,
+            get_move_candidates,
+            resolve_move_candidate,
+            preview_reconcile,
+            get_held_deletions,
+            confirm_held_deletions,
+            get_watch_modes,
+            // Duplicate commands
+            list_duplicate_groups,
+            get_duplicate_detection_status,
+            resolve_duplicates,
             // Onboarding commands (Requirements 17.1, 17.2, 17.3, 17.4, 17.5)
             check_first_launch,
             get_suggested_directories,
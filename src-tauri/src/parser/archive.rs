@@ -0,0 +1,248 @@
+//! Archive parser
+//!
+//! Indexes zip and tar archives:
+//! - the entry listing becomes the first chunks, so member names are
+//!   searchable even when their content is not
+//! - supported members are unpacked to a scratch directory and parsed by
+//!   a nested `ContentParserService`; their chunks record the member path
+//!   in `ChunkLocation::member`
+//! - archives inside archives are opened up to `ArchiveLimits::max_depth`
+//!
+//! Members over the size or compression ratio limits are listed but not
+//! parsed, and unpacking stops at the total size limit, which an archive
+//! shares with the archives nested in it.
+
+use std::fs::File;
+use std::io;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+use super::{
+    create_chunks_from_blocks, ContentParser, ContentParserService, DocumentBlock, ParseConfig,
+    ParseError, ParseMetadata, ParseResult,
+};
+use crate::archive::{self, ArchiveBudget, ArchiveError, ArchiveFormat, ArchiveLimits, MEMBER_SEPARATOR};
+use crate::core::types::chunk::ChunkType;
+use crate::core::types::file::FileType;
+
+/// Parser for zip and tar archives
+#[derive(Clone)]
+pub struct ArchiveParser {
+    supported_extensions: Vec<&'static str>,
+    limits: ArchiveLimits,
+    /// Archive nesting level of the files this parser sees (1 = on disk)
+    depth: u32,
+    /// Total size budget of the enclosing archive; each archive on disk
+    /// starts its own
+    budget: Option<ArchiveBudget>,
+}
+
+/// Entry listing and unpacked members of an archive
+struct Unpacked {
+    listing: Vec<String>,
+    /// Member paths and where they were unpacked, in archive order
+    members: Vec<(String, PathBuf)>,
+    /// Keeps the unpacked members until they are parsed
+    _scratch: TempDir,
+}
+
+impl ArchiveParser {
+    /// Create a new archive parser with default limits
+    pub fn new() -> Self {
+        Self::with_limits(ArchiveLimits::default())
+    }
+
+    /// Create a new archive parser with custom limits
+    pub fn with_limits(limits: ArchiveLimits) -> Self {
+        Self {
+            supported_extensions: vec!["zip", "tar", "tgz", "gz"],
+            limits,
+            depth: 1,
+            budget: None,
+        }
+    }
+
+    /// Parser for archives found inside the archives this parser reads,
+    /// drawing on the same budget
+    fn nested(&self, budget: ArchiveBudget) -> Self {
+        Self {
+            supported_extensions: self.supported_extensions.clone(),
+            limits: self.limits.clone(),
+            depth: self.depth + 1,
+            budget: Some(budget),
+        }
+    }
+
+    /// List the archive at `path` and unpack the members worth parsing
+    fn unpack(
+        &self,
+        path: &Path,
+        service: &ContentParserService,
+        budget: &ArchiveBudget,
+    ) -> Result<Unpacked, ParseError> {
+        let scratch = tempfile::Builder::new().prefix("neuralfs-archive-").tempdir()?;
+        let mut listing = Vec::new();
+        let mut members = Vec::new();
+        let mut unpacking = true;
+
+        let summary = archive::for_each_member_within(path, &self.limits, budget, |entry, reader| {
+            listing.push(entry.path.clone());
+
+            let member_path = Path::new(entry.file_name());
+            let is_archive = ArchiveFormat::from_name(&entry.path).is_some();
+            let wanted = service.is_supported(member_path)
+                && (!is_archive || self.depth < self.limits.max_depth)
+                && self.limits.allows(entry);
+            if !wanted || !unpacking {
+                return Ok(ControlFlow::Continue(()));
+            }
+
+            // Keep the member's file name so its parser is chosen by extension
+            let target = scratch
+                .path()
+                .join(format!("{}-{}", members.len(), entry.file_name()));
+            let copied = File::create(&target).and_then(|mut file| io::copy(reader, &mut file));
+            match copied {
+                Ok(_) => members.push((entry.path.clone(), target)),
+                Err(e) if archive::is_size_limit(&e) => {
+                    // Either the total budget is spent or the member is
+                    // larger than its header claims; list the rest only
+                    tracing::warn!("Stopped unpacking {:?} at {}: size limit reached", path, entry.path);
+                    let _ = std::fs::remove_file(&target);
+                    unpacking = false;
+                }
+                Err(e) => return Err(ArchiveError::Io(e)),
+            }
+            Ok(ControlFlow::Continue(()))
+        })
+        .map_err(archive_error)?;
+
+        if summary.truncated {
+            tracing::warn!(
+                "Archive {:?} has more than {} entries; the rest are not indexed",
+                path,
+                self.limits.max_entries
+            );
+        }
+
+        Ok(Unpacked {
+            listing,
+            members,
+            _scratch: scratch,
+        })
+    }
+}
+
+impl Default for ArchiveParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ContentParser for ArchiveParser {
+    async fn parse(&self, path: &Path, config: &ParseConfig) -> Result<ParseResult, ParseError> {
+        if !path.exists() {
+            return Err(ParseError::FileNotFound {
+                path: path.display().to_string(),
+            });
+        }
+        // `.gz` alone is a compressed file, not an archive
+        if ArchiveFormat::from_path(path).is_none() {
+            return Err(ParseError::UnsupportedFileType {
+                extension: path
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .unwrap_or("")
+                    .to_lowercase(),
+            });
+        }
+
+        // Members are parsed by the same parsers as files on disk
+        let budget = self
+            .budget
+            .clone()
+            .unwrap_or_else(|| ArchiveBudget::new(self.limits.max_total_size));
+        let mut service = ContentParserService::with_config(config.clone());
+        service.archive_parser = self.nested(budget.clone());
+
+        // Decompression is blocking
+        let path_owned = path.to_path_buf();
+        let parser = self.clone();
+        let (unpacked, service) = tokio::task::spawn_blocking(move || {
+            let unpacked = parser.unpack(&path_owned, &service, &budget);
+            (unpacked, service)
+        })
+        .await
+        .map_err(|e| ParseError::ParseFailed {
+            reason: format!("Task join error: {}", e),
+        })?;
+        let unpacked = unpacked?;
+
+        let file_id = Uuid::now_v7();
+        let listing = DocumentBlock::new(ChunkType::Paragraph, unpacked.listing.join("\n"));
+        let (mut text, mut chunks) = create_chunks_from_blocks(file_id, &[listing], config);
+
+        for (member, member_path) in &unpacked.members {
+            let parsed = match service.parse(member_path).await {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    tracing::debug!("Skipping {} in {:?}: {}", member, path, e);
+                    continue;
+                }
+            };
+            if parsed.text.trim().is_empty() {
+                continue;
+            }
+
+            if !text.is_empty() {
+                text.push_str("\n\n");
+            }
+            text.push_str(&parsed.text);
+            for mut chunk in parsed.chunks {
+                chunk.file_id = file_id;
+                chunk.chunk_index = chunks.len() as u32;
+                // Members of nested archives extend the path
+                chunk.location.member = Some(match chunk.location.member {
+                    Some(inner) => format!("{}{}{}", member, MEMBER_SEPARATOR, inner),
+                    None => member.clone(),
+                });
+                chunks.push(chunk);
+            }
+        }
+
+        let metadata = ParseMetadata {
+            word_count: text.split_whitespace().count(),
+            char_count: text.chars().count(),
+            ..Default::default()
+        };
+
+        Ok(ParseResult {
+            text,
+            chunks,
+            metadata,
+        })
+    }
+
+    fn supports(&self, file_type: FileType) -> bool {
+        matches!(file_type, FileType::Archive)
+    }
+
+    fn supported_extensions(&self) -> &[&str] {
+        &self.supported_extensions
+    }
+}
+
+fn archive_error(error: ArchiveError) -> ParseError {
+    match error {
+        ArchiveError::Io(e) => ParseError::Io(e),
+        ArchiveError::NotFound { path } => ParseError::FileNotFound { path },
+        other => ParseError::CorruptedFile {
+            reason: other.to_string(),
+        },
+    }
+}
@@ -22,7 +22,7 @@ impl CodeParser {
         Self {
             supported_extensions: vec![
                 "rs", "py", "js", "ts", "jsx", "tsx", "java", "c", "cpp", "h", "hpp", "cs", "go",
-                "rb", "php", "swift", "kt", "scala", "html", "css", "scss", "sql", "sh", "bash",
+                "rb", "php", "swift", "kt", "scala", "css", "scss", "sql", "sh", "bash",
                 "zsh", "ps1", "bat", "cmd",
             ],
         }
@@ -76,6 +76,9 @@ impl CodeParser {
                         end_line: Some(structure.end_line),
                         page_number: None,
                         bounding_box: None,
+                        section: None,
+                        section_name: None,
+                        member: None,
                     },
                     vector_id: 0,
                     created_at: Utc::now(),
@@ -487,6 +490,9 @@ impl CodeParser {
                         end_line: Some(chunk_end_line),
                         page_number: None,
                         bounding_box: None,
+                        section: None,
+                        section_name: None,
+                        member: None,
                     },
                     vector_id: 0,
                     created_at: Utc::now(),
@@ -538,6 +544,9 @@ impl CodeParser {
                             end_line: Some(chunk_end_line),
                             page_number: None,
                             bounding_box: None,
+                            section: None,
+                            section_name: None,
+                            member: None,
                         },
                         vector_id: 0,
                         created_at: Utc::now(),
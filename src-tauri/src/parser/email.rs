@@ -0,0 +1,291 @@
+//! Email parser
+//!
+//! Handles single exported messages (`.eml`) and mbox archives. Each
+//! message becomes a group of chunks sharing its message index and subject
+//! in `ChunkLocation`:
+//! - the subject as a `Heading` chunk
+//! - a header block with sender, recipients, date and attachment names
+//! - the body, preferring `text/plain` and falling back to the HTML part
+//!
+//! Participants are kept verbatim for local search. The header block
+//! uses `From:`/`To:`/`Cc:` lines and `Name <local@domain>` entries, which
+//! `DataAnonymizer` replaces with `[CONTACT]` as a whole, so neither
+//! display names nor addresses in non-standard forms reach a cloud model.
+//! The sender is also exposed through `ParseMetadata::author` in the same
+//! `Name <local@domain>` form and is redacted the same way.
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use async_trait::async_trait;
+use mail_parser::mailbox::mbox::MessageIterator;
+use mail_parser::{Address, Message, MessageParser, MimeHeaders};
+use scraper::Html;
+use uuid::Uuid;
+
+use super::html::{document_body, page_blocks};
+use super::{
+    create_chunks_from_blocks, ContentParser, DocumentBlock, ParseConfig, ParseError, ParseMetadata,
+    ParseResult,
+};
+use crate::core::types::chunk::ChunkType;
+use crate::core::types::file::FileType;
+
+/// Upper bound on messages read from one mbox archive
+const MAX_MBOX_MESSAGES: usize = 10_000;
+
+/// Heading of messages without a subject
+const NO_SUBJECT: &str = "(no subject)";
+
+/// Parser for exported email
+pub struct EmailParser {
+    supported_extensions: Vec<&'static str>,
+}
+
+/// Content and metadata of a message file or archive
+struct Mail {
+    blocks: Vec<DocumentBlock>,
+    message_count: u32,
+    subject: Option<String>,
+    sender: Option<String>,
+    date: Option<String>,
+}
+
+/// Headers and body of one message
+struct MailMessage {
+    subject: Option<String>,
+    sender: Option<String>,
+    date: Option<String>,
+    blocks: Vec<DocumentBlock>,
+}
+
+impl EmailParser {
+    /// Create a new email parser
+    pub fn new() -> Self {
+        Self {
+            supported_extensions: vec!["eml", "mbox"],
+        }
+    }
+
+    /// Read the message or mailbox at `path`
+    fn extract(&self, path: &Path, is_mbox: bool) -> Result<Mail, ParseError> {
+        if !is_mbox {
+            let bytes = std::fs::read(path)?;
+            let message = read_message(&bytes, 1).ok_or_else(|| ParseError::CorruptedFile {
+                reason: "Not a MIME message".to_string(),
+            })?;
+            return Ok(Mail {
+                blocks: message.blocks,
+                message_count: 1,
+                subject: message.subject,
+                sender: message.sender,
+                date: message.date,
+            });
+        }
+
+        let reader = BufReader::new(File::open(path)?);
+        let mut blocks = Vec::new();
+        let mut index = 0u32;
+        for entry in MessageIterator::new(reader).take(MAX_MBOX_MESSAGES) {
+            // A damaged message does not spoil the rest of the archive
+            let Ok(entry) = entry else {
+                continue;
+            };
+            if let Some(message) = read_message(entry.contents(), index + 1) {
+                index += 1;
+                blocks.extend(message.blocks);
+            }
+        }
+
+        Ok(Mail {
+            blocks,
+            message_count: index,
+            subject: None,
+            sender: None,
+            date: None,
+        })
+    }
+}
+
+impl Default for EmailParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ContentParser for EmailParser {
+    async fn parse(&self, path: &Path, config: &ParseConfig) -> Result<ParseResult, ParseError> {
+        if !path.exists() {
+            return Err(ParseError::FileNotFound {
+                path: path.display().to_string(),
+            });
+        }
+
+        let is_mbox = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("mbox"));
+
+        // MIME decoding of a whole archive is blocking
+        let path_owned = path.to_path_buf();
+        let parser = Self::new();
+        let mail = tokio::task::spawn_blocking(move || parser.extract(&path_owned, is_mbox))
+            .await
+            .map_err(|e| ParseError::ParseFailed {
+                reason: format!("Task join error: {}", e),
+            })??;
+
+        let file_id = Uuid::now_v7();
+        let (text, chunks) = create_chunks_from_blocks(file_id, &mail.blocks, config);
+
+        let metadata = ParseMetadata {
+            title: mail.subject,
+            author: mail.sender,
+            created_date: mail.date,
+            page_count: is_mbox.then_some(mail.message_count),
+            word_count: text.split_whitespace().count(),
+            char_count: text.chars().count(),
+            ..Default::default()
+        };
+
+        Ok(ParseResult {
+            text,
+            chunks,
+            metadata,
+        })
+    }
+
+    fn supports(&self, file_type: FileType) -> bool {
+        matches!(file_type, FileType::TextDocument)
+    }
+
+    fn supported_extensions(&self) -> &[&str] {
+        &self.supported_extensions
+    }
+}
+
+// ============================================================================
+// Messages
+// ============================================================================
+
+/// Parse one MIME message into blocks of section `index`
+fn read_message(bytes: &[u8], index: u32) -> Option<MailMessage> {
+    let message = MessageParser::default().parse(bytes)?;
+    // Anything parses as a message; one without headers is not mail
+    if message.headers().is_empty() {
+        return None;
+    }
+
+    let subject = message
+        .subject()
+        .map(collapse_whitespace)
+        .filter(|s| !s.is_empty());
+    let sender = message.from().and_then(format_addresses);
+    let date = message.date().map(|d| d.to_rfc3339());
+
+    let mut headers = Vec::new();
+    if let Some(sender) = &sender {
+        headers.push(format!("From: {}", sender));
+    }
+    for (label, addresses) in [("To", message.to()), ("Cc", message.cc())] {
+        if let Some(list) = addresses.and_then(format_addresses) {
+            headers.push(format!("{}: {}", label, list));
+        }
+    }
+    if let Some(date) = &date {
+        headers.push(format!("Date: {}", date));
+    }
+    let attachments: Vec<&str> = message
+        .attachments()
+        .filter_map(|part| part.attachment_name())
+        .filter(|name| !name.trim().is_empty())
+        .collect();
+    if !attachments.is_empty() {
+        headers.push(format!("Attachments: {}", attachments.join(", ")));
+    }
+
+    let mut blocks = vec![DocumentBlock::new(
+        ChunkType::Heading,
+        subject.as_deref().unwrap_or(NO_SUBJECT),
+    )];
+    if !headers.is_empty() {
+        blocks.push(DocumentBlock::new(ChunkType::Paragraph, headers.join("\n")));
+    }
+    blocks.extend(body_blocks(&message));
+
+    let blocks = blocks
+        .into_iter()
+        .map(|block| block.in_section(index, subject.clone()))
+        .collect();
+
+    Some(MailMessage {
+        subject,
+        sender,
+        date,
+        blocks,
+    })
+}
+
+/// Blocks of the readable body parts
+///
+/// The text bodies are the `text/plain` alternatives, or the HTML parts
+/// when a message has no plain text.
+fn body_blocks(message: &Message<'_>) -> Vec<DocumentBlock> {
+    let mut blocks = Vec::new();
+    for part in message.text_bodies() {
+        let Some(contents) = part.text_contents() else {
+            continue;
+        };
+        if part.is_text_html() {
+            let document = Html::parse_document(contents);
+            blocks.extend(page_blocks(document_body(&document), false));
+        } else {
+            blocks.extend(text_paragraphs(contents));
+        }
+    }
+    blocks
+}
+
+/// Paragraphs of a plain text body, split at blank lines
+fn text_paragraphs(text: &str) -> Vec<DocumentBlock> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    for line in text.lines().map(str::trim_end) {
+        if line.trim().is_empty() {
+            if !paragraph.is_empty() {
+                blocks.push(DocumentBlock::new(ChunkType::Paragraph, paragraph.join("\n")));
+                paragraph.clear();
+            }
+        } else {
+            paragraph.push(line);
+        }
+    }
+    if !paragraph.is_empty() {
+        blocks.push(DocumentBlock::new(ChunkType::Paragraph, paragraph.join("\n")));
+    }
+    blocks
+}
+
+/// `Name <local@domain>` entries of an address header, comma separated
+fn format_addresses(addresses: &Address<'_>) -> Option<String> {
+    let formatted: Vec<String> = addresses
+        .iter()
+        .filter_map(|addr| {
+            let name = addr.name().map(collapse_whitespace).filter(|n| !n.is_empty());
+            let address = addr.address().map(str::trim).filter(|a| !a.is_empty());
+            match (name, address) {
+                (Some(name), Some(address)) => Some(format!("{} <{}>", name, address)),
+                (None, Some(address)) => Some(address.to_string()),
+                (Some(name), None) => Some(name),
+                (None, None) => None,
+            }
+        })
+        .collect();
+    (!formatted.is_empty()).then(|| formatted.join(", "))
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
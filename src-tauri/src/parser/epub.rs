@@ -0,0 +1,354 @@
+//! EPUB parser
+//!
+//! Reads the package document named by `META-INF/container.xml` and walks
+//! its spine in reading order. Each chapter is extracted like a web page
+//! and its chunks carry the chapter number and title; titles come from the
+//! table of contents (EPUB 3 navigation document or EPUB 2 NCX), else the
+//! chapter's first heading.
+//!
+//! Title, author, date and language come from the package metadata.
+
+use std::collections::HashMap;
+use std::io::{Read, Seek};
+use std::path::Path;
+
+use async_trait::async_trait;
+use roxmltree::Node;
+use scraper::{Html, Selector};
+use uuid::Uuid;
+
+use super::html::{document_body, page_blocks};
+use super::package::{attr, children, is, parse_xml, resolve_target, Package};
+use super::{
+    create_chunks_from_blocks, ContentParser, DocumentBlock, ParseConfig, ParseError, ParseMetadata,
+    ParseResult,
+};
+use crate::core::types::chunk::ChunkType;
+use crate::core::types::file::FileType;
+
+/// Parser for EPUB ebooks
+pub struct EpubParser {
+    supported_extensions: Vec<&'static str>,
+}
+
+/// Content and metadata of an ebook
+struct Ebook {
+    blocks: Vec<DocumentBlock>,
+    title: Option<String>,
+    author: Option<String>,
+    created_date: Option<String>,
+    language: Option<String>,
+}
+
+/// A manifest entry of the package document
+struct ManifestItem {
+    path: String,
+    media_type: String,
+    properties: String,
+}
+
+impl EpubParser {
+    /// Create a new EPUB parser
+    pub fn new() -> Self {
+        Self {
+            supported_extensions: vec!["epub"],
+        }
+    }
+
+    /// Read the ebook at `path`
+    fn extract(&self, path: &Path) -> Result<Ebook, ParseError> {
+        let mut package = Package::open(path)?;
+
+        let container_xml = package.require_part("META-INF/container.xml")?;
+        let container = parse_xml(&container_xml)?;
+        let opf_path = container
+            .descendants()
+            .find(|n| is(*n, "rootfile"))
+            .and_then(|n| attr(n, "full-path"))
+            .map(percent_decode)
+            .ok_or_else(|| ParseError::CorruptedFile {
+                reason: "Container names no package document".to_string(),
+            })?;
+        let opf_dir = parent_dir(&opf_path).to_string();
+
+        let opf_xml = package.require_part(&opf_path)?;
+        let opf = parse_xml(&opf_xml)?;
+        let section = |name: &str| opf.root_element().children().find(|n| is(*n, name));
+
+        // Metadata
+        let metadata_texts = |name: &str| -> Vec<String> {
+            section("metadata")
+                .map(|metadata| {
+                    children(metadata, name)
+                        .filter_map(|n| n.text())
+                        .map(|t| t.trim().to_string())
+                        .filter(|t| !t.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let title = metadata_texts("title").into_iter().next();
+        let authors = metadata_texts("creator");
+        let author = (!authors.is_empty()).then(|| authors.join(", "));
+        let created_date = metadata_texts("date").into_iter().next();
+        let language = metadata_texts("language").into_iter().next();
+
+        // Manifest and spine
+        let manifest: HashMap<String, ManifestItem> = section("manifest")
+            .map(|manifest| {
+                children(manifest, "item")
+                    .filter_map(|item| {
+                        let id = attr(item, "id")?;
+                        let href = attr(item, "href")?;
+                        Some((
+                            id.to_string(),
+                            ManifestItem {
+                                path: resolve_target(&opf_dir, &percent_decode(strip_fragment(href))),
+                                media_type: attr(item, "media-type").unwrap_or("").to_string(),
+                                properties: attr(item, "properties").unwrap_or("").to_string(),
+                            },
+                        ))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let spine = section("spine").ok_or_else(|| ParseError::CorruptedFile {
+            reason: "Package document has no spine".to_string(),
+        })?;
+
+        let mut toc = read_nav_titles(&mut package, &manifest)?;
+        if let Some(ncx) = attr(spine, "toc").and_then(|id| manifest.get(id)) {
+            for (path, label) in read_ncx_titles(&mut package, &ncx.path)? {
+                toc.entry(path).or_insert(label);
+            }
+        }
+
+        // Chapters in reading order
+        let mut blocks = Vec::new();
+        let mut chapter = 0u32;
+        for itemref in children(spine, "itemref") {
+            let Some(item) = attr(itemref, "idref").and_then(|id| manifest.get(id)) else {
+                continue;
+            };
+            let is_document = matches!(item.media_type.as_str(), "application/xhtml+xml" | "text/html");
+            if !is_document || item.properties.split_whitespace().any(|p| p == "nav") {
+                continue;
+            }
+            let Some(xhtml) = package.read_part(&item.path)? else {
+                continue;
+            };
+
+            let document = Html::parse_document(&xhtml);
+            let chapter_blocks = page_blocks(document_body(&document), false);
+            if chapter_blocks.is_empty() {
+                continue;
+            }
+
+            chapter += 1;
+            let name = toc.get(&item.path).cloned().or_else(|| {
+                chapter_blocks
+                    .iter()
+                    .find(|b| b.kind == ChunkType::Heading)
+                    .map(|b| b.text.clone())
+            });
+            blocks.extend(
+                chapter_blocks
+                    .into_iter()
+                    .map(|block| block.in_section(chapter, name.clone())),
+            );
+        }
+
+        Ok(Ebook {
+            blocks,
+            title,
+            author,
+            created_date,
+            language,
+        })
+    }
+}
+
+impl Default for EpubParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ContentParser for EpubParser {
+    async fn parse(&self, path: &Path, config: &ParseConfig) -> Result<ParseResult, ParseError> {
+        if !path.exists() {
+            return Err(ParseError::FileNotFound {
+                path: path.display().to_string(),
+            });
+        }
+
+        // Unzipping and HTML parsing are blocking
+        let path_owned = path.to_path_buf();
+        let parser = Self::new();
+        let ebook = tokio::task::spawn_blocking(move || parser.extract(&path_owned))
+            .await
+            .map_err(|e| ParseError::ParseFailed {
+                reason: format!("Task join error: {}", e),
+            })??;
+
+        let file_id = Uuid::now_v7();
+        let (text, chunks) = create_chunks_from_blocks(file_id, &ebook.blocks, config);
+
+        let title = ebook.title.or_else(|| {
+            ebook
+                .blocks
+                .iter()
+                .find(|b| b.kind == ChunkType::Heading)
+                .map(|b| b.text.clone())
+        });
+
+        let metadata = ParseMetadata {
+            title,
+            author: ebook.author,
+            created_date: ebook.created_date,
+            language: ebook.language,
+            word_count: text.split_whitespace().count(),
+            char_count: text.chars().count(),
+            ..Default::default()
+        };
+
+        Ok(ParseResult {
+            text,
+            chunks,
+            metadata,
+        })
+    }
+
+    fn supports(&self, file_type: FileType) -> bool {
+        matches!(file_type, FileType::TextDocument)
+    }
+
+    fn supported_extensions(&self) -> &[&str] {
+        &self.supported_extensions
+    }
+}
+
+// ============================================================================
+// Table of contents
+// ============================================================================
+
+/// Chapter titles from the EPUB 3 navigation document, by chapter path
+fn read_nav_titles<R: Read + Seek>(
+    package: &mut Package<R>,
+    manifest: &HashMap<String, ManifestItem>,
+) -> Result<HashMap<String, String>, ParseError> {
+    let mut titles = HashMap::new();
+    let Some(nav) = manifest
+        .values()
+        .find(|item| item.properties.split_whitespace().any(|p| p == "nav"))
+    else {
+        return Ok(titles);
+    };
+    let Some(xhtml) = package.read_part(&nav.path)? else {
+        return Ok(titles);
+    };
+
+    let document = Html::parse_document(&xhtml);
+    let navs = Selector::parse("nav").expect("static selector is valid");
+    let links = Selector::parse("a[href]").expect("static selector is valid");
+    // The table of contents, not the landmarks or page list
+    let Some(toc) = document
+        .select(&navs)
+        .find(|nav| nav.value().attr("epub:type").is_some_and(|t| t.contains("toc")))
+        .or_else(|| document.select(&navs).next())
+    else {
+        return Ok(titles);
+    };
+
+    let base_dir = parent_dir(&nav.path);
+    for link in toc.select(&links) {
+        let href = link.value().attr("href").unwrap_or("");
+        let label = link.text().collect::<Vec<_>>().join(" ");
+        let label = label.split_whitespace().collect::<Vec<_>>().join(" ");
+        if label.is_empty() || href.starts_with("http") {
+            continue;
+        }
+        let path = resolve_target(base_dir, &percent_decode(strip_fragment(href)));
+        // A chapter's first entry names it; later ones are its sections
+        titles.entry(path).or_insert(label);
+    }
+    Ok(titles)
+}
+
+/// Chapter titles from an EPUB 2 NCX file, by chapter path
+fn read_ncx_titles<R: Read + Seek>(
+    package: &mut Package<R>,
+    ncx_path: &str,
+) -> Result<HashMap<String, String>, ParseError> {
+    let mut titles = HashMap::new();
+    let Some(xml) = package.read_part(ncx_path)? else {
+        return Ok(titles);
+    };
+    let doc = parse_xml(&xml)?;
+
+    let base_dir = parent_dir(ncx_path);
+    for point in doc.descendants().filter(|n| is(*n, "navPoint")) {
+        let label = children(point, "navLabel")
+            .next()
+            .map(ncx_text)
+            .unwrap_or_default();
+        let Some(src) = children(point, "content").next().and_then(|c| attr(c, "src")) else {
+            continue;
+        };
+        if label.is_empty() {
+            continue;
+        }
+        let path = resolve_target(base_dir, &percent_decode(strip_fragment(src)));
+        titles.entry(path).or_insert(label);
+    }
+    Ok(titles)
+}
+
+fn ncx_text(label: Node<'_, '_>) -> String {
+    children(label, "text")
+        .filter_map(|n| n.text())
+        .collect::<Vec<_>>()
+        .join(" ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// ============================================================================
+// Paths
+// ============================================================================
+
+/// Folder of a part path, without trailing slash
+fn parent_dir(path: &str) -> &str {
+    path.rsplit_once('/').map_or("", |(dir, _)| dir)
+}
+
+fn strip_fragment(href: &str) -> &str {
+    href.split('#').next().unwrap_or(href)
+}
+
+/// Decode `%XX` escapes in a package href
+fn percent_decode(href: &str) -> String {
+    let bytes = href.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
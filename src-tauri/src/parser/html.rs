@@ -0,0 +1,518 @@
+//! HTML parser
+//!
+//! Handles saved web pages (HTML, XHTML and MHTML archives). Only the main
+//! readable content is indexed:
+//! - scripts, styles, navigation, sidebars, footers and similar page
+//!   furniture are dropped by tag, ARIA role and class/id hints
+//! - the article is located by `<article>`/`<main>` markup, or else by
+//!   where most paragraph text sits
+//! - headings become `Heading` chunks, data tables `Table` chunks and
+//!   `<pre>` blocks `CodeBlock` chunks
+//!
+//! Title, author, publication date and language come from page metadata.
+
+use std::path::Path;
+
+use async_trait::async_trait;
+use encoding_rs::Encoding;
+use mail_parser::MessageParser;
+use scraper::{ElementRef, Html, Node, Selector};
+use uuid::Uuid;
+
+use super::office::CELL_SEPARATOR;
+use super::{
+    create_chunks_from_blocks, ContentParser, DocumentBlock, ParseConfig, ParseError, ParseMetadata,
+    ParseResult,
+};
+use crate::core::types::chunk::ChunkType;
+use crate::core::types::file::FileType;
+
+/// Elements that never hold readable text
+const SKIPPED_ELEMENTS: &[&str] = &[
+    "head", "script", "style", "noscript", "template", "svg", "canvas", "iframe", "object",
+    "embed", "button", "input", "select", "textarea", "option", "map",
+];
+
+/// Elements that are page furniture rather than content
+const BOILERPLATE_ELEMENTS: &[&str] = &["nav", "aside", "footer", "form", "menu", "dialog"];
+
+/// ARIA roles of page furniture
+const BOILERPLATE_ROLES: &[&str] = &[
+    "navigation", "banner", "contentinfo", "complementary", "search", "menu", "menubar",
+    "dialog", "alert",
+];
+
+/// Class and id words marking page furniture
+const BOILERPLATE_HINTS: &[&str] = &[
+    "nav", "navbar", "navigation", "menu", "sidebar", "footer", "breadcrumb", "breadcrumbs",
+    "cookie", "cookies", "consent", "banner", "share", "sharing", "social", "comment",
+    "comments", "related", "advert", "advertisement", "ad", "ads", "sponsor", "promo",
+    "subscribe", "newsletter", "popup", "modal", "masthead", "toolbar", "pagination", "skip",
+];
+
+/// Class and id words marking content; they outweigh boilerplate hints
+const CONTENT_HINTS: &[&str] = &[
+    "article", "content", "main", "post", "entry", "body", "story", "text",
+];
+
+/// Elements that start a new block of text
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address", "article", "blockquote", "body", "center", "dd", "details", "div", "dl", "dt",
+    "figure", "header", "hr", "li", "main", "ol", "p", "section", "summary", "ul", "table",
+    "thead", "tbody", "tfoot", "tr", "td", "th",
+];
+
+/// Blocks below this many characters are dropped when mostly link text
+const LINK_BLOCK_MAX_CHARS: usize = 200;
+
+/// Paragraphs shorter than this do not count towards locating the article
+const MIN_PARAGRAPH_CHARS: usize = 25;
+
+/// Article markup with less text than this is ignored (teasers, cards)
+const MIN_ARTICLE_CHARS: usize = 200;
+
+/// Bytes searched for a `charset` declaration
+const CHARSET_SNIFF_BYTES: usize = 1024;
+
+/// Parser for saved web pages (HTML, XHTML, MHTML)
+pub struct HtmlParser {
+    supported_extensions: Vec<&'static str>,
+}
+
+/// Content and metadata of a web page
+struct WebPage {
+    blocks: Vec<DocumentBlock>,
+    title: Option<String>,
+    author: Option<String>,
+    created_date: Option<String>,
+    language: Option<String>,
+}
+
+impl HtmlParser {
+    /// Create a new HTML parser
+    pub fn new() -> Self {
+        Self {
+            supported_extensions: vec!["html", "htm", "xhtml", "mhtml", "mht"],
+        }
+    }
+}
+
+impl Default for HtmlParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ContentParser for HtmlParser {
+    async fn parse(&self, path: &Path, config: &ParseConfig) -> Result<ParseResult, ParseError> {
+        if !path.exists() {
+            return Err(ParseError::FileNotFound {
+                path: path.display().to_string(),
+            });
+        }
+
+        let bytes = tokio::fs::read(path).await?;
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+
+        // DOM building and content scoring are CPU-bound
+        let page = tokio::task::spawn_blocking(move || {
+            let html = match extension.as_str() {
+                "mhtml" | "mht" => mhtml_document(&bytes)?,
+                _ => decode_html(&bytes),
+            };
+            Ok::<_, ParseError>(read_page(&html))
+        })
+        .await
+        .map_err(|e| ParseError::ParseFailed {
+            reason: format!("Task join error: {}", e),
+        })??;
+
+        let file_id = Uuid::now_v7();
+        let (text, chunks) = create_chunks_from_blocks(file_id, &page.blocks, config);
+
+        let title = page.title.or_else(|| {
+            page.blocks
+                .iter()
+                .find(|b| b.kind == ChunkType::Heading)
+                .map(|b| b.text.clone())
+        });
+
+        let metadata = ParseMetadata {
+            title,
+            author: page.author,
+            created_date: page.created_date,
+            language: page.language,
+            word_count: text.split_whitespace().count(),
+            char_count: text.chars().count(),
+            ..Default::default()
+        };
+
+        Ok(ParseResult {
+            text,
+            chunks,
+            metadata,
+        })
+    }
+
+    fn supports(&self, file_type: FileType) -> bool {
+        matches!(file_type, FileType::TextDocument)
+    }
+
+    fn supported_extensions(&self) -> &[&str] {
+        &self.supported_extensions
+    }
+}
+
+// ============================================================================
+// Decoding
+// ============================================================================
+
+/// Decode an HTML file using its byte order mark or `charset` declaration
+fn decode_html(bytes: &[u8]) -> String {
+    let head = &bytes[..bytes.len().min(CHARSET_SNIFF_BYTES)];
+    let encoding = declared_charset(head).unwrap_or(encoding_rs::UTF_8);
+    let (text, _, _) = encoding.decode(bytes);
+    text.into_owned()
+}
+
+fn declared_charset(head: &[u8]) -> Option<&'static Encoding> {
+    let head = String::from_utf8_lossy(head).to_ascii_lowercase();
+    let start = head.find("charset=")? + "charset=".len();
+    let label: String = head[start..]
+        .trim_start_matches(|c| c == '"' || c == '\'')
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.'))
+        .collect();
+    Encoding::for_label(label.as_bytes())
+}
+
+/// The HTML root part of an MHTML archive, decoded
+fn mhtml_document(bytes: &[u8]) -> Result<String, ParseError> {
+    let message = MessageParser::default()
+        .parse(bytes)
+        .ok_or_else(|| ParseError::CorruptedFile {
+            reason: "Not a MIME archive".to_string(),
+        })?;
+    message
+        .body_html(0)
+        .map(|html| html.into_owned())
+        .ok_or_else(|| ParseError::CorruptedFile {
+            reason: "Archive has no HTML part".to_string(),
+        })
+}
+
+// ============================================================================
+// Content extraction
+// ============================================================================
+
+fn selector(query: &str) -> Selector {
+    Selector::parse(query).expect("static selector is valid")
+}
+
+/// Readable content and metadata of a page
+fn read_page(html: &str) -> WebPage {
+    let document = Html::parse_document(html);
+    let blocks = page_blocks(content_root(&document), true);
+
+    let meta = |query: &str| {
+        document
+            .select(&selector(query))
+            .filter_map(|e| e.value().attr("content"))
+            .map(collapse_whitespace)
+            .find(|v| !v.is_empty())
+    };
+    let text_of = |query: &str| {
+        document
+            .select(&selector(query))
+            .map(|e| collapse_whitespace(&e.text().collect::<String>()))
+            .find(|v| !v.is_empty())
+    };
+
+    let title = meta(r#"meta[property="og:title"]"#).or_else(|| text_of("title"));
+    let author = meta(r#"meta[name="author"]"#)
+        .or_else(|| meta(r#"meta[property="article:author"]"#).filter(|a| !a.starts_with("http")))
+        .or_else(|| text_of(r#"[rel="author"]"#));
+    let created_date = meta(r#"meta[property="article:published_time"]"#)
+        .or_else(|| meta(r#"meta[itemprop="datePublished"]"#))
+        .or_else(|| meta(r#"meta[name="date"]"#))
+        .or_else(|| {
+            document
+                .select(&selector("time[datetime]"))
+                .find_map(|e| e.value().attr("datetime"))
+                .map(str::to_string)
+        });
+    let language = document
+        .root_element()
+        .value()
+        .attr("lang")
+        .map(|lang| lang.trim().to_string())
+        .filter(|lang| !lang.is_empty());
+
+    WebPage {
+        blocks,
+        title,
+        author,
+        created_date,
+        language,
+    }
+}
+
+/// The `<body>` of a document, or its root when there is none
+pub(super) fn document_body(document: &Html) -> ElementRef<'_> {
+    document
+        .select(&selector("body"))
+        .next()
+        .unwrap_or_else(|| document.root_element())
+}
+
+/// The element holding the main content of a page
+fn content_root(document: &Html) -> ElementRef<'_> {
+    // Explicit markup first; the longest candidate when a page lists several
+    for query in ["[itemprop=articleBody]", "article", "main, [role=main]"] {
+        let candidate = document
+            .select(&selector(query))
+            .map(|e| (e, text_len(e)))
+            .max_by_key(|(_, len)| *len);
+        if let Some((element, len)) = candidate {
+            if len >= MIN_ARTICLE_CHARS {
+                return element;
+            }
+        }
+    }
+
+    // Otherwise the container of most paragraph text, half of it credited
+    // to the grandparent so sibling wrappers add up
+    let mut scores: Vec<(ElementRef<'_>, usize)> = Vec::new();
+    let mut credit = |element: ElementRef<'_>, score: usize| {
+        match scores.iter_mut().find(|(e, _)| *e == element) {
+            Some((_, total)) => *total += score,
+            None => scores.push((element, score)),
+        }
+    };
+    for paragraph in document.select(&selector("p")) {
+        let len = text_len(paragraph);
+        if len < MIN_PARAGRAPH_CHARS {
+            continue;
+        }
+        let Some(parent) = paragraph.parent().and_then(ElementRef::wrap) else {
+            continue;
+        };
+        credit(parent, len);
+        if let Some(grandparent) = parent.parent().and_then(ElementRef::wrap) {
+            credit(grandparent, len / 2);
+        }
+    }
+
+    scores
+        .into_iter()
+        .max_by_key(|(_, score)| *score)
+        .map(|(element, _)| element)
+        .unwrap_or_else(|| document_body(document))
+}
+
+fn text_len(element: ElementRef<'_>) -> usize {
+    element.text().map(|t| t.trim().len()).sum()
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Text blocks of an element in reading order
+///
+/// With `readable` set, boilerplate elements and short link-heavy blocks
+/// are dropped; ebook chapters are taken whole.
+pub(super) fn page_blocks(root: ElementRef<'_>, readable: bool) -> Vec<DocumentBlock> {
+    let mut extractor = Extractor {
+        readable,
+        blocks: Vec::new(),
+        current: String::new(),
+        link_chars: 0,
+        link_depth: 0,
+    };
+    extractor.walk(root);
+    extractor.flush(ChunkType::Paragraph);
+    extractor.blocks
+}
+
+struct Extractor {
+    readable: bool,
+    blocks: Vec<DocumentBlock>,
+    /// Text of the block being collected
+    current: String,
+    /// Characters of `current` inside links
+    link_chars: usize,
+    link_depth: usize,
+}
+
+impl Extractor {
+    fn walk(&mut self, element: ElementRef<'_>) {
+        for child in element.children() {
+            match child.value() {
+                Node::Text(text) => self.push_text(text),
+                Node::Element(_) => {
+                    if let Some(child) = ElementRef::wrap(child) {
+                        self.element(child);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn element(&mut self, element: ElementRef<'_>) {
+        let name = element.value().name();
+        if SKIPPED_ELEMENTS.contains(&name)
+            || is_hidden(element)
+            || (self.readable && is_boilerplate(element))
+        {
+            return;
+        }
+
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.flush(ChunkType::Paragraph);
+                let text = collapse_whitespace(&element.text().collect::<String>());
+                if !text.is_empty() {
+                    self.blocks.push(DocumentBlock::new(ChunkType::Heading, text));
+                }
+            }
+            "pre" => {
+                self.flush(ChunkType::Paragraph);
+                let text: String = element.text().collect();
+                let text = text.trim_matches('\n').trim_end();
+                if !text.trim().is_empty() {
+                    self.blocks.push(DocumentBlock::new(ChunkType::CodeBlock, text));
+                }
+            }
+            "table" if is_data_table(element) => {
+                self.flush(ChunkType::Paragraph);
+                let text = table_text(element);
+                if !text.is_empty() {
+                    self.blocks.push(DocumentBlock::new(ChunkType::Table, text));
+                }
+            }
+            "figcaption" | "caption" => {
+                self.flush(ChunkType::Paragraph);
+                self.walk(element);
+                self.flush(ChunkType::Caption);
+            }
+            "br" => self.current.push('\n'),
+            "a" => {
+                self.link_depth += 1;
+                self.walk(element);
+                self.link_depth -= 1;
+            }
+            name if BLOCK_ELEMENTS.contains(&name) => {
+                self.flush(ChunkType::Paragraph);
+                self.walk(element);
+                self.flush(ChunkType::Paragraph);
+            }
+            _ => self.walk(element),
+        }
+    }
+
+    /// Append source text, collapsing whitespace as a browser would
+    fn push_text(&mut self, text: &str) {
+        for c in text.chars() {
+            if !c.is_whitespace() {
+                self.current.push(c);
+                if self.link_depth > 0 {
+                    self.link_chars += 1;
+                }
+            } else if !matches!(self.current.chars().last(), None | Some(' ') | Some('\n')) {
+                self.current.push(' ');
+            }
+        }
+    }
+
+    /// End the current block
+    fn flush(&mut self, kind: ChunkType) {
+        let text = std::mem::take(&mut self.current);
+        let link_chars = std::mem::take(&mut self.link_chars);
+
+        let text = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+        if text.is_empty() {
+            return;
+        }
+
+        // Link lists and "read more" lines
+        let chars = text.chars().filter(|c| !c.is_whitespace()).count();
+        if self.readable && chars < LINK_BLOCK_MAX_CHARS && link_chars * 2 > chars {
+            return;
+        }
+        self.blocks.push(DocumentBlock::new(kind, text));
+    }
+}
+
+fn is_hidden(element: ElementRef<'_>) -> bool {
+    let el = element.value();
+    el.attr("hidden").is_some()
+        || el.attr("aria-hidden") == Some("true")
+        || el
+            .attr("style")
+            .is_some_and(|style| style.replace(' ', "").to_ascii_lowercase().contains("display:none"))
+}
+
+fn is_boilerplate(element: ElementRef<'_>) -> bool {
+    let el = element.value();
+    let name = el.name();
+    if BOILERPLATE_ELEMENTS.contains(&name) {
+        return true;
+    }
+    // Page headers hold the logo and menu, article headers the title
+    if name == "header" && element.select(&selector("h1, h2, h3")).next().is_none() {
+        return true;
+    }
+    if el.attr("role").is_some_and(|role| BOILERPLATE_ROLES.contains(&role.trim())) {
+        return true;
+    }
+
+    let words: Vec<String> = el
+        .attr("class")
+        .into_iter()
+        .chain(el.attr("id"))
+        .flat_map(|value| value.split(|c: char| !c.is_ascii_alphanumeric()))
+        .map(|word| word.to_ascii_lowercase())
+        .collect();
+    let hinted = |hints: &[&str]| words.iter().any(|w| hints.contains(&w.as_str()));
+    hinted(BOILERPLATE_HINTS) && !hinted(CONTENT_HINTS)
+}
+
+/// Whether a table holds data rather than page layout
+fn is_data_table(table: ElementRef<'_>) -> bool {
+    !table.descendants().skip(1).filter_map(ElementRef::wrap).any(|e| {
+        matches!(
+            e.value().name(),
+            "table" | "div" | "p" | "ul" | "ol" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6"
+        )
+    })
+}
+
+fn table_text(table: ElementRef<'_>) -> String {
+    table
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .filter(|e| e.value().name() == "tr")
+        .filter_map(|row| {
+            let cells: Vec<String> = row
+                .children()
+                .filter_map(ElementRef::wrap)
+                .filter(|cell| matches!(cell.value().name(), "td" | "th"))
+                .map(|cell| collapse_whitespace(&cell.text().collect::<String>()))
+                .collect();
+            cells.iter().any(|c| !c.is_empty()).then(|| cells.join(CELL_SEPARATOR))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
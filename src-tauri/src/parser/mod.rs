@@ -7,16 +7,40 @@
 //! - Text files (TXT, MD, JSON)
 //! - PDF documents
 //! - Code files with syntax analysis
+//! - Office Open XML documents (DOCX, XLSX, PPTX)
+//! - OpenDocument files (ODT, ODS, ODP)
+//! - RTF documents
+//! - Saved web pages (HTML, MHTML) and EPUB ebooks
+//! - Email messages (EML) and mbox archives
+//! - Zip and tar archives, whose members are parsed recursively
+//! - Photo metadata (EXIF, XMP and dimensions of JPEG, PNG, TIFF, WebP)
 
 mod text;
 mod pdf;
 mod code;
+mod package;
+mod office;
+mod opendocument;
+mod rtf;
+mod html;
+mod epub;
+mod email;
+mod archive;
+mod photo;
 #[cfg(test)]
 mod tests;
 
 pub use text::TextParser;
 pub use pdf::PdfParser;
 pub use code::CodeParser;
+pub use office::OfficeParser;
+pub use opendocument::OpenDocumentParser;
+pub use rtf::RtfParser;
+pub use html::HtmlParser;
+pub use epub::EpubParser;
+pub use email::EmailParser;
+pub use archive::ArchiveParser;
+pub use photo::{GpsPosition, ImageMetadata, ImageMetadataParser};
 
 use crate::core::types::chunk::{ChunkLocation, ChunkType, ContentChunk};
 use crate::core::types::file::FileType;
@@ -76,6 +100,8 @@ pub struct ParseMetadata {
     pub word_count: usize,
     /// Character count
     pub char_count: usize,
+    /// Photo metadata (for images)
+    pub image: Option<ImageMetadata>,
 }
 
 /// Configuration for content parsing
@@ -123,6 +149,14 @@ pub struct ContentParserService {
     text_parser: TextParser,
     pdf_parser: PdfParser,
     code_parser: CodeParser,
+    office_parser: OfficeParser,
+    opendocument_parser: OpenDocumentParser,
+    rtf_parser: RtfParser,
+    html_parser: HtmlParser,
+    epub_parser: EpubParser,
+    email_parser: EmailParser,
+    archive_parser: ArchiveParser,
+    image_parser: ImageMetadataParser,
     config: ParseConfig,
 }
 
@@ -138,46 +172,64 @@ impl ContentParserService {
             text_parser: TextParser::new(),
             pdf_parser: PdfParser::new(),
             code_parser: CodeParser::new(),
+            office_parser: OfficeParser::new(),
+            opendocument_parser: OpenDocumentParser::new(),
+            rtf_parser: RtfParser::new(),
+            html_parser: HtmlParser::new(),
+            epub_parser: EpubParser::new(),
+            email_parser: EmailParser::new(),
+            archive_parser: ArchiveParser::new(),
+            image_parser: ImageMetadataParser::new(),
             config,
         }
     }
 
     /// Parse a file based on its type
     pub async fn parse(&self, path: &Path) -> Result<ParseResult, ParseError> {
+        self.select_parser(path)?.parse(path, &self.config).await
+    }
+
+    /// Parse with custom config
+    pub async fn parse_with_config(
+        &self,
+        path: &Path,
+        config: &ParseConfig,
+    ) -> Result<ParseResult, ParseError> {
+        self.select_parser(path)?.parse(path, config).await
+    }
+
+    /// Check if a file type is supported
+    pub fn is_supported(&self, path: &Path) -> bool {
         let extension = path
             .extension()
             .and_then(|e| e.to_str())
             .unwrap_or("")
             .to_lowercase();
 
-        let file_type = FileType::from_extension(&extension);
-
-        // Select appropriate parser
-        let parser: &dyn ContentParser = match file_type {
-            FileType::TextDocument => &self.text_parser,
-            FileType::Pdf => &self.pdf_parser,
-            FileType::Code => &self.code_parser,
-            _ => {
-                // Try text parser as fallback for unknown types
-                if self.text_parser.supported_extensions().contains(&extension.as_str()) {
-                    &self.text_parser
-                } else if self.code_parser.supported_extensions().contains(&extension.as_str()) {
-                    &self.code_parser
-                } else {
-                    return Err(ParseError::UnsupportedFileType { extension });
-                }
-            }
-        };
+        self.parsers()
+            .iter()
+            .any(|parser| parser.supported_extensions().contains(&extension.as_str()))
+    }
 
-        parser.parse(path, &self.config).await
+    /// All format-specific parsers
+    fn parsers(&self) -> [&dyn ContentParser; 11] {
+        [
+            &self.text_parser,
+            &self.pdf_parser,
+            &self.code_parser,
+            &self.office_parser,
+            &self.opendocument_parser,
+            &self.rtf_parser,
+            &self.html_parser,
+            &self.epub_parser,
+            &self.email_parser,
+            &self.archive_parser,
+            &self.image_parser,
+        ]
     }
 
-    /// Parse with custom config
-    pub async fn parse_with_config(
-        &self,
-        path: &Path,
-        config: &ParseConfig,
-    ) -> Result<ParseResult, ParseError> {
+    /// Select the parser for a file based on its extension
+    fn select_parser(&self, path: &Path) -> Result<&dyn ContentParser, ParseError> {
         let extension = path
             .extension()
             .and_then(|e| e.to_str())
@@ -186,11 +238,20 @@ impl ContentParserService {
 
         let file_type = FileType::from_extension(&extension);
 
+        // Formats sharing a file type are told apart by extension
+        let format_parser = self.parsers().into_iter().find(|parser| {
+            parser.supports(file_type) && parser.supported_extensions().contains(&extension.as_str())
+        });
+        if let Some(parser) = format_parser {
+            return Ok(parser);
+        }
+
         let parser: &dyn ContentParser = match file_type {
             FileType::TextDocument => &self.text_parser,
             FileType::Pdf => &self.pdf_parser,
             FileType::Code => &self.code_parser,
             _ => {
+                // Try text parser as fallback for unknown types
                 if self.text_parser.supported_extensions().contains(&extension.as_str()) {
                     &self.text_parser
                 } else if self.code_parser.supported_extensions().contains(&extension.as_str()) {
@@ -201,20 +262,7 @@ impl ContentParserService {
             }
         };
 
-        parser.parse(path, config).await
-    }
-
-    /// Check if a file type is supported
-    pub fn is_supported(&self, path: &Path) -> bool {
-        let extension = path
-            .extension()
-            .and_then(|e| e.to_str())
-            .unwrap_or("")
-            .to_lowercase();
-
-        self.text_parser.supported_extensions().contains(&extension.as_str())
-            || self.pdf_parser.supported_extensions().contains(&extension.as_str())
-            || self.code_parser.supported_extensions().contains(&extension.as_str())
+        Ok(parser)
     }
 }
 
@@ -254,6 +302,9 @@ pub fn create_chunks_from_text(
                 end_line: Some(line_count as u32),
                 page_number: None,
                 bounding_box: None,
+                section: None,
+                section_name: None,
+                member: None,
             },
             vector_id: 0,
             created_at: Utc::now(),
@@ -288,6 +339,9 @@ pub fn create_chunks_from_text(
                 end_line: Some(current_line + chunk_lines.saturating_sub(1) as u32),
                 page_number: None,
                 bounding_box: None,
+                section: None,
+                section_name: None,
+                member: None,
             },
             vector_id: 0,
             created_at: Utc::now(),
@@ -306,6 +360,101 @@ pub fn create_chunks_from_text(
     chunks
 }
 
+/// A structural block of a document, before chunking
+///
+/// Parsers for structured formats emit blocks in reading order; headings,
+/// tables and slides keep their own chunks while consecutive paragraphs of
+/// the same section are merged up to the chunk size.
+#[derive(Debug, Clone)]
+pub struct DocumentBlock {
+    /// Chunk type of the block
+    pub kind: ChunkType,
+    /// Block text
+    pub text: String,
+    /// Slide, sheet or other section (1-based)
+    pub section: Option<u32>,
+    /// Section name (sheet name, slide title)
+    pub section_name: Option<String>,
+}
+
+impl DocumentBlock {
+    /// Create a block outside any section
+    pub fn new(kind: ChunkType, text: impl Into<String>) -> Self {
+        Self {
+            kind,
+            text: text.into(),
+            section: None,
+            section_name: None,
+        }
+    }
+
+    /// Place the block in a section
+    pub fn in_section(mut self, section: u32, name: Option<String>) -> Self {
+        self.section = Some(section);
+        self.section_name = name;
+        self
+    }
+}
+
+/// Helper function to create chunks from document blocks
+///
+/// Returns the full document text (blocks separated by blank lines) and the
+/// chunks, whose offsets point into that text.
+pub fn create_chunks_from_blocks(
+    file_id: Uuid,
+    blocks: &[DocumentBlock],
+    config: &ParseConfig,
+) -> (String, Vec<ContentChunk>) {
+    let mut text = String::new();
+    let mut chunks = Vec::new();
+
+    // Group blocks into chunk-sized runs: (first block, last block, start offset)
+    let mut runs: Vec<(usize, usize, usize)> = Vec::new();
+    for (index, block) in blocks.iter().enumerate() {
+        let block_text = block.text.trim();
+        if block_text.is_empty() {
+            continue;
+        }
+        if !text.is_empty() {
+            text.push_str("\n\n");
+        }
+        let start = text.len();
+        text.push_str(block_text);
+
+        let merges = runs.last().is_some_and(|&(first, last, run_start)| {
+            let previous = &blocks[last];
+            block.kind == ChunkType::Paragraph
+                && previous.kind == ChunkType::Paragraph
+                && blocks[first].section == block.section
+                && text.len() - run_start <= config.max_chunk_size
+        });
+        if merges {
+            runs.last_mut().unwrap().1 = index;
+        } else {
+            runs.push((index, index, start));
+        }
+    }
+
+    let mut run_ends: Vec<usize> = runs.iter().skip(1).map(|&(_, _, start)| start - 2).collect();
+    run_ends.push(text.len());
+
+    for (&(first, _, start), end) in runs.iter().zip(run_ends) {
+        let block = &blocks[first];
+        for mut chunk in create_chunks_from_text(file_id, &text[start..end], config, block.kind) {
+            chunk.chunk_index = chunks.len() as u32;
+            chunk.location.start_offset += start as u64;
+            chunk.location.end_offset += start as u64;
+            chunk.location.start_line = None;
+            chunk.location.end_line = None;
+            chunk.location.section = block.section;
+            chunk.location.section_name = block.section_name.clone();
+            chunks.push(chunk);
+        }
+    }
+
+    (text, chunks)
+}
+
 /// Find a good break point for chunking (paragraph or sentence boundary)
 fn find_break_point(text: &str, start: usize, max_end: usize, min_size: usize) -> usize {
     let search_text = &text[start..max_end];
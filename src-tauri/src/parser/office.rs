@@ -0,0 +1,492 @@
+//! Office Open XML parser
+//!
+//! Handles DOCX, XLSX and PPTX documents by reading their XML parts:
+//! - DOCX: paragraphs (headings by style or outline level) and tables
+//! - XLSX: one table per worksheet, located by sheet index and name
+//! - PPTX: slide titles and text in presentation order, located by slide index
+//!
+//! Title, author and creation date come from `docProps/core.xml`.
+
+use std::collections::HashMap;
+use std::io::{Read, Seek};
+use std::path::Path;
+
+use async_trait::async_trait;
+use roxmltree::Node;
+use uuid::Uuid;
+
+use super::package::{
+    attr, children, descendant_text, is, parse_xml, relationship_id, resolve_target, Package,
+};
+use super::{
+    create_chunks_from_blocks, ContentParser, DocumentBlock, ParseConfig, ParseError, ParseMetadata,
+    ParseResult,
+};
+use crate::core::types::chunk::ChunkType;
+use crate::core::types::file::FileType;
+
+/// Rows read per worksheet; larger sheets are truncated
+pub(super) const MAX_SHEET_ROWS: usize = 10_000;
+
+/// Separator between table cells in extracted text
+pub(super) const CELL_SEPARATOR: &str = " | ";
+
+/// Parser for Office Open XML documents (DOCX, XLSX, PPTX)
+pub struct OfficeParser {
+    supported_extensions: Vec<&'static str>,
+}
+
+/// Content extracted from a package, before chunking
+struct OfficeContent {
+    blocks: Vec<DocumentBlock>,
+    title: Option<String>,
+    author: Option<String>,
+    created_date: Option<String>,
+    page_count: Option<u32>,
+}
+
+impl OfficeParser {
+    /// Create a new Office parser
+    pub fn new() -> Self {
+        Self {
+            supported_extensions: vec!["docx", "xlsx", "pptx"],
+        }
+    }
+
+    /// Read the package at `path` according to its extension
+    fn extract(&self, path: &Path) -> Result<OfficeContent, ParseError> {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+
+        let mut package = Package::open(path)?;
+        let (blocks, page_count) = match extension.as_str() {
+            "docx" => (read_docx(&mut package)?, app_property(&mut package, "Pages")?),
+            "xlsx" => (read_xlsx(&mut package)?, None),
+            "pptx" => {
+                let blocks = read_pptx(&mut package)?;
+                let slides = blocks.iter().filter_map(|b| b.section).max();
+                (blocks, app_property(&mut package, "Slides")?.or(slides))
+            }
+            _ => return Err(ParseError::UnsupportedFileType { extension }),
+        };
+
+        let mut content = OfficeContent {
+            blocks,
+            title: None,
+            author: None,
+            created_date: None,
+            page_count,
+        };
+        read_core_properties(&mut package, &mut content)?;
+        Ok(content)
+    }
+}
+
+impl Default for OfficeParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ContentParser for OfficeParser {
+    async fn parse(&self, path: &Path, config: &ParseConfig) -> Result<ParseResult, ParseError> {
+        if !path.exists() {
+            return Err(ParseError::FileNotFound {
+                path: path.display().to_string(),
+            });
+        }
+
+        // Unzipping and XML parsing are blocking
+        let path_owned = path.to_path_buf();
+        let parser = Self::new();
+        let content = tokio::task::spawn_blocking(move || parser.extract(&path_owned))
+            .await
+            .map_err(|e| ParseError::ParseFailed {
+                reason: format!("Task join error: {}", e),
+            })??;
+
+        let file_id = Uuid::now_v7();
+        let (text, chunks) = create_chunks_from_blocks(file_id, &content.blocks, config);
+
+        // Fall back to the first heading when the properties carry no title
+        let title = content.title.or_else(|| {
+            content
+                .blocks
+                .iter()
+                .find(|b| b.kind == ChunkType::Heading)
+                .map(|b| b.text.clone())
+        });
+
+        let metadata = ParseMetadata {
+            title,
+            author: content.author,
+            created_date: content.created_date,
+            page_count: content.page_count,
+            word_count: text.split_whitespace().count(),
+            char_count: text.chars().count(),
+            ..Default::default()
+        };
+
+        Ok(ParseResult {
+            text,
+            chunks,
+            metadata,
+        })
+    }
+
+    fn supports(&self, file_type: FileType) -> bool {
+        matches!(file_type, FileType::OfficeDocument)
+    }
+
+    fn supported_extensions(&self) -> &[&str] {
+        &self.supported_extensions
+    }
+}
+
+// ============================================================================
+// Document properties
+// ============================================================================
+
+/// Fill title, author and creation date from `docProps/core.xml`
+fn read_core_properties<R: Read + Seek>(
+    package: &mut Package<R>,
+    content: &mut OfficeContent,
+) -> Result<(), ParseError> {
+    let Some(xml) = package.read_part("docProps/core.xml")? else {
+        return Ok(());
+    };
+    let doc = parse_xml(&xml)?;
+
+    let property = |name: &str| {
+        children(doc.root_element(), name)
+            .next()
+            .and_then(|n| n.text())
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+    };
+    content.title = property("title");
+    content.author = property("creator");
+    content.created_date = property("created");
+    Ok(())
+}
+
+/// Numeric statistic from `docProps/app.xml` (e.g. `Pages`, `Slides`)
+fn app_property<R: Read + Seek>(package: &mut Package<R>, name: &str) -> Result<Option<u32>, ParseError> {
+    let Some(xml) = package.read_part("docProps/app.xml")? else {
+        return Ok(None);
+    };
+    let doc = parse_xml(&xml)?;
+    let value = children(doc.root_element(), name)
+        .next()
+        .and_then(|n| n.text())
+        .and_then(|t| t.trim().parse().ok());
+    Ok(value)
+}
+
+/// Relationship targets of a part, by relationship ID
+fn relationships<R: Read + Seek>(
+    package: &mut Package<R>,
+    rels_part: &str,
+    base_dir: &str,
+) -> Result<HashMap<String, String>, ParseError> {
+    let Some(xml) = package.read_part(rels_part)? else {
+        return Ok(HashMap::new());
+    };
+    let doc = parse_xml(&xml)?;
+    Ok(children(doc.root_element(), "Relationship")
+        .filter_map(|rel| {
+            let id = attr(rel, "Id")?;
+            let target = attr(rel, "Target")?;
+            Some((id.to_string(), resolve_target(base_dir, target)))
+        })
+        .collect())
+}
+
+// ============================================================================
+// DOCX
+// ============================================================================
+
+/// Paragraphs and tables of `word/document.xml` in document order
+fn read_docx<R: Read + Seek>(package: &mut Package<R>) -> Result<Vec<DocumentBlock>, ParseError> {
+    let heading_styles = match package.read_part("word/styles.xml")? {
+        Some(xml) => docx_heading_styles(&parse_xml(&xml)?),
+        None => Vec::new(),
+    };
+
+    let xml = package.require_part("word/document.xml")?;
+    let doc = parse_xml(&xml)?;
+    let body = doc
+        .root_element()
+        .children()
+        .find(|n| is(*n, "body"))
+        .ok_or_else(|| ParseError::CorruptedFile {
+            reason: "Document has no body".to_string(),
+        })?;
+
+    let mut blocks = Vec::new();
+    docx_collect(body, &heading_styles, &mut blocks);
+    Ok(blocks)
+}
+
+/// IDs of paragraph styles that mark headings
+///
+/// Built-in heading styles are named "heading N" or "Title" in every UI
+/// language; custom styles are headings when they set an outline level.
+fn docx_heading_styles(styles: &roxmltree::Document<'_>) -> Vec<String> {
+    children(styles.root_element(), "style")
+        .filter(|style| {
+            let name = children(*style, "name")
+                .next()
+                .and_then(|n| attr(n, "val"))
+                .unwrap_or("")
+                .to_lowercase();
+            let outline = children(*style, "pPr").any(|ppr| children(ppr, "outlineLvl").next().is_some());
+            name.starts_with("heading") || name == "title" || name == "subtitle" || outline
+        })
+        .filter_map(|style| attr(style, "styleId").map(str::to_string))
+        .collect()
+}
+
+fn docx_collect(node: Node<'_, '_>, heading_styles: &[String], blocks: &mut Vec<DocumentBlock>) {
+    for child in node.children().filter(|n| n.is_element()) {
+        match child.tag_name().name() {
+            "p" => {
+                let text = docx_paragraph_text(child);
+                if text.trim().is_empty() {
+                    continue;
+                }
+                let kind = if docx_is_heading(child, heading_styles) {
+                    ChunkType::Heading
+                } else {
+                    ChunkType::Paragraph
+                };
+                blocks.push(DocumentBlock::new(kind, text));
+            }
+            "tbl" => {
+                let text = docx_table_text(child);
+                if !text.trim().is_empty() {
+                    blocks.push(DocumentBlock::new(ChunkType::Table, text));
+                }
+            }
+            // Content controls and inserted revisions wrap ordinary paragraphs
+            "sdt" | "sdtContent" | "ins" | "customXml" => docx_collect(child, heading_styles, blocks),
+            _ => {}
+        }
+    }
+}
+
+fn docx_is_heading(paragraph: Node<'_, '_>, heading_styles: &[String]) -> bool {
+    children(paragraph, "pPr").any(|ppr| {
+        children(ppr, "outlineLvl").next().is_some()
+            || children(ppr, "pStyle")
+                .filter_map(|style| attr(style, "val"))
+                .any(|id| heading_styles.iter().any(|h| h == id))
+    })
+}
+
+fn docx_paragraph_text(paragraph: Node<'_, '_>) -> String {
+    let mut text = String::new();
+    for node in paragraph.descendants().filter(|n| n.is_element()) {
+        match node.tag_name().name() {
+            "t" => text.push_str(node.text().unwrap_or("")),
+            "tab" if !node.ancestors().any(|a| is(a, "pPr")) => text.push('\t'),
+            "br" | "cr" => text.push('\n'),
+            _ => {}
+        }
+    }
+    text
+}
+
+fn docx_table_text(table: Node<'_, '_>) -> String {
+    children(table, "tr")
+        .map(|row| {
+            children(row, "tc")
+                .map(|cell| {
+                    cell.descendants()
+                        .filter(|n| is(*n, "p"))
+                        .map(docx_paragraph_text)
+                        .filter(|t| !t.trim().is_empty())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect::<Vec<_>>()
+                .join(CELL_SEPARATOR)
+        })
+        .filter(|row| !row.replace(CELL_SEPARATOR, "").trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// ============================================================================
+// XLSX
+// ============================================================================
+
+/// One heading and one table per worksheet, in workbook order
+fn read_xlsx<R: Read + Seek>(package: &mut Package<R>) -> Result<Vec<DocumentBlock>, ParseError> {
+    let shared_strings = match package.read_part("xl/sharedStrings.xml")? {
+        Some(xml) => xlsx_shared_strings(&parse_xml(&xml)?),
+        None => Vec::new(),
+    };
+    let targets = relationships(package, "xl/_rels/workbook.xml.rels", "xl")?;
+
+    let workbook_xml = package.require_part("xl/workbook.xml")?;
+    let workbook = parse_xml(&workbook_xml)?;
+    let sheets: Vec<(String, Option<String>)> = workbook
+        .descendants()
+        .filter(|n| is(*n, "sheet"))
+        .map(|sheet| {
+            let name = attr(sheet, "name").unwrap_or("").to_string();
+            let target = relationship_id(sheet).and_then(|id| targets.get(id).cloned());
+            (name, target)
+        })
+        .collect();
+
+    let mut blocks = Vec::new();
+    for (index, (name, target)) in sheets.into_iter().enumerate() {
+        let section = index as u32 + 1;
+        let section_name = (!name.is_empty()).then(|| name.clone());
+        let Some(xml) = target.map(|t| package.read_part(&t)).transpose()?.flatten() else {
+            continue;
+        };
+
+        let rows = xlsx_sheet_rows(&parse_xml(&xml)?, &shared_strings);
+        if rows.is_empty() {
+            continue;
+        }
+        if !name.is_empty() {
+            blocks.push(DocumentBlock::new(ChunkType::Heading, name).in_section(section, section_name.clone()));
+        }
+        blocks.push(DocumentBlock::new(ChunkType::Table, rows.join("\n")).in_section(section, section_name));
+    }
+    Ok(blocks)
+}
+
+/// Shared string table; phonetic runs are left out
+fn xlsx_shared_strings(doc: &roxmltree::Document<'_>) -> Vec<String> {
+    children(doc.root_element(), "si")
+        .map(|item| {
+            item.descendants()
+                .filter(|n| is(*n, "t") && !n.parent().map(|p| is(p, "rPh")).unwrap_or(false))
+                .filter_map(|n| n.text())
+                .collect()
+        })
+        .collect()
+}
+
+/// Non-empty rows of a worksheet with their cell values
+fn xlsx_sheet_rows(sheet: &roxmltree::Document<'_>, shared_strings: &[String]) -> Vec<String> {
+    sheet
+        .descendants()
+        .filter(|n| is(*n, "row"))
+        .take(MAX_SHEET_ROWS)
+        .filter_map(|row| {
+            let cells: Vec<String> = children(row, "c")
+                .filter_map(|cell| xlsx_cell_value(cell, shared_strings))
+                .filter(|v| !v.trim().is_empty())
+                .collect();
+            (!cells.is_empty()).then(|| cells.join(CELL_SEPARATOR))
+        })
+        .collect()
+}
+
+fn xlsx_cell_value(cell: Node<'_, '_>, shared_strings: &[String]) -> Option<String> {
+    let value = || children(cell, "v").next().and_then(|v| v.text()).map(str::trim);
+    match attr(cell, "t") {
+        Some("s") => value()?
+            .parse::<usize>()
+            .ok()
+            .and_then(|i| shared_strings.get(i).cloned()),
+        Some("inlineStr") => Some(descendant_text(cell, "t")),
+        Some("b") => Some(if value()? == "1" { "TRUE" } else { "FALSE" }.to_string()),
+        _ => value().map(str::to_string),
+    }
+}
+
+// ============================================================================
+// PPTX
+// ============================================================================
+
+/// Title, text and tables of each slide, in presentation order
+fn read_pptx<R: Read + Seek>(package: &mut Package<R>) -> Result<Vec<DocumentBlock>, ParseError> {
+    let targets = relationships(package, "ppt/_rels/presentation.xml.rels", "ppt")?;
+    let presentation_xml = package.require_part("ppt/presentation.xml")?;
+    let presentation = parse_xml(&presentation_xml)?;
+    let slides: Vec<String> = presentation
+        .descendants()
+        .filter(|n| is(*n, "sldId"))
+        .filter_map(|slide| relationship_id(slide).and_then(|id| targets.get(id).cloned()))
+        .collect();
+
+    let mut blocks = Vec::new();
+    for (index, target) in slides.iter().enumerate() {
+        let Some(xml) = package.read_part(target)? else {
+            continue;
+        };
+        let slide = parse_xml(&xml)?;
+        blocks.extend(pptx_slide_blocks(&slide, index as u32 + 1));
+    }
+    Ok(blocks)
+}
+
+fn pptx_slide_blocks(slide: &roxmltree::Document<'_>, section: u32) -> Vec<DocumentBlock> {
+    let mut title = None;
+    let mut body = Vec::new();
+    for shape in slide.descendants().filter(|n| is(*n, "sp")) {
+        let text = pptx_text_body(shape);
+        if text.trim().is_empty() {
+            continue;
+        }
+        let is_title = shape
+            .descendants()
+            .filter(|n| is(*n, "ph"))
+            .any(|ph| matches!(attr(ph, "type"), Some("title") | Some("ctrTitle")));
+        if is_title && title.is_none() {
+            title = Some(text.replace('\n', " "));
+        } else {
+            body.push(text);
+        }
+    }
+
+    let mut blocks = Vec::new();
+    if let Some(title) = &title {
+        blocks.push(DocumentBlock::new(ChunkType::Heading, title.clone()).in_section(section, Some(title.clone())));
+    }
+    if !body.is_empty() {
+        blocks.push(DocumentBlock::new(ChunkType::Slide, body.join("\n")).in_section(section, title.clone()));
+    }
+    for table in slide.descendants().filter(|n| is(*n, "tbl")) {
+        let text = children(table, "tr")
+            .map(|row| {
+                children(row, "tc")
+                    .map(|cell| pptx_text_body(cell).replace('\n', " "))
+                    .collect::<Vec<_>>()
+                    .join(CELL_SEPARATOR)
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        if !text.replace(CELL_SEPARATOR, "").trim().is_empty() {
+            blocks.push(DocumentBlock::new(ChunkType::Table, text).in_section(section, title.clone()));
+        }
+    }
+    blocks
+}
+
+/// Paragraphs of a shape or table cell, one per line
+fn pptx_text_body(node: Node<'_, '_>) -> String {
+    node.descendants()
+        .filter(|n| is(*n, "txBody"))
+        .flat_map(|body| children(body, "p"))
+        .map(|p| {
+            p.descendants()
+                .filter(|n| is(*n, "t") || is(*n, "br"))
+                .map(|n| if is(n, "br") { "\n" } else { n.text().unwrap_or("") })
+                .collect::<String>()
+        })
+        .filter(|p| !p.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,365 @@
+//! OpenDocument parser
+//!
+//! Handles ODT, ODS and ODP documents by reading `content.xml`:
+//! - ODT: headings, paragraphs (including list items and sections) and tables
+//! - ODS: one table per sheet, located by sheet index and name
+//! - ODP: page titles and text in order, located by page index
+//!
+//! Title, author, creation date and page count come from `meta.xml`.
+
+use std::io::{Read, Seek};
+use std::path::Path;
+
+use async_trait::async_trait;
+use roxmltree::Node;
+use uuid::Uuid;
+
+use super::office::{CELL_SEPARATOR, MAX_SHEET_ROWS};
+use super::package::{attr, children, is, parse_xml, Package};
+use super::{
+    create_chunks_from_blocks, ContentParser, DocumentBlock, ParseConfig, ParseError, ParseMetadata,
+    ParseResult,
+};
+use crate::core::types::chunk::ChunkType;
+use crate::core::types::file::FileType;
+
+/// Longest run of spaces expanded from a `text:s` element
+const MAX_SPACE_RUN: usize = 64;
+
+/// Parser for OpenDocument files (ODT, ODS, ODP)
+pub struct OpenDocumentParser {
+    supported_extensions: Vec<&'static str>,
+}
+
+/// Content extracted from a package, before chunking
+struct OpenDocumentContent {
+    blocks: Vec<DocumentBlock>,
+    title: Option<String>,
+    author: Option<String>,
+    created_date: Option<String>,
+    page_count: Option<u32>,
+}
+
+impl OpenDocumentParser {
+    /// Create a new OpenDocument parser
+    pub fn new() -> Self {
+        Self {
+            supported_extensions: vec!["odt", "ods", "odp"],
+        }
+    }
+
+    /// Read the package at `path`
+    fn extract(&self, path: &Path) -> Result<OpenDocumentContent, ParseError> {
+        let mut package = Package::open(path)?;
+
+        let xml = package.require_part("content.xml")?;
+        let doc = parse_xml(&xml)?;
+        let body = doc
+            .root_element()
+            .children()
+            .find(|n| is(*n, "body"))
+            .and_then(|body| body.children().find(|n| n.is_element()))
+            .ok_or_else(|| ParseError::CorruptedFile {
+                reason: "Document has no body".to_string(),
+            })?;
+
+        // The body element, not the extension, says what the document is
+        let mut page_count = None;
+        let blocks = match body.tag_name().name() {
+            "text" => {
+                let mut blocks = Vec::new();
+                odt_collect(body, &mut blocks);
+                blocks
+            }
+            "spreadsheet" => ods_blocks(body),
+            "presentation" => {
+                let pages = children(body, "page").count() as u32;
+                page_count = Some(pages);
+                odp_blocks(body)
+            }
+            other => {
+                return Err(ParseError::CorruptedFile {
+                    reason: format!("Unexpected document body: {}", other),
+                })
+            }
+        };
+
+        let mut content = OpenDocumentContent {
+            blocks,
+            title: None,
+            author: None,
+            created_date: None,
+            page_count,
+        };
+        read_meta(&mut package, &mut content)?;
+        Ok(content)
+    }
+}
+
+impl Default for OpenDocumentParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ContentParser for OpenDocumentParser {
+    async fn parse(&self, path: &Path, config: &ParseConfig) -> Result<ParseResult, ParseError> {
+        if !path.exists() {
+            return Err(ParseError::FileNotFound {
+                path: path.display().to_string(),
+            });
+        }
+
+        // Unzipping and XML parsing are blocking
+        let path_owned = path.to_path_buf();
+        let parser = Self::new();
+        let content = tokio::task::spawn_blocking(move || parser.extract(&path_owned))
+            .await
+            .map_err(|e| ParseError::ParseFailed {
+                reason: format!("Task join error: {}", e),
+            })??;
+
+        let file_id = Uuid::now_v7();
+        let (text, chunks) = create_chunks_from_blocks(file_id, &content.blocks, config);
+
+        let title = content.title.or_else(|| {
+            content
+                .blocks
+                .iter()
+                .find(|b| b.kind == ChunkType::Heading)
+                .map(|b| b.text.clone())
+        });
+
+        let metadata = ParseMetadata {
+            title,
+            author: content.author,
+            created_date: content.created_date,
+            page_count: content.page_count,
+            word_count: text.split_whitespace().count(),
+            char_count: text.chars().count(),
+            ..Default::default()
+        };
+
+        Ok(ParseResult {
+            text,
+            chunks,
+            metadata,
+        })
+    }
+
+    fn supports(&self, file_type: FileType) -> bool {
+        matches!(file_type, FileType::OfficeDocument)
+    }
+
+    fn supported_extensions(&self) -> &[&str] {
+        &self.supported_extensions
+    }
+}
+
+/// Fill title, author, creation date and page count from `meta.xml`
+fn read_meta<R: Read + Seek>(
+    package: &mut Package<R>,
+    content: &mut OpenDocumentContent,
+) -> Result<(), ParseError> {
+    let Some(xml) = package.read_part("meta.xml")? else {
+        return Ok(());
+    };
+    let doc = parse_xml(&xml)?;
+    let Some(meta) = doc.descendants().find(|n| is(*n, "meta")) else {
+        return Ok(());
+    };
+
+    let property = |name: &str| {
+        children(meta, name)
+            .next()
+            .and_then(|n| n.text())
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+    };
+    content.title = property("title");
+    content.author = property("initial-creator").or_else(|| property("creator"));
+    content.created_date = property("creation-date");
+
+    if content.page_count.is_none() {
+        content.page_count = children(meta, "document-statistic")
+            .next()
+            .and_then(|stats| attr(stats, "page-count"))
+            .and_then(|count| count.parse().ok());
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Text
+// ============================================================================
+
+/// Text of a paragraph or heading
+///
+/// Whitespace in ODF paragraphs is collapsed; explicit spaces, tabs and line
+/// breaks are elements.
+fn odf_text(node: Node<'_, '_>) -> String {
+    let mut text = String::new();
+    push_odf_text(node, &mut text);
+    text.trim().to_string()
+}
+
+fn push_odf_text(node: Node<'_, '_>, text: &mut String) {
+    for child in node.children() {
+        if child.is_text() {
+            for c in child.text().unwrap_or("").chars() {
+                if !c.is_whitespace() {
+                    text.push(c);
+                } else if !text.ends_with(' ') {
+                    text.push(' ');
+                }
+            }
+            continue;
+        }
+        if !child.is_element() {
+            continue;
+        }
+        match child.tag_name().name() {
+            "s" => {
+                let count = attr(child, "c").and_then(|c| c.parse().ok()).unwrap_or(1);
+                text.extend(std::iter::repeat(' ').take(usize::min(count, MAX_SPACE_RUN)));
+            }
+            "tab" => text.push('\t'),
+            "line-break" => text.push('\n'),
+            // Footnotes and comments would interrupt the sentence they are anchored in
+            "note" | "annotation" | "annotation-end" => {}
+            _ => push_odf_text(child, text),
+        }
+    }
+}
+
+/// Non-empty rows of a table, cells separated like OOXML tables
+fn table_rows(table: Node<'_, '_>) -> Vec<String> {
+    table
+        .descendants()
+        .filter(|n| is(*n, "table-row"))
+        // Rows of nested tables belong to their cell
+        .filter(|row| row.ancestors().find(|a| is(*a, "table")) == Some(table))
+        .take(MAX_SHEET_ROWS)
+        .filter_map(|row| {
+            let cells: Vec<String> = children(row, "table-cell")
+                .map(|cell| {
+                    cell.children()
+                        .filter(|n| is(*n, "p") || is(*n, "h"))
+                        .map(odf_text)
+                        .filter(|t| !t.is_empty())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .filter(|t| !t.is_empty())
+                .collect();
+            (!cells.is_empty()).then(|| cells.join(CELL_SEPARATOR))
+        })
+        .collect()
+}
+
+// ============================================================================
+// ODT
+// ============================================================================
+
+fn odt_collect(node: Node<'_, '_>, blocks: &mut Vec<DocumentBlock>) {
+    for child in node.children().filter(|n| n.is_element()) {
+        match child.tag_name().name() {
+            "h" | "p" => {
+                let text = odf_text(child);
+                if text.is_empty() {
+                    continue;
+                }
+                let kind = if is(child, "h") {
+                    ChunkType::Heading
+                } else {
+                    ChunkType::Paragraph
+                };
+                blocks.push(DocumentBlock::new(kind, text));
+            }
+            "table" => {
+                let rows = table_rows(child);
+                if !rows.is_empty() {
+                    blocks.push(DocumentBlock::new(ChunkType::Table, rows.join("\n")));
+                }
+            }
+            // Generated indexes repeat the headings, so only real containers are entered
+            "list" | "list-item" | "list-header" | "section" => odt_collect(child, blocks),
+            _ => {}
+        }
+    }
+}
+
+// ============================================================================
+// ODS
+// ============================================================================
+
+/// One heading and one table per sheet, in document order
+fn ods_blocks(spreadsheet: Node<'_, '_>) -> Vec<DocumentBlock> {
+    let mut blocks = Vec::new();
+    for (index, sheet) in children(spreadsheet, "table").enumerate() {
+        let section = index as u32 + 1;
+        let name = attr(sheet, "name").unwrap_or("").to_string();
+        let section_name = (!name.is_empty()).then(|| name.clone());
+
+        let rows = table_rows(sheet);
+        if rows.is_empty() {
+            continue;
+        }
+        if !name.is_empty() {
+            blocks.push(DocumentBlock::new(ChunkType::Heading, name).in_section(section, section_name.clone()));
+        }
+        blocks.push(DocumentBlock::new(ChunkType::Table, rows.join("\n")).in_section(section, section_name));
+    }
+    blocks
+}
+
+// ============================================================================
+// ODP
+// ============================================================================
+
+/// Title, text and tables of each page, in presentation order
+fn odp_blocks(presentation: Node<'_, '_>) -> Vec<DocumentBlock> {
+    let mut blocks = Vec::new();
+    for (index, page) in children(presentation, "page").enumerate() {
+        let section = index as u32 + 1;
+        // Speaker notes are not part of the slide
+        let on_slide = |n: &Node<'_, '_>| !n.ancestors().any(|a| is(a, "notes"));
+
+        let mut title = Vec::new();
+        let mut body = Vec::new();
+        for paragraph in page
+            .descendants()
+            .filter(|n| (is(*n, "p") || is(*n, "h")) && on_slide(n))
+            .filter(|n| !n.ancestors().any(|a| is(a, "table")))
+        {
+            let text = odf_text(paragraph);
+            if text.is_empty() {
+                continue;
+            }
+            let is_title = paragraph
+                .ancestors()
+                .any(|a| is(a, "frame") && attr(a, "class") == Some("title"));
+            if is_title {
+                title.push(text);
+            } else {
+                body.push(text);
+            }
+        }
+
+        let title = (!title.is_empty()).then(|| title.join(" "));
+        if let Some(title) = &title {
+            blocks.push(DocumentBlock::new(ChunkType::Heading, title.clone()).in_section(section, Some(title.clone())));
+        }
+        if !body.is_empty() {
+            blocks.push(DocumentBlock::new(ChunkType::Slide, body.join("\n")).in_section(section, title.clone()));
+        }
+        for table in page.descendants().filter(|n| is(*n, "table") && on_slide(n)) {
+            let rows = table_rows(table);
+            if !rows.is_empty() {
+                blocks.push(DocumentBlock::new(ChunkType::Table, rows.join("\n")).in_section(section, title.clone()));
+            }
+        }
+    }
+    blocks
+}
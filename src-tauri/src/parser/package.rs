@@ -0,0 +1,138 @@
+//! Zip-based document packages
+//!
+//! Office Open XML and OpenDocument files are zip archives of XML parts.
+//! Parts are read with a size cap so a crafted package cannot exhaust
+//! memory, and parsed into read-only trees with `roxmltree`.
+
+use std::fs::File;
+use std::io::{Read, Seek};
+use std::path::Path;
+
+use roxmltree::{Document, Node};
+use zip::result::ZipError;
+use zip::ZipArchive;
+
+use super::ParseError;
+
+/// Largest part read from a package, uncompressed (default: 64MB)
+const MAX_PART_SIZE: u64 = 64 * 1024 * 1024;
+
+/// An opened zip package
+pub(crate) struct Package<R> {
+    archive: ZipArchive<R>,
+}
+
+impl Package<File> {
+    /// Open the package at `path`
+    pub(crate) fn open(path: &Path) -> Result<Self, ParseError> {
+        Self::new(File::open(path)?)
+    }
+}
+
+impl<R: Read + Seek> Package<R> {
+    /// Read a package from any seekable source
+    pub(crate) fn new(reader: R) -> Result<Self, ParseError> {
+        let archive = ZipArchive::new(reader).map_err(|e| ParseError::CorruptedFile {
+            reason: format!("Not a zip package: {}", e),
+        })?;
+        Ok(Self { archive })
+    }
+
+    /// Read a part as text; `None` if the package has no such part
+    pub(crate) fn read_part(&mut self, name: &str) -> Result<Option<String>, ParseError> {
+        let name = name.trim_start_matches('/');
+        let part = match self.archive.by_name(name) {
+            Ok(part) => part,
+            Err(ZipError::FileNotFound) => return Ok(None),
+            Err(e) => {
+                return Err(ParseError::CorruptedFile {
+                    reason: format!("Cannot read part {}: {}", name, e),
+                })
+            }
+        };
+
+        if part.size() > MAX_PART_SIZE {
+            return Err(ParseError::ParseFailed {
+                reason: format!("Part {} is too large ({} bytes)", name, part.size()),
+            });
+        }
+
+        // The declared size can lie; never read past the cap
+        let mut bytes = Vec::with_capacity(part.size() as usize);
+        part.take(MAX_PART_SIZE).read_to_end(&mut bytes)?;
+        String::from_utf8(bytes).map(Some).map_err(|e| ParseError::EncodingError {
+            reason: format!("Part {} is not UTF-8: {}", name, e),
+        })
+    }
+
+    /// Read a part that the format requires
+    pub(crate) fn require_part(&mut self, name: &str) -> Result<String, ParseError> {
+        self.read_part(name)?.ok_or_else(|| ParseError::CorruptedFile {
+            reason: format!("Missing part {}", name),
+        })
+    }
+}
+
+/// Parse an XML part
+pub(crate) fn parse_xml(xml: &str) -> Result<Document<'_>, ParseError> {
+    Document::parse(xml).map_err(|e| ParseError::ParseFailed {
+        reason: format!("Invalid XML: {}", e),
+    })
+}
+
+/// Attribute by local name, ignoring its namespace prefix
+pub(crate) fn attr<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    node.attributes().find(|a| a.name() == name).map(|a| a.value())
+}
+
+/// Relationship ID (`r:id`) of an element
+///
+/// Matched by namespace, since elements like `p:sldId` also carry a plain `id`.
+pub(crate) fn relationship_id<'a>(node: Node<'a, '_>) -> Option<&'a str> {
+    node.attributes()
+        .find(|a| a.name() == "id" && a.namespace().is_some())
+        .map(|a| a.value())
+}
+
+/// Whether the element has the given local name
+pub(crate) fn is(node: Node<'_, '_>, name: &str) -> bool {
+    node.is_element() && node.tag_name().name() == name
+}
+
+/// Child elements with the given local name
+pub(crate) fn children<'a, 'input: 'a>(
+    node: Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    node.children().filter(move |child| is(*child, name))
+}
+
+/// Concatenated text of all descendant elements named `name`
+pub(crate) fn descendant_text(node: Node<'_, '_>, name: &str) -> String {
+    node.descendants()
+        .filter(|n| is(*n, name))
+        .filter_map(|n| n.text())
+        .collect()
+}
+
+/// Resolve a relationship target against the folder of the referencing part
+///
+/// Targets are relative (`worksheets/sheet1.xml`, `../media/a.png`) or
+/// absolute within the package (`/xl/worksheets/sheet1.xml`).
+pub(crate) fn resolve_target(base_dir: &str, target: &str) -> String {
+    if let Some(absolute) = target.strip_prefix('/') {
+        return absolute.to_string();
+    }
+
+    let mut segments: Vec<&str> = base_dir.split('/').filter(|s| !s.is_empty()).collect();
+    for segment in target.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
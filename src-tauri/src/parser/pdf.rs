@@ -106,6 +106,9 @@ impl PdfParser {
                             end_line: None,
                             page_number: Some((page_num + 1) as u32),
                             bounding_box: None,
+                            section: None,
+                            section_name: None,
+                            member: None,
                         },
                         vector_id: 0,
                         created_at: Utc::now(),
@@ -138,6 +141,9 @@ impl PdfParser {
                         end_line: None,
                         page_number: Some(estimated_page.min(page_count)),
                         bounding_box: None,
+                        section: None,
+                        section_name: None,
+                        member: None,
                     },
                     vector_id: 0,
                     created_at: Utc::now(),
@@ -186,6 +192,9 @@ impl PdfParser {
                     end_line: None,
                     page_number: Some(page_number),
                     bounding_box: None,
+                    section: None,
+                    section_name: None,
+                    member: None,
                 },
                 vector_id: 0,
                 created_at: Utc::now(),
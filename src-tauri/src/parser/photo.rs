@@ -0,0 +1,433 @@
+//! Image metadata parser
+//!
+//! Reads what cameras and photo tools record about an image:
+//! - EXIF capture time, camera, lens, orientation and GPS position
+//! - XMP keywords (`dc:subject`) and place names (IPTC location, city,
+//!   state and country)
+//! - pixel dimensions from the image header
+//!
+//! The searchable description (capture time, camera, place, keywords)
+//! becomes a single caption chunk. GPS coordinates never enter the text;
+//! they are only returned in `ImageMetadata::gps`, which makes the file
+//! `PrivacyLevel::Sensitive` (see `ImageMetadata::privacy_level`).
+//!
+//! Place names come from XMP only. GPS positions are not reverse geocoded,
+//! so photos that record coordinates without an XMP place cannot be found
+//! by place name.
+
+use std::io::Cursor;
+use std::path::Path;
+
+use async_trait::async_trait;
+use chrono::{DateTime, FixedOffset, NaiveDate, TimeZone, Utc};
+use exif::{Exif, In, Tag, Value};
+use uuid::Uuid;
+
+use super::{
+    create_chunks_from_blocks, ContentParser, DocumentBlock, ParseConfig, ParseError, ParseMetadata,
+    ParseResult,
+};
+use crate::core::types::chunk::ChunkType;
+use crate::core::types::file::{FileType, PrivacyLevel};
+
+const RDF_NS: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+const DC_NS: &str = "http://purl.org/dc/elements/1.1/";
+const PHOTOSHOP_NS: &str = "http://ns.adobe.com/photoshop/1.0/";
+const IPTC_CORE_NS: &str = "http://iptc.org/std/Iptc4xmpCore/1.0/xmlns/";
+
+/// XMP properties naming the place, from most to least specific
+const XMP_PLACE_PROPERTIES: &[(&str, &str)] = &[
+    (IPTC_CORE_NS, "Location"),
+    (PHOTOSHOP_NS, "City"),
+    (PHOTOSHOP_NS, "State"),
+    (PHOTOSHOP_NS, "Country"),
+];
+
+/// Metadata recorded in an image file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImageMetadata {
+    /// Width in pixels
+    pub width: Option<u32>,
+    /// Height in pixels
+    pub height: Option<u32>,
+    /// Capture time; the offset is zero when the camera recorded none
+    pub captured_at: Option<DateTime<FixedOffset>>,
+    /// Camera manufacturer
+    pub camera_make: Option<String>,
+    /// Camera model
+    pub camera_model: Option<String>,
+    /// Lens model
+    pub lens: Option<String>,
+    /// EXIF orientation (1-8)
+    pub orientation: Option<u16>,
+    /// GPS position (sensitive)
+    pub gps: Option<GpsPosition>,
+    /// Place name from XMP, e.g. "Kyoto, Japan"; never derived from `gps`
+    pub location: Option<String>,
+    /// XMP keywords
+    pub keywords: Vec<String>,
+}
+
+/// Position recorded by the camera's GPS
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GpsPosition {
+    /// Degrees north (negative for south)
+    pub latitude: f64,
+    /// Degrees east (negative for west)
+    pub longitude: f64,
+    /// Meters above sea level
+    pub altitude: Option<f64>,
+}
+
+impl ImageMetadata {
+    /// Camera name without the make repeated ("Canon EOS R5")
+    pub fn camera(&self) -> Option<String> {
+        match (&self.camera_make, &self.camera_model) {
+            (Some(make), Some(model)) if model.to_lowercase().starts_with(&make.to_lowercase()) => {
+                Some(model.clone())
+            }
+            (Some(make), Some(model)) => Some(format!("{} {}", make, model)),
+            (make, model) => make.clone().or_else(|| model.clone()),
+        }
+    }
+
+    /// Capture time as a Unix timestamp of the camera's wall clock
+    ///
+    /// The local time is read as UTC so date filters match the calendar day
+    /// the photo was taken on, wherever that was.
+    pub fn capture_timestamp(&self) -> Option<i64> {
+        self.captured_at
+            .map(|captured_at| Utc.from_utc_datetime(&captured_at.naive_local()).timestamp())
+    }
+
+    /// Least restrictive privacy level the image allows
+    ///
+    /// A recorded GPS position reveals where the user was, so such images
+    /// are processed locally only.
+    pub fn privacy_level(&self) -> PrivacyLevel {
+        if self.gps.is_some() {
+            PrivacyLevel::Sensitive
+        } else {
+            PrivacyLevel::Normal
+        }
+    }
+
+    /// Searchable description of the image, without GPS coordinates
+    fn description(&self) -> String {
+        let mut lines = Vec::new();
+        if let Some(captured_at) = self.captured_at {
+            lines.push(format!("Taken: {}", captured_at.format("%Y-%m-%d %H:%M")));
+        }
+        if let Some(camera) = self.camera() {
+            lines.push(format!("Camera: {}", camera));
+        }
+        if let Some(ref lens) = self.lens {
+            lines.push(format!("Lens: {}", lens));
+        }
+        if let Some(ref location) = self.location {
+            lines.push(format!("Location: {}", location));
+        }
+        if !self.keywords.is_empty() {
+            lines.push(format!("Keywords: {}", self.keywords.join(", ")));
+        }
+        if let (Some(width), Some(height)) = (self.width, self.height) {
+            lines.push(format!("Dimensions: {} x {}", width, height));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Parser for photo metadata (JPEG, PNG, TIFF, WebP)
+pub struct ImageMetadataParser {
+    supported_extensions: Vec<&'static str>,
+}
+
+impl ImageMetadataParser {
+    /// Create a new image metadata parser
+    pub fn new() -> Self {
+        Self {
+            supported_extensions: vec!["jpg", "jpeg", "png", "tiff", "webp"],
+        }
+    }
+}
+
+impl Default for ImageMetadataParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ContentParser for ImageMetadataParser {
+    async fn parse(&self, path: &Path, config: &ParseConfig) -> Result<ParseResult, ParseError> {
+        if !path.exists() {
+            return Err(ParseError::FileNotFound {
+                path: path.display().to_string(),
+            });
+        }
+
+        // EXIF, XMP and header decoding is CPU-bound
+        let bytes = tokio::fs::read(path).await?;
+        let image = tokio::task::spawn_blocking(move || read_image_metadata(&bytes))
+            .await
+            .map_err(|e| ParseError::ParseFailed {
+                reason: format!("Task join error: {}", e),
+            })??;
+
+        let file_id = Uuid::now_v7();
+        let description = image.description();
+        let blocks: Vec<DocumentBlock> = if description.is_empty() {
+            Vec::new()
+        } else {
+            vec![DocumentBlock::new(ChunkType::Caption, description)]
+        };
+        let (text, chunks) = create_chunks_from_blocks(file_id, &blocks, config);
+
+        let metadata = ParseMetadata {
+            created_date: image.captured_at.map(|t| t.to_rfc3339()),
+            word_count: text.split_whitespace().count(),
+            char_count: text.chars().count(),
+            image: Some(image),
+            ..Default::default()
+        };
+
+        Ok(ParseResult {
+            text,
+            chunks,
+            metadata,
+        })
+    }
+
+    fn supports(&self, file_type: FileType) -> bool {
+        matches!(file_type, FileType::Image)
+    }
+
+    fn supported_extensions(&self) -> &[&str] {
+        &self.supported_extensions
+    }
+}
+
+/// Read EXIF, XMP and dimensions from the bytes of an image file
+fn read_image_metadata(bytes: &[u8]) -> Result<ImageMetadata, ParseError> {
+    let dimensions = image::io::Reader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .ok()
+        .and_then(|reader| reader.into_dimensions().ok());
+
+    let exif = match exif::Reader::new().read_from_container(&mut Cursor::new(bytes)) {
+        Ok(exif) => Some(exif),
+        Err(exif::Error::NotFound(_)) => None,
+        Err(e) => {
+            // Broken EXIF should not hide the rest of the metadata
+            tracing::debug!("Ignoring unreadable EXIF data: {}", e);
+            None
+        }
+    };
+
+    if dimensions.is_none() && exif.is_none() {
+        return Err(ParseError::CorruptedFile {
+            reason: "Unrecognized image data".to_string(),
+        });
+    }
+
+    let mut metadata = ImageMetadata::default();
+    if let Some((width, height)) = dimensions {
+        metadata.width = Some(width);
+        metadata.height = Some(height);
+    }
+
+    if let Some(ref exif) = exif {
+        metadata.captured_at = capture_time(exif);
+        metadata.camera_make = ascii_field(exif, Tag::Make);
+        metadata.camera_model = ascii_field(exif, Tag::Model);
+        metadata.lens = ascii_field(exif, Tag::LensModel);
+        metadata.orientation = uint_field(exif, Tag::Orientation)
+            .filter(|o| (1..=8).contains(o))
+            .map(|o| o as u16);
+        metadata.gps = gps_position(exif);
+        if metadata.width.is_none() {
+            metadata.width = uint_field(exif, Tag::PixelXDimension);
+            metadata.height = uint_field(exif, Tag::PixelYDimension);
+        }
+    }
+
+    if let Some(packet) = xmp_packet(bytes) {
+        match roxmltree::Document::parse(&packet) {
+            Ok(xmp) => {
+                metadata.keywords = xmp_keywords(&xmp);
+                metadata.location = xmp_location(&xmp);
+            }
+            Err(e) => tracing::debug!("Ignoring malformed XMP packet: {}", e),
+        }
+    }
+
+    Ok(metadata)
+}
+
+// ============================================================================
+// EXIF
+// ============================================================================
+
+fn ascii_field(exif: &Exif, tag: Tag) -> Option<String> {
+    match exif.get_field(tag, In::PRIMARY)?.value {
+        Value::Ascii(ref values) => values
+            .first()
+            .map(|v| String::from_utf8_lossy(v).trim_matches(char::from(0)).trim().to_string())
+            .filter(|v| !v.is_empty()),
+        _ => None,
+    }
+}
+
+fn uint_field(exif: &Exif, tag: Tag) -> Option<u32> {
+    exif.get_field(tag, In::PRIMARY)?.value.get_uint(0)
+}
+
+/// Rational values of a field as floats
+fn rational_field(exif: &Exif, tag: Tag) -> Option<Vec<f64>> {
+    match exif.get_field(tag, In::PRIMARY)?.value {
+        Value::Rational(ref values) => Some(values.iter().map(|r| r.to_f64()).collect()),
+        _ => None,
+    }
+}
+
+/// Original capture time, falling back to the file's EXIF time
+fn capture_time(exif: &Exif) -> Option<DateTime<FixedOffset>> {
+    let (tag, offset_tag) = if exif.get_field(Tag::DateTimeOriginal, In::PRIMARY).is_some() {
+        (Tag::DateTimeOriginal, Tag::OffsetTimeOriginal)
+    } else {
+        (Tag::DateTime, Tag::OffsetTime)
+    };
+
+    let mut time = match exif.get_field(tag, In::PRIMARY)?.value {
+        Value::Ascii(ref values) => exif::DateTime::from_ascii(values.first()?).ok()?,
+        _ => return None,
+    };
+    if let Some(Value::Ascii(values)) = exif.get_field(offset_tag, In::PRIMARY).map(|f| &f.value) {
+        if let Some(offset) = values.first() {
+            let _ = time.parse_offset(offset);
+        }
+    }
+
+    let offset = FixedOffset::east_opt(time.offset.unwrap_or(0) as i32 * 60)?;
+    NaiveDate::from_ymd_opt(time.year as i32, time.month as u32, time.day as u32)?
+        .and_hms_opt(time.hour as u32, time.minute as u32, time.second as u32)?
+        .and_local_timezone(offset)
+        .single()
+}
+
+fn gps_position(exif: &Exif) -> Option<GpsPosition> {
+    let latitude = gps_coordinate(exif, Tag::GPSLatitude, Tag::GPSLatitudeRef, 'S')?;
+    let longitude = gps_coordinate(exif, Tag::GPSLongitude, Tag::GPSLongitudeRef, 'W')?;
+    if latitude.abs() > 90.0 || longitude.abs() > 180.0 {
+        return None;
+    }
+    // Cameras without a fix often write zeros
+    if latitude == 0.0 && longitude == 0.0 {
+        return None;
+    }
+
+    let altitude = rational_field(exif, Tag::GPSAltitude)
+        .and_then(|values| values.first().copied())
+        .filter(|altitude| altitude.is_finite())
+        .map(|altitude| {
+            // Reference 1 means below sea level
+            if uint_field(exif, Tag::GPSAltitudeRef) == Some(1) {
+                -altitude
+            } else {
+                altitude
+            }
+        });
+
+    Some(GpsPosition {
+        latitude,
+        longitude,
+        altitude,
+    })
+}
+
+/// Degrees from a degrees/minutes/seconds field and its hemisphere
+fn gps_coordinate(exif: &Exif, tag: Tag, ref_tag: Tag, negative_ref: char) -> Option<f64> {
+    let values = rational_field(exif, tag)?;
+    let degrees = values.first()? + values.get(1).unwrap_or(&0.0) / 60.0 + values.get(2).unwrap_or(&0.0) / 3600.0;
+    if !degrees.is_finite() {
+        return None;
+    }
+
+    let negative = ascii_field(exif, ref_tag)
+        .map(|r| r.eq_ignore_ascii_case(&negative_ref.to_string()))
+        .unwrap_or(false);
+    Some(if negative { -degrees } else { degrees })
+}
+
+// ============================================================================
+// XMP
+// ============================================================================
+
+/// The embedded XMP packet, wherever the container stores it
+fn xmp_packet(bytes: &[u8]) -> Option<String> {
+    slice_between(bytes, b"<x:xmpmeta", b"</x:xmpmeta>")
+        .or_else(|| slice_between(bytes, b"<rdf:RDF", b"</rdf:RDF>"))
+        .map(|packet| String::from_utf8_lossy(packet).into_owned())
+}
+
+/// Bytes from the first `start` up to and including the following `end`
+fn slice_between<'a>(bytes: &'a [u8], start: &[u8], end: &[u8]) -> Option<&'a [u8]> {
+    let from = find(bytes, start)?;
+    let to = from + find(&bytes[from..], end)? + end.len();
+    Some(&bytes[from..to])
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+/// `dc:subject` keywords in order, without duplicates
+fn xmp_keywords(xmp: &roxmltree::Document) -> Vec<String> {
+    let mut keywords: Vec<String> = Vec::new();
+    let subjects = xmp
+        .descendants()
+        .filter(|n| n.has_tag_name((DC_NS, "subject")));
+    for item in subjects.flat_map(|s| s.descendants()).filter(|n| n.has_tag_name((RDF_NS, "li"))) {
+        let keyword = item.text().unwrap_or("").trim();
+        if !keyword.is_empty() && !keywords.iter().any(|k| k.eq_ignore_ascii_case(keyword)) {
+            keywords.push(keyword.to_string());
+        }
+    }
+    keywords
+}
+
+/// Place name joined from the XMP location properties
+fn xmp_location(xmp: &roxmltree::Document) -> Option<String> {
+    let mut parts: Vec<String> = Vec::new();
+    for &(ns, name) in XMP_PLACE_PROPERTIES {
+        if let Some(value) = xmp_property(xmp, ns, name) {
+            // City and state are often the same ("Kyoto, Kyoto")
+            if !parts.iter().any(|p| p.eq_ignore_ascii_case(&value)) {
+                parts.push(value);
+            }
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
+
+/// A simple XMP property, written either as an attribute of
+/// `rdf:Description` or as an element (possibly holding an `rdf:Alt`)
+fn xmp_property(xmp: &roxmltree::Document, ns: &str, name: &str) -> Option<String> {
+    xmp.descendants()
+        .filter(|n| n.is_element())
+        .find_map(|n| {
+            if let Some(value) = n.attribute((ns, name)) {
+                return Some(value.to_string());
+            }
+            if !n.has_tag_name((ns, name)) {
+                return None;
+            }
+            n.descendants()
+                .find(|d| d.has_tag_name((RDF_NS, "li")))
+                .unwrap_or(n)
+                .text()
+                .map(str::to_string)
+        })
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
@@ -0,0 +1,489 @@
+//! RTF parser
+//!
+//! Interprets the control words of Rich Text Format that carry text and
+//! structure and drops the rest:
+//! - paragraphs (`\par`), with `\outlinelevel` paragraphs as headings
+//! - table rows (`\cell`, `\row`) as tables
+//! - `\'hh` escapes in the document code page and `\uN` Unicode escapes
+//! - title, author and creation time from the `\info` group
+//!
+//! Destinations without document text (font and colour tables, pictures,
+//! field instructions, `\*` extensions) are skipped entirely.
+
+use std::path::Path;
+
+use async_trait::async_trait;
+use encoding_rs::Encoding;
+use uuid::Uuid;
+
+use super::office::CELL_SEPARATOR;
+use super::{
+    create_chunks_from_blocks, ContentParser, DocumentBlock, ParseConfig, ParseError, ParseMetadata,
+    ParseResult,
+};
+use crate::core::types::chunk::ChunkType;
+use crate::core::types::file::FileType;
+
+/// Destinations whose content is never document text
+const SKIPPED_DESTINATIONS: &[&str] = &[
+    "fonttbl",
+    "colortbl",
+    "stylesheet",
+    "listtable",
+    "listoverridetable",
+    "revtbl",
+    "rsidtbl",
+    "generator",
+    "pict",
+    "object",
+    "header",
+    "headerl",
+    "headerr",
+    "headerf",
+    "footer",
+    "footerl",
+    "footerr",
+    "footerf",
+    "footnote",
+    "fldinst",
+    "themedata",
+    "colorschememapping",
+    "datastore",
+    "latentstyles",
+    "xmlnstbl",
+    "filetbl",
+];
+
+/// Parser for Rich Text Format documents
+pub struct RtfParser {
+    supported_extensions: Vec<&'static str>,
+}
+
+impl RtfParser {
+    /// Create a new RTF parser
+    pub fn new() -> Self {
+        Self {
+            supported_extensions: vec!["rtf"],
+        }
+    }
+}
+
+impl Default for RtfParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ContentParser for RtfParser {
+    async fn parse(&self, path: &Path, config: &ParseConfig) -> Result<ParseResult, ParseError> {
+        if !path.exists() {
+            return Err(ParseError::FileNotFound {
+                path: path.display().to_string(),
+            });
+        }
+
+        let bytes = tokio::fs::read(path).await?;
+        if !bytes.starts_with(b"{\\rtf") {
+            return Err(ParseError::CorruptedFile {
+                reason: "Missing RTF header".to_string(),
+            });
+        }
+
+        let document = Interpreter::new().run(&bytes);
+
+        let file_id = Uuid::now_v7();
+        let (text, chunks) = create_chunks_from_blocks(file_id, &document.blocks, config);
+
+        let title = document.title.or_else(|| {
+            document
+                .blocks
+                .iter()
+                .find(|b| b.kind == ChunkType::Heading)
+                .map(|b| b.text.clone())
+        });
+
+        let metadata = ParseMetadata {
+            title,
+            author: document.author,
+            created_date: document.created_date,
+            word_count: text.split_whitespace().count(),
+            char_count: text.chars().count(),
+            ..Default::default()
+        };
+
+        Ok(ParseResult {
+            text,
+            chunks,
+            metadata,
+        })
+    }
+
+    fn supports(&self, file_type: FileType) -> bool {
+        matches!(file_type, FileType::TextDocument)
+    }
+
+    fn supported_extensions(&self) -> &[&str] {
+        &self.supported_extensions
+    }
+}
+
+// ============================================================================
+// Interpreter
+// ============================================================================
+
+/// Where text of the current group goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Destination {
+    Body,
+    Info,
+    Title,
+    Author,
+    Created,
+    Skip,
+}
+
+/// State saved and restored by `{` and `}`
+#[derive(Debug, Clone, Copy)]
+struct GroupState {
+    destination: Destination,
+    /// Fallback characters following a `\uN` escape (`\ucN`)
+    unicode_skip: usize,
+}
+
+/// Text and properties of an RTF document
+struct RtfDocument {
+    blocks: Vec<DocumentBlock>,
+    title: Option<String>,
+    author: Option<String>,
+    created_date: Option<String>,
+}
+
+struct Interpreter {
+    encoding: &'static Encoding,
+    groups: Vec<GroupState>,
+    state: GroupState,
+    /// Bytes awaiting decoding in the document code page
+    pending: Vec<u8>,
+    /// Fallback characters still to skip after a `\uN` escape
+    skip_chars: usize,
+    high_surrogate: Option<u16>,
+    paragraph: String,
+    heading: bool,
+    in_table: bool,
+    cells: Vec<String>,
+    rows: Vec<String>,
+    blocks: Vec<DocumentBlock>,
+    title: String,
+    author: String,
+    /// Year, month, day, hour and minute of `\creatim`
+    created: [Option<i32>; 5],
+}
+
+impl Interpreter {
+    fn new() -> Self {
+        Self {
+            encoding: encoding_rs::WINDOWS_1252,
+            groups: Vec::new(),
+            state: GroupState {
+                destination: Destination::Body,
+                unicode_skip: 1,
+            },
+            pending: Vec::new(),
+            skip_chars: 0,
+            high_surrogate: None,
+            paragraph: String::new(),
+            heading: false,
+            in_table: false,
+            cells: Vec::new(),
+            rows: Vec::new(),
+            blocks: Vec::new(),
+            title: String::new(),
+            author: String::new(),
+            created: [None; 5],
+        }
+    }
+
+    fn run(mut self, input: &[u8]) -> RtfDocument {
+        let mut i = 0;
+        while i < input.len() {
+            match input[i] {
+                b'{' => {
+                    self.flush_pending();
+                    self.groups.push(self.state);
+                    self.skip_chars = 0;
+                    i += 1;
+                }
+                b'}' => {
+                    self.flush_pending();
+                    if let Some(state) = self.groups.pop() {
+                        self.state = state;
+                    }
+                    self.skip_chars = 0;
+                    i += 1;
+                }
+                b'\\' => i = self.control(input, i + 1),
+                b'\r' | b'\n' => i += 1,
+                byte => {
+                    self.text_byte(byte);
+                    i += 1;
+                }
+            }
+        }
+
+        self.flush_pending();
+        self.in_table = false;
+        self.end_paragraph();
+
+        let non_empty = |s: String| {
+            let s = s.trim().to_string();
+            (!s.is_empty()).then_some(s)
+        };
+        let created_date = match self.created {
+            [Some(year), Some(month), Some(day), hour, minute] => Some(format!(
+                "{:04}-{:02}-{:02}T{:02}:{:02}:00",
+                year,
+                month,
+                day,
+                hour.unwrap_or(0),
+                minute.unwrap_or(0)
+            )),
+            _ => None,
+        };
+
+        RtfDocument {
+            blocks: self.blocks,
+            title: non_empty(self.title),
+            author: non_empty(self.author),
+            created_date,
+        }
+    }
+
+    /// Handle the control word or symbol after a backslash; returns the next index
+    fn control(&mut self, input: &[u8], start: usize) -> usize {
+        let Some(&first) = input.get(start) else {
+            return start;
+        };
+
+        if !first.is_ascii_alphabetic() {
+            return self.control_symbol(input, start, first);
+        }
+
+        let mut end = start;
+        while end < input.len() && input[end].is_ascii_alphabetic() {
+            end += 1;
+        }
+        let word = std::str::from_utf8(&input[start..end]).unwrap_or("");
+
+        let param_start = end;
+        if end < input.len() && input[end] == b'-' {
+            end += 1;
+        }
+        while end < input.len() && input[end].is_ascii_digit() {
+            end += 1;
+        }
+        let param = std::str::from_utf8(&input[param_start..end])
+            .ok()
+            .and_then(|p| p.parse::<i32>().ok());
+
+        // A single space delimits the control word and is not text
+        if end < input.len() && input[end] == b' ' {
+            end += 1;
+        }
+
+        // Binary data is skipped whatever the destination
+        if word == "bin" {
+            return end.saturating_add(param.unwrap_or(0).max(0) as usize).min(input.len());
+        }
+
+        self.flush_pending();
+        if self.state.destination != Destination::Skip {
+            self.word(word, param);
+        }
+        end
+    }
+
+    fn control_symbol(&mut self, input: &[u8], start: usize, symbol: u8) -> usize {
+        match symbol {
+            b'\'' => {
+                let byte = input
+                    .get(start + 1..start + 3)
+                    .and_then(|hex| std::str::from_utf8(hex).ok())
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                if let Some(byte) = byte {
+                    self.text_byte(byte);
+                }
+                return (start + 3).min(input.len());
+            }
+            // Ignorable destination: none of them carry document text
+            b'*' => self.state.destination = Destination::Skip,
+            b'\\' | b'{' | b'}' => self.text_byte(symbol),
+            b'~' => self.text(" "),
+            b'_' => self.text("-"),
+            b'\r' | b'\n' => {
+                self.flush_pending();
+                if self.state.destination != Destination::Skip {
+                    self.word("par", None);
+                }
+            }
+            _ => {}
+        }
+        start + 1
+    }
+
+    fn word(&mut self, word: &str, param: Option<i32>) {
+        match word {
+            "par" | "sect" | "page" => {
+                if self.in_table {
+                    self.paragraph.push(' ');
+                } else {
+                    self.end_paragraph();
+                }
+            }
+            "pard" => {
+                self.heading = false;
+                self.in_table = false;
+            }
+            "intbl" => self.in_table = true,
+            "outlinelevel" => self.heading = true,
+            "cell" | "nestcell" => {
+                let cell = std::mem::take(&mut self.paragraph).trim().to_string();
+                self.cells.push(cell);
+            }
+            "row" | "nestrow" => {
+                let cells = std::mem::take(&mut self.cells);
+                if cells.iter().any(|c| !c.is_empty()) {
+                    self.rows.push(cells.join(CELL_SEPARATOR));
+                }
+            }
+            "line" => self.text("\n"),
+            "tab" => self.text("\t"),
+            "emdash" => self.text("\u{2014}"),
+            "endash" => self.text("\u{2013}"),
+            "lquote" => self.text("\u{2018}"),
+            "rquote" => self.text("\u{2019}"),
+            "ldblquote" => self.text("\u{201C}"),
+            "rdblquote" => self.text("\u{201D}"),
+            "bullet" => self.text("\u{2022}"),
+            "uc" => self.state.unicode_skip = param.unwrap_or(1).max(0) as usize,
+            "u" => {
+                if let Some(code) = param {
+                    self.unicode(code);
+                }
+            }
+            "ansicpg" => self.encoding = codepage_encoding(param.unwrap_or(1252)),
+            "mac" => self.encoding = encoding_rs::MACINTOSH,
+            "info" => self.state.destination = Destination::Info,
+            "title" if self.state.destination == Destination::Info => {
+                self.state.destination = Destination::Title
+            }
+            "author" if self.state.destination == Destination::Info => {
+                self.state.destination = Destination::Author
+            }
+            "creatim" if self.state.destination == Destination::Info => {
+                self.state.destination = Destination::Created
+            }
+            "yr" | "mo" | "dy" | "hr" | "min" if self.state.destination == Destination::Created => {
+                let index = ["yr", "mo", "dy", "hr", "min"].iter().position(|w| *w == word);
+                if let Some(index) = index {
+                    self.created[index] = param;
+                }
+            }
+            word if SKIPPED_DESTINATIONS.contains(&word) => {
+                self.state.destination = Destination::Skip
+            }
+            _ => {}
+        }
+    }
+
+    /// A `\uN` escape: signed 16-bit code units, followed by fallback characters
+    fn unicode(&mut self, code: i32) {
+        let unit = (if code < 0 { code + 0x10000 } else { code }) as u16;
+        match unit {
+            0xD800..=0xDBFF => self.high_surrogate = Some(unit),
+            0xDC00..=0xDFFF => {
+                if let Some(high) = self.high_surrogate.take() {
+                    let decoded: String = char::decode_utf16([high, unit]).filter_map(|c| c.ok()).collect();
+                    self.text(&decoded);
+                }
+            }
+            unit => {
+                if let Some(c) = char::from_u32(unit as u32) {
+                    self.text(c.encode_utf8(&mut [0; 4]));
+                }
+            }
+        }
+        self.skip_chars = self.state.unicode_skip;
+    }
+
+    /// A byte of text in the document code page
+    fn text_byte(&mut self, byte: u8) {
+        if self.skip_chars > 0 {
+            self.skip_chars -= 1;
+            return;
+        }
+        if self.state.destination != Destination::Skip {
+            self.pending.push(byte);
+        }
+    }
+
+    fn flush_pending(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+        let bytes = std::mem::take(&mut self.pending);
+        let (decoded, _) = self.encoding.decode_without_bom_handling(&bytes);
+        self.text(&decoded);
+    }
+
+    fn text(&mut self, text: &str) {
+        match self.state.destination {
+            Destination::Body => self.paragraph.push_str(text),
+            Destination::Title => self.title.push_str(text),
+            Destination::Author => self.author.push_str(text),
+            Destination::Info | Destination::Created | Destination::Skip => {}
+        }
+    }
+
+    fn end_paragraph(&mut self) {
+        // A paragraph outside the table ends it
+        if !self.rows.is_empty() {
+            let rows = std::mem::take(&mut self.rows);
+            self.blocks.push(DocumentBlock::new(ChunkType::Table, rows.join("\n")));
+        }
+
+        let text = std::mem::take(&mut self.paragraph);
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+        let kind = if self.heading {
+            ChunkType::Heading
+        } else {
+            ChunkType::Paragraph
+        };
+        self.blocks.push(DocumentBlock::new(kind, text));
+    }
+}
+
+/// Encoding of a Windows code page from `\ansicpgN`
+fn codepage_encoding(codepage: i32) -> &'static Encoding {
+    match codepage {
+        874 => encoding_rs::WINDOWS_874,
+        932 => encoding_rs::SHIFT_JIS,
+        936 => encoding_rs::GBK,
+        949 => encoding_rs::EUC_KR,
+        950 => encoding_rs::BIG5,
+        1250 => encoding_rs::WINDOWS_1250,
+        1251 => encoding_rs::WINDOWS_1251,
+        1253 => encoding_rs::WINDOWS_1253,
+        1254 => encoding_rs::WINDOWS_1254,
+        1255 => encoding_rs::WINDOWS_1255,
+        1256 => encoding_rs::WINDOWS_1256,
+        1257 => encoding_rs::WINDOWS_1257,
+        1258 => encoding_rs::WINDOWS_1258,
+        10000 => encoding_rs::MACINTOSH,
+        65001 => encoding_rs::UTF_8,
+        _ => encoding_rs::WINDOWS_1252,
+    }
+}
@@ -11,6 +11,20 @@ async fn create_temp_file(dir: &TempDir, name: &str, content: &str) -> std::path
     path
 }
 
+/// Helper to write a zip package with the given parts
+fn create_package(dir: &TempDir, name: &str, parts: &[(&str, &str)]) -> std::path::PathBuf {
+    use std::io::Write;
+
+    let path = dir.path().join(name);
+    let mut zip = zip::ZipWriter::new(fs::File::create(&path).unwrap());
+    for (part, content) in parts {
+        zip.start_file(*part, zip::write::FileOptions::default()).unwrap();
+        zip.write_all(content.as_bytes()).unwrap();
+    }
+    zip.finish().unwrap();
+    path
+}
+
 mod text_parser_tests {
     use super::*;
 
@@ -323,10 +337,23 @@ mod content_parser_service_tests {
         assert!(service.is_supported(Path::new("test.rs")));
         assert!(service.is_supported(Path::new("test.py")));
         assert!(service.is_supported(Path::new("test.pdf")));
+        assert!(service.is_supported(Path::new("test.docx")));
+        assert!(service.is_supported(Path::new("test.XLSX")));
+        assert!(service.is_supported(Path::new("test.odt")));
+        assert!(service.is_supported(Path::new("test.rtf")));
+        assert!(service.is_supported(Path::new("test.html")));
+        assert!(service.is_supported(Path::new("test.epub")));
+        assert!(service.is_supported(Path::new("test.eml")));
+        assert!(service.is_supported(Path::new("test.mbox")));
+        assert!(service.is_supported(Path::new("test.zip")));
+        assert!(service.is_supported(Path::new("test.tar")));
+        assert!(service.is_supported(Path::new("test.jpg")));
+        assert!(service.is_supported(Path::new("test.PNG")));
         
         // Unsupported
         assert!(!service.is_supported(Path::new("test.xyz")));
         assert!(!service.is_supported(Path::new("test.bin")));
+        assert!(!service.is_supported(Path::new("test.doc")));
     }
 
     #[tokio::test]
@@ -362,6 +389,1120 @@ mod content_parser_service_tests {
     }
 }
 
+mod office_parser_tests {
+    use super::*;
+
+    const CORE_PROPERTIES: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties"
+    xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/">
+  <dc:title>Quarterly Report</dc:title>
+  <dc:creator>Ana Ito</dc:creator>
+  <dcterms:created>2024-03-01T09:00:00Z</dcterms:created>
+</cp:coreProperties>"#;
+
+    #[tokio::test]
+    async fn test_parse_docx() {
+        let dir = TempDir::new().unwrap();
+        let styles = r#"<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+  <w:style w:type="paragraph" w:styleId="Heading1"><w:name w:val="heading 1"/></w:style>
+  <w:style w:type="paragraph" w:styleId="Normal"><w:name w:val="Normal"/></w:style>
+</w:styles>"#;
+        let document = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+  <w:body>
+    <w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:r><w:t>Revenue</w:t></w:r></w:p>
+    <w:p><w:r><w:t xml:space="preserve">Sales grew </w:t></w:r><w:r><w:t>in March.</w:t></w:r></w:p>
+    <w:tbl>
+      <w:tr><w:tc><w:p><w:r><w:t>Region</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>Total</w:t></w:r></w:p></w:tc></w:tr>
+      <w:tr><w:tc><w:p><w:r><w:t>Kansai</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>42</w:t></w:r></w:p></w:tc></w:tr>
+    </w:tbl>
+  </w:body>
+</w:document>"#;
+        let path = create_package(
+            &dir,
+            "report.docx",
+            &[
+                ("word/document.xml", document),
+                ("word/styles.xml", styles),
+                ("docProps/core.xml", CORE_PROPERTIES),
+            ],
+        );
+
+        let result = OfficeParser::new().parse(&path, &ParseConfig::default()).await.unwrap();
+
+        let chunks: Vec<(ChunkType, &str)> = result
+            .chunks
+            .iter()
+            .map(|c| (c.chunk_type, c.content.as_str()))
+            .collect();
+        assert_eq!(
+            chunks,
+            vec![
+                (ChunkType::Heading, "Revenue"),
+                (ChunkType::Paragraph, "Sales grew in March."),
+                (ChunkType::Table, "Region | Total\nKansai | 42"),
+            ]
+        );
+        assert_eq!(result.metadata.title, Some("Quarterly Report".to_string()));
+        assert_eq!(result.metadata.author, Some("Ana Ito".to_string()));
+        assert_eq!(result.metadata.created_date, Some("2024-03-01T09:00:00Z".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_parse_xlsx_sheets() {
+        let dir = TempDir::new().unwrap();
+        let workbook = r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"
+    xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+  <sheets>
+    <sheet name="Budget" sheetId="1" r:id="rId1"/>
+    <sheet name="Notes" sheetId="2" r:id="rId2"/>
+  </sheets>
+</workbook>"#;
+        let rels = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rId1" Type="worksheet" Target="worksheets/sheet1.xml"/>
+  <Relationship Id="rId2" Type="worksheet" Target="/xl/worksheets/sheet2.xml"/>
+</Relationships>"#;
+        let shared = r#"<sst xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+  <si><t>Item</t></si><si><t>Cost</t></si><si><r><t>Rent</t></r></si>
+</sst>"#;
+        let sheet1 = r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>
+  <row r="1"><c r="A1" t="s"><v>0</v></c><c r="B1" t="s"><v>1</v></c></row>
+  <row r="2"><c r="A2" t="s"><v>2</v></c><c r="B2"><v>1200</v></c></row>
+</sheetData></worksheet>"#;
+        let sheet2 = r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>
+  <row r="1"><c r="A1" t="inlineStr"><is><t>Paid monthly</t></is></c><c r="B1" t="b"><v>1</v></c></row>
+</sheetData></worksheet>"#;
+        let path = create_package(
+            &dir,
+            "budget.xlsx",
+            &[
+                ("xl/workbook.xml", workbook),
+                ("xl/_rels/workbook.xml.rels", rels),
+                ("xl/sharedStrings.xml", shared),
+                ("xl/worksheets/sheet1.xml", sheet1),
+                ("xl/worksheets/sheet2.xml", sheet2),
+            ],
+        );
+
+        let result = OfficeParser::new().parse(&path, &ParseConfig::default()).await.unwrap();
+
+        let tables: Vec<&ContentChunk> = result
+            .chunks
+            .iter()
+            .filter(|c| c.chunk_type == ChunkType::Table)
+            .collect();
+        assert_eq!(tables.len(), 2);
+        assert_eq!(tables[0].content, "Item | Cost\nRent | 1200");
+        assert_eq!(tables[0].location.section, Some(1));
+        assert_eq!(tables[0].location.section_name.as_deref(), Some("Budget"));
+        assert_eq!(tables[1].content, "Paid monthly | TRUE");
+        assert_eq!(tables[1].location.section, Some(2));
+
+        // Without document properties the first sheet name is the title
+        assert_eq!(result.metadata.title, Some("Budget".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_parse_pptx_slides() {
+        let dir = TempDir::new().unwrap();
+        let presentation = r#"<p:presentation xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"
+    xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+  <p:sldIdLst><p:sldId id="256" r:id="rId3"/><p:sldId id="257" r:id="rId2"/></p:sldIdLst>
+</p:presentation>"#;
+        let rels = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rId2" Type="slide" Target="slides/slide2.xml"/>
+  <Relationship Id="rId3" Type="slide" Target="slides/slide1.xml"/>
+</Relationships>"#;
+        let slide = |title: &str, body: &str| {
+            format!(
+                r#"<p:sld xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"
+    xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"><p:cSld><p:spTree>
+  <p:sp><p:nvSpPr><p:nvPr><p:ph type="title"/></p:nvPr></p:nvSpPr>
+    <p:txBody><a:p><a:r><a:t>{}</a:t></a:r></a:p></p:txBody></p:sp>
+  <p:sp><p:txBody><a:p><a:r><a:t>{}</a:t></a:r></a:p></p:txBody></p:sp>
+</p:spTree></p:cSld></p:sld>"#,
+                title, body
+            )
+        };
+        let slide1 = slide("Roadmap", "Ship search in May");
+        let slide2 = slide("Risks", "Index size");
+        let path = create_package(
+            &dir,
+            "deck.pptx",
+            &[
+                ("ppt/presentation.xml", presentation),
+                ("ppt/_rels/presentation.xml.rels", rels),
+                ("ppt/slides/slide1.xml", slide1.as_str()),
+                ("ppt/slides/slide2.xml", slide2.as_str()),
+            ],
+        );
+
+        let result = OfficeParser::new().parse(&path, &ParseConfig::default()).await.unwrap();
+
+        let slides: Vec<(ChunkType, &str, Option<u32>)> = result
+            .chunks
+            .iter()
+            .map(|c| (c.chunk_type, c.content.as_str(), c.location.section))
+            .collect();
+        assert_eq!(
+            slides,
+            vec![
+                (ChunkType::Heading, "Roadmap", Some(1)),
+                (ChunkType::Slide, "Ship search in May", Some(1)),
+                (ChunkType::Heading, "Risks", Some(2)),
+                (ChunkType::Slide, "Index size", Some(2)),
+            ]
+        );
+        assert_eq!(result.chunks[3].location.section_name.as_deref(), Some("Risks"));
+        assert_eq!(result.metadata.page_count, Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_corrupted_package() {
+        let dir = TempDir::new().unwrap();
+        let service = ContentParserService::new();
+
+        let not_zip = create_temp_file(&dir, "broken.docx", "not a zip file").await;
+        let result = service.parse(&not_zip).await;
+        assert!(matches!(result, Err(ParseError::CorruptedFile { .. })));
+
+        // A zip without the main part is not a document
+        let empty = create_package(&dir, "empty.docx", &[("docProps/core.xml", CORE_PROPERTIES)]);
+        let result = service.parse(&empty).await;
+        assert!(matches!(result, Err(ParseError::CorruptedFile { .. })));
+    }
+}
+
+mod opendocument_parser_tests {
+    use super::*;
+
+    const META: &str = r#"<office:document-meta xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+    xmlns:meta="urn:oasis:names:tc:opendocument:xmlns:meta:1.0" xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <office:meta>
+    <dc:title>Field Guide</dc:title>
+    <meta:initial-creator>Mei Tan</meta:initial-creator>
+    <dc:creator>Someone Else</dc:creator>
+    <meta:creation-date>2023-04-05T10:30:00</meta:creation-date>
+    <meta:document-statistic meta:page-count="3"/>
+  </office:meta>
+</office:document-meta>"#;
+
+    /// Wrap an office body in a content.xml document
+    fn content(body: &str) -> String {
+        format!(
+            r#"<office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+    xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0"
+    xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0"
+    xmlns:draw="urn:oasis:names:tc:opendocument:xmlns:drawing:1.0"
+    xmlns:presentation="urn:oasis:names:tc:opendocument:xmlns:presentation:1.0">
+  <office:body>{}</office:body>
+</office:document-content>"#,
+            body
+        )
+    }
+
+    #[tokio::test]
+    async fn test_parse_odt() {
+        let dir = TempDir::new().unwrap();
+        let body = content(
+            r#"<office:text>
+  <text:h text:outline-level="1">Temples</text:h>
+  <text:p>Visit   early,<text:s text:c="2"/>before <text:span>the crowds</text:span>.<text:note><text:note-body><text:p>Footnote</text:p></text:note-body></text:note></text:p>
+  <text:list><text:list-item><text:p>Kiyomizu-dera</text:p></text:list-item></text:list>
+  <table:table>
+    <table:table-row><table:table-cell><text:p>Opens</text:p></table:table-cell><table:table-cell><text:p>6:00</text:p></table:table-cell></table:table-row>
+  </table:table>
+</office:text>"#,
+        );
+        let path = create_package(&dir, "guide.odt", &[("content.xml", body.as_str()), ("meta.xml", META)]);
+
+        let result = OpenDocumentParser::new().parse(&path, &ParseConfig::default()).await.unwrap();
+
+        let chunks: Vec<(ChunkType, &str)> = result
+            .chunks
+            .iter()
+            .map(|c| (c.chunk_type, c.content.as_str()))
+            .collect();
+        assert_eq!(
+            chunks,
+            vec![
+                (ChunkType::Heading, "Temples"),
+                (ChunkType::Paragraph, "Visit early,  before the crowds.\n\nKiyomizu-dera"),
+                (ChunkType::Table, "Opens | 6:00"),
+            ]
+        );
+        assert_eq!(result.metadata.title, Some("Field Guide".to_string()));
+        assert_eq!(result.metadata.author, Some("Mei Tan".to_string()));
+        assert_eq!(result.metadata.created_date, Some("2023-04-05T10:30:00".to_string()));
+        assert_eq!(result.metadata.page_count, Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_parse_ods_sheets() {
+        let dir = TempDir::new().unwrap();
+        let body = content(
+            r#"<office:spreadsheet>
+  <table:table table:name="Expenses">
+    <table:table-row><table:table-cell><text:p>Train</text:p></table:table-cell><table:table-cell office:value-type="float" office:value="14000"><text:p>14000</text:p></table:table-cell></table:table-row>
+    <table:table-row table:number-rows-repeated="1048575"><table:table-cell table:number-columns-repeated="1024"/></table:table-row>
+  </table:table>
+  <table:table table:name="Empty"/>
+</office:spreadsheet>"#,
+        );
+        let path = create_package(&dir, "trip.ods", &[("content.xml", body.as_str())]);
+
+        let result = OpenDocumentParser::new().parse(&path, &ParseConfig::default()).await.unwrap();
+
+        assert_eq!(result.chunks.len(), 2);
+        assert_eq!(result.chunks[0].chunk_type, ChunkType::Heading);
+        assert_eq!(result.chunks[1].chunk_type, ChunkType::Table);
+        assert_eq!(result.chunks[1].content, "Train | 14000");
+        assert_eq!(result.chunks[1].location.section, Some(1));
+        assert_eq!(result.chunks[1].location.section_name.as_deref(), Some("Expenses"));
+        assert_eq!(result.metadata.title, Some("Expenses".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_parse_odp_pages() {
+        let dir = TempDir::new().unwrap();
+        let body = content(
+            r#"<office:presentation>
+  <draw:page draw:name="page1">
+    <draw:frame presentation:class="title"><draw:text-box><text:p>Itinerary</text:p></draw:text-box></draw:frame>
+    <draw:frame presentation:class="outline"><draw:text-box><text:p>Day one: Arashiyama</text:p></draw:text-box></draw:frame>
+    <presentation:notes><draw:frame><draw:text-box><text:p>Speaker notes</text:p></draw:text-box></draw:frame></presentation:notes>
+  </draw:page>
+  <draw:page draw:name="page2">
+    <draw:frame><draw:text-box><text:p>Untitled page</text:p></draw:text-box></draw:frame>
+  </draw:page>
+</office:presentation>"#,
+        );
+        let path = create_package(&dir, "talk.odp", &[("content.xml", body.as_str()), ("meta.xml", META)]);
+
+        let result = OpenDocumentParser::new().parse(&path, &ParseConfig::default()).await.unwrap();
+
+        let chunks: Vec<(ChunkType, &str, Option<u32>)> = result
+            .chunks
+            .iter()
+            .map(|c| (c.chunk_type, c.content.as_str(), c.location.section))
+            .collect();
+        assert_eq!(
+            chunks,
+            vec![
+                (ChunkType::Heading, "Itinerary", Some(1)),
+                (ChunkType::Slide, "Day one: Arashiyama", Some(1)),
+                (ChunkType::Slide, "Untitled page", Some(2)),
+            ]
+        );
+        assert!(!result.text.contains("Speaker notes"));
+        // Pages are counted from the content, not the stored statistic
+        assert_eq!(result.metadata.page_count, Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_service_routes_opendocument() {
+        let dir = TempDir::new().unwrap();
+        let service = ContentParserService::new();
+
+        let body = content("<office:text><text:p>Routed</text:p></office:text>");
+        let path = create_package(&dir, "note.odt", &[("content.xml", body.as_str())]);
+        let result = service.parse(&path).await.unwrap();
+        assert_eq!(result.text, "Routed");
+    }
+}
+
+mod rtf_parser_tests {
+    use super::*;
+
+    const DOCUMENT: &str = r#"{\rtf1\ansi\ansicpg1252\deff0{\fonttbl{\f0 Times New Roman;}}{\colortbl;\red0\green0\blue0;}
+{\info{\title Field Notes}{\author Mei Tan}{\creatim\yr2023\mo4\dy5\hr10\min30}}
+{\*\generator Riched20 10.0;}
+\pard\outlinelevel0\b Kyoto trip\b0\par
+\pard Caf\'e9 visits in \u20140?\u37117?.\par
+\pard\intbl Day\cell Place\cell\row
+\pard\intbl 1\cell Gion\cell\row
+\pard Back {\i home}.\line Tired\par
+}"#;
+
+    #[tokio::test]
+    async fn test_parse_rtf() {
+        let dir = TempDir::new().unwrap();
+        let path = create_temp_file(&dir, "notes.rtf", DOCUMENT).await;
+
+        let result = RtfParser::new().parse(&path, &ParseConfig::default()).await.unwrap();
+
+        assert_eq!(
+            result.text,
+            "Kyoto trip\n\nCafé visits in 京都.\n\nDay | Place\n1 | Gion\n\nBack home.\nTired"
+        );
+        let kinds: Vec<ChunkType> = result.chunks.iter().map(|c| c.chunk_type).collect();
+        assert_eq!(
+            kinds,
+            vec![ChunkType::Heading, ChunkType::Paragraph, ChunkType::Table, ChunkType::Paragraph]
+        );
+        assert_eq!(result.metadata.title, Some("Field Notes".to_string()));
+        assert_eq!(result.metadata.author, Some("Mei Tan".to_string()));
+        assert_eq!(result.metadata.created_date, Some("2023-04-05T10:30:00".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_rtf_code_page() {
+        let dir = TempDir::new().unwrap();
+        // "日本" in Shift-JIS
+        let content = r"{\rtf1\ansi\ansicpg932 \'93\'fa\'96\'7b\par}";
+        let path = create_temp_file(&dir, "jp.rtf", content).await;
+
+        let result = RtfParser::new().parse(&path, &ParseConfig::default()).await.unwrap();
+        assert_eq!(result.text, "日本");
+    }
+
+    #[tokio::test]
+    async fn test_rtf_routing_and_header() {
+        let dir = TempDir::new().unwrap();
+        let service = ContentParserService::new();
+
+        // Control words no longer reach the index
+        let path = create_temp_file(&dir, "memo.rtf", r"{\rtf1\ansi {\fonttbl{\f0 Arial;}}\f0\fs24 Memo\par}").await;
+        let result = service.parse(&path).await.unwrap();
+        assert_eq!(result.text, "Memo");
+
+        let path = create_temp_file(&dir, "fake.rtf", "plain text").await;
+        let result = service.parse(&path).await;
+        assert!(matches!(result, Err(ParseError::CorruptedFile { .. })));
+    }
+}
+
+mod html_parser_tests {
+    use super::*;
+
+    const ARTICLE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>Kyoto in Spring | Travel Blog</title>
+  <meta name="author" content="Mei Tan">
+  <meta property="article:published_time" content="2024-04-02T08:00:00Z">
+  <script>var tracking = 1;</script>
+  <style>body { margin: 0; }</style>
+</head>
+<body>
+  <header><a href="/">Travel Blog</a><nav><a href="/">Home</a> <a href="/about">About</a></nav></header>
+  <div class="cookie-banner">We use cookies to improve your experience.</div>
+  <article>
+    <h1>Kyoto in Spring</h1>
+    <p>Cherry blossoms line the Philosopher's Path from late March,
+       and the walk is best done early in the morning.</p>
+    <h2>Getting there</h2>
+    <p>Take the Keihan line to Demachiyanagi and walk east along the canal for ten minutes.</p>
+    <table>
+      <tr><th>Spot</th><th>Peak</th></tr>
+      <tr><td>Maruyama Park</td><td>Early April</td></tr>
+    </table>
+    <div class="share-links"><a href="/share">Share this post</a></div>
+    <p><a href="/next">Read more posts</a></p>
+  </article>
+  <aside class="sidebar"><p>Popular posts this week</p></aside>
+  <footer>Copyright 2024 Travel Blog</footer>
+</body>
+</html>"#;
+
+    #[tokio::test]
+    async fn test_parse_html_article() {
+        let dir = TempDir::new().unwrap();
+        let path = create_temp_file(&dir, "kyoto.html", ARTICLE).await;
+
+        let result = HtmlParser::new().parse(&path, &ParseConfig::default()).await.unwrap();
+
+        let chunks: Vec<(ChunkType, &str)> = result
+            .chunks
+            .iter()
+            .map(|c| (c.chunk_type, c.content.as_str()))
+            .collect();
+        assert_eq!(
+            chunks,
+            vec![
+                (ChunkType::Heading, "Kyoto in Spring"),
+                (
+                    ChunkType::Paragraph,
+                    "Cherry blossoms line the Philosopher's Path from late March, and the walk is best done early in the morning."
+                ),
+                (ChunkType::Heading, "Getting there"),
+                (
+                    ChunkType::Paragraph,
+                    "Take the Keihan line to Demachiyanagi and walk east along the canal for ten minutes."
+                ),
+                (ChunkType::Table, "Spot | Peak\nMaruyama Park | Early April"),
+            ]
+        );
+        for boilerplate in ["tracking", "Home", "cookies", "Share", "Read more", "Popular", "Copyright"] {
+            assert!(!result.text.contains(boilerplate), "{} was indexed", boilerplate);
+        }
+
+        assert_eq!(result.metadata.title, Some("Kyoto in Spring | Travel Blog".to_string()));
+        assert_eq!(result.metadata.author, Some("Mei Tan".to_string()));
+        assert_eq!(result.metadata.created_date, Some("2024-04-02T08:00:00Z".to_string()));
+        assert_eq!(result.metadata.language, Some("en".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_html_without_article_markup() {
+        let dir = TempDir::new().unwrap();
+        let page = r#"<html><body>
+  <div id="menu"><a href="/">Home</a> | <a href="/archive">Archive</a></div>
+  <div class="wrapper"><div class="column">
+    <p>The moss garden at Saiho-ji requires a reservation made by postcard.</p>
+    <p>Visitors copy a sutra before they are allowed into the garden itself.</p>
+  </div></div>
+  <div id="colophon">Contact the editors</div>
+</body></html>"#;
+        let path = create_temp_file(&dir, "moss.htm", page).await;
+
+        let result = HtmlParser::new().parse(&path, &ParseConfig::default()).await.unwrap();
+
+        assert_eq!(
+            result.text,
+            "The moss garden at Saiho-ji requires a reservation made by postcard.\n\n\
+             Visitors copy a sutra before they are allowed into the garden itself."
+        );
+        assert_eq!(result.chunks.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_html_declared_charset() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("latin.html");
+        let page: &[u8] = b"<html><head><meta charset=\"windows-1252\"></head>\
+            <body><p>Caf\xe9 au lait and a long walk along the Kamo river.</p></body></html>";
+        fs::write(&path, page).unwrap();
+
+        let result = HtmlParser::new().parse(&path, &ParseConfig::default()).await.unwrap();
+        assert_eq!(result.text, "Café au lait and a long walk along the Kamo river.");
+    }
+
+    #[tokio::test]
+    async fn test_parse_mhtml() {
+        let dir = TempDir::new().unwrap();
+        let archive = [
+            "From: <Saved by Blink>",
+            "Snapshot-Content-Location: https://example.com/temples",
+            "Subject: Temple Notes",
+            "MIME-Version: 1.0",
+            "Content-Type: multipart/related; type=\"text/html\"; boundary=\"----boundary\"",
+            "",
+            "------boundary",
+            "Content-Type: text/html",
+            "Content-Transfer-Encoding: quoted-printable",
+            "Content-Location: https://example.com/temples",
+            "",
+            "<html><body><h1>Temple Notes</h1><p>Kinkaku-ji gl=",
+            "itters in the afternoon sun.</p></body></html>",
+            "------boundary--",
+            "",
+        ]
+        .join("\r\n");
+        let path = create_temp_file(&dir, "temples.mhtml", &archive).await;
+
+        let result = ContentParserService::new().parse(&path).await.unwrap();
+
+        let kinds: Vec<ChunkType> = result.chunks.iter().map(|c| c.chunk_type).collect();
+        assert_eq!(kinds, vec![ChunkType::Heading, ChunkType::Paragraph]);
+        assert_eq!(result.text, "Temple Notes\n\nKinkaku-ji glitters in the afternoon sun.");
+    }
+
+    #[tokio::test]
+    async fn test_parse_epub_spine() {
+        let dir = TempDir::new().unwrap();
+        let container = r#"<container xmlns="urn:oasis:names:tc:opendocument:xmlns:container" version="1.0">
+  <rootfiles><rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/></rootfiles>
+</container>"#;
+        let opf = r#"<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="id">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+    <dc:title>The Old Capital</dc:title>
+    <dc:creator>Yasunari Kawabata</dc:creator>
+    <dc:language>en</dc:language>
+    <dc:date>1962-01-01</dc:date>
+  </metadata>
+  <manifest>
+    <item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>
+    <item id="c2" href="Text/chapter%202.xhtml" media-type="application/xhtml+xml"/>
+    <item id="c1" href="Text/chapter1.xhtml" media-type="application/xhtml+xml"/>
+    <item id="css" href="style.css" media-type="text/css"/>
+  </manifest>
+  <spine><itemref idref="c1"/><itemref idref="c2"/></spine>
+</package>"#;
+        let nav = r#"<html xmlns="http://www.w3.org/1999/xhtml" xmlns:epub="http://www.idpf.org/2007/ops"><body>
+  <nav epub:type="toc"><ol>
+    <li><a href="Text/chapter1.xhtml">Spring Flowers</a></li>
+    <li><a href="Text/chapter%202.xhtml#start">The Convent</a></li>
+  </ol></nav>
+</body></html>"#;
+        let chapter1 = r#"<html xmlns="http://www.w3.org/1999/xhtml"><head><title>c1</title></head><body>
+  <h1>Violets</h1><p>Chieko noticed the violets had bloomed on the old maple.</p>
+</body></html>"#;
+        let chapter2 = r#"<html xmlns="http://www.w3.org/1999/xhtml"><body>
+  <p>The bell rang at dusk.</p>
+</body></html>"#;
+        let path = create_package(
+            &dir,
+            "capital.epub",
+            &[
+                ("mimetype", "application/epub+zip"),
+                ("META-INF/container.xml", container),
+                ("OEBPS/content.opf", opf),
+                ("OEBPS/nav.xhtml", nav),
+                ("OEBPS/Text/chapter1.xhtml", chapter1),
+                ("OEBPS/Text/chapter 2.xhtml", chapter2),
+            ],
+        );
+
+        let result = ContentParserService::new().parse(&path).await.unwrap();
+
+        let chunks: Vec<(ChunkType, &str, Option<u32>, Option<&str>)> = result
+            .chunks
+            .iter()
+            .map(|c| {
+                (
+                    c.chunk_type,
+                    c.content.as_str(),
+                    c.location.section,
+                    c.location.section_name.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            chunks,
+            vec![
+                (ChunkType::Heading, "Violets", Some(1), Some("Spring Flowers")),
+                (
+                    ChunkType::Paragraph,
+                    "Chieko noticed the violets had bloomed on the old maple.",
+                    Some(1),
+                    Some("Spring Flowers")
+                ),
+                (ChunkType::Paragraph, "The bell rang at dusk.", Some(2), Some("The Convent")),
+            ]
+        );
+        assert_eq!(result.metadata.title, Some("The Old Capital".to_string()));
+        assert_eq!(result.metadata.author, Some("Yasunari Kawabata".to_string()));
+        assert_eq!(result.metadata.language, Some("en".to_string()));
+        assert_eq!(result.metadata.created_date, Some("1962-01-01".to_string()));
+    }
+}
+
+mod email_parser_tests {
+    use super::*;
+    use crate::inference::DataAnonymizer;
+
+    fn message(from: &str, subject: &str, date: &str, body: &str) -> String {
+        [
+            format!("From: {}", from).as_str(),
+            "To: Kenji Sato <kenji@example.org>, hana@example.net",
+            format!("Subject: {}", subject).as_str(),
+            format!("Date: {}", date).as_str(),
+            "MIME-Version: 1.0",
+            "Content-Type: text/plain; charset=utf-8",
+            "",
+            body,
+            "",
+        ]
+        .join("\n")
+    }
+
+    #[tokio::test]
+    async fn test_parse_eml_with_attachment() {
+        let dir = TempDir::new().unwrap();
+        let eml = [
+            "From: \"Mei Tan\" <mei@example.com>",
+            "To: kenji@example.org",
+            "Cc: =?utf-8?q?Hana_M=C3=BCller?= <hana@example.net>",
+            "Subject: =?utf-8?q?Trip_itinerary_=E2=80=93_Kyoto?=",
+            "Date: Tue, 2 Apr 2024 09:30:00 +0900",
+            "MIME-Version: 1.0",
+            "Content-Type: multipart/mixed; boundary=\"outer\"",
+            "",
+            "--outer",
+            "Content-Type: multipart/alternative; boundary=\"inner\"",
+            "",
+            "--inner",
+            "Content-Type: text/plain; charset=utf-8",
+            "",
+            "Hi both,",
+            "",
+            "The ryokan is booked for three nights",
+            "near Gion.",
+            "--inner",
+            "Content-Type: text/html; charset=utf-8",
+            "",
+            "<p>Hi both,</p><p>The ryokan is booked for three nights near Gion.</p>",
+            "--inner--",
+            "--outer",
+            "Content-Type: application/pdf; name=\"booking.pdf\"",
+            "Content-Disposition: attachment; filename=\"booking.pdf\"",
+            "Content-Transfer-Encoding: base64",
+            "",
+            "JVBERi0xLjQK",
+            "--outer--",
+            "",
+        ]
+        .join("\r\n");
+        let path = create_temp_file(&dir, "itinerary.eml", &eml).await;
+
+        let result = ContentParserService::new().parse(&path).await.unwrap();
+
+        assert_eq!(
+            result.text,
+            "Trip itinerary – Kyoto\n\n\
+             From: Mei Tan <mei@example.com>\n\
+             To: kenji@example.org\n\
+             Cc: Hana Müller <hana@example.net>\n\
+             Date: 2024-04-02T09:30:00+09:00\n\
+             Attachments: booking.pdf\n\n\
+             Hi both,\n\n\
+             The ryokan is booked for three nights\nnear Gion."
+        );
+        assert_eq!(result.chunks[0].chunk_type, ChunkType::Heading);
+        for chunk in &result.chunks {
+            assert_eq!(chunk.location.section, Some(1));
+            assert_eq!(chunk.location.section_name.as_deref(), Some("Trip itinerary – Kyoto"));
+        }
+
+        assert_eq!(result.metadata.title, Some("Trip itinerary – Kyoto".to_string()));
+        assert_eq!(result.metadata.author, Some("Mei Tan <mei@example.com>".to_string()));
+        assert_eq!(
+            result.metadata.created_date,
+            Some("2024-04-02T09:30:00+09:00".to_string())
+        );
+        assert_eq!(result.metadata.page_count, None);
+    }
+
+    #[tokio::test]
+    async fn test_parse_html_only_eml() {
+        let dir = TempDir::new().unwrap();
+        let eml = [
+            "From: news@example.com",
+            "Subject: Weekly digest",
+            "Content-Type: text/html; charset=utf-8",
+            "",
+            "<html><body><h2>Temple openings</h2><p>Kiyomizu-dera extends its evening hours.</p></body></html>",
+            "",
+        ]
+        .join("\n");
+        let path = create_temp_file(&dir, "digest.eml", &eml).await;
+
+        let result = EmailParser::new().parse(&path, &ParseConfig::default()).await.unwrap();
+
+        let kinds: Vec<ChunkType> = result.chunks.iter().map(|c| c.chunk_type).collect();
+        assert_eq!(
+            kinds,
+            vec![ChunkType::Heading, ChunkType::Paragraph, ChunkType::Heading, ChunkType::Paragraph]
+        );
+        assert!(result.text.ends_with("Temple openings\n\nKiyomizu-dera extends its evening hours."));
+        assert!(!result.text.contains("<p>"));
+    }
+
+    #[tokio::test]
+    async fn test_parse_mbox_messages_as_sections() {
+        let dir = TempDir::new().unwrap();
+        let mbox = [
+            "From mei@example.com Tue Apr  2 09:30:00 2024".to_string(),
+            message(
+                "Mei Tan <mei@example.com>",
+                "Train tickets",
+                "Tue, 2 Apr 2024 09:30:00 +0900",
+                "Shinkansen seats are reserved for the 9:12 departure.",
+            ),
+            "From kenji@example.org Wed Apr  3 18:00:00 2024".to_string(),
+            message(
+                "Kenji Sato <kenji@example.org>",
+                "",
+                "Wed, 3 Apr 2024 18:00:00 +0900",
+                "I will bring the umbrellas.",
+            ),
+        ]
+        .join("\n");
+        let path = create_temp_file(&dir, "trip.mbox", &mbox).await;
+
+        let result = ContentParserService::new().parse(&path).await.unwrap();
+
+        let sections: Vec<(ChunkType, Option<u32>, Option<&str>)> = result
+            .chunks
+            .iter()
+            .map(|c| (c.chunk_type, c.location.section, c.location.section_name.as_deref()))
+            .collect();
+        assert_eq!(
+            sections,
+            vec![
+                (ChunkType::Heading, Some(1), Some("Train tickets")),
+                (ChunkType::Paragraph, Some(1), Some("Train tickets")),
+                (ChunkType::Heading, Some(2), None),
+                (ChunkType::Paragraph, Some(2), None),
+            ]
+        );
+        assert_eq!(result.chunks[2].content, "(no subject)");
+        assert!(result.chunks[3].content.ends_with("I will bring the umbrellas."));
+
+        assert_eq!(result.metadata.page_count, Some(2));
+        assert_eq!(result.metadata.title, None);
+        assert_eq!(result.metadata.author, None);
+    }
+
+    #[tokio::test]
+    async fn test_email_participants_are_anonymized_for_cloud() {
+        let dir = TempDir::new().unwrap();
+        let eml = message(
+            "Mei Tan <mei@example.com>",
+            "Dinner",
+            "Tue, 2 Apr 2024 19:00:00 +0900",
+            "Table for four at 7pm.",
+        );
+        let path = create_temp_file(&dir, "dinner.eml", &eml).await;
+
+        let result = EmailParser::new().parse(&path, &ParseConfig::default()).await.unwrap();
+        assert!(result.text.contains("mei@example.com"));
+
+        let anonymizer = DataAnonymizer::new();
+        let cloud_text = anonymizer.anonymize(&result.text);
+        let cloud_author = anonymizer.anonymize(result.metadata.author.as_deref().unwrap());
+        for participant in [
+            "mei@example.com",
+            "kenji@example.org",
+            "hana@example.net",
+            "Mei Tan",
+            "Kenji Sato",
+        ] {
+            assert!(!cloud_text.contains(participant), "{} reached the cloud text", participant);
+        }
+        assert!(cloud_text.contains("From: [CONTACT]\nTo: [CONTACT]"));
+        assert_eq!(cloud_author, "[CONTACT]");
+    }
+
+    #[tokio::test]
+    async fn test_eml_without_headers_is_rejected() {
+        let dir = TempDir::new().unwrap();
+        let path = create_temp_file(&dir, "empty.eml", "").await;
+
+        let result = EmailParser::new().parse(&path, &ParseConfig::default()).await;
+        assert!(matches!(result, Err(ParseError::CorruptedFile { .. })));
+    }
+}
+
+mod archive_parser_tests {
+    use super::*;
+    use std::io::Write;
+
+    /// Zip archive with binary members
+    fn create_zip(dir: &TempDir, name: &str, members: &[(&str, &[u8])]) -> std::path::PathBuf {
+        let path = dir.path().join(name);
+        let mut zip = zip::ZipWriter::new(fs::File::create(&path).unwrap());
+        for (member, data) in members {
+            zip.start_file(*member, zip::write::FileOptions::default()).unwrap();
+            zip.write_all(data).unwrap();
+        }
+        zip.finish().unwrap();
+        path
+    }
+
+    fn tar_with(members: &[(&str, &str)]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (member, content) in members {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            builder.append_data(&mut header, member, content.as_bytes()).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    fn chunk_members(result: &ParseResult) -> Vec<(Option<&str>, &str)> {
+        result
+            .chunks
+            .iter()
+            .map(|c| (c.location.member.as_deref(), c.content.as_str()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_parse_zip_members() {
+        let dir = TempDir::new().unwrap();
+        let inner = tar_with(&[("minutes.txt", "Kickoff meeting moved to Thursday.")]);
+        let path = create_zip(
+            &dir,
+            "project-2023.zip",
+            &[
+                ("docs/spec.md", b"The importer accepts CSV and JSON files.".as_slice()),
+                ("assets/logo.bin", [0u8, 159, 146, 150].as_slice()),
+                ("meetings/2023.tar", inner.as_slice()),
+            ],
+        );
+
+        let result = ContentParserService::new().parse(&path).await.unwrap();
+
+        assert_eq!(
+            chunk_members(&result),
+            vec![
+                (None, "docs/spec.md\nassets/logo.bin\nmeetings/2023.tar"),
+                (Some("docs/spec.md"), "The importer accepts CSV and JSON files."),
+                (Some("meetings/2023.tar"), "minutes.txt"),
+                (
+                    Some("meetings/2023.tar!/minutes.txt"),
+                    "Kickoff meeting moved to Thursday."
+                ),
+            ]
+        );
+        let indexes: Vec<u32> = result.chunks.iter().map(|c| c.chunk_index).collect();
+        assert_eq!(indexes, vec![0, 1, 2, 3]);
+        assert!(result.text.contains("Kickoff meeting moved to Thursday."));
+    }
+
+    #[tokio::test]
+    async fn test_archive_depth_limit() {
+        let dir = TempDir::new().unwrap();
+        let inner = tar_with(&[("minutes.txt", "Kickoff meeting moved to Thursday.")]);
+        let path = create_zip(&dir, "project.zip", &[("meetings/2023.tar", inner.as_slice())]);
+
+        let parser = ArchiveParser::with_limits(crate::archive::ArchiveLimits {
+            max_depth: 1,
+            ..Default::default()
+        });
+        let result = parser.parse(&path, &ParseConfig::default()).await.unwrap();
+
+        // The nested archive is listed but not opened
+        assert_eq!(chunk_members(&result), vec![(None, "meetings/2023.tar")]);
+    }
+
+    #[tokio::test]
+    async fn test_nested_archives_share_the_total_size_limit() {
+        let dir = TempDir::new().unwrap();
+        let inner = tar_with(&[("minutes.txt", "Kickoff meeting moved to Thursday.")]);
+        let path = create_zip(&dir, "project.zip", &[("meetings/2023.tar", inner.as_slice())]);
+
+        // Unpacking the outer archive leaves too little for the inner one
+        let parser = ArchiveParser::with_limits(crate::archive::ArchiveLimits {
+            max_total_size: inner.len() as u64 + 10,
+            ..Default::default()
+        });
+        let result = parser.parse(&path, &ParseConfig::default()).await.unwrap();
+
+        assert_eq!(
+            chunk_members(&result),
+            vec![(None, "meetings/2023.tar"), (Some("meetings/2023.tar"), "minutes.txt")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_oversized_member_is_listed_only() {
+        let dir = TempDir::new().unwrap();
+        let big = "word ".repeat(400);
+        let path = create_zip(
+            &dir,
+            "notes.zip",
+            &[("big.txt", big.as_bytes()), ("small.txt", b"Deer park in Nara.".as_slice())],
+        );
+
+        let parser = ArchiveParser::with_limits(crate::archive::ArchiveLimits {
+            max_member_size: 1000,
+            ..Default::default()
+        });
+        let result = parser.parse(&path, &ParseConfig::default()).await.unwrap();
+
+        assert_eq!(
+            chunk_members(&result),
+            vec![
+                (None, "big.txt\nsmall.txt"),
+                (Some("small.txt"), "Deer park in Nara."),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_plain_gzip_is_not_an_archive() {
+        let dir = TempDir::new().unwrap();
+        let path = create_temp_file(&dir, "notes.txt.gz", "not really gzip").await;
+
+        let result = ContentParserService::new().parse(&path).await;
+        assert!(matches!(result, Err(ParseError::UnsupportedFileType { .. })));
+    }
+}
+
+mod image_metadata_parser_tests {
+    use super::*;
+    use exif::{Field, In, Rational, Tag, Value};
+
+    const KYOTO_XMP: &str = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+ <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+  <rdf:Description rdf:about=""
+    xmlns:dc="http://purl.org/dc/elements/1.1/"
+    xmlns:photoshop="http://ns.adobe.com/photoshop/1.0/"
+    photoshop:City="Kyoto" photoshop:State="Kyoto">
+   <dc:subject>
+    <rdf:Bag><rdf:li>cherry blossom</rdf:li><rdf:li>temple</rdf:li><rdf:li>Temple</rdf:li></rdf:Bag>
+   </dc:subject>
+   <photoshop:Country>Japan</photoshop:Country>
+  </rdf:Description>
+ </rdf:RDF>
+</x:xmpmeta>"#;
+
+    fn ascii(tag: Tag, value: &str) -> Field {
+        Field {
+            tag,
+            ifd_num: In::PRIMARY,
+            value: Value::Ascii(vec![value.as_bytes().to_vec()]),
+        }
+    }
+
+    fn rationals(tag: Tag, values: &[u32]) -> Field {
+        Field {
+            tag,
+            ifd_num: In::PRIMARY,
+            value: Value::Rational(values.iter().map(|&num| Rational { num, denom: 1 }).collect()),
+        }
+    }
+
+    /// 64x48 JPEG with the given EXIF fields and XMP packet
+    fn create_photo(dir: &TempDir, name: &str, fields: &[Field], xmp: Option<&str>) -> std::path::PathBuf {
+        let mut jpeg = Vec::new();
+        image::DynamicImage::ImageRgb8(image::RgbImage::new(64, 48))
+            .write_to(&mut std::io::Cursor::new(&mut jpeg), image::ImageOutputFormat::Jpeg(80))
+            .unwrap();
+
+        let mut segments = Vec::new();
+        if !fields.is_empty() {
+            let mut writer = exif::experimental::Writer::new();
+            for field in fields {
+                writer.push_field(field);
+            }
+            let mut tiff = std::io::Cursor::new(Vec::new());
+            writer.write(&mut tiff, false).unwrap();
+            segments.push([b"Exif\0\0".as_slice(), tiff.get_ref()].concat());
+        }
+        if let Some(xmp) = xmp {
+            segments.push([b"http://ns.adobe.com/xap/1.0/\0".as_slice(), xmp.as_bytes()].concat());
+        }
+
+        // APP1 segments go right after the SOI marker
+        let mut bytes = jpeg[..2].to_vec();
+        for payload in segments {
+            bytes.extend_from_slice(&[0xFF, 0xE1]);
+            bytes.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+            bytes.extend_from_slice(&payload);
+        }
+        bytes.extend_from_slice(&jpeg[2..]);
+
+        let path = dir.path().join(name);
+        fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    fn kyoto_exif() -> Vec<Field> {
+        vec![
+            ascii(Tag::Make, "Canon"),
+            ascii(Tag::Model, "Canon EOS R5"),
+            ascii(Tag::LensModel, "RF24-105mm F4 L IS USM"),
+            ascii(Tag::DateTimeOriginal, "2023:04:02 10:15:00"),
+            ascii(Tag::OffsetTimeOriginal, "+09:00"),
+            Field {
+                tag: Tag::Orientation,
+                ifd_num: In::PRIMARY,
+                value: Value::Short(vec![6]),
+            },
+            ascii(Tag::GPSLatitudeRef, "N"),
+            rationals(Tag::GPSLatitude, &[35, 0, 42]),
+            ascii(Tag::GPSLongitudeRef, "E"),
+            rationals(Tag::GPSLongitude, &[135, 46, 5]),
+            rationals(Tag::GPSAltitude, &[50]),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_parse_photo_metadata() {
+        let dir = TempDir::new().unwrap();
+        let path = create_photo(&dir, "IMG_0412.jpg", &kyoto_exif(), Some(KYOTO_XMP));
+
+        let result = ContentParserService::new().parse(&path).await.unwrap();
+        let image = result.metadata.image.clone().expect("image metadata");
+
+        assert_eq!((image.width, image.height), (Some(64), Some(48)));
+        assert_eq!(
+            result.metadata.created_date.as_deref(),
+            Some("2023-04-02T10:15:00+09:00")
+        );
+        // Wall-clock time, so the date filter sees 2 April
+        assert_eq!(image.capture_timestamp(), Some(1680430500));
+        assert_eq!(image.camera().as_deref(), Some("Canon EOS R5"));
+        assert_eq!(image.lens.as_deref(), Some("RF24-105mm F4 L IS USM"));
+        assert_eq!(image.orientation, Some(6));
+        assert_eq!(image.location.as_deref(), Some("Kyoto, Japan"));
+        assert_eq!(image.keywords, vec!["cherry blossom", "temple"]);
+
+        let gps = image.gps.expect("gps position");
+        assert!((gps.latitude - 35.011_67).abs() < 1e-4);
+        assert!((gps.longitude - 135.768_06).abs() < 1e-4);
+        assert_eq!(gps.altitude, Some(50.0));
+        assert_eq!(image.privacy_level(), crate::core::types::PrivacyLevel::Sensitive);
+
+        assert_eq!(result.chunks.len(), 1);
+        assert_eq!(result.chunks[0].chunk_type, ChunkType::Caption);
+        assert_eq!(
+            result.text,
+            "Taken: 2023-04-02 10:15\nCamera: Canon EOS R5\nLens: RF24-105mm F4 L IS USM\n\
+             Location: Kyoto, Japan\nKeywords: cherry blossom, temple\nDimensions: 64 x 48"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gps_never_enters_text() {
+        let dir = TempDir::new().unwrap();
+        let path = create_photo(&dir, "IMG_0413.jpg", &kyoto_exif(), None);
+
+        let result = ContentParserService::new().parse(&path).await.unwrap();
+
+        assert!(result.metadata.image.as_ref().unwrap().gps.is_some());
+        assert!(!result.text.contains("35"));
+        assert!(!result.text.contains("135"));
+        assert!(result.chunks.iter().all(|c| !c.content.contains("135")));
+    }
+
+    #[tokio::test]
+    async fn test_zero_gps_is_ignored() {
+        let dir = TempDir::new().unwrap();
+        let fields = vec![
+            ascii(Tag::GPSLatitudeRef, "N"),
+            rationals(Tag::GPSLatitude, &[0, 0, 0]),
+            ascii(Tag::GPSLongitudeRef, "E"),
+            rationals(Tag::GPSLongitude, &[0, 0, 0]),
+        ];
+        let path = create_photo(&dir, "nofix.jpg", &fields, None);
+
+        let result = ContentParserService::new().parse(&path).await.unwrap();
+        let image = result.metadata.image.unwrap();
+        assert_eq!(image.gps, None);
+        assert_eq!(image.privacy_level(), crate::core::types::PrivacyLevel::Normal);
+    }
+
+    #[tokio::test]
+    async fn test_image_without_metadata() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("scan.png");
+        image::RgbImage::new(64, 48).save(&path).unwrap();
+
+        let result = ContentParserService::new().parse(&path).await.unwrap();
+        let image = result.metadata.image.unwrap();
+
+        assert_eq!(result.text, "Dimensions: 64 x 48");
+        assert_eq!(image.captured_at, None);
+        assert_eq!(image.gps, None);
+        assert!(image.keywords.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_corrupted_image() {
+        let dir = TempDir::new().unwrap();
+        let path = create_temp_file(&dir, "broken.jpg", "not an image").await;
+
+        let result = ContentParserService::new().parse(&path).await;
+        assert!(matches!(result, Err(ParseError::CorruptedFile { .. })));
+    }
+}
+
 mod chunk_creation_tests {
     use super::*;
 
@@ -410,6 +1551,35 @@ mod chunk_creation_tests {
         }
     }
 
+    #[test]
+    fn test_create_chunks_from_blocks() {
+        let file_id = Uuid::now_v7();
+        let blocks = vec![
+            DocumentBlock::new(ChunkType::Heading, "Introduction"),
+            DocumentBlock::new(ChunkType::Paragraph, "First paragraph."),
+            DocumentBlock::new(ChunkType::Paragraph, "  "),
+            DocumentBlock::new(ChunkType::Paragraph, "Second paragraph."),
+            DocumentBlock::new(ChunkType::Table, "a | b").in_section(2, Some("Data".to_string())),
+        ];
+        let config = ParseConfig::default();
+
+        let (text, chunks) = create_chunks_from_blocks(file_id, &blocks, &config);
+
+        assert_eq!(text, "Introduction\n\nFirst paragraph.\n\nSecond paragraph.\n\na | b");
+        let kinds: Vec<ChunkType> = chunks.iter().map(|c| c.chunk_type).collect();
+        assert_eq!(kinds, vec![ChunkType::Heading, ChunkType::Paragraph, ChunkType::Table]);
+        assert_eq!(chunks[1].content, "First paragraph.\n\nSecond paragraph.");
+
+        // Offsets point into the joined text
+        for (i, chunk) in chunks.iter().enumerate() {
+            assert_eq!(chunk.chunk_index, i as u32);
+            let range = chunk.location.start_offset as usize..chunk.location.end_offset as usize;
+            assert_eq!(&text[range], chunk.content);
+        }
+        assert_eq!(chunks[2].location.section, Some(2));
+        assert_eq!(chunks[2].location.section_name.as_deref(), Some("Data"));
+    }
+
     #[test]
     fn test_chunk_location_tracking() {
         let file_id = Uuid::now_v7();
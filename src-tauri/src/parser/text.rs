@@ -21,7 +21,7 @@ impl TextParser {
     pub fn new() -> Self {
         Self {
             supported_extensions: vec![
-                "txt", "md", "markdown", "rst", "rtf", "json", "yaml", "yml", "toml", "xml",
+                "txt", "md", "markdown", "rst", "json", "yaml", "yml", "toml", "xml",
                 "csv", "log", "ini", "cfg", "conf",
             ],
         }
@@ -122,6 +122,9 @@ impl TextParser {
                         end_line: Some(current_line + line_count.saturating_sub(1) as u32),
                         page_number: None,
                         bounding_box: None,
+                        section: None,
+                        section_name: None,
+                        member: None,
                     },
                     vector_id: 0,
                     created_at: chrono::Utc::now(),
@@ -4,6 +4,10 @@
 //! - Text files with snippet extraction and highlighting
 //! - Images with scaling and region marking
 //! - Documents (PDF) with page rendering and paragraph location
+//!
+//! Archive members are previewed like files on disk, addressed either by a
+//! virtual path (`archive.zip!/docs/spec.md`) or by the member recorded in
+//! a chunk's location.
 
 mod text;
 mod image;
@@ -15,8 +19,10 @@ pub use text::{TextPreviewGenerator, TextPreview, HighlightRange};
 pub use image::{ImagePreviewGenerator, ImagePreview, RegionMarker};
 pub use document::{DocumentPreviewGenerator, DocumentPreview, PagePreview};
 
+use crate::archive::{self, ArchiveError, ArchiveLimits};
 use crate::core::types::chunk::ChunkLocation;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -40,6 +46,9 @@ pub enum PreviewError {
 
     #[error("Document processing error: {reason}")]
     DocumentError { reason: String },
+
+    #[error("Archive error: {0}")]
+    Archive(#[from] ArchiveError),
 }
 
 /// Configuration for preview generation
@@ -96,11 +105,53 @@ impl PreviewService {
         }
     }
 
-    /// Generate a preview for a file
+    /// Generate a preview for a file or archive member
     pub async fn generate_preview(
         &self,
         path: &Path,
         file_id: Uuid,
+    ) -> Result<GeneratedPreview, PreviewError> {
+        match archive::split_virtual_path(path) {
+            Some((archive_path, member)) => {
+                let (_scratch, member_path) = unpack_member(&archive_path, &member).await?;
+                self.generate_file_preview(&member_path, file_id).await
+            }
+            None => self.generate_file_preview(path, file_id).await,
+        }
+    }
+
+    /// Generate a preview with highlight at specific location
+    ///
+    /// A location inside an archive member previews that member.
+    pub async fn generate_preview_with_highlight(
+        &self,
+        path: &Path,
+        file_id: Uuid,
+        location: &ChunkLocation,
+        query: Option<&str>,
+    ) -> Result<GeneratedPreview, PreviewError> {
+        let member = match &location.member {
+            Some(member) => Some((path.to_path_buf(), member.clone())),
+            None => archive::split_virtual_path(path),
+        };
+        match member {
+            Some((archive_path, member)) => {
+                let (_scratch, member_path) = unpack_member(&archive_path, &member).await?;
+                self.generate_file_preview_with_highlight(&member_path, file_id, location, query)
+                    .await
+            }
+            None => {
+                self.generate_file_preview_with_highlight(path, file_id, location, query)
+                    .await
+            }
+        }
+    }
+
+    /// Generate a preview for a file on disk
+    async fn generate_file_preview(
+        &self,
+        path: &Path,
+        file_id: Uuid,
     ) -> Result<GeneratedPreview, PreviewError> {
         let extension = path
             .extension()
@@ -136,8 +187,8 @@ impl PreviewService {
         }
     }
 
-    /// Generate a preview with highlight at specific location
-    pub async fn generate_preview_with_highlight(
+    /// Generate a preview of a file on disk with highlight at specific location
+    async fn generate_file_preview_with_highlight(
         &self,
         path: &Path,
         file_id: Uuid,
@@ -192,6 +243,28 @@ impl Default for PreviewService {
     }
 }
 
+/// Unpack an archive member to a scratch directory
+///
+/// The member is streamed to disk under the default limits and keeps its
+/// file name so the generator is chosen by extension; it is removed when
+/// the returned directory is dropped.
+async fn unpack_member(archive_path: &Path, member: &str) -> Result<(TempDir, PathBuf), PreviewError> {
+    let archive_path = archive_path.to_path_buf();
+    let member = member.to_string();
+    tokio::task::spawn_blocking(move || {
+        let scratch = tempfile::Builder::new().prefix("neuralfs-preview-").tempdir()?;
+        let file_name = member.rsplit('/').next().unwrap_or(&member).to_string();
+        let member_path = scratch.path().join(file_name);
+        let mut file = std::fs::File::create(&member_path)?;
+        archive::copy_member(&archive_path, &member, &ArchiveLimits::default(), &mut file)?;
+        Ok::<_, PreviewError>((scratch, member_path))
+    })
+    .await
+    .map_err(|e| PreviewError::GenerationFailed {
+        reason: format!("Task join error: {}", e),
+    })?
+}
+
 /// Generated preview result
 #[derive(Debug, Clone)]
 pub enum GeneratedPreview {
@@ -156,6 +156,9 @@ async fn test_preview_with_highlight() {
         end_line: Some(27),
         page_number: None,
         bounding_box: None,
+        section: None,
+        section_name: None,
+        member: None,
     };
 
     let preview = service
@@ -245,3 +248,52 @@ fn test_generated_preview_content_type() {
     });
     assert_eq!(doc_preview.content_type(), "application/json");
 }
+
+#[tokio::test]
+async fn test_preview_archive_member() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let archive_path = dir.path().join("project-2023.zip");
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(&archive_path).unwrap());
+    zip.start_file("docs/spec.md", zip::write::FileOptions::default()).unwrap();
+    for i in 1..=30 {
+        writeln!(zip, "Line {}: the importer accepts CSV files", i).unwrap();
+    }
+    zip.finish().unwrap();
+
+    let service = PreviewService::new();
+    let file_id = uuid::Uuid::new_v4();
+
+    let virtual_path = crate::archive::virtual_path(&archive_path, "docs/spec.md");
+    match service.generate_preview(&virtual_path, file_id).await.unwrap() {
+        GeneratedPreview::Text(text_preview) => {
+            assert!(text_preview.snippet.starts_with("Line 1: the importer"));
+            assert_eq!(text_preview.total_lines, 30);
+        }
+        _ => panic!("Expected text preview"),
+    }
+
+    // Search hits carry the member in their location
+    let location = crate::core::types::chunk::ChunkLocation {
+        start_line: Some(20),
+        end_line: Some(20),
+        member: Some("docs/spec.md".to_string()),
+        ..Default::default()
+    };
+    match service
+        .generate_preview_with_highlight(&archive_path, file_id, &location, Some("csv"))
+        .await
+        .unwrap()
+    {
+        GeneratedPreview::Text(text_preview) => {
+            assert!(text_preview.snippet.contains("Line 20:"));
+            assert!(!text_preview.highlights.is_empty());
+        }
+        _ => panic!("Expected text preview"),
+    }
+
+    let missing = crate::archive::virtual_path(&archive_path, "docs/missing.md");
+    assert!(matches!(
+        service.generate_preview(&missing, file_id).await,
+        Err(PreviewError::Archive(_))
+    ));
+}
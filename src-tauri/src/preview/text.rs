@@ -300,6 +300,9 @@ mod tests {
             end_line: Some(12),
             page_number: None,
             bounding_box: None,
+            section: None,
+            section_name: None,
+            member: None,
         };
 
         let preview = generator
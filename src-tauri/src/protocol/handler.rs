@@ -313,6 +313,13 @@ pub fn build_preview_url(state: &ProtocolState, uuid: Uuid, use_protocol: bool)
     }
 }
 
+/// Helper function to build archive member URLs with token
+///
+/// Members are only served by the HTTP asset server.
+pub fn build_archive_member_url(state: &ProtocolState, uuid: Uuid, member: &str) -> String {
+    state.asset_state.archive_member_url(uuid, member)
+}
+
 /// Helper function to build file URLs with token
 pub fn build_file_url(state: &ProtocolState, uuid: Uuid, use_protocol: bool) -> String {
     let token = state.get_session_token();
@@ -8,6 +8,9 @@
 //! - Thumbnail serving: `nfs://thumbnail/{uuid}`
 //! - Preview serving: `nfs://preview/{uuid}`
 //! - File serving: `nfs://file/{uuid}`
+//!
+//! Archive members are only served by the HTTP asset server; see
+//! `build_archive_member_url`.
 
 mod handler;
 #[cfg(test)]
@@ -15,5 +18,6 @@ mod tests;
 
 pub use handler::{
     register_custom_protocol, ProtocolState, ProtocolConfig,
-    get_session_token, AssetProtocolHandler,
+    get_session_token, AssetProtocolHandler, SessionTokenResponse,
+    build_thumbnail_url, build_preview_url, build_file_url, build_archive_member_url,
 };
@@ -7,12 +7,13 @@
 #[cfg(test)]
 mod tests;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use chrono::{DateTime, Utc};
+use futures::stream::{self, FuturesUnordered, StreamExt};
 use serde::{Deserialize, Serialize};
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
@@ -20,6 +21,29 @@ use crate::core::error::{NeuralFSError, Result};
 use crate::core::types::{FileRecord, FileType, IndexStatus, PrivacyLevel};
 use crate::watcher::{DirectoryFilter, FilterResult};
 
+/// `content_hash` of rows that have not been indexed yet
+const PENDING_HASH: &str = "pending";
+
+/// Confidence of a move given by identical size and content alone
+const CONTENT_MATCH_CONFIDENCE: f32 = 0.6;
+
+/// Added in proportion to how similar the file names are
+const NAME_SIMILARITY_WEIGHT: f32 = 0.3;
+
+/// Added when neither file has another match
+const UNIQUE_MATCH_BONUS: f32 = 0.1;
+
+/// Move candidates offered for one group of identical files; rows of a
+/// larger group beyond these are treated as deleted
+const MAX_GROUP_CANDIDATES: usize = 16;
+
+/// Pairs of one group of identical files scored for those candidates
+const MAX_GROUP_PAIRS: usize = 4096;
+
+/// Fewer deletions than this never trip the ratio threshold, so small roots
+/// do not need confirmation for every file
+const MIN_DELETIONS_FOR_RATIO: usize = 10;
+
 /// Platform-specific file identifier for tracking files across renames
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct FileId {
@@ -131,6 +155,18 @@ impl FileId {
         }
     }
 
+    /// Whether both files are on the same volume
+    pub fn same_volume(&self, other: &FileId) -> bool {
+        #[cfg(windows)]
+        {
+            self.volume_serial == other.volume_serial
+        }
+        #[cfg(unix)]
+        {
+            self.device == other.device
+        }
+    }
+
     /// Parse from string representation
     pub fn from_string_repr(s: &str) -> Option<Self> {
         let parts: Vec<&str> = s.split(':').collect();
@@ -177,7 +213,7 @@ pub struct FsFileInfo {
 }
 
 /// Rename event detected during reconciliation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct RenameEvent {
     /// Old file path (from database)
     pub old_path: PathBuf,
@@ -185,19 +221,72 @@ pub struct RenameEvent {
     pub new_path: PathBuf,
     /// File ID that links the two
     pub file_id: FileId,
+    /// How sure the match is: 1.0 for a matching FileID, lower for
+    /// files paired by content
+    pub confidence: f32,
 }
 
-/// Result of reconciliation operation
+/// A possible move found by content that needs the user's confirmation
+///
+/// The missing file's row is kept, with its tags and relations, until the
+/// candidate is resolved.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoveCandidate {
+    /// Candidate id
+    pub id: Uuid,
+    /// Database id of the missing file
+    pub file_id: Uuid,
+    /// Path the file was indexed under
+    pub old_path: PathBuf,
+    /// Path where identical content appeared
+    pub new_path: PathBuf,
+    /// Score of the match, from 0.0 to 1.0
+    pub confidence: f32,
+}
+
+/// Deletions in one root held back for the user's confirmation
+#[derive(Debug, Clone, Serialize)]
+pub struct HeldDeletions {
+    /// Monitored root the files were in
+    pub root: PathBuf,
+    /// Files that would be deleted
+    pub paths: Vec<PathBuf>,
+    /// Files indexed under the root
+    pub indexed_files: usize,
+}
+
+/// What resolving a move candidate leaves for the index to apply
 #[derive(Debug, Default)]
+pub struct MoveResolution {
+    /// Confirmed move of the indexed file (old path, new path)
+    pub moved: Option<(PathBuf, PathBuf)>,
+    /// Missing files with no candidates left, to remove from the index
+    pub removed: Vec<PathBuf>,
+}
+
+/// Result of reconciliation operation
+#[derive(Debug, Default, Serialize)]
 pub struct ReconcileResult {
     /// Files that were added (new files in filesystem)
     pub added: Vec<PathBuf>,
     /// Files that were deleted (missing from filesystem)
     pub deleted: Vec<PathBuf>,
+    /// Database ids of the deleted files, so derived data (vectors,
+    /// text index) can be purged after the rows are gone
+    pub deleted_file_ids: Vec<Uuid>,
     /// Files that were modified (content changed)
     pub modified: Vec<PathBuf>,
     /// Files that were renamed (detected via FileID)
     pub renamed: Vec<RenameEvent>,
+    /// Files whose mtime changed but whose content hash still matches;
+    /// only their recorded mtime is refreshed
+    pub touched: Vec<PathBuf>,
+    /// Possible moves too ambiguous to apply without confirmation
+    pub move_candidates: Vec<MoveCandidate>,
+    /// Deletions over the safety threshold, not applied
+    pub held_deletions: Vec<HeldDeletions>,
+    /// Roots that were not available and were left untouched
+    pub skipped_roots: Vec<PathBuf>,
     /// Errors encountered during reconciliation
     pub errors: Vec<(PathBuf, String)>,
 }
@@ -220,14 +309,24 @@ impl ReconcileResult {
 /// Configuration for reconciliation
 #[derive(Debug, Clone)]
 pub struct ReconcileConfig {
-    /// Maximum parallel scans
+    /// Maximum directories scanned (and files hashed) concurrently
     pub max_parallel_scans: usize,
-    /// Batch size for database operations
+    /// Rows loaded per database page and written per transaction
     pub batch_size: usize,
-    /// Use fast mode (only check mtime and size)
+    /// Use fast mode (only check mtime and size); when off, every indexed
+    /// file is hashed
     pub fast_mode: bool,
-    /// Verify content hash for modified files
+    /// Verify content hash for files whose mtime changed, so touching a
+    /// file without changing it does not cost a reindex
     pub verify_hash: bool,
+    /// Pair deleted and added files with identical content into moves
+    pub match_content_moves: bool,
+    /// Confidence a content match needs to be applied without asking
+    pub move_confidence_threshold: f32,
+    /// Deletions in a root above which the user must confirm them
+    pub max_unconfirmed_deletions: usize,
+    /// Share of a root's files whose deletion the user must confirm
+    pub max_unconfirmed_deletion_ratio: f32,
 }
 
 impl Default for ReconcileConfig {
@@ -236,7 +335,11 @@ impl Default for ReconcileConfig {
             max_parallel_scans: 4,
             batch_size: 1000,
             fast_mode: true,
-            verify_hash: false,
+            verify_hash: true,
+            match_content_moves: true,
+            move_confidence_threshold: 0.8,
+            max_unconfirmed_deletions: 1000,
+            max_unconfirmed_deletion_ratio: 0.5,
         }
     }
 }
@@ -250,6 +353,62 @@ struct DbFileRecord {
     size_bytes: u64,
     modified_at: DateTime<Utc>,
     content_hash: String,
+    /// Whether `content_hash` describes the current index
+    indexed: bool,
+    /// Whether move candidates are waiting for confirmation
+    awaiting_move: bool,
+}
+
+/// Columns read into a `DbFileRecord`
+const RECORD_COLUMNS: &str = "id, path, file_id, size_bytes, modified_at, content_hash, index_status,
+    EXISTS (SELECT 1 FROM move_candidates WHERE move_candidates.file_id = files.id)";
+
+/// Row of `RECORD_COLUMNS`
+type DbRecordRow = (String, String, Option<String>, i64, String, String, String, bool);
+
+/// Build a record from a row of `RECORD_COLUMNS`
+fn db_record(row: DbRecordRow) -> DbFileRecord {
+    let (id, path, file_id, size_bytes, modified_at, content_hash, index_status, awaiting_move) = row;
+    let file_id = file_id.as_ref().and_then(|s| FileId::from_string_repr(s));
+    let modified_at = DateTime::parse_from_rfc3339(&modified_at)
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now());
+
+    DbFileRecord {
+        id: Uuid::parse_str(&id).unwrap_or_else(|_| Uuid::now_v7()),
+        path: PathBuf::from(&path),
+        file_id,
+        size_bytes: size_bytes as u64,
+        modified_at,
+        content_hash,
+        indexed: index_status == "Indexed",
+        awaiting_move,
+    }
+}
+
+/// What one scanned directory differs in from the database
+#[derive(Default)]
+struct DirectoryDiff {
+    /// Files both on disk and in the database
+    present: Vec<(DbFileRecord, FsFileInfo)>,
+    /// Files on disk without a row
+    added: Vec<FsFileInfo>,
+    /// Rows without a file on disk
+    missing: Vec<DbFileRecord>,
+    /// Subdirectories still to scan
+    subdirs: Vec<PathBuf>,
+    /// Why the directory could not be listed; its rows are left alone
+    unreadable: Option<String>,
+}
+
+/// A database write produced by reconciliation
+enum Change<'a> {
+    Rename(&'a RenameEvent),
+    Delete(&'a Path),
+    Modify(&'a Path),
+    Add(&'a Path),
+    Touch(&'a Path),
+    Candidate(&'a MoveCandidate),
 }
 
 /// File system reconciliation service
@@ -292,201 +451,726 @@ impl ReconciliationService {
     }
 
     /// Execute reconciliation on startup
+    ///
+    /// Deletions above the safety threshold of a root are held back,
+    /// reported in `held_deletions` and kept in the database until
+    /// confirmed with `confirm_held_deletions`.
     pub async fn reconcile_on_startup(
         &self,
         monitored_paths: &[PathBuf],
     ) -> Result<ReconcileResult> {
-        let mut result = ReconcileResult::default();
+        let result = self.plan(monitored_paths, true).await?;
+        self.apply_reconcile_result(&result).await?;
+        self.record_held(monitored_paths, &result).await?;
+        Ok(result)
+    }
 
-        // 1. Load all known files from database
-        let db_files = self.load_db_files().await?;
-        let mut db_file_map: HashMap<PathBuf, DbFileRecord> = db_files
-            .into_iter()
-            .map(|f| (f.path.clone(), f))
-            .collect();
+    /// Report what reconciliation would change without changing anything
+    pub async fn reconcile_dry_run(&self, monitored_paths: &[PathBuf]) -> Result<ReconcileResult> {
+        self.plan(monitored_paths, true).await
+    }
 
-        // Build FileID -> DbFileRecord map for rename detection
-        let mut file_id_to_db: HashMap<String, DbFileRecord> = HashMap::new();
-        for record in db_file_map.values() {
-            if let Some(ref file_id) = record.file_id {
-                file_id_to_db.insert(file_id.to_string_repr(), record.clone());
+    /// Reconcile and apply deletions regardless of the safety threshold
+    pub async fn reconcile_confirmed(&self, monitored_paths: &[PathBuf]) -> Result<ReconcileResult> {
+        let result = self.plan(monitored_paths, false).await?;
+        self.apply_reconcile_result(&result).await?;
+        self.record_held(monitored_paths, &result).await?;
+        Ok(result)
+    }
+
+    /// Deletions held back for the user's confirmation, per root
+    pub async fn held_deletions(&self) -> Result<Vec<HeldDeletions>> {
+        let rows: Vec<(String, String, i64)> =
+            sqlx::query_as("SELECT root, path, indexed_files FROM held_deletions ORDER BY root, path")
+                .fetch_all(&self.db)
+                .await
+                .map_err(NeuralFSError::Database)?;
+
+        let mut held: Vec<HeldDeletions> = Vec::new();
+        for (root, path, indexed_files) in rows {
+            let root = PathBuf::from(root);
+            match held.last_mut() {
+                Some(last) if last.root == root => last.paths.push(PathBuf::from(path)),
+                _ => held.push(HeldDeletions {
+                    root,
+                    paths: vec![PathBuf::from(path)],
+                    indexed_files: indexed_files as usize,
+                }),
             }
         }
+        Ok(held)
+    }
 
-        // 2. Scan filesystem
-        let fs_files = self.scan_filesystem(monitored_paths).await?;
-        let fs_file_map: HashMap<PathBuf, FsFileInfo> = fs_files
-            .into_iter()
-            .map(|f| (f.path.clone(), f))
-            .collect();
+    /// Hold deletions found outside reconciliation, e.g. by the file
+    /// watcher, when they are over the safety threshold of `root`
+    ///
+    /// `paths` are indexed files below `root` that went missing. Once a root
+    /// has deletions waiting, later ones join them. Returns whether the
+    /// deletions were held.
+    pub async fn hold_if_over_threshold(&self, root: &Path, paths: &[PathBuf]) -> Result<bool> {
+        if paths.is_empty() {
+            return Ok(false);
+        }
 
-        // 3. Calculate differences
+        let (waiting,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM held_deletions WHERE root = ?")
+            .bind(root.to_string_lossy().to_string())
+            .fetch_one(&self.db)
+            .await
+            .map_err(NeuralFSError::Database)?;
+        let prefix = root_prefix(root);
+        let (indexed_files,): (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM files WHERE is_excluded = 0 AND substr(path, 1, length(?)) = ?",
+        )
+        .bind(&prefix)
+        .bind(&prefix)
+        .fetch_one(&self.db)
+        .await
+        .map_err(NeuralFSError::Database)?;
 
-        // 3.1 Check for new files and renames
-        for (path, fs_info) in &fs_file_map {
-            if !db_file_map.contains_key(path) {
-                // File not in DB at this path - check if it's a rename
-                let file_id_str = fs_info.file_id.to_string_repr();
-                if let Some(old_record) = file_id_to_db.get(&file_id_str) {
-                    // This is a rename - same FileID, different path
-                    result.renamed.push(RenameEvent {
-                        old_path: old_record.path.clone(),
-                        new_path: path.clone(),
-                        file_id: fs_info.file_id,
-                    });
-                    // Remove from db_file_map so it's not marked as deleted
-                    db_file_map.remove(&old_record.path);
-                } else {
-                    // Truly new file
-                    result.added.push(path.clone());
+        let indexed_files = indexed_files as usize;
+        if waiting == 0 && !self.needs_confirmation(paths.len(), indexed_files) {
+            return Ok(false);
+        }
+
+        tracing::warn!(
+            "Holding {} of {} deletions in {:?} until confirmed",
+            paths.len(),
+            indexed_files,
+            root
+        );
+        let held = HeldDeletions {
+            root: root.to_path_buf(),
+            paths: paths.to_vec(),
+            indexed_files,
+        };
+        for batch in held.paths.chunks(self.config.batch_size.max(1)) {
+            let mut tx = self.db.begin().await.map_err(NeuralFSError::Database)?;
+            for path in batch {
+                self.handle_held(&mut tx, &held.root, path, held.indexed_files).await?;
+            }
+            tx.commit().await.map_err(NeuralFSError::Database)?;
+        }
+        Ok(true)
+    }
+
+    /// Apply the held deletions of `root` the user confirmed
+    ///
+    /// Only `paths` held under `root` are touched: those still missing are
+    /// removed from the index, those that reappeared are released. The
+    /// result lists the removed files so derived data can be purged.
+    pub async fn confirm_held_deletions(&self, root: &Path, paths: &[PathBuf]) -> Result<ReconcileResult> {
+        let root_str = root.to_string_lossy().to_string();
+        let mut result = ReconcileResult::default();
+
+        for batch in paths.chunks(self.config.batch_size.max(1)) {
+            let mut tx = self.db.begin().await.map_err(NeuralFSError::Database)?;
+            for path in batch {
+                let path_str = path.to_string_lossy().to_string();
+                let held: Option<(String,)> = sqlx::query_as(
+                    r#"
+                    SELECT files.id FROM held_deletions
+                    JOIN files ON files.path = held_deletions.path
+                    WHERE held_deletions.path = ? AND held_deletions.root = ?
+                    "#,
+                )
+                .bind(&path_str)
+                .bind(&root_str)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(NeuralFSError::Database)?;
+                let Some((id,)) = held else {
+                    continue;
+                };
+
+                sqlx::query("DELETE FROM held_deletions WHERE path = ?")
+                    .bind(&path_str)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(NeuralFSError::Database)?;
+                if tokio::fs::symlink_metadata(path).await.is_ok() {
+                    tracing::info!("Released held deletion of {:?}: the file is back", path);
+                    continue;
                 }
+
+                self.handle_deletion(&mut tx, path).await?;
+                result.deleted.push(path.clone());
+                if let Ok(id) = Uuid::parse_str(&id) {
+                    result.deleted_file_ids.push(id);
+                }
+            }
+            tx.commit().await.map_err(NeuralFSError::Database)?;
+        }
+
+        Ok(result)
+    }
+
+    /// Replace the held deletions of the reconciled roots with the ones
+    /// `result` held
+    ///
+    /// Skipped roots keep theirs: nothing was decided about them.
+    async fn record_held(&self, monitored_paths: &[PathBuf], result: &ReconcileResult) -> Result<()> {
+        let mut tx = self.db.begin().await.map_err(NeuralFSError::Database)?;
+        for root in monitored_paths.iter().filter(|root| !result.skipped_roots.contains(root)) {
+            sqlx::query("DELETE FROM held_deletions WHERE root = ?")
+                .bind(root.to_string_lossy().to_string())
+                .execute(&mut *tx)
+                .await
+                .map_err(NeuralFSError::Database)?;
+        }
+        for held in &result.held_deletions {
+            for path in &held.paths {
+                self.handle_held(&mut tx, &held.root, path, held.indexed_files).await?;
+            }
+        }
+        tx.commit().await.map_err(NeuralFSError::Database)?;
+        Ok(())
+    }
+
+    /// Work out the differences between the database and the filesystem
+    ///
+    /// Directories are scanned concurrently and each is diffed against the
+    /// rows of its direct children, loaded in pages of `batch_size`, so only
+    /// changed files are held until rename detection. Rows in directories
+    /// the scan never reached (removed or excluded) are found by a final
+    /// pass over the database. Rows outside the available roots, and below
+    /// directories that could not be read, are left alone.
+    async fn plan(&self, monitored_paths: &[PathBuf], hold_mass_deletions: bool) -> Result<ReconcileResult> {
+        let mut result = ReconcileResult::default();
+
+        // 0. Skip roots that are not there, e.g. an unplugged drive
+        let mut roots = Vec::with_capacity(monitored_paths.len());
+        for root in monitored_paths {
+            if self.root_available(root).await? {
+                roots.push(root.clone());
+            } else {
+                tracing::warn!("Skipping unavailable root {:?}; its files are kept", root);
+                result.skipped_roots.push(root.clone());
             }
         }
 
-        // 3.2 Check for deleted files
-        for (path, _) in &db_file_map {
-            if !fs_file_map.contains_key(path) {
-                // Check if this was already handled as a rename
-                let is_renamed = result.renamed.iter().any(|r| r.old_path == *path);
-                if !is_renamed {
-                    result.deleted.push(path.clone());
+        // 1. Scan directories, at most `max_parallel_scans` at a time, and
+        //    diff each against its rows
+        if let Some(ref filter) = self.filter {
+            filter.set_monitored_roots(monitored_paths);
+        }
+        let mut visited: HashSet<PathBuf> = HashSet::new();
+        let mut pending: Vec<PathBuf> = roots.clone();
+        let mut in_flight = FuturesUnordered::new();
+        let limit = self.config.max_parallel_scans.max(1);
+        let mut present = Vec::new();
+        let mut new_files: Vec<FsFileInfo> = Vec::new();
+        let mut missing: Vec<DbFileRecord> = Vec::new();
+        let mut unreadable: Vec<PathBuf> = Vec::new();
+
+        loop {
+            while in_flight.len() < limit {
+                let Some(dir) = pending.pop() else {
+                    break;
+                };
+                // Nested roots are reached from their parent as well
+                if visited.insert(dir.clone()) {
+                    in_flight.push(self.reconcile_directory(dir));
                 }
             }
+
+            let Some(diff) = in_flight.next().await else {
+                break;
+            };
+            let (dir, diff) = diff?;
+            if let Some(reason) = diff.unreadable {
+                // A permission or I/O error says nothing about its files
+                tracing::warn!("Failed to read directory {:?}, keeping its files: {}", dir, reason);
+                result.errors.push((dir.clone(), reason));
+                unreadable.push(dir);
+                continue;
+            }
+            pending.extend(diff.subdirs);
+            new_files.extend(diff.added);
+            missing.extend(diff.missing);
+            present.extend(diff.present);
+
+            // 1.1 Check files that exist in both for modifications
+            if present.len() >= self.config.batch_size.max(1) {
+                self.check_modified(std::mem::take(&mut present), &mut result)
+                    .await;
+            }
         }
+        self.check_modified(present, &mut result).await;
+
+        // 2. Stream known files from database, page by page: rows below
+        //    directories the scan did not reach are missing unless an
+        //    unreadable directory hid them, and every row counts towards its
+        //    root's deletion threshold
+        let mut indexed_per_root = vec![0usize; roots.len()];
+        let mut after: Option<String> = None;
+        loop {
+            let page = self.load_db_page(after.as_deref()).await?;
+            let Some(last) = page.last() else {
+                break;
+            };
+            after = Some(last.path.to_string_lossy().to_string());
 
-        // 3.3 Check for modified files
-        for (path, fs_info) in &fs_file_map {
-            if let Some(db_record) = db_file_map.get(path) {
-                // File exists in both - check if modified
-                if self.is_file_modified(db_record, fs_info) {
-                    result.modified.push(path.clone());
+            for record in page {
+                let Some(root) = root_of(&record.path, &roots) else {
+                    continue;
+                };
+                indexed_per_root[root] += 1;
+                let reached = record.path.parent().is_some_and(|dir| visited.contains(dir));
+                let hidden = unreadable.iter().any(|dir| record.path.starts_with(dir));
+                // Kept until the user resolves its move candidates
+                if !reached && !hidden && !record.awaiting_move {
+                    missing.push(record);
                 }
             }
         }
 
-        // 4. Apply changes to database
-        self.apply_reconcile_result(&result).await?;
+        // 3. Files left in the scan are new or renamed; a FileID that
+        //    belonged to a missing row means the file moved
+        let mut missing_by_id: HashMap<FileId, usize> = missing
+            .iter()
+            .enumerate()
+            .filter_map(|(index, record)| record.file_id.map(|file_id| (file_id, index)))
+            .collect();
+        let mut renamed_rows = vec![false; missing.len()];
+        let mut unmatched = Vec::new();
+
+        for fs_info in new_files {
+            if let Some(index) = missing_by_id.remove(&fs_info.file_id) {
+                result.renamed.push(RenameEvent {
+                    old_path: missing[index].path.clone(),
+                    new_path: fs_info.path,
+                    file_id: fs_info.file_id,
+                    confidence: 1.0,
+                });
+                renamed_rows[index] = true;
+            } else {
+                unmatched.push(fs_info);
+            }
+        }
+        let mut new_files = unmatched;
+        let mut missing: Vec<DbFileRecord> = missing
+            .into_iter()
+            .zip(renamed_rows)
+            .filter(|(_, renamed)| !renamed)
+            .map(|(record, _)| record)
+            .collect();
 
-        Ok(result)
-    }
+        // 3.1 Files copied to another volume or restored from a backup get
+        //     a new FileID; pair those by content instead
+        if self.config.match_content_moves {
+            self.match_content_moves(&mut missing, &mut new_files, &mut result)
+                .await;
+        }
 
-    /// Check if a file has been modified
-    fn is_file_modified(&self, db_record: &DbFileRecord, fs_info: &FsFileInfo) -> bool {
-        // Fast mode: only check mtime and size
-        if self.config.fast_mode {
-            return fs_info.modified_at > db_record.modified_at
-                || fs_info.size_bytes != db_record.size_bytes;
+        // 3.2 Whatever is left was added or deleted
+        result.added.extend(new_files.into_iter().map(|f| f.path));
+        let mut missing_per_root: Vec<Vec<DbFileRecord>> = vec![Vec::new(); roots.len()];
+        for record in missing {
+            if let Some(root) = root_of(&record.path, &roots) {
+                missing_per_root[root].push(record);
+            }
         }
 
-        // Full mode: also verify hash (not implemented yet)
-        fs_info.modified_at > db_record.modified_at || fs_info.size_bytes != db_record.size_bytes
+        // 3.3 Losing much of a root at once needs the user's confirmation
+        for (root, records) in missing_per_root.into_iter().enumerate() {
+            let indexed_files = indexed_per_root[root];
+            if hold_mass_deletions && self.needs_confirmation(records.len(), indexed_files) {
+                tracing::warn!(
+                    "Holding {} of {} deletions in {:?} until confirmed",
+                    records.len(),
+                    indexed_files,
+                    roots[root]
+                );
+                result.held_deletions.push(HeldDeletions {
+                    root: roots[root].clone(),
+                    paths: records.into_iter().map(|r| r.path).collect(),
+                    indexed_files,
+                });
+                continue;
+            }
+            for record in records {
+                result.deleted.push(record.path);
+                result.deleted_file_ids.push(record.id);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Whether deleting `deletions` of a root's `indexed_files` is over the
+    /// safety threshold
+    fn needs_confirmation(&self, deletions: usize, indexed_files: usize) -> bool {
+        let over_ratio = deletions >= MIN_DELETIONS_FOR_RATIO
+            && deletions as f32 > indexed_files as f32 * self.config.max_unconfirmed_deletion_ratio;
+        deletions > self.config.max_unconfirmed_deletions || over_ratio
     }
 
-    /// Load all files from database
-    async fn load_db_files(&self) -> Result<Vec<DbFileRecord>> {
-        let rows = sqlx::query!(
+    /// Whether a root can be scanned
+    ///
+    /// A missing root is unavailable. So is an empty one whose indexed files
+    /// were on another volume: the mount point of an unplugged drive.
+    async fn root_available(&self, root: &Path) -> Result<bool> {
+        let mut entries = match tokio::fs::read_dir(root).await {
+            Ok(entries) => entries,
+            Err(_) => return Ok(false),
+        };
+        if matches!(entries.next_entry().await, Ok(Some(_))) {
+            return Ok(true);
+        }
+
+        let prefix = root_prefix(root);
+        let stored: Option<(String,)> = sqlx::query_as(
             r#"
-            SELECT id, path, file_id, size_bytes, modified_at, content_hash
-            FROM files
-            WHERE is_excluded = 0
-            "#
+            SELECT file_id FROM files
+            WHERE substr(path, 1, length(?)) = ? AND file_id IS NOT NULL
+            LIMIT 1
+            "#,
         )
-        .fetch_all(&self.db)
+        .bind(&prefix)
+        .bind(&prefix)
+        .fetch_optional(&self.db)
         .await
         .map_err(NeuralFSError::Database)?;
 
-        let mut records = Vec::with_capacity(rows.len());
-        for row in rows {
-            let file_id = row.file_id.as_ref().and_then(|s| FileId::from_string_repr(s));
-            let modified_at = DateTime::parse_from_rfc3339(&row.modified_at)
-                .map(|dt| dt.with_timezone(&Utc))
-                .unwrap_or_else(|_| Utc::now());
+        let stored = stored.and_then(|(repr,)| FileId::from_string_repr(&repr));
+        match (stored, FileId::from_path(root)) {
+            (Some(stored), Ok(current)) => Ok(stored.same_volume(&current)),
+            _ => Ok(true),
+        }
+    }
 
-            records.push(DbFileRecord {
-                id: Uuid::parse_str(&row.id).unwrap_or_else(|_| Uuid::now_v7()),
-                path: PathBuf::from(&row.path),
-                file_id,
-                size_bytes: row.size_bytes as u64,
-                modified_at,
-                content_hash: row.content_hash,
-            });
+    /// Pair missing rows with new files of identical size and content
+    ///
+    /// Rows and files are grouped by content hash. A group holding one
+    /// missing row and one new file becomes a rename when its score, from
+    /// the content match and the similarity of the file names, reaches
+    /// `move_confidence_threshold`, and a move candidate otherwise. Larger
+    /// groups are copies of the same content and are never moved without
+    /// asking: their best-named pairs, at most `MAX_GROUP_CANDIDATES` of
+    /// the first `MAX_GROUP_PAIRS`, become candidates. Rows with candidates
+    /// are kept until the user resolves them.
+    async fn match_content_moves(
+        &self,
+        missing: &mut Vec<DbFileRecord>,
+        new_files: &mut Vec<FsFileInfo>,
+        result: &mut ReconcileResult,
+    ) {
+        // Only indexed rows have a hash to compare; empty files all match
+        let mut groups: HashMap<String, (Vec<usize>, Vec<usize>)> = HashMap::new();
+        let mut sizes = HashSet::new();
+        for (index, record) in missing.iter().enumerate() {
+            if record.indexed && record.content_hash != PENDING_HASH && record.size_bytes > 0 {
+                groups.entry(record.content_hash.clone()).or_default().0.push(index);
+                sizes.insert(record.size_bytes);
+            }
+        }
+        if groups.is_empty() {
+            return;
         }
 
-        Ok(records)
+        let to_hash: Vec<(usize, PathBuf)> = new_files
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| sizes.contains(&f.size_bytes))
+            .map(|(index, f)| (index, f.path.clone()))
+            .collect();
+        let hashes: Vec<(usize, std::io::Result<String>)> = stream::iter(to_hash)
+            .map(|(index, path)| async move { (index, hash_content(path).await) })
+            .buffer_unordered(self.config.max_parallel_scans.max(1))
+            .collect()
+            .await;
+
+        for (new_index, hash) in hashes {
+            match hash {
+                Ok(hash) => {
+                    if let Some(group) = groups.get_mut(&hash) {
+                        group.1.push(new_index);
+                    }
+                }
+                Err(e) => {
+                    let path = &new_files[new_index].path;
+                    tracing::warn!("Failed to hash {:?}: {}", path, e);
+                    result.errors.push((path.clone(), e.to_string()));
+                }
+            }
+        }
+
+        let mut moved_missing = HashSet::new();
+        let mut moved_new = HashSet::new();
+        let mut awaiting = HashSet::new();
+        for (missing_side, new_side) in groups.into_values() {
+            // (missing index, new file index, confidence)
+            let mut pairs: Vec<(usize, usize, f32)> = Vec::new();
+            if let ([m], [n]) = (missing_side.as_slice(), new_side.as_slice()) {
+                let (m, n) = (*m, *n);
+                let confidence = move_confidence(&missing[m].path, &new_files[n].path, true);
+                if confidence >= self.config.move_confidence_threshold {
+                    result.renamed.push(RenameEvent {
+                        old_path: missing[m].path.clone(),
+                        new_path: new_files[n].path.clone(),
+                        file_id: new_files[n].file_id,
+                        confidence,
+                    });
+                    moved_missing.insert(m);
+                    moved_new.insert(n);
+                    continue;
+                }
+                pairs.push((m, n, confidence));
+            } else {
+                let scored = missing_side
+                    .iter()
+                    .flat_map(|&m| new_side.iter().map(move |&n| (m, n)))
+                    .take(MAX_GROUP_PAIRS);
+                for (m, n) in scored {
+                    let confidence = move_confidence(&missing[m].path, &new_files[n].path, false);
+                    pairs.push((m, n, confidence));
+                }
+                pairs.sort_by(|a, b| b.2.total_cmp(&a.2));
+                pairs.truncate(MAX_GROUP_CANDIDATES);
+            }
+
+            for (m, n, confidence) in pairs {
+                result.move_candidates.push(MoveCandidate {
+                    id: Uuid::now_v7(),
+                    file_id: missing[m].id,
+                    old_path: missing[m].path.clone(),
+                    new_path: new_files[n].path.clone(),
+                    confidence,
+                });
+                awaiting.insert(m);
+            }
+        }
+
+        *missing = std::mem::take(missing)
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| !moved_missing.contains(index) && !awaiting.contains(index))
+            .map(|(_, record)| record)
+            .collect();
+        *new_files = std::mem::take(new_files)
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| !moved_new.contains(index))
+            .map(|(_, file)| file)
+            .collect();
     }
 
+    /// Whether the content hash of files present on disk is checked
+    fn hashes_content(&self) -> bool {
+        self.config.verify_hash || !self.config.fast_mode
+    }
 
-    /// Scan filesystem for files
-    async fn scan_filesystem(&self, paths: &[PathBuf]) -> Result<Vec<FsFileInfo>> {
-        let mut all_files = Vec::new();
+    /// Sort files present both on disk and in the database into modified
+    /// and touched
+    ///
+    /// Fast mode compares mtime and size. With `verify_hash`, files whose
+    /// size is unchanged but whose mtime moved are hashed, and only count as
+    /// modified if the hash differs from the indexed one. Full mode hashes
+    /// every indexed file, catching edits that preserved the mtime.
+    async fn check_modified(
+        &self,
+        present: Vec<(DbFileRecord, FsFileInfo)>,
+        result: &mut ReconcileResult,
+    ) {
+        let mut to_hash = Vec::new();
+        for (db_record, fs_info) in present {
+            let metadata_changed = self.is_file_modified(&db_record, &fs_info);
+            let hash_known = db_record.indexed && db_record.content_hash != PENDING_HASH;
+            let needs_hash = self.hashes_content()
+                && hash_known
+                && fs_info.size_bytes == db_record.size_bytes
+                && (metadata_changed || !self.config.fast_mode);
+
+            if needs_hash {
+                to_hash.push((db_record, fs_info.path, metadata_changed));
+            } else if metadata_changed {
+                result.modified.push(fs_info.path);
+            }
+        }
 
-        for base_path in paths {
-            if !base_path.exists() {
-                continue;
+        let mut hashed = stream::iter(to_hash)
+            .map(|(db_record, path, metadata_changed)| async move {
+                let hash = hash_content(path.clone()).await;
+                (db_record, path, metadata_changed, hash)
+            })
+            .buffer_unordered(self.config.max_parallel_scans.max(1));
+
+        while let Some((db_record, path, metadata_changed, hash)) = hashed.next().await {
+            match hash {
+                Ok(hash) if hash == db_record.content_hash => {
+                    if metadata_changed {
+                        result.touched.push(path);
+                    }
+                }
+                Ok(_) => result.modified.push(path),
+                Err(e) => {
+                    tracing::warn!("Failed to hash {:?}: {}", path, e);
+                    result.errors.push((path.clone(), e.to_string()));
+                    if metadata_changed {
+                        result.modified.push(path);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Check if a file's metadata says it has been modified
+    fn is_file_modified(&self, db_record: &DbFileRecord, fs_info: &FsFileInfo) -> bool {
+        fs_info.modified_at > db_record.modified_at || fs_info.size_bytes != db_record.size_bytes
+    }
+
+    /// Load the next page of files from database, ordered by path
+    async fn load_db_page(&self, after: Option<&str>) -> Result<Vec<DbFileRecord>> {
+        let sql = format!(
+            r#"
+            SELECT {RECORD_COLUMNS}
+            FROM files
+            WHERE is_excluded = 0 AND (? IS NULL OR path > ?)
+            ORDER BY path
+            LIMIT ?
+            "#
+        );
+        let rows: Vec<DbRecordRow> = sqlx::query_as(&sql)
+            .bind(after)
+            .bind(after)
+            .bind(self.config.batch_size.max(1) as i64)
+            .fetch_all(&self.db)
+            .await
+            .map_err(NeuralFSError::Database)?;
+
+        Ok(rows.into_iter().map(db_record).collect())
+    }
+
+    /// Load the next page of files directly inside `dir`, ordered by path
+    ///
+    /// The path range keeps the query on the path index; rows in
+    /// subdirectories are dropped by the separator check.
+    async fn load_directory_page(&self, dir: &Path, after: Option<&str>) -> Result<Vec<DbFileRecord>> {
+        let prefix = root_prefix(dir);
+        // First string after every path starting with the prefix
+        let mut upper = prefix.clone();
+        upper.pop();
+        upper.push((std::path::MAIN_SEPARATOR as u8 + 1) as char);
+
+        let sql = format!(
+            r#"
+            SELECT {RECORD_COLUMNS}
+            FROM files
+            WHERE is_excluded = 0 AND path > ? AND path < ?
+                AND instr(substr(path, ?), ?) = 0
+            ORDER BY path
+            LIMIT ?
+            "#
+        );
+        let rows: Vec<DbRecordRow> = sqlx::query_as(&sql)
+            .bind(after.unwrap_or(&prefix))
+            .bind(&upper)
+            .bind(prefix.chars().count() as i64 + 1)
+            .bind(std::path::MAIN_SEPARATOR.to_string())
+            .bind(self.config.batch_size.max(1) as i64)
+            .fetch_all(&self.db)
+            .await
+            .map_err(NeuralFSError::Database)?;
+
+        Ok(rows.into_iter().map(db_record).collect())
+    }
+
+    /// Scan one directory and diff its files against the rows of its direct
+    /// children
+    async fn reconcile_directory(&self, dir: PathBuf) -> Result<(PathBuf, DirectoryDiff)> {
+        let (files, subdirs) = match self.scan_directory(dir.clone()).await {
+            Ok(listing) => listing,
+            Err(e) => {
+                let diff = DirectoryDiff {
+                    unreadable: Some(e.to_string()),
+                    ..DirectoryDiff::default()
+                };
+                return Ok((dir, diff));
             }
+        };
+        let mut files: HashMap<PathBuf, FsFileInfo> =
+            files.into_iter().map(|f| (f.path.clone(), f)).collect();
+        let mut diff = DirectoryDiff {
+            subdirs,
+            ..DirectoryDiff::default()
+        };
 
-            let files = self.scan_directory(base_path).await?;
-            all_files.extend(files);
+        let mut after: Option<String> = None;
+        loop {
+            let page = self.load_directory_page(&dir, after.as_deref()).await?;
+            let Some(last) = page.last() else {
+                break;
+            };
+            after = Some(last.path.to_string_lossy().to_string());
+
+            for record in page {
+                match files.remove(&record.path) {
+                    Some(fs_info) => diff.present.push((record, fs_info)),
+                    // Kept until the user resolves its move candidates
+                    None if record.awaiting_move => {}
+                    None => diff.missing.push(record),
+                }
+            }
         }
 
-        Ok(all_files)
+        diff.added = files.into_values().collect();
+        Ok((dir, diff))
     }
 
-    /// Scan a single directory recursively
-    async fn scan_directory(&self, path: &Path) -> Result<Vec<FsFileInfo>> {
+    /// Scan the entries of a single directory
+    ///
+    /// Returns the files found and the subdirectories still to scan. An
+    /// excluded directory, or one removed since its parent was listed, is
+    /// empty; a directory that cannot be listed in full is an error.
+    async fn scan_directory(&self, path: PathBuf) -> std::io::Result<(Vec<FsFileInfo>, Vec<PathBuf>)> {
         let mut files = Vec::new();
-        let mut stack = vec![path.to_path_buf()];
+        let mut subdirs = Vec::new();
 
-        while let Some(current_path) = stack.pop() {
-            // Check filter
+        // Check filter
+        if let Some(ref filter) = self.filter {
+            if let FilterResult::Exclude(_) = filter.should_filter(&path) {
+                return Ok((files, subdirs));
+            }
+        }
+
+        let mut entries = match tokio::fs::read_dir(&path).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((files, subdirs)),
+            Err(e) => return Err(e),
+        };
+
+        while let Some(entry) = entries.next_entry().await? {
+            let entry_path = entry.path();
+
+            // Check filter for each entry
             if let Some(ref filter) = self.filter {
-                if let FilterResult::Exclude(_) = filter.should_filter(&current_path) {
+                if let FilterResult::Exclude(_) = filter.should_filter(&entry_path) {
                     continue;
                 }
             }
 
-            let entries = match tokio::fs::read_dir(&current_path).await {
-                Ok(entries) => entries,
+            let metadata = match entry.metadata().await {
+                Ok(m) => m,
                 Err(e) => {
-                    tracing::warn!("Failed to read directory {:?}: {}", current_path, e);
+                    tracing::warn!("Failed to get metadata for {:?}: {}", entry_path, e);
                     continue;
                 }
             };
 
-            let mut entries = entries;
-            while let Ok(Some(entry)) = entries.next_entry().await {
-                let entry_path = entry.path();
-
-                // Check filter for each entry
-                if let Some(ref filter) = self.filter {
-                    if let FilterResult::Exclude(_) = filter.should_filter(&entry_path) {
-                        continue;
-                    }
-                }
-
-                let metadata = match entry.metadata().await {
-                    Ok(m) => m,
+            if metadata.is_dir() {
+                subdirs.push(entry_path);
+            } else if metadata.is_file() {
+                // Get file info
+                match self.get_file_info(&entry_path, &metadata).await {
+                    Ok(info) => files.push(info),
                     Err(e) => {
-                        tracing::warn!("Failed to get metadata for {:?}: {}", entry_path, e);
-                        continue;
-                    }
-                };
-
-                if metadata.is_dir() {
-                    stack.push(entry_path);
-                } else if metadata.is_file() {
-                    // Get file info
-                    match self.get_file_info(&entry_path, &metadata).await {
-                        Ok(info) => files.push(info),
-                        Err(e) => {
-                            tracing::warn!("Failed to get file info for {:?}: {}", entry_path, e);
-                        }
+                        tracing::warn!("Failed to get file info for {:?}: {}", entry_path, e);
                     }
                 }
             }
         }
 
-        Ok(files)
+        Ok((files, subdirs))
     }
 
     /// Get file information including FileID
@@ -518,26 +1202,42 @@ impl ReconciliationService {
     }
 
     /// Apply reconciliation result to database
+    ///
+    /// Changes are written in transactions of `batch_size` rows.
     async fn apply_reconcile_result(&self, result: &ReconcileResult) -> Result<()> {
-        // Handle renames - update path while preserving all other data
-        for rename in &result.renamed {
-            self.handle_rename(&rename.old_path, &rename.new_path, &rename.file_id)
-                .await?;
-        }
-
-        // Handle deletions - mark files as deleted or remove from index
-        for path in &result.deleted {
-            self.handle_deletion(path).await?;
-        }
-
-        // Handle modifications - mark for re-indexing
-        for path in &result.modified {
-            self.handle_modification(path).await?;
-        }
+        let changes: Vec<Change<'_>> = result
+            .renamed
+            .iter()
+            .map(Change::Rename)
+            .chain(result.deleted.iter().map(|p| Change::Delete(p.as_path())))
+            .chain(result.modified.iter().map(|p| Change::Modify(p.as_path())))
+            .chain(result.added.iter().map(|p| Change::Add(p.as_path())))
+            .chain(result.touched.iter().map(|p| Change::Touch(p.as_path())))
+            .chain(result.move_candidates.iter().map(Change::Candidate))
+            .collect();
 
-        // Handle additions - create new file records
-        for path in &result.added {
-            self.handle_addition(path).await?;
+        for batch in changes.chunks(self.config.batch_size.max(1)) {
+            let mut tx = self.db.begin().await.map_err(NeuralFSError::Database)?;
+            for change in batch {
+                match change {
+                    // Update path while preserving all other data
+                    Change::Rename(rename) => {
+                        self.handle_rename(&mut tx, &rename.old_path, &rename.new_path, &rename.file_id)
+                            .await?
+                    }
+                    // Remove from index
+                    Change::Delete(path) => self.handle_deletion(&mut tx, path).await?,
+                    // Mark for re-indexing
+                    Change::Modify(path) => self.handle_modification(&mut tx, path).await?,
+                    // Create new file records
+                    Change::Add(path) => self.handle_addition(&mut tx, path).await?,
+                    // Content unchanged - only remember the new mtime
+                    Change::Touch(path) => self.handle_touch(&mut tx, path).await?,
+                    // Keep the missing file until the user decides
+                    Change::Candidate(candidate) => self.handle_move_candidate(&mut tx, candidate).await?,
+                }
+            }
+            tx.commit().await.map_err(NeuralFSError::Database)?;
         }
 
         Ok(())
@@ -546,6 +1246,7 @@ impl ReconciliationService {
     /// Handle a file rename
     async fn handle_rename(
         &self,
+        conn: &mut SqliteConnection,
         old_path: &Path,
         new_path: &Path,
         file_id: &FileId,
@@ -578,7 +1279,7 @@ impl ReconciliationService {
             file_id_str,
             old_path_str
         )
-        .execute(&self.db)
+        .execute(&mut *conn)
         .await
         .map_err(NeuralFSError::Database)?;
 
@@ -587,7 +1288,7 @@ impl ReconciliationService {
     }
 
     /// Handle a file deletion
-    async fn handle_deletion(&self, path: &Path) -> Result<()> {
+    async fn handle_deletion(&self, conn: &mut SqliteConnection, path: &Path) -> Result<()> {
         let path_str = path.to_string_lossy().to_string();
 
         // Delete the file record (cascades to chunks, tags, relations)
@@ -597,7 +1298,7 @@ impl ReconciliationService {
             "#,
             path_str
         )
-        .execute(&self.db)
+        .execute(&mut *conn)
         .await
         .map_err(NeuralFSError::Database)?;
 
@@ -605,8 +1306,34 @@ impl ReconciliationService {
         Ok(())
     }
 
+    /// Keep a missing file's row until its deletion is confirmed
+    async fn handle_held(
+        &self,
+        conn: &mut SqliteConnection,
+        root: &Path,
+        path: &Path,
+        indexed_files: usize,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO held_deletions (path, root, indexed_files, held_at)
+            SELECT path, ?, ?, ? FROM files WHERE path = ?
+            ON CONFLICT(path) DO UPDATE SET root = excluded.root, indexed_files = excluded.indexed_files
+            "#,
+        )
+        .bind(root.to_string_lossy().to_string())
+        .bind(indexed_files as i64)
+        .bind(Utc::now().to_rfc3339())
+        .bind(path.to_string_lossy().to_string())
+        .execute(&mut *conn)
+        .await
+        .map_err(NeuralFSError::Database)?;
+
+        Ok(())
+    }
+
     /// Handle a file modification
-    async fn handle_modification(&self, path: &Path) -> Result<()> {
+    async fn handle_modification(&self, conn: &mut SqliteConnection, path: &Path) -> Result<()> {
         let path_str = path.to_string_lossy().to_string();
 
         // Mark file for re-indexing
@@ -618,7 +1345,7 @@ impl ReconciliationService {
             "#,
             path_str
         )
-        .execute(&self.db)
+        .execute(&mut *conn)
         .await
         .map_err(NeuralFSError::Database)?;
 
@@ -627,7 +1354,7 @@ impl ReconciliationService {
     }
 
     /// Handle a new file addition
-    async fn handle_addition(&self, path: &Path) -> Result<()> {
+    async fn handle_addition(&self, conn: &mut SqliteConnection, path: &Path) -> Result<()> {
         // Get file metadata
         let metadata = tokio::fs::metadata(path).await.map_err(|e| {
             NeuralFSError::FileSystem(crate::core::error::FileSystemError::ReadFailed {
@@ -668,7 +1395,7 @@ impl ReconciliationService {
             .to_rfc3339();
 
         // Placeholder hash - will be computed during indexing
-        let content_hash = "pending".to_string();
+        let content_hash = PENDING_HASH.to_string();
 
         sqlx::query!(
             r#"
@@ -694,7 +1421,7 @@ impl ReconciliationService {
             modified_at,
             file_id_str
         )
-        .execute(&self.db)
+        .execute(&mut *conn)
         .await
         .map_err(NeuralFSError::Database)?;
 
@@ -702,6 +1429,63 @@ impl ReconciliationService {
         Ok(())
     }
 
+    /// Handle a file whose content is unchanged but whose mtime moved
+    async fn handle_touch(&self, conn: &mut SqliteConnection, path: &Path) -> Result<()> {
+        let metadata = tokio::fs::metadata(path).await.map_err(|e| {
+            NeuralFSError::FileSystem(crate::core::error::FileSystemError::ReadFailed {
+                path: path.display().to_string(),
+                reason: e.to_string(),
+            })
+        })?;
+        let modified_at = metadata
+            .modified()
+            .map(DateTime::<Utc>::from)
+            .unwrap_or_else(|_| Utc::now())
+            .to_rfc3339();
+
+        sqlx::query("UPDATE files SET modified_at = ? WHERE path = ?")
+            .bind(modified_at)
+            .bind(path.to_string_lossy().to_string())
+            .execute(&mut *conn)
+            .await
+            .map_err(NeuralFSError::Database)?;
+
+        tracing::debug!("Content unchanged, refreshed mtime: {:?}", path);
+        Ok(())
+    }
+
+    /// Record a possible move for the user to confirm
+    async fn handle_move_candidate(
+        &self,
+        conn: &mut SqliteConnection,
+        candidate: &MoveCandidate,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO move_candidates (
+                id, file_id, old_path, new_path, confidence, detected_at
+            ) VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(candidate.id.to_string())
+        .bind(candidate.file_id.to_string())
+        .bind(candidate.old_path.to_string_lossy().to_string())
+        .bind(candidate.new_path.to_string_lossy().to_string())
+        .bind(candidate.confidence)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&mut *conn)
+        .await
+        .map_err(NeuralFSError::Database)?;
+
+        tracing::info!(
+            "Possible move needs confirmation: {:?} -> {:?} ({:.2})",
+            candidate.old_path,
+            candidate.new_path,
+            candidate.confidence
+        );
+        Ok(())
+    }
+
     /// Update FileID for an existing file record
     pub async fn update_file_id(&self, path: &Path) -> Result<FileId> {
         let file_id = FileId::from_path(path)?;
@@ -752,6 +1536,110 @@ impl ReconciliationService {
         cache.clear();
     }
 
+    /// Possible moves waiting for the user's confirmation
+    pub async fn move_candidates(&self) -> Result<Vec<MoveCandidate>> {
+        let rows: Vec<(String, String, String, String, f32)> = sqlx::query_as(
+            r#"
+            SELECT id, file_id, old_path, new_path, confidence
+            FROM move_candidates
+            ORDER BY old_path, confidence DESC
+            "#,
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(NeuralFSError::Database)?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(id, file_id, old_path, new_path, confidence)| {
+                Some(MoveCandidate {
+                    id: Uuid::parse_str(&id).ok()?,
+                    file_id: Uuid::parse_str(&file_id).ok()?,
+                    old_path: PathBuf::from(old_path),
+                    new_path: PathBuf::from(new_path),
+                    confidence,
+                })
+            })
+            .collect())
+    }
+
+    /// Confirm or reject a move candidate
+    ///
+    /// Confirming drops every other candidate of the file and every other
+    /// claim on the new path. Missing files left without candidates are
+    /// returned for removal; applying the result to the index (moving or
+    /// removing rows with their derived data) is up to the caller. Returns
+    /// `None` for unknown candidates.
+    pub async fn resolve_move_candidate(
+        &self,
+        candidate_id: Uuid,
+        accept: bool,
+    ) -> Result<Option<MoveResolution>> {
+        let Some(candidate) = self
+            .move_candidates()
+            .await?
+            .into_iter()
+            .find(|c| c.id == candidate_id)
+        else {
+            return Ok(None);
+        };
+
+        let file_id = candidate.file_id.to_string();
+        let new_path = candidate.new_path.to_string_lossy().to_string();
+        let mut tx = self.db.begin().await.map_err(NeuralFSError::Database)?;
+
+        // Files whose claims are dropped, to check for remaining candidates
+        let mut affected = vec![(
+            file_id.clone(),
+            candidate.old_path.to_string_lossy().to_string(),
+        )];
+        if accept {
+            let rivals: Vec<(String, String)> = sqlx::query_as(
+                "SELECT file_id, old_path FROM move_candidates WHERE new_path = ? AND file_id != ?",
+            )
+            .bind(&new_path)
+            .bind(&file_id)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(NeuralFSError::Database)?;
+            affected = rivals;
+
+            sqlx::query("DELETE FROM move_candidates WHERE file_id = ? OR new_path = ?")
+                .bind(&file_id)
+                .bind(&new_path)
+                .execute(&mut *tx)
+                .await
+                .map_err(NeuralFSError::Database)?;
+        } else {
+            sqlx::query("DELETE FROM move_candidates WHERE id = ?")
+                .bind(candidate_id.to_string())
+                .execute(&mut *tx)
+                .await
+                .map_err(NeuralFSError::Database)?;
+        }
+
+        let mut resolution = MoveResolution {
+            moved: accept.then(|| (candidate.old_path.clone(), candidate.new_path.clone())),
+            removed: Vec::new(),
+        };
+        for (file_id, old_path) in affected {
+            let (remaining,): (i64,) =
+                sqlx::query_as("SELECT COUNT(*) FROM move_candidates WHERE file_id = ?")
+                    .bind(&file_id)
+                    .fetch_one(&mut *tx)
+                    .await
+                    .map_err(NeuralFSError::Database)?;
+            let old_path = PathBuf::from(old_path);
+            // A file that came back is picked up by the next reconcile
+            if remaining == 0 && !old_path.exists() {
+                resolution.removed.push(old_path);
+            }
+        }
+
+        tx.commit().await.map_err(NeuralFSError::Database)?;
+        Ok(Some(resolution))
+    }
+
     /// Get statistics about the reconciliation service
     pub async fn get_stats(&self) -> Result<ReconcileStats> {
         let total_files: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM files")
@@ -775,6 +1663,78 @@ impl ReconciliationService {
     }
 }
 
+/// BLAKE3 hash of a file's content (hex), streamed from disk
+pub(crate) fn hash_file_content(path: &Path) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = blake3::Hasher::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+/// `hash_file_content` on the blocking thread pool
+pub(crate) async fn hash_content(path: PathBuf) -> std::io::Result<String> {
+    tokio::task::spawn_blocking(move || hash_file_content(&path))
+        .await
+        .map_err(std::io::Error::other)?
+}
+
+/// Index of the innermost root containing `path`
+pub(crate) fn root_of(path: &Path, roots: &[PathBuf]) -> Option<usize> {
+    roots
+        .iter()
+        .enumerate()
+        .filter(|(_, root)| path.starts_with(root))
+        .max_by_key(|(_, root)| root.components().count())
+        .map(|(index, _)| index)
+}
+
+/// `root` with a trailing separator, for matching stored paths below it
+pub(crate) fn root_prefix(root: &Path) -> String {
+    let mut prefix = root.to_string_lossy().to_string();
+    if !prefix.ends_with(std::path::MAIN_SEPARATOR) {
+        prefix.push(std::path::MAIN_SEPARATOR);
+    }
+    prefix
+}
+
+/// Confidence that a missing file moved to `new_path`, given identical content
+fn move_confidence(old_path: &Path, new_path: &Path, unique: bool) -> f32 {
+    let bonus = if unique { UNIQUE_MATCH_BONUS } else { 0.0 };
+    CONTENT_MATCH_CONFIDENCE + NAME_SIMILARITY_WEIGHT * name_similarity(old_path, new_path) + bonus
+}
+
+/// Similarity of two file names, from 0.0 (nothing alike) to 1.0 (equal)
+fn name_similarity(a: &Path, b: &Path) -> f32 {
+    let name = |path: &Path| -> Vec<char> {
+        path.file_name()
+            .map(|n| n.to_string_lossy().to_lowercase())
+            .unwrap_or_default()
+            .chars()
+            .collect()
+    };
+    let (a, b) = (name(a), name(b));
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - edit_distance(&a, &b) as f32 / longest as f32
+}
+
+/// Levenshtein distance between two strings
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
 /// Statistics about the reconciliation service
 #[derive(Debug, Clone)]
 pub struct ReconcileStats {
@@ -178,7 +178,7 @@ async fn test_reconcile_config_default() {
     assert_eq!(config.max_parallel_scans, 4);
     assert_eq!(config.batch_size, 1000);
     assert!(config.fast_mode);
-    assert!(!config.verify_hash);
+    assert!(config.verify_hash);
 }
 
 #[tokio::test]
@@ -315,6 +315,606 @@ async fn test_update_file_id_in_database() {
 }
 
 
+/// Helper to record a file as indexed with the hash of `content`
+async fn mark_indexed(pool: &SqlitePool, path: &Path, content: &str) {
+    sqlx::query("UPDATE files SET content_hash = ?, index_status = 'Indexed' WHERE path = ?")
+        .bind(blake3::hash(content.as_bytes()).to_hex().to_string())
+        .bind(path.to_string_lossy().to_string())
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+fn hashing_config(fast_mode: bool) -> ReconcileConfig {
+    ReconcileConfig {
+        verify_hash: true,
+        fast_mode,
+        ..ReconcileConfig::default()
+    }
+}
+
+#[tokio::test]
+async fn test_verify_hash_ignores_touch_only_changes() {
+    let (pool, _db_temp_dir) = create_test_db().await;
+    let files_dir = TempDir::new().unwrap();
+    let file_path = create_test_file(files_dir.path(), "file1.txt", "content 1");
+
+    let service = ReconciliationService::with_config(pool.clone(), hashing_config(true));
+    let roots = [files_dir.path().to_path_buf()];
+    service.reconcile_on_startup(&roots).await.unwrap();
+    mark_indexed(&pool, &file_path, "content 1").await;
+
+    // Rewrite the same content, which only moves the mtime
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    create_test_file(files_dir.path(), "file1.txt", "content 1");
+
+    let result = service.reconcile_on_startup(&roots).await.unwrap();
+    assert!(result.modified.is_empty());
+    assert_eq!(result.touched, vec![file_path.clone()]);
+    assert!(!result.has_changes());
+
+    // The refreshed mtime means the file is not hashed again
+    let result = service.reconcile_on_startup(&roots).await.unwrap();
+    assert!(result.touched.is_empty());
+    assert!(result.modified.is_empty());
+}
+
+#[tokio::test]
+async fn test_verify_hash_detects_same_size_edits() {
+    let (pool, _db_temp_dir) = create_test_db().await;
+    let files_dir = TempDir::new().unwrap();
+    let file_path = create_test_file(files_dir.path(), "file1.txt", "content 1");
+
+    let service = ReconciliationService::with_config(pool.clone(), hashing_config(true));
+    let roots = [files_dir.path().to_path_buf()];
+    service.reconcile_on_startup(&roots).await.unwrap();
+    mark_indexed(&pool, &file_path, "content 1").await;
+
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    create_test_file(files_dir.path(), "file1.txt", "content 2");
+
+    let result = service.reconcile_on_startup(&roots).await.unwrap();
+    assert_eq!(result.modified, vec![file_path]);
+    assert!(result.touched.is_empty());
+}
+
+#[tokio::test]
+async fn test_verify_hash_skips_files_not_indexed_yet() {
+    let (pool, _db_temp_dir) = create_test_db().await;
+    let files_dir = TempDir::new().unwrap();
+    let file_path = create_test_file(files_dir.path(), "file1.txt", "content 1");
+
+    let service = ReconciliationService::with_config(pool.clone(), hashing_config(true));
+    let roots = [files_dir.path().to_path_buf()];
+    service.reconcile_on_startup(&roots).await.unwrap();
+
+    // Still pending: there is no hash to compare against
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    create_test_file(files_dir.path(), "file1.txt", "content 1");
+
+    let result = service.reconcile_on_startup(&roots).await.unwrap();
+    assert_eq!(result.modified, vec![file_path]);
+}
+
+#[tokio::test]
+async fn test_full_mode_detects_edits_that_keep_mtime() {
+    let (pool, _db_temp_dir) = create_test_db().await;
+    let files_dir = TempDir::new().unwrap();
+    let file_path = create_test_file(files_dir.path(), "file1.txt", "content 1");
+    let mtime = std::fs::metadata(&file_path).unwrap().modified().unwrap();
+
+    let fast = ReconciliationService::with_config(pool.clone(), hashing_config(true));
+    let full = ReconciliationService::with_config(pool.clone(), hashing_config(false));
+    let roots = [files_dir.path().to_path_buf()];
+    fast.reconcile_on_startup(&roots).await.unwrap();
+    mark_indexed(&pool, &file_path, "content 1").await;
+
+    // Same size, and the original mtime restored
+    let file = std::fs::OpenOptions::new().write(true).open(&file_path).unwrap();
+    (&file).write_all(b"content 2").unwrap();
+    file.set_modified(mtime).unwrap();
+    drop(file);
+
+    let result = fast.reconcile_on_startup(&roots).await.unwrap();
+    assert!(result.modified.is_empty());
+
+    let result = full.reconcile_on_startup(&roots).await.unwrap();
+    assert_eq!(result.modified, vec![file_path]);
+}
+
+#[tokio::test]
+async fn test_reconcile_scans_in_parallel_across_db_pages() {
+    let (pool, _db_temp_dir) = create_test_db().await;
+    let files_dir = TempDir::new().unwrap();
+    let mut paths = Vec::new();
+    for dir in ["a", "b", "b/c", "d"] {
+        std::fs::create_dir_all(files_dir.path().join(dir)).unwrap();
+        for i in 0..3 {
+            let name = format!("{}/file{}.txt", dir, i);
+            paths.push(create_test_file(files_dir.path(), &name, &name));
+        }
+    }
+
+    // Pages and transactions smaller than the tree
+    let config = ReconcileConfig {
+        max_parallel_scans: 2,
+        batch_size: 5,
+        ..ReconcileConfig::default()
+    };
+    let service = ReconciliationService::with_config(pool.clone(), config);
+    let roots = [files_dir.path().to_path_buf()];
+
+    let result = service.reconcile_on_startup(&roots).await.unwrap();
+    assert_eq!(result.added.len(), paths.len());
+    assert_eq!(service.get_stats().await.unwrap().total_files, paths.len() as u64);
+
+    std::fs::remove_file(&paths[0]).unwrap();
+    let moved = files_dir.path().join("b/c/moved.txt");
+    std::fs::rename(&paths[11], &moved).unwrap();
+
+    let result = service.reconcile_on_startup(&roots).await.unwrap();
+    assert_eq!(result.deleted, vec![paths[0].clone()]);
+    assert_eq!(result.renamed.len(), 1);
+    assert_eq!(result.renamed[0].old_path, paths[11]);
+    assert_eq!(result.renamed[0].new_path, moved);
+    assert!(result.added.is_empty());
+    assert!(result.modified.is_empty());
+
+    let result = service.reconcile_on_startup(&roots).await.unwrap();
+    assert!(!result.has_changes());
+}
+
+#[tokio::test]
+async fn test_removed_directory_rows_are_deleted() {
+    let (pool, _db_temp_dir) = create_test_db().await;
+    let files_dir = TempDir::new().unwrap();
+    std::fs::create_dir_all(files_dir.path().join("old/inner")).unwrap();
+    let kept = create_test_file(files_dir.path(), "kept.txt", "kept");
+    let mut gone = vec![
+        create_test_file(files_dir.path(), "old/a.txt", "a"),
+        create_test_file(files_dir.path(), "old/inner/b.txt", "b"),
+    ];
+    // A sibling whose name sorts between the directory and its children
+    let sibling = create_test_file(files_dir.path(), "old-notes.txt", "notes");
+
+    let config = ReconcileConfig {
+        batch_size: 1,
+        ..ReconcileConfig::default()
+    };
+    let service = ReconciliationService::with_config(pool.clone(), config);
+    // The nested root is reached from its parent as well
+    let roots = [files_dir.path().to_path_buf(), files_dir.path().join("old")];
+    let result = service.reconcile_on_startup(&roots).await.unwrap();
+    assert_eq!(result.added.len(), 4);
+
+    std::fs::remove_dir_all(files_dir.path().join("old/inner")).unwrap();
+    std::fs::remove_file(&gone[0]).unwrap();
+
+    let result = service.reconcile_on_startup(&roots[..1]).await.unwrap();
+    let mut deleted = result.deleted.clone();
+    deleted.sort();
+    gone.sort();
+    assert_eq!(deleted, gone);
+    assert!(result.added.is_empty());
+    assert!(result.modified.is_empty());
+    assert!(row_id(&pool, &kept).await.is_some());
+    assert!(row_id(&pool, &sibling).await.is_some());
+}
+
+/// Helper to read the row id stored for a path
+async fn row_id(pool: &SqlitePool, path: &Path) -> Option<String> {
+    sqlx::query_as::<_, (String,)>("SELECT id FROM files WHERE path = ?")
+        .bind(path.to_string_lossy().to_string())
+        .fetch_optional(pool)
+        .await
+        .unwrap()
+        .map(|row| row.0)
+}
+
+#[tokio::test]
+async fn test_content_move_across_volumes_becomes_rename() {
+    let (pool, _db_temp_dir) = create_test_db().await;
+    let files_dir = TempDir::new().unwrap();
+    let original = create_test_file(files_dir.path(), "report.txt", "quarterly numbers");
+
+    let service = ReconciliationService::new(pool.clone());
+    let roots = [files_dir.path().to_path_buf()];
+    service.reconcile_on_startup(&roots).await.unwrap();
+    mark_indexed(&pool, &original, "quarterly numbers").await;
+    let id = row_id(&pool, &original).await.unwrap();
+
+    // Copied elsewhere and removed, so the FileID changes
+    std::fs::create_dir(files_dir.path().join("backup")).unwrap();
+    let copy = files_dir.path().join("backup/report.txt");
+    std::fs::copy(&original, &copy).unwrap();
+    std::fs::remove_file(&original).unwrap();
+
+    let result = service.reconcile_on_startup(&roots).await.unwrap();
+    assert_eq!(result.renamed.len(), 1);
+    assert_eq!(result.renamed[0].old_path, original);
+    assert_eq!(result.renamed[0].new_path, copy);
+    assert!(result.renamed[0].confidence > 0.99);
+    assert!(result.added.is_empty());
+    assert!(result.deleted.is_empty());
+    assert_eq!(row_id(&pool, &copy).await, Some(id));
+}
+
+#[tokio::test]
+async fn test_ambiguous_content_moves_await_confirmation() {
+    let (pool, _db_temp_dir) = create_test_db().await;
+    let files_dir = TempDir::new().unwrap();
+    let original = create_test_file(files_dir.path(), "notes.txt", "meeting notes");
+
+    let service = ReconciliationService::new(pool.clone());
+    let roots = [files_dir.path().to_path_buf()];
+    service.reconcile_on_startup(&roots).await.unwrap();
+    mark_indexed(&pool, &original, "meeting notes").await;
+    let id = row_id(&pool, &original).await.unwrap();
+
+    // Two equally likely destinations
+    for dir in ["a", "b"] {
+        std::fs::create_dir(files_dir.path().join(dir)).unwrap();
+        std::fs::copy(&original, files_dir.path().join(dir).join("notes.txt")).unwrap();
+    }
+    std::fs::remove_file(&original).unwrap();
+
+    let result = service.reconcile_on_startup(&roots).await.unwrap();
+    assert!(result.renamed.is_empty());
+    assert!(result.deleted.is_empty());
+    assert_eq!(result.added.len(), 2);
+    assert_eq!(result.move_candidates.len(), 2);
+
+    // The missing file is kept while the user decides
+    let result = service.reconcile_on_startup(&roots).await.unwrap();
+    assert!(!result.has_changes());
+    assert_eq!(row_id(&pool, &original).await, Some(id.clone()));
+
+    let candidates = service.move_candidates().await.unwrap();
+    assert_eq!(candidates.len(), 2);
+    assert!(candidates.iter().all(|c| c.file_id.to_string() == id));
+
+    let resolution = service
+        .resolve_move_candidate(candidates[0].id, true)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        resolution.moved,
+        Some((original.clone(), candidates[0].new_path.clone()))
+    );
+    assert!(resolution.removed.is_empty());
+    assert!(service.move_candidates().await.unwrap().is_empty());
+    assert!(service
+        .resolve_move_candidate(candidates[1].id, true)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_identical_copies_only_become_candidates() {
+    let (pool, _db_temp_dir) = create_test_db().await;
+    let files_dir = TempDir::new().unwrap();
+    let originals: Vec<PathBuf> = ["one.txt", "two.txt"]
+        .iter()
+        .map(|name| create_test_file(files_dir.path(), name, "boilerplate"))
+        .collect();
+
+    let service = ReconciliationService::new(pool.clone());
+    let roots = [files_dir.path().to_path_buf()];
+    service.reconcile_on_startup(&roots).await.unwrap();
+    for original in &originals {
+        mark_indexed(&pool, original, "boilerplate").await;
+    }
+
+    // Both restored under their own names, plus many more copies
+    std::fs::create_dir(files_dir.path().join("restored")).unwrap();
+    for original in &originals {
+        let restored = files_dir.path().join("restored").join(original.file_name().unwrap());
+        std::fs::copy(original, restored).unwrap();
+        std::fs::remove_file(original).unwrap();
+    }
+    for i in 0..20 {
+        create_test_file(files_dir.path(), &format!("restored/copy{}.txt", i), "boilerplate");
+    }
+
+    let result = service.reconcile_on_startup(&roots).await.unwrap();
+    assert!(result.renamed.is_empty());
+    assert!(result.deleted.is_empty());
+    assert_eq!(result.move_candidates.len(), MAX_GROUP_CANDIDATES);
+    // The best-named pairs are offered first
+    let best = &result.move_candidates[..2];
+    assert!(best.iter().all(|c| c.old_path.file_name() == c.new_path.file_name()));
+}
+
+#[tokio::test]
+async fn test_rejected_move_candidate_is_removed() {
+    let (pool, _db_temp_dir) = create_test_db().await;
+    let files_dir = TempDir::new().unwrap();
+    let original = create_test_file(files_dir.path(), "budget.txt", "spreadsheet export");
+
+    let service = ReconciliationService::new(pool.clone());
+    let roots = [files_dir.path().to_path_buf()];
+    service.reconcile_on_startup(&roots).await.unwrap();
+    mark_indexed(&pool, &original, "spreadsheet export").await;
+
+    // Same content under an unrelated name is not enough on its own
+    let copy = files_dir.path().join("x9.dat");
+    std::fs::copy(&original, &copy).unwrap();
+    std::fs::remove_file(&original).unwrap();
+
+    let result = service.reconcile_on_startup(&roots).await.unwrap();
+    assert!(result.renamed.is_empty());
+    assert_eq!(result.move_candidates.len(), 1);
+    assert!(result.move_candidates[0].confidence < 0.8);
+
+    let resolution = service
+        .resolve_move_candidate(result.move_candidates[0].id, false)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(resolution.moved.is_none());
+    assert_eq!(resolution.removed, vec![original]);
+}
+
+#[tokio::test]
+async fn test_dry_run_leaves_database_untouched() {
+    let (pool, _db_temp_dir) = create_test_db().await;
+    let files_dir = TempDir::new().unwrap();
+    create_test_file(files_dir.path(), "file1.txt", "content 1");
+    create_test_file(files_dir.path(), "file2.txt", "content 2");
+
+    let service = ReconciliationService::new(pool);
+    let roots = [files_dir.path().to_path_buf()];
+    let preview = service.reconcile_dry_run(&roots).await.unwrap();
+    assert_eq!(preview.added.len(), 2);
+    assert_eq!(service.get_stats().await.unwrap().total_files, 0);
+
+    // The preview matches what a real run does
+    let result = service.reconcile_on_startup(&roots).await.unwrap();
+    assert_eq!(result.added.len(), 2);
+}
+
+#[tokio::test]
+async fn test_mass_deletion_is_held_until_confirmed() {
+    let (pool, _db_temp_dir) = create_test_db().await;
+    let files_dir = TempDir::new().unwrap();
+    let paths: Vec<PathBuf> = (0..12)
+        .map(|i| create_test_file(files_dir.path(), &format!("file{}.txt", i), &format!("content {}", i)))
+        .collect();
+
+    let service = ReconciliationService::new(pool);
+    let roots = [files_dir.path().to_path_buf()];
+    service.reconcile_on_startup(&roots).await.unwrap();
+
+    for path in &paths[1..] {
+        std::fs::remove_file(path).unwrap();
+    }
+
+    let result = service.reconcile_on_startup(&roots).await.unwrap();
+    assert!(result.deleted.is_empty());
+    assert_eq!(result.held_deletions.len(), 1);
+    assert_eq!(result.held_deletions[0].root, roots[0]);
+    assert_eq!(result.held_deletions[0].paths.len(), 11);
+    assert_eq!(result.held_deletions[0].indexed_files, 12);
+    assert_eq!(service.get_stats().await.unwrap().total_files, 12);
+
+    let result = service.reconcile_confirmed(&roots).await.unwrap();
+    assert_eq!(result.deleted.len(), 11);
+    assert!(result.held_deletions.is_empty());
+    assert_eq!(service.get_stats().await.unwrap().total_files, 1);
+}
+
+#[tokio::test]
+async fn test_held_deletions_persist_and_confirm_only_reviewed_paths() {
+    let (pool, _db_temp_dir) = create_test_db().await;
+    let files_dir = TempDir::new().unwrap();
+    let paths: Vec<PathBuf> = (0..12)
+        .map(|i| create_test_file(files_dir.path(), &format!("file{}.txt", i), &format!("content {}", i)))
+        .collect();
+
+    let service = ReconciliationService::new(pool.clone());
+    let roots = [files_dir.path().to_path_buf()];
+    service.reconcile_on_startup(&roots).await.unwrap();
+    for path in &paths[1..] {
+        std::fs::remove_file(path).unwrap();
+    }
+    service.reconcile_on_startup(&roots).await.unwrap();
+
+    // A new service, as after a restart, still sees them
+    let service = ReconciliationService::new(pool.clone());
+    let held = service.held_deletions().await.unwrap();
+    assert_eq!(held.len(), 1);
+    assert_eq!(held[0].root, roots[0]);
+    assert_eq!(held[0].paths.len(), 11);
+    assert_eq!(held[0].indexed_files, 12);
+
+    // One reviewed file came back; one was never held
+    create_test_file(files_dir.path(), "file1.txt", "content 1");
+    let reviewed = vec![paths[1].clone(), paths[2].clone(), paths[3].clone(), paths[0].clone()];
+    let result = service.confirm_held_deletions(&roots[0], &reviewed).await.unwrap();
+    assert_eq!(result.deleted, vec![paths[2].clone(), paths[3].clone()]);
+    assert_eq!(result.deleted_file_ids.len(), 2);
+    assert!(row_id(&pool, &paths[0]).await.is_some());
+    assert!(row_id(&pool, &paths[1]).await.is_some());
+    assert!(row_id(&pool, &paths[2]).await.is_none());
+
+    let mut remaining = paths[4..].to_vec();
+    remaining.sort();
+    let held = service.held_deletions().await.unwrap();
+    assert_eq!(held[0].paths, remaining);
+}
+
+#[tokio::test]
+async fn test_hold_if_over_threshold() {
+    let (pool, _db_temp_dir) = create_test_db().await;
+    let files_dir = TempDir::new().unwrap();
+    let paths: Vec<PathBuf> = (0..3)
+        .map(|i| create_test_file(files_dir.path(), &format!("file{}.txt", i), "content"))
+        .collect();
+
+    let config = ReconcileConfig {
+        max_unconfirmed_deletions: 1,
+        ..ReconcileConfig::default()
+    };
+    let service = ReconciliationService::with_config(pool, config);
+    let root = files_dir.path().to_path_buf();
+    service.reconcile_on_startup(&[root.clone()]).await.unwrap();
+
+    assert!(!service.hold_if_over_threshold(&root, &paths[..1]).await.unwrap());
+    assert!(service.held_deletions().await.unwrap().is_empty());
+
+    assert!(service.hold_if_over_threshold(&root, &paths[..2]).await.unwrap());
+    // Later deletions join the ones waiting
+    assert!(service.hold_if_over_threshold(&root, &paths[2..]).await.unwrap());
+    let held = service.held_deletions().await.unwrap();
+    assert_eq!(held[0].paths, paths);
+}
+
+#[tokio::test]
+async fn test_deletion_count_threshold() {
+    let (pool, _db_temp_dir) = create_test_db().await;
+    let files_dir = TempDir::new().unwrap();
+    let paths: Vec<PathBuf> = (0..3)
+        .map(|i| create_test_file(files_dir.path(), &format!("file{}.txt", i), "content"))
+        .collect();
+
+    let config = ReconcileConfig {
+        max_unconfirmed_deletions: 1,
+        ..ReconcileConfig::default()
+    };
+    let service = ReconciliationService::with_config(pool, config);
+    let roots = [files_dir.path().to_path_buf()];
+    service.reconcile_on_startup(&roots).await.unwrap();
+
+    std::fs::remove_file(&paths[0]).unwrap();
+    let result = service.reconcile_on_startup(&roots).await.unwrap();
+    assert_eq!(result.deleted, vec![paths[0].clone()]);
+
+    std::fs::remove_file(&paths[1]).unwrap();
+    std::fs::remove_file(&paths[2]).unwrap();
+    let result = service.reconcile_on_startup(&roots).await.unwrap();
+    assert!(result.deleted.is_empty());
+    assert_eq!(result.held_deletions[0].paths.len(), 2);
+}
+
+#[tokio::test]
+async fn test_missing_root_is_skipped() {
+    let (pool, _db_temp_dir) = create_test_db().await;
+    let parent = TempDir::new().unwrap();
+    let root = parent.path().join("drive");
+    std::fs::create_dir(&root).unwrap();
+    create_test_file(&root, "file1.txt", "content 1");
+
+    let service = ReconciliationService::new(pool);
+    service.reconcile_on_startup(&[root.clone()]).await.unwrap();
+
+    std::fs::remove_dir_all(&root).unwrap();
+    let result = service.reconcile_on_startup(&[root.clone()]).await.unwrap();
+    assert_eq!(result.skipped_roots, vec![root]);
+    assert!(result.deleted.is_empty());
+    assert_eq!(service.get_stats().await.unwrap().total_files, 1);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_unreadable_directory_keeps_its_files() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let (pool, _db_temp_dir) = create_test_db().await;
+    let files_dir = TempDir::new().unwrap();
+    let locked = files_dir.path().join("locked");
+    let gone = files_dir.path().join("gone");
+    std::fs::create_dir_all(locked.join("deep")).unwrap();
+    std::fs::create_dir(&gone).unwrap();
+    create_test_file(&locked, "file1.txt", "content 1");
+    create_test_file(&locked.join("deep"), "file2.txt", "content 2");
+    let removed = create_test_file(&gone, "file3.txt", "content 3");
+
+    let service = ReconciliationService::new(pool);
+    let roots = [files_dir.path().to_path_buf()];
+    service.reconcile_on_startup(&roots).await.unwrap();
+
+    std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o000)).unwrap();
+    if std::fs::read_dir(&locked).is_ok() {
+        // Permissions do not apply (e.g. running as root)
+        std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o755)).unwrap();
+        return;
+    }
+    std::fs::remove_dir_all(&gone).unwrap();
+
+    let result = service.reconcile_on_startup(&roots).await;
+    std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let result = result.unwrap();
+
+    // Only the directory that was really removed loses its files
+    assert_eq!(result.deleted, vec![removed]);
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].0, locked);
+    assert_eq!(service.get_stats().await.unwrap().total_files, 2);
+}
+
+#[tokio::test]
+async fn test_empty_mount_point_is_skipped() {
+    let (pool, _db_temp_dir) = create_test_db().await;
+    let files_dir = TempDir::new().unwrap();
+    let file_path = create_test_file(files_dir.path(), "file1.txt", "content 1");
+
+    let service = ReconciliationService::new(pool.clone());
+    let roots = [files_dir.path().to_path_buf()];
+    service.reconcile_on_startup(&roots).await.unwrap();
+
+    // Files recorded on another volume, now gone from an empty directory
+    let mut foreign = FileId::from_path(&file_path).unwrap();
+    #[cfg(unix)]
+    {
+        foreign.device = foreign.device.wrapping_add(1);
+    }
+    #[cfg(windows)]
+    {
+        foreign.volume_serial = foreign.volume_serial.wrapping_add(1);
+    }
+    sqlx::query("UPDATE files SET file_id = ?")
+        .bind(foreign.to_string_repr())
+        .execute(&pool)
+        .await
+        .unwrap();
+    std::fs::remove_file(&file_path).unwrap();
+
+    let result = service.reconcile_on_startup(&roots).await.unwrap();
+    assert_eq!(result.skipped_roots, roots.to_vec());
+    assert!(result.deleted.is_empty());
+}
+
+#[tokio::test]
+async fn test_files_outside_reconciled_roots_are_kept() {
+    let (pool, _db_temp_dir) = create_test_db().await;
+    let first = TempDir::new().unwrap();
+    let second = TempDir::new().unwrap();
+    create_test_file(first.path(), "file1.txt", "content 1");
+    create_test_file(second.path(), "file2.txt", "content 2");
+
+    let service = ReconciliationService::new(pool);
+    let both = [first.path().to_path_buf(), second.path().to_path_buf()];
+    service.reconcile_on_startup(&both).await.unwrap();
+
+    let result = service
+        .reconcile_on_startup(&[first.path().to_path_buf()])
+        .await
+        .unwrap();
+    assert!(!result.has_changes());
+    assert_eq!(service.get_stats().await.unwrap().total_files, 2);
+}
+
+#[test]
+fn test_name_similarity() {
+    let same = name_similarity(Path::new("/a/report.txt"), Path::new("/b/Report.txt"));
+    assert_eq!(same, 1.0);
+    assert!(name_similarity(Path::new("report.txt"), Path::new("report (1).txt")) > 0.5);
+    assert!(name_similarity(Path::new("report.txt"), Path::new("x9.dat")) < 0.3);
+    assert_eq!(edit_distance(&['k', 'i', 't'], &['s', 'i', 't', 's']), 2);
+}
+
 // Property-based tests using proptest
 #[cfg(test)]
 mod property_tests {
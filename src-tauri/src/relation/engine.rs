@@ -512,6 +512,7 @@ impl RelationRow {
             "Derivative" => RelationType::Derivative,
             "Workflow" => RelationType::Workflow,
             "UserDefined" => RelationType::UserDefined,
+            "Duplicate" => RelationType::Duplicate,
             _ => RelationType::ContentSimilar,
         };
 
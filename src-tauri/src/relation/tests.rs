@@ -31,6 +31,7 @@ fn arb_relation_type() -> impl Strategy<Value = RelationType> {
         Just(RelationType::Derivative),
         Just(RelationType::Workflow),
         Just(RelationType::UserDefined),
+        Just(RelationType::Duplicate),
     ]
 }
 
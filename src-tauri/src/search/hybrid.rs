@@ -5,6 +5,8 @@
 //! - Query type classification (exact keyword, natural language, mixed)
 //! - Search filtering by file type, tags, time range, and privacy level
 //! - Score normalization and result merging
+//! - Grouping of chunk-level hits into file-level results with best passages
+//! - Collapsing duplicate files into a single result
 //!
 //! **Validates: Requirements 2.2, 2.3, Hybrid Search Logic**
 
@@ -17,13 +19,16 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::core::types::chunk::ChunkLocation;
 use crate::core::types::file::FileType;
 use crate::core::types::search::{
-    Pagination, ResultSource, SearchFilters, SearchRequest, SearchResponse, SearchResult,
-    SearchResultType, SearchStatus, TimeRange,
+    Pagination, ResultSource, SearchFilters, SearchIntent, SearchRequest, SearchResponse,
+    SearchResult, SearchResultType, SearchStatus, TimeRange,
 };
+use crate::dedup::DuplicateMap;
 use crate::search::text_index::{SearchFilters as TextSearchFilters, SearchResult as TextSearchResult, TextIndex};
 use crate::vector::store::{SearchFilter as VectorSearchFilter, SearchResult as VectorSearchResult, VectorStore};
+use crate::vector::VectorSpace;
 
 /// Error types for hybrid search operations
 #[derive(Error, Debug)]
@@ -74,6 +79,70 @@ pub struct HybridSearchConfig {
     pub max_results: usize,
     /// Timeout in milliseconds
     pub timeout_ms: u64,
+    /// Record a per-result score explanation (relevance debugging)
+    #[serde(default)]
+    pub explain: bool,
+    /// How chunk scores are combined into a file score
+    #[serde(default)]
+    pub chunk_aggregation: ChunkAggregation,
+    /// Maximum passages (chunks) returned per file
+    #[serde(default = "default_passages_per_file")]
+    pub passages_per_file: usize,
+    /// Show one result per duplicate group (see `collapse_duplicates`)
+    #[serde(default)]
+    pub collapse_duplicates: bool,
+}
+
+fn default_passages_per_file() -> usize {
+    3
+}
+
+/// Aggregation of chunk scores into a single file score
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChunkAggregation {
+    /// File score is its best chunk score
+    Max,
+    /// File score is the sum of its chunk scores (favors many matching passages)
+    Sum,
+    /// Softmax-weighted mean of chunk scores; lower temperature approaches `Max`
+    Softmax { temperature: f32 },
+}
+
+impl Default for ChunkAggregation {
+    fn default() -> Self {
+        ChunkAggregation::Max
+    }
+}
+
+impl ChunkAggregation {
+    /// Combine chunk scores into a file score
+    pub fn aggregate(&self, scores: &[f32]) -> f32 {
+        if scores.is_empty() {
+            return 0.0;
+        }
+
+        let max = scores.iter().copied().fold(f32::MIN, f32::max);
+        match self {
+            ChunkAggregation::Max => max,
+            ChunkAggregation::Sum => scores.iter().sum(),
+            ChunkAggregation::Softmax { temperature } => {
+                let temperature = temperature.max(f32::EPSILON);
+                // Shift by the max for numerical stability
+                let weights: Vec<f32> = scores
+                    .iter()
+                    .map(|s| ((s - max) / temperature).exp())
+                    .collect();
+                let total: f32 = weights.iter().sum();
+                scores
+                    .iter()
+                    .zip(&weights)
+                    .map(|(s, w)| s * w)
+                    .sum::<f32>()
+                    / total
+            }
+        }
+    }
 }
 
 impl Default for HybridSearchConfig {
@@ -87,6 +156,10 @@ impl Default for HybridSearchConfig {
             min_bm25_score: 0.1,
             max_results: 100,
             timeout_ms: 5000,
+            explain: false,
+            chunk_aggregation: ChunkAggregation::Max,
+            passages_per_file: default_passages_per_file(),
+            collapse_duplicates: false,
         }
     }
 }
@@ -113,6 +186,30 @@ impl HybridSearchConfig {
             ..Default::default()
         }
     }
+
+    /// Enable or disable per-result score explanations
+    pub fn with_explain(mut self, explain: bool) -> Self {
+        self.explain = explain;
+        self
+    }
+
+    /// Set how chunk scores are combined into a file score
+    pub fn with_chunk_aggregation(mut self, aggregation: ChunkAggregation) -> Self {
+        self.chunk_aggregation = aggregation;
+        self
+    }
+
+    /// Set the maximum number of passages returned per file
+    pub fn with_passages_per_file(mut self, passages: usize) -> Self {
+        self.passages_per_file = passages.max(1);
+        self
+    }
+
+    /// Set whether members of a duplicate group collapse into one result
+    pub fn with_collapse_duplicates(mut self, collapse: bool) -> Self {
+        self.collapse_duplicates = collapse;
+        self
+    }
 }
 
 /// Intermediate scored result for merging
@@ -134,6 +231,31 @@ pub struct ScoredResult {
     pub filename: Option<String>,
     /// Associated tags
     pub tags: Vec<String>,
+    /// Score breakdown (only when `HybridSearchConfig::explain` is set)
+    pub explanation: Option<ScoreExplanation>,
+    /// Location of the matched chunk within the file (if known)
+    pub location: Option<ChunkLocation>,
+}
+
+/// File-level result built from one or more chunk hits
+#[derive(Debug, Clone)]
+pub struct FileResult {
+    /// File UUID
+    pub file_id: Uuid,
+    /// Aggregated file score (see `ChunkAggregation`)
+    pub score: f32,
+    /// Source of the result (`Both` if chunks came from different searches)
+    pub source: SearchSource,
+    /// Filename (from the first hit that carried one)
+    pub filename: Option<String>,
+    /// Associated tags
+    pub tags: Vec<String>,
+    /// Best matching passages, highest score first
+    pub passages: Vec<ScoredResult>,
+    /// Total number of matching chunks before truncation to `passages`
+    pub matched_chunks: usize,
+    /// Duplicates of this file folded into it by `collapse_duplicates`
+    pub duplicates: Vec<Uuid>,
 }
 
 /// Source of a search result
@@ -147,6 +269,79 @@ pub enum SearchSource {
     Both,
 }
 
+/// Breakdown of how a result's score was computed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreExplanation {
+    /// Vector weight used for merging (from `get_adjusted_weights`)
+    pub vector_weight: f32,
+    /// BM25 weight used for merging (from `get_adjusted_weights`)
+    pub bm25_weight: f32,
+    /// Raw vector similarity before normalization
+    pub raw_vector_score: Option<f32>,
+    /// Vector score normalized against the best vector hit
+    pub vector_score: Option<f32>,
+    /// Raw BM25 score before normalization
+    pub raw_bm25_score: Option<f32>,
+    /// BM25 score normalized against the best BM25 hit
+    pub bm25_score: Option<f32>,
+    /// Tantivy explanation of the BM25 score (JSON)
+    pub bm25_explanation: Option<String>,
+    /// Weighted score after merging, before boosts
+    pub merged_score: f32,
+    /// Boosts applied after merging, in order
+    pub boosts: Vec<ScoreBoost>,
+    /// Filters evaluated against the result
+    pub filters: Vec<FilterDecision>,
+    /// Final score
+    pub final_score: f32,
+}
+
+/// A multiplicative boost applied to a result's score
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreBoost {
+    /// What triggered the boost (e.g. "filename_exact", "filename_word:report")
+    pub reason: String,
+    /// Multiplicative factor
+    pub factor: f32,
+}
+
+/// Outcome of a filter check for a single result
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FilterDecision {
+    /// Filter name (e.g. "min_score", "min_vector_score")
+    pub filter: String,
+    /// Whether the result passed this filter
+    pub passed: bool,
+    /// Human-readable detail (threshold and actual value)
+    pub detail: String,
+}
+
+impl ScoreExplanation {
+    fn new(weights: (f32, f32)) -> Self {
+        Self {
+            vector_weight: weights.0,
+            bm25_weight: weights.1,
+            raw_vector_score: None,
+            vector_score: None,
+            raw_bm25_score: None,
+            bm25_score: None,
+            bm25_explanation: None,
+            merged_score: 0.0,
+            boosts: Vec::new(),
+            filters: Vec::new(),
+            final_score: 0.0,
+        }
+    }
+
+    fn record_filter(&mut self, filter: &str, passed: bool, detail: String) {
+        self.filters.push(FilterDecision {
+            filter: filter.to_string(),
+            passed,
+            detail,
+        });
+    }
+}
+
 /// Hybrid search engine combining vector and BM25 search
 pub struct HybridSearchEngine {
     /// Configuration
@@ -195,6 +390,75 @@ impl HybridSearchEngine {
         }
     }
 
+    /// Run a hybrid search against the text index and a vector store
+    ///
+    /// `query_vector` is the query embedded into the store's vector space.
+    /// Without one only BM25 hits are returned, at full weight. If the
+    /// query cannot be parsed as a keyword query, the vector hits are
+    /// returned on their own.
+    ///
+    /// Returns chunk-level results, boosted, filtered and limited to
+    /// `max_results`; shape them with `group_for_intent`.
+    pub async fn search(
+        &self,
+        text_index: &TextIndex,
+        vector_store: &VectorStore,
+        query: &str,
+        query_vector: Option<&[f32]>,
+        filters: &HybridSearchFilters,
+    ) -> Result<Vec<ScoredResult>, HybridSearchError> {
+        if query.trim().is_empty() {
+            return Err(HybridSearchError::InvalidQuery("empty query".to_string()));
+        }
+
+        // Over-fetch chunks so grouping them by file still fills a page
+        let candidates = self.config.max_results * 2;
+
+        let (weights, vector_results) = match query_vector {
+            Some(vector) => {
+                let results = vector_store
+                    .search(vector, candidates, Some(filters.to_vector_filter()))
+                    .await
+                    .map_err(|e| HybridSearchError::VectorSearch(e.to_string()))?;
+                (self.get_adjusted_weights(self.classify_query(query)), results)
+            }
+            None => ((0.0, 1.0), Vec::new()),
+        };
+
+        let bm25_results = match self.keyword_search(text_index, query, filters, candidates) {
+            Ok(results) => results,
+            Err(e) if query_vector.is_some() => {
+                tracing::debug!("Keyword search skipped for {:?}: {}", query, e);
+                Vec::new()
+            }
+            Err(e) => return Err(e),
+        };
+
+        let mut results = self.merge_results(vector_results, bm25_results, weights);
+        self.apply_exact_match_boost(&mut results, query);
+        let results = self.filter_by_score(results);
+        let results = apply_filters(results, filters);
+        Ok(self.limit_results(results))
+    }
+
+    /// BM25 search over the text index, with Tantivy explanations when
+    /// `explain` is configured
+    pub fn keyword_search(
+        &self,
+        text_index: &TextIndex,
+        query: &str,
+        filters: &HybridSearchFilters,
+        limit: usize,
+    ) -> Result<Vec<TextSearchResult>, HybridSearchError> {
+        let text_filters = filters.to_text_filter();
+        let results = if self.config.explain {
+            text_index.search_with_filters_explained(query, &text_filters, limit)
+        } else {
+            text_index.search_with_filters(query, &text_filters, limit)
+        };
+        results.map_err(|e| HybridSearchError::TextSearch(e.to_string()))
+    }
+
 
     /// Merge vector and BM25 search results with weighted scoring
     ///
@@ -212,7 +476,9 @@ impl HybridSearchEngine {
         weights: (f32, f32),
     ) -> Vec<ScoredResult> {
         let (vector_weight, bm25_weight) = weights;
-        let mut result_map: HashMap<Uuid, ScoredResult> = HashMap::new();
+        // Keyed per chunk so passages of the same file are kept apart;
+        // `group_by_file` collapses them afterwards
+        let mut result_map: HashMap<(Uuid, Option<Uuid>), ScoredResult> = HashMap::new();
 
         // Normalize vector scores to [0, 1] range
         let max_vector_score = vector_results
@@ -246,17 +512,29 @@ impl HybridSearchEngine {
             let normalized_score = vr.score / vector_normalizer;
             let weighted_score = normalized_score * vector_weight;
 
+            let explanation = self.config.explain.then(|| {
+                let mut explanation = ScoreExplanation::new(weights);
+                explanation.raw_vector_score = Some(vr.score);
+                explanation.vector_score = Some(normalized_score);
+                explanation.merged_score = weighted_score;
+                explanation.final_score = weighted_score;
+                explanation
+            });
+
+            let chunk_id = vr.chunk_id();
             result_map.insert(
-                file_id,
+                (file_id, chunk_id),
                 ScoredResult {
                     file_id,
-                    chunk_id: vr.chunk_id(),
+                    chunk_id,
                     score: weighted_score,
                     vector_score: Some(normalized_score),
                     bm25_score: None,
                     source: SearchSource::Vector,
                     filename: None,
                     tags: Vec::new(),
+                    explanation,
+                    location: vr.chunk_location(),
                 },
             );
         }
@@ -266,17 +544,35 @@ impl HybridSearchEngine {
             let normalized_score = br.score / bm25_normalizer;
             let weighted_score = normalized_score * bm25_weight;
 
-            if let Some(existing) = result_map.get_mut(&br.file_id) {
+            if let Some(existing) = result_map.get_mut(&(br.file_id, br.chunk_id)) {
                 // Merge with existing vector result
                 existing.score += weighted_score;
                 existing.bm25_score = Some(normalized_score);
                 existing.source = SearchSource::Both;
                 existing.filename = br.filename.clone();
                 existing.tags = br.tags.clone();
+
+                if let Some(ref mut explanation) = existing.explanation {
+                    explanation.raw_bm25_score = Some(br.score);
+                    explanation.bm25_score = Some(normalized_score);
+                    explanation.bm25_explanation = br.explanation;
+                    explanation.merged_score = existing.score;
+                    explanation.final_score = existing.score;
+                }
             } else {
                 // New result from BM25 only
+                let explanation = self.config.explain.then(|| {
+                    let mut explanation = ScoreExplanation::new(weights);
+                    explanation.raw_bm25_score = Some(br.score);
+                    explanation.bm25_score = Some(normalized_score);
+                    explanation.bm25_explanation = br.explanation;
+                    explanation.merged_score = weighted_score;
+                    explanation.final_score = weighted_score;
+                    explanation
+                });
+
                 result_map.insert(
-                    br.file_id,
+                    (br.file_id, br.chunk_id),
                     ScoredResult {
                         file_id: br.file_id,
                         chunk_id: br.chunk_id,
@@ -286,6 +582,8 @@ impl HybridSearchEngine {
                         source: SearchSource::BM25,
                         filename: br.filename,
                         tags: br.tags,
+                        explanation,
+                        location: None,
                     },
                 );
             }
@@ -304,7 +602,7 @@ impl HybridSearchEngine {
         let query_words: Vec<&str> = query_lower.split_whitespace().collect();
 
         for result in results.iter_mut() {
-            let mut boost = 1.0f32;
+            let mut boosts: Vec<ScoreBoost> = Vec::new();
 
             // Check filename match
             if let Some(ref filename) = result.filename {
@@ -312,13 +610,19 @@ impl HybridSearchEngine {
                 
                 // Exact filename match
                 if filename_lower.contains(&query_lower) {
-                    boost *= self.config.filename_match_boost;
+                    boosts.push(ScoreBoost {
+                        reason: "filename_exact".to_string(),
+                        factor: self.config.filename_match_boost,
+                    });
                 }
                 
                 // Partial word match in filename
                 for word in &query_words {
                     if filename_lower.contains(word) {
-                        boost *= 1.1;
+                        boosts.push(ScoreBoost {
+                            reason: format!("filename_word:{}", word),
+                            factor: 1.1,
+                        });
                     }
                 }
             }
@@ -327,11 +631,20 @@ impl HybridSearchEngine {
             for tag in &result.tags {
                 let tag_lower = tag.to_lowercase();
                 if query_words.iter().any(|w| tag_lower.contains(w)) {
-                    boost *= 1.2;
+                    boosts.push(ScoreBoost {
+                        reason: format!("tag:{}", tag),
+                        factor: 1.2,
+                    });
                 }
             }
 
+            let boost: f32 = boosts.iter().map(|b| b.factor).product();
             result.score *= boost;
+
+            if let Some(ref mut explanation) = result.explanation {
+                explanation.boosts.extend(boosts);
+                explanation.final_score = result.score;
+            }
         }
 
         // Re-sort after boosting
@@ -342,12 +655,26 @@ impl HybridSearchEngine {
     pub fn filter_by_score(&self, results: Vec<ScoredResult>) -> Vec<ScoredResult> {
         results
             .into_iter()
-            .filter(|r| {
+            .filter_map(|mut r| {
                 // Keep if combined score is above threshold
                 // or if individual scores meet their thresholds
                 let vector_ok = r.vector_score.map_or(true, |s| s >= self.config.min_vector_score);
                 let bm25_ok = r.bm25_score.map_or(true, |s| s >= self.config.min_bm25_score);
-                vector_ok || bm25_ok
+
+                if let Some(ref mut explanation) = r.explanation {
+                    explanation.record_filter(
+                        "min_vector_score",
+                        vector_ok,
+                        format!("{:?} >= {}", r.vector_score, self.config.min_vector_score),
+                    );
+                    explanation.record_filter(
+                        "min_bm25_score",
+                        bm25_ok,
+                        format!("{:?} >= {}", r.bm25_score, self.config.min_bm25_score),
+                    );
+                }
+
+                (vector_ok || bm25_ok).then_some(r)
             })
             .collect()
     }
@@ -356,6 +683,123 @@ impl HybridSearchEngine {
     pub fn limit_results(&self, results: Vec<ScoredResult>) -> Vec<ScoredResult> {
         results.into_iter().take(self.config.max_results).collect()
     }
+
+    /// Collapse chunk-level hits into file-level results
+    ///
+    /// Chunk scores of each file are combined with the configured
+    /// `ChunkAggregation`, and the best `passages_per_file` chunks are kept
+    /// (with their locations) so the UI can show why the file matched.
+    /// Results are sorted by file score descending.
+    pub fn group_by_file(&self, results: Vec<ScoredResult>) -> Vec<FileResult> {
+        let mut groups: HashMap<Uuid, Vec<ScoredResult>> = HashMap::new();
+        for result in results {
+            groups.entry(result.file_id).or_default().push(result);
+        }
+
+        let mut files: Vec<FileResult> = groups
+            .into_iter()
+            .map(|(file_id, mut chunks)| {
+                chunks.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+
+                let scores: Vec<f32> = chunks.iter().map(|c| c.score).collect();
+                let score = self.config.chunk_aggregation.aggregate(&scores);
+                let source = chunks
+                    .iter()
+                    .map(|c| c.source)
+                    .reduce(|a, b| if a == b { a } else { SearchSource::Both })
+                    .unwrap_or(SearchSource::Vector);
+                let filename = chunks.iter().find_map(|c| c.filename.clone());
+                let tags = chunks
+                    .iter()
+                    .find(|c| !c.tags.is_empty())
+                    .map(|c| c.tags.clone())
+                    .unwrap_or_default();
+                let matched_chunks = chunks.len();
+                chunks.truncate(self.config.passages_per_file.max(1));
+
+                FileResult {
+                    file_id,
+                    score,
+                    source,
+                    filename,
+                    tags,
+                    passages: chunks,
+                    matched_chunks,
+                    duplicates: Vec::new(),
+                }
+            })
+            .collect();
+
+        files.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        files
+    }
+
+    /// Shape results for the parsed search intent
+    ///
+    /// Segment-level intent (`FindContent`) keeps every passage as its own
+    /// result so individual matches can be listed; other intents are grouped
+    /// per file via `group_by_file`.
+    pub fn group_for_intent(&self, results: Vec<ScoredResult>, intent: &SearchIntent) -> Vec<FileResult> {
+        match intent {
+            SearchIntent::FindContent { .. } => results
+                .into_iter()
+                .map(|result| FileResult {
+                    file_id: result.file_id,
+                    score: result.score,
+                    source: result.source,
+                    filename: result.filename.clone(),
+                    tags: result.tags.clone(),
+                    passages: vec![result],
+                    matched_chunks: 1,
+                    duplicates: Vec::new(),
+                })
+                .collect(),
+            _ => self.group_by_file(results),
+        }
+    }
+
+    /// Fold the members of each duplicate group into a single result
+    ///
+    /// Does nothing unless `collapse_duplicates` is configured. The group
+    /// keeps the position of its best-ranked member; if the group's kept
+    /// file matched, that file represents it. Folded
+    /// files are listed in `duplicates`; further passages of the
+    /// representative stay separate results. Expects results sorted by score.
+    pub fn collapse_duplicates(&self, files: Vec<FileResult>, duplicates: &DuplicateMap) -> Vec<FileResult> {
+        if !self.config.collapse_duplicates || duplicates.is_empty() {
+            return files;
+        }
+
+        let mut collapsed: Vec<FileResult> = Vec::with_capacity(files.len());
+        let mut position: HashMap<Uuid, usize> = HashMap::new();
+        for mut file in files {
+            let Some(group_id) = duplicates.group_of(&file.file_id) else {
+                collapsed.push(file);
+                continue;
+            };
+
+            match position.get(&group_id) {
+                None => {
+                    position.insert(group_id, collapsed.len());
+                    collapsed.push(file);
+                }
+                Some(&index) if collapsed[index].file_id == file.file_id => collapsed.push(file),
+                Some(&index) if duplicates.canonical(&group_id) == Some(file.file_id) => {
+                    let previous = std::mem::replace(&mut collapsed[index], file);
+                    let representative = &mut collapsed[index];
+                    representative.score = representative.score.max(previous.score);
+                    representative.duplicates.push(previous.file_id);
+                    representative.duplicates.extend(previous.duplicates);
+                }
+                Some(&index) => {
+                    let representative = &mut collapsed[index];
+                    representative.duplicates.push(file.file_id);
+                    representative.duplicates.append(&mut file.duplicates);
+                }
+            }
+        }
+        collapsed
+    }
 }
 
 impl Default for HybridSearchEngine {
@@ -389,6 +833,31 @@ pub fn classify_query(query: &str) -> QueryType {
     QueryType::Mixed
 }
 
+/// Select the vector space to query for a search
+///
+/// Image-oriented searches are answered from the CLIP image space, where the
+/// query is embedded with the CLIP text tower. Everything else searches the
+/// text chunk space. An explicit file type filter takes precedence over the
+/// hint extracted from the query text.
+pub fn select_vector_space(
+    file_type_hint: Option<FileType>,
+    filter_types: Option<&[FileType]>,
+) -> VectorSpace {
+    match filter_types {
+        Some(types) if !types.is_empty() => {
+            if types.iter().all(|t| *t == FileType::Image) {
+                VectorSpace::Image
+            } else {
+                VectorSpace::Text
+            }
+        }
+        _ => match file_type_hint {
+            Some(FileType::Image) => VectorSpace::Image,
+            _ => VectorSpace::Text,
+        },
+    }
+}
+
 /// Check if query matches exact keyword patterns
 fn is_exact_keyword_query(query: &str) -> bool {
     // Hexadecimal error codes (e.g., 0x80070005)
@@ -479,10 +948,16 @@ pub struct HybridSearchFilters {
     pub exclude_tag_ids: Option<Vec<Uuid>>,
     /// Time range filter
     pub time_range: Option<TimeRange>,
+    /// Photo capture time range filter
+    pub capture_range: Option<TimeRange>,
+    /// Photo location filter (place name substring)
+    pub location: Option<String>,
     /// Minimum score threshold
     pub min_score: Option<f32>,
     /// Exclude private files
     pub exclude_private: bool,
+    /// Exclude sensitive and private files
+    pub exclude_sensitive: bool,
     /// Path prefix filter
     pub path_prefix: Option<String>,
 }
@@ -517,6 +992,18 @@ impl HybridSearchFilters {
         self
     }
 
+    /// Filter photos by capture time
+    pub fn with_capture_range(mut self, start: Option<DateTime<Utc>>, end: Option<DateTime<Utc>>) -> Self {
+        self.capture_range = Some(TimeRange { start, end });
+        self
+    }
+
+    /// Filter photos by place name
+    pub fn with_location(mut self, location: impl Into<String>) -> Self {
+        self.location = Some(location.into());
+        self
+    }
+
     /// Set minimum score threshold
     pub fn with_min_score(mut self, score: f32) -> Self {
         self.min_score = Some(score);
@@ -529,6 +1016,12 @@ impl HybridSearchFilters {
         self
     }
 
+    /// Exclude sensitive and private files, which are processed locally only
+    pub fn exclude_sensitive(mut self) -> Self {
+        self.exclude_sensitive = true;
+        self
+    }
+
     /// Filter by path prefix
     pub fn with_path_prefix(mut self, prefix: String) -> Self {
         self.path_prefix = Some(prefix);
@@ -552,6 +1045,10 @@ impl HybridSearchFilters {
             filter = filter.exclude_private();
         }
 
+        if self.exclude_sensitive {
+            filter = filter.exclude_sensitive();
+        }
+
         filter
     }
 
@@ -573,6 +1070,17 @@ impl HybridSearchFilters {
             }
         }
 
+        if let Some(ref range) = self.capture_range {
+            if let Some(start) = range.start {
+                filter.min_captured_at = Some(start.timestamp().max(0) as u64);
+            }
+            if let Some(end) = range.end {
+                filter.max_captured_at = Some(end.timestamp().max(0) as u64);
+            }
+        }
+
+        filter.location = self.location.clone();
+
         filter
     }
 
@@ -590,9 +1098,38 @@ impl HybridSearchFilters {
     }
 }
 
+impl From<&SearchFilters> for HybridSearchFilters {
+    fn from(filters: &SearchFilters) -> Self {
+        Self {
+            file_types: filters.file_types.clone(),
+            tag_ids: filters.tags.clone(),
+            exclude_tag_ids: filters.exclude_tags.clone(),
+            time_range: filters.time_range.clone(),
+            capture_range: None,
+            location: None,
+            min_score: Some(filters.min_score),
+            exclude_private: filters.exclude_private,
+            exclude_sensitive: false,
+            path_prefix: filters
+                .path_prefix
+                .as_ref()
+                .map(|p| p.to_string_lossy().to_string()),
+        }
+    }
+}
+
 /// Apply filters to scored results
 pub fn apply_filters(results: Vec<ScoredResult>, filters: &HybridSearchFilters) -> Vec<ScoredResult> {
-    results.into_iter().filter(|r| filters.matches(r)).collect()
+    results
+        .into_iter()
+        .filter_map(|mut r| {
+            let passed = filters.matches(&r);
+            if let (Some(min), Some(explanation)) = (filters.min_score, r.explanation.as_mut()) {
+                explanation.record_filter("min_score", passed, format!("{} >= {}", r.score, min));
+            }
+            passed.then_some(r)
+        })
+        .collect()
 }
 
 // ============================================================================
@@ -663,6 +1200,30 @@ mod unit_tests {
         assert_eq!(classify_query("AI"), QueryType::Mixed);
     }
 
+    #[test]
+    fn test_select_vector_space() {
+        let parser = crate::search::intent::IntentParser::new();
+
+        // Image-oriented queries go to the CLIP image space
+        let hint = parser.extract_file_type_hint("whiteboard photo with diagrams");
+        assert_eq!(hint, Some(FileType::Image));
+        assert_eq!(select_vector_space(hint, None), VectorSpace::Image);
+
+        // Everything else stays in the text space
+        let hint = parser.extract_file_type_hint("quarterly budget report");
+        assert_eq!(select_vector_space(hint, None), VectorSpace::Text);
+
+        // Explicit filters override the query hint
+        assert_eq!(
+            select_vector_space(Some(FileType::Image), Some(&[FileType::Pdf])),
+            VectorSpace::Text
+        );
+        assert_eq!(
+            select_vector_space(None, Some(&[FileType::Image])),
+            VectorSpace::Image
+        );
+    }
+
     #[test]
     fn test_config_validation() {
         // Valid config
@@ -716,6 +1277,22 @@ mod unit_tests {
         assert!(filters.exclude_private);
     }
 
+    #[test]
+    fn test_capture_filters_reach_text_filter() {
+        let start = DateTime::parse_from_rfc3339("2023-03-01T00:00:00Z").unwrap().with_timezone(&Utc);
+        let end = DateTime::parse_from_rfc3339("2023-05-31T23:59:59Z").unwrap().with_timezone(&Utc);
+        let filters = HybridSearchFilters::new()
+            .with_capture_range(Some(start), Some(end))
+            .with_location("Kyoto");
+
+        let text_filter = filters.to_text_filter();
+        assert_eq!(text_filter.min_captured_at, Some(1677628800));
+        assert_eq!(text_filter.max_captured_at, Some(1685577599));
+        assert_eq!(text_filter.location.as_deref(), Some("Kyoto"));
+        // Capture time is separate from modification time
+        assert_eq!(text_filter.min_modified_at, None);
+    }
+
     #[test]
     fn test_merge_results_empty() {
         let engine = HybridSearchEngine::new();
@@ -4,10 +4,17 @@
 //! - Intent classification (file-level vs segment-level)
 //! - Query pattern recognition
 //! - Clarification question generation for ambiguous queries
+//! - Versioned keyword lexicons with user overrides
+//! - Learning from clarification answers to auto-resolve ambiguous queries
 //!
 //! **Validates: Requirements 2.1**
 
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::OnceLock;
+
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 use crate::core::types::chunk::ChunkType;
 use crate::core::types::file::FileType;
@@ -21,20 +28,14 @@ use crate::core::types::search::{SearchIntent, TimeRange};
 /// - An ambiguous query that needs clarification
 #[derive(Debug, Clone)]
 pub struct IntentParser {
-    /// Keywords that indicate file-level search intent
-    file_keywords: Vec<&'static str>,
-    /// Keywords that indicate content/segment-level search intent
-    content_keywords: Vec<&'static str>,
-    /// File type indicators (extension patterns)
-    file_type_patterns: Vec<(&'static str, FileType)>,
-    /// Time-related keywords
-    time_keywords: Vec<(&'static str, TimeHint)>,
-    /// Content type indicators
-    content_type_patterns: Vec<(&'static str, ChunkType)>,
+    /// Keyword lexicon (file, content, file type, time and content type terms)
+    lexicon: IntentLexicon,
+    /// Clarification answers used to auto-resolve ambiguous queries
+    clarifications: ClarificationMemory,
 }
 
 /// Time hint extracted from query
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TimeHint {
     Recent,    // "recent", "latest", "new"
     Today,     // "today"
@@ -44,6 +45,18 @@ pub enum TimeHint {
     Old,       // "old", "archive"
 }
 
+/// Capture date and place a photo query asks for
+///
+/// "photos taken in Kyoto last spring" yields the capture range of the
+/// last spring and the location "Kyoto".
+#[derive(Debug, Clone, Default)]
+pub struct PhotoHints {
+    /// Capture time range
+    pub capture_range: Option<TimeRange>,
+    /// Place name
+    pub location: Option<String>,
+}
+
 /// Result of intent parsing
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IntentParseResult {
@@ -75,83 +88,55 @@ impl Default for IntentParser {
 }
 
 impl IntentParser {
-    /// Create a new IntentParser with default configuration
+    /// Create a new IntentParser with the built-in lexicon
     pub fn new() -> Self {
+        Self::with_lexicon(IntentLexicon::builtin())
+    }
+
+    /// Create an IntentParser with a custom lexicon
+    pub fn with_lexicon(lexicon: IntentLexicon) -> Self {
         Self {
-            file_keywords: vec![
-                "file", "document", "find file", "where is", "locate",
-                "文件", "文档", "找文件", "在哪", "哪个文件",
-                "ファイル", "書類",
-            ],
-            content_keywords: vec![
-                "content", "text", "paragraph", "section", "quote", "mention",
-                "says", "contains", "written", "about", "describes",
-                "内容", "段落", "提到", "写着", "关于", "描述",
-                "内容", "段落", "書いてある",
-            ],
-            file_type_patterns: vec![
-                ("pdf", FileType::Pdf),
-                ("document", FileType::TextDocument),
-                ("doc", FileType::OfficeDocument),
-                ("docx", FileType::OfficeDocument),
-                ("image", FileType::Image),
-                ("photo", FileType::Image),
-                ("picture", FileType::Image),
-                ("video", FileType::Video),
-                ("movie", FileType::Video),
-                ("code", FileType::Code),
-                ("source", FileType::Code),
-                ("script", FileType::Code),
-                ("model", FileType::Model3D),
-                ("3d", FileType::Model3D),
-                // Chinese
-                ("图片", FileType::Image),
-                ("照片", FileType::Image),
-                ("视频", FileType::Video),
-                ("代码", FileType::Code),
-                ("文档", FileType::TextDocument),
-            ],
-            time_keywords: vec![
-                ("recent", TimeHint::Recent),
-                ("latest", TimeHint::Recent),
-                ("new", TimeHint::Recent),
-                ("today", TimeHint::Today),
-                ("yesterday", TimeHint::Yesterday),
-                ("this week", TimeHint::ThisWeek),
-                ("this month", TimeHint::ThisMonth),
-                ("old", TimeHint::Old),
-                ("archive", TimeHint::Old),
-                // Chinese
-                ("最近", TimeHint::Recent),
-                ("最新", TimeHint::Recent),
-                ("今天", TimeHint::Today),
-                ("昨天", TimeHint::Yesterday),
-                ("本周", TimeHint::ThisWeek),
-                ("这周", TimeHint::ThisWeek),
-                ("本月", TimeHint::ThisMonth),
-                ("这个月", TimeHint::ThisMonth),
-                ("旧的", TimeHint::Old),
-            ],
-            content_type_patterns: vec![
-                ("paragraph", ChunkType::Paragraph),
-                ("heading", ChunkType::Heading),
-                ("title", ChunkType::Heading),
-                ("code", ChunkType::CodeBlock),
-                ("function", ChunkType::CodeBlock),
-                ("table", ChunkType::Table),
-                ("image", ChunkType::Image),
-                ("caption", ChunkType::Caption),
-                // Chinese
-                ("段落", ChunkType::Paragraph),
-                ("标题", ChunkType::Heading),
-                ("代码", ChunkType::CodeBlock),
-                ("函数", ChunkType::CodeBlock),
-                ("表格", ChunkType::Table),
-                ("图片", ChunkType::Image),
-            ],
+            lexicon,
+            clarifications: ClarificationMemory::default(),
         }
     }
 
+    /// Use previously recorded clarification answers
+    pub fn with_clarification_memory(mut self, memory: ClarificationMemory) -> Self {
+        self.clarifications = memory;
+        self
+    }
+
+    /// Get the active lexicon
+    pub fn lexicon(&self) -> &IntentLexicon {
+        &self.lexicon
+    }
+
+    /// Get the recorded clarification answers
+    pub fn clarification_memory(&self) -> &ClarificationMemory {
+        &self.clarifications
+    }
+
+    /// Record the user's answer to a clarification question
+    ///
+    /// Returns `false` if the answer was not recorded (ambiguous choice or
+    /// a query without meaningful keywords).
+    pub fn record_clarification(&mut self, query: &str, choice: IntentCategory) -> bool {
+        let pattern = self.clarification_pattern(query);
+        self.clarifications.record(&pattern, choice)
+    }
+
+    /// Normalized pattern under which clarification answers are stored
+    ///
+    /// The pattern is the sorted set of extracted keywords, so
+    /// "budget report" and "report budget" share the same answers.
+    pub fn clarification_pattern(&self, query: &str) -> String {
+        let mut keywords = self.extract_keywords(&query.to_lowercase());
+        keywords.sort();
+        keywords.dedup();
+        keywords.join(" ")
+    }
+
     /// Parse a query string and determine the search intent
     ///
     /// # Arguments
@@ -202,7 +187,10 @@ impl IntentParser {
         let score_diff = (file_score - content_score).abs();
         
         if score_diff < 0.2 && file_score < 0.5 && content_score < 0.5 {
-            IntentCategory::Ambiguous
+            self.clarifications
+                .resolve(&self.clarification_pattern(query))
+                .map(|(category, _)| category)
+                .unwrap_or(IntentCategory::Ambiguous)
         } else if file_score > content_score {
             IntentCategory::File
         } else {
@@ -210,6 +198,15 @@ impl IntentParser {
         }
     }
 
+    /// Extract the file type hint from a query regardless of the classified intent
+    ///
+    /// Content-level and ambiguous intents do not carry a file type hint, but
+    /// the search layer still needs it to pick the vector space
+    /// (e.g. "whiteboard photo with diagrams" should search image vectors).
+    pub fn extract_file_type_hint(&self, query: &str) -> Option<FileType> {
+        self.extract_file_type(&query.to_lowercase())
+    }
+
     /// Check if a query indicates file-level intent
     pub fn is_file_intent(&self, query: &str) -> bool {
         matches!(self.classify(query), IntentCategory::File)
@@ -225,14 +222,14 @@ impl IntentParser {
         let mut score = 0.0f32;
         
         // Check for file keywords
-        for keyword in &self.file_keywords {
+        for keyword in &self.lexicon.file_keywords {
             if query.contains(keyword) {
                 score += 0.3;
             }
         }
         
         // Check for file type patterns
-        for (pattern, _) in &self.file_type_patterns {
+        for (pattern, _) in &self.lexicon.file_type_patterns {
             if query.contains(pattern) {
                 score += 0.2;
             }
@@ -256,14 +253,14 @@ impl IntentParser {
         let mut score = 0.0f32;
         
         // Check for content keywords
-        for keyword in &self.content_keywords {
+        for keyword in &self.lexicon.content_keywords {
             if query.contains(keyword) {
                 score += 0.3;
             }
         }
         
         // Check for content type patterns
-        for (pattern, _) in &self.content_type_patterns {
+        for (pattern, _) in &self.lexicon.content_type_patterns {
             if query.contains(pattern) {
                 score += 0.2;
             }
@@ -301,7 +298,7 @@ impl IntentParser {
 
     /// Extract file type hint from query
     fn extract_file_type(&self, query: &str) -> Option<FileType> {
-        for (pattern, file_type) in &self.file_type_patterns {
+        for (pattern, file_type) in &self.lexicon.file_type_patterns {
             if query.contains(pattern) {
                 return Some(*file_type);
             }
@@ -311,7 +308,7 @@ impl IntentParser {
 
     /// Extract time hint from query
     fn extract_time_hint(&self, query: &str) -> Option<TimeHint> {
-        for (pattern, hint) in &self.time_keywords {
+        for (pattern, hint) in &self.lexicon.time_keywords {
             if query.contains(pattern) {
                 return Some(*hint);
             }
@@ -321,7 +318,7 @@ impl IntentParser {
 
     /// Extract content type hint from query
     fn extract_content_type(&self, query: &str) -> Option<ChunkType> {
-        for (pattern, chunk_type) in &self.content_type_patterns {
+        for (pattern, chunk_type) in &self.lexicon.content_type_patterns {
             if query.contains(pattern) {
                 return Some(*chunk_type);
             }
@@ -361,6 +358,15 @@ impl IntentParser {
             .collect()
     }
 
+    /// Extract the capture date and place of a photo query
+    ///
+    /// Dates are English month and season names ("march", "last spring",
+    /// "summer 2023"), years and "this/last year". Places are capitalized
+    /// words after "in", "at", "near" or "from" ("in New York").
+    pub fn extract_photo_hints(&self, query: &str) -> PhotoHints {
+        photo_hints(query, chrono::Utc::now().date_naive())
+    }
+
     /// Classify intent and generate appropriate SearchIntent
     fn classify_intent(
         &self,
@@ -376,6 +382,32 @@ impl IntentParser {
         
         // Ambiguous case: scores are close and both low
         if score_diff < 0.15 && max_score < 0.4 {
+            // Answered often enough before: follow the user's earlier choice
+            let learned = self.clarifications.resolve(&self.clarification_pattern(query));
+            match learned {
+                Some((IntentCategory::File, agreement)) => {
+                    return (
+                        SearchIntent::FindFile {
+                            file_type_hint,
+                            time_hint: time_hint.map(|h| self.time_hint_to_range(h)),
+                        },
+                        agreement,
+                        false,
+                    );
+                }
+                Some((IntentCategory::Content, agreement)) => {
+                    return (
+                        SearchIntent::FindContent {
+                            content_type: content_type_hint,
+                            need_location: true,
+                        },
+                        agreement,
+                        false,
+                    );
+                }
+                _ => {}
+            }
+
             let clarification_questions = self.generate_clarification_questions(query);
             let possible_intents = vec![
                 SearchIntent::FindFile {
@@ -475,6 +507,421 @@ impl IntentParser {
     }
 }
 
+// ============================================================================
+// Photo hints
+// ============================================================================
+
+/// Month names, January first
+const MONTHS: &[&str] = &[
+    "january", "february", "march", "april", "may", "june",
+    "july", "august", "september", "october", "november", "december",
+];
+
+/// Seasons and their first month (northern hemisphere); winter runs from
+/// December into the next year
+const SEASONS: &[(&str, u32)] = &[
+    ("spring", 3),
+    ("summer", 6),
+    ("autumn", 9),
+    ("fall", 9),
+    ("winter", 12),
+];
+
+/// Date words that are also common verbs; they need a date context
+const AMBIGUOUS_DATE_WORDS: &[&str] = &["may", "march", "fall"];
+
+/// Words that introduce a place ("in Kyoto", "at Lake Tahoe")
+const PLACE_PREPOSITIONS: &[&str] = &["in", "at", "near", "from"];
+
+fn photo_hints(query: &str, today: chrono::NaiveDate) -> PhotoHints {
+    PhotoHints {
+        capture_range: capture_range(&query.to_lowercase(), today),
+        location: place_name(query),
+    }
+}
+
+/// Capture range named by a lowercased query
+///
+/// Periods are counted in months since year 0 so seasons can cross a year.
+fn capture_range(query: &str, today: chrono::NaiveDate) -> Option<TimeRange> {
+    use chrono::{Datelike, Duration};
+
+    let words: Vec<&str> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+    let is_year = |word: &str| word.parse::<i32>().is_ok_and(|year| (1900..=2100).contains(&year));
+    let year = words.iter().find(|&&word| is_year(word)).and_then(|word| word.parse::<i32>().ok());
+    let current = today.year() * 12 + today.month0() as i32;
+
+    // A month or season with the word before it ("last", "this", "in")
+    let period = words.iter().enumerate().find_map(|(i, &word)| {
+        let (first, months) = match MONTHS.iter().position(|&month| month == word) {
+            Some(month) => (month as i32, 1),
+            None => {
+                let &(_, first) = SEASONS.iter().find(|(season, _)| *season == word)?;
+                (first as i32 - 1, 3)
+            }
+        };
+        let before = i.checked_sub(1).map(|i| words[i]);
+        let after = words.get(i + 1).copied();
+        if AMBIGUOUS_DATE_WORDS.contains(&word)
+            && !matches!(before, Some("in" | "last" | "this" | "during"))
+            && !after.is_some_and(is_year)
+        {
+            return None;
+        }
+        Some((first, months, before))
+    });
+
+    let (start, months) = match (period, year) {
+        (Some((first, months, _)), Some(year)) => (year * 12 + first, months),
+        (Some((first, months, before)), None) => {
+            let this_year = today.year() * 12 + first;
+            let start = match before {
+                // The latest one that is over
+                Some("last") => (0..3)
+                    .map(|back| this_year - back * 12)
+                    .find(|&start| start + months <= current)?,
+                // The one under way, else this calendar year's
+                Some("this") if this_year > current && this_year - 12 + months > current => this_year - 12,
+                Some("this") => this_year,
+                // The latest one that has begun
+                _ => (0..2).map(|back| this_year - back * 12).find(|&start| start <= current)?,
+            };
+            (start, months)
+        }
+        (None, Some(year)) => (year * 12, 12),
+        (None, None) => {
+            let offset = words.windows(2).find_map(|pair| match pair {
+                ["last", "year"] => Some(-1),
+                ["this", "year"] => Some(0),
+                _ => None,
+            })?;
+            ((today.year() + offset) * 12, 12)
+        }
+    };
+
+    Some(TimeRange {
+        start: Some(month_start(start)?),
+        end: Some(month_start(start + months)? - Duration::seconds(1)),
+    })
+}
+
+/// First instant of a month counted since year 0
+fn month_start(month: i32) -> Option<chrono::DateTime<chrono::Utc>> {
+    use chrono::TimeZone;
+
+    chrono::Utc
+        .with_ymd_and_hms(month.div_euclid(12), month.rem_euclid(12) as u32 + 1, 1, 0, 0, 0)
+        .single()
+}
+
+/// Capitalized place name after a place preposition
+///
+/// The name ends at the first word followed by punctuation, so
+/// "in Kyoto, Japan" names "Kyoto".
+fn place_name(query: &str) -> Option<String> {
+    let is_date_word = |word: &str| {
+        let word = word.to_lowercase();
+        MONTHS.contains(&word.as_str())
+            || SEASONS.iter().any(|(season, _)| *season == word)
+            || matches!(word.as_str(), "last" | "this")
+    };
+
+    let words: Vec<&str> = query.split_whitespace().collect();
+    words.iter().enumerate().find_map(|(i, word)| {
+        if !PLACE_PREPOSITIONS.contains(&word.to_lowercase().as_str()) {
+            return None;
+        }
+
+        let mut place = Vec::new();
+        for &word in &words[i + 1..] {
+            let name = word.trim_matches(|c: char| c.is_ascii_punctuation());
+            if !name.chars().next().is_some_and(char::is_uppercase) || is_date_word(name) {
+                break;
+            }
+            place.push(name);
+            if name.len() != word.len() {
+                break;
+            }
+        }
+        (!place.is_empty()).then(|| place.join(" "))
+    })
+}
+
+// ============================================================================
+// Lexicons
+// ============================================================================
+
+/// Current version of the intent lexicon data format
+pub const INTENT_LEXICON_VERSION: u32 = 1;
+
+/// Built-in lexicon shipped with the application
+const BUILTIN_LEXICON: &str = include_str!("intent_lexicon.json");
+
+/// Error types for lexicon loading
+#[derive(Error, Debug)]
+pub enum LexiconError {
+    #[error("Failed to read lexicon: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid lexicon: {0}")]
+    Parse(#[from] serde_json::Error),
+
+    #[error("Unsupported lexicon version {found} (supported up to {supported})")]
+    UnsupportedVersion { found: u32, supported: u32 },
+}
+
+/// Keyword lexicon used by `IntentParser`
+///
+/// Patterns are matched as lowercase substrings of the query. For the
+/// `*_patterns` and `time_keywords` lists the first match wins, so order
+/// matters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntentLexicon {
+    /// Data format version
+    pub version: u32,
+    /// Keywords that indicate file-level search intent
+    #[serde(default)]
+    pub file_keywords: Vec<String>,
+    /// Keywords that indicate content/segment-level search intent
+    #[serde(default)]
+    pub content_keywords: Vec<String>,
+    /// File type indicators
+    #[serde(default)]
+    pub file_type_patterns: Vec<(String, FileType)>,
+    /// Time-related keywords
+    #[serde(default)]
+    pub time_keywords: Vec<(String, TimeHint)>,
+    /// Content type indicators
+    #[serde(default)]
+    pub content_type_patterns: Vec<(String, ChunkType)>,
+}
+
+impl Default for IntentLexicon {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl IntentLexicon {
+    /// The built-in lexicon (English, Chinese and Japanese terms)
+    pub fn builtin() -> Self {
+        static BUILTIN: OnceLock<IntentLexicon> = OnceLock::new();
+        BUILTIN
+            .get_or_init(|| {
+                Self::from_json(BUILTIN_LEXICON).expect("built-in intent lexicon must be valid")
+            })
+            .clone()
+    }
+
+    /// Parse a lexicon from JSON, checking its version
+    pub fn from_json(json: &str) -> Result<Self, LexiconError> {
+        let lexicon: Self = serde_json::from_str(json)?;
+        if lexicon.version == 0 || lexicon.version > INTENT_LEXICON_VERSION {
+            return Err(LexiconError::UnsupportedVersion {
+                found: lexicon.version,
+                supported: INTENT_LEXICON_VERSION,
+            });
+        }
+        Ok(lexicon.normalized())
+    }
+
+    /// Load a lexicon from a JSON data file
+    pub fn load(path: &Path) -> Result<Self, LexiconError> {
+        let json = std::fs::read_to_string(path)?;
+        Self::from_json(&json)
+    }
+
+    /// Load a lexicon from `path`, falling back to the built-in lexicon
+    pub fn load_or_builtin(path: Option<&Path>) -> Self {
+        let Some(path) = path else {
+            return Self::builtin();
+        };
+
+        match Self::load(path) {
+            Ok(lexicon) => lexicon,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to load intent lexicon from {:?}: {}. Using built-in lexicon.",
+                    path,
+                    e
+                );
+                Self::builtin()
+            }
+        }
+    }
+
+    /// Apply user overrides on top of this lexicon
+    ///
+    /// User terms are placed before existing ones so they take precedence
+    /// where the first match wins.
+    pub fn with_overrides(mut self, overrides: &IntentLexiconOverrides) -> Self {
+        fn merge<T: Clone>(user: &[(String, T)], base: Vec<(String, T)>) -> Vec<(String, T)> {
+            user.iter()
+                .map(|(term, value)| (term.to_lowercase(), value.clone()))
+                .chain(base)
+                .collect()
+        }
+
+        let user_keywords = |terms: &[String]| terms.iter().map(|t| t.to_lowercase()).collect::<Vec<_>>();
+
+        self.file_keywords = user_keywords(&overrides.file_keywords)
+            .into_iter()
+            .chain(self.file_keywords)
+            .collect();
+        self.content_keywords = user_keywords(&overrides.content_keywords)
+            .into_iter()
+            .chain(self.content_keywords)
+            .collect();
+        self.file_type_patterns = merge(&overrides.file_type_patterns, self.file_type_patterns);
+        self.time_keywords = merge(&overrides.time_keywords, self.time_keywords);
+        self.content_type_patterns = merge(&overrides.content_type_patterns, self.content_type_patterns);
+
+        if !overrides.removed_terms.is_empty() {
+            let removed: Vec<String> = overrides.removed_terms.iter().map(|t| t.to_lowercase()).collect();
+            let keep = |term: &String| !removed.contains(term);
+            self.file_keywords.retain(keep);
+            self.content_keywords.retain(keep);
+            self.file_type_patterns.retain(|(term, _)| keep(term));
+            self.time_keywords.retain(|(term, _)| keep(term));
+            self.content_type_patterns.retain(|(term, _)| keep(term));
+        }
+
+        self
+    }
+
+    /// Lowercase all terms so they match the lowercased query
+    fn normalized(mut self) -> Self {
+        self.file_keywords.iter_mut().for_each(|t| *t = t.to_lowercase());
+        self.content_keywords.iter_mut().for_each(|t| *t = t.to_lowercase());
+        self.file_type_patterns.iter_mut().for_each(|(t, _)| *t = t.to_lowercase());
+        self.time_keywords.iter_mut().for_each(|(t, _)| *t = t.to_lowercase());
+        self.content_type_patterns.iter_mut().for_each(|(t, _)| *t = t.to_lowercase());
+        self
+    }
+}
+
+/// User additions to and removals from the intent lexicon (stored in `AppConfig`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IntentLexiconOverrides {
+    /// Extra file-level keywords
+    #[serde(default)]
+    pub file_keywords: Vec<String>,
+    /// Extra content-level keywords
+    #[serde(default)]
+    pub content_keywords: Vec<String>,
+    /// Extra file type indicators
+    #[serde(default)]
+    pub file_type_patterns: Vec<(String, FileType)>,
+    /// Extra time keywords
+    #[serde(default)]
+    pub time_keywords: Vec<(String, TimeHint)>,
+    /// Extra content type indicators
+    #[serde(default)]
+    pub content_type_patterns: Vec<(String, ChunkType)>,
+    /// Terms removed from every list
+    #[serde(default)]
+    pub removed_terms: Vec<String>,
+}
+
+// ============================================================================
+// Clarification Learning
+// ============================================================================
+
+/// Minimum answers for a pattern before it is auto-resolved
+const MIN_CLARIFICATION_ANSWERS: u32 = 2;
+
+/// Share of answers that must agree before a pattern is auto-resolved
+const MIN_CLARIFICATION_AGREEMENT: f32 = 0.75;
+
+/// Patterns remembered before the least recently answered one is dropped
+const MAX_CLARIFICATION_PATTERNS: usize = 500;
+
+/// Answers recorded for one query pattern
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClarificationCounts {
+    /// Times the user chose "find files"
+    pub file: u32,
+    /// Times the user chose "find content"
+    pub content: u32,
+    /// Number of the latest answer (see `ClarificationMemory::answers`)
+    #[serde(default)]
+    pub last_answer: u64,
+}
+
+/// Clarification answers keyed by query pattern (stored in `AppConfig`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClarificationMemory {
+    /// Answers per pattern (see `IntentParser::clarification_pattern`),
+    /// at most `MAX_CLARIFICATION_PATTERNS`
+    #[serde(default)]
+    pub patterns: HashMap<String, ClarificationCounts>,
+    /// Answers recorded so far, numbering each pattern's latest answer
+    #[serde(default)]
+    pub answers: u64,
+}
+
+impl ClarificationMemory {
+    /// Record an answer for a pattern
+    ///
+    /// Once `MAX_CLARIFICATION_PATTERNS` are remembered, the pattern
+    /// answered least recently is forgotten. Returns `false` for an empty
+    /// pattern or an `Ambiguous` choice.
+    pub fn record(&mut self, pattern: &str, choice: IntentCategory) -> bool {
+        if pattern.is_empty() || choice == IntentCategory::Ambiguous {
+            return false;
+        }
+
+        self.answers += 1;
+        let counts = self.patterns.entry(pattern.to_string()).or_default();
+        if choice == IntentCategory::File {
+            counts.file += 1;
+        } else {
+            counts.content += 1;
+        }
+        counts.last_answer = self.answers;
+
+        if self.patterns.len() > MAX_CLARIFICATION_PATTERNS {
+            let oldest = self
+                .patterns
+                .iter()
+                .min_by_key(|(_, counts)| counts.last_answer)
+                .map(|(pattern, _)| pattern.clone());
+            if let Some(oldest) = oldest {
+                self.patterns.remove(&oldest);
+            }
+        }
+        true
+    }
+
+    /// Resolve a pattern from earlier answers
+    ///
+    /// Returns the preferred category and the share of answers agreeing
+    /// with it, once enough consistent answers have been recorded.
+    pub fn resolve(&self, pattern: &str) -> Option<(IntentCategory, f32)> {
+        let counts = self.patterns.get(pattern)?;
+        let total = counts.file + counts.content;
+        if total < MIN_CLARIFICATION_ANSWERS {
+            return None;
+        }
+
+        let (category, votes) = if counts.file >= counts.content {
+            (IntentCategory::File, counts.file)
+        } else {
+            (IntentCategory::Content, counts.content)
+        };
+        let agreement = votes as f32 / total as f32;
+        (agreement >= MIN_CLARIFICATION_AGREEMENT).then_some((category, agreement))
+    }
+
+    /// Forget the answers recorded for a pattern
+    pub fn forget(&mut self, pattern: &str) -> bool {
+        self.patterns.remove(pattern).is_some()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -531,6 +978,64 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_photo_hint_extraction() {
+        use chrono::{NaiveDate, TimeZone, Utc};
+
+        let day = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let range = |hints: &PhotoHints| {
+            let range = hints.capture_range.clone().unwrap();
+            (range.start.unwrap(), range.end.unwrap())
+        };
+
+        let hints = photo_hints("photos taken in Kyoto last spring", day(2026, 10, 18));
+        assert_eq!(hints.location.as_deref(), Some("Kyoto"));
+        assert_eq!(
+            range(&hints),
+            (
+                Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 5, 31, 23, 59, 59).unwrap()
+            )
+        );
+
+        // Last spring is the previous year's while spring is under way
+        let hints = photo_hints("photos taken in Kyoto last spring", day(2026, 4, 10));
+        assert_eq!(range(&hints).0, Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap());
+
+        // This winter started in the previous December
+        let hints = photo_hints("snow pictures this winter", day(2026, 1, 15));
+        assert_eq!(
+            range(&hints),
+            (
+                Utc.with_ymd_and_hms(2025, 12, 1, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 2, 28, 23, 59, 59).unwrap()
+            )
+        );
+
+        let hints = photo_hints("beach photos summer 2023", day(2026, 10, 18));
+        assert_eq!(range(&hints).0, Utc.with_ymd_and_hms(2023, 6, 1, 0, 0, 0).unwrap());
+
+        let hints = photo_hints("pictures from New York, 2019", day(2026, 10, 18));
+        assert_eq!(hints.location.as_deref(), Some("New York"));
+        assert_eq!(
+            range(&hints),
+            (
+                Utc.with_ymd_and_hms(2019, 1, 1, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2019, 12, 31, 23, 59, 59).unwrap()
+            )
+        );
+
+        // A later month than the current one is last year's
+        let hints = photo_hints("photos at Lake Tahoe in December", day(2026, 10, 18));
+        assert_eq!(hints.location.as_deref(), Some("Lake Tahoe"));
+        assert_eq!(range(&hints).0, Utc.with_ymd_and_hms(2025, 12, 1, 0, 0, 0).unwrap());
+
+        // Verbs and lowercase words are not dates or places
+        let hints = photo_hints("images that may show a cat in the garden", day(2026, 10, 18));
+        assert!(hints.capture_range.is_none());
+        assert!(hints.location.is_none());
+    }
+
     #[test]
     fn test_ambiguous_query_detection() {
         let parser = IntentParser::new();
@@ -571,4 +1076,104 @@ mod tests {
         let result = parser.parse("关于机器学习的段落");
         assert!(matches!(result.intent, SearchIntent::FindContent { .. }));
     }
+
+    #[test]
+    fn test_builtin_lexicon() {
+        let lexicon = IntentLexicon::builtin();
+        assert_eq!(lexicon.version, INTENT_LEXICON_VERSION);
+        assert!(lexicon.file_keywords.contains(&"找文件".to_string()));
+        assert_eq!(lexicon.file_type_patterns[0], ("pdf".to_string(), FileType::Pdf));
+        assert!(lexicon.time_keywords.contains(&("本周".to_string(), TimeHint::ThisWeek)));
+    }
+
+    #[test]
+    fn test_lexicon_version_check() {
+        let result = IntentLexicon::from_json(r#"{"version": 99}"#);
+        assert!(matches!(
+            result,
+            Err(LexiconError::UnsupportedVersion { found: 99, .. })
+        ));
+
+        assert!(matches!(
+            IntentLexicon::from_json("not json"),
+            Err(LexiconError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_lexicon_load_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lexicon.json");
+        std::fs::write(
+            &path,
+            r#"{"version": 1, "file_keywords": ["Datei"], "file_type_patterns": [["bild", "Image"]]}"#,
+        )
+        .unwrap();
+
+        let parser = IntentParser::with_lexicon(IntentLexicon::load_or_builtin(Some(&path)));
+        assert!(parser.is_file_intent("Datei bericht"));
+        assert_eq!(parser.extract_file_type_hint("bild vom urlaub"), Some(FileType::Image));
+
+        // Missing file falls back to the built-in lexicon
+        let fallback = IntentLexicon::load_or_builtin(Some(&dir.path().join("missing.json")));
+        assert_eq!(fallback, IntentLexicon::builtin());
+    }
+
+    #[test]
+    fn test_lexicon_overrides() {
+        let overrides = IntentLexiconOverrides {
+            file_keywords: vec!["Dossier".to_string()],
+            file_type_patterns: vec![("spreadsheet".to_string(), FileType::OfficeDocument)],
+            removed_terms: vec!["pdf".to_string()],
+            ..Default::default()
+        };
+        let parser = IntentParser::with_lexicon(IntentLexicon::builtin().with_overrides(&overrides));
+
+        assert!(parser.is_file_intent("dossier budget"));
+        assert_eq!(
+            parser.extract_file_type_hint("quarterly spreadsheet"),
+            Some(FileType::OfficeDocument)
+        );
+        assert_eq!(parser.extract_file_type_hint("pdf"), None);
+    }
+
+    #[test]
+    fn test_clarification_learning() {
+        let mut parser = IntentParser::new();
+        assert!(parser.parse("hello world").is_ambiguous);
+
+        // A single answer is not enough to auto-resolve
+        assert!(parser.record_clarification("hello world", IntentCategory::Content));
+        assert!(parser.parse("hello world").is_ambiguous);
+
+        // Patterns ignore keyword order
+        assert!(parser.record_clarification("world hello", IntentCategory::Content));
+        let result = parser.parse("hello world");
+        assert!(!result.is_ambiguous);
+        assert!(matches!(result.intent, SearchIntent::FindContent { .. }));
+        assert_eq!(parser.classify("hello world"), IntentCategory::Content);
+
+        // Conflicting answers fall back to asking again
+        parser.record_clarification("hello world", IntentCategory::File);
+        assert!(parser.parse("hello world").is_ambiguous);
+
+        assert!(!parser.record_clarification("hello world", IntentCategory::Ambiguous));
+        assert!(!parser.record_clarification("the", IntentCategory::File));
+    }
+
+    #[test]
+    fn test_clarification_memory_is_capped() {
+        let mut memory = ClarificationMemory::default();
+        for i in 0..MAX_CLARIFICATION_PATTERNS {
+            assert!(memory.record(&format!("pattern{}", i), IntentCategory::File));
+        }
+        // Answering again makes a pattern recent
+        memory.record("pattern0", IntentCategory::File);
+
+        memory.record("newest", IntentCategory::Content);
+        assert_eq!(memory.patterns.len(), MAX_CLARIFICATION_PATTERNS);
+        assert!(memory.patterns.contains_key("pattern0"));
+        assert!(!memory.patterns.contains_key("pattern1"));
+        assert!(memory.patterns.contains_key("newest"));
+    }
 }
@@ -5,12 +5,15 @@
 //! - Tantivy-based full-text indexing
 //! - Schema version control and migration
 //! - Intent parsing for file-level vs content-level search
+//! - Configurable intent lexicons and learned clarification answers
 //! - Hybrid search combining vector and BM25 search
+//! - Progressive streaming search with cancellation
 
 pub mod tokenizer;
 pub mod text_index;
 pub mod intent;
 pub mod hybrid;
+pub mod streaming;
 
 #[cfg(test)]
 mod tests;
@@ -18,11 +21,20 @@ mod tests;
 pub use tokenizer::{
     JiebaTokenizer, MultilingualTokenizer, SimpleTokenizer, Language, LanguageDetector,
 };
-pub use text_index::{TextIndex, TextIndexConfig, TextIndexError};
-pub use intent::{IntentParser, IntentParseResult, IntentCategory, TimeHint};
+pub use text_index::{PhotoFields, TextIndex, TextIndexConfig, TextIndexError};
+pub use intent::{
+    IntentParser, IntentParseResult, IntentCategory, PhotoHints, TimeHint, IntentLexicon,
+    IntentLexiconOverrides, ClarificationMemory, ClarificationCounts, LexiconError,
+    INTENT_LEXICON_VERSION,
+};
 pub use hybrid::{
     HybridSearchEngine, HybridSearchConfig, HybridSearchError, HybridSearchFilters,
-    QueryType, ScoredResult, SearchSource, classify_query, apply_filters,
+    QueryType, ScoredResult, SearchSource, ScoreExplanation, ScoreBoost, FilterDecision,
+    ChunkAggregation, FileResult,
+    classify_query, apply_filters, select_vector_space,
+};
+pub use streaming::{
+    SearchPhase, SearchPhaseUpdate, SearchCancellationRegistry, PhaseFuture, run_progressive_search,
 };
 
 #[cfg(feature = "japanese")]
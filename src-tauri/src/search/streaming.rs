@@ -0,0 +1,208 @@
+//! Progressive (streaming) search for NeuralFS
+//!
+//! This module provides:
+//! - Phase ordering for progressive results (keyword, vector, cloud-refined)
+//! - Cancellation of in-flight searches by request id
+//! - Partial vs final status reporting for each emitted phase
+//!
+//! Each phase produces its own batch of results. The transport (Tauri events
+//! in `commands::search`) is supplied by the caller as a sink, so the phase
+//! runner stays independent of the UI layer.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::core::types::search::SearchStatus;
+
+/// Phase of a progressive search, in emission order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchPhase {
+    /// Filename and BM25 keyword hits (fastest)
+    Keyword,
+    /// Vector (semantic) hits
+    Vector,
+    /// Results re-ranked with cloud-enhanced understanding
+    CloudRefined,
+}
+
+/// A batch of results emitted for one phase
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchPhaseUpdate<R> {
+    /// Request this update belongs to
+    pub request_id: Uuid,
+    /// Phase that produced these results
+    pub phase: SearchPhase,
+    /// `Partial` while more phases follow, a terminal status otherwise
+    pub status: SearchStatus,
+    /// Results produced by this phase
+    pub results: Vec<R>,
+    /// Whether this is the last update for the request
+    pub is_final: bool,
+    /// Error message if the phase failed
+    pub error: Option<String>,
+}
+
+/// Boxed future producing the results of a single phase
+pub type PhaseFuture<'a, R> = Pin<Box<dyn Future<Output = Result<Vec<R>, String>> + Send + 'a>>;
+
+/// Registry of in-flight streaming searches, used for cancellation
+#[derive(Debug, Clone, Default)]
+pub struct SearchCancellationRegistry {
+    tokens: Arc<DashMap<Uuid, CancellationToken>>,
+}
+
+impl SearchCancellationRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a new search and return its cancellation token
+    ///
+    /// A search still in flight under the same `request_id` is cancelled:
+    /// the new one replaces it.
+    pub fn register(&self, request_id: Uuid) -> CancellationToken {
+        let token = CancellationToken::new();
+        match self.tokens.entry(request_id) {
+            Entry::Occupied(mut entry) => {
+                tracing::debug!("Search {} replaced by a newer request", request_id);
+                entry.insert(token.clone()).cancel();
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(token.clone());
+            }
+        }
+        token
+    }
+
+    /// Cancel an in-flight search
+    ///
+    /// Returns `false` if the request is unknown or already finished.
+    pub fn cancel(&self, request_id: &Uuid) -> bool {
+        match self.tokens.remove(request_id) {
+            Some((_, token)) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove a finished search from the registry
+    ///
+    /// `token` is the one the search was registered with. A cancelled token
+    /// has already left the registry, possibly replaced by a newer search,
+    /// so nothing is removed for it.
+    pub fn complete(&self, request_id: &Uuid, token: &CancellationToken) {
+        self.tokens.remove_if(request_id, |_, _| !token.is_cancelled());
+    }
+
+    /// Number of in-flight searches
+    pub fn active_count(&self) -> usize {
+        self.tokens.len()
+    }
+}
+
+/// Run search phases in order, emitting one update per phase
+///
+/// Phases run sequentially so the cheapest results reach the UI first.
+/// A failed phase is reported and the search continues; the final status is
+/// then `PartialSuccess`. Cancellation interrupts the current phase and
+/// emits a final `Cancelled` update.
+///
+/// Returns the terminal status of the search.
+pub async fn run_progressive_search<'a, R, S>(
+    request_id: Uuid,
+    token: CancellationToken,
+    phases: Vec<(SearchPhase, PhaseFuture<'a, R>)>,
+    mut sink: S,
+) -> SearchStatus
+where
+    S: FnMut(SearchPhaseUpdate<R>),
+{
+    let phase_count = phases.len();
+    let mut any_results = false;
+    let mut any_failed = false;
+
+    for (index, (phase, future)) in phases.into_iter().enumerate() {
+        let is_last = index + 1 == phase_count;
+
+        let outcome = tokio::select! {
+            biased;
+            _ = token.cancelled() => None,
+            result = future => Some(result),
+        };
+
+        let Some(result) = outcome else {
+            tracing::debug!("Search {} cancelled during {:?} phase", request_id, phase);
+            sink(SearchPhaseUpdate {
+                request_id,
+                phase,
+                status: SearchStatus::Cancelled,
+                results: Vec::new(),
+                is_final: true,
+                error: None,
+            });
+            return SearchStatus::Cancelled;
+        };
+
+        let (results, error) = match result {
+            Ok(results) => (results, None),
+            Err(e) => {
+                tracing::warn!("Search {} {:?} phase failed: {}", request_id, phase, e);
+                any_failed = true;
+                (Vec::new(), Some(e))
+            }
+        };
+        any_results |= !results.is_empty();
+
+        let status = if !is_last {
+            SearchStatus::Partial
+        } else {
+            final_status(any_results, any_failed)
+        };
+
+        sink(SearchPhaseUpdate {
+            request_id,
+            phase,
+            status,
+            results,
+            is_final: is_last,
+            error,
+        });
+
+        if is_last {
+            return status;
+        }
+    }
+
+    // No phases at all
+    let status = final_status(false, false);
+    sink(SearchPhaseUpdate {
+        request_id,
+        phase: SearchPhase::Keyword,
+        status,
+        results: Vec::new(),
+        is_final: true,
+        error: None,
+    });
+    status
+}
+
+fn final_status(any_results: bool, any_failed: bool) -> SearchStatus {
+    if any_failed {
+        SearchStatus::PartialSuccess
+    } else if any_results {
+        SearchStatus::Success
+    } else {
+        SearchStatus::NoResults
+    }
+}
@@ -413,9 +413,10 @@ mod intent_tests {
 // ============================================================================
 
 use super::hybrid::{
-    HybridSearchConfig, HybridSearchEngine, HybridSearchFilters, QueryType, ScoredResult,
-    SearchSource, apply_filters, classify_query,
+    ChunkAggregation, HybridSearchConfig, HybridSearchEngine, HybridSearchFilters, QueryType,
+    ScoredResult, SearchSource, apply_filters, classify_query,
 };
+use crate::core::types::chunk::ChunkLocation;
 use crate::core::types::file::FileType;
 use crate::vector::store::SearchResult as VectorSearchResult;
 use crate::search::text_index::SearchResult as TextSearchResult;
@@ -461,6 +462,8 @@ fn scored_result_strategy() -> impl Strategy<Value = ScoredResult> {
             source,
             filename,
             tags,
+            explanation: None,
+            location: None,
         }
     })
 }
@@ -499,7 +502,10 @@ fn text_result_strategy() -> impl Strategy<Value = TextSearchResult> {
             filename,
             tags,
             modified_at: None,
+            captured_at: None,
+            location: None,
             score,
+            explanation: None,
         }
     })
 }
@@ -695,6 +701,8 @@ mod hybrid_search_tests {
                 source: SearchSource::Vector,
                 filename: Some("high_score.txt".to_string()),
                 tags: vec![],
+                explanation: None,
+                location: None,
             },
             ScoredResult {
                 file_id: Uuid::new_v4(),
@@ -705,6 +713,8 @@ mod hybrid_search_tests {
                 source: SearchSource::Vector,
                 filename: Some("low_score.txt".to_string()),
                 tags: vec![],
+                explanation: None,
+                location: None,
             },
         ];
 
@@ -736,7 +746,10 @@ mod hybrid_search_tests {
             filename: Some("test.txt".to_string()),
             tags: vec!["tag1".to_string()],
             modified_at: None,
+            captured_at: None,
+            location: None,
             score: 10.0, // BM25 scores can be > 1
+            explanation: None,
         }];
 
         let merged = engine.merge_results(vector_results, bm25_results, (0.6, 0.4));
@@ -746,6 +759,62 @@ mod hybrid_search_tests {
         assert_eq!(merged[0].source, SearchSource::Both);
         assert!(merged[0].vector_score.is_some());
         assert!(merged[0].bm25_score.is_some());
+        assert!(merged[0].explanation.is_none());
+    }
+
+    #[test]
+    fn test_explain_records_score_breakdown() {
+        let engine = HybridSearchEngine::with_config(
+            HybridSearchConfig::default().with_explain(true),
+        )
+        .unwrap();
+
+        let file_id = Uuid::new_v4();
+        let mut payload = HashMap::new();
+        payload.insert("file_id".to_string(), Value::String(file_id.to_string()));
+
+        let vector_results = vec![VectorSearchResult {
+            id: 1,
+            score: 0.8,
+            payload,
+            vector: None,
+        }];
+
+        let bm25_results = vec![TextSearchResult {
+            file_id,
+            chunk_id: None,
+            filename: Some("report.txt".to_string()),
+            tags: vec![],
+            modified_at: None,
+            captured_at: None,
+            location: None,
+            score: 10.0,
+            explanation: Some("{\"value\":10.0}".to_string()),
+        }];
+
+        let weights = engine.get_adjusted_weights(QueryType::NaturalLanguage);
+        let mut merged = engine.merge_results(vector_results, bm25_results, weights);
+        engine.apply_exact_match_boost(&mut merged, "report");
+        let merged = engine.filter_by_score(merged);
+        let merged = apply_filters(merged, &HybridSearchFilters::new().with_min_score(0.1));
+
+        assert_eq!(merged.len(), 1);
+        let explanation = merged[0].explanation.as_ref().unwrap();
+        assert_eq!((explanation.vector_weight, explanation.bm25_weight), weights);
+        assert_eq!(explanation.raw_vector_score, Some(0.8));
+        assert_eq!(explanation.raw_bm25_score, Some(10.0));
+        assert_eq!(explanation.bm25_explanation.as_deref(), Some("{\"value\":10.0}"));
+        assert!((explanation.merged_score - 1.0).abs() < 0.001);
+
+        // Exact filename match plus the per-word match
+        assert_eq!(explanation.boosts.len(), 2);
+        let boost: f32 = explanation.boosts.iter().map(|b| b.factor).product();
+        assert!((explanation.final_score - explanation.merged_score * boost).abs() < 0.001);
+        assert!((explanation.final_score - merged[0].score).abs() < 0.001);
+
+        let filter_names: Vec<&str> = explanation.filters.iter().map(|f| f.filter.as_str()).collect();
+        assert_eq!(filter_names, vec!["min_vector_score", "min_bm25_score", "min_score"]);
+        assert!(explanation.filters.iter().all(|f| f.passed));
     }
 
     #[test]
@@ -762,6 +831,8 @@ mod hybrid_search_tests {
                 source: SearchSource::Vector,
                 filename: Some("report.pdf".to_string()),
                 tags: vec![],
+                explanation: None,
+                location: None,
             },
             ScoredResult {
                 file_id: Uuid::new_v4(),
@@ -772,6 +843,8 @@ mod hybrid_search_tests {
                 source: SearchSource::Vector,
                 filename: Some("other.txt".to_string()),
                 tags: vec![],
+                explanation: None,
+                location: None,
             },
         ];
 
@@ -781,6 +854,156 @@ mod hybrid_search_tests {
         let report_result = results.iter().find(|r| r.filename == Some("report.pdf".to_string())).unwrap();
         assert!(report_result.score > 0.5, "Score should be boosted");
     }
+
+    fn chunk_hit(file_id: Uuid, score: f32, start_line: u32) -> VectorSearchResult {
+        let location = ChunkLocation {
+            start_line: Some(start_line),
+            end_line: Some(start_line + 10),
+            ..Default::default()
+        };
+        let mut payload = HashMap::new();
+        payload.insert("file_id".to_string(), Value::String(file_id.to_string()));
+        payload.insert("chunk_id".to_string(), Value::String(Uuid::new_v4().to_string()));
+        payload.insert("chunk_location".to_string(), serde_json::to_value(&location).unwrap());
+
+        VectorSearchResult {
+            id: start_line as u64,
+            score,
+            payload,
+            vector: None,
+        }
+    }
+
+    #[test]
+    fn test_group_by_file_collapses_chunks() {
+        let engine = HybridSearchEngine::with_config(
+            HybridSearchConfig::default().with_passages_per_file(2),
+        )
+        .unwrap();
+
+        let doc = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let vector_results = vec![
+            chunk_hit(doc, 0.9, 1),
+            chunk_hit(doc, 0.5, 20),
+            chunk_hit(doc, 0.7, 40),
+            chunk_hit(other, 0.6, 1),
+        ];
+
+        let merged = engine.merge_results(vector_results, vec![], (1.0, 0.0));
+        // Chunks of the same file are no longer overwritten during merging
+        assert_eq!(merged.len(), 4);
+
+        let files = engine.group_by_file(merged);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].file_id, doc);
+        assert_eq!(files[0].matched_chunks, 3);
+        assert_eq!(files[0].passages.len(), 2);
+        assert!((files[0].score - 1.0).abs() < 0.001, "Max aggregation uses the best chunk");
+
+        let lines: Vec<Option<u32>> = files[0]
+            .passages
+            .iter()
+            .map(|p| p.location.as_ref().and_then(|l| l.start_line))
+            .collect();
+        assert_eq!(lines, vec![Some(1), Some(40)]);
+    }
+
+    #[test]
+    fn test_chunk_aggregation_modes() {
+        let scores = [0.9, 0.5, 0.1];
+
+        assert!((ChunkAggregation::Max.aggregate(&scores) - 0.9).abs() < 0.001);
+        assert!((ChunkAggregation::Sum.aggregate(&scores) - 1.5).abs() < 0.001);
+
+        let sharp = ChunkAggregation::Softmax { temperature: 0.01 }.aggregate(&scores);
+        assert!((sharp - 0.9).abs() < 0.001, "Low temperature approaches max");
+        let flat = ChunkAggregation::Softmax { temperature: 100.0 }.aggregate(&scores);
+        assert!((flat - 0.5).abs() < 0.01, "High temperature approaches mean");
+
+        assert_eq!(ChunkAggregation::Sum.aggregate(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_group_for_intent_keeps_passages_for_content_search() {
+        let engine = HybridSearchEngine::new();
+        let doc = Uuid::new_v4();
+        let merged = engine.merge_results(
+            vec![chunk_hit(doc, 0.9, 1), chunk_hit(doc, 0.8, 20)],
+            vec![],
+            (1.0, 0.0),
+        );
+
+        let content_intent = SearchIntent::FindContent {
+            content_type: None,
+            need_location: true,
+        };
+        let passages = engine.group_for_intent(merged.clone(), &content_intent);
+        assert_eq!(passages.len(), 2);
+        assert!(passages.iter().all(|p| p.passages.len() == 1));
+
+        let file_intent = SearchIntent::FindFile {
+            file_type_hint: None,
+            time_hint: None,
+        };
+        let files = engine.group_for_intent(merged, &file_intent);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].passages.len(), 2);
+    }
+
+    #[test]
+    fn test_collapse_duplicates_folds_group_members() {
+        let (original, copy, kept, other) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let hits = vec![
+            chunk_hit(original, 0.9, 1),
+            chunk_hit(other, 0.8, 1),
+            chunk_hit(copy, 0.7, 1),
+            chunk_hit(kept, 0.6, 1),
+        ];
+
+        let open_group = Uuid::new_v4();
+        let resolved_group = Uuid::new_v4();
+        let mut duplicates = crate::dedup::DuplicateMap::new();
+        duplicates.insert(original, open_group);
+        duplicates.insert(copy, open_group);
+        duplicates.insert(other, resolved_group);
+        duplicates.insert(kept, resolved_group);
+        duplicates.set_canonical(resolved_group, kept);
+
+        // Off by default
+        let engine = HybridSearchEngine::new();
+        let files = engine.group_by_file(engine.merge_results(hits.clone(), vec![], (1.0, 0.0)));
+        assert_eq!(engine.collapse_duplicates(files, &duplicates).len(), 4);
+
+        let engine =
+            HybridSearchEngine::with_config(HybridSearchConfig::default().with_collapse_duplicates(true)).unwrap();
+        let files = engine.group_by_file(engine.merge_results(hits, vec![], (1.0, 0.0)));
+        let collapsed = engine.collapse_duplicates(files, &duplicates);
+
+        assert_eq!(collapsed.len(), 2);
+        assert_eq!(collapsed[0].file_id, original);
+        assert_eq!(collapsed[0].duplicates, vec![copy]);
+        // The kept file represents its resolved group at the best member's rank
+        assert_eq!(collapsed[1].file_id, kept);
+        assert_eq!(collapsed[1].duplicates, vec![other]);
+        assert!(collapsed[1].score > 0.85);
+
+        // Passages of one file stay separate; the copy folds into the first
+        let content_intent = SearchIntent::FindContent {
+            content_type: None,
+            need_location: true,
+        };
+        let hits = vec![
+            chunk_hit(original, 0.9, 1),
+            chunk_hit(original, 0.8, 20),
+            chunk_hit(copy, 0.7, 1),
+        ];
+        let passages = engine.group_for_intent(engine.merge_results(hits, vec![], (1.0, 0.0)), &content_intent);
+        let collapsed = engine.collapse_duplicates(passages, &duplicates);
+        assert_eq!(collapsed.len(), 2);
+        assert!(collapsed.iter().all(|p| p.file_id == original));
+        assert_eq!(collapsed[0].duplicates, vec![copy]);
+    }
 }
 
 
@@ -937,7 +1160,10 @@ mod latency_tests {
                 filename: Some(format!("file_{}.txt", i)),
                 tags: vec!["test".to_string()],
                 modified_at: None,
+                captured_at: None,
+                location: None,
                 score: 5.0 + (i as f32 * 0.1),
+                explanation: None,
             })
             .collect();
 
@@ -985,3 +1211,160 @@ mod latency_tests {
         }
     }
 }
+
+// ============================================================================
+// Streaming Search Tests
+// ============================================================================
+
+#[cfg(test)]
+mod streaming_tests {
+    use super::super::streaming::{
+        run_progressive_search, PhaseFuture, SearchCancellationRegistry, SearchPhase,
+        SearchPhaseUpdate,
+    };
+    use crate::core::types::search::SearchStatus;
+    use uuid::Uuid;
+
+    fn ready(results: Vec<u32>) -> PhaseFuture<'static, u32> {
+        Box::pin(async move { Ok(results) })
+    }
+
+    #[tokio::test]
+    async fn test_phases_emitted_in_order_with_partial_status() {
+        let registry = SearchCancellationRegistry::new();
+        let request_id = Uuid::now_v7();
+        let token = registry.register(request_id);
+
+        let mut updates: Vec<SearchPhaseUpdate<u32>> = Vec::new();
+        let status = run_progressive_search(
+            request_id,
+            token,
+            vec![
+                (SearchPhase::Keyword, ready(vec![1])),
+                (SearchPhase::Vector, ready(vec![2, 3])),
+                (SearchPhase::CloudRefined, ready(vec![3, 2, 1])),
+            ],
+            |update| updates.push(update),
+        )
+        .await;
+
+        assert_eq!(status, SearchStatus::Success);
+        let phases: Vec<SearchPhase> = updates.iter().map(|u| u.phase).collect();
+        assert_eq!(
+            phases,
+            vec![SearchPhase::Keyword, SearchPhase::Vector, SearchPhase::CloudRefined]
+        );
+        assert_eq!(updates[0].status, SearchStatus::Partial);
+        assert_eq!(updates[1].status, SearchStatus::Partial);
+        assert_eq!(updates[2].status, SearchStatus::Success);
+        assert!(updates[..2].iter().all(|u| !u.is_final));
+        assert!(updates[2].is_final);
+        assert!(updates.iter().all(|u| u.request_id == request_id));
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_stops_remaining_phases() {
+        let registry = SearchCancellationRegistry::new();
+        let request_id = Uuid::now_v7();
+        let token = registry.register(request_id);
+
+        let cancel_registry = registry.clone();
+        let pending: PhaseFuture<'static, u32> = Box::pin(async move {
+            // Cancel from inside the phase, then never finish
+            assert!(cancel_registry.cancel(&request_id));
+            std::future::pending::<()>().await;
+            Ok(vec![])
+        });
+
+        let mut updates = Vec::new();
+        let status = run_progressive_search(
+            request_id,
+            token,
+            vec![
+                (SearchPhase::Keyword, ready(vec![1])),
+                (SearchPhase::Vector, pending),
+                (SearchPhase::CloudRefined, ready(vec![2])),
+            ],
+            |update| updates.push(update),
+        )
+        .await;
+
+        assert_eq!(status, SearchStatus::Cancelled);
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[1].phase, SearchPhase::Vector);
+        assert_eq!(updates[1].status, SearchStatus::Cancelled);
+        assert!(updates[1].is_final);
+
+        // Already removed from the registry
+        assert!(!registry.cancel(&request_id));
+        assert_eq!(registry.active_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_failed_phase_yields_partial_success() {
+        let registry = SearchCancellationRegistry::new();
+        let request_id = Uuid::now_v7();
+        let token = registry.register(request_id);
+
+        let failing: PhaseFuture<'static, u32> =
+            Box::pin(async { Err("cloud timeout".to_string()) });
+
+        let mut updates = Vec::new();
+        let status = run_progressive_search(
+            request_id,
+            token,
+            vec![
+                (SearchPhase::Keyword, ready(vec![1])),
+                (SearchPhase::CloudRefined, failing),
+            ],
+            |update| updates.push(update),
+        )
+        .await;
+
+        assert_eq!(status, SearchStatus::PartialSuccess);
+        assert_eq!(updates[1].error.as_deref(), Some("cloud timeout"));
+        assert!(updates[1].is_final);
+    }
+
+    #[tokio::test]
+    async fn test_no_results_status() {
+        let registry = SearchCancellationRegistry::new();
+        let request_id = Uuid::now_v7();
+        let token = registry.register(request_id);
+
+        let mut updates = Vec::new();
+        let status = run_progressive_search(
+            request_id,
+            token.clone(),
+            vec![(SearchPhase::Keyword, ready(vec![]))],
+            |update| updates.push(update),
+        )
+        .await;
+
+        assert_eq!(status, SearchStatus::NoResults);
+        assert_eq!(updates.len(), 1);
+        assert!(updates[0].is_final);
+
+        registry.complete(&request_id, &token);
+        assert_eq!(registry.active_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_reused_request_id_cancels_previous_search() {
+        let registry = SearchCancellationRegistry::new();
+        let request_id = Uuid::now_v7();
+        let first = registry.register(request_id);
+        let second = registry.register(request_id);
+
+        assert!(first.is_cancelled());
+        assert!(!second.is_cancelled());
+        assert_eq!(registry.active_count(), 1);
+
+        // The replaced search finishing leaves the newer one cancellable
+        registry.complete(&request_id, &first);
+        assert_eq!(registry.active_count(), 1);
+        assert!(registry.cancel(&request_id));
+        assert!(second.is_cancelled());
+        assert_eq!(registry.active_count(), 0);
+    }
+}
@@ -4,6 +4,7 @@
 //! - Multi-language full-text indexing
 //! - Schema version control
 //! - Incremental index updates
+//! - Photo capture time and location fields for date and place filters
 
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -21,7 +22,7 @@ use uuid::Uuid;
 use super::tokenizer::register_tokenizers;
 
 /// Current schema version - increment when schema changes
-const SCHEMA_VERSION: u32 = 1;
+const SCHEMA_VERSION: u32 = 2;
 
 /// Schema version file name
 const SCHEMA_VERSION_FILE: &str = ".schema_version";
@@ -85,6 +86,17 @@ pub struct SchemaFields {
     pub content: Field,
     pub tags: Field,
     pub modified_at: Field,
+    pub captured_at: Field,
+    pub location: Field,
+}
+
+/// Capture fields of a photo, indexed with each of its documents
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PhotoFields {
+    /// Capture timestamp (camera wall-clock time read as UTC)
+    pub captured_at: Option<u64>,
+    /// Place name, e.g. "Kyoto, Japan"
+    pub location: Option<String>,
 }
 
 /// Full-text search index using Tantivy
@@ -193,6 +205,19 @@ impl TextIndex {
         // Modified timestamp - indexed for range queries
         let modified_at = schema_builder.add_u64_field("modified_at", INDEXED | STORED);
 
+        // Photo capture timestamp - indexed for range queries
+        let captured_at = schema_builder.add_u64_field("captured_at", INDEXED | STORED);
+
+        // Photo location - use multilingual tokenizer
+        let location_options = TextOptions::default()
+            .set_indexing_options(
+                TextFieldIndexing::default()
+                    .set_tokenizer("multilingual")
+                    .set_index_option(IndexRecordOption::WithFreqsAndPositions),
+            )
+            .set_stored();
+        let location = schema_builder.add_text_field("location", location_options);
+
         let schema = schema_builder.build();
         let fields = SchemaFields {
             file_id,
@@ -201,6 +226,8 @@ impl TextIndex {
             content,
             tags,
             modified_at,
+            captured_at,
+            location,
         };
 
         (schema, fields)
@@ -263,6 +290,7 @@ impl TextIndex {
     pub fn validate_schema_compatibility(&self) -> Result<bool, TextIndexError> {
         let expected_fields = vec![
             "file_id", "chunk_id", "filename", "content", "tags", "modified_at",
+            "captured_at", "location",
         ];
 
         let existing_fields: Vec<&str> = self
@@ -319,6 +347,31 @@ impl TextIndex {
         content: &str,
         tags: &[String],
         modified_at: u64,
+    ) -> Result<(), TextIndexError> {
+        self.index_photo_document(
+            writer,
+            file_id,
+            chunk_id,
+            filename,
+            content,
+            tags,
+            modified_at,
+            &PhotoFields::default(),
+        )
+    }
+
+    /// Index a document of a photo along with its capture fields
+    #[allow(clippy::too_many_arguments)]
+    pub fn index_photo_document(
+        &self,
+        writer: &IndexWriter,
+        file_id: &Uuid,
+        chunk_id: Option<&Uuid>,
+        filename: &str,
+        content: &str,
+        tags: &[String],
+        modified_at: u64,
+        photo: &PhotoFields,
     ) -> Result<(), TextIndexError> {
         let mut doc = TantivyDocument::new();
 
@@ -331,6 +384,12 @@ impl TextIndex {
         doc.add_text(self.fields.content, content);
         doc.add_text(self.fields.tags, &tags.join(" "));
         doc.add_u64(self.fields.modified_at, modified_at);
+        if let Some(captured_at) = photo.captured_at {
+            doc.add_u64(self.fields.captured_at, captured_at);
+        }
+        if let Some(ref location) = photo.location {
+            doc.add_text(self.fields.location, location);
+        }
 
         writer.add_document(doc)?;
         Ok(())
@@ -358,13 +417,39 @@ impl TextIndex {
         &self,
         query: &str,
         limit: usize,
+    ) -> Result<Vec<SearchResult>, TextIndexError> {
+        self.search_impl(query, limit, false)
+    }
+
+    /// Search the index and attach Tantivy's score explanation to each result
+    ///
+    /// Explanations are comparatively expensive to compute, so this is only
+    /// used when a caller explicitly asks for relevance debugging output.
+    pub fn search_explained(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<SearchResult>, TextIndexError> {
+        self.search_impl(query, limit, true)
+    }
+
+    fn search_impl(
+        &self,
+        query: &str,
+        limit: usize,
+        explain: bool,
     ) -> Result<Vec<SearchResult>, TextIndexError> {
         let searcher = self.reader.searcher();
 
-        // Create query parser for content and filename fields
+        // Create query parser for content, filename, tag and location fields
         let query_parser = QueryParser::for_index(
             &self.index,
-            vec![self.fields.content, self.fields.filename, self.fields.tags],
+            vec![
+                self.fields.content,
+                self.fields.filename,
+                self.fields.tags,
+                self.fields.location,
+            ],
         );
 
         let parsed_query = query_parser.parse_query(query)?;
@@ -403,6 +488,21 @@ impl TextIndex {
                 .get_first(self.fields.modified_at)
                 .and_then(|v| v.as_u64());
 
+            let captured_at = doc
+                .get_first(self.fields.captured_at)
+                .and_then(|v| v.as_u64());
+
+            let location = doc
+                .get_first(self.fields.location)
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string());
+
+            let explanation = if explain {
+                Some(parsed_query.explain(&searcher, doc_address)?.to_pretty_json())
+            } else {
+                None
+            };
+
             if let Some(file_id) = file_id {
                 results.push(SearchResult {
                     file_id,
@@ -410,7 +510,10 @@ impl TextIndex {
                     filename,
                     tags,
                     modified_at,
+                    captured_at,
+                    location,
                     score,
+                    explanation,
                 });
             }
         }
@@ -425,11 +528,28 @@ impl TextIndex {
         filters: &SearchFilters,
         limit: usize,
     ) -> Result<Vec<SearchResult>, TextIndexError> {
-        // For now, perform basic search and filter in memory
-        // TODO: Implement proper Tantivy filter queries
-        let mut results = self.search(query, limit * 2)?;
+        let results = self.search(query, limit * 2)?;
+        Ok(Self::apply_filters(results, filters, limit))
+    }
 
-        // Apply filters
+    /// Search with filters, attaching Tantivy's score explanation to each result
+    pub fn search_with_filters_explained(
+        &self,
+        query: &str,
+        filters: &SearchFilters,
+        limit: usize,
+    ) -> Result<Vec<SearchResult>, TextIndexError> {
+        let results = self.search_explained(query, limit * 2)?;
+        Ok(Self::apply_filters(results, filters, limit))
+    }
+
+    fn apply_filters(
+        mut results: Vec<SearchResult>,
+        filters: &SearchFilters,
+        limit: usize,
+    ) -> Vec<SearchResult> {
+        // For now, filter in memory after the search
+        // TODO: Implement proper Tantivy filter queries
         if let Some(ref tag_filter) = filters.tags {
             results.retain(|r| {
                 tag_filter.iter().any(|t| r.tags.contains(t))
@@ -448,8 +568,31 @@ impl TextIndex {
             });
         }
 
+        // Capture filters only match photos that recorded the field
+        if let Some(min_captured) = filters.min_captured_at {
+            results.retain(|r| {
+                r.captured_at.map(|c| c >= min_captured).unwrap_or(false)
+            });
+        }
+
+        if let Some(max_captured) = filters.max_captured_at {
+            results.retain(|r| {
+                r.captured_at.map(|c| c <= max_captured).unwrap_or(false)
+            });
+        }
+
+        if let Some(ref place) = filters.location {
+            let place = place.to_lowercase();
+            results.retain(|r| {
+                r.location
+                    .as_ref()
+                    .map(|l| l.to_lowercase().contains(&place))
+                    .unwrap_or(false)
+            });
+        }
+
         results.truncate(limit);
-        Ok(results)
+        results
     }
 
     /// Get the number of documents in the index
@@ -509,8 +652,17 @@ pub struct SearchResult {
     /// Last modified timestamp
     pub modified_at: Option<u64>,
 
+    /// Photo capture timestamp
+    pub captured_at: Option<u64>,
+
+    /// Photo location
+    pub location: Option<String>,
+
     /// BM25 relevance score
     pub score: f32,
+
+    /// Tantivy score explanation as JSON (explain searches only)
+    pub explanation: Option<String>,
 }
 
 /// Search filters
@@ -527,6 +679,15 @@ pub struct SearchFilters {
 
     /// File type filter
     pub file_types: Option<Vec<String>>,
+
+    /// Minimum photo capture timestamp
+    pub min_captured_at: Option<u64>,
+
+    /// Maximum photo capture timestamp
+    pub max_captured_at: Option<u64>,
+
+    /// Photo location (case-insensitive substring)
+    pub location: Option<String>,
 }
 
 impl std::fmt::Debug for TextIndex {
@@ -617,6 +778,89 @@ mod unit_tests {
         assert_eq!(results[0].file_id, file_id);
     }
 
+    #[test]
+    fn test_search_explained() {
+        let (index, _temp_dir) = create_test_index();
+        let mut writer = index.writer().unwrap();
+
+        let file_id = Uuid::new_v4();
+        index
+            .index_document(
+                &writer,
+                &file_id,
+                None,
+                "explain.txt",
+                "Relevance explanations help debug ranking",
+                &[],
+                1234567890,
+            )
+            .unwrap();
+
+        writer.commit().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(100));
+
+        // Plain search does not pay for explanations
+        let results = index.search("ranking", 10).unwrap();
+        assert!(!results.is_empty());
+        assert!(results[0].explanation.is_none());
+
+        let results = index.search_explained("ranking", 10).unwrap();
+        assert!(!results.is_empty());
+        let explanation = results[0].explanation.as_ref().expect("explanation should be present");
+        assert!(explanation.contains("value"));
+    }
+
+    #[test]
+    fn test_photo_capture_filters() {
+        let (index, _temp_dir) = create_test_index();
+        let mut writer = index.writer().unwrap();
+
+        let kyoto = Uuid::new_v4();
+        let osaka = Uuid::new_v4();
+        let scan = Uuid::new_v4();
+        let photos = [
+            (kyoto, "Photo taken 2023-04-02 10:15", Some(1680430500), Some("Kyoto, Japan")),
+            (osaka, "Photo taken 2022-11-20 16:40", Some(1668962400), Some("Osaka, Japan")),
+            (scan, "Photo scan", None, None),
+        ];
+        for (file_id, content, captured_at, location) in photos {
+            let photo = PhotoFields {
+                captured_at,
+                location: location.map(String::from),
+            };
+            index
+                .index_photo_document(&writer, &file_id, None, "IMG.jpg", content, &[], 1700000000, &photo)
+                .unwrap();
+        }
+        writer.commit().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(100));
+
+        // Place names are searchable
+        let results = index.search("kyoto", 10).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].file_id, kyoto);
+        assert_eq!(results[0].location.as_deref(), Some("Kyoto, Japan"));
+        assert_eq!(results[0].captured_at, Some(1680430500));
+
+        // Spring 2023
+        let filters = SearchFilters {
+            min_captured_at: Some(1677628800),
+            max_captured_at: Some(1685577599),
+            ..Default::default()
+        };
+        let results = index.search_with_filters("photo", &filters, 10).unwrap();
+        let ids: Vec<Uuid> = results.iter().map(|r| r.file_id).collect();
+        assert_eq!(ids, vec![kyoto]);
+
+        let filters = SearchFilters {
+            location: Some("japan".to_string()),
+            ..Default::default()
+        };
+        let results = index.search_with_filters("photo", &filters, 10).unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| r.file_id != scan));
+    }
+
     #[test]
     fn test_delete_document() {
         let (index, _temp_dir) = create_test_index();
@@ -676,7 +920,8 @@ mod unit_tests {
         let info = index.schema_info();
 
         assert_eq!(info.version, SCHEMA_VERSION);
-        assert_eq!(info.field_count, 6); // file_id, chunk_id, filename, content, tags, modified_at
+        // file_id, chunk_id, filename, content, tags, modified_at, captured_at, location
+        assert_eq!(info.field_count, 8);
         assert!(info.field_names.contains(&"file_id".to_string()));
         assert!(info.field_names.contains(&"content".to_string()));
     }
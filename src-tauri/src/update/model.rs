@@ -65,6 +65,8 @@ pub enum ModelType {
     TextEmbedding,
     /// Image embedding model (e.g., CLIP)
     ImageEmbedding,
+    /// CLIP text tower for text-to-image search
+    ClipText,
     /// Intent parsing model
     IntentParser,
     /// Tokenizer vocabulary
@@ -76,6 +78,7 @@ impl std::fmt::Display for ModelType {
         match self {
             ModelType::TextEmbedding => write!(f, "text_embedding"),
             ModelType::ImageEmbedding => write!(f, "image_embedding"),
+            ModelType::ClipText => write!(f, "clip_text"),
             ModelType::IntentParser => write!(f, "intent_parser"),
             ModelType::Tokenizer => write!(f, "tokenizer"),
         }
@@ -167,6 +170,17 @@ impl Default for ModelManifest {
                     description: "Image embedding model for visual search".to_string(),
                     vram_mb: 512,
                 },
+                ModelInfo {
+                    id: "clip-vit-base-text".to_string(),
+                    name: "CLIP ViT-Base Text Encoder".to_string(),
+                    model_type: ModelType::ClipText,
+                    filename: "clip-vit-base-patch32-text.onnx".to_string(),
+                    size_bytes: 254_000_000, // ~254MB
+                    sha256: "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855".to_string(),
+                    required: false,
+                    description: "CLIP text encoder for searching images with natural language".to_string(),
+                    vram_mb: 256,
+                },
             ],
             version: "1.0.0".to_string(),
             updated_at: Utc::now(),
@@ -707,6 +721,7 @@ mod tests {
     fn test_model_type_display() {
         assert_eq!(ModelType::TextEmbedding.to_string(), "text_embedding");
         assert_eq!(ModelType::ImageEmbedding.to_string(), "image_embedding");
+        assert_eq!(ModelType::ClipText.to_string(), "clip_text");
         assert_eq!(ModelType::IntentParser.to_string(), "intent_parser");
         assert_eq!(ModelType::Tokenizer.to_string(), "tokenizer");
     }
@@ -0,0 +1,123 @@
+//! Active and in-progress vector collections
+//!
+//! Each embedding model writes to its own collection. Search reads the
+//! active collection while a replacement is built in the background;
+//! `cut_over` then swaps the replacement in under a single lock.
+
+use std::sync::{Arc, RwLock};
+
+use uuid::Uuid;
+
+use crate::embeddings::EmbeddingModelTag;
+
+use super::store::{VectorResult, VectorStore};
+
+/// A vector store together with the model that fills it
+#[derive(Clone)]
+pub struct VectorCollection {
+    /// Model whose vectors the store holds
+    pub model: EmbeddingModelTag,
+    /// The store
+    pub store: Arc<VectorStore>,
+}
+
+impl VectorCollection {
+    /// Pair a store with its model
+    pub fn new(model: EmbeddingModelTag, store: Arc<VectorStore>) -> Self {
+        Self { model, store }
+    }
+}
+
+impl std::fmt::Debug for VectorCollection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VectorCollection")
+            .field("model", &self.model)
+            .field("collection_name", &self.store.config().collection_name)
+            .finish()
+    }
+}
+
+#[derive(Debug)]
+struct Slots {
+    active: VectorCollection,
+    building: Option<VectorCollection>,
+}
+
+/// The collection serving search and the one being built to replace it
+#[derive(Debug)]
+pub struct VectorCollections {
+    slots: RwLock<Slots>,
+}
+
+impl VectorCollections {
+    /// Start with a single active collection
+    pub fn new(active: VectorCollection) -> Self {
+        Self {
+            slots: RwLock::new(Slots {
+                active,
+                building: None,
+            }),
+        }
+    }
+
+    /// Collection that serves search
+    pub fn active(&self) -> VectorCollection {
+        self.slots.read().unwrap().active.clone()
+    }
+
+    /// Collection being built for a new model, if any
+    pub fn building(&self) -> Option<VectorCollection> {
+        self.slots.read().unwrap().building.clone()
+    }
+
+    /// Collection new vectors are written to: the one being built, if any
+    pub fn write_target(&self) -> VectorCollection {
+        let slots = self.slots.read().unwrap();
+        slots.building.clone().unwrap_or_else(|| slots.active.clone())
+    }
+
+    /// Store holding vectors of the given model key, if it is tracked
+    pub fn store_for(&self, model_key: &str) -> Option<Arc<VectorStore>> {
+        let slots = self.slots.read().unwrap();
+        std::iter::once(&slots.active)
+            .chain(slots.building.as_ref())
+            .find(|collection| collection.model.key() == model_key)
+            .map(|collection| collection.store.clone())
+    }
+
+    /// Begin building a replacement, returning any build it supersedes
+    pub fn begin_build(&self, collection: VectorCollection) -> Option<VectorCollection> {
+        self.slots.write().unwrap().building.replace(collection)
+    }
+
+    /// Drop the replacement under construction
+    pub fn abort_build(&self) -> Option<VectorCollection> {
+        self.slots.write().unwrap().building.take()
+    }
+
+    /// Make the replacement active, returning the retired collection
+    ///
+    /// Returns `None` if no replacement is being built.
+    pub fn cut_over(&self) -> Option<VectorCollection> {
+        let mut slots = self.slots.write().unwrap();
+        let building = slots.building.take()?;
+        Some(std::mem::replace(&mut slots.active, building))
+    }
+
+    /// Remove a file's vectors from every tracked collection
+    pub async fn delete_by_file_id(&self, file_id: Uuid) -> VectorResult<u64> {
+        let stores: Vec<Arc<VectorStore>> = {
+            let slots = self.slots.read().unwrap();
+            std::iter::once(&slots.active)
+                .chain(slots.building.as_ref())
+                .map(|collection| collection.store.clone())
+                .collect()
+        };
+
+        let mut deleted = 0;
+        for store in stores {
+            deleted += store.delete_by_file_id(file_id).await?;
+        }
+        Ok(deleted)
+    }
+}
@@ -19,6 +19,42 @@ impl Default for Distance {
     }
 }
 
+/// Embedding space a collection belongs to
+///
+/// Text chunks and images are embedded by different models with different
+/// dimensions, so each space lives in its own collection.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum VectorSpace {
+    /// Text chunk embeddings (all-MiniLM-L6-v2, 384 dimensions)
+    Text,
+    /// Image embeddings and CLIP text queries (CLIP ViT-B/32, 512 dimensions)
+    Image,
+}
+
+impl VectorSpace {
+    /// Default collection name for this space
+    pub fn collection_name(&self) -> &'static str {
+        match self {
+            VectorSpace::Text => "neuralfs_vectors",
+            VectorSpace::Image => "neuralfs_image_vectors",
+        }
+    }
+
+    /// Vector dimension stored in this space
+    pub fn vector_size(&self) -> u64 {
+        match self {
+            VectorSpace::Text => 384,
+            VectorSpace::Image => 512,
+        }
+    }
+}
+
+impl Default for VectorSpace {
+    fn default() -> Self {
+        VectorSpace::Text
+    }
+}
+
 /// HNSW index configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HnswConfig {
@@ -119,6 +155,13 @@ impl Default for VectorStoreConfig {
 }
 
 impl VectorStoreConfig {
+    /// Create a default config for the given embedding space
+    pub fn for_space(space: VectorSpace) -> Self {
+        Self::default()
+            .with_collection_name(space.collection_name())
+            .with_vector_size(space.vector_size())
+    }
+
     /// Create a new config with custom collection name
     pub fn with_collection_name(mut self, name: impl Into<String>) -> Self {
         self.collection_name = name.into();
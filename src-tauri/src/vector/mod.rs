@@ -2,17 +2,21 @@
 //!
 //! This module provides vector storage and retrieval functionality for semantic search.
 //! It uses Qdrant in embedded mode for zero-dependency local deployment.
+//! Vectors from different embedding models live in separate collections;
+//! `VectorCollections` tracks which one serves search.
 
 mod store;
 mod config;
 mod error;
+mod collections;
 
 #[cfg(test)]
 mod tests;
 
-pub use store::VectorStore;
-pub use config::{VectorStoreConfig, HnswConfig, OptimizerConfig, Distance};
+pub use store::{VectorStore, VectorPoint};
+pub use config::{VectorStoreConfig, VectorSpace, HnswConfig, OptimizerConfig, Distance};
 pub use error::VectorError;
+pub use collections::{VectorCollection, VectorCollections};
 
 /// Payload field names for vector points
 pub mod payload_fields {
@@ -20,6 +24,8 @@ pub mod payload_fields {
     pub const FILE_ID: &str = "file_id";
     /// Chunk UUID as string
     pub const CHUNK_ID: &str = "chunk_id";
+    /// Chunk location within the file (serialized `ChunkLocation`)
+    pub const CHUNK_LOCATION: &str = "chunk_location";
     /// File type enum value
     pub const FILE_TYPE: &str = "file_type";
     /// Array of tag UUIDs
@@ -30,4 +36,8 @@ pub mod payload_fields {
     pub const MODIFIED_AT: &str = "modified_at";
     /// Privacy level enum value
     pub const PRIVACY_LEVEL: &str = "privacy_level";
+    /// Identifier of the embedding model that produced the vector
+    pub const MODEL_ID: &str = "model_id";
+    /// Version of the embedding model that produced the vector
+    pub const MODEL_VERSION: &str = "model_version";
 }
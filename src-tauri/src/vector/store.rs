@@ -2,7 +2,7 @@
 //!
 //! Provides vector storage and retrieval for semantic search functionality.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -11,6 +11,9 @@ use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+use crate::core::types::chunk::ChunkLocation;
+use crate::embeddings::EmbeddingModelTag;
+
 use super::config::{Distance, VectorStoreConfig};
 use super::error::VectorError;
 use super::payload_fields;
@@ -55,6 +58,14 @@ impl VectorPoint {
         self.with_payload(payload_fields::CHUNK_ID, Value::String(chunk_id.to_string()))
     }
 
+    /// Add chunk location to payload
+    pub fn with_chunk_location(self, location: &ChunkLocation) -> Self {
+        match serde_json::to_value(location) {
+            Ok(value) => self.with_payload(payload_fields::CHUNK_LOCATION, value),
+            Err(_) => self,
+        }
+    }
+
     /// Add file_type to payload
     pub fn with_file_type(self, file_type: &str) -> Self {
         self.with_payload(payload_fields::FILE_TYPE, Value::String(file_type.to_string()))
@@ -70,6 +81,12 @@ impl VectorPoint {
     pub fn with_privacy_level(self, level: &str) -> Self {
         self.with_payload(payload_fields::PRIVACY_LEVEL, Value::String(level.to_string()))
     }
+
+    /// Add the producing embedding model to payload
+    pub fn with_model(self, model: &EmbeddingModelTag) -> Self {
+        self.with_payload(payload_fields::MODEL_ID, Value::String(model.model_id.clone()))
+            .with_payload(payload_fields::MODEL_VERSION, Value::String(model.version.clone()))
+    }
 }
 
 /// Search result from vector query
@@ -101,6 +118,13 @@ impl SearchResult {
             .and_then(|v| v.as_str())
             .and_then(|s| Uuid::parse_str(s).ok())
     }
+
+    /// Extract chunk location from payload
+    pub fn chunk_location(&self) -> Option<ChunkLocation> {
+        self.payload
+            .get(payload_fields::CHUNK_LOCATION)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
 }
 
 /// Filter conditions for vector search
@@ -112,6 +136,8 @@ pub struct SearchFilter {
     pub tag_ids: Option<Vec<Uuid>>,
     /// Exclude private files
     pub exclude_private: bool,
+    /// Exclude sensitive and private files (cloud-assisted searches)
+    pub exclude_sensitive: bool,
     /// Filter by file IDs (OR logic)
     pub file_ids: Option<Vec<Uuid>>,
 }
@@ -140,6 +166,12 @@ impl SearchFilter {
         self
     }
 
+    /// Exclude sensitive and private files
+    pub fn exclude_sensitive(mut self) -> Self {
+        self.exclude_sensitive = true;
+        self
+    }
+
     /// Filter by specific file IDs
     pub fn with_file_ids(mut self, ids: Vec<Uuid>) -> Self {
         self.file_ids = Some(ids);
@@ -578,6 +610,12 @@ impl VectorStore {
         Ok(vectors.contains_key(&id))
     }
 
+    /// Which of `ids` exist, checked under a single lock
+    pub async fn existing(&self, ids: &[u64]) -> VectorResult<HashSet<u64>> {
+        let vectors = self.vectors.read().await;
+        Ok(ids.iter().copied().filter(|id| vectors.contains_key(id)).collect())
+    }
+
     /// Clear all vectors from the store
     pub async fn clear(&self) -> VectorResult<u64> {
         let mut vectors = self.vectors.write().await;
@@ -691,6 +729,17 @@ impl VectorStore {
             }
         }
 
+        if filter.exclude_sensitive {
+            let privacy = stored
+                .payload
+                .get(payload_fields::PRIVACY_LEVEL)
+                .and_then(|v| v.as_str());
+
+            if matches!(privacy, Some("Sensitive") | Some("Private")) {
+                return false;
+            }
+        }
+
         // Check file ID filter
         if let Some(ref file_ids) = filter.file_ids {
             let stored_file_id = stored
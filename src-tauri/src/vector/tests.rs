@@ -151,6 +151,18 @@ async fn test_delete() {
     assert!(store.get(1).await.unwrap().is_none());
 }
 
+#[tokio::test]
+async fn test_existing_ids() {
+    let (store, _temp_dir) = create_test_store(4).await;
+
+    store.upsert(VectorPoint::new(1, vec![1.0, 0.0, 0.0, 0.0])).await.unwrap();
+    store.upsert(VectorPoint::new(3, vec![0.0, 1.0, 0.0, 0.0])).await.unwrap();
+
+    let existing = store.existing(&[1, 2, 3]).await.unwrap();
+    assert_eq!(existing, std::collections::HashSet::from([1, 3]));
+    assert!(store.existing(&[]).await.unwrap().is_empty());
+}
+
 #[tokio::test]
 async fn test_delete_by_file_id() {
     let (store, _temp_dir) = create_test_store(4).await;
@@ -191,6 +203,27 @@ async fn test_invalid_dimension() {
     }
 }
 
+#[tokio::test]
+async fn test_image_space_is_separate_collection() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config = VectorStoreConfig::for_space(VectorSpace::Image)
+        .with_storage_path(temp_dir.path().to_string_lossy().to_string());
+
+    assert_eq!(config.collection_name, "neuralfs_image_vectors");
+    assert_ne!(config.collection_name, VectorStoreConfig::default().collection_name);
+
+    let store = VectorStore::new(config).await.expect("Failed to create store");
+
+    // Text embeddings must not be written into the image space
+    let result = store.upsert(VectorPoint::new(1, vec![0.0; 384])).await;
+    assert!(matches!(
+        result,
+        Err(VectorError::InvalidDimension { expected: 512, actual: 384 })
+    ));
+
+    assert!(store.upsert(VectorPoint::new(1, vec![0.0; 512])).await.is_ok());
+}
+
 #[tokio::test]
 async fn test_clear() {
     let (store, _temp_dir) = create_test_store(4).await;
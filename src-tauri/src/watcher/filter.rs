@@ -3,11 +3,12 @@
 //! Provides blacklist/whitelist filtering for file system paths.
 //! Protects against "folder bombs" (directories with excessive files).
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 use glob::Pattern;
 
 use crate::core::error::{NeuralFSError, Result};
+use super::ignore::{IgnoreCache, IgnoreRule, DEFAULT_IGNORE_FILES};
 
 /// Configuration for directory filtering
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +25,20 @@ pub struct DirectoryFilterConfig {
     pub max_file_size: u64,
     /// Whether to follow symbolic links
     pub follow_symlinks: bool,
+    /// Whether to honor ignore files found in scanned directories
+    #[serde(default = "default_respect_ignore_files")]
+    pub respect_ignore_files: bool,
+    /// Ignore file names, lowest precedence first
+    #[serde(default = "default_ignore_file_names")]
+    pub ignore_file_names: Vec<String>,
+}
+
+fn default_respect_ignore_files() -> bool {
+    true
+}
+
+fn default_ignore_file_names() -> Vec<String> {
+    DEFAULT_IGNORE_FILES.iter().map(|name| name.to_string()).collect()
 }
 
 impl Default for DirectoryFilterConfig {
@@ -80,13 +95,15 @@ impl Default for DirectoryFilterConfig {
             max_files_per_dir: 10000,
             max_file_size: 500 * 1024 * 1024, // 500MB
             follow_symlinks: false,
+            respect_ignore_files: default_respect_ignore_files(),
+            ignore_file_names: default_ignore_file_names(),
         }
     }
 }
 
 
 /// Result of filtering a path
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FilterResult {
     /// Path should be included
     Include,
@@ -95,10 +112,12 @@ pub enum FilterResult {
 }
 
 /// Reason for excluding a path
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FilterReason {
     /// Path matches a blacklist pattern
     Blacklisted,
+    /// Path matches a rule in an ignore file
+    Ignored(IgnoreRule),
     /// Directory depth exceeds maximum
     TooDeep,
     /// Directory contains too many files
@@ -135,6 +154,7 @@ pub struct DirectoryFilter {
     config: DirectoryFilterConfig,
     blacklist_matchers: Vec<Pattern>,
     whitelist_matchers: Vec<Pattern>,
+    ignore_cache: IgnoreCache,
 }
 
 impl DirectoryFilter {
@@ -154,10 +174,13 @@ impl DirectoryFilter {
             .collect::<std::result::Result<Vec<_>, _>>()
             .map_err(|e| NeuralFSError::ConfigError(format!("Invalid whitelist pattern: {}", e)))?;
 
+        let ignore_cache = IgnoreCache::new(config.ignore_file_names.clone());
+
         Ok(Self {
             config,
             blacklist_matchers,
             whitelist_matchers,
+            ignore_cache,
         })
     }
 
@@ -205,7 +228,14 @@ impl DirectoryFilter {
             }
         }
 
-        // 3. Check depth
+        // 3. Check ignore files
+        if self.config.respect_ignore_files {
+            if let Some(rule) = self.ignore_cache.excluding_rule(path) {
+                return FilterResult::Exclude(FilterReason::Ignored(rule));
+            }
+        }
+
+        // 4. Check depth
         let depth = path.components().count();
         if depth > self.config.max_depth as usize {
             return FilterResult::Exclude(FilterReason::TooDeep);
@@ -214,6 +244,39 @@ impl DirectoryFilter {
         FilterResult::Include
     }
 
+    /// Check if a path is one of the configured ignore files
+    pub fn is_ignore_file(&self, path: &Path) -> bool {
+        self.config.respect_ignore_files && self.ignore_cache.is_ignore_file(path)
+    }
+
+    /// Drop cached rules after an ignore file was created, changed or removed
+    ///
+    /// Does nothing if `path` is not an ignore file. Returns whether the
+    /// cache was invalidated.
+    pub fn invalidate_ignore_file(&self, path: &Path) -> bool {
+        if !self.ignore_cache.is_ignore_file(path) {
+            return false;
+        }
+        self.ignore_cache.invalidate(path);
+        true
+    }
+
+    /// Read ignore files no higher than `roots`, or than the repository
+    /// root enclosing them
+    pub fn set_monitored_roots(&self, roots: &[PathBuf]) {
+        self.ignore_cache.set_roots(roots);
+    }
+
+    /// Drop all cached ignore-file rules
+    pub fn clear_ignore_cache(&self) {
+        self.ignore_cache.clear();
+    }
+
+    /// Number of directories whose ignore files are cached
+    pub fn cached_ignore_dirs(&self) -> usize {
+        self.ignore_cache.cached_dirs()
+    }
+
     /// Check if a path matches any blacklist pattern
     pub fn is_blacklisted(&self, path: &Path) -> bool {
         let path_str = path.to_string_lossy();
@@ -0,0 +1,276 @@
+//! Hierarchical ignore files (.gitignore, .ignore, .neuralfsignore)
+//!
+//! Follows gitignore semantics:
+//! - Rules apply to the directory holding the file and everything below it
+//! - Deeper files override shallower ones; later rules override earlier ones
+//! - `!pattern` re-includes, except below a directory that is itself ignored
+//! - A trailing `/` matches directories only; a `/` elsewhere anchors the
+//!   pattern to the ignore file's directory
+//!
+//! Rules are read from the enclosing monitored root down, or from the
+//! repository root when a monitored root lies inside a git repository;
+//! ignore files above that are never consulted. Parsed files are cached per
+//! directory until invalidated, for at most `MAX_CACHED_DIRS` directories.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use glob::{MatchOptions, Pattern};
+use parking_lot::RwLock;
+
+/// Ignore files read in each directory, lowest precedence first
+pub const DEFAULT_IGNORE_FILES: &[&str] = &[".gitignore", ".ignore", ".neuralfsignore"];
+
+/// Directories whose parsed ignore files are kept at once
+pub(crate) const MAX_CACHED_DIRS: usize = 4096;
+
+/// An ignore-file rule that decided whether a path is excluded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IgnoreRule {
+    /// Ignore file the rule comes from
+    pub source: PathBuf,
+    /// 1-based line number in the source file
+    pub line: usize,
+    /// The rule as written, including any leading `!`
+    pub pattern: String,
+    /// Whether the rule re-includes matching paths
+    pub negated: bool,
+}
+
+#[derive(Debug)]
+struct IgnorePattern {
+    rule: IgnoreRule,
+    glob: Pattern,
+    dir_only: bool,
+}
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+impl IgnorePattern {
+    fn parse(line: &str, source: &Path, line_number: usize) -> Option<Self> {
+        let raw = line.trim_end_matches('\r');
+        let mut text = trim_unescaped_trailing_spaces(raw);
+        if text.is_empty() || text.starts_with('#') {
+            return None;
+        }
+
+        let negated = text.starts_with('!');
+        if negated {
+            text = &text[1..];
+        }
+        if text.starts_with("\\!") || text.starts_with("\\#") {
+            text = &text[1..];
+        }
+
+        let dir_only = text.ends_with('/');
+        let text = text.trim_end_matches('/');
+        if text.is_empty() {
+            return None;
+        }
+
+        // A separator anywhere but the end anchors the pattern
+        let anchored = text.contains('/');
+        let text = text.trim_start_matches('/');
+        let glob = if anchored {
+            text.to_string()
+        } else {
+            format!("**/{}", text)
+        };
+
+        Some(Self {
+            rule: IgnoreRule {
+                source: source.to_path_buf(),
+                line: line_number,
+                pattern: raw.trim_end().to_string(),
+                negated,
+            },
+            glob: Pattern::new(&glob).ok()?,
+            dir_only,
+        })
+    }
+
+    fn matches(&self, relative: &str, is_dir: bool) -> bool {
+        (is_dir || !self.dir_only) && self.glob.matches_with(relative, MATCH_OPTIONS)
+    }
+}
+
+fn trim_unescaped_trailing_spaces(line: &str) -> &str {
+    let mut end = line.len();
+    while end > 0 && line.as_bytes()[end - 1] == b' ' {
+        if end >= 2 && line.as_bytes()[end - 2] == b'\\' {
+            break;
+        }
+        end -= 1;
+    }
+    &line[..end]
+}
+
+/// Rules of all ignore files in one directory
+#[derive(Debug, Default)]
+struct DirRules {
+    patterns: Vec<IgnorePattern>,
+}
+
+/// Reads and caches ignore files per directory
+#[derive(Debug)]
+pub(crate) struct IgnoreCache {
+    file_names: Vec<String>,
+    dirs: RwLock<HashMap<PathBuf, Arc<DirRules>>>,
+    /// Monitored roots and the directory their rules start at
+    scopes: RwLock<Vec<(PathBuf, PathBuf)>>,
+}
+
+impl IgnoreCache {
+    pub(crate) fn new(file_names: Vec<String>) -> Self {
+        Self {
+            file_names,
+            dirs: RwLock::new(HashMap::new()),
+            scopes: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Limit rule lookups to `roots`
+    ///
+    /// Rules of a path below a root are read from the root down, or from the
+    /// enclosing repository root when the root lies inside a git work tree.
+    /// Paths outside every root see all ignore files above them.
+    pub(crate) fn set_roots(&self, roots: &[PathBuf]) {
+        let scopes = roots
+            .iter()
+            .map(|root| {
+                let top = root
+                    .ancestors()
+                    .find(|dir| dir.join(".git").exists())
+                    .unwrap_or(root);
+                (root.clone(), top.to_path_buf())
+            })
+            .collect();
+        *self.scopes.write() = scopes;
+        self.clear();
+    }
+
+    /// Directory the rules of `path` start at, if it lies below a root
+    fn scope_top(&self, path: &Path) -> Option<PathBuf> {
+        self.scopes
+            .read()
+            .iter()
+            .filter(|(root, _)| path.starts_with(root))
+            .max_by_key(|(root, _)| root.components().count())
+            .map(|(_, top)| top.clone())
+    }
+
+    /// Whether `path` names one of the ignore files
+    pub(crate) fn is_ignore_file(&self, path: &Path) -> bool {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .map_or(false, |name| self.file_names.iter().any(|f| f == name))
+    }
+
+    /// Drop the cached rules of the directory holding `ignore_file`
+    pub(crate) fn invalidate(&self, ignore_file: &Path) {
+        if let Some(dir) = ignore_file.parent() {
+            self.dirs.write().remove(dir);
+        }
+    }
+
+    /// Drop all cached rules
+    pub(crate) fn clear(&self) {
+        self.dirs.write().clear();
+    }
+
+    /// Number of directories with cached rules
+    pub(crate) fn cached_dirs(&self) -> usize {
+        self.dirs.read().len()
+    }
+
+    /// The rule excluding `path`, if any
+    ///
+    /// Ancestor directories are checked first, since nothing below an
+    /// ignored directory can be re-included.
+    pub(crate) fn excluding_rule(&self, path: &Path) -> Option<IgnoreRule> {
+        let top = self.scope_top(path);
+        if top.as_deref() == Some(path) {
+            return None;
+        }
+        let mut ancestors: Vec<&Path> = Vec::new();
+        for dir in path.ancestors().skip(1) {
+            ancestors.push(dir);
+            if top.as_deref() == Some(dir) {
+                break;
+            }
+        }
+        let mut scopes: Vec<(&Path, Arc<DirRules>)> = Vec::with_capacity(ancestors.len());
+
+        for (index, dir) in ancestors.iter().rev().enumerate() {
+            // The top directory has no rules above it
+            if index > 0 {
+                if let Some(rule) = Self::decide(dir, true, &scopes) {
+                    return Some(rule);
+                }
+            }
+            let rules = self.rules_for(dir);
+            if !rules.patterns.is_empty() {
+                scopes.push((dir, rules));
+            }
+        }
+
+        Self::decide(path, path.is_dir(), &scopes)
+    }
+
+    /// Last matching rule across scopes decides; returns it if it excludes
+    fn decide(target: &Path, is_dir: bool, scopes: &[(&Path, Arc<DirRules>)]) -> Option<IgnoreRule> {
+        let mut decision: Option<&IgnorePattern> = None;
+        for (dir, rules) in scopes {
+            let Ok(relative) = target.strip_prefix(dir) else {
+                continue;
+            };
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            if relative.is_empty() {
+                continue;
+            }
+            for pattern in &rules.patterns {
+                if pattern.matches(&relative, is_dir) {
+                    decision = Some(pattern);
+                }
+            }
+        }
+
+        decision
+            .filter(|pattern| !pattern.rule.negated)
+            .map(|pattern| pattern.rule.clone())
+    }
+
+    fn rules_for(&self, dir: &Path) -> Arc<DirRules> {
+        if let Some(rules) = self.dirs.read().get(dir) {
+            return Arc::clone(rules);
+        }
+
+        let mut patterns = Vec::new();
+        for name in &self.file_names {
+            let source = dir.join(name);
+            let Ok(content) = std::fs::read_to_string(&source) else {
+                continue;
+            };
+            patterns.extend(
+                content
+                    .lines()
+                    .enumerate()
+                    .filter_map(|(index, line)| IgnorePattern::parse(line, &source, index + 1)),
+            );
+        }
+
+        let rules = Arc::new(DirRules { patterns });
+        let mut dirs = self.dirs.write();
+        // Start over rather than grow without bound on huge trees
+        if dirs.len() >= MAX_CACHED_DIRS {
+            dirs.clear();
+        }
+        dirs.insert(dir.to_path_buf(), Arc::clone(&rules));
+        rules
+    }
+}
@@ -0,0 +1,333 @@
+//! Persistent journal of watcher batches
+//!
+//! Batches are appended before they are handed to the indexer, and the
+//! indexer records a processed watermark once a batch has been applied. If
+//! the app stops in between, the unprocessed batches are replayed on the
+//! next start instead of rescanning every monitored root.
+//!
+//! The journal is a JSON-lines file:
+//! - A header identifying the format
+//! - `reconciled` once a full scan brought the index up to date
+//! - `batch` records with increasing sequence numbers
+//! - `processed` records carrying the watermark
+//!
+//! A journal that is missing, unreadable or was never reconciled cannot
+//! vouch for the index, so callers fall back to a full scan.
+
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{EventBatch, FileEvent};
+use crate::core::error::{NeuralFSError, Result};
+
+/// Journal format version
+const JOURNAL_VERSION: u32 = 1;
+
+/// Size above which a fully processed journal is rewritten (default: 4MB)
+const DEFAULT_COMPACT_BYTES: u64 = 4 * 1024 * 1024;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Record {
+    Header { version: u32 },
+    Reconciled { seq: u64 },
+    Batch { seq: u64, id: Uuid, events: Vec<FileEvent> },
+    Processed { seq: u64 },
+}
+
+/// Condition the journal was found in when opened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JournalState {
+    /// Replayable: every batch since the last full scan is recorded
+    Intact,
+    /// No journal existed
+    Missing,
+    /// A full scan was started but never finished
+    Unreconciled,
+    /// The journal could not be read (reason)
+    Corrupt(String),
+}
+
+/// What opening the journal found
+#[derive(Debug)]
+pub struct JournalRecovery {
+    /// Condition of the journal
+    pub state: JournalState,
+    /// Batches received but not processed, in order
+    pub pending: Vec<EventBatch>,
+}
+
+impl JournalRecovery {
+    /// Whether the index must be brought up to date with a full scan
+    pub fn needs_full_scan(&self) -> bool {
+        self.state != JournalState::Intact
+    }
+}
+
+struct Inner {
+    file: File,
+    bytes: u64,
+    reconciled: bool,
+    next_seq: u64,
+    watermark: u64,
+    /// Appended batches above the watermark (seq -> batch id)
+    unprocessed: BTreeMap<u64, Uuid>,
+}
+
+/// Append-only journal of watcher batches
+pub struct EventJournal {
+    path: PathBuf,
+    compact_bytes: u64,
+    inner: Mutex<Inner>,
+}
+
+impl EventJournal {
+    /// Open the journal at `path`, creating it if needed
+    ///
+    /// A corrupt journal is moved aside (`<path>.corrupt`) and replaced by
+    /// an empty one.
+    pub fn open(path: impl Into<PathBuf>) -> Result<(Self, JournalRecovery)> {
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let (state, mut inner, pending) = match std::fs::read_to_string(&path) {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                (JournalState::Missing, Self::create(&path, 0, false)?, Vec::new())
+            }
+            Err(e) => return Err(e.into()),
+            Ok(content) => match Self::parse(&content) {
+                Ok(parsed) => {
+                    let state = if parsed.reconciled {
+                        JournalState::Intact
+                    } else {
+                        JournalState::Unreconciled
+                    };
+                    let file = OpenOptions::new().append(true).open(&path)?;
+                    let inner = Inner {
+                        file,
+                        bytes: content.len() as u64,
+                        reconciled: parsed.reconciled,
+                        next_seq: parsed.last_seq + 1,
+                        watermark: parsed.watermark,
+                        unprocessed: parsed.pending.iter().map(|batch| (batch.0, batch.1.id)).collect(),
+                    };
+                    (state, inner, parsed.pending.into_iter().map(|(_, batch)| batch).collect())
+                }
+                Err(reason) => {
+                    tracing::warn!("Event journal {:?} is corrupt: {}", path, reason);
+                    let mut aside = path.clone().into_os_string();
+                    aside.push(".corrupt");
+                    std::fs::rename(&path, PathBuf::from(aside))?;
+                    (JournalState::Corrupt(reason), Self::create(&path, 0, false)?, Vec::new())
+                }
+            },
+        };
+
+        if state != JournalState::Intact {
+            // Anything recorded is superseded by the full scan
+            inner.unprocessed.clear();
+        }
+
+        let journal = Self {
+            path,
+            compact_bytes: DEFAULT_COMPACT_BYTES,
+            inner: Mutex::new(inner),
+        };
+        Ok((journal, JournalRecovery { state, pending }))
+    }
+
+    /// Rewrite the journal once fully processed and larger than `bytes`
+    pub fn with_compact_bytes(mut self, bytes: u64) -> Self {
+        self.compact_bytes = bytes;
+        self
+    }
+
+    /// Location of the journal file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Record a batch before it is handed on; returns its sequence number
+    pub fn append(&self, batch: &EventBatch) -> Result<u64> {
+        let mut inner = self.inner.lock();
+        let seq = inner.next_seq;
+        Self::write(
+            &mut inner,
+            &Record::Batch {
+                seq,
+                id: batch.id,
+                events: batch.events.clone(),
+            },
+        )?;
+        inner.next_seq += 1;
+        inner.unprocessed.insert(seq, batch.id);
+        Ok(seq)
+    }
+
+    /// Record that a batch, and every batch before it, has been applied
+    ///
+    /// Returns `false` for batches the journal does not know about.
+    pub fn mark_processed(&self, batch_id: Uuid) -> Result<bool> {
+        let mut inner = self.inner.lock();
+        let Some(seq) = inner
+            .unprocessed
+            .iter()
+            .find(|(_, id)| **id == batch_id)
+            .map(|(seq, _)| *seq)
+        else {
+            return Ok(false);
+        };
+
+        Self::write(&mut inner, &Record::Processed { seq })?;
+        inner.watermark = seq;
+        inner.unprocessed = inner.unprocessed.split_off(&(seq + 1));
+
+        if inner.unprocessed.is_empty() && inner.bytes > self.compact_bytes {
+            self.compact(&mut inner)?;
+        }
+        Ok(true)
+    }
+
+    /// Record that a full scan brought the index up to date
+    ///
+    /// `through` is the `last_seq()` taken before the scan started; those
+    /// batches are covered by the scan, later ones still need processing.
+    pub fn mark_reconciled(&self, through: u64) -> Result<()> {
+        let mut inner = self.inner.lock();
+        inner.unprocessed = inner.unprocessed.split_off(&(through + 1));
+        inner.watermark = inner.watermark.max(through);
+        inner.reconciled = true;
+        if inner.unprocessed.is_empty() {
+            self.compact(&mut inner)
+        } else {
+            Self::write(&mut inner, &Record::Reconciled { seq: through })
+        }
+    }
+
+    /// Sequence number of the last appended batch
+    pub fn last_seq(&self) -> u64 {
+        self.inner.lock().next_seq - 1
+    }
+
+    /// Sequence number of the last processed batch
+    pub fn watermark(&self) -> u64 {
+        self.inner.lock().watermark
+    }
+
+    /// Number of batches appended but not processed
+    pub fn unprocessed(&self) -> usize {
+        self.inner.lock().unprocessed.len()
+    }
+
+    /// Replace the file with one holding only the current state
+    fn compact(&self, inner: &mut Inner) -> Result<()> {
+        let mut temp = self.path.clone().into_os_string();
+        temp.push(".tmp");
+        let temp = PathBuf::from(temp);
+
+        let fresh = Self::create(&temp, inner.watermark, inner.reconciled)?;
+        std::fs::rename(&temp, &self.path)?;
+        inner.file = OpenOptions::new().append(true).open(&self.path)?;
+        inner.bytes = fresh.bytes;
+        Ok(())
+    }
+
+    /// Write a journal holding just the header and, if `reconciled`, the
+    /// reconciled marker at `watermark`
+    fn create(path: &Path, watermark: u64, reconciled: bool) -> Result<Inner> {
+        let file = File::create(path)?;
+        let mut inner = Inner {
+            file,
+            bytes: 0,
+            reconciled,
+            next_seq: watermark + 1,
+            watermark,
+            unprocessed: BTreeMap::new(),
+        };
+        Self::write(&mut inner, &Record::Header { version: JOURNAL_VERSION })?;
+        if reconciled {
+            Self::write(&mut inner, &Record::Reconciled { seq: watermark })?;
+        }
+        Ok(inner)
+    }
+
+    fn write(inner: &mut Inner, record: &Record) -> Result<()> {
+        let mut line = serde_json::to_string(record)
+            .map_err(|e| NeuralFSError::WatcherError(format!("Failed to encode journal record: {}", e)))?;
+        line.push('\n');
+        inner.file.write_all(line.as_bytes())?;
+        inner.file.sync_data()?;
+        inner.bytes += line.len() as u64;
+        Ok(())
+    }
+
+    fn parse(content: &str) -> std::result::Result<ParsedJournal, String> {
+        if !content.is_empty() && !content.ends_with('\n') {
+            return Err("last record is incomplete".to_string());
+        }
+
+        let mut lines = content.lines().enumerate();
+        match lines.next().map(|(_, line)| serde_json::from_str::<Record>(line)) {
+            Some(Ok(Record::Header { version })) if version == JOURNAL_VERSION => {}
+            Some(Ok(Record::Header { version })) => return Err(format!("unsupported version {}", version)),
+            _ => return Err("missing header".to_string()),
+        }
+
+        let mut parsed = ParsedJournal::default();
+        let mut batches: BTreeMap<u64, EventBatch> = BTreeMap::new();
+        for (index, line) in lines {
+            let record: Record =
+                serde_json::from_str(line).map_err(|e| format!("line {}: {}", index + 1, e))?;
+            match record {
+                Record::Header { .. } => return Err(format!("line {}: unexpected header", index + 1)),
+                Record::Reconciled { seq } => {
+                    parsed.reconciled = true;
+                    parsed.last_seq = parsed.last_seq.max(seq);
+                    parsed.watermark = parsed.watermark.max(seq);
+                    batches = batches.split_off(&(seq + 1));
+                }
+                Record::Batch { seq, id, events } => {
+                    if seq <= parsed.last_seq {
+                        return Err(format!("line {}: sequence {} out of order", index + 1, seq));
+                    }
+                    parsed.last_seq = seq;
+                    batches.insert(
+                        seq,
+                        EventBatch {
+                            id,
+                            events,
+                            created_at: Instant::now(),
+                        },
+                    );
+                }
+                Record::Processed { seq } => {
+                    if seq > parsed.last_seq {
+                        return Err(format!("line {}: watermark {} beyond last batch", index + 1, seq));
+                    }
+                    parsed.watermark = parsed.watermark.max(seq);
+                    batches = batches.split_off(&(seq + 1));
+                }
+            }
+        }
+
+        parsed.pending = batches.into_iter().collect();
+        Ok(parsed)
+    }
+}
+
+#[derive(Default)]
+struct ParsedJournal {
+    reconciled: bool,
+    last_seq: u64,
+    watermark: u64,
+    pending: Vec<(u64, EventBatch)>,
+}
@@ -1,27 +1,45 @@
 //! File Watcher Module
 //!
 //! Provides file system monitoring with event deduplication and throttling.
-//! Uses notify-rs for cross-platform file system events.
+//! Uses notify-rs for cross-platform file system events, falling back per
+//! root to mtime/size polling when native watching is unavailable (inotify
+//! watch limit, network and FUSE mounts). Deletes and creates of the same
+//! file within the debounce window are reported as a single rename.
 
 mod filter;
+mod ignore;
+mod journal;
+mod polling;
+mod rename;
 #[cfg(test)]
 mod tests;
 
 pub use filter::{DirectoryFilter, DirectoryFilterConfig, FilterResult, FilterReason, UserFilterRules};
+pub use ignore::{IgnoreRule, DEFAULT_IGNORE_FILES};
+pub use polling::{
+    filesystem_type, is_remote_filesystem, is_watch_limit_error, PollingReason, PollingScanner, RootWatchStatus,
+    WatchMode,
+};
+pub use journal::{EventJournal, JournalRecovery, JournalState};
+pub use rename::{hash_file, FileIdentity, FileIdentitySource};
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
 use tokio::sync::{mpsc, RwLock};
+use tokio::task::JoinHandle;
 use uuid::Uuid;
 
 use crate::core::error::{NeuralFSError, Result};
+use crate::reconcile::hash_content;
+use rename::RenamePairer;
 
 /// File system event types
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FileEvent {
     /// File was created
     Created(PathBuf),
@@ -66,6 +84,18 @@ pub struct FileWatcherConfig {
     pub max_batch_wait: Duration,
     /// Channel buffer size for events
     pub channel_buffer_size: usize,
+    /// Interval between scans of polled roots (default: 30s)
+    pub poll_interval: Duration,
+    /// Poll roots the native watcher fails on instead of failing (default: true)
+    pub polling_fallback: bool,
+    /// Poll roots on network and FUSE filesystems (default: true)
+    pub poll_remote_filesystems: bool,
+    /// Roots that are always polled
+    pub force_polling: Vec<PathBuf>,
+    /// Pair deletes and creates of the same file into renames (default: true)
+    ///
+    /// Both are held for `debounce_duration` waiting for a partner.
+    pub pair_renames: bool,
 }
 
 impl Default for FileWatcherConfig {
@@ -75,10 +105,39 @@ impl Default for FileWatcherConfig {
             max_batch_size: 100,
             max_batch_wait: Duration::from_millis(500),
             channel_buffer_size: 1000,
+            poll_interval: Duration::from_secs(30),
+            polling_fallback: true,
+            poll_remote_filesystems: true,
+            force_polling: Vec::new(),
+            pair_renames: true,
         }
     }
 }
 
+/// Handler the native watcher delivers raw events to
+pub type RawEventHandler = Box<dyn FnMut(notify::Result<Event>) + Send>;
+
+/// Creates the native watcher; replaceable to simulate platform failures
+pub type NativeWatcherFactory =
+    Arc<dyn Fn(RawEventHandler) -> notify::Result<Box<dyn Watcher + Send>> + Send + Sync>;
+
+fn recommended_watcher_factory() -> NativeWatcherFactory {
+    Arc::new(|handler| Ok(Box::new(notify::recommended_watcher(handler)?) as Box<dyn Watcher + Send>))
+}
+
+/// Hands finished batches to the consumer, journaling them first
+#[derive(Clone)]
+struct BatchSink {
+    sender: mpsc::Sender<EventBatch>,
+    journal: Option<Arc<EventJournal>>,
+}
+
+/// Held deletes and creates waiting for a rename partner
+type SharedPairer = Arc<tokio::sync::Mutex<RenamePairer>>;
+
+/// Polled roots and their scanners
+type Pollers = Arc<std::sync::Mutex<HashMap<PathBuf, Arc<std::sync::Mutex<PollingScanner>>>>>;
+
 /// Internal state for event deduplication
 #[derive(Debug)]
 struct EventState {
@@ -105,7 +164,25 @@ pub struct FileWatcher {
     /// Sender for batched events
     batch_sender: mpsc::Sender<EventBatch>,
     /// Internal watcher handle
-    _watcher: Option<RecommendedWatcher>,
+    _watcher: Option<Box<dyn Watcher + Send>>,
+    /// Creates the native watcher
+    watcher_factory: NativeWatcherFactory,
+    /// Why the native watcher could not be created, if it could not
+    native_unavailable: Option<PollingReason>,
+    /// Watch mode per monitored root
+    watch_status: Arc<RwLock<HashMap<PathBuf, RootWatchStatus>>>,
+    /// Scanners of polled roots
+    pollers: Pollers,
+    /// Sender feeding raw events into the processing task
+    raw_tx: Option<mpsc::Sender<Event>>,
+    /// Task scanning polled roots
+    poll_task: Option<JoinHandle<()>>,
+    /// Deletes and creates waiting for a rename partner
+    pairer: SharedPairer,
+    /// Identities of deleted files, e.g. from the index
+    identity_source: Option<Arc<dyn FileIdentitySource>>,
+    /// Journal batches are recorded in before they are sent
+    journal: Option<Arc<EventJournal>>,
     /// Shutdown signal
     shutdown_tx: Option<mpsc::Sender<()>>,
 }
@@ -122,8 +199,18 @@ impl FileWatcher {
         config: FileWatcherConfig,
     ) -> Result<(Self, mpsc::Receiver<EventBatch>)> {
         let (batch_sender, batch_receiver) = mpsc::channel(config.channel_buffer_size);
+        Ok((Self::with_sender(filter, config, batch_sender), batch_receiver))
+    }
+
+    /// Create a FileWatcher that sends its batches to an existing channel
+    pub fn with_sender(
+        filter: DirectoryFilter,
+        config: FileWatcherConfig,
+        batch_sender: mpsc::Sender<EventBatch>,
+    ) -> Self {
+        let pairer = Arc::new(tokio::sync::Mutex::new(RenamePairer::new(config.debounce_duration)));
 
-        let watcher = Self {
+        Self {
             config,
             filter: Arc::new(filter),
             watched_dirs: Arc::new(RwLock::new(Vec::new())),
@@ -131,12 +218,38 @@ impl FileWatcher {
             current_batch: Arc::new(RwLock::new(EventBatch::new())),
             batch_sender,
             _watcher: None,
+            watcher_factory: recommended_watcher_factory(),
+            native_unavailable: None,
+            watch_status: Arc::new(RwLock::new(HashMap::new())),
+            pollers: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            raw_tx: None,
+            poll_task: None,
+            pairer,
+            identity_source: None,
+            journal: None,
             shutdown_tx: None,
-        };
+        }
+    }
 
-        Ok((watcher, batch_receiver))
+    /// Use a different native watcher, e.g. to simulate watch-limit errors
+    pub fn with_native_watcher_factory(mut self, factory: NativeWatcherFactory) -> Self {
+        self.watcher_factory = factory;
+        self
+    }
+
+    /// Look up identities of deleted files in `source`, so moves of files
+    /// the watcher has not seen since it started can be paired too
+    pub fn with_identity_source(mut self, source: Arc<dyn FileIdentitySource>) -> Self {
+        self.identity_source = Some(source);
+        self
     }
 
+    /// Record every batch in `journal` before sending it, so batches not
+    /// yet processed can be replayed after a crash
+    pub fn with_journal(mut self, journal: Arc<EventJournal>) -> Self {
+        self.journal = Some(journal);
+        self
+    }
 
     /// Start watching directories
     pub async fn start(&mut self, directories: Vec<PathBuf>) -> Result<()> {
@@ -145,20 +258,48 @@ impl FileWatcher {
 
         // Create internal channel for raw events
         let (raw_tx, mut raw_rx) = mpsc::channel::<Event>(self.config.channel_buffer_size);
+        self.raw_tx = Some(raw_tx.clone());
 
         // Create the notify watcher
-        let watcher = notify::recommended_watcher(move |res: std::result::Result<Event, notify::Error>| {
-            if let Ok(event) = res {
-                let _ = raw_tx.blocking_send(event);
+        let native_tx = raw_tx.clone();
+        let (error_tx, error_rx) = mpsc::channel::<notify::Error>(16);
+        let handler: RawEventHandler = Box::new(move |res: std::result::Result<Event, notify::Error>| match res {
+            Ok(event) => {
+                let _ = native_tx.blocking_send(event);
             }
-        }).map_err(|e| NeuralFSError::WatcherError(e.to_string()))?;
+            Err(e) => {
+                let _ = error_tx.try_send(e);
+            }
+        });
+        match (self.watcher_factory)(handler) {
+            Ok(watcher) => self._watcher = Some(watcher),
+            Err(e) if self.config.polling_fallback => {
+                tracing::warn!("Native file watcher unavailable, polling all roots: {}", e);
+                self.native_unavailable = Some(Self::polling_reason(&e));
+            }
+            Err(e) => return Err(NeuralFSError::WatcherError(e.to_string())),
+        }
 
-        self._watcher = Some(watcher);
+        // Ignore files above the roots do not apply to them
+        self.filter.set_monitored_roots(&directories);
 
         // Watch all directories
         for dir in &directories {
-            self.watch_directory(dir)?;
+            self.watch_directory(dir).await?;
         }
+        self.poll_task = Some(Self::spawn_polling(
+            Arc::clone(&self.pollers),
+            Arc::clone(&self.filter),
+            raw_tx,
+            self.config.poll_interval,
+        ));
+        tokio::spawn(Self::fall_back_on_errors(
+            error_rx,
+            Arc::clone(&self.watch_status),
+            Arc::clone(&self.pollers),
+            Arc::clone(&self.filter),
+            self.config.polling_fallback,
+        ));
 
         // Store watched directories
         {
@@ -169,21 +310,31 @@ impl FileWatcher {
         // Spawn the event processing task
         let event_states = Arc::clone(&self.event_states);
         let current_batch = Arc::clone(&self.current_batch);
-        let batch_sender = self.batch_sender.clone();
+        let batch_sender = BatchSink {
+            sender: self.batch_sender.clone(),
+            journal: self.journal.clone(),
+        };
         let filter = Arc::clone(&self.filter);
         let config = self.config.clone();
+        let pairer = Arc::clone(&self.pairer);
+        let identity_source = self.identity_source.clone();
 
         tokio::spawn(async move {
             let mut flush_interval = tokio::time::interval(config.max_batch_wait);
+            let (pair_tx, pair_rx) = mpsc::channel(config.channel_buffer_size);
+            let pairing = tokio::spawn(Self::run_pairing(
+                pair_rx,
+                Arc::clone(&pairer),
+                identity_source,
+                Arc::clone(&current_batch),
+                batch_sender.clone(),
+                config.clone(),
+            ));
 
             loop {
                 tokio::select! {
                     // Handle shutdown
-                    _ = shutdown_rx.recv() => {
-                        // Flush remaining events
-                        Self::flush_batch(&current_batch, &batch_sender).await;
-                        break;
-                    }
+                    _ = shutdown_rx.recv() => break,
 
                     // Handle raw events
                     Some(event) = raw_rx.recv() => {
@@ -194,36 +345,199 @@ impl FileWatcher {
                             &batch_sender,
                             &filter,
                             &config,
+                            &pair_tx,
                         ).await;
                     }
 
                     // Periodic flush
                     _ = flush_interval.tick() => {
+                        let expired = pairer.lock().await.expire(Instant::now());
+                        Self::push_events(expired, &current_batch, &batch_sender, &config).await;
                         Self::flush_batch(&current_batch, &batch_sender).await;
                     }
                 }
             }
+
+            // Let pairing finish, then flush remaining events, including unpaired ones
+            drop(pair_tx);
+            let _ = pairing.await;
+            let held = pairer.lock().await.drain();
+            Self::push_events(held, &current_batch, &batch_sender, &config).await;
+            Self::flush_batch(&current_batch, &batch_sender).await;
         });
 
         Ok(())
     }
 
-    /// Watch a single directory
-    fn watch_directory(&mut self, path: &Path) -> Result<()> {
-        if let Some(ref mut watcher) = self._watcher {
-            watcher
-                .watch(path, RecursiveMode::Recursive)
-                .map_err(|e| NeuralFSError::WatcherError(format!("Failed to watch {:?}: {}", path, e)))?;
+    /// Watch a single directory, natively if possible and by polling otherwise
+    async fn watch_directory(&mut self, path: &Path) -> Result<()> {
+        let reason = if self.config.force_polling.iter().any(|root| root == path) {
+            Some(PollingReason::Configured)
+        } else if let Some(fs_type) = filesystem_type(path)
+            .filter(|fs_type| self.config.poll_remote_filesystems && is_remote_filesystem(fs_type))
+        {
+            Some(PollingReason::RemoteFilesystem(fs_type))
+        } else if let Some(ref mut watcher) = self._watcher {
+            match watcher.watch(path, RecursiveMode::Recursive) {
+                Ok(()) => None,
+                Err(e) if self.config.polling_fallback => {
+                    // A recursive watch may have been added partially; release it
+                    let _ = watcher.unwatch(path);
+                    tracing::warn!("Falling back to polling for {:?}: {}", path, e);
+                    Some(Self::polling_reason(&e))
+                }
+                Err(e) => {
+                    return Err(NeuralFSError::WatcherError(format!("Failed to watch {:?}: {}", path, e)));
+                }
+            }
+        } else {
+            self.native_unavailable.clone()
+        };
+
+        if reason.is_some() {
+            let filter = Arc::clone(&self.filter);
+            let root = path.to_path_buf();
+            let scanner = tokio::task::spawn_blocking(move || PollingScanner::new(root, &filter))
+                .await
+                .map_err(|e| NeuralFSError::WatcherError(format!("Failed to scan {:?}: {}", path, e)))?;
+            self.pollers
+                .lock()
+                .unwrap()
+                .insert(path.to_path_buf(), Arc::new(std::sync::Mutex::new(scanner)));
         }
+
+        let status = RootWatchStatus {
+            root: path.to_path_buf(),
+            mode: if reason.is_some() { WatchMode::Polling } else { WatchMode::Native },
+            reason,
+        };
+        self.watch_status.write().await.insert(path.to_path_buf(), status);
         Ok(())
     }
 
+    fn polling_reason(error: &notify::Error) -> PollingReason {
+        if is_watch_limit_error(error) {
+            PollingReason::WatchLimit
+        } else {
+            PollingReason::WatchFailed(error.to_string())
+        }
+    }
+
+    /// Poll the roots a runtime watcher error leaves unwatched
+    ///
+    /// inotify adds a watch for every directory created under a root, so
+    /// `max_user_watches` can run out long after `start`. Such errors arrive
+    /// through the event handler; the roots they affect are polled from then
+    /// on. Runs until the native watcher is dropped.
+    async fn fall_back_on_errors(
+        mut errors: mpsc::Receiver<notify::Error>,
+        watch_status: Arc<RwLock<HashMap<PathBuf, RootWatchStatus>>>,
+        pollers: Pollers,
+        filter: Arc<DirectoryFilter>,
+        polling_fallback: bool,
+    ) {
+        while let Some(error) = errors.recv().await {
+            // Without paths, only a watch limit can be traced to the roots
+            if !polling_fallback || (error.paths.is_empty() && !is_watch_limit_error(&error)) {
+                tracing::warn!("File watcher error: {}", error);
+                continue;
+            }
+
+            let roots: Vec<PathBuf> = watch_status
+                .read()
+                .await
+                .values()
+                .filter(|status| status.mode == WatchMode::Native)
+                .filter(|status| {
+                    error.paths.is_empty() || error.paths.iter().any(|path| path.starts_with(&status.root))
+                })
+                .map(|status| status.root.clone())
+                .collect();
+
+            for root in roots {
+                tracing::warn!("Falling back to polling for {:?}: {}", root, error);
+                let filter = Arc::clone(&filter);
+                let scan_root = root.clone();
+                let Ok(scanner) = tokio::task::spawn_blocking(move || PollingScanner::new(scan_root, &filter)).await
+                else {
+                    continue;
+                };
+                pollers
+                    .lock()
+                    .unwrap()
+                    .insert(root.clone(), Arc::new(std::sync::Mutex::new(scanner)));
+                let status = RootWatchStatus {
+                    root: root.clone(),
+                    mode: WatchMode::Polling,
+                    reason: Some(Self::polling_reason(&error)),
+                };
+                watch_status.write().await.insert(root, status);
+            }
+        }
+    }
+
+    /// Rescan polled roots every `interval` and feed changes to the processing task
+    fn spawn_polling(
+        pollers: Pollers,
+        filter: Arc<DirectoryFilter>,
+        raw_tx: mpsc::Sender<Event>,
+        interval: Duration,
+    ) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+
+            loop {
+                ticker.tick().await;
+                let scanners: Vec<_> = pollers.lock().unwrap().values().cloned().collect();
+
+                for scanner in scanners {
+                    let filter = Arc::clone(&filter);
+                    let scanned = tokio::task::spawn_blocking(move || {
+                        let mut scanner = scanner.lock().unwrap();
+                        scanner.scan(&filter).map_err(|e| (scanner.root().to_path_buf(), e))
+                    })
+                    .await;
+                    let events = match scanned {
+                        Ok(Ok(events)) => events,
+                        Ok(Err((root, e))) => {
+                            tracing::warn!("Skipping scan of {:?}, it could not be read: {}", root, e);
+                            continue;
+                        }
+                        Err(_) => continue,
+                    };
+                    for event in events {
+                        if raw_tx.send(Self::polled_event(event)).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    /// Express a polled change as the notify event a native watcher would send
+    fn polled_event(event: FileEvent) -> Event {
+        use notify::event::{CreateKind, DataChange, ModifyKind, RemoveKind};
+        match event {
+            FileEvent::Created(path) | FileEvent::Renamed(_, path) => {
+                Event::new(EventKind::Create(CreateKind::File)).add_path(path)
+            }
+            FileEvent::Modified(path) => Event::new(EventKind::Modify(ModifyKind::Data(DataChange::Any))).add_path(path),
+            FileEvent::Deleted(path) => Event::new(EventKind::Remove(RemoveKind::File)).add_path(path),
+        }
+    }
+
     /// Stop watching and cleanup
     pub async fn stop(&mut self) -> Result<()> {
+        if let Some(task) = self.poll_task.take() {
+            task.abort();
+        }
         if let Some(tx) = self.shutdown_tx.take() {
             let _ = tx.send(()).await;
         }
         self._watcher = None;
+        self.raw_tx = None;
         Ok(())
     }
 
@@ -233,13 +547,17 @@ impl FileWatcher {
         event: Event,
         event_states: &Arc<RwLock<HashMap<PathBuf, EventState>>>,
         current_batch: &Arc<RwLock<EventBatch>>,
-        batch_sender: &mpsc::Sender<EventBatch>,
+        batch_sender: &BatchSink,
         filter: &Arc<DirectoryFilter>,
         config: &FileWatcherConfig,
+        pair_tx: &mpsc::Sender<(FileEvent, Instant)>,
     ) {
         let now = Instant::now();
 
         for path in event.paths {
+            // Pick up edited ignore rules before filtering anything else
+            filter.invalidate_ignore_file(&path);
+
             // Check if path should be filtered
             if let FilterResult::Exclude(_) = filter.should_filter(&path) {
                 continue;
@@ -277,15 +595,132 @@ impl FileWatcher {
                 };
 
                 if should_add {
-                    let mut batch = current_batch.write().await;
-                    batch.events.push(file_event);
+                    if config.pair_renames {
+                        let _ = pair_tx.send((file_event, now)).await;
+                    } else {
+                        Self::push_events(vec![file_event], current_batch, batch_sender, config).await;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Add events to the current batch, flushing whenever it fills up
+    async fn push_events(
+        events: Vec<FileEvent>,
+        current_batch: &Arc<RwLock<EventBatch>>,
+        batch_sender: &BatchSink,
+        config: &FileWatcherConfig,
+    ) {
+        for event in events {
+            let mut batch = current_batch.write().await;
+            batch.events.push(event);
+
+            // Flush if batch is full
+            if batch.events.len() >= config.max_batch_size {
+                drop(batch);
+                Self::flush_batch(current_batch, batch_sender).await;
+            }
+        }
+    }
+
+    /// Pair deletes and creates into renames, in arrival order
+    ///
+    /// Runs in its own task so hashing and identity lookups never hold up
+    /// deduplication and flushing. Deletes that queue up together are
+    /// looked up in the identity source with one call.
+    async fn run_pairing(
+        mut events: mpsc::Receiver<(FileEvent, Instant)>,
+        pairer: SharedPairer,
+        identity_source: Option<Arc<dyn FileIdentitySource>>,
+        current_batch: Arc<RwLock<EventBatch>>,
+        batch_sender: BatchSink,
+        config: FileWatcherConfig,
+    ) {
+        while let Some(first) = events.recv().await {
+            let mut queued = vec![first];
+            while queued.len() < config.max_batch_size {
+                match events.try_recv() {
+                    Ok(event) => queued.push(event),
+                    Err(_) => break,
+                }
+            }
+
+            let deleted: Vec<PathBuf> = queued
+                .iter()
+                .filter_map(|(event, _)| match event {
+                    FileEvent::Deleted(path) => Some(path.clone()),
+                    _ => None,
+                })
+                .collect();
+            let mut stored = match identity_source {
+                Some(ref source) if !deleted.is_empty() => source.identities(&deleted).await,
+                _ => HashMap::new(),
+            };
+
+            for (event, at) in queued {
+                let paired = Self::pair_event(event, &pairer, &mut stored, at).await;
+                let events = paired.into_iter().chain(pairer.lock().await.expire(at)).collect();
+                Self::push_events(events, &current_batch, &batch_sender, &config).await;
+            }
+        }
+    }
 
-                    // Flush if batch is full
-                    if batch.events.len() >= config.max_batch_size {
-                        drop(batch);
-                        Self::flush_batch(current_batch, batch_sender).await;
+    /// Hold deletes and creates until they pair up or the window expires
+    ///
+    /// Returns the event to emit now, if any.
+    async fn pair_event(
+        event: FileEvent,
+        pairer: &SharedPairer,
+        stored: &mut HashMap<PathBuf, FileIdentity>,
+        now: Instant,
+    ) -> Option<FileEvent> {
+        match event {
+            FileEvent::Created(path) => {
+                let mut identity = FileIdentity::from_path(&path);
+                if let Some(identity) = identity.as_mut() {
+                    // Only hash when a held delete can be matched by content alone
+                    if pairer.lock().await.has_hashed_delete_of_size(identity.size) {
+                        identity.content_hash = hash_content(path.clone()).await.ok();
                     }
                 }
+                pairer.lock().await.created(&path, identity, now)
+            }
+            FileEvent::Modified(path) => {
+                let identity = FileIdentity::from_path(&path);
+                let mut pairer = pairer.lock().await;
+                if pairer.absorb_modify(&path, identity.clone(), now) {
+                    // The held create reports it once it is released
+                    return None;
+                }
+                if let Some(identity) = identity {
+                    pairer.observe(&path, identity, now);
+                }
+                Some(FileEvent::Modified(path))
+            }
+            FileEvent::Deleted(path) => {
+                let known = pairer.lock().await.known_identity(&path);
+                let identity = match (stored.remove(&path), known) {
+                    (Some(mut stored), Some(known)) => {
+                        stored.file_id = stored.file_id.or(known.file_id);
+                        Some(stored)
+                    }
+                    (stored, known) => stored.or(known),
+                };
+
+                if let Some(size) = identity.as_ref().filter(|i| i.content_hash.is_some()).map(|i| i.size) {
+                    let unhashed = pairer.lock().await.unhashed_creates_of_size(size);
+                    for created in unhashed {
+                        if let Ok(hash) = hash_content(created.clone()).await {
+                            pairer.lock().await.set_create_hash(&created, hash);
+                        }
+                    }
+                }
+                pairer.lock().await.deleted(&path, identity, now)
+            }
+            FileEvent::Renamed(old_path, new_path) => {
+                pairer.lock().await.renamed(&old_path, &new_path);
+                Some(FileEvent::Renamed(old_path, new_path))
             }
         }
     }
@@ -342,7 +777,7 @@ impl FileWatcher {
     /// Flush the current batch
     async fn flush_batch(
         current_batch: &Arc<RwLock<EventBatch>>,
-        batch_sender: &mpsc::Sender<EventBatch>,
+        batch_sender: &BatchSink,
     ) {
         let batch = {
             let mut batch = current_batch.write().await;
@@ -352,12 +787,25 @@ impl FileWatcher {
             std::mem::replace(&mut *batch, EventBatch::new())
         };
 
-        let _ = batch_sender.send(batch).await;
+        // Journal first, so the batch survives a crash before it is processed.
+        // The write syncs to disk, so it runs off the async runtime
+        if let Some(journal) = batch_sender.journal.clone() {
+            let record = batch.clone();
+            match tokio::task::spawn_blocking(move || journal.append(&record)).await {
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => tracing::warn!("Failed to journal watcher batch {}: {}", batch.id, e),
+                Err(e) => tracing::warn!("Failed to journal watcher batch {}: {}", batch.id, e),
+            }
+        }
+        let _ = batch_sender.sender.send(batch).await;
     }
 
     /// Add a directory to watch
     pub async fn add_watch(&mut self, path: &Path) -> Result<()> {
-        self.watch_directory(path)?;
+        let mut roots = self.watched_directories().await;
+        roots.push(path.to_path_buf());
+        self.filter.set_monitored_roots(&roots);
+        self.watch_directory(path).await?;
         let mut watched = self.watched_dirs.write().await;
         if !watched.contains(&path.to_path_buf()) {
             watched.push(path.to_path_buf());
@@ -370,8 +818,11 @@ impl FileWatcher {
         if let Some(ref mut watcher) = self._watcher {
             let _ = watcher.unwatch(path);
         }
+        self.pollers.lock().unwrap().remove(path);
+        self.watch_status.write().await.remove(path);
         let mut watched = self.watched_dirs.write().await;
         watched.retain(|p| p != path);
+        self.filter.set_monitored_roots(&watched);
         Ok(())
     }
 
@@ -380,6 +831,17 @@ impl FileWatcher {
         self.watched_dirs.read().await.clone()
     }
 
+    /// Get how each watched directory is monitored, in watch order
+    pub async fn watch_modes(&self) -> Vec<RootWatchStatus> {
+        let status = self.watch_status.read().await;
+        self.watched_dirs
+            .read()
+            .await
+            .iter()
+            .filter_map(|dir| status.get(dir).cloned())
+            .collect()
+    }
+
     /// Clear event state (useful for testing)
     pub async fn clear_state(&self) {
         let mut states = self.event_states.write().await;
@@ -427,6 +889,26 @@ impl FileWatcherBuilder {
         self
     }
 
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.config.poll_interval = interval;
+        self
+    }
+
+    pub fn polling_fallback(mut self, enabled: bool) -> Self {
+        self.config.polling_fallback = enabled;
+        self
+    }
+
+    pub fn pair_renames(mut self, enabled: bool) -> Self {
+        self.config.pair_renames = enabled;
+        self
+    }
+
+    pub fn force_polling(mut self, root: impl Into<PathBuf>) -> Self {
+        self.config.force_polling.push(root.into());
+        self
+    }
+
     pub fn filter_config(mut self, config: DirectoryFilterConfig) -> Self {
         self.filter_config = config;
         self
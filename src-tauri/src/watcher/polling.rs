@@ -0,0 +1,238 @@
+//! Polling fallback for roots native watching cannot cover
+//!
+//! Used when the platform watcher fails for a root, e.g. because
+//! `fs.inotify.max_user_watches` is exhausted, or when the root lives on a
+//! network or FUSE filesystem that does not deliver change notifications.
+//! Each scan compares modification time and size with the previous scan.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use super::filter::{DirectoryFilter, FilterResult};
+use super::FileEvent;
+
+/// How a monitored root is watched
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WatchMode {
+    /// Platform notifications (inotify, FSEvents, ReadDirectoryChangesW)
+    Native,
+    /// Periodic mtime/size scanning
+    Polling,
+}
+
+/// Why a root is polled instead of watched natively
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PollingReason {
+    /// The native watcher ran out of watches (inotify `max_user_watches`)
+    WatchLimit,
+    /// The root is on a network or FUSE filesystem (filesystem type)
+    RemoteFilesystem(String),
+    /// The native watcher failed for another reason
+    WatchFailed(String),
+    /// Polling was requested for this root in the configuration
+    Configured,
+}
+
+/// Watch mode of one monitored root
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RootWatchStatus {
+    /// The monitored root
+    pub root: PathBuf,
+    /// How it is watched
+    pub mode: WatchMode,
+    /// Why it is polled, when it is
+    pub reason: Option<PollingReason>,
+}
+
+/// Whether a notify error means the watch limit was hit
+///
+/// inotify reports an exhausted `max_user_watches` as `ENOSPC` and an
+/// exhausted `max_user_instances` as `EMFILE`; notify maps the former to
+/// `MaxFilesWatch`.
+pub fn is_watch_limit_error(error: &notify::Error) -> bool {
+    match &error.kind {
+        notify::ErrorKind::MaxFilesWatch => true,
+        notify::ErrorKind::Io(io) => matches!(io.raw_os_error(), Some(28) | Some(24)),
+        _ => false,
+    }
+}
+
+/// Filesystem types that do not deliver reliable change notifications
+const REMOTE_FILESYSTEMS: &[&str] = &[
+    "nfs", "nfs4", "cifs", "smb3", "smbfs", "9p", "afs", "sshfs", "davfs", "fuse",
+];
+
+/// Whether a filesystem type (as in `/proc/mounts`) is remote or FUSE
+pub fn is_remote_filesystem(fs_type: &str) -> bool {
+    REMOTE_FILESYSTEMS.contains(&fs_type) || fs_type.starts_with("fuse.")
+}
+
+/// Filesystem type of the mount containing `path`
+///
+/// Only available on Linux; returns `None` elsewhere.
+pub fn filesystem_type(path: &Path) -> Option<String> {
+    #[cfg(target_os = "linux")]
+    {
+        let mounts = std::fs::read_to_string("/proc/mounts").ok()?;
+        mount_filesystem_type(&mounts, path)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = path;
+        None
+    }
+}
+
+/// Filesystem type of the deepest mount point containing `path`
+pub(crate) fn mount_filesystem_type(mounts: &str, path: &Path) -> Option<String> {
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let _device = fields.next()?;
+            // Spaces in mount points are escaped as \040
+            let mount_point = PathBuf::from(fields.next()?.replace("\\040", " "));
+            let fs_type = fields.next()?;
+            path.starts_with(&mount_point).then(|| (mount_point, fs_type.to_string()))
+        })
+        .max_by_key(|(mount_point, _)| mount_point.components().count())
+        .map(|(_, fs_type)| fs_type)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    modified: Option<SystemTime>,
+    size: u64,
+}
+
+/// Scans one root and reports what changed since the previous scan
+///
+/// A scan that cannot read the root or one of its directories fails and
+/// keeps the previous snapshot: an unmounted share or a directory that lost
+/// its permissions must not look like every file beneath it was deleted.
+#[derive(Debug)]
+pub struct PollingScanner {
+    root: PathBuf,
+    files: HashMap<PathBuf, FileStamp>,
+    /// Whether `files` holds a complete walk of the root
+    primed: bool,
+}
+
+impl PollingScanner {
+    /// Start polling `root`, recording its current state without events
+    ///
+    /// When the root cannot be read yet, the first scan that succeeds
+    /// records its state instead, again without events.
+    pub fn new(root: PathBuf, filter: &DirectoryFilter) -> Self {
+        match Self::walk(&root, filter) {
+            Ok(files) => Self { root, files, primed: true },
+            Err(e) => {
+                tracing::warn!("Cannot scan polled root {:?} yet: {}", root, e);
+                Self {
+                    root,
+                    files: HashMap::new(),
+                    primed: false,
+                }
+            }
+        }
+    }
+
+    /// The polled root
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Number of files being tracked
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Rescan the root and return created, modified and deleted files
+    ///
+    /// Fails without changing the snapshot when the root or any directory
+    /// below it cannot be read.
+    pub fn scan(&mut self, filter: &DirectoryFilter) -> std::io::Result<Vec<FileEvent>> {
+        let mut current = Self::walk(&self.root, filter)?;
+        if !self.primed {
+            self.files = current;
+            self.primed = true;
+            return Ok(Vec::new());
+        }
+
+        // Edited ignore files change what the walk sees, so walk again
+        let mut ignore_changed = false;
+        for (path, stamp) in &current {
+            if self.files.get(path) != Some(stamp) && filter.invalidate_ignore_file(path) {
+                ignore_changed = true;
+            }
+        }
+        for path in self.files.keys() {
+            if !current.contains_key(path) && filter.invalidate_ignore_file(path) {
+                ignore_changed = true;
+            }
+        }
+        if ignore_changed {
+            current = Self::walk(&self.root, filter)?;
+        }
+
+        let mut events = Vec::new();
+
+        for (path, stamp) in &current {
+            match self.files.get(path) {
+                None => events.push(FileEvent::Created(path.clone())),
+                Some(previous) if previous != stamp => events.push(FileEvent::Modified(path.clone())),
+                Some(_) => {}
+            }
+        }
+        for path in self.files.keys() {
+            if !current.contains_key(path) {
+                events.push(FileEvent::Deleted(path.clone()));
+            }
+        }
+
+        self.files = current;
+        Ok(events)
+    }
+
+    fn walk(root: &Path, filter: &DirectoryFilter) -> std::io::Result<HashMap<PathBuf, FileStamp>> {
+        let mut files = HashMap::new();
+        let mut pending = vec![root.to_path_buf()];
+
+        while let Some(dir) = pending.pop() {
+            let entries = match std::fs::read_dir(&dir) {
+                Ok(entries) => entries,
+                // A subdirectory deleted during the walk really is gone
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound && dir != root => continue,
+                Err(e) => return Err(e),
+            };
+            for entry in entries {
+                let entry = entry?;
+                let path = entry.path();
+                if let FilterResult::Exclude(_) = filter.should_filter(&path) {
+                    continue;
+                }
+                let Ok(file_type) = entry.file_type() else {
+                    continue;
+                };
+                if file_type.is_dir() {
+                    pending.push(path);
+                } else if file_type.is_file() {
+                    if let Ok(metadata) = entry.metadata() {
+                        files.insert(
+                            path,
+                            FileStamp {
+                                modified: metadata.modified().ok(),
+                                size: metadata.len(),
+                            },
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(files)
+    }
+}
@@ -0,0 +1,302 @@
+//! Pairing of delete and create events into renames
+//!
+//! Moves often reach the watcher as a separate delete and create, e.g. moves
+//! across filesystems, applications that write a new file and remove the old
+//! one, or changes picked up by polling. Both events are held for the
+//! debounce window; a delete and a create of the same file become a single
+//! `FileEvent::Renamed`.
+//!
+//! Two paths are the same file when:
+//! - Their `FileId` (inode and device on Unix) and size match, or
+//! - Their size and BLAKE3 content hash match
+//!
+//! Deleted files can no longer be inspected, so their identity comes from
+//! what the watcher saw earlier or from a `FileIdentitySource` such as the
+//! index.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+use super::FileEvent;
+use crate::reconcile::{hash_file_content, FileId};
+
+/// What identifies a file independently of its path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileIdentity {
+    /// Platform file ID, if known
+    pub file_id: Option<FileId>,
+    /// Size in bytes
+    pub size: u64,
+    /// BLAKE3 hash of the content (hex), if known
+    pub content_hash: Option<String>,
+}
+
+impl FileIdentity {
+    /// Read the identity of an existing file, without hashing it
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        if !metadata.is_file() {
+            return None;
+        }
+        Some(Self {
+            file_id: FileId::from_path(path).ok(),
+            size: metadata.len(),
+            content_hash: None,
+        })
+    }
+
+    /// Whether both identities describe the same file
+    pub fn same_file(&self, other: &FileIdentity) -> bool {
+        if self.size != other.size {
+            return false;
+        }
+        if let (Some(a), Some(b)) = (&self.file_id, &other.file_id) {
+            if a == b {
+                return true;
+            }
+        }
+        matches!((&self.content_hash, &other.content_hash), (Some(a), Some(b)) if a == b)
+    }
+}
+
+/// BLAKE3 hash of a file's content (hex), streamed from disk
+pub fn hash_file(path: &Path) -> Option<String> {
+    hash_file_content(path).ok()
+}
+
+/// Provides identities of files that no longer exist, e.g. from the index
+#[async_trait]
+pub trait FileIdentitySource: Send + Sync {
+    /// Identity last recorded for `path`
+    async fn identity(&self, path: &Path) -> Option<FileIdentity>;
+
+    /// Identities last recorded for `paths`, keyed by path
+    ///
+    /// Looks each path up on its own by default; sources backed by a
+    /// database should answer with one query.
+    async fn identities(&self, paths: &[PathBuf]) -> HashMap<PathBuf, FileIdentity> {
+        let mut found = HashMap::new();
+        for path in paths {
+            if let Some(identity) = self.identity(path).await {
+                found.insert(path.clone(), identity);
+            }
+        }
+        found
+    }
+}
+
+/// How long the identity of a path seen while it existed is remembered
+pub(crate) const KNOWN_TTL: Duration = Duration::from_secs(600);
+
+/// Most identities remembered at once; the oldest go first
+pub(crate) const MAX_KNOWN: usize = 10_000;
+
+#[derive(Debug)]
+struct Pending {
+    path: PathBuf,
+    identity: Option<FileIdentity>,
+    at: Instant,
+}
+
+/// Holds deletes and creates for the pairing window
+#[derive(Debug)]
+pub(crate) struct RenamePairer {
+    window: Duration,
+    /// Identities of paths seen while they existed, and when
+    known: HashMap<PathBuf, (FileIdentity, Instant)>,
+    deletes: Vec<Pending>,
+    creates: Vec<Pending>,
+}
+
+impl RenamePairer {
+    pub(crate) fn new(window: Duration) -> Self {
+        Self {
+            window,
+            known: HashMap::new(),
+            deletes: Vec::new(),
+            creates: Vec::new(),
+        }
+    }
+
+    /// Remember the identity of a path that exists
+    pub(crate) fn observe(&mut self, path: &Path, identity: FileIdentity, now: Instant) {
+        self.known.insert(path.to_path_buf(), (identity, now));
+    }
+
+    /// Identity recorded for `path` while it existed
+    pub(crate) fn known_identity(&self, path: &Path) -> Option<FileIdentity> {
+        self.known.get(path).map(|(identity, _)| identity.clone())
+    }
+
+    /// Number of remembered identities
+    pub(crate) fn known_count(&self) -> usize {
+        self.known.len()
+    }
+
+    /// Forget identities older than `KNOWN_TTL`, and the oldest beyond
+    /// `MAX_KNOWN`
+    fn prune_known(&mut self, now: Instant) {
+        self.known
+            .retain(|_, (_, seen)| now.saturating_duration_since(*seen) < KNOWN_TTL);
+        if self.known.len() > MAX_KNOWN {
+            let mut seen: Vec<Instant> = self.known.values().map(|(_, seen)| *seen).collect();
+            seen.sort_unstable();
+            let cutoff = seen[seen.len() - MAX_KNOWN];
+            self.known.retain(|_, (_, seen)| *seen >= cutoff);
+        }
+    }
+
+    /// Whether a held delete of `size` can only be matched by content hash
+    pub(crate) fn has_hashed_delete_of_size(&self, size: u64) -> bool {
+        self.deletes.iter().any(|delete| {
+            delete
+                .identity
+                .as_ref()
+                .map_or(false, |identity| identity.size == size && identity.content_hash.is_some())
+        })
+    }
+
+    /// A held create was modified again; keeps holding it with the new identity
+    ///
+    /// Returns `false` if no create is held for `path`.
+    pub(crate) fn absorb_modify(&mut self, path: &Path, identity: Option<FileIdentity>, now: Instant) -> bool {
+        let Some(create) = self.creates.iter_mut().find(|create| create.path == path) else {
+            return false;
+        };
+        if let Some(identity) = identity {
+            self.known.insert(path.to_path_buf(), (identity.clone(), now));
+            create.identity = Some(identity);
+        }
+        true
+    }
+
+    /// Fill in the content hash of a held create
+    pub(crate) fn set_create_hash(&mut self, path: &Path, hash: String) {
+        if let Some(identity) = self
+            .creates
+            .iter_mut()
+            .find(|create| create.path == path)
+            .and_then(|create| create.identity.as_mut())
+        {
+            identity.content_hash = Some(hash);
+        }
+    }
+
+    /// Paths of held creates of `size` whose content hash is not known yet
+    pub(crate) fn unhashed_creates_of_size(&self, size: u64) -> Vec<PathBuf> {
+        self.creates
+            .iter()
+            .filter(|create| {
+                create
+                    .identity
+                    .as_ref()
+                    .map_or(false, |identity| identity.size == size && identity.content_hash.is_none())
+            })
+            .map(|create| create.path.clone())
+            .collect()
+    }
+
+    /// A path was deleted; returns the event to emit now, if any
+    pub(crate) fn deleted(&mut self, path: &Path, identity: Option<FileIdentity>, now: Instant) -> Option<FileEvent> {
+        self.known.remove(path);
+
+        // Created and removed within the window, e.g. a temporary file
+        if let Some(index) = self.creates.iter().position(|create| create.path == path) {
+            self.creates.remove(index);
+            return Some(FileEvent::Deleted(path.to_path_buf()));
+        }
+
+        if let Some(identity) = &identity {
+            if let Some(index) = self.creates.iter().position(|create| {
+                create.identity.as_ref().map_or(false, |created| created.same_file(identity))
+            }) {
+                let create = self.creates.remove(index);
+                return Some(FileEvent::Renamed(path.to_path_buf(), create.path));
+            }
+        }
+
+        self.deletes.retain(|delete| delete.path != path);
+        self.deletes.push(Pending {
+            path: path.to_path_buf(),
+            identity,
+            at: now,
+        });
+        None
+    }
+
+    /// A path was created; returns the event to emit now, if any
+    pub(crate) fn created(&mut self, path: &Path, identity: Option<FileIdentity>, now: Instant) -> Option<FileEvent> {
+        // Removed and written again, e.g. an editor saving by replacement
+        if let Some(index) = self.deletes.iter().position(|delete| delete.path == path) {
+            self.deletes.remove(index);
+            if let Some(identity) = identity {
+                self.known.insert(path.to_path_buf(), (identity, now));
+            }
+            return Some(FileEvent::Modified(path.to_path_buf()));
+        }
+
+        let Some(identity) = identity else {
+            // Not a regular file (or already gone); nothing to pair
+            return Some(FileEvent::Created(path.to_path_buf()));
+        };
+        self.known.insert(path.to_path_buf(), (identity.clone(), now));
+
+        if let Some(index) = self.deletes.iter().position(|delete| {
+            delete.identity.as_ref().map_or(false, |deleted| deleted.same_file(&identity))
+        }) {
+            let delete = self.deletes.remove(index);
+            return Some(FileEvent::Renamed(delete.path, path.to_path_buf()));
+        }
+
+        self.creates.retain(|create| create.path != path);
+        self.creates.push(Pending {
+            path: path.to_path_buf(),
+            identity: Some(identity),
+            at: now,
+        });
+        None
+    }
+
+    /// A path moved; keeps its identity under the new path
+    pub(crate) fn renamed(&mut self, old_path: &Path, new_path: &Path) {
+        if let Some(known) = self.known.remove(old_path) {
+            self.known.insert(new_path.to_path_buf(), known);
+        }
+    }
+
+    /// Release events held longer than the window, deletes first
+    pub(crate) fn expire(&mut self, now: Instant) -> Vec<FileEvent> {
+        self.prune_known(now);
+        let window = self.window;
+        let expired = |pending: &Pending| now.duration_since(pending.at) >= window;
+
+        let mut events = Vec::new();
+        let (old, kept): (Vec<_>, Vec<_>) = self.deletes.drain(..).partition(|delete| expired(delete));
+        self.deletes = kept;
+        events.extend(old.into_iter().map(|delete| FileEvent::Deleted(delete.path)));
+
+        let (old, kept): (Vec<_>, Vec<_>) = self.creates.drain(..).partition(|create| expired(create));
+        self.creates = kept;
+        events.extend(old.into_iter().map(|create| FileEvent::Created(create.path)));
+        events
+    }
+
+    /// Release everything still held
+    pub(crate) fn drain(&mut self) -> Vec<FileEvent> {
+        let mut events: Vec<_> = self
+            .deletes
+            .drain(..)
+            .map(|delete| FileEvent::Deleted(delete.path))
+            .collect();
+        events.extend(self.creates.drain(..).map(|create| FileEvent::Created(create.path)));
+        events
+    }
+
+    /// Number of events waiting for a partner
+    pub(crate) fn pending(&self) -> usize {
+        self.deletes.len() + self.creates.len()
+    }
+}
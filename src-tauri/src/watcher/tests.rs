@@ -26,6 +26,7 @@ async fn test_file_watcher_with_config() {
         max_batch_size: 50,
         max_batch_wait: Duration::from_millis(200),
         channel_buffer_size: 500,
+        ..Default::default()
     };
     let filter = DirectoryFilter::with_defaults().unwrap();
     let (watcher, _receiver) = FileWatcher::with_config(filter, config).unwrap();
@@ -300,6 +301,7 @@ async fn test_event_deduplication_stress() {
         max_batch_size: 100,
         max_batch_wait: Duration::from_millis(200),
         channel_buffer_size: 2000,
+        ..Default::default()
     };
     
     let (mut watcher, mut receiver) = FileWatcher::with_config(filter, watcher_config).unwrap();
@@ -499,6 +501,7 @@ async fn test_rapid_modification_deduplication() {
         max_batch_size: 100,
         max_batch_wait: Duration::from_millis(500),
         channel_buffer_size: 1000,
+        ..Default::default()
     };
     
     let (mut watcher, mut receiver) = FileWatcher::with_config(filter, watcher_config).unwrap();
@@ -558,6 +561,7 @@ async fn test_batch_size_limit() {
         max_batch_size,
         max_batch_wait: Duration::from_secs(10), // Long wait to test batch size trigger
         channel_buffer_size: 1000,
+        ..Default::default()
     };
     
     let (mut watcher, mut receiver) = FileWatcher::with_config(filter, watcher_config).unwrap();
@@ -620,6 +624,7 @@ async fn test_filtered_paths_no_events() {
         max_batch_size: 100,
         max_batch_wait: Duration::from_millis(200),
         channel_buffer_size: 1000,
+        ..Default::default()
     };
     
     let (mut watcher, mut receiver) = FileWatcher::with_config(filter, watcher_config).unwrap();
@@ -691,6 +696,7 @@ async fn test_concurrent_file_operations() {
         max_batch_size: 200,
         max_batch_wait: Duration::from_millis(300),
         channel_buffer_size: 2000,
+        ..Default::default()
     };
     
     let (mut watcher, mut receiver) = FileWatcher::with_config(filter, watcher_config).unwrap();
@@ -742,3 +748,827 @@ async fn test_concurrent_file_operations() {
     // The exact event count depends on OS behavior
     assert!(batch_count >= 0, "Should handle concurrent operations");
 }
+
+// ============================================================================
+// Polling Fallback Tests
+// ============================================================================
+
+mod polling_fallback {
+    use super::*;
+    use notify::{RecursiveMode, Watcher, WatcherKind};
+    use std::path::Path;
+    use std::sync::Arc;
+
+    /// Native watcher that reports an exhausted inotify watch limit for some roots
+    struct LimitedWatcher {
+        exhausted: Vec<PathBuf>,
+        inner: Box<dyn Watcher + Send>,
+    }
+
+    impl Watcher for LimitedWatcher {
+        fn new<F: notify::EventHandler>(_handler: F, _config: notify::Config) -> notify::Result<Self> {
+            Err(notify::Error::generic("constructed through the factory"))
+        }
+
+        fn watch(&mut self, path: &Path, recursive_mode: RecursiveMode) -> notify::Result<()> {
+            if self.exhausted.iter().any(|root| root == path) {
+                return Err(notify::Error::new(notify::ErrorKind::MaxFilesWatch));
+            }
+            self.inner.watch(path, recursive_mode)
+        }
+
+        fn unwatch(&mut self, path: &Path) -> notify::Result<()> {
+            self.inner.unwatch(path)
+        }
+
+        fn kind() -> WatcherKind {
+            WatcherKind::NullWatcher
+        }
+    }
+
+    fn exhausting(exhausted: Vec<PathBuf>) -> NativeWatcherFactory {
+        Arc::new(move |handler: RawEventHandler| {
+            let inner = notify::recommended_watcher(handler)?;
+            Ok(Box::new(LimitedWatcher {
+                exhausted: exhausted.clone(),
+                inner: Box::new(inner),
+            }) as Box<dyn Watcher + Send>)
+        })
+    }
+
+    fn polling_config() -> FileWatcherConfig {
+        FileWatcherConfig {
+            debounce_duration: Duration::from_millis(10),
+            max_batch_wait: Duration::from_millis(50),
+            poll_interval: Duration::from_millis(100),
+            ..Default::default()
+        }
+    }
+
+    async fn wait_for_created(receiver: &mut tokio::sync::mpsc::Receiver<EventBatch>, path: &Path) -> bool {
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while std::time::Instant::now() < deadline {
+            if let Ok(Some(batch)) = tokio::time::timeout(Duration::from_millis(200), receiver.recv()).await {
+                if batch.events.iter().any(|event| event == &FileEvent::Created(path.to_path_buf())) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    #[tokio::test]
+    async fn test_watch_limit_falls_back_to_polling_per_root() {
+        let native = TempDir::new().unwrap();
+        let exhausted = TempDir::new().unwrap();
+        let filter = DirectoryFilter::with_defaults().unwrap();
+        let (watcher, mut receiver) = FileWatcher::with_config(filter, polling_config()).unwrap();
+        let mut watcher = watcher.with_native_watcher_factory(exhausting(vec![exhausted.path().to_path_buf()]));
+
+        watcher
+            .start(vec![native.path().to_path_buf(), exhausted.path().to_path_buf()])
+            .await
+            .unwrap();
+
+        let modes = watcher.watch_modes().await;
+        assert_eq!(modes.len(), 2);
+        assert_eq!(modes[0].mode, WatchMode::Native);
+        assert_eq!(modes[0].reason, None);
+        assert_eq!(modes[1].mode, WatchMode::Polling);
+        assert_eq!(modes[1].reason, Some(PollingReason::WatchLimit));
+
+        // Changes under the exhausted root still arrive, via polling
+        let path = exhausted.path().join("polled.txt");
+        tokio::fs::write(&path, "found by scanning").await.unwrap();
+        assert!(wait_for_created(&mut receiver, &path).await);
+
+        watcher.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_watch_limit_without_fallback_fails() {
+        let root = TempDir::new().unwrap();
+        let filter = DirectoryFilter::with_defaults().unwrap();
+        let config = FileWatcherConfig {
+            polling_fallback: false,
+            ..polling_config()
+        };
+        let (watcher, _receiver) = FileWatcher::with_config(filter, config).unwrap();
+        let mut watcher = watcher.with_native_watcher_factory(exhausting(vec![root.path().to_path_buf()]));
+
+        assert!(watcher.start(vec![root.path().to_path_buf()]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_native_watcher_polls_every_root() {
+        let first = TempDir::new().unwrap();
+        let second = TempDir::new().unwrap();
+        let filter = DirectoryFilter::with_defaults().unwrap();
+        let (watcher, _receiver) = FileWatcher::with_config(filter, polling_config()).unwrap();
+        // inotify_init fails with EMFILE once max_user_instances is used up
+        let mut watcher = watcher.with_native_watcher_factory(Arc::new(|_handler: RawEventHandler| {
+            Err(notify::Error::io(std::io::Error::from_raw_os_error(24)))
+        }));
+
+        watcher
+            .start(vec![first.path().to_path_buf(), second.path().to_path_buf()])
+            .await
+            .unwrap();
+
+        let modes = watcher.watch_modes().await;
+        assert!(modes.iter().all(|status| status.mode == WatchMode::Polling));
+        assert!(modes.iter().all(|status| status.reason == Some(PollingReason::WatchLimit)));
+        watcher.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_configured_roots_are_polled() {
+        let root = TempDir::new().unwrap();
+        let (mut watcher, _receiver) = FileWatcherBuilder::new()
+            .poll_interval(Duration::from_millis(100))
+            .force_polling(root.path())
+            .build()
+            .unwrap();
+
+        watcher.start(vec![root.path().to_path_buf()]).await.unwrap();
+        let modes = watcher.watch_modes().await;
+        assert_eq!(modes[0].mode, WatchMode::Polling);
+        assert_eq!(modes[0].reason, Some(PollingReason::Configured));
+
+        watcher.remove_watch(root.path()).await.unwrap();
+        assert!(watcher.watch_modes().await.is_empty());
+        watcher.stop().await.unwrap();
+    }
+
+    #[test]
+    fn test_polling_scanner_reports_changes() {
+        let root = TempDir::new().unwrap();
+        let filter = DirectoryFilter::with_defaults().unwrap();
+        let kept = root.path().join("kept.txt");
+        let changed = root.path().join("nested").join("changed.txt");
+        let removed = root.path().join("removed.txt");
+        std::fs::create_dir_all(changed.parent().unwrap()).unwrap();
+        std::fs::write(&kept, "same").unwrap();
+        std::fs::write(&changed, "short").unwrap();
+        std::fs::write(&removed, "gone soon").unwrap();
+        std::fs::create_dir_all(root.path().join("node_modules")).unwrap();
+
+        let mut scanner = PollingScanner::new(root.path().to_path_buf(), &filter);
+        assert_eq!(scanner.file_count(), 3);
+        assert!(scanner.scan(&filter).unwrap().is_empty());
+
+        let added = root.path().join("added.txt");
+        std::fs::write(&added, "new").unwrap();
+        std::fs::write(&changed, "a longer body").unwrap();
+        std::fs::remove_file(&removed).unwrap();
+        std::fs::write(root.path().join("node_modules").join("ignored.js"), "filtered").unwrap();
+
+        let mut events = scanner.scan(&filter).unwrap();
+        events.sort_by_key(|event| format!("{:?}", event));
+        assert_eq!(
+            events,
+            vec![FileEvent::Created(added), FileEvent::Deleted(removed), FileEvent::Modified(changed)]
+        );
+        assert_eq!(scanner.file_count(), 3);
+    }
+
+    #[test]
+    fn test_polling_scanner_keeps_snapshot_when_root_unreadable() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("share");
+        let offline = temp.path().join("share.offline");
+        std::fs::create_dir_all(root.join("nested")).unwrap();
+        std::fs::write(root.join("a.txt"), "a").unwrap();
+        std::fs::write(root.join("nested").join("b.txt"), "b").unwrap();
+        let filter = DirectoryFilter::with_defaults().unwrap();
+
+        let mut scanner = PollingScanner::new(root.clone(), &filter);
+        assert_eq!(scanner.file_count(), 2);
+
+        // An unmounted share is not a mass deletion
+        std::fs::rename(&root, &offline).unwrap();
+        assert!(scanner.scan(&filter).is_err());
+        assert_eq!(scanner.file_count(), 2);
+
+        std::fs::rename(&offline, &root).unwrap();
+        assert!(scanner.scan(&filter).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_polling_scanner_primes_once_root_is_readable() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("late");
+        let filter = DirectoryFilter::with_defaults().unwrap();
+
+        let mut scanner = PollingScanner::new(root.clone(), &filter);
+        assert_eq!(scanner.file_count(), 0);
+
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("existing.txt"), "already there").unwrap();
+        assert!(scanner.scan(&filter).unwrap().is_empty());
+        assert_eq!(scanner.file_count(), 1);
+
+        let added = root.join("added.txt");
+        std::fs::write(&added, "new").unwrap();
+        assert_eq!(scanner.scan(&filter).unwrap(), vec![FileEvent::Created(added)]);
+    }
+
+    #[tokio::test]
+    async fn test_runtime_watch_limit_falls_back_to_polling() {
+        let root = TempDir::new().unwrap();
+        let slot: Arc<std::sync::Mutex<Option<RawEventHandler>>> = Arc::new(std::sync::Mutex::new(None));
+        let factory_slot = Arc::clone(&slot);
+        let factory: NativeWatcherFactory = Arc::new(move |handler: RawEventHandler| {
+            *factory_slot.lock().unwrap() = Some(handler);
+            let inner = notify::NullWatcher::new(|_: notify::Result<notify::Event>| {}, notify::Config::default())?;
+            Ok(Box::new(inner) as Box<dyn Watcher + Send>)
+        });
+
+        let filter = DirectoryFilter::with_defaults().unwrap();
+        let (watcher, mut receiver) = FileWatcher::with_config(filter, polling_config()).unwrap();
+        let mut watcher = watcher.with_native_watcher_factory(factory);
+        watcher.start(vec![root.path().to_path_buf()]).await.unwrap();
+        assert_eq!(watcher.watch_modes().await[0].mode, WatchMode::Native);
+
+        // inotify runs out of watches for a directory created after start
+        let error = notify::Error::new(notify::ErrorKind::MaxFilesWatch).add_path(root.path().join("new-dir"));
+        (slot.lock().unwrap().as_mut().unwrap())(Err(error));
+
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while watcher.watch_modes().await[0].mode != WatchMode::Polling {
+            assert!(std::time::Instant::now() < deadline, "root was not switched to polling");
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(watcher.watch_modes().await[0].reason, Some(PollingReason::WatchLimit));
+
+        let path = root.path().join("polled.txt");
+        tokio::fs::write(&path, "found by scanning").await.unwrap();
+        assert!(wait_for_created(&mut receiver, &path).await);
+
+        watcher.stop().await.unwrap();
+    }
+
+    #[test]
+    fn test_watch_limit_error_detection() {
+        assert!(is_watch_limit_error(&notify::Error::new(notify::ErrorKind::MaxFilesWatch)));
+        assert!(is_watch_limit_error(&notify::Error::io(std::io::Error::from_raw_os_error(28))));
+        assert!(!is_watch_limit_error(&notify::Error::path_not_found()));
+        assert!(!is_watch_limit_error(&notify::Error::generic("other")));
+    }
+
+    #[test]
+    fn test_remote_filesystem_detection() {
+        let mounts = "/dev/sda1 / ext4 rw 0 0\n\
+                      server:/export /mnt/share nfs4 rw 0 0\n\
+                      sshfs#host: /home/me/remote\\040dir fuse.sshfs rw 0 0\n";
+
+        let fs_type = |path: &str| super::super::polling::mount_filesystem_type(mounts, Path::new(path));
+        assert_eq!(fs_type("/home/me/docs").as_deref(), Some("ext4"));
+        assert_eq!(fs_type("/mnt/share/reports").as_deref(), Some("nfs4"));
+        assert_eq!(fs_type("/home/me/remote dir/a").as_deref(), Some("fuse.sshfs"));
+
+        assert!(is_remote_filesystem("nfs4"));
+        assert!(is_remote_filesystem("cifs"));
+        assert!(is_remote_filesystem("fuse.sshfs"));
+        assert!(!is_remote_filesystem("ext4"));
+        assert!(!is_remote_filesystem("btrfs"));
+    }
+}
+
+// ============================================================================
+// Ignore files
+// ============================================================================
+
+mod ignore_files {
+    use super::*;
+    use std::path::Path;
+
+    fn write(path: &Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn ignored_by(filter: &DirectoryFilter, path: &Path) -> Option<IgnoreRule> {
+        match filter.should_filter(path) {
+            FilterResult::Exclude(FilterReason::Ignored(rule)) => Some(rule),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_nested_gitignore_with_negation() {
+        let root = TempDir::new().unwrap();
+        let root = root.path();
+        write(&root.join(".gitignore"), "# scratch output\n*.bak\nout/\n");
+        write(&root.join("notes").join(".gitignore"), "!keep.bak\n");
+        write(&root.join("notes").join("keep.bak"), "kept");
+        write(&root.join("notes").join("old.bak"), "dropped");
+        write(&root.join("out").join("report.md"), "generated");
+        write(&root.join("readme.md"), "indexed");
+
+        let filter = DirectoryFilter::with_defaults().unwrap();
+
+        let rule = ignored_by(&filter, &root.join("notes").join("old.bak")).unwrap();
+        assert_eq!(rule.source, root.join(".gitignore"));
+        assert_eq!(rule.line, 2);
+        assert_eq!(rule.pattern, "*.bak");
+        assert!(!rule.negated);
+
+        assert_eq!(filter.should_filter(&root.join("notes").join("keep.bak")), FilterResult::Include);
+        assert_eq!(filter.should_filter(&root.join("readme.md")), FilterResult::Include);
+
+        // Files below an ignored directory are reported with the directory rule
+        let rule = ignored_by(&filter, &root.join("out").join("report.md")).unwrap();
+        assert_eq!(rule.pattern, "out/");
+        assert_eq!(rule.line, 3);
+    }
+
+    #[test]
+    fn test_neuralfsignore_takes_precedence() {
+        let root = TempDir::new().unwrap();
+        let root = root.path();
+        write(&root.join(".gitignore"), "*.csv\n");
+        write(&root.join(".neuralfsignore"), "!data.csv\nprivate.md\n");
+        write(&root.join("data.csv"), "a,b");
+        write(&root.join("other.csv"), "c,d");
+        write(&root.join("private.md"), "secret");
+
+        let filter = DirectoryFilter::with_defaults().unwrap();
+
+        assert_eq!(filter.should_filter(&root.join("data.csv")), FilterResult::Include);
+        assert_eq!(
+            ignored_by(&filter, &root.join("other.csv")).unwrap().source,
+            root.join(".gitignore")
+        );
+        assert_eq!(
+            ignored_by(&filter, &root.join("private.md")).unwrap().source,
+            root.join(".neuralfsignore")
+        );
+    }
+
+    #[test]
+    fn test_ignored_directory_cannot_be_reincluded() {
+        let root = TempDir::new().unwrap();
+        let root = root.path();
+        write(&root.join(".gitignore"), "archive/\n!archive/keep.md\n");
+        write(&root.join("archive").join("keep.md"), "still ignored");
+
+        let filter = DirectoryFilter::with_defaults().unwrap();
+
+        let rule = ignored_by(&filter, &root.join("archive").join("keep.md")).unwrap();
+        assert_eq!(rule.pattern, "archive/");
+    }
+
+    #[test]
+    fn test_directory_only_and_anchored_rules() {
+        let root = TempDir::new().unwrap();
+        let root = root.path();
+        write(&root.join(".ignore"), "logs/\n/top.txt\n");
+        write(&root.join("logs"), "a file named logs");
+        write(&root.join("nested").join("logs").join("a.txt"), "ignored");
+        write(&root.join("top.txt"), "ignored");
+        write(&root.join("nested").join("top.txt"), "kept");
+
+        let filter = DirectoryFilter::with_defaults().unwrap();
+
+        assert_eq!(filter.should_filter(&root.join("logs")), FilterResult::Include);
+        assert!(ignored_by(&filter, &root.join("nested").join("logs").join("a.txt")).is_some());
+        assert!(ignored_by(&filter, &root.join("top.txt")).is_some());
+        assert_eq!(filter.should_filter(&root.join("nested").join("top.txt")), FilterResult::Include);
+    }
+
+    #[test]
+    fn test_ignore_files_can_be_disabled() {
+        let root = TempDir::new().unwrap();
+        let root = root.path();
+        write(&root.join(".gitignore"), "*.md\n");
+        write(&root.join("readme.md"), "indexed");
+
+        let filter = DirectoryFilter::new(DirectoryFilterConfig {
+            respect_ignore_files: false,
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(filter.should_filter(&root.join("readme.md")), FilterResult::Include);
+    }
+
+    #[test]
+    fn test_cache_invalidated_when_ignore_file_changes() {
+        let root = TempDir::new().unwrap();
+        let root = root.path();
+        let gitignore = root.join(".gitignore");
+        let file = root.join("draft.md");
+        write(&gitignore, "*.txt\n");
+        write(&file, "draft");
+
+        let filter = DirectoryFilter::with_defaults().unwrap();
+        assert_eq!(filter.should_filter(&file), FilterResult::Include);
+        assert!(filter.cached_ignore_dirs() > 0);
+
+        // Stale until the change is reported
+        write(&gitignore, "*.md\n");
+        assert_eq!(filter.should_filter(&file), FilterResult::Include);
+
+        assert!(!filter.invalidate_ignore_file(&file));
+        assert!(filter.invalidate_ignore_file(&gitignore));
+        assert_eq!(ignored_by(&filter, &file).unwrap().pattern, "*.md");
+    }
+
+    #[test]
+    fn test_rules_above_monitored_root_do_not_apply() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("notes");
+        let file = root.join("readme.md");
+        write(&temp.path().join(".gitignore"), "*.md\n");
+        write(&file, "indexed");
+
+        let filter = DirectoryFilter::with_defaults().unwrap();
+        assert!(ignored_by(&filter, &file).is_some());
+
+        filter.set_monitored_roots(&[root.clone()]);
+        assert_eq!(filter.should_filter(&file), FilterResult::Include);
+        assert_eq!(filter.should_filter(&root), FilterResult::Include);
+    }
+
+    #[test]
+    fn test_rules_of_enclosing_repository_apply() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path().join("project");
+        let root = repo.join("docs");
+        let file = root.join("draft.md");
+        std::fs::create_dir_all(repo.join(".git")).unwrap();
+        write(&temp.path().join(".gitignore"), "*.txt\n");
+        write(&repo.join(".gitignore"), "*.md\n");
+        write(&file, "ignored by the project");
+        write(&root.join("notes.txt"), "above the repository");
+
+        let filter = DirectoryFilter::with_defaults().unwrap();
+        filter.set_monitored_roots(&[root.clone()]);
+
+        assert_eq!(ignored_by(&filter, &file).unwrap().source, repo.join(".gitignore"));
+        assert_eq!(filter.should_filter(&root.join("notes.txt")), FilterResult::Include);
+    }
+
+    #[test]
+    fn test_ignore_cache_is_bounded() {
+        use super::super::ignore::MAX_CACHED_DIRS;
+
+        let root = TempDir::new().unwrap();
+        let filter = DirectoryFilter::with_defaults().unwrap();
+        filter.set_monitored_roots(&[root.path().to_path_buf()]);
+
+        for index in 0..MAX_CACHED_DIRS + 10 {
+            filter.should_filter(&root.path().join(format!("dir{}", index)).join("file.txt"));
+        }
+        assert!(filter.cached_ignore_dirs() <= MAX_CACHED_DIRS);
+    }
+
+    #[test]
+    fn test_polling_scanner_applies_edited_ignore_file() {
+        let root = TempDir::new().unwrap();
+        let gitignore = root.path().join(".gitignore");
+        let hidden = root.path().join("hidden.md");
+        write(&gitignore, "hidden.md\n");
+        write(&hidden, "not indexed yet");
+
+        let filter = DirectoryFilter::with_defaults().unwrap();
+        let mut scanner = PollingScanner::new(root.path().to_path_buf(), &filter);
+        assert_eq!(scanner.file_count(), 1);
+
+        write(&gitignore, "# nothing ignored\n");
+        let mut events = scanner.scan(&filter).unwrap();
+        events.sort_by_key(|event| format!("{:?}", event));
+        assert_eq!(events, vec![FileEvent::Created(hidden), FileEvent::Modified(gitignore)]);
+    }
+}
+
+// ============================================================================
+// Rename pairing
+// ============================================================================
+
+mod rename_pairing {
+    use super::super::rename::RenamePairer;
+    use super::*;
+    use std::path::Path;
+    use std::time::Instant;
+
+    const WINDOW: Duration = Duration::from_millis(100);
+
+    fn identity(path: &Path) -> FileIdentity {
+        FileIdentity::from_path(path).unwrap()
+    }
+
+    fn hashed(path: &Path) -> FileIdentity {
+        FileIdentity {
+            file_id: None,
+            content_hash: hash_file(path),
+            ..identity(path)
+        }
+    }
+
+    #[test]
+    fn test_known_identities_are_pruned() {
+        use super::super::rename::{KNOWN_TTL, MAX_KNOWN};
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("seen.txt");
+        std::fs::write(&path, "observed once").unwrap();
+        let seen = identity(&path);
+
+        let mut pairer = RenamePairer::new(WINDOW);
+        let now = Instant::now();
+        for index in 0..MAX_KNOWN + 5 {
+            let at = now + Duration::from_nanos(index as u64);
+            pairer.observe(&dir.path().join(format!("{}.txt", index)), seen.clone(), at);
+        }
+        pairer.expire(now);
+        assert_eq!(pairer.known_count(), MAX_KNOWN);
+        assert!(pairer.known_identity(&dir.path().join("0.txt")).is_none());
+
+        pairer.expire(now + KNOWN_TTL + Duration::from_secs(1));
+        assert_eq!(pairer.known_count(), 0);
+    }
+
+    #[test]
+    fn test_delete_then_create_pairs_by_file_id() {
+        let dir = TempDir::new().unwrap();
+        let old_path = dir.path().join("a").join("report.md");
+        let new_path = dir.path().join("b").join("report.md");
+        std::fs::create_dir_all(old_path.parent().unwrap()).unwrap();
+        std::fs::create_dir_all(new_path.parent().unwrap()).unwrap();
+        std::fs::write(&old_path, "quarterly numbers").unwrap();
+
+        let mut pairer = RenamePairer::new(WINDOW);
+        let now = Instant::now();
+        let before = identity(&old_path);
+        std::fs::rename(&old_path, &new_path).unwrap();
+
+        assert_eq!(pairer.deleted(&old_path, Some(before), now), None);
+        assert_eq!(
+            pairer.created(&new_path, Some(identity(&new_path)), now),
+            Some(FileEvent::Renamed(old_path, new_path))
+        );
+        assert_eq!(pairer.pending(), 0);
+    }
+
+    #[test]
+    fn test_create_then_delete_pairs_by_content_hash() {
+        let dir = TempDir::new().unwrap();
+        let old_path = dir.path().join("old.txt");
+        let new_path = dir.path().join("new.txt");
+        std::fs::write(&old_path, "copied across filesystems").unwrap();
+        std::fs::write(&new_path, "copied across filesystems").unwrap();
+
+        let mut pairer = RenamePairer::new(WINDOW);
+        let now = Instant::now();
+        let deleted = hashed(&old_path);
+
+        assert_eq!(pairer.created(&new_path, Some(identity(&new_path)), now), None);
+        // Different inodes: only the hash can match them up
+        assert_eq!(pairer.unhashed_creates_of_size(deleted.size), vec![new_path.clone()]);
+        pairer.set_create_hash(&new_path, hash_file(&new_path).unwrap());
+
+        assert_eq!(
+            pairer.deleted(&old_path, Some(deleted), now),
+            Some(FileEvent::Renamed(old_path, new_path))
+        );
+    }
+
+    #[test]
+    fn test_different_content_is_not_paired() {
+        let dir = TempDir::new().unwrap();
+        let old_path = dir.path().join("old.txt");
+        let new_path = dir.path().join("new.txt");
+        std::fs::write(&old_path, "first body").unwrap();
+        std::fs::write(&new_path, "other body").unwrap();
+
+        let mut pairer = RenamePairer::new(WINDOW);
+        let now = Instant::now();
+        assert_eq!(pairer.deleted(&old_path, Some(hashed(&old_path)), now), None);
+        assert_eq!(pairer.created(&new_path, Some(hashed(&new_path)), now), None);
+
+        assert!(pairer.expire(now).is_empty());
+        assert_eq!(
+            pairer.expire(now + WINDOW),
+            vec![FileEvent::Deleted(old_path), FileEvent::Created(new_path)]
+        );
+        assert_eq!(pairer.pending(), 0);
+    }
+
+    #[test]
+    fn test_same_path_events_collapse() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("notes.md");
+        std::fs::write(&path, "saved by replacement").unwrap();
+
+        let mut pairer = RenamePairer::new(WINDOW);
+        let now = Instant::now();
+        assert_eq!(pairer.deleted(&path, Some(identity(&path)), now), None);
+        assert_eq!(
+            pairer.created(&path, Some(identity(&path)), now),
+            Some(FileEvent::Modified(path.clone()))
+        );
+
+        // A file that only lived within the window
+        let scratch = dir.path().join("scratch.md");
+        std::fs::write(&scratch, "short lived").unwrap();
+        assert_eq!(pairer.created(&scratch, Some(identity(&scratch)), now), None);
+        assert_eq!(pairer.deleted(&scratch, None, now), Some(FileEvent::Deleted(scratch)));
+        assert_eq!(pairer.pending(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_watcher_reports_move_as_rename() {
+        let root = TempDir::new().unwrap();
+        let old_path = root.path().join("inbox").join("letter.txt");
+        let new_path = root.path().join("archive").join("letter.txt");
+        std::fs::create_dir_all(old_path.parent().unwrap()).unwrap();
+        std::fs::create_dir_all(new_path.parent().unwrap()).unwrap();
+
+        let config = FileWatcherConfig {
+            debounce_duration: Duration::from_millis(200),
+            max_batch_wait: Duration::from_millis(50),
+            poll_interval: Duration::from_millis(100),
+            force_polling: vec![root.path().to_path_buf()],
+            ..Default::default()
+        };
+        let filter = DirectoryFilter::with_defaults().unwrap();
+        let (mut watcher, mut receiver) = FileWatcher::with_config(filter, config).unwrap();
+        watcher.start(vec![root.path().to_path_buf()]).await.unwrap();
+
+        std::fs::write(&old_path, "dear index").unwrap();
+        let mut events = Vec::new();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !events.contains(&FileEvent::Created(old_path.clone())) && Instant::now() < deadline {
+            if let Ok(Some(batch)) = tokio::time::timeout(Duration::from_millis(200), receiver.recv()).await {
+                events.extend(batch.events);
+            }
+        }
+        assert!(events.contains(&FileEvent::Created(old_path.clone())));
+
+        // A polling scan sees the move as a delete plus a create
+        std::fs::rename(&old_path, &new_path).unwrap();
+        let mut events = Vec::new();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while events.is_empty() && Instant::now() < deadline {
+            if let Ok(Some(batch)) = tokio::time::timeout(Duration::from_millis(200), receiver.recv()).await {
+                events.extend(batch.events);
+            }
+        }
+        watcher.stop().await.unwrap();
+
+        assert_eq!(events, vec![FileEvent::Renamed(old_path, new_path)]);
+    }
+}
+
+// ============================================================================
+// Event journal
+// ============================================================================
+
+mod event_journal {
+    use super::*;
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    fn batch(events: Vec<FileEvent>) -> EventBatch {
+        EventBatch {
+            id: Uuid::now_v7(),
+            events,
+            created_at: std::time::Instant::now(),
+        }
+    }
+
+    fn reconciled(path: &std::path::Path) -> EventJournal {
+        let (journal, _) = EventJournal::open(path).unwrap();
+        journal.mark_reconciled(journal.last_seq()).unwrap();
+        journal
+    }
+
+    #[test]
+    fn test_missing_journal_needs_full_scan() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("watcher.journal");
+
+        let (journal, recovery) = EventJournal::open(&path).unwrap();
+        assert_eq!(recovery.state, JournalState::Missing);
+        assert!(recovery.needs_full_scan());
+
+        // Interrupted before the full scan finished
+        journal.append(&batch(vec![FileEvent::Created(PathBuf::from("/a.txt"))])).unwrap();
+        drop(journal);
+        let (journal, recovery) = EventJournal::open(&path).unwrap();
+        assert_eq!(recovery.state, JournalState::Unreconciled);
+        assert!(recovery.needs_full_scan());
+
+        journal.mark_reconciled(journal.last_seq()).unwrap();
+        drop(journal);
+        let (_, recovery) = EventJournal::open(&path).unwrap();
+        assert_eq!(recovery.state, JournalState::Intact);
+        assert!(recovery.pending.is_empty());
+    }
+
+    #[test]
+    fn test_unprocessed_batches_are_replayed_in_order() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("watcher.journal");
+        let journal = reconciled(&path);
+
+        let first = batch(vec![FileEvent::Created(PathBuf::from("/docs/one.md"))]);
+        let second = batch(vec![FileEvent::Deleted(PathBuf::from("/docs/two.md"))]);
+        let third = batch(vec![FileEvent::Renamed(
+            PathBuf::from("/docs/old.md"),
+            PathBuf::from("/docs/new.md"),
+        )]);
+        for batch in [&first, &second, &third] {
+            journal.append(batch).unwrap();
+        }
+        assert!(journal.mark_processed(first.id).unwrap());
+        assert!(!journal.mark_processed(Uuid::now_v7()).unwrap());
+        assert_eq!(journal.unprocessed(), 2);
+        drop(journal);
+
+        let (journal, recovery) = EventJournal::open(&path).unwrap();
+        assert_eq!(recovery.state, JournalState::Intact);
+        let replayed: Vec<_> = recovery.pending.iter().map(|batch| (batch.id, batch.events.clone())).collect();
+        assert_eq!(replayed, vec![(second.id, second.events), (third.id, third.events.clone())]);
+
+        // Processing the last batch covers the ones before it
+        assert!(journal.mark_processed(third.id).unwrap());
+        assert_eq!(journal.unprocessed(), 0);
+        drop(journal);
+        let (_, recovery) = EventJournal::open(&path).unwrap();
+        assert!(recovery.pending.is_empty());
+    }
+
+    #[test]
+    fn test_corrupt_journal_is_set_aside() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("watcher.journal");
+        let journal = reconciled(&path);
+        journal.append(&batch(vec![FileEvent::Modified(PathBuf::from("/x.txt"))])).unwrap();
+        drop(journal);
+
+        // A record torn by a crash mid-write
+        let mut content = std::fs::read_to_string(&path).unwrap();
+        content.push_str("{\"kind\":\"batch\",\"seq\":2,");
+        std::fs::write(&path, content).unwrap();
+
+        let (journal, recovery) = EventJournal::open(&path).unwrap();
+        assert!(matches!(recovery.state, JournalState::Corrupt(_)));
+        assert!(recovery.pending.is_empty());
+        assert!(dir.path().join("watcher.journal.corrupt").exists());
+        assert_eq!(journal.unprocessed(), 0);
+    }
+
+    #[test]
+    fn test_processed_journal_is_compacted() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("watcher.journal");
+        let journal = reconciled(&path).with_compact_bytes(0);
+
+        let mut last = 0;
+        for i in 0..5 {
+            let batch = batch(vec![FileEvent::Created(PathBuf::from(format!("/f{}.txt", i)))]);
+            last = journal.append(&batch).unwrap();
+            journal.mark_processed(batch.id).unwrap();
+        }
+        assert_eq!(journal.watermark(), last);
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 2);
+        drop(journal);
+
+        // Sequence numbers continue after compaction
+        let (journal, recovery) = EventJournal::open(&path).unwrap();
+        assert_eq!(recovery.state, JournalState::Intact);
+        assert_eq!(journal.append(&batch(vec![])).unwrap(), last + 1);
+    }
+
+    #[tokio::test]
+    async fn test_watcher_journals_batches_before_sending() {
+        let root = TempDir::new().unwrap();
+        let data = TempDir::new().unwrap();
+        let journal = Arc::new(reconciled(&data.path().join("watcher.journal")));
+
+        let config = FileWatcherConfig {
+            debounce_duration: Duration::from_millis(10),
+            max_batch_wait: Duration::from_millis(50),
+            poll_interval: Duration::from_millis(100),
+            force_polling: vec![root.path().to_path_buf()],
+            ..Default::default()
+        };
+        let filter = DirectoryFilter::with_defaults().unwrap();
+        let (watcher, mut receiver) = FileWatcher::with_config(filter, config).unwrap();
+        let mut watcher = watcher.with_journal(journal.clone());
+        watcher.start(vec![root.path().to_path_buf()]).await.unwrap();
+
+        std::fs::write(root.path().join("new.txt"), "journaled").unwrap();
+        let received = tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        watcher.stop().await.unwrap();
+
+        assert_eq!(journal.unprocessed(), 1);
+        assert!(journal.mark_processed(received.id).unwrap());
+    }
+}
@@ -3,9 +3,12 @@
  */
 
 import { invoke } from '@tauri-apps/api/tauri';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 import type {
   SearchRequest,
   SearchResponse,
+  SearchFilesRequest,
+  SearchPhaseUpdate,
   Tag,
   FileTagRelation,
   TagSuggestion,
@@ -37,15 +40,62 @@ export function getAssetUrl(path: string): string {
   return `nfs://localhost/${path}?token=${encodeURIComponent(token)}`;
 }
 
+/**
+ * Get the URL streaming a member of an indexed archive (a passage's
+ * `location.member`)
+ */
+export async function getArchiveMemberUrl(fileId: string, member: string): Promise<string> {
+  return invoke<string>('build_archive_member_url_cmd', { uuid: fileId, member });
+}
+
 // Search API
 export async function searchFiles(request: SearchRequest): Promise<SearchResponse> {
   return invoke<SearchResponse>('search_files', { request });
 }
 
+/**
+ * Start a streaming search
+ *
+ * `onUpdate` receives one update per phase (keyword, vector, cloud-refined)
+ * until the update with `is_final` set, after which the listener is removed.
+ */
+export async function searchFilesStream(
+  request: SearchFilesRequest,
+  onUpdate: (update: SearchPhaseUpdate) => void
+): Promise<{ requestId: string; unlisten: UnlistenFn }> {
+  // Subscribe before starting so the first phase is not missed
+  const requestId = request.request_id ?? crypto.randomUUID();
+  const unlisten = await listen<SearchPhaseUpdate>(`search-stream:${requestId}`, (event) => {
+    onUpdate(event.payload);
+    if (event.payload.is_final) {
+      unlisten();
+    }
+  });
+
+  try {
+    await invoke<string>('search_files_stream', { request: { ...request, request_id: requestId } });
+  } catch (error) {
+    unlisten();
+    throw error;
+  }
+  return { requestId, unlisten };
+}
+
+/**
+ * Cancel a streaming search; resolves to whether it was still running
+ */
+export async function cancelSearch(requestId: string): Promise<boolean> {
+  return invoke<boolean>('cancel_search', { requestId });
+}
+
 export async function getSearchSuggestions(query: string): Promise<string[]> {
   return invoke<string[]>('get_search_suggestions', { query });
 }
 
+export async function recordClarification(query: string, choice: 'file' | 'content'): Promise<boolean> {
+  return invoke<boolean>('record_clarification', { query, choice });
+}
+
 // Tag API
 export async function getTags(): Promise<Tag[]> {
   return invoke<Tag[]>('get_tags');
@@ -11,7 +11,7 @@
 
 import { createSignal, createEffect, Show, For, onCleanup } from 'solid-js';
 import type { SearchIntent, Clarification, ClarificationOption } from '../../types';
-import { searchFiles, getSearchSuggestions } from '../../api/tauri';
+import { searchFiles, getSearchSuggestions, recordClarification } from '../../api/tauri';
 import './SearchBar.css';
 
 export interface SearchBarProps {
@@ -157,6 +157,12 @@ export function SearchBar(props: SearchBarProps) {
   // Handle clarification option selection
   const handleClarificationSelect = (option: ClarificationOption) => {
     setClarifications(null);
+    if (option.choice) {
+      // Remember the answer so this query pattern resolves itself next time
+      recordClarification(query(), option.choice).catch((error) =>
+        console.error('Failed to record clarification:', error)
+      );
+    }
     props.onClarificationSelect?.(option);
     props.onSearch(query(), option.intent);
   };
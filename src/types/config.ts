@@ -20,6 +20,18 @@ export interface PerformanceConfig {
   embedding_batch_size: number;
   enable_cuda: boolean;
   fast_inference_mode: boolean;
+  indexing_budget?: IndexingBudget;
+}
+
+/** Resource budgets for background indexing */
+export interface IndexingBudget {
+  max_cpu_percent: number;
+  max_io_wait_percent: number;
+  battery_max_concurrency: number;
+  pause_below_battery_percent: number;
+  turbo_when_idle: boolean;
+  idle_threshold_secs: number;
+  turbo_multiplier: number;
 }
 
 /** Privacy configuration */
@@ -55,6 +55,53 @@ export interface ScanProgress {
   isComplete: boolean;
 }
 
+/**
+ * Indexing pipeline stage
+ */
+export type IndexStage = 'Scan' | 'Parse' | 'Embed' | 'Store';
+
+/**
+ * Progress of one pipeline stage
+ */
+export interface StageProgress {
+  stage: IndexStage;
+  /** Files that went through the stage */
+  completed: number;
+  /** Average time per file in milliseconds */
+  avg_ms: number;
+  /** Files per minute over the throughput window */
+  throughput_per_minute: number;
+}
+
+/**
+ * Aggregate indexing progress
+ */
+export interface ProgressSummary {
+  queued: number;
+  completed: number;
+  failed: number;
+  remaining: number;
+  throughput_per_minute: number;
+  /** Estimated seconds until the remaining files are done */
+  eta_secs?: number;
+  stages: StageProgress[];
+}
+
+/**
+ * Indexing progress of one monitored root
+ */
+export interface RootProgress extends ProgressSummary {
+  root: string;
+}
+
+/**
+ * Payload of `get_index_progress` and `index-progress` events
+ */
+export interface IndexProgressSnapshot {
+  total: ProgressSummary;
+  roots: RootProgress[];
+}
+
 /**
  * Directory suggestion for onboarding
  */
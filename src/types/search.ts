@@ -71,7 +71,9 @@ export type SearchStatus =
   | 'PartialSuccess'
   | 'NeedsClarity'
   | 'NoResults'
-  | 'Error';
+  | 'Error'
+  | 'Partial'
+  | 'Cancelled';
 
 export type ResultSource = 'LocalVector' | 'LocalTag' | 'CloudEnhanced';
 
@@ -150,6 +152,7 @@ export interface Clarification {
 export interface ClarificationOption {
   text: string;
   intent: SearchIntent;
+  choice?: 'file' | 'content';
   estimated_count?: number;
 }
 
@@ -173,3 +176,74 @@ export type TagType =
   | 'Status'
   | 'Custom'
   | 'AutoGenerated';
+
+/**
+ * Request for `search_files_stream`
+ */
+export interface SearchFilesRequest {
+  query: string;
+  file_types?: string[];
+  tag_ids?: string[];
+  time_range?: TimeRange;
+  min_score?: number;
+  exclude_private?: boolean;
+  enable_cloud?: boolean;
+  offset?: number;
+  limit?: number;
+  explain?: boolean;
+  collapse_duplicates?: boolean;
+  /** Chosen by the client so it can subscribe before the first phase */
+  request_id?: string;
+}
+
+export type SearchResultSource = 'local_vector' | 'local_keyword' | 'cloud_enhanced';
+
+export interface SearchResultItem {
+  file_id: string;
+  path: string;
+  filename: string;
+  file_type: string;
+  score: number;
+  preview?: string;
+  chunk_id?: string;
+  source: SearchResultSource;
+  tags: string[];
+  explanation?: ResultExplanation;
+  passages: Passage[];
+  duplicates: string[];
+}
+
+export interface Passage {
+  chunk_id?: string;
+  score: number;
+  location?: ChunkLocation;
+  virtual_path?: string;
+}
+
+export interface ResultExplanation {
+  raw_vector_score?: number;
+  vector_score?: number;
+  raw_bm25_score?: number;
+  bm25_score?: number;
+  bm25_explanation?: string;
+  vector_weight: number;
+  bm25_weight: number;
+  merged_score: number;
+  boosts: [string, number][];
+  filters: [string, boolean, string][];
+  final_score: number;
+}
+
+export type SearchPhase = 'keyword' | 'vector' | 'cloud_refined';
+
+/**
+ * One phase of a streaming search, pushed on `search-stream:<request_id>`
+ */
+export interface SearchPhaseUpdate {
+  request_id: string;
+  phase: SearchPhase;
+  status: SearchStatus;
+  results: SearchResultItem[];
+  is_final: boolean;
+  error?: string;
+}